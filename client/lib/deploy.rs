@@ -12,8 +12,11 @@ use casper_node::{
     types::{Deploy, DeployHash, TimeDiff, Timestamp},
 };
 use casper_types::{
-    account::AccountHash, AsymmetricType, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey,
-    UIntParseError, URef, U512,
+    account::AccountHash,
+    system::auction::{
+        ARG_AMOUNT, ARG_DELEGATOR, ARG_NEW_VALIDATOR, ARG_VALIDATOR, METHOD_REDELEGATE,
+    },
+    AsymmetricType, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, UIntParseError, URef, U512,
 };
 
 use crate::{
@@ -199,6 +202,16 @@ pub(super) trait DeployExt {
         payment: ExecutableDeployItem,
     ) -> Result<Deploy>;
 
+    /// Constructs a `Deploy` calling the auction contract to redelegate the session account's stake.
+    fn new_redelegate(
+        auction_contract_hash: &str,
+        validator: &str,
+        new_validator: &str,
+        amount: &str,
+        params: DeployParams,
+        payment: ExecutableDeployItem,
+    ) -> Result<Deploy>;
+
     /// Writes the `Deploy` to `output`.
     fn write_deploy<W>(&self, output: W) -> Result<()>
     where
@@ -299,6 +312,55 @@ impl DeployExt for Deploy {
         Deploy::with_payment_and_session(params, payment, session)
     }
 
+    fn new_redelegate(
+        auction_contract_hash: &str,
+        validator: &str,
+        new_validator: &str,
+        amount: &str,
+        params: DeployParams,
+        payment: ExecutableDeployItem,
+    ) -> Result<Deploy> {
+        let auction_contract_hash =
+            parsing::contract_hash(auction_contract_hash)?.ok_or(Error::InvalidArgument {
+                context: "auction_contract_hash",
+                error: "auction contract hash is required".to_string(),
+            })?;
+
+        let public_key = |context, value: &str| {
+            PublicKey::from_hex(value).map_err(|error| Error::InvalidArgument {
+                context,
+                error: error.to_string(),
+            })
+        };
+        let validator = public_key("validator", validator)?;
+        let new_validator = public_key("new_validator", new_validator)?;
+
+        let amount = U512::from_dec_str(amount).map_err(|err| Error::FailedToParseUint {
+            context: ARG_AMOUNT,
+            error: UIntParseError::FromDecStr(err),
+        })?;
+
+        // The stake is redelegated on behalf of the account the deploy is executed by.
+        let delegator = params
+            .session_account
+            .clone()
+            .unwrap_or_else(|| PublicKey::from(&params.secret_key));
+
+        let mut redelegate_args = RuntimeArgs::new();
+        redelegate_args.insert(ARG_DELEGATOR, delegator)?;
+        redelegate_args.insert(ARG_VALIDATOR, validator)?;
+        redelegate_args.insert(ARG_NEW_VALIDATOR, new_validator)?;
+        redelegate_args.insert(ARG_AMOUNT, amount)?;
+
+        let session = ExecutableDeployItem::StoredContractByHash {
+            hash: auction_contract_hash.into(),
+            entry_point: METHOD_REDELEGATE.to_string(),
+            args: redelegate_args,
+        };
+
+        Deploy::with_payment_and_session(params, payment, session)
+    }
+
     fn write_deploy<W>(&self, mut output: W) -> Result<()>
     where
         W: Write,
@@ -273,6 +273,56 @@ pub fn make_transfer(
     output.commit()
 }
 
+/// Redelegates stake of the deploy's session account from one validator to another.
+///
+/// The stake leaves `validator` immediately and is delegated to `new_validator` at the end of the
+/// current era, without waiting for the unbonding delay.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, the JSON-RPC request will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the request will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the request
+///   will not be printed to `stdout`.
+/// * `auction_contract_hash` is the hex-encoded hash of the auction contract, optionally prefixed
+///   with `hash-`.
+/// * `validator` is the hex-encoded public key of the validator the stake is currently delegated
+///   to.
+/// * `new_validator` is the hex-encoded public key of the validator the stake is moved to.
+/// * `amount` is a string to be parsed as a `U512` specifying the number of motes to redelegate.
+/// * `deploy_params` contains deploy-related options for this `Deploy`. See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `payment_params` contains payment-related options for this `Deploy`. See
+///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+#[allow(clippy::too_many_arguments)]
+pub async fn redelegate(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbosity_level: u64,
+    auction_contract_hash: &str,
+    validator: &str,
+    new_validator: &str,
+    amount: &str,
+    deploy_params: DeployStrParams<'_>,
+    payment_params: PaymentStrParams<'_>,
+) -> Result<JsonRpc> {
+    let deploy = Deploy::new_redelegate(
+        auction_contract_hash,
+        validator,
+        new_validator,
+        amount,
+        deploy_params.try_into()?,
+        payment_params.try_into()?,
+    )?;
+    RpcCall::new(maybe_rpc_id, node_address, verbosity_level)
+        .put_deploy(deploy)
+        .await
+}
+
 /// Retrieves a `Deploy` from the network.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -399,6 +449,7 @@ pub async fn get_state_root_hash(
 /// bid-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20           # Key::Bid
 /// withdraw-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20      # Key::Withdraw
 /// dictionary-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20    # Key::Dictionary
+/// redelegation-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20  # Key::Redelegation
 /// The Key::SystemContractRegistry variant is unique and can only take the following value:
 /// system-contract-registry-0000000000000000000000000000000000000000000000000000000000000000
 /// ```
@@ -553,6 +604,7 @@ pub async fn get_account_info(
 /// bid-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20           # Key::Bid
 /// withdraw-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20      # Key::Withdraw
 /// dictionary-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20    # Key::Dictionary
+/// redelegation-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20  # Key::Redelegation
 /// The Key::SystemContractRegistry variant is unique and can only take the following value:
 /// system-contract-registry-0000000000000000000000000000000000000000000000000000000000000000
 /// ```
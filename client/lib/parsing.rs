@@ -444,7 +444,7 @@ pub(super) fn parse_session_info(params: SessionStrParams) -> Result<ExecutableD
         });
     }
 
-    if let Some(session_hash) = parse_contract_hash(session_hash)? {
+    if let Some(session_hash) = contract_hash(session_hash)? {
         return Ok(ExecutableDeployItem::StoredContractByHash {
             hash: session_hash.into(),
             entry_point: entry_point(session_entry_point).ok_or_else(invalid_entry_point)?,
@@ -462,7 +462,7 @@ pub(super) fn parse_session_info(params: SessionStrParams) -> Result<ExecutableD
         });
     }
 
-    if let Some(package_hash) = parse_contract_hash(session_package_hash)? {
+    if let Some(package_hash) = contract_hash(session_package_hash)? {
         return Ok(ExecutableDeployItem::StoredVersionedContractByHash {
             hash: package_hash.into(),
             version, // defaults to highest enabled version
@@ -543,7 +543,7 @@ pub(super) fn parse_payment_info(params: PaymentStrParams) -> Result<ExecutableD
         });
     }
 
-    if let Some(payment_hash) = parse_contract_hash(payment_hash)? {
+    if let Some(payment_hash) = contract_hash(payment_hash)? {
         return Ok(ExecutableDeployItem::StoredContractByHash {
             hash: payment_hash.into(),
             entry_point: entry_point(payment_entry_point).ok_or_else(invalid_entry_point)?,
@@ -561,7 +561,7 @@ pub(super) fn parse_payment_info(params: PaymentStrParams) -> Result<ExecutableD
         });
     }
 
-    if let Some(package_hash) = parse_contract_hash(payment_package_hash)? {
+    if let Some(package_hash) = contract_hash(payment_package_hash)? {
         return Ok(ExecutableDeployItem::StoredVersionedContractByHash {
             hash: package_hash.into(),
             version, // defaults to highest enabled version
@@ -580,7 +580,7 @@ pub(super) fn parse_payment_info(params: PaymentStrParams) -> Result<ExecutableD
     })
 }
 
-fn parse_contract_hash(value: &str) -> Result<Option<HashAddr>> {
+pub(crate) fn contract_hash(value: &str) -> Result<Option<HashAddr>> {
     if value.is_empty() {
         return Ok(None);
     }
@@ -4,6 +4,7 @@ mod list;
 mod make;
 mod make_transfer;
 mod put;
+mod redelegate;
 mod send;
 mod sign;
 mod transfer;
@@ -11,6 +12,7 @@ mod transfer;
 pub use list::ListDeploys;
 pub use make::MakeDeploy;
 pub use make_transfer::MakeTransfer;
+pub use redelegate::Redelegate;
 pub use send::SendDeploy;
 pub use sign::SignDeploy;
 pub use transfer::Transfer;
@@ -21,6 +21,10 @@ pub(super) enum DisplayOrder {
     TransferAmount,
     TransferTargetAccount,
     TransferId,
+    RedelegateAuctionHash,
+    RedelegateValidator,
+    RedelegateNewValidator,
+    RedelegateAmount,
     Timestamp,
     Ttl,
     GasPrice,
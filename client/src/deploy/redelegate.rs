@@ -0,0 +1,167 @@
+use async_trait::async_trait;
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::{DeployStrParams, Error};
+
+use super::creation_common::{self, DisplayOrder};
+use crate::{command::ClientCommand, common, Success};
+
+/// Handles providing the arg for and retrieval of the auction contract hash.
+mod auction_contract_hash {
+    use super::*;
+
+    const ARG_NAME: &str = "auction-contract-hash";
+    const ARG_VALUE_NAME: &str = "HEX STRING";
+    const ARG_HELP: &str =
+        "Hex-encoded hash of the auction contract, as found in the system contract registry";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::RedelegateAuctionHash as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the validator the stake is delegated to.
+mod validator {
+    use super::*;
+
+    const ARG_NAME: &str = "validator";
+    const ARG_VALUE_NAME: &str = "HEX STRING";
+    const ARG_HELP: &str =
+        "Hex-encoded public key of the validator the stake is currently delegated to";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::RedelegateValidator as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the validator the stake is moved to.
+mod new_validator {
+    use super::*;
+
+    const ARG_NAME: &str = "new-validator";
+    const ARG_VALUE_NAME: &str = "HEX STRING";
+    const ARG_HELP: &str = "Hex-encoded public key of the validator the stake is moved to";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::RedelegateNewValidator as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the redelegated amount.
+mod amount {
+    use super::*;
+
+    const ARG_NAME: &str = "amount";
+    const ARG_SHORT: &str = "a";
+    const ARG_VALUE_NAME: &str = "512-BIT INTEGER";
+    const ARG_HELP: &str = "The number of motes to redelegate";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::RedelegateAmount as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+pub struct Redelegate {}
+
+#[async_trait]
+impl<'a, 'b> ClientCommand<'a, 'b> for Redelegate {
+    const NAME: &'static str = "redelegate";
+    const ABOUT: &'static str =
+        "Moves delegated stake of the session account to another validator at the end of the era";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(auction_contract_hash::arg())
+            .arg(validator::arg())
+            .arg(new_validator::arg())
+            .arg(amount::arg());
+        let subcommand = creation_common::apply_common_payment_options(subcommand);
+        creation_common::apply_common_creation_options(subcommand, true)
+    }
+
+    async fn run(matches: &ArgMatches<'a>) -> Result<Success, Error> {
+        creation_common::show_arg_examples_and_exit_if_required(matches);
+
+        let auction_contract_hash = auction_contract_hash::get(matches);
+        let validator = validator::get(matches);
+        let new_validator = new_validator::get(matches);
+        let amount = amount::get(matches);
+
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbosity_level = common::verbose::get(matches);
+
+        let secret_key = common::secret_key::get(matches);
+        let timestamp = creation_common::timestamp::get(matches);
+        let ttl = creation_common::ttl::get(matches);
+        let gas_price = creation_common::gas_price::get(matches);
+        let dependencies = creation_common::dependencies::get(matches);
+        let chain_name = creation_common::chain_name::get(matches);
+        let session_account = common::session_account::get(matches)?;
+
+        let payment_str_params = creation_common::payment_str_params(matches);
+
+        casper_client::redelegate(
+            maybe_rpc_id,
+            node_address,
+            verbosity_level,
+            auction_contract_hash,
+            validator,
+            new_validator,
+            amount,
+            DeployStrParams {
+                secret_key,
+                timestamp,
+                ttl,
+                gas_price,
+                dependencies,
+                chain_name,
+                session_account: &session_account,
+            },
+            payment_str_params,
+        )
+        .await
+        .map(Success::from)
+    }
+}
@@ -30,7 +30,7 @@ use casper_node::rpcs::{
 
 use account_address::GenerateAccountHash as AccountAddress;
 use command::{ClientCommand, Success};
-use deploy::{ListDeploys, MakeDeploy, MakeTransfer, SendDeploy, SignDeploy, Transfer};
+use deploy::{ListDeploys, MakeDeploy, MakeTransfer, Redelegate, SendDeploy, SignDeploy, Transfer};
 use generate_completion::GenerateCompletion;
 use keygen::Keygen;
 
@@ -44,6 +44,7 @@ enum DisplayOrder {
     SendDeploy,
     Transfer,
     MakeTransfer,
+    Redelegate,
     GetDeploy,
     GetBlock,
     GetBlockTransfers,
@@ -72,6 +73,7 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
         .subcommand(SendDeploy::build(DisplayOrder::SendDeploy as usize))
         .subcommand(Transfer::build(DisplayOrder::Transfer as usize))
         .subcommand(MakeTransfer::build(DisplayOrder::MakeTransfer as usize))
+        .subcommand(Redelegate::build(DisplayOrder::Redelegate as usize))
         .subcommand(GetDeploy::build(DisplayOrder::GetDeploy as usize))
         .subcommand(GetBlock::build(DisplayOrder::GetBlock as usize))
         .subcommand(GetBlockTransfers::build(
@@ -114,6 +116,7 @@ async fn main() {
         (SendDeploy::NAME, Some(matches)) => (SendDeploy::run(matches).await, matches),
         (Transfer::NAME, Some(matches)) => (Transfer::run(matches).await, matches),
         (MakeTransfer::NAME, Some(matches)) => (MakeTransfer::run(matches).await, matches),
+        (Redelegate::NAME, Some(matches)) => (Redelegate::run(matches).await, matches),
         (GetDeploy::NAME, Some(matches)) => (GetDeploy::run(matches).await, matches),
         (GetBlock::NAME, Some(matches)) => (GetBlock::run(matches).await, matches),
         (GetBlockTransfers::NAME, Some(matches)) => {
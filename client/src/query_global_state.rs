@@ -79,12 +79,12 @@ mod key {
     const ARG_VALUE_NAME: &str = "FORMATTED STRING or PATH";
     const ARG_HELP: &str =
         "The base key for the query. This must be a properly formatted public key, account hash, \
-        contract address hash, URef, transfer hash, deploy-info hash,era-info number, bid, withdraw \
-        or dictionary address. The format for each respectively is \"<HEX STRING>\", \
+        contract address hash, URef, transfer hash, deploy-info hash,era-info number, bid, withdraw, \
+        dictionary address or redelegation. The format for each respectively is \"<HEX STRING>\", \
         \"account-hash-<HEX STRING>\", \"hash-<HEX STRING>\", \
         \"uref-<HEX STRING>-<THREE DIGIT INTEGER>\", \"transfer-<HEX-STRING>\", \
         \"deploy-<HEX-STRING>\", \"era-<u64>\", \"bid-<HEX-STRING>\",\
-        \"withdraw-<HEX-STRING>\" or \"dictionary-<HEX-STRING>\". \
+        \"withdraw-<HEX-STRING>\", \"dictionary-<HEX-STRING>\" or \"redelegation-<HEX-STRING>\". \
         The system contract registry key is unique and can only take the value: \
         system-contract-registry-0000000000000000000000000000000000000000000000000000000000000000. \
         \nThe public key may instead be read in from a file, in which case \
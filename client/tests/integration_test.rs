@@ -29,6 +29,12 @@ const VALID_PURSE_UREF: &str =
 const VALID_STATE_ROOT_HASH: &str =
     "55db08058acb54c295b115cbd9b282eb2862e76d5bb8493bb80c0598a50a12a5";
 
+const VALID_AUCTION_CONTRACT_HASH: &str =
+    "hash-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20";
+const VALID_VALIDATOR: &str = "01522ef6c89038019cb7af05c340623804392dd2bb1f4dab5e4a9c3ab752fc0179";
+const VALID_NEW_VALIDATOR: &str =
+    "01c9e33693951aaac23c49bee44ad6f863eedcd38c084a3a8f11237716a3df9c2c";
+
 const DEFAULT_RATE_LIMIT: u64 = 1;
 const DEFAULT_RATE_PER: Duration = Duration::from_secs(1);
 
@@ -201,6 +207,28 @@ impl MockServerHandle {
         .map(|_| ())
     }
 
+    async fn redelegate(
+        &self,
+        validator: &str,
+        new_validator: &str,
+        deploy_params: DeployStrParams<'_>,
+        payment_params: PaymentStrParams<'_>,
+    ) -> Result<(), Error> {
+        casper_client::redelegate(
+            "1",
+            &self.url(),
+            0,
+            VALID_AUCTION_CONTRACT_HASH,
+            validator,
+            new_validator,
+            "100",
+            deploy_params,
+            payment_params,
+        )
+        .await
+        .map(|_| ())
+    }
+
     async fn put_deploy(
         &self,
         deploy_params: DeployStrParams<'_>,
@@ -1399,3 +1427,40 @@ mod transfer {
             .is_ok());
     }
 }
+
+mod redelegate {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_succeed() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        assert!(server_handle
+            .redelegate(
+                VALID_VALIDATOR,
+                VALID_NEW_VALIDATOR,
+                deploy_params::test_data_valid(),
+                payment_params::test_data_with_name()
+            )
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_fail_with_invalid_new_validator() {
+        let server_handle = MockServerHandle::spawn::<PutDeployParams>(PutDeploy::METHOD);
+        assert!(matches!(
+            server_handle
+                .redelegate(
+                    VALID_VALIDATOR,
+                    "invalid",
+                    deploy_params::test_data_valid(),
+                    payment_params::test_data_with_name()
+                )
+                .await,
+            Err(Error::InvalidArgument {
+                context: "new_validator",
+                ..
+            })
+        ));
+    }
+}
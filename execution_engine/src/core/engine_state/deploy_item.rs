@@ -23,20 +23,29 @@ pub struct DeployItem {
     pub gas_price: GasPrice,
     /// List of accounts that signed this deploy.
     pub authorization_keys: BTreeSet<AccountHash>,
+    /// Address of a separate account paying for this deploy, if any. When set, payment code is
+    /// executed in the context of this account while session code still runs in the context of
+    /// `address`.
+    pub payer: Option<AccountHash>,
     /// A unique identifier of the deploy.
     /// Currently it is the hash of the deploy header (see `DeployHeader` in the `types` crate).
     pub deploy_hash: DeployHash,
+    /// Deploys which must have been executed successfully before this deploy can be executed.
+    pub dependencies: Vec<DeployHash>,
 }
 
 impl DeployItem {
     /// Creates a [`DeployItem`].
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         address: AccountHash,
         session: ExecutableDeployItem,
         payment: ExecutableDeployItem,
         gas_price: GasPrice,
         authorization_keys: BTreeSet<AccountHash>,
+        payer: Option<AccountHash>,
         deploy_hash: DeployHash,
+        dependencies: Vec<DeployHash>,
     ) -> Self {
         DeployItem {
             address,
@@ -44,7 +53,14 @@ impl DeployItem {
             payment,
             gas_price,
             authorization_keys,
+            payer,
             deploy_hash,
+            dependencies,
         }
     }
+
+    /// Returns the address of the account paying for this deploy.
+    pub fn payer_address(&self) -> AccountHash {
+        self.payer.unwrap_or(self.address)
+    }
 }
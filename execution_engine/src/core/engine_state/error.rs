@@ -3,7 +3,7 @@ use datasize::DataSize;
 use thiserror::Error;
 
 use casper_hashing::Digest;
-use casper_types::{bytesrepr, system::mint, ApiError, ProtocolVersion};
+use casper_types::{bytesrepr, system::mint, ApiError, DeployHash, ProtocolVersion};
 
 use crate::{
     core::{
@@ -78,6 +78,9 @@ pub enum Error {
     /// Missing system contract hash.
     #[error("Missing system contract hash: {0}")]
     MissingSystemContractHash(String),
+    /// A deploy this deploy depends on was not executed successfully.
+    #[error("Dependency failed: {}", base16::encode_lower(.0.as_bytes()))]
+    DependencyFailed(DeployHash),
 }
 
 impl Error {
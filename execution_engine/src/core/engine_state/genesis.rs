@@ -22,9 +22,12 @@ use casper_types::{
         auction::{
             self, Bid, Bids, DelegationRate, Delegator, SeigniorageRecipient,
             SeigniorageRecipients, SeigniorageRecipientsSnapshot, AUCTION_DELAY_KEY,
-            DELEGATION_RATE_DENOMINATOR, ERA_END_TIMESTAMP_MILLIS_KEY, ERA_ID_KEY,
-            INITIAL_ERA_END_TIMESTAMP_MILLIS, INITIAL_ERA_ID, LOCKED_FUNDS_PERIOD_KEY,
-            SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
+            DELEGATION_RATE_CHANGE_DELAY_KEY, DELEGATION_RATE_DENOMINATOR,
+            ERA_END_TIMESTAMP_MILLIS_KEY, ERA_ID_KEY, INITIAL_ERA_END_TIMESTAMP_MILLIS,
+            INITIAL_ERA_ID, LOCKED_FUNDS_PERIOD_KEY, MAXIMUM_DELEGATION_AMOUNT_KEY,
+            MAX_DELEGATION_RATE_INCREASE_KEY, MAX_DELEGATORS_PER_VALIDATOR_KEY,
+            MINIMUM_DELEGATION_AMOUNT_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
+            UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
         },
         handle_payment,
         mint::{
@@ -532,6 +535,11 @@ pub struct ExecConfig {
     locked_funds_period_millis: u64,
     round_seigniorage_rate: Ratio<u64>,
     unbonding_delay: u64,
+    minimum_delegation_amount: u64,
+    maximum_delegation_amount: u64,
+    max_delegators_per_validator: u32,
+    delegation_rate_change_delay: u64,
+    max_delegation_rate_increase: DelegationRate,
     genesis_timestamp_millis: u64,
 }
 
@@ -547,6 +555,11 @@ impl ExecConfig {
         locked_funds_period_millis: u64,
         round_seigniorage_rate: Ratio<u64>,
         unbonding_delay: u64,
+        minimum_delegation_amount: u64,
+        maximum_delegation_amount: u64,
+        max_delegators_per_validator: u32,
+        delegation_rate_change_delay: u64,
+        max_delegation_rate_increase: DelegationRate,
         genesis_timestamp_millis: u64,
     ) -> ExecConfig {
         ExecConfig {
@@ -558,6 +571,11 @@ impl ExecConfig {
             locked_funds_period_millis,
             round_seigniorage_rate,
             unbonding_delay,
+            minimum_delegation_amount,
+            maximum_delegation_amount,
+            max_delegators_per_validator,
+            delegation_rate_change_delay,
+            max_delegation_rate_increase,
             genesis_timestamp_millis,
         }
     }
@@ -623,6 +641,31 @@ impl ExecConfig {
         self.unbonding_delay
     }
 
+    /// Returns the minimum amount a delegator must have delegated to a single validator.
+    pub fn minimum_delegation_amount(&self) -> u64 {
+        self.minimum_delegation_amount
+    }
+
+    /// Returns the maximum amount a delegator may have delegated to a single validator.
+    pub fn maximum_delegation_amount(&self) -> u64 {
+        self.maximum_delegation_amount
+    }
+
+    /// Returns the maximum number of delegators a single validator may have.
+    pub fn max_delegators_per_validator(&self) -> u32 {
+        self.max_delegators_per_validator
+    }
+
+    /// Returns the number of eras after which a validator's delegation rate increase takes effect.
+    pub fn delegation_rate_change_delay(&self) -> u64 {
+        self.delegation_rate_change_delay
+    }
+
+    /// Returns the maximum increase of a validator's delegation rate allowed in a single change.
+    pub fn max_delegation_rate_increase(&self) -> DelegationRate {
+        self.max_delegation_rate_increase
+    }
+
     /// Returns genesis timestamp expressed in milliseconds.
     pub fn genesis_timestamp_millis(&self) -> u64 {
         self.genesis_timestamp_millis
@@ -652,6 +695,16 @@ impl Distribution<ExecConfig> for Standard {
 
         let unbonding_delay = rng.gen();
 
+        let minimum_delegation_amount = rng.gen_range(0..1_000_000_000);
+
+        let maximum_delegation_amount = rng.gen_range(minimum_delegation_amount..u64::MAX);
+
+        let max_delegators_per_validator = rng.gen();
+
+        let delegation_rate_change_delay = rng.gen();
+
+        let max_delegation_rate_increase = rng.gen_range(0..=DELEGATION_RATE_DENOMINATOR);
+
         let genesis_timestamp_millis = rng.gen();
 
         ExecConfig {
@@ -663,6 +716,11 @@ impl Distribution<ExecConfig> for Standard {
             locked_funds_period_millis,
             round_seigniorage_rate,
             unbonding_delay,
+            minimum_delegation_amount,
+            maximum_delegation_amount,
+            max_delegators_per_validator,
+            delegation_rate_change_delay,
+            max_delegation_rate_increase,
             genesis_timestamp_millis,
         }
     }
@@ -727,6 +785,15 @@ pub enum GenesisError {
         /// Number of validator slots specified.
         validator_slots: u32,
     },
+    /// Too many delegators configured for a single genesis validator.
+    ExceededDelegatorSizeLimit {
+        /// Validator's public key.
+        validator_public_key: PublicKey,
+        /// Number of delegators of the validator in the genesis config.
+        delegators: usize,
+        /// Maximum number of delegators per validator specified.
+        max_delegators_per_validator: u32,
+    },
 }
 
 pub(crate) struct GenesisInstaller<S>
@@ -930,12 +997,18 @@ where
         }
 
         let genesis_delegators: Vec<_> = self.exec_config.get_bonded_delegators().collect();
+        let minimum_delegation_amount = U512::from(self.exec_config.minimum_delegation_amount());
+        let maximum_delegation_amount = U512::from(self.exec_config.maximum_delegation_amount());
+        let max_delegators_per_validator = self.exec_config.max_delegators_per_validator();
 
         // Make sure all delegators have corresponding genesis validator entries
         for (validator_public_key, delegator_public_key, _balance, delegated_amount) in
             genesis_delegators.iter()
         {
-            if delegated_amount.is_zero() {
+            if delegated_amount.is_zero()
+                || delegated_amount.value() < minimum_delegation_amount
+                || delegated_amount.value() > maximum_delegation_amount
+            {
                 return Err(GenesisError::InvalidDelegatedAmount {
                     public_key: (*delegator_public_key).clone(),
                 });
@@ -1023,6 +1096,14 @@ where
                         }
                     }
 
+                    if bid.delegators().len() > max_delegators_per_validator as usize {
+                        return Err(GenesisError::ExceededDelegatorSizeLimit {
+                            validator_public_key: public_key,
+                            delegators: bid.delegators().len(),
+                            max_delegators_per_validator,
+                        });
+                    }
+
                     bid
                 };
 
@@ -1143,6 +1224,89 @@ where
         );
         named_keys.insert(UNBONDING_DELAY_KEY.into(), unbonding_delay_uref.into());
 
+        let minimum_delegation_amount_uref = self
+            .uref_address_generator
+            .borrow_mut()
+            .new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            minimum_delegation_amount_uref.into(),
+            StoredValue::CLValue(
+                CLValue::from_t(self.exec_config.minimum_delegation_amount()).map_err(|_| {
+                    GenesisError::CLValue(MINIMUM_DELEGATION_AMOUNT_KEY.to_string())
+                })?,
+            ),
+        );
+        named_keys.insert(
+            MINIMUM_DELEGATION_AMOUNT_KEY.into(),
+            minimum_delegation_amount_uref.into(),
+        );
+
+        let maximum_delegation_amount_uref = self
+            .uref_address_generator
+            .borrow_mut()
+            .new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            maximum_delegation_amount_uref.into(),
+            StoredValue::CLValue(
+                CLValue::from_t(self.exec_config.maximum_delegation_amount()).map_err(|_| {
+                    GenesisError::CLValue(MAXIMUM_DELEGATION_AMOUNT_KEY.to_string())
+                })?,
+            ),
+        );
+        named_keys.insert(
+            MAXIMUM_DELEGATION_AMOUNT_KEY.into(),
+            maximum_delegation_amount_uref.into(),
+        );
+
+        let max_delegators_per_validator_uref = self
+            .uref_address_generator
+            .borrow_mut()
+            .new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            max_delegators_per_validator_uref.into(),
+            StoredValue::CLValue(CLValue::from_t(max_delegators_per_validator).map_err(|_| {
+                GenesisError::CLValue(MAX_DELEGATORS_PER_VALIDATOR_KEY.to_string())
+            })?),
+        );
+        named_keys.insert(
+            MAX_DELEGATORS_PER_VALIDATOR_KEY.into(),
+            max_delegators_per_validator_uref.into(),
+        );
+
+        let delegation_rate_change_delay_uref = self
+            .uref_address_generator
+            .borrow_mut()
+            .new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            delegation_rate_change_delay_uref.into(),
+            StoredValue::CLValue(
+                CLValue::from_t(self.exec_config.delegation_rate_change_delay()).map_err(|_| {
+                    GenesisError::CLValue(DELEGATION_RATE_CHANGE_DELAY_KEY.to_string())
+                })?,
+            ),
+        );
+        named_keys.insert(
+            DELEGATION_RATE_CHANGE_DELAY_KEY.into(),
+            delegation_rate_change_delay_uref.into(),
+        );
+
+        let max_delegation_rate_increase_uref = self
+            .uref_address_generator
+            .borrow_mut()
+            .new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            max_delegation_rate_increase_uref.into(),
+            StoredValue::CLValue(
+                CLValue::from_t(self.exec_config.max_delegation_rate_increase()).map_err(|_| {
+                    GenesisError::CLValue(MAX_DELEGATION_RATE_INCREASE_KEY.to_string())
+                })?,
+            ),
+        );
+        named_keys.insert(
+            MAX_DELEGATION_RATE_INCREASE_KEY.into(),
+            max_delegation_rate_increase_uref.into(),
+        );
+
         let entry_points = auction::auction_entry_points();
 
         let access_key = self
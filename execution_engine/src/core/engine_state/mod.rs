@@ -39,16 +39,19 @@ use casper_types::{
     system::{
         auction::{
             EraValidators, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_EVICTED_VALIDATORS,
-            ARG_REWARD_FACTORS, ARG_VALIDATOR_PUBLIC_KEYS, AUCTION_DELAY_KEY,
-            LOCKED_FUNDS_PERIOD_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
+            ARG_INACTIVITY_POLICY, ARG_REWARD_FACTORS, ARG_SLASH_REQUESTS, AUCTION_DELAY_KEY,
+            DELEGATION_RATE_CHANGE_DELAY_KEY, LOCKED_FUNDS_PERIOD_KEY,
+            MAXIMUM_DELEGATION_AMOUNT_KEY, MAX_DELEGATION_RATE_INCREASE_KEY,
+            MAX_DELEGATORS_PER_VALIDATOR_KEY, MINIMUM_DELEGATION_AMOUNT_KEY, UNBONDING_DELAY_KEY,
+            VALIDATOR_SLOTS_KEY,
         },
         handle_payment,
         mint::{self, ROUND_SEIGNIORAGE_RATE_KEY},
         CallStackElement, AUCTION, HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
-    AccessRights, ApiError, BlockTime, CLValue, Contract, ContractHash, DeployHash, DeployInfo,
-    Gas, Key, KeyTag, Motes, Phase, ProtocolVersion, PublicKey, RuntimeArgs, StoredValue, URef,
-    U512,
+    AccessRights, ApiError, BlockTime, CLTyped, CLValue, Contract, ContractHash, DeployHash,
+    DeployInfo, Gas, Key, KeyTag, Motes, Phase, ProtocolVersion, PublicKey, RuntimeArgs,
+    StoredValue, URef, U512,
 };
 
 pub use self::{
@@ -77,7 +80,7 @@ use crate::{
             genesis::GenesisInstaller,
             upgrade::{ProtocolUpgradeError, SystemUpgrader},
         },
-        execution::{self, DirectSystemContractCall, Executor},
+        execution::{self, AddressGenerator, DirectSystemContractCall, Executor},
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
     shared::{
@@ -382,6 +385,73 @@ where
             tracking_copy.borrow_mut().write(unbonding_delay_key, value);
         }
 
+        // The delegation bounds postdate the genesis of existing networks, so their named keys are
+        // created in the auction contract if missing.
+        let mut address_generator = AddressGenerator::new(pre_state_hash.as_ref(), Phase::System);
+
+        if let Some(new_minimum_delegation_amount) = upgrade_config.new_minimum_delegation_amount()
+        {
+            Self::write_auction_config_value(
+                correlation_id,
+                &tracking_copy,
+                *auction_hash,
+                &mut address_generator,
+                MINIMUM_DELEGATION_AMOUNT_KEY,
+                new_minimum_delegation_amount,
+            )?;
+        }
+
+        if let Some(new_maximum_delegation_amount) = upgrade_config.new_maximum_delegation_amount()
+        {
+            Self::write_auction_config_value(
+                correlation_id,
+                &tracking_copy,
+                *auction_hash,
+                &mut address_generator,
+                MAXIMUM_DELEGATION_AMOUNT_KEY,
+                new_maximum_delegation_amount,
+            )?;
+        }
+
+        if let Some(new_max_delegators_per_validator) =
+            upgrade_config.new_max_delegators_per_validator()
+        {
+            Self::write_auction_config_value(
+                correlation_id,
+                &tracking_copy,
+                *auction_hash,
+                &mut address_generator,
+                MAX_DELEGATORS_PER_VALIDATOR_KEY,
+                new_max_delegators_per_validator,
+            )?;
+        }
+
+        if let Some(new_delegation_rate_change_delay) =
+            upgrade_config.new_delegation_rate_change_delay()
+        {
+            Self::write_auction_config_value(
+                correlation_id,
+                &tracking_copy,
+                *auction_hash,
+                &mut address_generator,
+                DELEGATION_RATE_CHANGE_DELAY_KEY,
+                new_delegation_rate_change_delay,
+            )?;
+        }
+
+        if let Some(new_max_delegation_rate_increase) =
+            upgrade_config.new_max_delegation_rate_increase()
+        {
+            Self::write_auction_config_value(
+                correlation_id,
+                &tracking_copy,
+                *auction_hash,
+                &mut address_generator,
+                MAX_DELEGATION_RATE_INCREASE_KEY,
+                new_max_delegation_rate_increase,
+            )?;
+        }
+
         if let Some(new_round_seigniorage_rate) = upgrade_config.new_round_seigniorage_rate() {
             let new_round_seigniorage_rate: Ratio<U512> = {
                 let (numer, denom) = new_round_seigniorage_rate.into();
@@ -426,6 +496,38 @@ where
         })
     }
 
+    /// Writes a configuration value stored under the given named key of the auction contract,
+    /// creating the named key if it doesn't exist yet.
+    fn write_auction_config_value<T: CLTyped + ToBytes>(
+        correlation_id: CorrelationId,
+        tracking_copy: &RefCell<TrackingCopy<<S as StateProvider>::Reader>>,
+        auction_hash: ContractHash,
+        address_generator: &mut AddressGenerator,
+        name: &str,
+        value: T,
+    ) -> Result<(), Error> {
+        let value = StoredValue::CLValue(
+            CLValue::from_t(value).map_err(|_| Error::Bytesrepr(name.to_string()))?,
+        );
+        let mut auction_contract = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, auction_hash)?;
+        match auction_contract.named_keys().get(name) {
+            Some(key) => tracking_copy.borrow_mut().write(*key, value),
+            None => {
+                let uref = address_generator.new_uref(AccessRights::READ_ADD_WRITE);
+                tracking_copy.borrow_mut().write(uref.into(), value);
+                let mut named_keys = NamedKeys::new();
+                named_keys.insert(name.to_string(), uref.into());
+                auction_contract.named_keys_append(&mut named_keys);
+                tracking_copy
+                    .borrow_mut()
+                    .write(auction_hash.into(), StoredValue::Contract(auction_contract));
+            }
+        }
+        Ok(())
+    }
+
     /// Creates a new tracking copy instance.
     pub fn tracking_copy(&self, hash: Digest) -> Result<Option<TrackingCopy<S::Reader>>, Error> {
         match self.state.checkout(hash).map_err(Into::into)? {
@@ -511,14 +613,49 @@ where
         Ok(results)
     }
 
+    /// Applies the account's pending recovery if it has taken effect at `blocktime`, writing the
+    /// recovered account to the tracking copy so that the deploy is authorized against it.
+    fn apply_matured_recovery(
+        account: &mut Account,
+        blocktime: BlockTime,
+        tracking_copy: &RefCell<TrackingCopy<<S as StateProvider>::Reader>>,
+    ) {
+        if account.apply_matured_recovery(blocktime) {
+            tracking_copy.borrow_mut().write(
+                Key::Account(account.account_hash()),
+                StoredValue::Account(account.clone()),
+            );
+        }
+    }
+
+    /// Checks that every dependency of a deploy has been executed successfully, i.e. that a
+    /// [`DeployInfo`] has been recorded for it in global state.
+    fn check_dependencies(
+        correlation_id: CorrelationId,
+        dependencies: &[DeployHash],
+        tracking_copy: &RefCell<TrackingCopy<<S as StateProvider>::Reader>>,
+    ) -> Result<(), Error> {
+        for dependency in dependencies {
+            let maybe_deploy_info = tracking_copy
+                .borrow_mut()
+                .get(correlation_id, &Key::DeployInfo(*dependency))
+                .map_err(|error| Error::Exec(error.into()))?;
+            if !matches!(maybe_deploy_info, Some(StoredValue::DeployInfo(_))) {
+                return Err(Error::DependencyFailed(*dependency));
+            }
+        }
+        Ok(())
+    }
+
     fn get_authorized_account(
         &self,
         correlation_id: CorrelationId,
         account_hash: AccountHash,
         authorization_keys: &BTreeSet<AccountHash>,
+        blocktime: BlockTime,
         tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
     ) -> Result<Account, Error> {
-        let account: Account = match tracking_copy
+        let mut account: Account = match tracking_copy
             .borrow_mut()
             .get_account(correlation_id, account_hash)
         {
@@ -528,6 +665,8 @@ where
             }
         };
 
+        Self::apply_matured_recovery(&mut account, blocktime, &tracking_copy);
+
         // Authorize using provided authorization keys
         if !account.can_authorize(authorization_keys) {
             return Err(error::Error::Authorization);
@@ -541,6 +680,60 @@ where
         Ok(account)
     }
 
+    /// Authorizes a deploy whose payment is made by a separate payer account.
+    ///
+    /// Each of the provided authorization keys must be associated with either the deploy's account
+    /// or the payer's account.  The keys associated with each account are checked separately
+    /// against that account's deployment threshold, and returned alongside the account.
+    #[allow(clippy::type_complexity)]
+    fn get_authorized_account_and_payer(
+        &self,
+        correlation_id: CorrelationId,
+        account_hash: AccountHash,
+        payer_hash: AccountHash,
+        authorization_keys: &BTreeSet<AccountHash>,
+        blocktime: BlockTime,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+    ) -> Result<
+        (
+            (Account, BTreeSet<AccountHash>),
+            (Account, BTreeSet<AccountHash>),
+        ),
+        Error,
+    > {
+        let mut account: Account = tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, account_hash)
+            .map_err(|_| Error::Authorization)?;
+        Self::apply_matured_recovery(&mut account, blocktime, &tracking_copy);
+
+        let mut payer: Account = tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, payer_hash)
+            .map_err(|_| Error::Authorization)?;
+        Self::apply_matured_recovery(&mut payer, blocktime, &tracking_copy);
+
+        let account_keys = account.associated_authorization_keys(authorization_keys);
+        let payer_keys = payer.associated_authorization_keys(authorization_keys);
+
+        // Every provided key has to belong to one of the two accounts.
+        if account_keys.union(&payer_keys).count() != authorization_keys.len() {
+            return Err(Error::Authorization);
+        }
+
+        // Authorize each account using its own subset of the provided keys
+        if !account.can_authorize(&account_keys) || !payer.can_authorize(&payer_keys) {
+            return Err(Error::Authorization);
+        }
+
+        // Check each subset's total key weight against its account's deploy threshold
+        if !account.can_deploy_with(&account_keys) || !payer.can_deploy_with(&payer_keys) {
+            return Err(execution::Error::DeploymentAuthorizationFailure.into());
+        }
+
+        Ok(((account, account_keys), (payer, payer_keys)))
+    }
+
     /// Get the balance of a passed purse referenced by its [`URef`].
     pub fn get_purse_balance(
         &self,
@@ -598,6 +791,20 @@ where
             }
         };
 
+        if let Err(error) =
+            Self::check_dependencies(correlation_id, &deploy_item.dependencies, &tracking_copy)
+        {
+            return Ok(ExecutionResult::precondition_failure(error));
+        }
+
+        if deploy_item.payer.is_some() {
+            // Wasmless transfers are paid for from the transfer's source purse, so a separate
+            // payer can't be honored.
+            return Ok(ExecutionResult::precondition_failure(
+                Error::InvalidDeployItemVariant("Transfer with a separate payer".to_string()),
+            ));
+        }
+
         let base_key = Key::Account(deploy_item.address);
 
         let account_public_key = match base_key.into_account() {
@@ -615,6 +822,7 @@ where
             correlation_id,
             account_public_key,
             &authorization_keys,
+            blocktime,
             Rc::clone(&tracking_copy),
         ) {
             Ok(account) => account,
@@ -1192,25 +1400,54 @@ where
             }
         };
 
+        // Deploys whose dependencies have not been executed successfully are not executed.
+        if let Err(error) =
+            Self::check_dependencies(correlation_id, &deploy_item.dependencies, &tracking_copy)
+        {
+            return Ok(ExecutionResult::precondition_failure(error));
+        }
+
         // Get addr bytes from `address` (which is actually a Key)
         // validation_spec_3: account validity
 
         let authorization_keys = deploy_item.authorization_keys;
 
-        // Get account from tracking copy
+        // Get account from tracking copy, along with the account paying for the deploy (which is
+        // the same account unless a separate payer was specified).
         // validation_spec_3: account validity
-        let account = {
-            let account_hash = deploy_item.address;
-            match self.get_authorized_account(
-                correlation_id,
-                account_hash,
-                &authorization_keys,
-                Rc::clone(&tracking_copy),
-            ) {
-                Ok(account) => account,
-                Err(e) => return Ok(ExecutionResult::precondition_failure(e)),
-            }
-        };
+        let (account, session_authorization_keys, payer_account, payment_authorization_keys) =
+            match deploy_item.payer {
+                Some(payer_hash) if payer_hash != deploy_item.address => {
+                    match self.get_authorized_account_and_payer(
+                        correlation_id,
+                        deploy_item.address,
+                        payer_hash,
+                        &authorization_keys,
+                        blocktime,
+                        Rc::clone(&tracking_copy),
+                    ) {
+                        Ok(((account, account_keys), (payer, payer_keys))) => {
+                            (account, account_keys, payer, payer_keys)
+                        }
+                        Err(e) => return Ok(ExecutionResult::precondition_failure(e)),
+                    }
+                }
+                _ => match self.get_authorized_account(
+                    correlation_id,
+                    deploy_item.address,
+                    &authorization_keys,
+                    blocktime,
+                    Rc::clone(&tracking_copy),
+                ) {
+                    Ok(account) => (
+                        account.clone(),
+                        authorization_keys.clone(),
+                        account,
+                        authorization_keys.clone(),
+                    ),
+                    Err(e) => return Ok(ExecutionResult::precondition_failure(e)),
+                },
+            };
 
         let session = deploy_item.session;
         let payment = deploy_item.payment;
@@ -1236,10 +1473,10 @@ where
             }
         };
 
-        // Get account main purse balance key
+        // Get payer's main purse balance key
         // validation_spec_5: account main purse minimum balance
         let account_main_purse_balance_key: Key = {
-            let account_key = Key::URef(account.main_purse());
+            let account_key = Key::URef(payer_account.main_purse());
             match tracking_copy
                 .borrow_mut()
                 .get_purse_balance_key(correlation_id, account_key)
@@ -1306,7 +1543,7 @@ where
             let phase = Phase::Payment;
             let payment_metadata = match payment.get_deploy_metadata(
                 Rc::clone(&tracking_copy),
-                &account,
+                &payer_account,
                 correlation_id,
                 &preprocessor,
                 &protocol_version,
@@ -1329,7 +1566,7 @@ where
             let mut payment_named_keys = if payment_metadata.kind == DeployKind::Contract {
                 payment_metadata.contract.named_keys().clone()
             } else {
-                account.named_keys().clone()
+                payer_account.named_keys().clone()
             };
             let payment_entry_point = payment_metadata.entry_point;
 
@@ -1341,9 +1578,9 @@ where
                     payment_module,
                     payment_args,
                     payment_base_key,
-                    &account,
+                    &payer_account,
                     &mut payment_named_keys,
-                    authorization_keys.clone(),
+                    payment_authorization_keys,
                     blocktime,
                     deploy_hash,
                     payment_gas_limit,
@@ -1360,9 +1597,9 @@ where
                     payment_entry_point,
                     payment_args,
                     payment_base_key,
-                    &account,
+                    &payer_account,
                     &mut payment_named_keys,
-                    authorization_keys.clone(),
+                    payment_authorization_keys,
                     blocktime,
                     deploy_hash,
                     payment_gas_limit,
@@ -1545,7 +1782,7 @@ where
                 session_base_key,
                 &account,
                 &mut session_named_keys,
-                authorization_keys.clone(),
+                session_authorization_keys,
                 blocktime,
                 deploy_hash,
                 session_gas_limit,
@@ -1568,7 +1805,7 @@ where
                 deploy_hash,
                 transfers,
                 account.account_hash(),
-                account.main_purse(),
+                payer_account.main_purse(),
                 cost,
             );
             session_tracking_copy.borrow_mut().write(
@@ -1634,7 +1871,7 @@ where
 
                 let maybe_runtime_args = RuntimeArgs::try_new(|args| {
                     args.insert(handle_payment::ARG_AMOUNT, finalize_cost_motes.value())?;
-                    args.insert(handle_payment::ARG_ACCOUNT, account.account_hash())?;
+                    args.insert(handle_payment::ARG_ACCOUNT, payer_account.account_hash())?;
                     args.insert(handle_payment::ARG_TARGET, proposer_purse)?;
                     Ok(())
                 });
@@ -2001,6 +2238,7 @@ where
 
         let reward_args = RuntimeArgs::try_new(|args| {
             args.insert(ARG_REWARD_FACTORS, reward_factors)?;
+            args.insert(ARG_INACTIVITY_POLICY, step_request.inactivity_policy)?;
             Ok(())
         })?;
 
@@ -2036,21 +2274,10 @@ where
             return Err(StepError::DistributeError(exec_error));
         }
 
-        let slashed_validators = match step_request.slashed_validators() {
-            Ok(slashed_validators) => slashed_validators,
-            Err(error) => {
-                error!(
-                    "failed to deserialize validator_ids for slashing: {}",
-                    error.to_string()
-                );
-                return Err(StepError::BytesRepr(error));
-            }
-        };
-
         let slash_args = {
             let mut runtime_args = RuntimeArgs::new();
             runtime_args
-                .insert(ARG_VALIDATOR_PUBLIC_KEYS, slashed_validators)
+                .insert(ARG_SLASH_REQUESTS, step_request.slash_requests())
                 .map_err(|e| Error::Exec(e.into()))?;
             runtime_args
         };
@@ -4,9 +4,13 @@
 //! rewards.
 use std::{collections::BTreeMap, vec::Vec};
 
+use num_rational::Ratio;
+
 use casper_hashing::Digest;
 use casper_types::{
-    bytesrepr, bytesrepr::ToBytes, CLValueError, EraId, ProtocolVersion, PublicKey,
+    bytesrepr,
+    system::auction::{InactivityPolicy, SlashEvidence, SlashRequest},
+    CLValueError, EraId, ProtocolVersion, PublicKey,
 };
 
 use crate::core::{
@@ -19,12 +23,34 @@ use crate::core::{
 pub struct SlashItem {
     /// The public key of the validator that will be slashed.
     pub validator_id: PublicKey,
+    /// Fraction of the stakes bonded to the validator that will be slashed.
+    pub fraction: Ratio<u64>,
+    /// The public key of the validator that will receive the slashed funds.  If `None`, the
+    /// slashed funds are burned.
+    pub recipient: Option<PublicKey>,
+    /// Reference to the evidence of the offense, if known.
+    pub evidence: Option<SlashEvidence>,
 }
 
 impl SlashItem {
-    /// Creates a new slash item.
+    /// Creates a new slash item which slashes the whole stake of the validator and burns it.
     pub fn new(validator_id: PublicKey) -> Self {
-        Self { validator_id }
+        Self::with_fraction(validator_id, Ratio::from_integer(1), None, None)
+    }
+
+    /// Creates a new slash item which slashes the given fraction of the validator's stake.
+    pub fn with_fraction(
+        validator_id: PublicKey,
+        fraction: Ratio<u64>,
+        recipient: Option<PublicKey>,
+        evidence: Option<SlashEvidence>,
+    ) -> Self {
+        Self {
+            validator_id,
+            fraction,
+            recipient,
+            evidence,
+        }
     }
 }
 
@@ -70,7 +96,7 @@ pub struct StepRequest {
     pub protocol_version: ProtocolVersion,
     /// List of validators to be slashed.
     ///
-    /// A slashed validator is removed from the next validator set.
+    /// A validator whose whole stake is slashed is removed from the next validator set.
     pub slash_items: Vec<SlashItem>,
     /// List of validators that will be rewarded.
     pub reward_items: Vec<RewardItem>,
@@ -79,6 +105,8 @@ pub struct StepRequest {
     /// Compared to a slashing, evictions are deactivating a given validator, but his stake is
     /// unchanged. A further re-activation is possible.
     pub evict_items: Vec<EvictItem>,
+    /// How validators which participated too little in the era are penalized.
+    pub inactivity_policy: InactivityPolicy,
     /// If true an auction contract will be executed to compute new era validators.
     pub run_auction: bool,
     /// Specifies which era validators will be returned based on `next_era_id`.
@@ -99,6 +127,7 @@ impl StepRequest {
         slash_items: Vec<SlashItem>,
         reward_items: Vec<RewardItem>,
         evict_items: Vec<EvictItem>,
+        inactivity_policy: InactivityPolicy,
         run_auction: bool,
         next_era_id: EraId,
         era_end_timestamp_millis: u64,
@@ -109,21 +138,26 @@ impl StepRequest {
             slash_items,
             reward_items,
             evict_items,
+            inactivity_policy,
             run_auction,
             next_era_id,
             era_end_timestamp_millis,
         }
     }
 
-    /// Returns list of slashed validators.
-    pub fn slashed_validators(&self) -> Result<Vec<PublicKey>, bytesrepr::Error> {
-        let mut ret = vec![];
-        for slash_item in &self.slash_items {
-            let public_key: PublicKey =
-                bytesrepr::deserialize(slash_item.validator_id.clone().to_bytes()?)?;
-            ret.push(public_key);
-        }
-        Ok(ret)
+    /// Returns the slash requests passed to the auction contract.
+    pub fn slash_requests(&self) -> Vec<SlashRequest> {
+        self.slash_items
+            .iter()
+            .map(|slash_item| {
+                SlashRequest::new(
+                    slash_item.validator_id.clone(),
+                    slash_item.fraction,
+                    slash_item.recipient.clone(),
+                    slash_item.evidence.clone(),
+                )
+            })
+            .collect()
     }
 
     /// Returns all reward factors.
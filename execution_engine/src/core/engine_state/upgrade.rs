@@ -8,8 +8,8 @@ use casper_hashing::Digest;
 use casper_types::{
     bytesrepr,
     system::{
-        auction, handle_payment, mint, standard_payment, AUCTION, HANDLE_PAYMENT, MINT,
-        STANDARD_PAYMENT,
+        auction::{self, DelegationRate},
+        handle_payment, mint, standard_payment, AUCTION, HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
     Contract, ContractHash, EntryPoints, EraId, Key, ProtocolVersion, StoredValue,
 };
@@ -51,6 +51,11 @@ pub struct UpgradeConfig {
     new_locked_funds_period_millis: Option<u64>,
     new_round_seigniorage_rate: Option<Ratio<u64>>,
     new_unbonding_delay: Option<u64>,
+    new_minimum_delegation_amount: Option<u64>,
+    new_maximum_delegation_amount: Option<u64>,
+    new_max_delegators_per_validator: Option<u32>,
+    new_delegation_rate_change_delay: Option<u64>,
+    new_max_delegation_rate_increase: Option<DelegationRate>,
     global_state_update: BTreeMap<Key, StoredValue>,
 }
 
@@ -67,6 +72,11 @@ impl UpgradeConfig {
         new_locked_funds_period_millis: Option<u64>,
         new_round_seigniorage_rate: Option<Ratio<u64>>,
         new_unbonding_delay: Option<u64>,
+        new_minimum_delegation_amount: Option<u64>,
+        new_maximum_delegation_amount: Option<u64>,
+        new_max_delegators_per_validator: Option<u32>,
+        new_delegation_rate_change_delay: Option<u64>,
+        new_max_delegation_rate_increase: Option<DelegationRate>,
         global_state_update: BTreeMap<Key, StoredValue>,
     ) -> Self {
         UpgradeConfig {
@@ -79,6 +89,11 @@ impl UpgradeConfig {
             new_locked_funds_period_millis,
             new_round_seigniorage_rate,
             new_unbonding_delay,
+            new_minimum_delegation_amount,
+            new_maximum_delegation_amount,
+            new_max_delegators_per_validator,
+            new_delegation_rate_change_delay,
+            new_max_delegation_rate_increase,
             global_state_update,
         }
     }
@@ -128,6 +143,31 @@ impl UpgradeConfig {
         self.new_unbonding_delay
     }
 
+    /// Returns new minimum delegation amount if specified.
+    pub fn new_minimum_delegation_amount(&self) -> Option<u64> {
+        self.new_minimum_delegation_amount
+    }
+
+    /// Returns new maximum delegation amount if specified.
+    pub fn new_maximum_delegation_amount(&self) -> Option<u64> {
+        self.new_maximum_delegation_amount
+    }
+
+    /// Returns new maximum number of delegators per validator if specified.
+    pub fn new_max_delegators_per_validator(&self) -> Option<u32> {
+        self.new_max_delegators_per_validator
+    }
+
+    /// Returns new delegation rate change delay if specified.
+    pub fn new_delegation_rate_change_delay(&self) -> Option<u64> {
+        self.new_delegation_rate_change_delay
+    }
+
+    /// Returns new maximum delegation rate increase if specified.
+    pub fn new_max_delegation_rate_increase(&self) -> Option<DelegationRate> {
+        self.new_max_delegation_rate_increase
+    }
+
     /// Returns new map of emergency global state updates.
     pub fn global_state_update(&self) -> &BTreeMap<Key, StoredValue> {
         &self.global_state_update
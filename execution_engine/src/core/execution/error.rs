@@ -3,7 +3,10 @@ use parity_wasm::elements;
 use thiserror::Error;
 
 use casper_types::{
-    account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
+    account::{
+        AddKeyFailure, RecoveryFailure, RemoveKeyFailure, SetThresholdFailure,
+        SpendingLimitFailure, UpdateKeyFailure,
+    },
     bytesrepr, system, AccessRights, ApiError, CLType, CLValueError, ContractHash,
     ContractPackageHash, ContractVersionKey, ContractWasmHash, Key, StoredValueTypeMismatch, URef,
 };
@@ -79,6 +82,12 @@ pub enum Error {
     /// Error setting threshold on associated key.
     #[error("{}", _0)]
     SetThresholdFailure(SetThresholdFailure),
+    /// Error managing the spending limit of an associated key.
+    #[error("{}", _0)]
+    SpendingLimitFailure(SpendingLimitFailure),
+    /// Error managing the recovery of an account.
+    #[error("{}", _0)]
+    RecoveryFailure(RecoveryFailure),
     /// Error executing system contract.
     #[error("{}", _0)]
     SystemContract(system::Error),
@@ -242,6 +251,18 @@ impl From<SetThresholdFailure> for Error {
     }
 }
 
+impl From<SpendingLimitFailure> for Error {
+    fn from(err: SpendingLimitFailure) -> Self {
+        Error::SpendingLimitFailure(err)
+    }
+}
+
+impl From<RecoveryFailure> for Error {
+    fn from(err: RecoveryFailure) -> Self {
+        Error::RecoveryFailure(err)
+    }
+}
+
 impl From<system::Error> for Error {
     fn from(error: system::Error) -> Self {
         Error::SystemContract(error)
@@ -56,6 +56,9 @@ pub(crate) enum FunctionIndex {
     DictionaryGetFuncIndex,
     DictionaryPutFuncIndex,
     LoadCallStack,
+    SetSpendingLimitFuncIndex,
+    RemoveSpendingLimitFuncIndex,
+    ManageRecoveryFuncIndex,
 }
 
 impl From<FunctionIndex> for usize {
@@ -6,8 +6,8 @@ use casper_types::{
     contracts::NamedKeys,
     system::{
         auction::{
-            AccountProvider, Auction, Bid, EraInfo, Error, MintProvider, RuntimeProvider,
-            StorageProvider, UnbondingPurse,
+            AccountProvider, Auction, Bid, EraInfo, Error, MintProvider, Redelegation,
+            RuntimeProvider, StorageProvider, UnbondingPurse,
         },
         mint,
     },
@@ -102,6 +102,51 @@ where
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
     }
 
+    fn read_redelegations(
+        &mut self,
+        account_hash: &AccountHash,
+    ) -> Result<Vec<Redelegation>, Error> {
+        match self.context.read_gs(&Key::Redelegation(*account_hash)) {
+            Ok(Some(StoredValue::CLValue(cl_value))) => {
+                Ok(cl_value.into_t().map_err(|_| Error::CLValue)?)
+            }
+            Ok(Some(_)) => Err(Error::Storage),
+            Ok(None) => Ok(Vec::new()),
+            Err(execution::Error::BytesRepr(_)) => Err(Error::Serialization),
+            // NOTE: This extra condition is needed to correctly propagate GasLimit to the user. See
+            // also [`Runtime::reverter`] and [`to_auction_error`]
+            Err(execution::Error::GasLimit) => Err(Error::GasLimit),
+            Err(_) => Err(Error::Storage),
+        }
+    }
+
+    fn write_redelegations(
+        &mut self,
+        account_hash: AccountHash,
+        redelegations: Vec<Redelegation>,
+    ) -> Result<(), Error> {
+        let cl_value = CLValue::from_t(redelegations).map_err(|_| Error::CLValue)?;
+        self.context
+            .metered_write_gs_unsafe(
+                Key::Redelegation(account_hash),
+                StoredValue::CLValue(cl_value),
+            )
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
+
+    fn read_era_info(&mut self, era_id: EraId) -> Result<Option<EraInfo>, Error> {
+        match self.context.read_gs(&Key::EraInfo(era_id)) {
+            Ok(Some(StoredValue::EraInfo(era_info))) => Ok(Some(era_info)),
+            Ok(Some(_)) => Err(Error::Storage),
+            Ok(None) => Ok(None),
+            Err(execution::Error::BytesRepr(_)) => Err(Error::Serialization),
+            // NOTE: This extra condition is needed to correctly propagate GasLimit to the user. See
+            // also [`Runtime::reverter`] and [`to_auction_error`]
+            Err(execution::Error::GasLimit) => Err(Error::GasLimit),
+            Err(_) => Err(Error::Storage),
+        }
+    }
+
     fn record_era_info(&mut self, era_id: EraId, era_info: EraInfo) -> Result<(), Error> {
         Runtime::record_era_info(self, era_id, era_info)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::RecordEraInfo))
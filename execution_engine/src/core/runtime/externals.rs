@@ -1040,6 +1040,84 @@ where
                 let ret = self.load_call_stack(call_stack_len_ptr, result_size_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
+
+            FunctionIndex::SetSpendingLimitFuncIndex => {
+                // args(0) = pointer to array of bytes of an account hash
+                // args(1) = size of an account hash
+                // args(2) = pointer to array of bytes of the limit in motes
+                // args(3) = size of the limit
+                // args(4) = pointer to array of bytes of the spending window
+                // args(5) = size of the spending window
+                let (
+                    account_hash_ptr,
+                    account_hash_size,
+                    limit_ptr,
+                    limit_size,
+                    window_ptr,
+                    window_size,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.set_spending_limit,
+                    [
+                        account_hash_ptr,
+                        account_hash_size,
+                        limit_ptr,
+                        limit_size,
+                        window_ptr,
+                        window_size,
+                    ],
+                )?;
+                let value = self.set_spending_limit(
+                    account_hash_ptr,
+                    account_hash_size as usize,
+                    limit_ptr,
+                    limit_size as usize,
+                    window_ptr,
+                    window_size as usize,
+                )?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::RemoveSpendingLimitFuncIndex => {
+                // args(0) = pointer to array of bytes of an account hash
+                // args(1) = size of an account hash
+                let (account_hash_ptr, account_hash_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.remove_spending_limit,
+                    [account_hash_ptr, account_hash_size],
+                )?;
+                let value =
+                    self.remove_spending_limit(account_hash_ptr, account_hash_size as usize)?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+
+            FunctionIndex::ManageRecoveryFuncIndex => {
+                // args(0) = the recovery action type
+                // args(1) = pointer to array of bytes of the account hash of the account to recover
+                // args(2) = size of the account hash
+                // args(3) = pointer to array of bytes of the action's value
+                // args(4) = size of the action's value
+                let (action_type, account_hash_ptr, account_hash_size, value_ptr, value_size) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.manage_recovery,
+                    [
+                        action_type,
+                        account_hash_ptr,
+                        account_hash_size,
+                        value_ptr,
+                        value_size,
+                    ],
+                )?;
+                let value = self.manage_recovery(
+                    action_type,
+                    account_hash_ptr,
+                    account_hash_size as usize,
+                    value_ptr,
+                    value_size as usize,
+                )?;
+                Ok(Some(RuntimeValue::I32(value)))
+            }
         }
     }
 }
@@ -49,6 +49,15 @@ where
     fn get_key(&self, name: &str) -> Option<Key> {
         self.context.named_keys_get(name).cloned()
     }
+
+    fn record_spending(&mut self, source: URef, amount: U512) -> Result<(), Error> {
+        self.context
+            .record_spending(source, amount)
+            .map_err(|exec_error| match exec_error {
+                execution::Error::SpendingLimitFailure(_) => Error::SpendingLimitExceeded,
+                exec_error => <Option<Error>>::from(exec_error).unwrap_or(Error::Storage),
+            })
+    }
 }
 
 // TODO: update Mint + StorageProvider to better handle errors
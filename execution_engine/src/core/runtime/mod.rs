@@ -15,12 +15,16 @@ use std::{
 };
 
 use itertools::Itertools;
+use num_rational::Ratio;
 use parity_wasm::elements::Module;
 use tracing::error;
 use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
 
 use casper_types::{
-    account::{Account, AccountHash, ActionType, Weight},
+    account::{
+        Account, AccountHash, ActionType, RecoveryActionType, RecoveryConfig, RecoveryRequest,
+        SpendingLimit, SpendingWindow, Weight,
+    },
     bytesrepr::{self, FromBytes, ToBytes},
     contracts::{
         self, Contract, ContractPackage, ContractPackageStatus, ContractVersion, ContractVersions,
@@ -28,7 +32,7 @@ use casper_types::{
     },
     system::{
         self,
-        auction::{self, Auction, EraInfo},
+        auction::{self, Auction, EraInfo, InactivityPolicy, SlashRequest},
         handle_payment::{self, HandlePayment},
         mint::{self, Mint},
         standard_payment::{self, StandardPayment},
@@ -109,6 +113,7 @@ pub fn key_to_tuple(key: Key) -> Option<([u8; 32], AccessRights)> {
         Key::Balance(_) => None,
         Key::Bid(_) => None,
         Key::Withdraw(_) => None,
+        Key::Redelegation(_) => None,
         Key::Dictionary(_) => None,
         Key::SystemContractRegistry => None,
     }
@@ -1812,6 +1817,22 @@ where
                 CLValue::from_t(result).map_err(Self::reverter)
             })(),
 
+            auction::METHOD_REDELEGATE => (|| {
+                runtime.charge_system_contract_call(auction_costs.redelegate)?;
+
+                let delegator = Self::get_named_argument(runtime_args, auction::ARG_DELEGATOR)?;
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let amount = Self::get_named_argument(runtime_args, auction::ARG_AMOUNT)?;
+                let new_validator =
+                    Self::get_named_argument(runtime_args, auction::ARG_NEW_VALIDATOR)?;
+
+                let result = runtime
+                    .redelegate(delegator, validator, amount, new_validator)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+
             auction::METHOD_RUN_AUCTION => (|| {
                 runtime.charge_system_contract_call(auction_costs.run_auction)?;
 
@@ -1827,25 +1848,49 @@ where
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
 
-            // Type: `fn slash(validator_account_hashes: &[AccountHash]) -> Result<(), Error>`
+            // Type: `fn slash(slash_requests: Vec<SlashRequest>) -> Result<(), Error>`
+            //
+            // Callers may still pass `validator_public_keys: Vec<PublicKey>` instead, which
+            // slashes the whole stakes of those validators and burns them.
             auction::METHOD_SLASH => (|| {
                 runtime.charge_system_contract_call(auction_costs.slash)?;
 
-                let validator_public_keys =
-                    Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR_PUBLIC_KEYS)?;
-                runtime
-                    .slash(validator_public_keys)
-                    .map_err(Self::reverter)?;
+                let slash_requests = if runtime_args
+                    .get(auction::ARG_VALIDATOR_PUBLIC_KEYS)
+                    .is_some()
+                {
+                    let validator_public_keys: Vec<PublicKey> =
+                        Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR_PUBLIC_KEYS)?;
+                    validator_public_keys
+                        .into_iter()
+                        .map(|validator_public_key| {
+                            SlashRequest::new(
+                                validator_public_key,
+                                Ratio::from_integer(1),
+                                None,
+                                None,
+                            )
+                        })
+                        .collect()
+                } else {
+                    Self::get_named_argument(runtime_args, auction::ARG_SLASH_REQUESTS)?
+                };
+                runtime.slash(slash_requests).map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
 
-            // Type: `fn distribute(reward_factors: BTreeMap<PublicKey, u64>) -> Result<(), Error>`
+            // Type: `fn distribute(reward_factors: BTreeMap<PublicKey, u64>,
+            //                     inactivity_policy: InactivityPolicy) -> Result<(), Error>`
             auction::METHOD_DISTRIBUTE => (|| {
                 runtime.charge_system_contract_call(auction_costs.distribute)?;
 
                 let reward_factors: BTreeMap<PublicKey, u64> =
                     Self::get_named_argument(runtime_args, auction::ARG_REWARD_FACTORS)?;
-                runtime.distribute(reward_factors).map_err(Self::reverter)?;
+                let inactivity_policy: InactivityPolicy =
+                    Self::get_named_argument(runtime_args, auction::ARG_INACTIVITY_POLICY)?;
+                runtime
+                    .distribute(reward_factors, inactivity_policy)
+                    .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
 
@@ -2868,6 +2913,95 @@ where
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn set_spending_limit(
+        &mut self,
+        account_hash_ptr: u32,
+        account_hash_size: usize,
+        limit_ptr: u32,
+        limit_size: usize,
+        window_ptr: u32,
+        window_size: usize,
+    ) -> Result<i32, Trap> {
+        let account_hash: AccountHash = {
+            let bytes = self.bytes_from_mem(account_hash_ptr, account_hash_size)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let limit: U512 = {
+            let bytes = self.bytes_from_mem(limit_ptr, limit_size)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let window: SpendingWindow = {
+            let bytes = self.bytes_from_mem(window_ptr, window_size)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let spending_limit = SpendingLimit::new(limit, window);
+
+        match self
+            .context
+            .set_spending_limit(account_hash, spending_limit)
+        {
+            Ok(_) => Ok(0),
+            Err(Error::SpendingLimitFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn remove_spending_limit(
+        &mut self,
+        account_hash_ptr: u32,
+        account_hash_size: usize,
+    ) -> Result<i32, Trap> {
+        let account_hash: AccountHash = {
+            let bytes = self.bytes_from_mem(account_hash_ptr, account_hash_size)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+
+        match self.context.remove_spending_limit(account_hash) {
+            Ok(_) => Ok(0),
+            Err(Error::SpendingLimitFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn manage_recovery(
+        &mut self,
+        action_type_value: u32,
+        account_hash_ptr: u32,
+        account_hash_size: usize,
+        value_ptr: u32,
+        value_size: usize,
+    ) -> Result<i32, Trap> {
+        let action_type = RecoveryActionType::try_from(action_type_value)
+            .map_err(|_| Trap::new(TrapKind::Unreachable))?;
+        let account_hash: AccountHash = {
+            let bytes = self.bytes_from_mem(account_hash_ptr, account_hash_size)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let value_bytes = self.bytes_from_mem(value_ptr, value_size)?;
+
+        let result = match action_type {
+            RecoveryActionType::Configure => {
+                let recovery_config: Option<RecoveryConfig> =
+                    bytesrepr::deserialize(value_bytes).map_err(Error::BytesRepr)?;
+                self.context
+                    .set_recovery_config(account_hash, recovery_config)
+            }
+            RecoveryActionType::Start => {
+                let request: RecoveryRequest =
+                    bytesrepr::deserialize(value_bytes).map_err(Error::BytesRepr)?;
+                self.context.start_recovery(account_hash, request)
+            }
+            RecoveryActionType::Cancel => self.context.cancel_recovery(account_hash),
+        };
+
+        match result {
+            Ok(_) => Ok(0),
+            Err(Error::RecoveryFailure(e)) => Ok(e as i32),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     fn set_action_threshold(
         &mut self,
         action_type_value: u32,
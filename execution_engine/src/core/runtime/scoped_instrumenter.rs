@@ -147,6 +147,9 @@ impl Drop for ScopedInstrumenter {
             FunctionIndex::DictionaryGetFuncIndex => "host_dictionary_get",
             FunctionIndex::DictionaryPutFuncIndex => "host_dictionary_put",
             FunctionIndex::LoadCallStack => "host_load_call_stack",
+            FunctionIndex::SetSpendingLimitFuncIndex => "host_function_set_spending_limit",
+            FunctionIndex::RemoveSpendingLimitFuncIndex => "host_function_remove_spending_limit",
+            FunctionIndex::ManageRecoveryFuncIndex => "host_function_manage_recovery",
         };
 
         let mut properties = mem::take(&mut self.properties);
@@ -11,16 +11,20 @@ use tracing::error;
 
 use casper_types::{
     account::{
-        Account, AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        Account, AccountHash, ActionType, AddKeyFailure, RecoveryConfig, RecoveryFailure,
+        RecoveryRequest, RemoveKeyFailure, SetThresholdFailure, SpendingLimit,
+        SpendingLimitFailure, UpdateKeyFailure, Weight,
     },
     bytesrepr::ToBytes,
     contracts::NamedKeys,
-    system::auction::EraInfo,
+    system::{
+        auction::{EraInfo, ERA_ID_KEY},
+        AUCTION,
+    },
     AccessRights, BlockTime, CLType, CLValue, Contract, ContractHash, ContractPackage,
-    ContractPackageHash, DeployHash, DeployInfo, EntryPointAccess, EntryPointType, Gas, Key,
+    ContractPackageHash, DeployHash, DeployInfo, EntryPointAccess, EntryPointType, EraId, Gas, Key,
     KeyTag, Phase, ProtocolVersion, PublicKey, RuntimeArgs, StoredValue, Transfer, TransferAddr,
-    URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, KEY_HASH_LENGTH,
+    URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, KEY_HASH_LENGTH, U512,
 };
 
 use crate::{
@@ -281,6 +285,10 @@ where
                 self.named_keys.remove(name);
                 Ok(())
             }
+            Key::Redelegation(_) => {
+                self.named_keys.remove(name);
+                Ok(())
+            }
             Key::Dictionary(_) => {
                 self.named_keys.remove(name);
                 Ok(())
@@ -735,6 +743,7 @@ where
             Key::Balance(_) => false,
             Key::Bid(_) => true,
             Key::Withdraw(_) => true,
+            Key::Redelegation(_) => true,
             Key::Dictionary(_) => {
                 // Dictionary is a special case that will not be readable by default, but the access
                 // bits are verified from within API call.
@@ -755,6 +764,7 @@ where
             Key::Balance(_) => false,
             Key::Bid(_) => false,
             Key::Withdraw(_) => false,
+            Key::Redelegation(_) => false,
             Key::Dictionary(_) => {
                 // Dictionary is a special case that will not be readable by default, but the access
                 // bits are verified from within API call.
@@ -775,6 +785,7 @@ where
             Key::Balance(_) => false,
             Key::Bid(_) => false,
             Key::Withdraw(_) => false,
+            Key::Redelegation(_) => false,
             Key::Dictionary(_) => {
                 // Dictionary is a special case that will not be readable by default, but the access
                 // bits are verified from within API call.
@@ -1089,6 +1100,229 @@ where
         Ok(())
     }
 
+    /// Sets the spending limit of an associated key.
+    pub(crate) fn set_spending_limit(
+        &mut self,
+        account_hash: AccountHash,
+        spending_limit: SpendingLimit,
+    ) -> Result<(), Error> {
+        // Check permission to modify spending limits
+        if !self.is_valid_context() {
+            // Exit early with error to avoid mutations
+            return Err(SpendingLimitFailure::PermissionDenied.into());
+        }
+
+        if !self
+            .account()
+            .can_manage_keys_with(&self.authorization_keys)
+        {
+            // Exit early if authorization keys weight doesn't exceed required
+            // key management threshold
+            return Err(SpendingLimitFailure::PermissionDenied.into());
+        }
+
+        // Converts an account's public key into a URef
+        let key = Key::Account(self.account().account_hash());
+
+        // Take an account out of the global state
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        // Exit early in case of error without updating global state
+        account
+            .set_spending_limit(account_hash, spending_limit)
+            .map_err(Error::from)?;
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.metered_write_gs_unsafe(key, account_value)?;
+
+        Ok(())
+    }
+
+    /// Removes the spending limit of an associated key.
+    pub(crate) fn remove_spending_limit(&mut self, account_hash: AccountHash) -> Result<(), Error> {
+        // Check permission to modify spending limits
+        if !self.is_valid_context() {
+            // Exit early with error to avoid mutations
+            return Err(SpendingLimitFailure::PermissionDenied.into());
+        }
+
+        if !self
+            .account()
+            .can_manage_keys_with(&self.authorization_keys)
+        {
+            // Exit early if authorization keys weight doesn't exceed required
+            // key management threshold
+            return Err(SpendingLimitFailure::PermissionDenied.into());
+        }
+
+        // Converts an account's public key into a URef
+        let key = Key::Account(self.account().account_hash());
+
+        // Take an account out of the global state
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        // Exit early in case of error without updating global state
+        account
+            .remove_spending_limit(account_hash)
+            .map_err(Error::from)?;
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.metered_write_gs_unsafe(key, account_value)?;
+
+        Ok(())
+    }
+
+    /// Sets or clears the recovery configuration of the current account.
+    pub(crate) fn set_recovery_config(
+        &mut self,
+        account_hash: AccountHash,
+        recovery_config: Option<RecoveryConfig>,
+    ) -> Result<(), Error> {
+        // Check permission to modify the recovery configuration
+        if !self.is_valid_context() || account_hash != self.account().account_hash() {
+            // Exit early with error to avoid mutations
+            return Err(RecoveryFailure::PermissionDenied.into());
+        }
+
+        if !self
+            .account()
+            .can_manage_keys_with(&self.authorization_keys)
+        {
+            // Exit early if authorization keys weight doesn't exceed required
+            // key management threshold
+            return Err(RecoveryFailure::PermissionDenied.into());
+        }
+
+        let key = Key::Account(account_hash);
+
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        account.set_recovery_config(recovery_config);
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.metered_write_gs_unsafe(key, account_value)?;
+
+        Ok(())
+    }
+
+    /// Starts the recovery of the given account on behalf of its recovery keys.
+    ///
+    /// The recovered account is usually not the current account, so it is read and written
+    /// directly rather than through the access checks of the current context.  Only its
+    /// pending recovery is modified.
+    pub(crate) fn start_recovery(
+        &mut self,
+        account_hash: AccountHash,
+        request: RecoveryRequest,
+    ) -> Result<(), Error> {
+        if !self.is_valid_context() {
+            return Err(RecoveryFailure::PermissionDenied.into());
+        }
+
+        let key = Key::Account(account_hash);
+
+        let mut account: Account = match self.read_gs_direct(&key)? {
+            Some(StoredValue::Account(account)) => account,
+            Some(_) => return Err(Error::UnexpectedStoredValueVariant),
+            None => return Err(RecoveryFailure::MissingAccount.into()),
+        };
+
+        account
+            .start_recovery(&self.authorization_keys, request, self.blocktime)
+            .map_err(Error::from)?;
+
+        self.metered_write_gs_unsafe(key, StoredValue::Account(account))?;
+
+        Ok(())
+    }
+
+    /// Cancels the pending recovery of the current account.
+    pub(crate) fn cancel_recovery(&mut self, account_hash: AccountHash) -> Result<(), Error> {
+        if !self.is_valid_context() || account_hash != self.account().account_hash() {
+            return Err(RecoveryFailure::PermissionDenied.into());
+        }
+
+        let key = Key::Account(account_hash);
+
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        account
+            .cancel_recovery(&self.authorization_keys)
+            .map_err(Error::from)?;
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.metered_write_gs_unsafe(key, account_value)?;
+
+        Ok(())
+    }
+
+    /// Records a transfer of `amount` out of the `source` purse against the spending limits of
+    /// the keys authorizing the deploy, if `source` is one of the account's purses.
+    ///
+    /// The account's purses are its main purse and the purses it holds with write access under
+    /// its named keys.
+    ///
+    /// This is called from within the mint, so the account is read and written directly rather
+    /// than through the access checks of the current context.
+    pub(crate) fn record_spending(&mut self, source: URef, amount: U512) -> Result<(), Error> {
+        let key = Key::Account(self.account().account_hash());
+
+        let mut account: Account = match self.read_gs_direct(&key)? {
+            Some(StoredValue::Account(account)) => account,
+            Some(_) => return Err(Error::UnexpectedStoredValueVariant),
+            None => return Err(Error::AccountNotFound(key)),
+        };
+
+        if account.spending_limits().is_empty() {
+            return Ok(());
+        }
+
+        let is_account_purse = source.addr() == account.main_purse().addr()
+            || account
+                .named_keys()
+                .values()
+                .filter_map(Key::as_uref)
+                .any(|uref| uref.addr() == source.addr() && uref.is_writeable());
+        if !is_account_purse {
+            return Ok(());
+        }
+
+        let era_id = self.get_era_id()?;
+        account
+            .record_spending(&self.authorization_keys, amount, era_id, self.blocktime)
+            .map_err(Error::from)?;
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.metered_write_gs_unsafe(key, account_value)?;
+
+        Ok(())
+    }
+
+    /// Reads the current era id held by the auction contract.
+    fn get_era_id(&mut self) -> Result<EraId, Error> {
+        let auction_key = Key::from(self.get_system_contract(AUCTION)?);
+        let auction: Contract = match self.read_gs_direct(&auction_key)? {
+            Some(StoredValue::Contract(contract)) => contract,
+            Some(_) => return Err(Error::UnexpectedStoredValueVariant),
+            None => return Err(Error::KeyNotFound(auction_key)),
+        };
+        let era_id_key = auction
+            .named_keys()
+            .get(ERA_ID_KEY)
+            .copied()
+            .ok_or_else(|| Error::NamedKeyNotFound(ERA_ID_KEY.to_string()))?;
+        match self.read_gs_direct(&era_id_key)? {
+            Some(StoredValue::CLValue(cl_value)) => Ok(cl_value.into_t()?),
+            Some(_) => Err(Error::UnexpectedStoredValueVariant),
+            None => Err(Error::KeyNotFound(era_id_key)),
+        }
+    }
+
     /// Returns borrowed instance of engine config.
     pub fn engine_config(&self) -> &EngineConfig {
         &self.engine_config
@@ -56,6 +56,10 @@ const DEFAULT_DICTIONARY_GET_KEY_SIZE_WEIGHT: u32 = 590;
 
 const DEFAULT_REMOVE_ASSOCIATED_KEY_COST: u32 = 4_200;
 
+const DEFAULT_SET_SPENDING_LIMIT_COST: u32 = 9_000;
+const DEFAULT_REMOVE_SPENDING_LIMIT_COST: u32 = 4_200;
+const DEFAULT_MANAGE_RECOVERY_COST: u32 = 9_000;
+
 const DEFAULT_REMOVE_KEY_COST: u32 = 61_000;
 const DEFAULT_REMOVE_KEY_NAME_SIZE_WEIGHT: u32 = 3_200;
 
@@ -284,6 +288,12 @@ pub struct HostFunctionCosts {
     pub print: HostFunction<[Cost; 2]>,
     /// Cost of calling the `blake2b` host function.
     pub blake2b: HostFunction<[Cost; 4]>,
+    /// Cost of calling the `set_spending_limit` host function.
+    pub set_spending_limit: HostFunction<[Cost; 6]>,
+    /// Cost of calling the `remove_spending_limit` host function.
+    pub remove_spending_limit: HostFunction<[Cost; 2]>,
+    /// Cost of calling the `manage_recovery` host function.
+    pub manage_recovery: HostFunction<[Cost; 5]>,
 }
 
 impl Default for HostFunctionCosts {
@@ -401,6 +411,9 @@ impl Default for HostFunctionCosts {
                 [NOT_USED, DEFAULT_PRINT_TEXT_SIZE_WEIGHT],
             ),
             blake2b: HostFunction::default(),
+            set_spending_limit: HostFunction::fixed(DEFAULT_SET_SPENDING_LIMIT_COST),
+            remove_spending_limit: HostFunction::fixed(DEFAULT_REMOVE_SPENDING_LIMIT_COST),
+            manage_recovery: HostFunction::fixed(DEFAULT_MANAGE_RECOVERY_COST),
         }
     }
 }
@@ -450,6 +463,9 @@ impl ToBytes for HostFunctionCosts {
         ret.append(&mut self.remove_contract_user_group_urefs.to_bytes()?);
         ret.append(&mut self.print.to_bytes()?);
         ret.append(&mut self.blake2b.to_bytes()?);
+        ret.append(&mut self.set_spending_limit.to_bytes()?);
+        ret.append(&mut self.remove_spending_limit.to_bytes()?);
+        ret.append(&mut self.manage_recovery.to_bytes()?);
         Ok(ret)
     }
 
@@ -496,6 +512,9 @@ impl ToBytes for HostFunctionCosts {
             + self.remove_contract_user_group_urefs.serialized_length()
             + self.print.serialized_length()
             + self.blake2b.serialized_length()
+            + self.set_spending_limit.serialized_length()
+            + self.remove_spending_limit.serialized_length()
+            + self.manage_recovery.serialized_length()
     }
 }
 
@@ -543,6 +562,9 @@ impl FromBytes for HostFunctionCosts {
         let (remove_contract_user_group_urefs, rem) = FromBytes::from_bytes(rem)?;
         let (print, rem) = FromBytes::from_bytes(rem)?;
         let (blake2b, rem) = FromBytes::from_bytes(rem)?;
+        let (set_spending_limit, rem) = FromBytes::from_bytes(rem)?;
+        let (remove_spending_limit, rem) = FromBytes::from_bytes(rem)?;
+        let (manage_recovery, rem) = FromBytes::from_bytes(rem)?;
         Ok((
             HostFunctionCosts {
                 read_value,
@@ -587,6 +609,9 @@ impl FromBytes for HostFunctionCosts {
                 remove_contract_user_group_urefs,
                 print,
                 blake2b,
+                set_spending_limit,
+                remove_spending_limit,
+                manage_recovery,
             },
             rem,
         ))
@@ -638,6 +663,9 @@ impl Distribution<HostFunctionCosts> for Standard {
             remove_contract_user_group_urefs: rng.gen(),
             print: rng.gen(),
             blake2b: rng.gen(),
+            set_spending_limit: rng.gen(),
+            remove_spending_limit: rng.gen(),
+            manage_recovery: rng.gen(),
         }
     }
 }
@@ -697,6 +725,9 @@ pub mod gens {
             remove_contract_user_group_urefs in host_function_cost_arb(),
             print in host_function_cost_arb(),
             blake2b in host_function_cost_arb(),
+            set_spending_limit in host_function_cost_arb(),
+            remove_spending_limit in host_function_cost_arb(),
+            manage_recovery in host_function_cost_arb(),
         ) -> HostFunctionCosts {
             HostFunctionCosts {
                 read_value,
@@ -741,6 +772,9 @@ pub mod gens {
                 remove_contract_user_group_urefs,
                 print,
                 blake2b,
+                set_spending_limit,
+                remove_spending_limit,
+                manage_recovery,
             }
         }
     }
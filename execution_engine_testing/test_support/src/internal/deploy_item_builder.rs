@@ -18,7 +18,9 @@ struct DeployItemData {
     pub session_code: Option<ExecutableDeployItem>,
     pub gas_price: u64,
     pub authorization_keys: BTreeSet<AccountHash>,
+    pub payer: Option<AccountHash>,
     pub deploy_hash: DeployHash,
+    pub dependencies: Vec<DeployHash>,
 }
 
 pub struct DeployItemBuilder {
@@ -225,6 +227,11 @@ impl DeployItemBuilder {
         self
     }
 
+    pub fn with_payer(mut self, payer: AccountHash) -> Self {
+        self.deploy_item.payer = Some(payer);
+        self
+    }
+
     pub fn with_gas_price(mut self, gas_price: u64) -> Self {
         self.deploy_item.gas_price = gas_price;
         self
@@ -236,6 +243,11 @@ impl DeployItemBuilder {
         self
     }
 
+    pub fn with_dependencies(mut self, dependencies: &[DeployHash]) -> Self {
+        self.deploy_item.dependencies = dependencies.to_vec();
+        self
+    }
+
     pub fn build(self) -> DeployItem {
         DeployItem {
             address: self
@@ -252,7 +264,9 @@ impl DeployItemBuilder {
                 .expect("should have payment code"),
             gas_price: self.deploy_item.gas_price,
             authorization_keys: self.deploy_item.authorization_keys,
+            payer: self.deploy_item.payer,
             deploy_hash: self.deploy_item.deploy_hash,
+            dependencies: self.deploy_item.dependencies,
         }
     }
 }
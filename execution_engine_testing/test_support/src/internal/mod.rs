@@ -18,7 +18,11 @@ use casper_execution_engine::{
     shared::{system_config::SystemConfig, wasm_config::WasmConfig},
 };
 use casper_hashing::Digest;
-use casper_types::{account::AccountHash, Motes, ProtocolVersion, PublicKey, SecretKey, U512};
+use casper_types::{
+    account::AccountHash,
+    system::auction::{DelegationRate, DELEGATION_RATE_DENOMINATOR},
+    Motes, ProtocolVersion, PublicKey, SecretKey, U512,
+};
 
 use super::DEFAULT_ACCOUNT_INITIAL_BALANCE;
 
@@ -37,6 +41,16 @@ pub const DEFAULT_AUCTION_DELAY: u64 = 3;
 pub const DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS: u64 = 90 * 24 * 60 * 60 * 1000;
 /// Default number of eras that need to pass to be able to withdraw unbonded funds.
 pub const DEFAULT_UNBONDING_DELAY: u64 = 14;
+/// Default minimum amount a delegator must have delegated to a single validator.
+pub const DEFAULT_MINIMUM_DELEGATION_AMOUNT: u64 = 0;
+/// Default maximum amount a delegator may have delegated to a single validator.
+pub const DEFAULT_MAXIMUM_DELEGATION_AMOUNT: u64 = u64::MAX;
+/// Default maximum number of delegators a single validator may have.
+pub const DEFAULT_MAX_DELEGATORS_PER_VALIDATOR: u32 = u32::MAX;
+/// Default number of eras after which a validator's delegation rate increase takes effect.
+pub const DEFAULT_DELEGATION_RATE_CHANGE_DELAY: u64 = 0;
+/// Default maximum increase of a validator's delegation rate allowed in a single change.
+pub const DEFAULT_MAX_DELEGATION_RATE_INCREASE: DelegationRate = DELEGATION_RATE_DENOMINATOR;
 
 /// Default round seigniorage rate represented as a fractional number.
 ///
@@ -106,6 +120,11 @@ pub static DEFAULT_EXEC_CONFIG: Lazy<ExecConfig> = Lazy::new(|| {
         DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
         DEFAULT_ROUND_SEIGNIORAGE_RATE,
         DEFAULT_UNBONDING_DELAY,
+        DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+        DEFAULT_MAXIMUM_DELEGATION_AMOUNT,
+        DEFAULT_MAX_DELEGATORS_PER_VALIDATOR,
+        DEFAULT_DELEGATION_RATE_CHANGE_DELAY,
+        DEFAULT_MAX_DELEGATION_RATE_INCREASE,
         DEFAULT_GENESIS_TIMESTAMP_MILLIS,
     )
 });
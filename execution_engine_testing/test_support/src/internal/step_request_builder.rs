@@ -3,7 +3,7 @@ use casper_execution_engine::core::engine_state::{
     StepRequest,
 };
 use casper_hashing::Digest;
-use casper_types::{EraId, ProtocolVersion};
+use casper_types::{system::auction::InactivityPolicy, EraId, ProtocolVersion};
 
 #[derive(Debug)]
 pub struct StepRequestBuilder {
@@ -12,6 +12,7 @@ pub struct StepRequestBuilder {
     slash_items: Vec<SlashItem>,
     reward_items: Vec<RewardItem>,
     evict_items: Vec<EvictItem>,
+    inactivity_policy: InactivityPolicy,
     run_auction: bool,
     next_era_id: EraId,
     era_end_timestamp_millis: u64,
@@ -47,6 +48,11 @@ impl StepRequestBuilder {
         self
     }
 
+    pub fn with_inactivity_policy(mut self, inactivity_policy: InactivityPolicy) -> Self {
+        self.inactivity_policy = inactivity_policy;
+        self
+    }
+
     pub fn with_run_auction(mut self, run_auction: bool) -> Self {
         self.run_auction = run_auction;
         self
@@ -69,6 +75,7 @@ impl StepRequestBuilder {
             self.slash_items,
             self.reward_items,
             self.evict_items,
+            self.inactivity_policy,
             self.run_auction,
             self.next_era_id,
             self.era_end_timestamp_millis,
@@ -84,6 +91,7 @@ impl Default for StepRequestBuilder {
             slash_items: Default::default(),
             reward_items: Default::default(),
             evict_items: Default::default(),
+            inactivity_policy: Default::default(),
             run_auction: true, //<-- run_auction by default
             next_era_id: Default::default(),
             era_end_timestamp_millis: Default::default(),
@@ -4,7 +4,7 @@ use num_rational::Ratio;
 
 use casper_execution_engine::core::engine_state::UpgradeConfig;
 use casper_hashing::Digest;
-use casper_types::{EraId, Key, ProtocolVersion, StoredValue};
+use casper_types::{system::auction::DelegationRate, EraId, Key, ProtocolVersion, StoredValue};
 
 #[derive(Default)]
 pub struct UpgradeRequestBuilder {
@@ -17,6 +17,11 @@ pub struct UpgradeRequestBuilder {
     new_locked_funds_period_millis: Option<u64>,
     new_round_seigniorage_rate: Option<Ratio<u64>>,
     new_unbonding_delay: Option<u64>,
+    new_minimum_delegation_amount: Option<u64>,
+    new_maximum_delegation_amount: Option<u64>,
+    new_max_delegators_per_validator: Option<u32>,
+    new_delegation_rate_change_delay: Option<u64>,
+    new_max_delegation_rate_increase: Option<DelegationRate>,
     global_state_update: BTreeMap<Key, StoredValue>,
 }
 
@@ -68,6 +73,40 @@ impl UpgradeRequestBuilder {
         self
     }
 
+    pub fn with_new_minimum_delegation_amount(mut self, minimum_delegation_amount: u64) -> Self {
+        self.new_minimum_delegation_amount = Some(minimum_delegation_amount);
+        self
+    }
+
+    pub fn with_new_maximum_delegation_amount(mut self, maximum_delegation_amount: u64) -> Self {
+        self.new_maximum_delegation_amount = Some(maximum_delegation_amount);
+        self
+    }
+
+    pub fn with_new_max_delegators_per_validator(
+        mut self,
+        max_delegators_per_validator: u32,
+    ) -> Self {
+        self.new_max_delegators_per_validator = Some(max_delegators_per_validator);
+        self
+    }
+
+    pub fn with_new_delegation_rate_change_delay(
+        mut self,
+        delegation_rate_change_delay: u64,
+    ) -> Self {
+        self.new_delegation_rate_change_delay = Some(delegation_rate_change_delay);
+        self
+    }
+
+    pub fn with_new_max_delegation_rate_increase(
+        mut self,
+        max_delegation_rate_increase: DelegationRate,
+    ) -> Self {
+        self.new_max_delegation_rate_increase = Some(max_delegation_rate_increase);
+        self
+    }
+
     pub fn with_global_state_update(
         mut self,
         global_state_update: BTreeMap<Key, StoredValue>,
@@ -92,6 +131,11 @@ impl UpgradeRequestBuilder {
             self.new_locked_funds_period_millis,
             self.new_round_seigniorage_rate,
             self.new_unbonding_delay,
+            self.new_minimum_delegation_amount,
+            self.new_maximum_delegation_amount,
+            self.new_max_delegators_per_validator,
+            self.new_delegation_rate_change_delay,
+            self.new_max_delegation_rate_increase,
             self.global_state_update,
         )
     }
@@ -49,8 +49,9 @@ use casper_types::{
     bytesrepr, runtime_args,
     system::{
         auction::{
-            Bids, EraValidators, UnbondingPurses, ValidatorWeights, ARG_ERA_END_TIMESTAMP_MILLIS,
-            ARG_EVICTED_VALIDATORS, AUCTION_DELAY_KEY, ERA_ID_KEY, METHOD_RUN_AUCTION,
+            Bids, EraValidators, Redelegation, UnbondingPurses, ValidatorWeights,
+            ARG_ERA_END_TIMESTAMP_MILLIS, ARG_EVICTED_VALIDATORS, AUCTION_DELAY_KEY, ERA_ID_KEY,
+            METHOD_RUN_AUCTION,
         },
         mint::TOTAL_SUPPLY_KEY,
         AUCTION, HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
@@ -943,6 +944,36 @@ where
         ret
     }
 
+    pub fn get_redelegations(&mut self) -> BTreeMap<AccountHash, Vec<Redelegation>> {
+        let correlation_id = CorrelationId::new();
+        let state_root_hash = self.get_post_state_hash();
+
+        let tracking_copy = self
+            .engine_state
+            .tracking_copy(state_root_hash)
+            .unwrap()
+            .unwrap();
+
+        let reader = tracking_copy.reader();
+
+        let redelegation_keys = reader
+            .keys_with_prefix(correlation_id, &[KeyTag::Redelegation as u8])
+            .unwrap_or_default();
+
+        let mut ret = BTreeMap::new();
+
+        for key in redelegation_keys.into_iter() {
+            let read_result = reader.read(correlation_id, &key);
+            if let (Key::Redelegation(account_hash), Ok(Some(StoredValue::CLValue(cl_value)))) =
+                (key, read_result)
+            {
+                ret.insert(account_hash, cl_value.into_t().unwrap());
+            }
+        }
+
+        ret
+    }
+
     pub fn get_value<T>(&mut self, contract_hash: ContractHash, name: &str) -> T
     where
         T: FromBytes + CLTyped,
@@ -2,3 +2,5 @@ mod associated_keys;
 mod authorized_keys;
 mod key_management_thresholds;
 mod named_keys;
+mod recovery;
+mod spending_limits;
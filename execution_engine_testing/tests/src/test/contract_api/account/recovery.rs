@@ -0,0 +1,209 @@
+use casper_engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::core::{
+    engine_state::{Error, ExecuteRequest},
+    execution,
+};
+use casper_types::{account::AccountHash, runtime_args, ApiError, RuntimeArgs, U512};
+
+const CONTRACT_MANAGE_RECOVERY: &str = "manage_recovery.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ARG_ACTION: &str = "action";
+const ARG_ACCOUNT: &str = "account";
+const ARG_DELAY: &str = "delay";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+const ACTION_CONFIGURE: &str = "configure";
+const ACTION_START: &str = "start";
+const ACTION_CANCEL: &str = "cancel";
+const RECOVERY_DELAY: u64 = 1_000;
+
+fn manage_recovery_request(
+    account: AccountHash,
+    authorization_key: AccountHash,
+    args: RuntimeArgs,
+    block_time: u64,
+    deploy_hash: [u8; 32],
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(account)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_session_code(CONTRACT_MANAGE_RECOVERY, args)
+        .with_authorization_keys(&[authorization_key])
+        .with_deploy_hash(deploy_hash)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy)
+        .with_block_time(block_time)
+        .build()
+}
+
+/// Funds account 1, makes the default account its recovery key and starts a recovery of
+/// account 1 at block time 0 which hands it over to the default account.
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => *DEFAULT_PAYMENT * 10 },
+    )
+    .build();
+    let configure_request = manage_recovery_request(
+        ACCOUNT_1_ADDR,
+        ACCOUNT_1_ADDR,
+        runtime_args! {
+            ARG_ACTION => ACTION_CONFIGURE,
+            ARG_ACCOUNT => *DEFAULT_ACCOUNT_ADDR,
+            ARG_DELAY => RECOVERY_DELAY,
+        },
+        0,
+        [1u8; 32],
+    );
+    let start_request = manage_recovery_request(
+        *DEFAULT_ACCOUNT_ADDR,
+        *DEFAULT_ACCOUNT_ADDR,
+        runtime_args! { ARG_ACTION => ACTION_START, ARG_ACCOUNT => ACCOUNT_1_ADDR },
+        0,
+        [2u8; 32],
+    );
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(fund_request)
+        .expect_success()
+        .commit()
+        .exec(configure_request)
+        .expect_success()
+        .commit()
+        .exec(start_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn transfer_request(
+    authorization_key: AccountHash,
+    block_time: u64,
+    deploy_hash: [u8; 32],
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(ACCOUNT_1_ADDR)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => *DEFAULT_ACCOUNT_ADDR, ARG_AMOUNT => U512::one() },
+        )
+        .with_authorization_keys(&[authorization_key])
+        .with_deploy_hash(deploy_hash)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy)
+        .with_block_time(block_time)
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_start_recovery() {
+    let builder = setup();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account");
+    let pending_recovery = account_1
+        .pending_recovery()
+        .expect("should have pending recovery");
+
+    assert_eq!(pending_recovery.effective_at(), RECOVERY_DELAY);
+    assert!(pending_recovery
+        .request()
+        .associated_keys()
+        .contains_key(&DEFAULT_ACCOUNT_ADDR));
+    assert!(account_1.associated_keys().contains_key(&ACCOUNT_1_ADDR));
+}
+
+#[ignore]
+#[test]
+fn should_apply_recovery_after_delay() {
+    let mut builder = setup();
+
+    // The recovery hasn't taken effect yet, so the default account can't use account 1.
+    let exec_request = transfer_request(*DEFAULT_ACCOUNT_ADDR, RECOVERY_DELAY - 1, [3u8; 32]);
+    builder.exec(exec_request).commit();
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, Error::Authorization),
+        "expected authorization error, got {:?}",
+        error
+    );
+
+    let exec_request = transfer_request(*DEFAULT_ACCOUNT_ADDR, RECOVERY_DELAY, [4u8; 32]);
+    builder.exec(exec_request).expect_success().commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account");
+    assert!(account_1.pending_recovery().is_none());
+    assert!(account_1
+        .associated_keys()
+        .contains_key(&DEFAULT_ACCOUNT_ADDR));
+    assert!(!account_1.associated_keys().contains_key(&ACCOUNT_1_ADDR));
+}
+
+#[ignore]
+#[test]
+fn should_cancel_pending_recovery() {
+    let mut builder = setup();
+
+    let cancel_request = manage_recovery_request(
+        ACCOUNT_1_ADDR,
+        ACCOUNT_1_ADDR,
+        runtime_args! { ARG_ACTION => ACTION_CANCEL },
+        RECOVERY_DELAY - 1,
+        [3u8; 32],
+    );
+    builder.exec(cancel_request).expect_success().commit();
+
+    let exec_request = transfer_request(ACCOUNT_1_ADDR, RECOVERY_DELAY, [4u8; 32]);
+    builder.exec(exec_request).expect_success().commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account");
+    assert!(account_1.pending_recovery().is_none());
+    assert!(account_1.associated_keys().contains_key(&ACCOUNT_1_ADDR));
+    assert!(!account_1
+        .associated_keys()
+        .contains_key(&DEFAULT_ACCOUNT_ADDR));
+}
+
+#[ignore]
+#[test]
+fn should_not_start_recovery_without_recovery_key() {
+    let mut builder = setup();
+
+    // Account 1 isn't its own recovery key.
+    let start_request = manage_recovery_request(
+        ACCOUNT_1_ADDR,
+        ACCOUNT_1_ADDR,
+        runtime_args! { ARG_ACTION => ACTION_START, ARG_ACCOUNT => ACCOUNT_1_ADDR },
+        0,
+        [3u8; 32],
+    );
+    builder.exec(start_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            Error::Exec(execution::Error::Revert(ApiError::PermissionDenied))
+        ),
+        "expected permission denied, got {:?}",
+        error
+    );
+}
@@ -0,0 +1,226 @@
+use casper_engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::core::{
+    engine_state::{Error, ExecuteRequest},
+    execution,
+};
+use casper_types::{
+    account::{AccountHash, SpendingWindow},
+    runtime_args,
+    system::mint,
+    ApiError, RuntimeArgs, U512,
+};
+
+const CONTRACT_ADD_UPDATE_ASSOCIATED_KEY: &str = "add_update_associated_key.wasm";
+const CONTRACT_SET_SPENDING_LIMIT: &str = "set_spending_limit.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ACCOUNT_2_ADDR: AccountHash = AccountHash::new([2u8; 32]);
+const ARG_ACCOUNT: &str = "account";
+const ARG_LIMIT: &str = "limit";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+const SPENDING_LIMIT: u64 = 5_000_000_000_000;
+
+/// Funds account 1, associates the default account's key with it and limits that key.
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => *DEFAULT_PAYMENT * 10 },
+    )
+    .build();
+    let add_key_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
+        runtime_args! { ARG_ACCOUNT => *DEFAULT_ACCOUNT_ADDR },
+    )
+    .build();
+    let set_limit_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_SET_SPENDING_LIMIT,
+        runtime_args! {
+            ARG_ACCOUNT => *DEFAULT_ACCOUNT_ADDR,
+            ARG_LIMIT => Some(U512::from(SPENDING_LIMIT)),
+        },
+    )
+    .build();
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(fund_request)
+        .expect_success()
+        .commit()
+        .exec(add_key_request)
+        .expect_success()
+        .commit()
+        .exec(set_limit_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn transfer_request(
+    authorization_key: AccountHash,
+    amount: U512,
+    deploy_hash: [u8; 32],
+) -> ExecuteRequest {
+    transfer_request_with_payment(authorization_key, amount, *DEFAULT_PAYMENT, deploy_hash)
+}
+
+fn transfer_request_with_payment(
+    authorization_key: AccountHash,
+    amount: U512,
+    payment: U512,
+    deploy_hash: [u8; 32],
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(ACCOUNT_1_ADDR)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => payment })
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => ACCOUNT_2_ADDR, ARG_AMOUNT => amount },
+        )
+        .with_authorization_keys(&[authorization_key])
+        .with_deploy_hash(deploy_hash)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy).build()
+}
+
+#[ignore]
+#[test]
+fn should_set_spending_limit() {
+    let builder = setup();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account");
+    let spending_limit = account_1
+        .spending_limits()
+        .get(&DEFAULT_ACCOUNT_ADDR)
+        .expect("should have spending limit");
+
+    assert_eq!(spending_limit.limit(), U512::from(SPENDING_LIMIT));
+    assert_eq!(spending_limit.window(), SpendingWindow::Era);
+    assert!(spending_limit.spent().is_zero());
+}
+
+#[ignore]
+#[test]
+fn should_allow_limited_key_to_spend_up_to_limit() {
+    let mut builder = setup();
+
+    // The payment of both deploys counts towards the limit as well.
+    let amount = U512::from(SPENDING_LIMIT) - *DEFAULT_PAYMENT * 2;
+    let exec_request = transfer_request(*DEFAULT_ACCOUNT_ADDR, amount, [3u8; 32]);
+    builder.exec(exec_request).expect_success().commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account");
+    let spending_limit = account_1
+        .spending_limits()
+        .get(&DEFAULT_ACCOUNT_ADDR)
+        .expect("should have spending limit");
+    assert_eq!(spending_limit.spent(), amount + *DEFAULT_PAYMENT);
+
+    let exec_request = transfer_request(*DEFAULT_ACCOUNT_ADDR, U512::one(), [4u8; 32]);
+    builder.exec(exec_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            Error::Exec(execution::Error::Revert(ApiError::Mint(mint_error)))
+            if mint_error == mint::Error::SpendingLimitExceeded as u8
+        ),
+        "expected spending limit error, got {:?}",
+        error
+    );
+}
+
+#[ignore]
+#[test]
+fn should_limit_payment_of_limited_key() {
+    let mut builder = setup();
+
+    let exec_request = transfer_request_with_payment(
+        *DEFAULT_ACCOUNT_ADDR,
+        U512::one(),
+        U512::from(SPENDING_LIMIT + 1),
+        [3u8; 32],
+    );
+    builder.exec(exec_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            Error::Exec(execution::Error::Revert(ApiError::Mint(mint_error)))
+            if mint_error == mint::Error::SpendingLimitExceeded as u8
+        ),
+        "expected spending limit error, got {:?}",
+        error
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_limit_unlimited_key() {
+    let mut builder = setup();
+
+    let exec_request = transfer_request(ACCOUNT_1_ADDR, U512::from(SPENDING_LIMIT + 1), [3u8; 32]);
+    builder.exec(exec_request).expect_success().commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account");
+    let spending_limit = account_1
+        .spending_limits()
+        .get(&DEFAULT_ACCOUNT_ADDR)
+        .expect("should have spending limit");
+    assert!(spending_limit.spent().is_zero());
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_limited_key_to_remove_its_limit() {
+    let mut builder = setup();
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(ACCOUNT_1_ADDR)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_session_code(
+            CONTRACT_SET_SPENDING_LIMIT,
+            runtime_args! { ARG_ACCOUNT => *DEFAULT_ACCOUNT_ADDR, ARG_LIMIT => None::<U512> },
+        )
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([3u8; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+    builder.exec(exec_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            Error::Exec(execution::Error::Revert(ApiError::PermissionDenied))
+        ),
+        "expected permission denied, got {:?}",
+        error
+    );
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account");
+    assert!(account_1
+        .spending_limits()
+        .contains_key(&DEFAULT_ACCOUNT_ADDR));
+}
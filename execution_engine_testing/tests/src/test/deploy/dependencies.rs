@@ -0,0 +1,111 @@
+use assert_matches::assert_matches;
+
+use casper_engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::core::engine_state::{Error, ExecuteRequest};
+use casper_types::{
+    account::AccountHash, runtime_args, DeployHash, Key, RuntimeArgs, StoredValue, U512,
+};
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const ARG_AMOUNT: &str = "amount";
+const ARG_TARGET: &str = "target";
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const TRANSFER_PURSE_TO_ACCOUNT_WASM: &str = "transfer_purse_to_account.wasm";
+
+const DEPENDENCY_HASH: [u8; 32] = [1; 32];
+const DEPENDENT_HASH: [u8; 32] = [2; 32];
+
+fn transfer_request(deploy_hash: [u8; 32], amount: U512) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(*DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(
+            TRANSFER_PURSE_TO_ACCOUNT_WASM,
+            runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => amount },
+        )
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+fn dependent_request() -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(*DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(DEPENDENT_HASH)
+        .with_dependencies(&[DeployHash::new(DEPENDENCY_HASH)])
+        .build();
+
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_with_successful_dependency() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    builder
+        .exec(transfer_request(DEPENDENCY_HASH, U512::one()))
+        .expect_success()
+        .commit();
+
+    builder.exec(dependent_request()).expect_success().commit();
+
+    let deploy_info = builder
+        .query(None, Key::DeployInfo(DeployHash::new(DEPENDENT_HASH)), &[])
+        .expect("should have deploy info for the dependent deploy");
+    assert_matches!(deploy_info, StoredValue::DeployInfo(_));
+}
+
+#[ignore]
+#[test]
+fn should_not_execute_deploy_with_missing_dependency() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    builder.exec(dependent_request()).commit();
+
+    let response = builder
+        .get_exec_result(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::DependencyFailed(deploy_hash) if *deploy_hash == DeployHash::new(DEPENDENCY_HASH)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_execute_deploy_with_failed_dependency() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // Transferring more than the account holds makes the dependency's session fail.
+    builder
+        .exec(transfer_request(DEPENDENCY_HASH, U512::MAX))
+        .commit();
+    assert!(builder.is_error(), "dependency should have failed");
+
+    builder.exec(dependent_request()).commit();
+
+    let response = builder
+        .get_exec_result(1)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(
+        precondition_failure,
+        Error::DependencyFailed(deploy_hash) if *deploy_hash == DeployHash::new(DEPENDENCY_HASH)
+    );
+}
@@ -1,5 +1,7 @@
 mod context_association;
+mod dependencies;
 mod non_standard_payment;
+mod payer;
 mod preconditions;
 mod receipts;
 mod stored_contracts;
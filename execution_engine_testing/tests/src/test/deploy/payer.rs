@@ -0,0 +1,140 @@
+use assert_matches::assert_matches;
+
+use casper_engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR, MINIMUM_ACCOUNT_CREATION_BALANCE,
+};
+use casper_execution_engine::core::engine_state::Error;
+use casper_types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let setup_exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE)
+        },
+    )
+    .build();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder.exec(setup_exec_request).expect_success().commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_charge_separate_payer() {
+    let mut builder = setup();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1");
+    let default_account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+
+    let account_1_starting_balance = builder.get_purse_balance(account_1.main_purse());
+    let payer_starting_balance = builder.get_purse_balance(default_account.main_purse());
+
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(ACCOUNT_1_ADDR)
+            .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_payer(*DEFAULT_ACCOUNT_ADDR)
+            .with_authorization_keys(&[ACCOUNT_1_ADDR, *DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash([2; 32])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let proposer_reward_starting_balance = builder.get_proposer_purse_balance();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let transaction_fee = builder.get_proposer_purse_balance() - proposer_reward_starting_balance;
+
+    assert_eq!(
+        builder.get_purse_balance(account_1.main_purse()),
+        account_1_starting_balance,
+        "sender's balance should be untouched"
+    );
+    assert_eq!(
+        builder.get_purse_balance(default_account.main_purse()),
+        payer_starting_balance - transaction_fee,
+        "payer should be charged for the deploy"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_authorization_failure_unsigned_payer() {
+    let mut builder = setup();
+
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(ACCOUNT_1_ADDR)
+            .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_payer(*DEFAULT_ACCOUNT_ADDR)
+            // payer's key is missing
+            .with_authorization_keys(&[ACCOUNT_1_ADDR])
+            .with_deploy_hash([2; 32])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_result(1)
+        .expect("there should be a response");
+
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(precondition_failure, Error::Authorization);
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_authorization_failure_unrelated_key_with_payer() {
+    let mut builder = setup();
+    let unrelated_key = AccountHash::new([99u8; 32]);
+
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(ACCOUNT_1_ADDR)
+            .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_payer(*DEFAULT_ACCOUNT_ADDR)
+            // a key associated with neither the sender nor the payer
+            .with_authorization_keys(&[ACCOUNT_1_ADDR, *DEFAULT_ACCOUNT_ADDR, unrelated_key])
+            .with_deploy_hash([2; 32])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_result(1)
+        .expect("there should be a response");
+
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_matches!(precondition_failure, Error::Authorization);
+}
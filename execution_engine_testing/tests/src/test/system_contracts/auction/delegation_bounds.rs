@@ -0,0 +1,292 @@
+use num_traits::Zero;
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_AUCTION_DELAY,
+        DEFAULT_DELEGATION_RATE_CHANGE_DELAY, DEFAULT_GENESIS_CONFIG_HASH,
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS, DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
+        DEFAULT_MAX_DELEGATION_RATE_INCREASE, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_SYSTEM_CONFIG, DEFAULT_UNBONDING_DELAY,
+        DEFAULT_VALIDATOR_SLOTS, DEFAULT_WASM_CONFIG,
+    },
+    DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use casper_execution_engine::core::{
+    engine_state::{
+        self,
+        genesis::{ExecConfig, GenesisAccount, GenesisValidator},
+        run_genesis_request::RunGenesisRequest,
+    },
+    execution,
+};
+use casper_types::{
+    api_error::ApiError,
+    runtime_args,
+    system::auction::{self, Bids, DelegationRate, ARG_AMOUNT, ARG_DELEGATOR, ARG_VALIDATOR},
+    Motes, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+const CONTRACT_DELEGATE: &str = "delegate.wasm";
+const CONTRACT_UNDELEGATE: &str = "undelegate.wasm";
+
+const VALIDATOR_1_STAKE: u64 = 1_000_000;
+const MINIMUM_DELEGATION_AMOUNT: u64 = 100_000;
+const MAXIMUM_DELEGATION_AMOUNT: u64 = 1_000_000;
+const MAX_DELEGATORS_PER_VALIDATOR: u32 = 1;
+
+static VALIDATOR_1: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([3; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static DELEGATOR_1: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([205; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static DELEGATOR_2: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([206; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let validator_1 = GenesisAccount::account(
+            VALIDATOR_1.clone(),
+            Motes::new(VALIDATOR_1_STAKE.into()),
+            Some(GenesisValidator::new(
+                Motes::new(VALIDATOR_1_STAKE.into()),
+                DelegationRate::zero(),
+            )),
+        );
+        let delegator_1 = GenesisAccount::account(
+            DELEGATOR_1.clone(),
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            None,
+        );
+        let delegator_2 = GenesisAccount::account(
+            DELEGATOR_2.clone(),
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            None,
+        );
+        tmp.push(validator_1);
+        tmp.push(delegator_1);
+        tmp.push(delegator_2);
+        tmp
+    };
+
+    let exec_config = ExecConfig::new(
+        accounts,
+        *DEFAULT_WASM_CONFIG,
+        *DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_VALIDATOR_SLOTS,
+        DEFAULT_AUCTION_DELAY,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
+        DEFAULT_ROUND_SEIGNIORAGE_RATE,
+        DEFAULT_UNBONDING_DELAY,
+        MINIMUM_DELEGATION_AMOUNT,
+        MAXIMUM_DELEGATION_AMOUNT,
+        MAX_DELEGATORS_PER_VALIDATOR,
+        DEFAULT_DELEGATION_RATE_CHANGE_DELAY,
+        DEFAULT_MAX_DELEGATION_RATE_INCREASE,
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn delegate<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    delegator: &PublicKey,
+    amount: u64,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let delegate_request = ExecuteRequestBuilder::standard(
+        delegator.to_account_hash(),
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(amount),
+            ARG_VALIDATOR => VALIDATOR_1.clone(),
+            ARG_DELEGATOR => delegator.clone(),
+        },
+    )
+    .build();
+    builder.exec(delegate_request).commit()
+}
+
+fn undelegate<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    delegator: &PublicKey,
+    amount: u64,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let undelegate_request = ExecuteRequestBuilder::standard(
+        delegator.to_account_hash(),
+        CONTRACT_UNDELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(amount),
+            ARG_VALIDATOR => VALIDATOR_1.clone(),
+            ARG_DELEGATOR => delegator.clone(),
+        },
+    )
+    .build();
+    builder.exec(undelegate_request).commit()
+}
+
+fn assert_auction_error(builder: &InMemoryWasmTestBuilder, expected: auction::Error) {
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+            if auction_error == expected as u8
+        ),
+        "expected {:?}, got {:?}",
+        expected,
+        error
+    );
+}
+
+fn delegated_amount(builder: &mut InMemoryWasmTestBuilder, delegator: &PublicKey) -> Option<U512> {
+    let bids: Bids = builder.get_bids();
+    bids[&*VALIDATOR_1]
+        .delegators()
+        .get(delegator)
+        .map(|delegator| *delegator.staked_amount())
+}
+
+#[ignore]
+#[test]
+fn should_not_delegate_less_than_minimum_delegation_amount() {
+    let mut builder = setup();
+
+    delegate(&mut builder, &DELEGATOR_1, MINIMUM_DELEGATION_AMOUNT - 1);
+    assert_auction_error(&builder, auction::Error::DelegationAmountTooSmall);
+    assert_eq!(delegated_amount(&mut builder, &DELEGATOR_1), None);
+
+    delegate(&mut builder, &DELEGATOR_1, MINIMUM_DELEGATION_AMOUNT).expect_success();
+    assert_eq!(
+        delegated_amount(&mut builder, &DELEGATOR_1),
+        Some(U512::from(MINIMUM_DELEGATION_AMOUNT))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_delegate_more_than_maximum_delegation_amount() {
+    let mut builder = setup();
+
+    delegate(&mut builder, &DELEGATOR_1, MAXIMUM_DELEGATION_AMOUNT + 1);
+    assert_auction_error(&builder, auction::Error::DelegationAmountTooLarge);
+
+    delegate(&mut builder, &DELEGATOR_1, MAXIMUM_DELEGATION_AMOUNT).expect_success();
+
+    // A top-up is checked against the resulting stake, not the amount added.
+    delegate(&mut builder, &DELEGATOR_1, MINIMUM_DELEGATION_AMOUNT);
+    assert_auction_error(&builder, auction::Error::DelegationAmountTooLarge);
+    assert_eq!(
+        delegated_amount(&mut builder, &DELEGATOR_1),
+        Some(U512::from(MAXIMUM_DELEGATION_AMOUNT))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_exceed_max_delegators_per_validator() {
+    let mut builder = setup();
+
+    delegate(&mut builder, &DELEGATOR_1, MINIMUM_DELEGATION_AMOUNT).expect_success();
+
+    delegate(&mut builder, &DELEGATOR_2, MINIMUM_DELEGATION_AMOUNT);
+    assert_auction_error(&builder, auction::Error::ExceededDelegatorSizeLimit);
+    assert_eq!(delegated_amount(&mut builder, &DELEGATOR_2), None);
+
+    // Existing delegators can still top up their stake.
+    delegate(&mut builder, &DELEGATOR_1, MINIMUM_DELEGATION_AMOUNT).expect_success();
+    assert_eq!(
+        delegated_amount(&mut builder, &DELEGATOR_1),
+        Some(U512::from(2 * MINIMUM_DELEGATION_AMOUNT))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_undelegate_below_minimum_delegation_amount() {
+    let mut builder = setup();
+
+    delegate(&mut builder, &DELEGATOR_1, 2 * MINIMUM_DELEGATION_AMOUNT).expect_success();
+
+    undelegate(&mut builder, &DELEGATOR_1, MINIMUM_DELEGATION_AMOUNT + 1);
+    assert_auction_error(&builder, auction::Error::DelegationAmountTooSmall);
+
+    undelegate(&mut builder, &DELEGATOR_1, MINIMUM_DELEGATION_AMOUNT).expect_success();
+    assert_eq!(
+        delegated_amount(&mut builder, &DELEGATOR_1),
+        Some(U512::from(MINIMUM_DELEGATION_AMOUNT))
+    );
+
+    // Withdrawing the whole stake is always allowed.
+    undelegate(&mut builder, &DELEGATOR_1, MINIMUM_DELEGATION_AMOUNT).expect_success();
+    assert_eq!(delegated_amount(&mut builder, &DELEGATOR_1), None);
+}
+
+#[ignore]
+#[test]
+fn should_not_run_genesis_with_too_many_delegators() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        tmp.push(GenesisAccount::account(
+            VALIDATOR_1.clone(),
+            Motes::new(VALIDATOR_1_STAKE.into()),
+            Some(GenesisValidator::new(
+                Motes::new(VALIDATOR_1_STAKE.into()),
+                DelegationRate::zero(),
+            )),
+        ));
+        for delegator in [&*DELEGATOR_1, &*DELEGATOR_2] {
+            tmp.push(GenesisAccount::delegator(
+                VALIDATOR_1.clone(),
+                delegator.clone(),
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+                Motes::new(MINIMUM_DELEGATION_AMOUNT.into()),
+            ));
+        }
+        tmp
+    };
+
+    let exec_config = ExecConfig::new(
+        accounts,
+        *DEFAULT_WASM_CONFIG,
+        *DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_VALIDATOR_SLOTS,
+        DEFAULT_AUCTION_DELAY,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
+        DEFAULT_ROUND_SEIGNIORAGE_RATE,
+        DEFAULT_UNBONDING_DELAY,
+        MINIMUM_DELEGATION_AMOUNT,
+        MAXIMUM_DELEGATION_AMOUNT,
+        MAX_DELEGATORS_PER_VALIDATOR,
+        DEFAULT_DELEGATION_RATE_CHANGE_DELAY,
+        DEFAULT_MAX_DELEGATION_RATE_INCREASE,
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+    );
+
+    let builder = InMemoryWasmTestBuilder::default();
+    let result = builder.get_engine_state().commit_genesis(
+        Default::default(),
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        &exec_config,
+    );
+
+    assert!(matches!(
+        result,
+        Err(engine_state::Error::Genesis(genesis_error))
+        if matches!(*genesis_error, engine_state::genesis::GenesisError::ExceededDelegatorSizeLimit { .. })
+    ));
+}
@@ -0,0 +1,211 @@
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_AUCTION_DELAY,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_MAXIMUM_DELEGATION_AMOUNT,
+        DEFAULT_MAX_DELEGATORS_PER_VALIDATOR, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_UNBONDING_DELAY, DEFAULT_VALIDATOR_SLOTS, DEFAULT_WASM_CONFIG,
+    },
+    DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use casper_execution_engine::core::{
+    engine_state::{
+        self,
+        genesis::{ExecConfig, GenesisAccount, GenesisValidator},
+        run_genesis_request::RunGenesisRequest,
+    },
+    execution,
+};
+use casper_types::{
+    api_error::ApiError,
+    runtime_args,
+    system::auction::{
+        self, Bid, Bids, DelegationRate, DelegationRateChange, ARG_AMOUNT, ARG_DELEGATION_RATE,
+        ARG_PUBLIC_KEY,
+    },
+    EraId, Motes, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+const CONTRACT_ADD_BID: &str = "add_bid.wasm";
+
+const VALIDATOR_1_STAKE: u64 = 1_000_000;
+const ADD_BID_AMOUNT: u64 = 1_000;
+const INITIAL_DELEGATION_RATE: DelegationRate = 10;
+const DELEGATION_RATE_CHANGE_DELAY: u64 = 2;
+const MAX_DELEGATION_RATE_INCREASE: DelegationRate = 20;
+
+const ERA_END_TIMESTAMP_MILLIS: u64 =
+    DEFAULT_GENESIS_TIMESTAMP_MILLIS + DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS;
+
+static VALIDATOR_1: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([3; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        tmp.push(GenesisAccount::account(
+            VALIDATOR_1.clone(),
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            Some(GenesisValidator::new(
+                Motes::new(VALIDATOR_1_STAKE.into()),
+                INITIAL_DELEGATION_RATE,
+            )),
+        ));
+        tmp
+    };
+
+    let exec_config = ExecConfig::new(
+        accounts,
+        *DEFAULT_WASM_CONFIG,
+        *DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_VALIDATOR_SLOTS,
+        DEFAULT_AUCTION_DELAY,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
+        DEFAULT_ROUND_SEIGNIORAGE_RATE,
+        DEFAULT_UNBONDING_DELAY,
+        DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+        DEFAULT_MAXIMUM_DELEGATION_AMOUNT,
+        DEFAULT_MAX_DELEGATORS_PER_VALIDATOR,
+        DELEGATION_RATE_CHANGE_DELAY,
+        MAX_DELEGATION_RATE_INCREASE,
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn add_bid(
+    builder: &mut InMemoryWasmTestBuilder,
+    delegation_rate: DelegationRate,
+) -> &mut InMemoryWasmTestBuilder {
+    let add_bid_request = ExecuteRequestBuilder::standard(
+        VALIDATOR_1.to_account_hash(),
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => VALIDATOR_1.clone(),
+            ARG_AMOUNT => U512::from(ADD_BID_AMOUNT),
+            ARG_DELEGATION_RATE => delegation_rate,
+        },
+    )
+    .build();
+    builder.exec(add_bid_request).commit()
+}
+
+fn get_bid(builder: &mut InMemoryWasmTestBuilder) -> Bid {
+    let mut bids: Bids = builder.get_bids();
+    bids.remove(&*VALIDATOR_1).expect("should have bid")
+}
+
+fn assert_auction_error(builder: &InMemoryWasmTestBuilder, expected: auction::Error) {
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+            if auction_error == expected as u8
+        ),
+        "expected {:?}, got {:?}",
+        expected,
+        error
+    );
+}
+
+#[ignore]
+#[test]
+fn should_apply_delegation_rate_increase_after_delay() {
+    let mut builder = setup();
+
+    let new_delegation_rate = INITIAL_DELEGATION_RATE + MAX_DELEGATION_RATE_INCREASE;
+    add_bid(&mut builder, new_delegation_rate).expect_success();
+
+    let bid = get_bid(&mut builder);
+    assert_eq!(*bid.delegation_rate(), INITIAL_DELEGATION_RATE);
+    assert_eq!(
+        bid.pending_delegation_rate_change(),
+        Some(&DelegationRateChange::new(
+            new_delegation_rate,
+            EraId::new(DELEGATION_RATE_CHANGE_DELAY)
+        ))
+    );
+
+    builder.run_auction(ERA_END_TIMESTAMP_MILLIS, Vec::new());
+    assert_eq!(
+        *get_bid(&mut builder).delegation_rate(),
+        INITIAL_DELEGATION_RATE
+    );
+
+    builder.run_auction(ERA_END_TIMESTAMP_MILLIS + 1, Vec::new());
+    let bid = get_bid(&mut builder);
+    assert_eq!(*bid.delegation_rate(), new_delegation_rate);
+    assert_eq!(bid.pending_delegation_rate_change(), None);
+}
+
+#[ignore]
+#[test]
+fn should_not_increase_delegation_rate_more_than_maximum() {
+    let mut builder = setup();
+
+    add_bid(
+        &mut builder,
+        INITIAL_DELEGATION_RATE + MAX_DELEGATION_RATE_INCREASE + 1,
+    );
+    assert_auction_error(&builder, auction::Error::DelegationRateIncreaseTooLarge);
+
+    let bid = get_bid(&mut builder);
+    assert_eq!(*bid.delegation_rate(), INITIAL_DELEGATION_RATE);
+    assert_eq!(bid.pending_delegation_rate_change(), None);
+}
+
+#[ignore]
+#[test]
+fn should_decrease_delegation_rate_immediately() {
+    let mut builder = setup();
+
+    add_bid(&mut builder, INITIAL_DELEGATION_RATE + 1).expect_success();
+
+    // Lowering the rate cancels the pending increase.
+    add_bid(&mut builder, INITIAL_DELEGATION_RATE - 1).expect_success();
+
+    let bid = get_bid(&mut builder);
+    assert_eq!(*bid.delegation_rate(), INITIAL_DELEGATION_RATE - 1);
+    assert_eq!(bid.pending_delegation_rate_change(), None);
+}
+
+#[ignore]
+#[test]
+fn should_keep_activation_era_of_repeated_delegation_rate_increase() {
+    let mut builder = setup();
+
+    let new_delegation_rate = INITIAL_DELEGATION_RATE + 1;
+    add_bid(&mut builder, new_delegation_rate).expect_success();
+
+    builder.run_auction(ERA_END_TIMESTAMP_MILLIS, Vec::new());
+
+    // Topping up the stake with the pending rate doesn't restart the delay.
+    add_bid(&mut builder, new_delegation_rate).expect_success();
+    assert_eq!(
+        get_bid(&mut builder).pending_delegation_rate_change(),
+        Some(&DelegationRateChange::new(
+            new_delegation_rate,
+            EraId::new(DELEGATION_RATE_CHANGE_DELAY)
+        ))
+    );
+
+    builder.run_auction(ERA_END_TIMESTAMP_MILLIS + 1, Vec::new());
+    assert_eq!(
+        *get_bid(&mut builder).delegation_rate(),
+        new_delegation_rate
+    );
+}
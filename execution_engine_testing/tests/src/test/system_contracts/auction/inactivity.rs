@@ -0,0 +1,183 @@
+use num_rational::Ratio;
+use num_traits::Zero;
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    internal::{utils, InMemoryWasmTestBuilder, StepRequestBuilder, DEFAULT_ACCOUNTS},
+    DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use casper_execution_engine::core::engine_state::{
+    genesis::{GenesisAccount, GenesisValidator},
+    RewardItem,
+};
+use casper_types::{
+    system::auction::{
+        Bids, DelegationRate, EraInfo, InactivityPolicy, InactivityRecord, INITIAL_ERA_ID,
+    },
+    EraId, Key, Motes, ProtocolVersion, PublicKey, SecretKey,
+};
+
+const VALIDATOR_1_STAKE: u64 = 1_000_000;
+const VALIDATOR_2_STAKE: u64 = 2_000_000;
+
+/// Reward factors for which validator 1 participates a quarter as much as validator 2, relative to
+/// their stakes.
+const PARTIAL_REWARD_FACTORS: (u64, u64) = (100_000_000_000, 800_000_000_000);
+/// Reward factors for which both validators participate equally, relative to their stakes.
+const FULL_REWARD_FACTORS: (u64, u64) = (300_000_000_000, 600_000_000_000);
+
+static VALIDATOR_1: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([3; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static VALIDATOR_2: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([5; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static INACTIVITY_POLICY: Lazy<InactivityPolicy> =
+    Lazy::new(|| InactivityPolicy::new(Ratio::new(1, 2), Ratio::new(1, 4), 3));
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for (public_key, stake) in [
+            (VALIDATOR_1.clone(), VALIDATOR_1_STAKE),
+            (VALIDATOR_2.clone(), VALIDATOR_2_STAKE),
+        ] {
+            tmp.push(GenesisAccount::account(
+                public_key,
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+                Some(GenesisValidator::new(
+                    Motes::new(stake.into()),
+                    DelegationRate::zero(),
+                )),
+            ));
+        }
+        tmp
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+    builder
+}
+
+fn step(
+    builder: &mut InMemoryWasmTestBuilder,
+    era_id: EraId,
+    (validator_1_factor, validator_2_factor): (u64, u64),
+) {
+    let step_request = StepRequestBuilder::new()
+        .with_parent_state_hash(builder.get_post_state_hash())
+        .with_protocol_version(ProtocolVersion::V1_0_0)
+        .with_reward_item(RewardItem::new(VALIDATOR_1.clone(), validator_1_factor))
+        .with_reward_item(RewardItem::new(VALIDATOR_2.clone(), validator_2_factor))
+        .with_inactivity_policy(*INACTIVITY_POLICY)
+        .with_next_era_id(era_id.successor())
+        .build();
+    builder.step(step_request);
+}
+
+fn get_era_info(builder: &InMemoryWasmTestBuilder, era_id: EraId) -> EraInfo {
+    builder
+        .query(None, Key::EraInfo(era_id), &[])
+        .expect("should have era info")
+        .as_era_info()
+        .cloned()
+        .expect("should be era info")
+}
+
+fn get_inactivity_record(builder: &InMemoryWasmTestBuilder, era_id: EraId) -> InactivityRecord {
+    let era_info = get_era_info(builder, era_id);
+    match era_info.inactivity().as_slice() {
+        [record] => record.clone(),
+        records => panic!("expected a single inactivity record, got {:?}", records),
+    }
+}
+
+fn is_bid_inactive(builder: &mut InMemoryWasmTestBuilder) -> bool {
+    let bids: Bids = builder.get_bids();
+    bids.get(&*VALIDATOR_1).expect("should have bid").inactive()
+}
+
+#[ignore]
+#[test]
+fn should_penalize_inactive_validator_in_steps_before_evicting() {
+    let mut builder = setup();
+
+    let mut era_id = INITIAL_ERA_ID;
+    let mut withheld_rewards = vec![];
+    for consecutive_inactive_eras in 1..=3 {
+        step(&mut builder, era_id, PARTIAL_REWARD_FACTORS);
+
+        let record = get_inactivity_record(&builder, era_id);
+        assert_eq!(record.validator_public_key(), &*VALIDATOR_1);
+        assert_eq!(record.participation_percent(), 25);
+        assert_eq!(
+            record.consecutive_inactive_eras(),
+            consecutive_inactive_eras
+        );
+        assert_eq!(record.evicted(), consecutive_inactive_eras == 3);
+        assert_eq!(
+            is_bid_inactive(&mut builder),
+            consecutive_inactive_eras == 3
+        );
+        withheld_rewards.push(*record.withheld_reward());
+
+        era_id = era_id.successor();
+    }
+
+    assert!(!withheld_rewards[0].is_zero());
+    assert!(withheld_rewards[0] < withheld_rewards[1]);
+    assert!(withheld_rewards[1] < withheld_rewards[2]);
+}
+
+#[ignore]
+#[test]
+fn should_reset_inactivity_after_active_era() {
+    let mut builder = setup();
+
+    let era_id = INITIAL_ERA_ID;
+    step(&mut builder, era_id, PARTIAL_REWARD_FACTORS);
+    assert_eq!(
+        get_inactivity_record(&builder, era_id).consecutive_inactive_eras(),
+        1
+    );
+
+    let era_id = era_id.successor();
+    step(&mut builder, era_id, FULL_REWARD_FACTORS);
+    assert!(get_era_info(&builder, era_id).inactivity().is_empty());
+
+    let era_id = era_id.successor();
+    step(&mut builder, era_id, PARTIAL_REWARD_FACTORS);
+    assert_eq!(
+        get_inactivity_record(&builder, era_id).consecutive_inactive_eras(),
+        1
+    );
+    assert!(!is_bid_inactive(&mut builder));
+}
+
+#[ignore]
+#[test]
+fn should_not_penalize_without_inactivity_policy() {
+    let mut builder = setup();
+
+    let step_request = StepRequestBuilder::new()
+        .with_parent_state_hash(builder.get_post_state_hash())
+        .with_protocol_version(ProtocolVersion::V1_0_0)
+        .with_reward_item(RewardItem::new(
+            VALIDATOR_1.clone(),
+            PARTIAL_REWARD_FACTORS.0,
+        ))
+        .with_reward_item(RewardItem::new(
+            VALIDATOR_2.clone(),
+            PARTIAL_REWARD_FACTORS.1,
+        ))
+        .with_next_era_id(INITIAL_ERA_ID.successor())
+        .build();
+    builder.step(step_request);
+
+    assert!(get_era_info(&builder, INITIAL_ERA_ID)
+        .inactivity()
+        .is_empty());
+    assert!(!is_bid_inactive(&mut builder));
+}
@@ -1,2 +1,7 @@
 mod bids;
+mod delegation_bounds;
+mod delegation_rate_change;
 mod distribute;
+mod inactivity;
+mod redelegate;
+mod slashing;
@@ -0,0 +1,358 @@
+use num_traits::Zero;
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, StepRequestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_AUCTION_DELAY, DEFAULT_DELEGATION_RATE_CHANGE_DELAY, DEFAULT_GENESIS_CONFIG_HASH,
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS, DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
+        DEFAULT_MAXIMUM_DELEGATION_AMOUNT, DEFAULT_MAX_DELEGATION_RATE_INCREASE,
+        DEFAULT_MAX_DELEGATORS_PER_VALIDATOR, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_UNBONDING_DELAY, DEFAULT_VALIDATOR_SLOTS, DEFAULT_WASM_CONFIG,
+        TIMESTAMP_MILLIS_INCREMENT,
+    },
+    DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use casper_execution_engine::core::{
+    engine_state::{
+        self,
+        genesis::{ExecConfig, GenesisAccount, GenesisValidator},
+        run_genesis_request::RunGenesisRequest,
+        SlashItem,
+    },
+    execution,
+};
+use casper_types::{
+    api_error::ApiError,
+    runtime_args,
+    system::auction::{
+        self, Bids, DelegationRate, ARG_AMOUNT, ARG_DELEGATOR, ARG_NEW_VALIDATOR, ARG_VALIDATOR,
+    },
+    EraId, Motes, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+const CONTRACT_DELEGATE: &str = "delegate.wasm";
+const CONTRACT_REDELEGATE: &str = "redelegate.wasm";
+
+const VALIDATOR_STAKE: u64 = 1_000_000;
+const DELEGATE_AMOUNT: u64 = 500_000;
+const REDELEGATE_AMOUNT: u64 = 200_000;
+
+const ERA_END_TIMESTAMP_MILLIS: u64 =
+    DEFAULT_GENESIS_TIMESTAMP_MILLIS + DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS;
+
+static VALIDATOR_1: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([3; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static VALIDATOR_2: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([4; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static NON_VALIDATOR: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([5; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static DELEGATOR_1: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([205; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+
+/// Runs genesis with two validators and delegates `DELEGATE_AMOUNT` from `DELEGATOR_1` to
+/// `VALIDATOR_1`.
+fn setup() -> InMemoryWasmTestBuilder {
+    setup_with_maximum_delegation_amount(DEFAULT_MAXIMUM_DELEGATION_AMOUNT)
+}
+
+/// Like `setup`, but with the given maximum amount a delegator may have delegated to a single
+/// validator.
+fn setup_with_maximum_delegation_amount(maximum_delegation_amount: u64) -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for validator in [&*VALIDATOR_1, &*VALIDATOR_2] {
+            tmp.push(GenesisAccount::account(
+                validator.clone(),
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+                Some(GenesisValidator::new(
+                    Motes::new(VALIDATOR_STAKE.into()),
+                    DelegationRate::zero(),
+                )),
+            ));
+        }
+        tmp.push(GenesisAccount::account(
+            DELEGATOR_1.clone(),
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            None,
+        ));
+        tmp
+    };
+
+    let exec_config = ExecConfig::new(
+        accounts,
+        *DEFAULT_WASM_CONFIG,
+        *DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_VALIDATOR_SLOTS,
+        DEFAULT_AUCTION_DELAY,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
+        DEFAULT_ROUND_SEIGNIORAGE_RATE,
+        DEFAULT_UNBONDING_DELAY,
+        DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+        maximum_delegation_amount,
+        DEFAULT_MAX_DELEGATORS_PER_VALIDATOR,
+        DEFAULT_DELEGATION_RATE_CHANGE_DELAY,
+        DEFAULT_MAX_DELEGATION_RATE_INCREASE,
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let delegate_request = ExecuteRequestBuilder::standard(
+        DELEGATOR_1.to_account_hash(),
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATE_AMOUNT),
+            ARG_VALIDATOR => VALIDATOR_1.clone(),
+            ARG_DELEGATOR => DELEGATOR_1.clone(),
+        },
+    )
+    .build();
+    builder.exec(delegate_request).commit().expect_success();
+
+    builder
+}
+
+fn redelegate<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    amount: u64,
+    new_validator: &PublicKey,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let redelegate_request = ExecuteRequestBuilder::standard(
+        DELEGATOR_1.to_account_hash(),
+        CONTRACT_REDELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(amount),
+            ARG_VALIDATOR => VALIDATOR_1.clone(),
+            ARG_DELEGATOR => DELEGATOR_1.clone(),
+            ARG_NEW_VALIDATOR => new_validator.clone(),
+        },
+    )
+    .build();
+    builder.exec(redelegate_request).commit()
+}
+
+fn delegated_amount(builder: &mut InMemoryWasmTestBuilder, validator: &PublicKey) -> Option<U512> {
+    let bids: Bids = builder.get_bids();
+    bids[validator]
+        .delegators()
+        .get(&*DELEGATOR_1)
+        .map(|delegator| *delegator.staked_amount())
+}
+
+fn assert_auction_error(builder: &InMemoryWasmTestBuilder, expected: auction::Error) {
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+            if auction_error == expected as u8
+        ),
+        "expected {:?}, got {:?}",
+        expected,
+        error
+    );
+}
+
+#[ignore]
+#[test]
+fn should_redelegate_at_the_end_of_the_era() {
+    let mut builder = setup();
+
+    redelegate(&mut builder, REDELEGATE_AMOUNT, &VALIDATOR_2).expect_success();
+
+    // The stake leaves the current validator immediately...
+    assert_eq!(
+        delegated_amount(&mut builder, &VALIDATOR_1),
+        Some(U512::from(DELEGATE_AMOUNT - REDELEGATE_AMOUNT))
+    );
+    assert_eq!(delegated_amount(&mut builder, &VALIDATOR_2), None);
+
+    // ...and is delegated to the new validator at the end of the era.
+    builder.run_auction(ERA_END_TIMESTAMP_MILLIS, Vec::new());
+
+    assert_eq!(
+        delegated_amount(&mut builder, &VALIDATOR_1),
+        Some(U512::from(DELEGATE_AMOUNT - REDELEGATE_AMOUNT))
+    );
+    assert_eq!(
+        delegated_amount(&mut builder, &VALIDATOR_2),
+        Some(U512::from(REDELEGATE_AMOUNT))
+    );
+
+    // Nothing is left waiting for the unbonding delay.
+    assert!(builder
+        .get_withdraws()
+        .values()
+        .all(|unbonding_purses| unbonding_purses.is_empty()));
+
+    let era_validators = builder.get_era_validators();
+    let (_, validator_weights) = era_validators
+        .into_iter()
+        .next_back()
+        .expect("should have era validators");
+    assert_eq!(
+        validator_weights[&*VALIDATOR_2],
+        U512::from(VALIDATOR_STAKE + REDELEGATE_AMOUNT)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_remove_processed_redelegations() {
+    let mut builder = setup();
+
+    redelegate(&mut builder, REDELEGATE_AMOUNT, &VALIDATOR_2).expect_success();
+    redelegate(&mut builder, REDELEGATE_AMOUNT, &VALIDATOR_2).expect_success();
+
+    // Pending requests are stored under their delegator's key.
+    let redelegations = builder.get_redelegations();
+    assert_eq!(redelegations.len(), 1);
+    assert_eq!(redelegations[&DELEGATOR_1.to_account_hash()].len(), 2);
+
+    builder.run_auction(ERA_END_TIMESTAMP_MILLIS, Vec::new());
+
+    assert!(builder
+        .get_redelegations()
+        .values()
+        .all(|redelegations| redelegations.is_empty()));
+    assert_eq!(
+        delegated_amount(&mut builder, &VALIDATOR_2),
+        Some(U512::from(2 * REDELEGATE_AMOUNT))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_redelegate_whole_stake() {
+    let mut builder = setup();
+
+    redelegate(&mut builder, DELEGATE_AMOUNT, &VALIDATOR_2).expect_success();
+    assert_eq!(delegated_amount(&mut builder, &VALIDATOR_1), None);
+
+    builder.run_auction(ERA_END_TIMESTAMP_MILLIS, Vec::new());
+
+    assert_eq!(delegated_amount(&mut builder, &VALIDATOR_1), None);
+    assert_eq!(
+        delegated_amount(&mut builder, &VALIDATOR_2),
+        Some(U512::from(DELEGATE_AMOUNT))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_redelegate_to_same_validator() {
+    let mut builder = setup();
+
+    redelegate(&mut builder, REDELEGATE_AMOUNT, &VALIDATOR_1);
+    assert_auction_error(&builder, auction::Error::RedelegationToSameValidator);
+}
+
+#[ignore]
+#[test]
+fn should_not_redelegate_to_unknown_validator() {
+    let mut builder = setup();
+
+    redelegate(&mut builder, REDELEGATE_AMOUNT, &NON_VALIDATOR);
+    assert_auction_error(&builder, auction::Error::RedelegationValidatorNotFound);
+    assert_eq!(
+        delegated_amount(&mut builder, &VALIDATOR_1),
+        Some(U512::from(DELEGATE_AMOUNT))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_unbond_rejected_redelegation_after_unbonding_delay() {
+    let mut builder = setup_with_maximum_delegation_amount(DELEGATE_AMOUNT);
+
+    redelegate(&mut builder, REDELEGATE_AMOUNT, &VALIDATOR_2).expect_success();
+
+    // Fill up the delegation with the new validator before the redelegation is processed.
+    let delegate_request = ExecuteRequestBuilder::standard(
+        DELEGATOR_1.to_account_hash(),
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATE_AMOUNT),
+            ARG_VALIDATOR => VALIDATOR_2.clone(),
+            ARG_DELEGATOR => DELEGATOR_1.clone(),
+        },
+    )
+    .build();
+    builder.exec(delegate_request).commit().expect_success();
+
+    let delegator_purse = builder
+        .get_account(DELEGATOR_1.to_account_hash())
+        .expect("should have delegator account")
+        .main_purse();
+    let delegator_purse_balance_before = builder.get_purse_balance(delegator_purse);
+
+    let mut timestamp_millis = ERA_END_TIMESTAMP_MILLIS;
+    builder.run_auction(timestamp_millis, Vec::new());
+    timestamp_millis += TIMESTAMP_MILLIS_INCREMENT;
+
+    // The stake is not delegated to the new validator, but waits for the unbonding delay with the
+    // old one, where it can still be slashed.
+    assert_eq!(
+        delegated_amount(&mut builder, &VALIDATOR_2),
+        Some(U512::from(DELEGATE_AMOUNT))
+    );
+    let unbonding_purses = &builder.get_withdraws()[&VALIDATOR_1.to_account_hash()];
+    assert_eq!(unbonding_purses.len(), 1);
+    assert_eq!(unbonding_purses[0].unbonder_public_key(), &*DELEGATOR_1);
+    assert_eq!(*unbonding_purses[0].amount(), U512::from(REDELEGATE_AMOUNT));
+
+    for _ in 1..DEFAULT_UNBONDING_DELAY {
+        assert_eq!(
+            builder.get_purse_balance(delegator_purse),
+            delegator_purse_balance_before
+        );
+        builder.run_auction(timestamp_millis, Vec::new());
+        timestamp_millis += TIMESTAMP_MILLIS_INCREMENT;
+    }
+    assert_eq!(
+        builder.get_purse_balance(delegator_purse),
+        delegator_purse_balance_before
+    );
+
+    builder.run_auction(timestamp_millis, Vec::new());
+
+    assert_eq!(
+        builder.get_purse_balance(delegator_purse),
+        delegator_purse_balance_before + U512::from(REDELEGATE_AMOUNT)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_burn_redelegated_stake_of_slashed_validator() {
+    let mut builder = setup();
+
+    redelegate(&mut builder, REDELEGATE_AMOUNT, &VALIDATOR_2).expect_success();
+
+    let step_request = StepRequestBuilder::new()
+        .with_parent_state_hash(builder.get_post_state_hash())
+        .with_protocol_version(ProtocolVersion::V1_0_0)
+        .with_slash_item(SlashItem::new(VALIDATOR_1.clone()))
+        .with_next_era_id(EraId::from(1))
+        .with_era_end_timestamp_millis(ERA_END_TIMESTAMP_MILLIS)
+        .build();
+    builder.step(step_request);
+
+    assert_eq!(delegated_amount(&mut builder, &VALIDATOR_2), None);
+}
@@ -0,0 +1,179 @@
+use num_rational::Ratio;
+use num_traits::Zero;
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    internal::{utils, InMemoryWasmTestBuilder, StepRequestBuilder, DEFAULT_ACCOUNTS},
+    DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use casper_execution_engine::core::engine_state::{
+    genesis::{GenesisAccount, GenesisValidator},
+    RewardItem, SlashItem,
+};
+use casper_types::{
+    system::{
+        auction::{Bids, DelegationRate, EraInfo, SlashEvidence, SlashRecord, INITIAL_ERA_ID},
+        mint::TOTAL_SUPPLY_KEY,
+    },
+    EraId, Key, Motes, ProtocolVersion, PublicKey, SecretKey, U512,
+};
+
+const VALIDATOR_1_STAKE: u64 = 1_000_000;
+const VALIDATOR_2_STAKE: u64 = 2_000_000;
+const EVIDENCE_BLOCK_HEIGHT: u64 = 42;
+const EVIDENCE_MESSAGE_HASH: [u8; 32] = [7; 32];
+
+static VALIDATOR_1: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([3; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static VALIDATOR_2: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([5; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+
+/// Evidence of `VALIDATOR_1`'s offense, first cited by `VALIDATOR_2`.
+fn evidence() -> SlashEvidence {
+    SlashEvidence::new(
+        VALIDATOR_2.clone(),
+        EVIDENCE_MESSAGE_HASH,
+        EVIDENCE_BLOCK_HEIGHT,
+    )
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for (public_key, stake) in [
+            (VALIDATOR_1.clone(), VALIDATOR_1_STAKE),
+            (VALIDATOR_2.clone(), VALIDATOR_2_STAKE),
+        ] {
+            tmp.push(GenesisAccount::account(
+                public_key,
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+                Some(GenesisValidator::new(
+                    Motes::new(stake.into()),
+                    DelegationRate::zero(),
+                )),
+            ));
+        }
+        tmp
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+    builder
+}
+
+fn step(builder: &mut InMemoryWasmTestBuilder, slash_item: SlashItem) {
+    let step_request = StepRequestBuilder::new()
+        .with_parent_state_hash(builder.get_post_state_hash())
+        .with_protocol_version(ProtocolVersion::V1_0_0)
+        .with_slash_item(slash_item)
+        .with_reward_item(RewardItem::new(VALIDATOR_1.clone(), 0))
+        .with_reward_item(RewardItem::new(VALIDATOR_2.clone(), 0))
+        .with_next_era_id(INITIAL_ERA_ID.successor())
+        .build();
+    builder.step(step_request);
+}
+
+fn get_total_supply(builder: &mut InMemoryWasmTestBuilder) -> U512 {
+    let mint_hash = builder.get_mint_contract_hash();
+    builder.get_value(mint_hash, TOTAL_SUPPLY_KEY)
+}
+
+fn get_era_info(builder: &InMemoryWasmTestBuilder, era_id: EraId) -> EraInfo {
+    builder
+        .query(None, Key::EraInfo(era_id), &[])
+        .expect("should have era info")
+        .as_era_info()
+        .cloned()
+        .expect("should be era info")
+}
+
+#[ignore]
+#[test]
+fn should_slash_fraction_of_stake_and_pay_reporter() {
+    let mut builder = setup();
+    let total_supply_before = get_total_supply(&mut builder);
+
+    step(
+        &mut builder,
+        SlashItem::with_fraction(
+            VALIDATOR_1.clone(),
+            Ratio::new(1, 10),
+            Some(VALIDATOR_2.clone()),
+            Some(evidence()),
+        ),
+    );
+
+    let slashed_amount = U512::from(VALIDATOR_1_STAKE / 10);
+
+    let bids: Bids = builder.get_bids();
+    let validator_1_bid = bids.get(&*VALIDATOR_1).expect("should have bid");
+    assert!(!validator_1_bid.inactive());
+    assert_eq!(
+        *validator_1_bid.staked_amount(),
+        U512::from(VALIDATOR_1_STAKE) - slashed_amount
+    );
+    let validator_2_bid = bids.get(&*VALIDATOR_2).expect("should have bid");
+    assert_eq!(
+        *validator_2_bid.staked_amount(),
+        U512::from(VALIDATOR_2_STAKE) + slashed_amount
+    );
+
+    assert_eq!(get_total_supply(&mut builder), total_supply_before);
+
+    assert_eq!(
+        get_era_info(&builder, INITIAL_ERA_ID).slashes(),
+        &vec![SlashRecord::new(
+            VALIDATOR_1.clone(),
+            slashed_amount,
+            Some(VALIDATOR_2.clone()),
+            Some(evidence())
+        )]
+    );
+}
+
+#[ignore]
+#[test]
+fn should_burn_slashed_fraction_and_record_reporter() {
+    let mut builder = setup();
+    let total_supply_before = get_total_supply(&mut builder);
+
+    step(
+        &mut builder,
+        SlashItem::with_fraction(
+            VALIDATOR_1.clone(),
+            Ratio::new(1, 4),
+            None,
+            Some(evidence()),
+        ),
+    );
+
+    let slashed_amount = U512::from(VALIDATOR_1_STAKE / 4);
+
+    let bids: Bids = builder.get_bids();
+    assert_eq!(
+        *bids
+            .get(&*VALIDATOR_2)
+            .expect("should have bid")
+            .staked_amount(),
+        U512::from(VALIDATOR_2_STAKE)
+    );
+
+    assert_eq!(
+        get_total_supply(&mut builder),
+        total_supply_before - slashed_amount
+    );
+
+    assert_eq!(
+        get_era_info(&builder, INITIAL_ERA_ID).slashes(),
+        &vec![SlashRecord::new(
+            VALIDATOR_1.clone(),
+            slashed_amount,
+            None,
+            Some(evidence())
+        )]
+    );
+}
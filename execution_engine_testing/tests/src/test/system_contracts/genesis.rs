@@ -3,9 +3,12 @@ use once_cell::sync::Lazy;
 
 use casper_engine_test_support::{
     internal::{
-        InMemoryWasmTestBuilder, DEFAULT_AUCTION_DELAY, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
-        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_SYSTEM_CONFIG,
-        DEFAULT_UNBONDING_DELAY, DEFAULT_VALIDATOR_SLOTS, DEFAULT_WASM_CONFIG,
+        InMemoryWasmTestBuilder, DEFAULT_AUCTION_DELAY, DEFAULT_DELEGATION_RATE_CHANGE_DELAY,
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS, DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
+        DEFAULT_MAXIMUM_DELEGATION_AMOUNT, DEFAULT_MAX_DELEGATION_RATE_INCREASE,
+        DEFAULT_MAX_DELEGATORS_PER_VALIDATOR, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+        DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_SYSTEM_CONFIG, DEFAULT_UNBONDING_DELAY,
+        DEFAULT_VALIDATOR_SLOTS, DEFAULT_WASM_CONFIG,
     },
     AccountHash,
 };
@@ -74,6 +77,11 @@ fn should_run_genesis() {
     let locked_funds_period = DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS;
     let round_seigniorage_rate = DEFAULT_ROUND_SEIGNIORAGE_RATE;
     let unbonding_delay = DEFAULT_UNBONDING_DELAY;
+    let minimum_delegation_amount = DEFAULT_MINIMUM_DELEGATION_AMOUNT;
+    let maximum_delegation_amount = DEFAULT_MAXIMUM_DELEGATION_AMOUNT;
+    let max_delegators_per_validator = DEFAULT_MAX_DELEGATORS_PER_VALIDATOR;
+    let delegation_rate_change_delay = DEFAULT_DELEGATION_RATE_CHANGE_DELAY;
+    let max_delegation_rate_increase = DEFAULT_MAX_DELEGATION_RATE_INCREASE;
     let genesis_timestamp = DEFAULT_GENESIS_TIMESTAMP_MILLIS;
 
     let exec_config = ExecConfig::new(
@@ -85,6 +93,11 @@ fn should_run_genesis() {
         locked_funds_period,
         round_seigniorage_rate,
         unbonding_delay,
+        minimum_delegation_amount,
+        maximum_delegation_amount,
+        max_delegators_per_validator,
+        delegation_rate_change_delay,
+        max_delegation_rate_increase,
         genesis_timestamp,
     );
     let run_genesis_request =
@@ -145,6 +158,11 @@ fn should_track_total_token_supply_in_mint() {
     let locked_funds_period = DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS;
     let round_seigniorage_rate = DEFAULT_ROUND_SEIGNIORAGE_RATE;
     let unbonding_delay = DEFAULT_UNBONDING_DELAY;
+    let minimum_delegation_amount = DEFAULT_MINIMUM_DELEGATION_AMOUNT;
+    let maximum_delegation_amount = DEFAULT_MAXIMUM_DELEGATION_AMOUNT;
+    let max_delegators_per_validator = DEFAULT_MAX_DELEGATORS_PER_VALIDATOR;
+    let delegation_rate_change_delay = DEFAULT_DELEGATION_RATE_CHANGE_DELAY;
+    let max_delegation_rate_increase = DEFAULT_MAX_DELEGATION_RATE_INCREASE;
     let genesis_timestamp = DEFAULT_GENESIS_TIMESTAMP_MILLIS;
     let ee_config = ExecConfig::new(
         accounts.clone(),
@@ -155,6 +173,11 @@ fn should_track_total_token_supply_in_mint() {
         locked_funds_period,
         round_seigniorage_rate,
         unbonding_delay,
+        minimum_delegation_amount,
+        maximum_delegation_amount,
+        max_delegators_per_validator,
+        delegation_rate_change_delay,
+        max_delegation_rate_increase,
         genesis_timestamp,
     );
     let run_genesis_request =
@@ -843,6 +843,9 @@ fn should_verify_wasm_add_bid_wasm_cost_is_not_recursive() {
         remove_contract_user_group_urefs: HostFunction::fixed(0),
         print: HostFunction::fixed(0),
         blake2b: HostFunction::fixed(0),
+        set_spending_limit: HostFunction::fixed(0),
+        remove_spending_limit: HostFunction::fixed(0),
+        manage_recovery: HostFunction::fixed(0),
     };
 
     let new_wasm_config = WasmConfig::new(
@@ -0,0 +1,41 @@
+//! # Casper consensus simulator
+//!
+//! Simulates a single Highway era in a virtual network, as described by a scenario file, and
+//! prints statistics about finality latency, orphaned units and rewards. Run with `--help` to see
+//! available command-line arguments.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+use structopt::StructOpt;
+
+use casper_node::consensus_sim::{self, Scenario};
+
+/// Command-line arguments of the consensus simulator.
+#[derive(Debug, StructOpt)]
+#[structopt(about = "Simulates a Highway era with configurable validator and network faults.")]
+struct Args {
+    /// Path to the scenario TOML file.
+    #[structopt(parse(from_os_str))]
+    scenario: PathBuf,
+    /// Print the report as JSON instead of a human-readable table.
+    #[structopt(long)]
+    json: bool,
+}
+
+/// Main function.
+fn main() -> anyhow::Result<()> {
+    let args = Args::from_args();
+    let input = fs::read_to_string(&args.scenario)
+        .with_context(|| format!("failed to read {}", args.scenario.display()))?;
+    let scenario: Scenario = input
+        .parse()
+        .with_context(|| format!("failed to load {}", args.scenario.display()))?;
+    let report = consensus_sim::run(&scenario)?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", report);
+    }
+    Ok(())
+}
@@ -8,6 +8,7 @@ use std::{
     env, fs,
     path::{Path, PathBuf},
     str::FromStr,
+    sync::Arc,
 };
 
 use anyhow::{self, Context};
@@ -17,14 +18,23 @@ use structopt::StructOpt;
 use toml::{value::Table, Value};
 use tracing::{error, info, warn};
 
+use casper_types::SecretKey;
+
 use crate::{
-    logging,
+    components::{
+        consensus::{
+            signer::{self, LocalSigner},
+            slashing_protection::SlashingProtection,
+        },
+        small_network::{read_capture, CaptureRecord},
+    },
+    logging, protocol,
     reactor::{initializer, joiner, participating, ReactorExit, Runner},
     setup_signal_hooks,
     types::ExitCode,
     utils::{
         pid_file::{PidFile, PidFileOutcome},
-        WithDir,
+        External, WithDir,
     },
 };
 
@@ -75,6 +85,39 @@ pub enum Cli {
         #[structopt(long)]
         new_config: PathBuf,
     },
+    /// Export the slashing-protection history from the configured database, so that it can be
+    /// imported on another machine using the same validator key.
+    ExportSlashingProtection {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Path to the file the history will be written to.
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Import a slashing-protection history exported on another machine, merging it into the
+    /// configured database.
+    ImportSlashingProtection {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Path to the file containing the exported history.
+        #[structopt(long)]
+        input: PathBuf,
+    },
+    /// Run a remote signer holding the validator's secret key, serving signing requests from nodes
+    /// on a Unix domain socket.
+    RunSigner {
+        /// Path to the validator's secret key file.
+        #[structopt(long)]
+        secret_key: PathBuf,
+        /// Path to the Unix domain socket to listen on. It must not exist yet.
+        #[structopt(long)]
+        socket: PathBuf,
+    },
+    /// Print the messages of a network traffic capture as JSON, one message per line.
+    DumpCapture {
+        /// Path to the capture file, as written when `network.capture_path` is set.
+        capture: PathBuf,
+    },
 }
 
 #[derive(Debug)]
@@ -279,9 +322,56 @@ impl Cli {
                 )?;
                 Ok(ExitCode::Success as i32)
             }
+            Cli::ExportSlashingProtection { config, output } => {
+                let slashing_protection = Self::slashing_protection(&config)?;
+                let history = slashing_protection.export()?;
+                let bytes = serde_json::to_vec_pretty(&history)?;
+                fs::write(&output, bytes)
+                    .context("could not write slashing-protection history")
+                    .with_context(|| output.display().to_string())?;
+                info!(output = %output.display(), "exported slashing-protection history");
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ImportSlashingProtection { config, input } => {
+                let slashing_protection = Self::slashing_protection(&config)?;
+                let bytes = fs::read(&input)
+                    .context("could not read slashing-protection history")
+                    .with_context(|| input.display().to_string())?;
+                let history = serde_json::from_slice(&bytes)
+                    .context("could not parse slashing-protection history")
+                    .with_context(|| input.display().to_string())?;
+                slashing_protection.import(history)?;
+                info!(input = %input.display(), "imported slashing-protection history");
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::DumpCapture { capture } => {
+                let records: Vec<CaptureRecord<protocol::Message>> = read_capture(&capture)?;
+                for record in records {
+                    println!("{}", serde_json::to_string(&record)?);
+                }
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::RunSigner { secret_key, socket } => {
+                logging::init_with_config(&Default::default())?;
+                let secret_key: Arc<SecretKey> = External::Path(secret_key).load(".")?;
+                signer::serve_unix(&socket, Arc::new(LocalSigner::new(secret_key)))?;
+                Ok(ExitCode::Success as i32)
+            }
         }
     }
 
+    /// Opens the slashing-protection database configured in the given config file.
+    fn slashing_protection(config: &Path) -> anyhow::Result<SlashingProtection> {
+        let config = Self::init(config, vec![])?;
+        let path = config
+            .value()
+            .consensus
+            .slashing_protection_path
+            .clone()
+            .context("consensus.slashing_protection_path is not set in the config file")?;
+        Ok(SlashingProtection::open(config.with_dir(path))?)
+    }
+
     /// Parses the config file for the current version of casper-node, and initializes logging.
     fn init(
         config: &Path,
@@ -258,6 +258,12 @@ impl BlockProposerReady {
                         .ignore()
                 }
             }
+            Event::Request(BlockProposerRequest::IsFinalized {
+                deploy_hash,
+                responder,
+            }) => responder
+                .respond(self.contains_finalized(&deploy_hash))
+                .ignore(),
             Event::BufferDeploy { hash, deploy_info } => {
                 self.add_deploy(Timestamp::now(), hash, *deploy_info);
                 Effects::new()
@@ -450,8 +456,11 @@ impl BlockProposerReady {
         let block_timestamp = context.timestamp();
         let mut appendable_block = AppendableBlock::new(deploy_config, block_timestamp);
 
-        // We prioritize transfers over deploys, so we try to include them first.
-        for (hash, (deploy_info, received_time)) in &self.sets.pending_transfers {
+        // We prioritize transfers over deploys, so we try to include them first.  Within each
+        // category, deploys offering a higher gas price are included first.
+        for (hash, (deploy_info, received_time)) in
+            deploy_sets::by_gas_price(&self.sets.pending_transfers)
+        {
             if !self.deps_resolved(&deploy_info.header, &past_deploys)
                 || past_deploys.contains(hash)
                 || self.contains_finalized(hash)
@@ -475,7 +484,9 @@ impl BlockProposerReady {
         }
 
         // Now we try to add other deploys to the block.
-        for (hash, (deploy_info, received_time)) in &self.sets.pending_deploys {
+        for (hash, (deploy_info, received_time)) in
+            deploy_sets::by_gas_price(&self.sets.pending_deploys)
+        {
             if !self.deps_resolved(&deploy_info.header, &past_deploys)
                 || past_deploys.contains(hash)
                 || self.contains_finalized(hash)
@@ -1,4 +1,5 @@
 use std::{
+    cmp::Reverse,
     collections::HashMap,
     fmt::{self, Display, Formatter},
     hash::Hash,
@@ -93,6 +94,24 @@ impl BlockProposerDeploySets {
     }
 }
 
+/// Returns the given pending deploys ordered by decreasing gas price, so that the deploys offering
+/// the highest fees are included first when a block is full.  Deploys with equal gas prices are
+/// ordered by the time we received them.
+pub(super) fn by_gas_price(
+    deploys: &HashMap<DeployHash, (DeployInfo, Timestamp)>,
+) -> Vec<(&DeployHash, &(DeployInfo, Timestamp))> {
+    deploys
+        .iter()
+        .sorted_by_key(|(hash, (deploy_info, received_time))| {
+            (
+                Reverse(deploy_info.header.gas_price()),
+                *received_time,
+                **hash,
+            )
+        })
+        .collect()
+}
+
 /// Drains items that satisfy the given predicate from the hash map and retains the rest.
 /// Returns keys of the drained elements.
 ///
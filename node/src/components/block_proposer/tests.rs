@@ -725,6 +725,85 @@ fn should_return_deploy_dependencies() {
     assert!(deploys2.contains(deploy2.id()));
 }
 
+#[test]
+fn should_prefer_higher_gas_prices_when_block_is_full() {
+    let creation_time = Timestamp::from(100);
+    let ttl = TimeDiff::from(Duration::from_millis(100));
+    let block_time = Timestamp::from(120);
+
+    let mut rng = crate::new_rng();
+    let mut proposer = create_test_proposer(0.into());
+    let mut config = DeployConfig::default();
+    config.block_max_deploy_count = 2;
+
+    let deploys = [1, 5, 10, 3]
+        .iter()
+        .map(|gas_price| {
+            let deploy = generate_deploy(
+                &mut rng,
+                creation_time,
+                ttl,
+                vec![],
+                default_gas_payment(),
+                *gas_price,
+            );
+            proposer.add_deploy(
+                creation_time,
+                deploy.deploy_or_transfer_hash(),
+                deploy.deploy_info().unwrap(),
+            );
+            deploy
+        })
+        .collect_vec();
+
+    let block =
+        proposer.propose_block_payload(config, BlockContext::new(block_time, vec![]), vec![], true);
+    assert_eq!(
+        block.deploy_hashes(),
+        &vec![*deploys[2].id(), *deploys[1].id()]
+    );
+}
+
+#[test]
+fn should_not_propose_deploy_below_min_gas_price() {
+    let creation_time = Timestamp::from(100);
+    let ttl = TimeDiff::from(Duration::from_millis(100));
+    let block_time = Timestamp::from(120);
+
+    let mut rng = crate::new_rng();
+    let mut proposer = create_test_proposer(0.into());
+    let mut config = DeployConfig::default();
+    config.min_gas_price = 2;
+
+    let cheap_deploy = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        1,
+    );
+    let deploy = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        2,
+    );
+    for deploy in &[&cheap_deploy, &deploy] {
+        proposer.add_deploy(
+            creation_time,
+            deploy.deploy_or_transfer_hash(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    let block =
+        proposer.propose_block_payload(config, BlockContext::new(block_time, vec![]), vec![], true);
+    assert_eq!(block.deploy_hashes(), &vec![*deploy.id()]);
+}
+
 #[test]
 fn should_respect_deploy_delay() {
     let mut rng = crate::new_rng();
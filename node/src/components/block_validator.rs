@@ -1,7 +1,9 @@
 //! Block validator
 //!
 //! The block validator checks whether all the deploys included in the block payload exist, either
-//! locally or on the network.
+//! locally or on the network, and whether their dependencies are satisfied, either by an earlier
+//! deploy in the same block or by a deploy in an ancestor block.  For proposed blocks, deploys in
+//! finalized blocks that haven't been executed yet count as well, like in the block proposer.
 //!
 //! When multiple requests are made to validate the same block payload, they will eagerly return
 //! true if valid, but only fail if all sources have been exhausted. This is only relevant when
@@ -32,7 +34,7 @@ use crate::{
         Component,
     },
     effect::{
-        requests::{BlockValidationRequest, FetcherRequest, StorageRequest},
+        requests::{BlockProposerRequest, BlockValidationRequest, FetcherRequest, StorageRequest},
         EffectBuilder, EffectExt, EffectOptionExt, Effects, Responder,
     },
     types::{
@@ -98,6 +100,32 @@ impl ValidatingBlock {
             .map(|hash| DeployOrTransferHash::Transfer(*hash));
         deploys.chain(transfers)
     }
+
+    /// Returns the hashes of the deploys and transfers in the block's not yet finalized ancestors.
+    fn ancestor_deploy_hashes(&self) -> HashSet<DeployHash> {
+        match self {
+            ValidatingBlock::Block(_) => HashSet::new(),
+            ValidatingBlock::ProposedBlock(pb) => pb
+                .context()
+                .ancestor_values()
+                .iter()
+                .flat_map(|block_payload| block_payload.deploys_and_transfers_iter())
+                .map(DeployOrTransferHash::into)
+                .collect(),
+        }
+    }
+
+    /// Returns whether a finalized block at the given height precedes this block.
+    ///
+    /// A height of `None` stands for a finalized block that hasn't been executed and stored yet.
+    fn is_preceded_by(&self, height: Option<u64>) -> bool {
+        match (self, height) {
+            (ValidatingBlock::Block(block), Some(height)) => height < block.height(),
+            (ValidatingBlock::Block(_), None) => false,
+            // Every finalized block is an ancestor of a proposed block.
+            (ValidatingBlock::ProposedBlock(_), _) => true,
+        }
+    }
 }
 
 /// Block validator component event.
@@ -121,17 +149,32 @@ pub(crate) enum Event<I> {
     /// Deploy was invalid. Unable to convert to a deploy type.
     #[display(fmt = "{} invalid", _0)]
     CannotConvertDeploy(DeployOrTransferHash),
+
+    /// The height of the block containing a dependency has been looked up in storage, or whether
+    /// it is in a finalized block at all, if that block has not been stored yet.
+    #[display(fmt = "dependency {} checked", deploy_hash)]
+    DependencyChecked {
+        deploy_hash: DeployHash,
+        block_height: Option<u64>,
+        is_finalized: bool,
+    },
 }
 
 /// State of the current process of block validation.
 ///
-/// Tracks whether or not there are deploys still missing and who is interested in the final result.
+/// Tracks whether or not there are deploys or dependencies still missing and who is interested in
+/// the final result.
 #[derive(DataSize, Debug)]
 pub(crate) struct BlockValidationState<I> {
     /// Appendable block ensuring that the deploys satisfy the validity conditions.
     appendable_block: AppendableBlock,
     /// The deploys that have not yet been "crossed off" the list of potential misses.
     missing_deploys: HashSet<DeployOrTransferHash>,
+    /// The dependencies of the deploys found so far.
+    dependencies: HashMap<DeployOrTransferHash, Vec<DeployHash>>,
+    /// The dependencies that are neither in the block nor its ancestors, and still have to be
+    /// looked up in storage.
+    missing_dependencies: HashSet<DeployHash>,
     /// A list of responders that are awaiting an answer.
     responders: SmallVec<[Responder<bool>; 2]>,
     /// Peers that should have the data.
@@ -158,6 +201,42 @@ where
         self.sources.pop_front()
     }
 
+    /// Returns whether all deploys have been found and all dependencies have been resolved.
+    fn is_complete(&self) -> bool {
+        self.missing_deploys.is_empty() && self.missing_dependencies.is_empty()
+    }
+
+    /// Checks the dependencies of all deploys in the block, once all of them have been found.
+    ///
+    /// Dependencies on earlier deploys in the same block or on deploys in the block's ancestors
+    /// are resolved immediately, all other ones are added to `missing_dependencies`. Returns the
+    /// offending dependency if a deploy depends on itself or on a later deploy in the same block.
+    fn resolve_dependencies(&mut self, block: &ValidatingBlock) -> Result<(), DeployHash> {
+        let positions: HashMap<DeployHash, usize> = block
+            .deploys_and_transfers_iter()
+            .enumerate()
+            .map(|(position, dt_hash)| (dt_hash.into(), position))
+            .collect();
+        let ancestor_deploys = block.ancestor_deploy_hashes();
+        for (position, dt_hash) in block.deploys_and_transfers_iter().enumerate() {
+            let dependencies = match self.dependencies.get(&dt_hash) {
+                Some(dependencies) => dependencies,
+                None => continue,
+            };
+            for dependency in dependencies {
+                match positions.get(dependency) {
+                    Some(dependency_position) if *dependency_position < position => (),
+                    Some(_) => return Err(*dependency),
+                    None if ancestor_deploys.contains(dependency) => (),
+                    None => {
+                        self.missing_dependencies.insert(*dependency);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn respond<REv>(&mut self, value: bool) -> Effects<REv> {
         self.responders
             .drain(..)
@@ -216,6 +295,7 @@ where
         + From<BlockValidationRequest<I>>
         + From<FetcherRequest<I, Deploy>>
         + From<StorageRequest>
+        + From<BlockProposerRequest>
         + Send,
 {
     type Event = Event<I>;
@@ -249,7 +329,7 @@ where
                 match self.validation_states.entry(block) {
                     Entry::Occupied(mut entry) => {
                         // The entry already exists.
-                        if entry.get().missing_deploys.is_empty() {
+                        if entry.get().is_complete() {
                             // Block has already been validated successfully, early return to
                             // caller.
                             effects.extend(responder.respond(true).ignore());
@@ -277,6 +357,8 @@ where
                         entry.insert(BlockValidationState {
                             appendable_block: AppendableBlock::new(deploy_config, block_timestamp),
                             missing_deploys: block_deploys,
+                            dependencies: HashMap::new(),
+                            missing_dependencies: HashSet::new(),
                             responders: smallvec![responder],
                             sources: VecDeque::new(), /* This is empty b/c we create the first
                                                        * request using `sender`. */
@@ -311,6 +393,24 @@ where
                         if let Err(err) = add_result {
                             info!(block = ?key, %dt_hash, ?deploy_info, ?err, "block invalid");
                             invalid.push(key.clone());
+                            continue;
+                        }
+                        state
+                            .dependencies
+                            .insert(dt_hash, deploy_info.header.dependencies().clone());
+                        // Once all deploys are known, check that their dependencies are met.
+                        if state.missing_deploys.is_empty() {
+                            if let Err(dependency) = state.resolve_dependencies(key) {
+                                info!(
+                                    block = ?key, %dependency,
+                                    "dependency not executed before its dependent deploy"
+                                );
+                                invalid.push(key.clone());
+                                continue;
+                            }
+                            effects.extend(state.missing_dependencies.iter().flat_map(
+                                |dependency| check_dependency(effect_builder, *dependency),
+                            ));
                         }
                     }
                 }
@@ -321,7 +421,7 @@ where
                         effects.extend(state.respond(false));
                         return false;
                     }
-                    if state.missing_deploys.is_empty() {
+                    if state.is_complete() {
                         // This one is done and valid.
                         effects.extend(state.respond(true));
                         return false;
@@ -396,6 +496,32 @@ where
                     }
                 });
             }
+            Event::DependencyChecked {
+                deploy_hash,
+                block_height,
+                is_finalized,
+            } => {
+                self.validation_states.retain(|key, state| {
+                    if !state.missing_dependencies.remove(&deploy_hash) {
+                        return true;
+                    }
+                    if !is_finalized || !key.is_preceded_by(block_height) {
+                        // Notify everyone still waiting on it that all is lost.
+                        info!(
+                            block = ?key, dependency = %deploy_hash,
+                            "dependency not found in an ancestor block. block is invalid"
+                        );
+                        effects.extend(state.respond(false));
+                        return false;
+                    }
+                    if state.is_complete() {
+                        // This one is done and valid.
+                        effects.extend(state.respond(true));
+                        return false;
+                    }
+                    true
+                });
+            }
         }
         effects
     }
@@ -433,3 +559,33 @@ where
         .fetch_deploy(dt_hash.into(), sender)
         .map_or_else(validate_deploy, move || Event::DeployMissing(dt_hash))
 }
+
+/// Returns an effect that looks up the height of the block containing the given dependency.
+///
+/// If the block is not in storage, it may have been finalized but not executed yet, so the block
+/// proposer is asked whether it knows the dependency as finalized.
+fn check_dependency<REv, I>(
+    effect_builder: EffectBuilder<REv>,
+    deploy_hash: DeployHash,
+) -> Effects<Event<I>>
+where
+    REv: From<StorageRequest> + From<BlockProposerRequest> + Send,
+    I: Send + 'static,
+{
+    async move {
+        let block_height = effect_builder
+            .get_block_header_for_deploy_from_storage(deploy_hash)
+            .await
+            .map(|header| header.height());
+        let is_finalized =
+            block_height.is_some() || effect_builder.is_deploy_finalized(deploy_hash).await;
+        (block_height, is_finalized)
+    }
+    .event(
+        move |(block_height, is_finalized)| Event::DependencyChecked {
+            deploy_hash,
+            block_height,
+            is_finalized,
+        },
+    )
+}
@@ -29,6 +29,8 @@ enum ReactorEvent {
     Fetcher(FetcherRequest<NodeId, Deploy>),
     #[from]
     Storage(StorageRequest),
+    #[from]
+    BlockProposer(BlockProposerRequest),
 }
 
 impl From<BlockValidationRequest<NodeId>> for ReactorEvent {
@@ -80,6 +82,48 @@ impl MockReactor {
             panic!("unexpected event: {:?}", reactor_event);
         }
     }
+
+    /// Answers the next request to look up a dependency, either in storage or in the block
+    /// proposer's finalized deploys.
+    ///
+    /// Returns `true` if the dependency was not found in storage, so that the block proposer will
+    /// be asked next.
+    async fn answer_dependency_lookup(&self, rng: &mut TestRng, history: &History<'_>) -> bool {
+        let ((_ancestor, reactor_event), _) = self.scheduler.pop().await;
+        match reactor_event {
+            ReactorEvent::Storage(StorageRequest::GetBlockHeaderForDeploy {
+                deploy_hash,
+                responder,
+            }) => {
+                let maybe_header = history
+                    .executed
+                    .contains(&deploy_hash)
+                    .then(|| Block::random(rng).take_header());
+                let not_stored = maybe_header.is_none();
+                responder.respond(maybe_header).await;
+                not_stored
+            }
+            ReactorEvent::BlockProposer(BlockProposerRequest::IsFinalized {
+                deploy_hash,
+                responder,
+            }) => {
+                responder
+                    .respond(history.finalized.contains(&deploy_hash))
+                    .await;
+                false
+            }
+            _ => panic!("unexpected event: {:?}", reactor_event),
+        }
+    }
+}
+
+/// The deploys included in earlier blocks.
+#[derive(Default)]
+struct History<'a> {
+    /// Deploys in finalized blocks which have been executed and stored.
+    executed: &'a [DeployHash],
+    /// Deploys in finalized blocks which haven't been executed yet.
+    finalized: &'a [DeployHash],
 }
 
 fn new_proposed_block(
@@ -95,6 +139,15 @@ fn new_proposed_block(
 }
 
 fn new_deploy(rng: &mut TestRng, timestamp: Timestamp, ttl: TimeDiff) -> Deploy {
+    new_deploy_with_dependencies(rng, timestamp, ttl, vec![])
+}
+
+fn new_deploy_with_dependencies(
+    rng: &mut TestRng,
+    timestamp: Timestamp,
+    ttl: TimeDiff,
+    dependencies: Vec<DeployHash>,
+) -> Deploy {
     let secret_key = SecretKey::random(rng);
     let chain_name = "chain".to_string();
     let payment = ExecutableDeployItem::ModuleBytes {
@@ -105,7 +158,6 @@ fn new_deploy(rng: &mut TestRng, timestamp: Timestamp, ttl: TimeDiff) -> Deploy
         module_bytes: Bytes::new(),
         args: RuntimeArgs::new(),
     };
-    let dependencies = vec![];
     let gas_price = 1;
 
     Deploy::new(
@@ -122,6 +174,15 @@ fn new_deploy(rng: &mut TestRng, timestamp: Timestamp, ttl: TimeDiff) -> Deploy
 }
 
 fn new_transfer(rng: &mut TestRng, timestamp: Timestamp, ttl: TimeDiff) -> Deploy {
+    new_transfer_with_dependencies(rng, timestamp, ttl, vec![])
+}
+
+fn new_transfer_with_dependencies(
+    rng: &mut TestRng,
+    timestamp: Timestamp,
+    ttl: TimeDiff,
+    dependencies: Vec<DeployHash>,
+) -> Deploy {
     let secret_key = SecretKey::random(rng);
     let chain_name = "chain".to_string();
     let payment = ExecutableDeployItem::ModuleBytes {
@@ -131,7 +192,6 @@ fn new_transfer(rng: &mut TestRng, timestamp: Timestamp, ttl: TimeDiff) -> Deplo
     let session = ExecutableDeployItem::Transfer {
         args: RuntimeArgs::new(),
     };
-    let dependencies = vec![];
     let gas_price = 1;
 
     Deploy::new(
@@ -153,6 +213,18 @@ async fn validate_block(
     timestamp: Timestamp,
     deploys: Vec<Deploy>,
     transfers: Vec<Deploy>,
+) -> bool {
+    validate_block_with_history(rng, timestamp, deploys, transfers, History::default()).await
+}
+
+/// Validates a block using a `BlockValidator` component, with the given deploys already included
+/// in finalized blocks, and returns the result.
+async fn validate_block_with_history(
+    rng: &mut TestRng,
+    timestamp: Timestamp,
+    deploys: Vec<Deploy>,
+    transfers: Vec<Deploy>,
+    history: History<'_>,
 ) -> bool {
     // Assemble the block to be validated.
     let deploy_hashes = deploys.iter().map(|deploy| *deploy.id()).collect_vec();
@@ -200,6 +272,27 @@ async fn validate_block(
         }));
     }
 
+    // If the validity still can't be determined, the effects must be requests to look up the
+    // dependencies in storage, and in the block proposer if they aren't stored.
+    if !block_validator.validation_states.is_empty() {
+        let lookup_results: Vec<_> = effects.into_iter().map(tokio::spawn).collect();
+        let mut pending_lookups = lookup_results.len();
+        while pending_lookups > 0 {
+            pending_lookups -= 1;
+            if reactor.answer_dependency_lookup(rng, &history).await {
+                pending_lookups += 1;
+            }
+        }
+        effects = Effects::new();
+        for lookup_result in lookup_results {
+            let events = lookup_result.await.unwrap();
+            assert_eq!(1, events.len());
+            effects.extend(events.into_iter().flat_map(|dependency_checked| {
+                block_validator.handle_event(effect_builder, rng, dependency_checked)
+            }));
+        }
+    }
+
     // We expect exactly one effect: the validation response. This will resolve the result.
     assert_eq!(1, effects.len());
     for effect in effects {
@@ -282,3 +375,52 @@ async fn transfer_deploy_mixup_and_replay() {
     let transfers = vec![transfer1.clone(), transfer2.clone(), transfer2.clone()];
     assert!(!validate_block(&mut rng, timestamp, deploys, transfers).await);
 }
+
+/// Verifies that a block is invalid if it contains a deploy whose dependencies are neither executed
+/// earlier in the same block nor included in a finalized block.
+#[tokio::test]
+async fn dependencies() {
+    let mut rng = TestRng::new();
+    let ttl = TimeDiff::from(200);
+    let timestamp = Timestamp::from(1000);
+    let dependency = new_deploy(&mut rng, timestamp, ttl);
+    let dependent = new_deploy_with_dependencies(&mut rng, timestamp, ttl, vec![*dependency.id()]);
+
+    // A dependency executed earlier in the same block is satisfied.
+    let deploys = vec![dependency.clone(), dependent.clone()];
+    assert!(validate_block(&mut rng, timestamp, deploys, vec![]).await);
+
+    // A dependency executed later in the same block is not.
+    let deploys = vec![dependent.clone(), dependency.clone()];
+    assert!(!validate_block(&mut rng, timestamp, deploys, vec![]).await);
+
+    // A dependency that is neither in the block nor in a finalized block isn't satisfied either.
+    let deploys = vec![dependent.clone()];
+    assert!(!validate_block(&mut rng, timestamp, deploys, vec![]).await);
+
+    // But a dependency in a finalized block is.
+    let deploys = vec![dependent.clone()];
+    let history = History {
+        executed: &[*dependency.id()],
+        ..History::default()
+    };
+    assert!(validate_block_with_history(&mut rng, timestamp, deploys, vec![], history).await);
+
+    // Even if that block hasn't been executed and stored yet.
+    let deploys = vec![dependent.clone()];
+    let history = History {
+        finalized: &[*dependency.id()],
+        ..History::default()
+    };
+    assert!(validate_block_with_history(&mut rng, timestamp, deploys, vec![], history).await);
+
+    // A transfer can depend on a deploy, since deploys are executed before transfers.
+    let transfer = new_transfer_with_dependencies(&mut rng, timestamp, ttl, vec![*dependency.id()]);
+    let deploys = vec![dependency.clone()];
+    assert!(validate_block(&mut rng, timestamp, deploys, vec![transfer]).await);
+
+    // A deploy can't depend on a transfer in the same block, however.
+    let transfer = new_transfer(&mut rng, timestamp, ttl);
+    let dependent = new_deploy_with_dependencies(&mut rng, timestamp, ttl, vec![*transfer.id()]);
+    assert!(!validate_block(&mut rng, timestamp, vec![dependent], vec![transfer]).await);
+}
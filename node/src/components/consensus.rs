@@ -10,7 +10,11 @@ mod era_supervisor;
 mod highway_core;
 mod metrics;
 mod protocols;
-#[cfg(test)]
+pub(crate) mod signer;
+pub(crate) mod slashing_protection;
+mod status;
+#[cfg(any(test, feature = "consensus-sim"))]
+#[cfg_attr(not(test), allow(dead_code))] // Parts of the test harness are only used in tests.
 mod tests;
 mod traits;
 mod validator_change;
@@ -51,9 +55,12 @@ use crate::{
 
 pub(crate) use cl_context::ClContext;
 pub(crate) use config::Config;
-pub(crate) use consensus_protocol::{BlockContext, EraReport, ProposedBlock};
+pub(crate) use consensus_protocol::{BlockContext, EquivocationReport, EraReport, ProposedBlock};
 pub(crate) use era_supervisor::EraSupervisor;
-pub(crate) use protocols::highway::HighwayProtocol;
+#[cfg(feature = "consensus-sim")]
+pub use highway_core::highway_testing::simulator as consensus_sim;
+pub(crate) use protocols::new_consensus_protocol;
+pub(crate) use status::{ConsensusStatus, FinalizedBlockStatus};
 use traits::NodeIdT;
 pub(crate) use validator_change::ValidatorChange;
 
@@ -346,6 +353,9 @@ where
                 let validator_changes = self.get_validator_changes();
                 responder.respond(validator_changes).ignore()
             }
+            Event::ConsensusRequest(ConsensusRequest::FullStatus(responder)) => {
+                responder.respond(Some(self.consensus_status())).ignore()
+            }
         }
     }
 }
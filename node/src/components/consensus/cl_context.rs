@@ -1,36 +1,35 @@
 use std::sync::Arc;
 
 use datasize::DataSize;
-use tracing::info;
+use tracing::{error, info};
 
 use casper_hashing::Digest;
 use casper_types::{PublicKey, SecretKey, Signature};
 
 use crate::{
-    components::consensus::traits::{ConsensusValueT, Context, ValidatorSecret},
+    components::consensus::{
+        signer::{LocalSigner, Signer, SigningRequest},
+        traits::{ConsensusValueT, Context, ValidatorSecret},
+    },
     crypto,
     types::BlockPayload,
 };
 
 #[derive(DataSize)]
 pub struct Keypair {
-    secret_key: Arc<SecretKey>,
-    public_key: PublicKey,
+    #[data_size(skip)]
+    signer: Arc<dyn Signer>,
 }
 
 impl Keypair {
-    pub(crate) fn new(secret_key: Arc<SecretKey>, public_key: PublicKey) -> Self {
-        Self {
-            secret_key,
-            public_key,
-        }
+    pub(crate) fn new(signer: Arc<dyn Signer>) -> Self {
+        Self { signer }
     }
 }
 
 impl From<Arc<SecretKey>> for Keypair {
     fn from(secret_key: Arc<SecretKey>) -> Self {
-        let public_key: PublicKey = secret_key.as_ref().into();
-        Self::new(secret_key, public_key)
+        Self::new(Arc::new(LocalSigner::new(secret_key)))
     }
 }
 
@@ -38,8 +37,19 @@ impl ValidatorSecret for Keypair {
     type Hash = Digest;
     type Signature = Signature;
 
-    fn sign(&self, hash: &Digest) -> Signature {
-        crypto::sign(hash, self.secret_key.as_ref(), &self.public_key)
+    // The consensus protocols need the signature before they can continue, so this blocks the
+    // reactor until the signer responds. A remote signer must respond within the configured
+    // `remote_signer_timeout`, which is at most `signer::MAX_REMOTE_SIGNER_TIMEOUT`. All other
+    // signatures, i.e. finality signatures and network handshakes, are created off the reactor.
+    fn sign(&self, hash: &Digest) -> Option<Signature> {
+        let request = SigningRequest::Consensus { hash: *hash };
+        match self.signer.sign(&request) {
+            Ok(signature) => Some(signature),
+            Err(err) => {
+                error!(%err, public_key = %self.signer.public_key(), "failed to sign consensus message");
+                None
+            }
+        }
     }
 }
 
@@ -1,15 +1,25 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use datasize::DataSize;
 use serde::Deserialize;
 
 use casper_hashing::Digest;
-use casper_types::{ProtocolVersion, PublicKey, SecretKey};
+use casper_types::{ProtocolVersion, SecretKey};
 
 use crate::{
-    components::consensus::{protocols::highway::config::Config as HighwayConfig, EraId},
-    types::{chainspec::HighwayConfig as HighwayProtocolConfig, Chainspec, TimeDiff, Timestamp},
-    utils::{External, LoadError, Loadable},
+    components::consensus::{
+        protocols::highway::config::Config as HighwayConfig,
+        signer::{self, LocalSigner, RemoteSigner, RemoteSignerConfig, Signer},
+        EraId,
+    },
+    types::{
+        chainspec::{ConsensusProtocolName, HighwayConfig as HighwayProtocolConfig, SimpleConfig},
+        Chainspec, TimeDiff, Timestamp,
+    },
+    utils::External,
 };
 
 /// Consensus configuration.
@@ -17,8 +27,18 @@ use crate::{
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
 #[serde(deny_unknown_fields)]
 pub(crate) struct Config {
-    /// Path to secret key file.
+    /// Path to secret key file. Not used if `remote_signer` is set.
     pub(crate) secret_key_path: External,
+    /// The connection to a separate signer process holding the secret key. If not set, the node
+    /// loads the secret key from `secret_key_path` and signs in-process.
+    pub(crate) remote_signer: Option<RemoteSignerConfig>,
+    /// The time within which the remote signer must answer each request. Required if
+    /// `remote_signer` is set, and at most `signer::MAX_REMOTE_SIGNER_TIMEOUT`: Consensus messages
+    /// are signed on the reactor thread, so the node stalls while it waits for the signer.
+    pub(crate) remote_signer_timeout: Option<TimeDiff>,
+    /// Path to the slashing-protection database, which can be shared by all nodes using the same
+    /// validator key. If not set, only the unit hash files protect against equivocating.
+    pub(crate) slashing_protection_path: Option<PathBuf>,
     /// Highway-specific node configuration.
     pub(crate) highway: HighwayConfig,
 }
@@ -27,28 +47,47 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             secret_key_path: External::Missing,
+            remote_signer: None,
+            remote_signer_timeout: None,
+            slashing_protection_path: None,
             highway: HighwayConfig::default(),
         }
     }
 }
 
 impl Config {
-    /// Loads the secret key from the configuration file and derives the public key.
-    #[allow(clippy::type_complexity)]
-    pub(crate) fn load_keys<P: AsRef<Path>>(
+    /// Returns the signer for the validator key: either connects to the remote signer, or loads
+    /// the secret key from the configured file.
+    pub(crate) fn load_signer<P: AsRef<Path>>(
         &self,
         root: P,
-    ) -> Result<(Arc<SecretKey>, PublicKey), LoadError<<Arc<SecretKey> as Loadable>::Error>> {
-        let secret_signing_key: Arc<SecretKey> = self.secret_key_path.clone().load(root)?;
-        let public_key: PublicKey = PublicKey::from(secret_signing_key.as_ref());
-        Ok((secret_signing_key, public_key))
+    ) -> Result<Arc<dyn Signer>, signer::Error> {
+        match &self.remote_signer {
+            Some(remote_signer_config) => {
+                let timeout = self
+                    .remote_signer_timeout
+                    .ok_or(signer::Error::InvalidTimeout)?;
+                Ok(Arc::new(RemoteSigner::connect(
+                    remote_signer_config,
+                    timeout.into(),
+                    root.as_ref(),
+                )?))
+            }
+            None => {
+                let secret_key: Arc<SecretKey> = self.secret_key_path.clone().load(root)?;
+                Ok(Arc::new(LocalSigner::new(secret_key)))
+            }
+        }
     }
 }
 
 /// Consensus protocol configuration.
 #[derive(DataSize, Debug)]
 pub(crate) struct ProtocolConfig {
+    /// The consensus protocol run in the eras of the current protocol version.
+    pub(crate) consensus_protocol: ConsensusProtocolName,
     pub(crate) highway: HighwayProtocolConfig,
+    pub(crate) simple: SimpleConfig,
     pub(crate) era_duration: TimeDiff,
     pub(crate) minimum_era_height: u64,
     /// Number of eras before an auction actually defines the set of validators.
@@ -69,10 +108,30 @@ pub(crate) struct ProtocolConfig {
     pub(crate) chainspec_hash: Digest,
 }
 
+impl ProtocolConfig {
+    /// Returns the consensus protocol run in the eras of the given protocol version.
+    ///
+    /// The chainspec only configures the protocol of its own version.  Earlier versions, whose eras
+    /// are never instantiated after an upgrade, are assumed to have run Highway, the only protocol
+    /// available before it became configurable.
+    pub(crate) fn consensus_protocol(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> ConsensusProtocolName {
+        if protocol_version == self.protocol_version {
+            self.consensus_protocol
+        } else {
+            ConsensusProtocolName::Highway
+        }
+    }
+}
+
 impl From<&Chainspec> for ProtocolConfig {
     fn from(chainspec: &Chainspec) -> Self {
         ProtocolConfig {
+            consensus_protocol: chainspec.core_config.consensus_protocol,
             highway: chainspec.highway_config,
+            simple: chainspec.simple_config,
             era_duration: chainspec.core_config.era_duration,
             minimum_era_height: chainspec.core_config.minimum_era_height,
             auction_delay: chainspec.core_config.auction_delay,
@@ -10,10 +10,12 @@ use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
 use casper_hashing::Digest;
-use casper_types::bytesrepr::ToBytes;
+use casper_types::bytesrepr::{self, FromBytes, ToBytes};
 
 use crate::{
-    components::consensus::{traits::Context, ActionId, TimerId},
+    components::consensus::{
+        slashing_protection::SlashingGuard, traits::Context, ActionId, TimerId,
+    },
     types::{TimeDiff, Timestamp},
 };
 
@@ -110,6 +112,42 @@ pub struct EraReport<VID> {
     pub(crate) rewards: BTreeMap<VID, u64>,
     /// Validators that haven't produced any unit during the era.
     pub(crate) inactive_validators: Vec<VID>,
+    /// For equivocators whose offense was cited in the era, the first message citing it.
+    pub(crate) equivocation_reports: BTreeMap<VID, EquivocationReport<VID>>,
+}
+
+/// The first consensus message which cited a validator's equivocation.
+#[derive(Clone, DataSize, Debug, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EquivocationReport<VID> {
+    /// The creator of the message.
+    pub(crate) reporter: VID,
+    /// The hash of the message.
+    pub(crate) message_hash: Digest,
+}
+
+impl<VID: ToBytes> ToBytes for EquivocationReport<VID> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.reporter.to_bytes()?);
+        buffer.extend(self.message_hash.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.reporter.serialized_length() + self.message_hash.serialized_length()
+    }
+}
+
+impl<VID: FromBytes> FromBytes for EquivocationReport<VID> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (reporter, remainder) = VID::from_bytes(bytes)?;
+        let (message_hash, remainder) = Digest::from_bytes(remainder)?;
+        let equivocation_report = EquivocationReport {
+            reporter,
+            message_hash,
+        };
+        Ok((equivocation_report, remainder))
+    }
 }
 
 impl<VID> Default for EraReport<VID>
@@ -121,6 +159,7 @@ where
             equivocators: vec![],
             rewards: BTreeMap::new(),
             inactive_validators: vec![],
+            equivocation_reports: BTreeMap::new(),
         }
     }
 }
@@ -149,16 +188,20 @@ impl<VID> EraReport<VID> {
             equivocators,
             inactive_validators,
             rewards,
+            equivocation_reports,
         } = self;
 
         let hashed_equivocators = hash_slice_of_validators(equivocators);
         let hashed_inactive_validators = hash_slice_of_validators(inactive_validators);
         let hashed_rewards = Digest::hash_btree_map(rewards).expect("Could not hash rewards");
+        let hashed_equivocation_reports = Digest::hash_btree_map(equivocation_reports)
+            .expect("Could not hash equivocation reports");
 
         Digest::hash_slice_rfold(&[
             hashed_equivocators,
             hashed_rewards,
             hashed_inactive_validators,
+            hashed_equivocation_reports,
         ])
     }
 }
@@ -169,6 +212,9 @@ pub(crate) struct TerminalBlockData<C: Context> {
     pub(crate) rewards: BTreeMap<C::ValidatorId, u64>,
     /// The list of validators that haven't produced any units.
     pub(crate) inactive_validators: Vec<C::ValidatorId>,
+    /// Maps equivocators to the creator and hash of the first message which cited their
+    /// equivocation.
+    pub(crate) equivocation_reports: BTreeMap<C::ValidatorId, (C::ValidatorId, C::Hash)>,
 }
 
 /// A finalized block. All nodes are guaranteed to see the same sequence of blocks, and to agree
@@ -189,6 +235,9 @@ pub(crate) struct FinalizedBlock<C: Context> {
     pub(crate) terminal_block_data: Option<TerminalBlockData<C>>,
     /// Proposer of this value
     pub(crate) proposer: C::ValidatorId,
+    /// The validators whose messages contributed to finalizing this value, e.g. the members of the
+    /// summit's highest committee in Highway.
+    pub(crate) summit: Vec<C::ValidatorId>,
 }
 
 pub(crate) type ProtocolOutcomes<I, C> = Vec<ProtocolOutcome<I, C>>;
@@ -270,12 +319,15 @@ pub(crate) trait ConsensusProtocol<I, C: Context>: Send {
     ) -> ProtocolOutcomes<I, C>;
 
     /// Turns this instance into an active validator, that participates in the consensus protocol.
+    ///
+    /// If a `slashing_guard` is given, the protocol checks with it before signing any unit.
     fn activate_validator(
         &mut self,
         our_id: C::ValidatorId,
         secret: C::ValidatorSecret,
         timestamp: Timestamp,
         unit_hash_file: Option<PathBuf>,
+        slashing_guard: Option<SlashingGuard>,
     ) -> ProtocolOutcomes<I, C>;
 
     /// Turns this instance into a passive observer, that does not create any new vertices.
@@ -299,6 +351,10 @@ pub(crate) trait ConsensusProtocol<I, C: Context>: Send {
     /// Returns the list of all validators that were observed as faulty in this consensus instance.
     fn validators_with_evidence(&self) -> Vec<&C::ValidatorId>;
 
+    /// Returns the list of all validators considered faulty in this consensus instance, including
+    /// the ones banned because of faults in earlier eras.
+    fn faulty_validators(&self) -> Vec<&C::ValidatorId>;
+
     /// Returns true if the protocol has received some messages since initialization.
     fn has_received_messages(&self) -> bool;
 
@@ -310,4 +366,7 @@ pub(crate) trait ConsensusProtocol<I, C: Context>: Send {
 
     // TODO: Make this less Highway-specific.
     fn next_round_length(&self) -> Option<TimeDiff>;
+
+    /// Returns our next round exponent, if we are an active validator and the protocol has one.
+    fn next_round_exp(&self) -> Option<u8>;
 }
@@ -8,7 +8,7 @@
 mod era;
 
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     convert::TryInto,
     fmt::{self, Debug, Formatter},
     fs, io,
@@ -26,7 +26,7 @@ use rand::Rng;
 use tracing::{debug, error, info, trace, warn};
 
 use casper_hashing::Digest;
-use casper_types::{AsymmetricType, EraId, PublicKey, SecretKey, U512};
+use casper_types::{AsymmetricType, EraId, ProtocolVersion, PublicKey, U512};
 
 pub use self::era::Era;
 use crate::{
@@ -34,15 +34,19 @@ use crate::{
         cl_context::{ClContext, Keypair},
         config::ProtocolConfig,
         consensus_protocol::{
-            ConsensusProtocol, EraReport, FinalizedBlock as CpFinalizedBlock, ProposedBlock,
-            ProtocolOutcome, ProtocolOutcomes,
+            ConsensusProtocol, EquivocationReport, EraReport, FinalizedBlock as CpFinalizedBlock,
+            ProposedBlock, ProtocolOutcome, ProtocolOutcomes,
         },
         metrics::ConsensusMetrics,
+        signer::{Signer, SigningRequest},
+        slashing_protection::{self, SlashingProtection},
+        status::{ConsensusStatus, FinalizedBlockStatus},
         traits::NodeIdT,
         validator_change::ValidatorChanges,
         ActionId, Config, ConsensusMessage, Event, NewBlockPayload, ReactorEventT, ResolveValidity,
         TimerId, ValidatorChange,
     },
+    components::small_network::Offense,
     effect::{
         announcements::ControlAnnouncement,
         requests::{BlockValidationRequest, ContractRuntimeRequest, StorageRequest},
@@ -61,6 +65,9 @@ use crate::{
 /// fault tolerance threshold.
 const FTT_EXCEEDED_SHUTDOWN_DELAY_MILLIS: u64 = 60 * 1000;
 
+/// The number of recently finalized blocks that are included in the consensus status.
+const RECENT_FINALIZED_BLOCKS: usize = 10;
+
 type ConsensusConstructor<I> = dyn Fn(
         Digest,                    // the era's unique instance ID
         BTreeMap<PublicKey, U512>, // validator weights
@@ -68,6 +75,7 @@ type ConsensusConstructor<I> = dyn Fn(
                                     * this era */
         &HashSet<PublicKey>, // inactive validators that can't be leaders
         &ProtocolConfig,     // the network's chainspec
+        ProtocolVersion,     // the protocol version the era runs under
         &Config,             // The consensus part of the node config.
         Option<&dyn ConsensusProtocol<I, ClContext>>, // previous era's consensus instance
         Timestamp,           // start time for this era
@@ -86,7 +94,9 @@ pub struct EraSupervisor<I> {
     /// This map always contains exactly `2 * bonded_eras + 1` entries, with the last one being the
     /// current one.
     active_eras: HashMap<EraId, Era<I>>,
-    secret_signing_key: Arc<SecretKey>,
+    /// Signs consensus messages and finality signatures with our validator key.
+    #[data_size(skip)]
+    signer: Arc<dyn Signer>,
     public_signing_key: PublicKey,
     current_era: EraId,
     protocol_config: ProtocolConfig,
@@ -106,6 +116,8 @@ pub struct EraSupervisor<I> {
     metrics: ConsensusMetrics,
     /// The path to the folder where unit hash files will be stored.
     unit_hashes_folder: PathBuf,
+    /// The slashing-protection database shared with other nodes using our key, if configured.
+    slashing_protection: Option<SlashingProtection>,
     /// The next upgrade activation point. When the era immediately before the activation point is
     /// deactivated, the era supervisor indicates that the node should stop running to allow an
     /// upgrade.
@@ -114,6 +126,8 @@ pub struct EraSupervisor<I> {
     stop_for_upgrade: bool,
     /// The era that was current when this node joined the network.
     era_where_we_joined: EraId,
+    /// Information about the most recently finalized blocks, oldest first.
+    recent_finalized_blocks: VecDeque<FinalizedBlockStatus>,
 }
 
 impl<I> Debug for EraSupervisor<I> {
@@ -147,8 +161,15 @@ where
             );
         }
         let unit_hashes_folder = config.with_dir(config.value().highway.unit_hashes_folder.clone());
+        let slashing_protection = config
+            .value()
+            .slashing_protection_path
+            .as_ref()
+            .map(|path| SlashingProtection::open(config.with_dir(path.clone())))
+            .transpose()?;
         let (root, config) = config.into_parts();
-        let (secret_signing_key, public_signing_key) = config.load_keys(root)?;
+        let signer = config.load_signer(root)?;
+        let public_signing_key = signer.public_key().clone();
         info!(our_id = %public_signing_key, "EraSupervisor pubkey",);
         let metrics = ConsensusMetrics::new(registry)
             .expect("failure to setup and register ConsensusMetrics");
@@ -159,7 +180,7 @@ where
 
         let era_supervisor = Self {
             active_eras: Default::default(),
-            secret_signing_key,
+            signer,
             public_signing_key,
             current_era,
             protocol_config,
@@ -168,10 +189,12 @@ where
             next_block_height: next_height,
             metrics,
             unit_hashes_folder,
+            slashing_protection,
             next_upgrade_activation_point,
             stop_for_upgrade: false,
             next_executed_height: next_height,
             era_where_we_joined: current_era,
+            recent_finalized_blocks: VecDeque::new(),
         };
 
         let bonded_eras = era_supervisor.bonded_eras();
@@ -246,6 +269,27 @@ where
         result
     }
 
+    /// Returns diagnostic information about the current era and recently finalized blocks.
+    pub(super) fn consensus_status(&self) -> ConsensusStatus {
+        let maybe_consensus = self
+            .active_eras
+            .get(&self.current_era)
+            .map(|era| &era.consensus);
+        let to_owned = |validators: Vec<&PublicKey>| validators.into_iter().cloned().collect();
+        ConsensusStatus {
+            era_id: self.current_era,
+            round_exponent: maybe_consensus.and_then(|consensus| consensus.next_round_exp()),
+            equivocators: maybe_consensus.map_or_else(Vec::new, |consensus| {
+                to_owned(consensus.validators_with_evidence())
+            }),
+            faulty_validators: maybe_consensus.map_or_else(Vec::new, |consensus| {
+                to_owned(consensus.faulty_validators())
+            }),
+            last_finalized_block: self.recent_finalized_blocks.back().cloned(),
+            recent_finalized_blocks: self.recent_finalized_blocks.iter().cloned().collect(),
+        }
+    }
+
     fn era_seed(booking_block_hash: BlockHash, key_block_seed: Digest) -> u64 {
         let result = Digest::hash_pair(booking_block_hash, key_block_seed).value();
         u64::from_le_bytes(result[0..std::mem::size_of::<u64>()].try_into().unwrap())
@@ -280,11 +324,29 @@ where
         (era_id.value()..=era_id.value().saturating_add(num_eras)).map(EraId::from)
     }
 
+    /// Returns the protocol version the given era runs under.
+    ///
+    /// That is the version its key block, the previous era's switch block, was created with, except
+    /// for eras starting at the last activation point, which run under the current version.
+    fn era_protocol_version(
+        &self,
+        era_id: EraId,
+        maybe_key_block: Option<&BlockHeader>,
+    ) -> ProtocolVersion {
+        match maybe_key_block {
+            Some(key_block) if era_id > self.protocol_config.last_activation_point => {
+                key_block.protocol_version()
+            }
+            _ => self.protocol_config.protocol_version,
+        }
+    }
+
     /// Starts a new era; panics if it already exists.
     #[allow(clippy::too_many_arguments)] // FIXME
     fn new_era(
         &mut self,
         era_id: EraId,
+        protocol_version: ProtocolVersion,
         now: Timestamp,
         validators: BTreeMap<PublicKey, U512>,
         new_faulty: Vec<PublicKey>,
@@ -341,6 +403,7 @@ where
             &faulty,
             &inactive,
             &self.protocol_config,
+            protocol_version,
             &self.config,
             prev_era.map(|era| &*era.consensus),
             start_time,
@@ -349,12 +412,17 @@ where
         );
 
         if should_activate {
-            let secret = Keypair::new(self.secret_signing_key.clone(), our_id.clone());
+            let secret = Keypair::new(Arc::clone(&self.signer));
+            let slashing_guard = self
+                .slashing_protection
+                .as_ref()
+                .map(|db| db.guard(our_id.clone(), era_id, instance_id));
             outcomes.extend(consensus.activate_validator(
                 our_id.clone(),
                 secret,
                 now,
                 Some(self.unit_hash_file(&instance_id)),
+                slashing_guard,
             ))
         }
 
@@ -410,6 +478,13 @@ where
                 }
             }
         }
+        // Prune the slashing-protection history at every era switch, including eras we never had
+        // active, e.g. before a restart, so that the database doesn't grow with every era.
+        if let Some(db) = &self.slashing_protection {
+            if let Err(err) = db.prune(&self.public_signing_key, oldest_evidence_era_id) {
+                warn!(%err, "could not prune slashing-protection database");
+            }
+        }
 
         outcomes
     }
@@ -438,6 +513,28 @@ where
         self.update_consensus_pause();
     }
 
+    /// Updates the metrics and the consensus status with a newly finalized block.
+    fn record_finalized_block(&mut self, finalized_block: &FinalizedBlock, summit: Vec<PublicKey>) {
+        self.metrics.finalized_block(finalized_block);
+        if let Some(era) = self.active_eras.get(&finalized_block.era_id()) {
+            let faulty_count = era.consensus.faulty_validators().len();
+            self.metrics.faulty_validators.set(faulty_count as i64);
+        }
+        if self.recent_finalized_blocks.len() >= RECENT_FINALIZED_BLOCKS {
+            self.recent_finalized_blocks.pop_front();
+        }
+        self.recent_finalized_blocks
+            .push_back(FinalizedBlockStatus {
+                block_hash: None,
+                era_id: finalized_block.era_id(),
+                height: finalized_block.height(),
+                proposer: finalized_block.proposer(),
+                proposed_at: finalized_block.timestamp(),
+                finalized_at: Timestamp::now(),
+                summit,
+            });
+    }
+
     /// Pauses or unpauses consensus: Whenever the last executed block is too far behind the last
     /// finalized block, we suspend consensus.
     fn update_consensus_pause(&mut self) {
@@ -518,8 +615,10 @@ where
                 .flat_map(|era_end| era_end.equivocators.clone())
                 .collect();
 
+            let protocol_version = self.era_protocol_version(era_id, key_blocks.get(&era_id));
             let results = self.new_era(
                 era_id,
+                protocol_version,
                 now,
                 validators,
                 new_faulty,
@@ -565,6 +664,23 @@ where
         self.bonded_eras().min(self.protocol_config.auction_delay)
     }
 
+    /// Records our finality signature for the block in the slashing-protection database, if
+    /// configured. Returns an error if we must not sign it.
+    fn check_finality_signature(
+        &self,
+        block_header: &BlockHeader,
+    ) -> Result<(), slashing_protection::Error> {
+        match &self.slashing_protection {
+            None => Ok(()),
+            Some(db) => db.check_finality_signature(
+                &self.public_signing_key,
+                block_header.era_id(),
+                block_header.height(),
+                block_header.hash(),
+            ),
+        }
+    }
+
     /// Returns the path to the era's unit hash file.
     fn unit_hash_file(&self, instance_id: &Digest) -> PathBuf {
         self.unit_hashes_folder.join(format!(
@@ -787,20 +903,49 @@ where
 
     pub(super) fn handle_block_added(&mut self, block_header: BlockHeader) -> Effects<Event<I>> {
         let our_pk = self.era_supervisor.public_signing_key.clone();
-        let our_sk = self.era_supervisor.secret_signing_key.clone();
         let era_id = block_header.era_id();
         self.era_supervisor.executed_block(&block_header);
-        let mut effects = if self.era_supervisor.is_validator_in(&our_pk, era_id) {
-            self.effect_builder
-                .announce_created_finality_signature(FinalitySignature::new(
-                    block_header.hash(),
-                    era_id,
-                    &our_sk,
-                    our_pk,
-                ))
-                .ignore()
-        } else {
+        if let Some(status) = self
+            .era_supervisor
+            .recent_finalized_blocks
+            .iter_mut()
+            .find(|status| status.height == block_header.height())
+        {
+            status.block_hash = Some(block_header.hash());
+        }
+        let mut effects = if !self.era_supervisor.is_validator_in(&our_pk, era_id) {
+            Effects::new()
+        } else if let Err(err) = self.era_supervisor.check_finality_signature(&block_header) {
+            error!(%err, "not signing block");
             Effects::new()
+        } else {
+            let block_hash = block_header.hash();
+            let request = SigningRequest::FinalitySignature { block_hash, era_id };
+            let signer = Arc::clone(&self.era_supervisor.signer);
+            let effect_builder = self.effect_builder;
+            // Signing may require a round trip to a remote signer, so it must not block the
+            // reactor.
+            async move {
+                match tokio::task::spawn_blocking(move || signer.sign(&request)).await {
+                    Ok(Ok(signature)) => {
+                        effect_builder
+                            .announce_created_finality_signature(FinalitySignature {
+                                block_hash,
+                                era_id,
+                                signature,
+                                public_key: our_pk,
+                            })
+                            .await
+                    }
+                    Ok(Err(err)) => {
+                        error!(%err, %block_hash, "failed to create finality signature");
+                    }
+                    Err(err) => {
+                        error!(%err, %block_hash, "finality signature task failed");
+                    }
+                }
+            }
+            .ignore()
         };
         if era_id < self.era_supervisor.current_era {
             trace!(era = era_id.value(), "executed block in old era");
@@ -933,8 +1078,12 @@ where
             .collect();
         let now = Timestamp::now(); // TODO: This should be passed in.
         #[allow(clippy::integer_arithmetic)] // Block height should never reach u64::MAX.
+        let protocol_version = self
+            .era_supervisor
+            .era_protocol_version(era_id, Some(&switch_block_header));
         let mut outcomes = self.era_supervisor.new_era(
             era_id,
+            protocol_version,
             now,
             next_era_validators_weights.clone(),
             new_faulty,
@@ -970,7 +1119,7 @@ where
                 era = %era_id.value(),
                 "invalid consensus value; disconnecting from the sender"
             );
-            effects.extend(self.disconnect(sender));
+            effects.extend(self.disconnect(sender, Offense::InvalidProposal));
         }
         if self
             .era_supervisor
@@ -1026,14 +1175,14 @@ where
                     %error,
                     "invalid incoming message to consensus instance; disconnecting from the sender"
                 );
-                self.disconnect(sender)
+                self.disconnect(sender, Offense::InvalidConsensusMessage)
             }
             ProtocolOutcome::Disconnect(sender) => {
                 warn!(
                     %sender,
                     "disconnecting from the sender of invalid data"
                 );
-                self.disconnect(sender)
+                self.disconnect(sender, Offense::InvalidConsensusMessage)
             }
             ProtocolOutcome::CreatedGossipMessage(payload) => {
                 let message = ConsensusMessage::Protocol { era_id, payload };
@@ -1104,6 +1253,7 @@ where
                 terminal_block_data,
                 equivocators,
                 proposer,
+                summit,
             }) => {
                 if era_id != self.era_supervisor.current_era {
                     debug!(era = era_id.value(), "finalized block in old era");
@@ -1114,10 +1264,26 @@ where
                 era.add_accusations(value.accusations());
                 // If this is the era's last block, it contains rewards. Everyone who is accused in
                 // the block or seen as equivocating via the consensus protocol gets faulty.
-                let era_end = terminal_block_data.map(|tbd| EraReport {
-                    rewards: tbd.rewards,
-                    equivocators: era.accusations(),
-                    inactive_validators: tbd.inactive_validators,
+                let era_end = terminal_block_data.map(|tbd| {
+                    let equivocators = era.accusations();
+                    let equivocation_reports = tbd
+                        .equivocation_reports
+                        .into_iter()
+                        .filter(|(equivocator, _)| equivocators.contains(equivocator))
+                        .map(|(equivocator, (reporter, message_hash))| {
+                            let report = EquivocationReport {
+                                reporter,
+                                message_hash,
+                            };
+                            (equivocator, report)
+                        })
+                        .collect();
+                    EraReport {
+                        rewards: tbd.rewards,
+                        equivocators,
+                        inactive_validators: tbd.inactive_validators,
+                        equivocation_reports,
+                    }
                 });
                 let finalized_block = FinalizedBlock::new(
                     Arc::try_unwrap(value).unwrap_or_else(|arc| (*arc).clone()),
@@ -1131,8 +1297,7 @@ where
                         height=?finalized_block.height(),
                         timestamp=?finalized_block.timestamp(), "finalized block");
                 self.era_supervisor
-                    .metrics
-                    .finalized_block(&finalized_block);
+                    .record_finalized_block(&finalized_block, summit);
                 // Announce the finalized block.
                 let mut effects = self
                     .effect_builder
@@ -1277,9 +1442,9 @@ where
         responder.respond(Some((public_key, round_length))).ignore()
     }
 
-    fn disconnect(&self, sender: I) -> Effects<Event<I>> {
+    fn disconnect(&self, sender: I, offense: Offense) -> Effects<Event<I>> {
         self.effect_builder
-            .announce_disconnect_from_peer(sender)
+            .announce_offense(sender, offense)
             .ignore()
     }
 
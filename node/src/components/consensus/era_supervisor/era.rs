@@ -14,7 +14,7 @@ use crate::{
     components::consensus::{
         cl_context::ClContext,
         consensus_protocol::{ConsensusProtocol, ProposedBlock},
-        protocols::highway::HighwayProtocol,
+        protocols::{highway::HighwayProtocol, simple::SimpleConsensus},
     },
     types::Timestamp,
 };
@@ -207,10 +207,12 @@ where
                 } else {
                     (*highway).estimate_heap_size()
                 }
+            } else if let Some(simple) = any_ref.downcast_ref::<SimpleConsensus<I, ClContext>>() {
+                (*simple).estimate_heap_size()
             } else {
                 warn!(
-                    "could not downcast consensus protocol to \
-                    HighwayProtocol<I, ClContext> to determine heap allocation size"
+                    "could not downcast consensus protocol to a known protocol type to determine \
+                    heap allocation size"
                 );
                 0
             }
@@ -41,7 +41,8 @@ pub(crate) mod validators;
 
 mod endorsement;
 mod evidence;
-#[cfg(test)]
+#[cfg(any(test, feature = "consensus-sim"))]
+#[cfg_attr(not(test), allow(dead_code))] // Parts of the test harness are only used in tests.
 pub(crate) mod highway_testing;
 
 pub(crate) use state::{State, Weight};
@@ -21,6 +21,7 @@ use crate::{
     components::consensus::{
         consensus_protocol::BlockContext,
         highway_core::{highway::SignedWireUnit, state::Fault},
+        slashing_protection::SlashingGuard,
         traits::{Context, ValidatorSecret},
     },
     types::{TimeDiff, Timestamp},
@@ -73,6 +74,8 @@ where
     next_proposal: Option<(BlockContext<C>, Panorama<C>)>,
     /// The path to the file storing the hash of our latest known unit (if any).
     unit_file: Option<PathBuf>,
+    /// The slashing-protection database check for new units, if configured.
+    slashing_guard: Option<SlashingGuard>,
     /// The last known unit created by us.
     own_last_unit: Option<SignedWireUnit<C>>,
     /// The target fault tolerance threshold. The validator pauses (i.e. doesn't create new units)
@@ -103,6 +106,7 @@ impl<C: Context> ActiveValidator<C> {
         start_time: Timestamp,
         state: &State<C>,
         unit_file: Option<PathBuf>,
+        slashing_guard: Option<SlashingGuard>,
         target_ftt: Weight,
         instance_id: C::InstanceId,
     ) -> (Self, Vec<Effect<C>>) {
@@ -123,12 +127,13 @@ impl<C: Context> ActiveValidator<C> {
             next_timer: state.params().start_timestamp(),
             next_proposal: None,
             unit_file,
+            slashing_guard,
             own_last_unit,
             target_ftt,
             paused: false,
         };
         let mut effects = av.schedule_timer(start_time, state);
-        effects.push(av.send_ping(current_time, instance_id));
+        effects.extend(av.send_ping(current_time, instance_id));
         (av, effects)
     }
 
@@ -216,15 +221,19 @@ impl<C: Context> ActiveValidator<C> {
         let one_max_round_ago = timestamp.saturating_sub(state.params().max_round_length());
         if !state.has_ping(self.vidx, one_max_round_ago + 1.into()) {
             warn!(%timestamp, "too many validators offline, sending ping");
-            effects.push(self.send_ping(timestamp, instance_id));
+            effects.extend(self.send_ping(timestamp, instance_id));
         }
         effects
     }
 
-    /// Creates a Ping vertex.
-    pub(crate) fn send_ping(&self, timestamp: Timestamp, instance_id: C::InstanceId) -> Effect<C> {
-        let ping = Ping::new(self.vidx, timestamp, instance_id, &self.secret);
-        Effect::NewVertex(ValidVertex(Vertex::Ping(ping)))
+    /// Creates a Ping vertex, or returns `None` if it could not be signed.
+    pub(crate) fn send_ping(
+        &self,
+        timestamp: Timestamp,
+        instance_id: C::InstanceId,
+    ) -> Option<Effect<C>> {
+        let ping = Ping::new(self.vidx, timestamp, instance_id, &self.secret)?;
+        Some(Effect::NewVertex(ValidVertex(Vertex::Ping(ping))))
     }
 
     /// Returns whether enough validators are online to finalize values with the target fault
@@ -267,8 +276,9 @@ impl<C: Context> ActiveValidator<C> {
             }
         };
         if self.should_endorse(uhash, state) {
-            let endorsement = self.endorse(uhash);
-            effects.extend(vec![Effect::NewVertex(ValidVertex(endorsement))]);
+            if let Some(endorsement) = self.endorse(uhash) {
+                effects.push(Effect::NewVertex(ValidVertex(endorsement)));
+            }
         }
         effects
     }
@@ -288,7 +298,7 @@ impl<C: Context> ActiveValidator<C> {
                 let unit = state.unit(v);
                 unit.new_hash_obs(state, vidx)
             })
-            .map(|v| self.endorse(v))
+            .filter_map(|v| self.endorse(v))
             .map(|endorsement| Effect::NewVertex(ValidVertex(endorsement)))
             .collect()
     }
@@ -455,7 +465,13 @@ impl<C: Context> ActiveValidator<C> {
             endorsed,
         }
         .into_hashed();
-        let swunit = SignedWireUnit::new(hwunit, &self.secret);
+        if let Some(guard) = &self.slashing_guard {
+            if let Err(err) = guard.check_unit(seq_number, timestamp, &hwunit.hash()) {
+                error!(%err, "canceling unit creation");
+                return None;
+            }
+        }
+        let swunit = SignedWireUnit::new(hwunit, &self.secret)?;
         write_last_unit(&self.unit_file, swunit.clone()).unwrap_or_else(|err| {
             panic!(
                 "should successfully write unit's hash to {:?}, got {:?}",
@@ -553,11 +569,13 @@ impl<C: Context> ActiveValidator<C> {
                 .any(|(vidx, _)| state.is_faulty(vidx) && unit.new_hash_obs(state, vidx))
     }
 
-    /// Creates endorsement of the `vhash`.
-    fn endorse(&self, vhash: &C::Hash) -> Vertex<C> {
+    /// Creates endorsement of the `vhash`, or returns `None` if it could not be signed.
+    fn endorse(&self, vhash: &C::Hash) -> Option<Vertex<C>> {
         let endorsement = Endorsement::new(*vhash, self.vidx);
-        let signature = self.secret.sign(&endorsement.hash());
-        Vertex::Endorsements(SignedEndorsement::new(endorsement, signature).into())
+        let signature = self.secret.sign(&endorsement.hash())?;
+        Some(Vertex::Endorsements(
+            SignedEndorsement::new(endorsement, signature).into(),
+        ))
     }
 
     /// Returns a panorama that is valid to use in our own unit at the given timestamp.
@@ -610,6 +628,11 @@ impl<C: Context> ActiveValidator<C> {
     pub(crate) fn next_round_length(&self) -> TimeDiff {
         state::round_len(self.next_round_exp)
     }
+
+    /// Returns the round exponent of our next round.
+    pub(crate) fn next_round_exp(&self) -> u8 {
+        self.next_round_exp
+    }
 }
 
 pub(crate) fn read_last_unit<C, P>(path: P) -> io::Result<SignedWireUnit<C>>
@@ -718,6 +741,7 @@ mod tests {
                     start_time,
                     &state,
                     None,
+                    None,
                     target_ftt,
                     TEST_INSTANCE_ID,
                 );
@@ -955,6 +979,7 @@ mod tests {
             410.into(),
             &state,
             None,
+            None,
             Weight(2),
             TEST_INSTANCE_ID,
         );
@@ -978,11 +1003,14 @@ mod tests {
             410.into(),
             &state,
             None,
+            None,
             Weight(2),
             TEST_INSTANCE_ID,
         );
 
-        let ping = Vertex::Ping(Ping::new(ALICE, 500.into(), TEST_INSTANCE_ID, &ALICE_SEC));
+        let ping = Vertex::Ping(
+            Ping::new(ALICE, 500.into(), TEST_INSTANCE_ID, &ALICE_SEC).expect("should sign ping"),
+        );
 
         // The ping is suspicious if it is newer than the latest ping (or unit) that has been added
         // to the state.
@@ -1029,6 +1057,7 @@ mod tests {
             410.into(),
             &state,
             unit_file,
+            None,
             Weight(2),
             TEST_INSTANCE_ID,
         );
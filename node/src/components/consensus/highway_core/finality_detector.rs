@@ -1,7 +1,10 @@
 mod horizon;
 mod rewards;
 
-use std::iter;
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    iter,
+};
 
 use datasize::DataSize;
 use tracing::{trace, warn};
@@ -60,9 +63,10 @@ impl<C: Context> FinalityDetector<C> {
             return Err(FttExceeded(fault_w));
         }
         Ok(iter::from_fn(move || {
-            let bhash = self.next_finalized(state)?;
+            let (bhash, lvl) = self.next_finalized_with_level(state)?;
             // Safe to unwrap: Index exists, since we have units from them.
             let to_id = |vidx: ValidatorIndex| highway.validators().id(vidx).unwrap().clone();
+            let summit = self.summit_committee(lvl, bhash, state);
             let block = state.block(bhash);
             let unit = state.unit(bhash);
             let terminal_block_data = state
@@ -75,6 +79,7 @@ impl<C: Context> FinalityDetector<C> {
                 terminal_block_data,
                 equivocators: unit.panorama.iter_faulty().map(to_id).collect(),
                 proposer: to_id(unit.creator),
+                summit: summit.into_iter().map(to_id).collect(),
             };
             trace!(panorama = ?state.panorama(), ?finalized_block, "finality detected");
             Some(finalized_block)
@@ -82,7 +87,18 @@ impl<C: Context> FinalityDetector<C> {
     }
 
     /// Returns the next block, if any has been finalized since the last call.
+    #[cfg(test)]
     pub(super) fn next_finalized<'a>(&mut self, state: &'a State<C>) -> Option<&'a C::Hash> {
+        self.next_finalized_with_level(state)
+            .map(|(bhash, _)| bhash)
+    }
+
+    /// Returns the next block, if any has been finalized since the last call, together with the
+    /// level of the summit that finalized it.
+    fn next_finalized_with_level<'a>(
+        &mut self,
+        state: &'a State<C>,
+    ) -> Option<(&'a C::Hash, usize)> {
         let start_time = Timestamp::now();
         let candidate = self.next_candidate(state)?;
         // For `lvl` → ∞, the quorum converges to a fixed value. After level 63, it is closer
@@ -96,7 +112,7 @@ impl<C: Context> FinalityDetector<C> {
                 self.last_finalized = Some(*candidate);
                 let elapsed = start_time.elapsed();
                 trace!(%elapsed, "found finalized block");
-                return Some(candidate);
+                return Some((candidate, lvl));
             }
             // The required quorum increases with decreasing level, so choosing `target_lvl`
             // greater than `lvl` would always yield a summit of level `lvl` or lower.
@@ -124,6 +140,23 @@ impl<C: Context> FinalityDetector<C> {
         horizons_iter.skip(1).take(target_lvl).count()
     }
 
+    /// Returns the validators in the highest committee of the level-`lvl` summit for `candidate`,
+    /// i.e. the ones whose units contributed to finalizing it.
+    fn summit_committee(
+        &self,
+        lvl: usize,
+        candidate: &C::Hash,
+        state: &State<C>,
+    ) -> Vec<ValidatorIndex> {
+        let quorum = self.quorum_for_lvl(lvl, state.total_weight());
+        let latest = state.panorama().iter().map(Observation::correct).collect();
+        let sec0 = Horizon::level0(candidate, state, &latest);
+        iter::successors(Some(sec0), |sec| sec.next(quorum))
+            .take(lvl.saturating_add(1))
+            .last()
+            .map_or_else(Vec::new, |sec| sec.committee())
+    }
+
     /// Returns the quorum required by a summit with the specified level and the required FTT.
     #[allow(clippy::integer_arithmetic)] // See comments.
     fn quorum_for_lvl(&self, lvl: usize, total_w: Weight) -> Weight {
@@ -196,11 +229,57 @@ impl<C: Context> FinalityDetector<C> {
             Vec::new()
         };
 
+        let equivocation_reports = Self::first_citations(bhash, unit, state)
+            .into_iter()
+            .map(|(vidx, (_, creator, hash))| (to_id(vidx), (to_id(creator), *hash)))
+            .collect();
+
         TerminalBlockData {
             rewards,
             inactive_validators,
+            equivocation_reports,
         }
     }
+
+    /// Returns, for each validator that `unit` sees as faulty, the timestamp, creator and hash of
+    /// the earliest unit citing them as faulty, among `unit` and the units it cites.
+    ///
+    /// The result only depends on `unit` and its justifications, so all nodes agree on it.
+    fn first_citations<'a>(
+        bhash: &'a C::Hash,
+        unit: &'a Unit<C>,
+        state: &'a State<C>,
+    ) -> BTreeMap<ValidatorIndex, (Timestamp, ValidatorIndex, &'a C::Hash)> {
+        let mut first_citations = BTreeMap::new();
+        if unit.panorama.iter_faulty().next().is_none() {
+            return first_citations;
+        }
+        // Since a unit sees everything its creator's previous unit saw, it suffices to follow
+        // each swimlane back as long as its units cite any faults.
+        for latest_hash in unit.panorama.iter_correct_hashes().chain(iter::once(bhash)) {
+            for (hash, swimlane_unit) in state.swimlane(latest_hash) {
+                let citation = (swimlane_unit.timestamp, swimlane_unit.creator, hash);
+                let mut cites_faults = false;
+                for faulty_vidx in swimlane_unit.panorama.iter_faulty() {
+                    cites_faults = true;
+                    match first_citations.entry(faulty_vidx) {
+                        Entry::Vacant(entry) => {
+                            entry.insert(citation);
+                        }
+                        Entry::Occupied(mut entry) => {
+                            if citation < *entry.get() {
+                                entry.insert(citation);
+                            }
+                        }
+                    }
+                }
+                if !cites_faults {
+                    break;
+                }
+            }
+        }
+        first_citations
+    }
 }
 
 #[allow(unused_qualifications)] // This is to suppress warnings originating in the test macros.
@@ -249,6 +328,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn summit_committee() -> Result<(), AddUnitError<TestContext>> {
+        let mut state = State::new_test(&[Weight(5), Weight(4), Weight(1)], 0);
+        let mut fd4 = FinalityDetector::new(Weight(4)); // Fault tolerance 4.
+
+        // `a0`, `b1` form a level-1 summit for `B0`; Carol's units don't see it.
+        let b0 = add_unit!(state, BOB, 0xB0; N, N, N)?;
+        let c0 = add_unit!(state, CAROL, 0xC0; N, b0, N)?;
+        let _c1 = add_unit!(state, CAROL, 0xC1; N, b0, c0)?;
+        let a0 = add_unit!(state, ALICE, 0xA0; N, b0, N)?;
+        let _b1 = add_unit!(state, BOB, 0xB1; a0, b0, N)?;
+
+        let (bhash, lvl) = fd4
+            .next_finalized_with_level(&state)
+            .expect("B0 is finalized");
+        assert_eq!((&b0, 1), (bhash, lvl));
+        let mut summit = fd4.summit_committee(lvl, bhash, &state);
+        summit.sort();
+        assert_eq!(vec![ALICE, BOB], summit);
+        Ok(())
+    }
+
     #[test]
     fn equivocators() -> Result<(), AddUnitError<TestContext>> {
         let mut state = State::new_test(&[Weight(5), Weight(4), Weight(1)], 0);
@@ -292,4 +393,27 @@ mod tests {
         assert_eq!(Some(&a0), fde4.next_finalized(&bstate));
         Ok(())
     }
+
+    #[test]
+    fn first_citations() -> Result<(), AddUnitError<TestContext>> {
+        let mut state = State::new_test(&[Weight(5), Weight(4), Weight(1)], 0);
+        let c0 = add_unit!(state, CAROL, 0xC0; N, N, N)?;
+        let _c0_prime = add_unit!(state, CAROL, 0xCC0; N, N, N)?;
+        let b0 = add_unit!(state, BOB, 0xB0; N, N, F)?;
+        let a0 = add_unit!(state, ALICE, None; N, b0, F)?;
+        let a1 = add_unit!(state, ALICE, None; a0, b0, F)?;
+
+        // Carol's own unit doesn't cite any faults.
+        let citations = FinalityDetector::first_citations(&c0, state.unit(&c0), &state);
+        assert!(citations.is_empty());
+
+        // Bob cited Carol's equivocation before Alice did.
+        let citations = FinalityDetector::first_citations(&a1, state.unit(&a1), &state);
+        assert_eq!(1, citations.len());
+        let (timestamp, creator, hash) = citations[&CAROL];
+        assert_eq!(state.unit(&b0).timestamp, timestamp);
+        assert_eq!(BOB, creator);
+        assert_eq!(&b0, hash);
+        Ok(())
+    }
 }
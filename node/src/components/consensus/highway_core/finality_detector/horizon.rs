@@ -66,6 +66,11 @@ impl<'a, C: Context> Horizon<'a, C> {
         }
     }
 
+    /// Returns the members of the committee this horizon belongs to.
+    pub(super) fn committee(&self) -> Committee {
+        self.sequence_numbers.keys_some().collect()
+    }
+
     /// Returns the greatest subset of the `committee` of validators whose latest units can see a
     /// quorum of units by the subset in `self`.
     ///
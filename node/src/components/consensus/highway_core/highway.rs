@@ -20,6 +20,7 @@ use crate::{
             state::{Fault, State, UnitError, Weight},
             validators::{Validator, Validators},
         },
+        slashing_protection::SlashingGuard,
         traits::Context,
     },
     types::{TimeDiff, Timestamp},
@@ -78,7 +79,7 @@ impl<C: Context> PreValidatedVertex<C> {
         self.0.timestamp()
     }
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "consensus-sim"))]
     pub(crate) fn into_vertex(self) -> Vertex<C> {
         self.0
     }
@@ -196,6 +197,7 @@ impl<C: Context> Highway<C> {
         secret: C::ValidatorSecret,
         current_time: Timestamp,
         unit_hash_file: Option<PathBuf>,
+        slashing_guard: Option<SlashingGuard>,
         target_ftt: Weight,
     ) -> Vec<Effect<C>> {
         if self.active_validator.is_some() {
@@ -217,6 +219,7 @@ impl<C: Context> Highway<C> {
             start_time,
             &self.state,
             unit_hash_file,
+            slashing_guard,
             target_ftt,
             self.instance_id,
         );
@@ -474,6 +477,13 @@ impl<C: Context> Highway<C> {
             .map(|(_, v_id)| v_id)
     }
 
+    /// Returns an iterator over the IDs of all validators considered faulty.
+    pub(crate) fn faulty_validators(&self) -> impl Iterator<Item = &C::ValidatorId> {
+        self.state
+            .faulty_validators()
+            .filter_map(move |idx| self.validators.id(idx))
+    }
+
     pub(crate) fn state(&self) -> &State<C> {
         &self.state
     }
@@ -710,6 +720,11 @@ impl<C: Context> Highway<C> {
             .map(|av| av.next_round_length())
     }
 
+    /// Returns our next round exponent, if we are an active validator.
+    pub(crate) fn next_round_exp(&self) -> Option<u8> {
+        self.active_validator.as_ref().map(|av| av.next_round_exp())
+    }
+
     /// Logs a message if this is a block and any previous blocks were skipped.
     fn log_if_missing_proposal(&self, unit_hash: &C::Hash) {
         let state = &self.state;
@@ -832,7 +847,7 @@ pub(crate) mod tests {
         assert_eq!(Err(expected), highway.pre_validate_vertex(invalid_vertex));
 
         let hwunit = wunit.into_hashed();
-        let valid_signature = CAROL_SEC.sign(&hwunit.hash());
+        let valid_signature = CAROL_SEC.sign(&hwunit.hash()).expect("should sign unit");
         let correct_signature_unit = SignedWireUnit {
             hashed_wire_unit: hwunit,
             signature: valid_signature,
@@ -926,9 +941,9 @@ pub(crate) mod tests {
                         wunit1: &WireUnit<TestContext>,
                         signer1: &TestSecret| {
             let hwunit0 = wunit0.clone().into_hashed();
-            let swunit0 = SignedWireUnit::new(hwunit0, signer0);
+            let swunit0 = SignedWireUnit::new(hwunit0, signer0).expect("should sign unit");
             let hwunit1 = wunit1.clone().into_hashed();
-            let swunit1 = SignedWireUnit::new(hwunit1, signer1);
+            let swunit1 = SignedWireUnit::new(hwunit1, signer1).expect("should sign unit");
             let evidence = Evidence::Equivocation(swunit0, swunit1);
             let vertex = Vertex::Evidence(evidence);
             highway
@@ -1021,8 +1036,9 @@ pub(crate) mod tests {
 
         // Ping by validator that is not bonded, with an index that is outside of boundaries of the
         // state.
-        let ping: Vertex<TestContext> =
-            Vertex::Ping(Ping::new(DAN, now, TEST_INSTANCE_ID, &DAN_SEC));
+        let ping: Vertex<TestContext> = Vertex::Ping(
+            Ping::new(DAN, now, TEST_INSTANCE_ID, &DAN_SEC).expect("should sign ping"),
+        );
         assert!(
             DAN.0 >= WEIGHTS.len() as u32,
             "should use validator that is not bonded"
@@ -1046,11 +1062,15 @@ pub(crate) mod tests {
         };
 
         let _effects =
-            highway.activate_validator(ALICE.0, ALICE_SEC.clone(), now, None, target_ftt);
+            highway.activate_validator(ALICE.0, ALICE_SEC.clone(), now, None, None, target_ftt);
 
-        let ping = Vertex::Ping(Ping::new(ALICE, now, TEST_INSTANCE_ID, &ALICE_SEC));
+        let ping = Vertex::Ping(
+            Ping::new(ALICE, now, TEST_INSTANCE_ID, &ALICE_SEC).expect("should sign ping"),
+        );
         assert!(!highway.is_doppelganger_vertex(&ping));
-        let ping = Vertex::Ping(Ping::new(ALICE, later, TEST_INSTANCE_ID, &ALICE_SEC));
+        let ping = Vertex::Ping(
+            Ping::new(ALICE, later, TEST_INSTANCE_ID, &ALICE_SEC).expect("should sign ping"),
+        );
         assert!(highway.is_doppelganger_vertex(&ping));
     }
 }
@@ -1,5 +1,7 @@
 #![allow(clippy::integer_arithmetic)] // In tests, overflows panic anyway.
 
+pub mod simulator;
+
 use std::{
     collections::{hash_map::DefaultHasher, HashMap, VecDeque},
     fmt::{self, Debug, Display, Formatter},
@@ -143,6 +145,8 @@ pub(crate) enum TestRunError {
     SenderMissingDependency(ValidatorId, Dependency<TestContext>),
     /// No more messages in the message queue.
     NoMessages,
+    /// The weight of faulty validators exceeded the validator's fault tolerance threshold.
+    FttExceeded(ValidatorId),
 }
 
 impl Display for TestRunError {
@@ -160,6 +164,11 @@ impl Display for TestRunError {
             TestRunError::MissingValidator(id) => {
                 write!(f, "Virtual net is missing validator {:?}.", id)
             }
+            TestRunError::FttExceeded(id) => write!(
+                f,
+                "Faulty validators exceeded the fault tolerance threshold of {:?}.",
+                id
+            ),
         }
     }
 }
@@ -192,6 +201,8 @@ struct HighwayValidator {
     highway: Highway<TestContext>,
     finality_detector: FinalityDetector<TestContext>,
     fault: Option<DesFault>,
+    /// All blocks finalized by this validator, in order.
+    finalized_blocks: Vec<FinalizedBlock<TestContext>>,
 }
 
 impl HighwayValidator {
@@ -204,6 +215,7 @@ impl HighwayValidator {
             highway,
             finality_detector,
             fault,
+            finalized_blocks: Vec::new(),
         }
     }
 
@@ -216,7 +228,10 @@ impl HighwayValidator {
     }
 
     fn run_finality(&mut self) -> Result<Vec<FinalizedBlock<TestContext>>, FttExceeded> {
-        Ok(self.finality_detector.run(&self.highway)?.collect())
+        let finalized_blocks: Vec<_> = self.finality_detector.run(&self.highway)?.collect();
+        self.finalized_blocks
+            .extend(finalized_blocks.iter().cloned());
+        Ok(finalized_blocks)
     }
 
     fn post_hook(&mut self, delivery_time: Timestamp, msg: HighwayMessage) -> Vec<HighwayMessage> {
@@ -276,7 +291,8 @@ impl HighwayValidator {
                                 }
                                 let secret = TestSecret(wunit2.creator.0.into());
                                 let hwunit2 = wunit2.into_hashed();
-                                let swunit2 = SignedWireUnit::new(hwunit2, &secret);
+                                let swunit2 = SignedWireUnit::new(hwunit2, &secret)
+                                    .expect("should sign unit");
                                 let vertex2 = Box::new(Vertex::Unit(swunit2));
                                 vec![msg, HighwayMessage::NewVertex(vertex2)]
                             }
@@ -490,8 +506,7 @@ where
         let finalized_values = recipient
             .validator_mut()
             .run_finality()
-            // TODO: https://casperlabs.atlassian.net/browse/HWY-119
-            .expect("FTT exceeded but not handled");
+            .map_err(|_| TestRunError::FttExceeded(*validator_id))?;
         for FinalizedBlock {
             value,
             timestamp: _,
@@ -499,6 +514,7 @@ where
             terminal_block_data,
             equivocators: _,
             proposer: _,
+            summit: _,
         } in finalized_values
         {
             trace!(
@@ -933,7 +949,8 @@ impl<DS: DeliveryStrategy> HighwayTestHarnessBuilder<DS> {
                 let v_sec = secrets.remove(&vid).expect("Secret key should exist.");
 
                 let mut highway = Highway::new(instance_id, validators.clone(), params.clone());
-                let effects = highway.activate_validator(vid, v_sec, start_time, None, Weight(ftt));
+                let effects =
+                    highway.activate_validator(vid, v_sec, start_time, None, None, Weight(ftt));
 
                 let finality_detector = FinalityDetector::new(Weight(ftt));
 
@@ -1030,8 +1047,8 @@ impl ValidatorSecret for TestSecret {
     type Hash = HashWrapper;
     type Signature = SignatureWrapper;
 
-    fn sign(&self, data: &Self::Hash) -> Self::Signature {
-        SignatureWrapper(data.0 + self.0)
+    fn sign(&self, data: &Self::Hash) -> Option<Self::Signature> {
+        Some(SignatureWrapper(data.0 + self.0))
     }
 }
 
@@ -1059,6 +1076,7 @@ impl Context for TestContext {
     }
 }
 
+#[cfg(test)]
 mod test_harness {
     use std::{collections::HashSet, fmt::Debug};
 
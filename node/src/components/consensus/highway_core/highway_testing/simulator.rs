@@ -0,0 +1,833 @@
+//! A simulator running a single Highway era in a virtual network with configurable faults.
+//!
+//! The simulation is described by a [`Scenario`], usually parsed from a TOML file. It uses the
+//! discrete event simulation of the Highway test harness, with virtual time, so the results only
+//! depend on the scenario and its random seed. The resulting [`Report`] contains the distribution
+//! of finality latencies, the number of orphaned units and the reward factors per validator.
+
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::trace;
+
+use casper_types::system::auction::BLOCK_REWARD;
+
+use super::{
+    ConsensusValue, Distribution, HighwayMessage, HighwayNode, HighwayTestHarness,
+    HighwayValidator, InstantDeliveryNoDropping, TestRunError, TestSecret,
+};
+use crate::{
+    components::consensus::{
+        highway_core::{
+            finality_detector::FinalityDetector,
+            highway::{Highway, Params, Vertex},
+            state,
+            validators::Validators,
+            Weight,
+        },
+        tests::{
+            consensus_des_testing::{
+                Fault as DesFault, Message, Node, Target, TargetedMessage, ValidatorId, VirtualNet,
+            },
+            queue::QueueEntry,
+        },
+    },
+    types::{chainspec::HighwayConfig, TimeDiff, Timestamp},
+    NodeRng,
+};
+
+/// The maximum endorsement evidence limit, as used by the node.
+const MAX_ENDORSEMENT_EVIDENCE_LIMIT: u64 = 10_000;
+
+/// An error parsing a scenario or running a simulation.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The scenario is not valid TOML or has missing or unknown fields.
+    #[error("failed to parse scenario: {0}")]
+    Parse(#[from] toml::de::Error),
+    /// The scenario's values are inconsistent.
+    #[error("invalid scenario: {0}")]
+    InvalidScenario(String),
+    /// The simulation failed.
+    #[error("simulation failed: {0}")]
+    Simulation(String),
+}
+
+impl From<TestRunError> for Error {
+    fn from(error: TestRunError) -> Self {
+        Error::Simulation(error.to_string())
+    }
+}
+
+/// The message latency: Each message is delayed by a uniformly distributed random duration
+/// between the minimum and maximum.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Latency {
+    minimum: TimeDiff,
+    maximum: TimeDiff,
+}
+
+/// A fault of a single validator.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Fault {
+    /// The validator signs two conflicting versions of each of its units.
+    Equivocate,
+    /// The validator stops permanently at the given time after the start of the era.
+    Crash {
+        /// The time after the start of the era at which the validator crashes.
+        at: TimeDiff,
+    },
+    /// The validator doesn't send any units in the given interval after the start of the era.
+    Mute {
+        /// The time after the start of the era at which the validator goes silent.
+        from: TimeDiff,
+        /// The time after the start of the era at which the validator resumes sending units.
+        till: TimeDiff,
+    },
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Fault::Equivocate => write!(f, "equivocate"),
+            Fault::Crash { at } => write!(f, "crash at {}", at),
+            Fault::Mute { from, till } => write!(f, "mute {} - {}", from, till),
+        }
+    }
+}
+
+/// A validator and its fault, if any.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ValidatorSpec {
+    weight: u64,
+    #[serde(default)]
+    fault: Option<Fault>,
+}
+
+/// A network partition: Validators in different groups can't communicate in the given interval.
+/// Validators not listed in any group form another group. Messages between groups are delayed
+/// until the partition is healed.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Partition {
+    from: TimeDiff,
+    till: TimeDiff,
+    groups: Vec<Vec<usize>>,
+}
+
+impl Partition {
+    /// Returns whether the validators with the given indices are separated at the given time.
+    fn separates(&self, idx0: usize, idx1: usize, time: Timestamp) -> bool {
+        let group = |idx| self.groups.iter().position(|group| group.contains(&idx));
+        Timestamp::zero() + self.from <= time
+            && time < Timestamp::zero() + self.till
+            && group(idx0) != group(idx1)
+    }
+}
+
+/// A simulated era: the Highway configuration, the validators and their faults, and the network.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    /// The seed for the leader sequence and the message latencies.
+    #[serde(default)]
+    seed: u64,
+    /// The minimum duration of the era.
+    era_duration: TimeDiff,
+    /// The minimum number of blocks in the era.
+    minimum_era_height: u64,
+    /// The simulation is stopped if the era hasn't ended after this much simulated time.
+    timeout: TimeDiff,
+    /// The round exponent the validators start with. Defaults to the minimum round exponent.
+    #[serde(default)]
+    initial_round_exponent: Option<u8>,
+    highway: HighwayConfig,
+    latency: Latency,
+    validators: Vec<ValidatorSpec>,
+    #[serde(default)]
+    partitions: Vec<Partition>,
+}
+
+impl Scenario {
+    /// Returns an error if the scenario's values are inconsistent.
+    fn validate(&self) -> Result<(), Error> {
+        let invalid = |msg: &str| Err(Error::InvalidScenario(msg.to_string()));
+        if !self.highway.is_valid() {
+            return invalid("invalid Highway configuration");
+        }
+        let init_round_exp = self.init_round_exp();
+        if init_round_exp < self.highway.minimum_round_exponent
+            || init_round_exp > self.highway.maximum_round_exponent
+        {
+            return invalid("initial round exponent is not between the minimum and maximum");
+        }
+        if self.minimum_era_height == 0 {
+            return invalid("minimum era height is zero");
+        }
+        if self.latency.minimum > self.latency.maximum {
+            return invalid("minimum latency is greater than the maximum latency");
+        }
+        if self.validators.is_empty() {
+            return invalid("no validators");
+        }
+        if self.validators.iter().any(|spec| spec.weight == 0) {
+            return invalid("validator weight is zero");
+        }
+        let total_weight = self
+            .validators
+            .iter()
+            .try_fold(0u64, |sum, spec| sum.checked_add(spec.weight));
+        if total_weight.is_none() {
+            return invalid("total weight is greater than 2^64");
+        }
+        for spec in &self.validators {
+            if let Some(Fault::Mute { from, till }) = spec.fault {
+                if from >= till {
+                    return invalid("mute fault ends before it starts");
+                }
+            }
+        }
+        for partition in &self.partitions {
+            if partition.from >= partition.till {
+                return invalid("partition ends before it starts");
+            }
+            let mut seen = HashSet::new();
+            for idx in partition.groups.iter().flatten() {
+                if *idx >= self.validators.len() {
+                    return invalid("partition contains an unknown validator index");
+                }
+                if !seen.insert(*idx) {
+                    return invalid("partition contains a validator in more than one group");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn init_round_exp(&self) -> u8 {
+        self.initial_round_exponent
+            .unwrap_or(self.highway.minimum_round_exponent)
+    }
+
+    /// Returns the Highway parameters, computed the same way as in the node.
+    fn params(&self) -> Params {
+        let min_round_len = state::round_len(self.highway.minimum_round_exponent);
+        let min_rounds_per_era = self
+            .minimum_era_height
+            .max((TimeDiff::from(1) + self.era_duration) / min_round_len);
+        let endorsement_evidence_limit = min_rounds_per_era
+            .saturating_mul(2)
+            .min(MAX_ENDORSEMENT_EVIDENCE_LIMIT);
+        Params::new(
+            self.seed,
+            BLOCK_REWARD,
+            (self.highway.reduced_reward_multiplier * BLOCK_REWARD).to_integer(),
+            self.highway.minimum_round_exponent,
+            self.highway.maximum_round_exponent,
+            self.init_round_exp(),
+            self.minimum_era_height,
+            Timestamp::zero(),
+            Timestamp::zero() + self.era_duration,
+            endorsement_evidence_limit,
+        )
+    }
+
+    /// Returns the time at which the validator with the given index crashes, if any.
+    fn crash_time(&self, idx: usize) -> Option<Timestamp> {
+        match self.validators.get(idx)?.fault? {
+            Fault::Crash { at } => Some(Timestamp::zero() + at),
+            Fault::Equivocate | Fault::Mute { .. } => None,
+        }
+    }
+}
+
+impl FromStr for Scenario {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let scenario: Scenario = toml::from_str(input)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+}
+
+/// Statistics about a set of durations.
+#[derive(Clone, Debug, Serialize)]
+pub struct LatencyDistribution {
+    /// The number of samples.
+    pub count: usize,
+    /// The shortest duration.
+    pub min: TimeDiff,
+    /// The median.
+    pub p50: TimeDiff,
+    /// The 90th percentile.
+    pub p90: TimeDiff,
+    /// The 99th percentile.
+    pub p99: TimeDiff,
+    /// The longest duration.
+    pub max: TimeDiff,
+    /// The arithmetic mean.
+    pub mean: TimeDiff,
+}
+
+impl LatencyDistribution {
+    /// Returns the distribution of the given samples, or `None` if there are none.
+    fn new(mut samples: Vec<TimeDiff>) -> Option<Self> {
+        samples.sort();
+        let count = samples.len();
+        let percentile = |p: usize| samples[(count - 1) * p / 100];
+        let sum: u64 = samples.iter().map(TimeDiff::millis).sum();
+        Some(LatencyDistribution {
+            count,
+            min: *samples.first()?,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: *samples.last()?,
+            mean: TimeDiff::from(sum / count as u64),
+        })
+    }
+}
+
+impl Display for LatencyDistribution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {}, p50 {}, p90 {}, p99 {}, max {}, mean {} ({} samples)",
+            self.min, self.p50, self.p90, self.p99, self.max, self.mean, self.count
+        )
+    }
+}
+
+/// The results for a single validator.
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidatorReport {
+    /// The validator's index in the scenario.
+    pub index: usize,
+    /// The validator's weight.
+    pub weight: u64,
+    /// The validator's fault, if any.
+    pub fault: Option<Fault>,
+    /// The number of units the validator created.
+    pub units: usize,
+    /// The number of units with a proposed block the validator created.
+    pub proposals: usize,
+    /// The number of the validator's proposal units whose block didn't become part of the
+    /// finalized chain, even though later blocks were finalized.
+    pub orphaned_units: usize,
+    /// The number of blocks the validator finalized.
+    pub finalized_blocks: usize,
+    /// The time between a block's timestamp and its finalization by this validator.
+    pub finality_latency: Option<LatencyDistribution>,
+    /// The validator's reward factor in the era's terminal block, if it was finalized.
+    pub reward_factor: Option<u64>,
+}
+
+/// The results of a simulation.
+#[derive(Clone, Debug, Serialize)]
+pub struct Report {
+    /// Whether every validator that hasn't crashed finalized the era's terminal block.
+    pub era_ended: bool,
+    /// The simulated time at which the simulation ended.
+    pub end_time: TimeDiff,
+    /// The number of blocks finalized by the first correct validator.
+    pub finalized_blocks: usize,
+    /// The time between a block's timestamp and its finalization by a correct validator.
+    pub finality_latency: Option<LatencyDistribution>,
+    /// The results for each validator.
+    pub validators: Vec<ValidatorReport>,
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.era_ended {
+            writeln!(f, "era ended after {}", self.end_time)?;
+        } else {
+            writeln!(
+                f,
+                "era did not end; simulation stopped after {}",
+                self.end_time
+            )?;
+        }
+        writeln!(f, "finalized blocks: {}", self.finalized_blocks)?;
+        match &self.finality_latency {
+            Some(latency) => writeln!(f, "finality latency: {}", latency)?,
+            None => writeln!(f, "finality latency: no finalized blocks")?,
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>5} {:>20} {:<24} {:>7} {:>9} {:>8} {:>9} {:>16} {:>16} {:>14}",
+            "index",
+            "weight",
+            "fault",
+            "units",
+            "proposals",
+            "orphaned",
+            "finalized",
+            "latency p50",
+            "latency p99",
+            "reward factor",
+        )?;
+        for validator in &self.validators {
+            let or_dash = |maybe: Option<String>| maybe.unwrap_or_else(|| "-".to_string());
+            let latency = validator.finality_latency.as_ref();
+            writeln!(
+                f,
+                "{:>5} {:>20} {:<24} {:>7} {:>9} {:>8} {:>9} {:>16} {:>16} {:>14}",
+                validator.index,
+                validator.weight,
+                or_dash(validator.fault.map(|fault| fault.to_string())),
+                validator.units,
+                validator.proposals,
+                validator.orphaned_units,
+                validator.finalized_blocks,
+                or_dash(latency.map(|latency| latency.p50.to_string())),
+                or_dash(latency.map(|latency| latency.p99.to_string())),
+                or_dash(validator.reward_factor.map(|reward| reward.to_string())),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the simulation described by the scenario.
+pub fn run(scenario: &Scenario) -> Result<Report, Error> {
+    let mut rng = NodeRng::seed_from_u64(scenario.seed);
+    let mut simulation = Simulation::new(scenario);
+    simulation.run(&mut rng)?;
+    Ok(simulation.report())
+}
+
+/// The identifying fields of a proposed block: its proposer, timestamp and value.
+type BlockKey = (ValidatorId, Timestamp, ConsensusValue);
+
+struct Simulation<'a> {
+    scenario: &'a Scenario,
+    /// The test harness. Its delivery strategy is unused: The simulation schedules messages
+    /// itself, according to the scenario's latencies and partitions.
+    harness: HighwayTestHarness<InstantDeliveryNoDropping>,
+    /// The time of the last delivered message.
+    now: Timestamp,
+    /// The time between each finalized block's timestamp and its finalization, by validator index.
+    latencies: Vec<Vec<TimeDiff>>,
+}
+
+impl<'a> Simulation<'a> {
+    fn new(scenario: &'a Scenario) -> Self {
+        let validators: Validators<ValidatorId> = scenario
+            .validators
+            .iter()
+            .enumerate()
+            .map(|(idx, spec)| (ValidatorId(idx as u64), spec.weight))
+            .collect();
+
+        let total_weight = u128::from(validators.total_weight());
+        let ftt_fraction = scenario.highway.finality_threshold_fraction;
+        let ftt =
+            total_weight * u128::from(*ftt_fraction.numer()) / u128::from(*ftt_fraction.denom());
+        let ftt = Weight(ftt as u64);
+
+        let params = scenario.params();
+        let start_time = Timestamp::zero();
+        let mut nodes = vec![];
+        let mut init_messages = vec![];
+        for (idx, spec) in scenario.validators.iter().enumerate() {
+            let vid = ValidatorId(idx as u64);
+            let mut highway = Highway::new(0, validators.clone(), params.clone());
+            let effects =
+                highway.activate_validator(vid, TestSecret(vid.0), start_time, None, None, ftt);
+            init_messages.extend(
+                effects.into_iter().map(|effect| {
+                    QueueEntry::new(start_time, vid, Message::new(vid, effect.into()))
+                }),
+            );
+            // Crashes are handled by the simulation itself, by dropping all later messages.
+            let fault = spec.fault.and_then(|fault| match fault {
+                Fault::Equivocate => Some(DesFault::Equivocate),
+                Fault::Mute { from, till } => Some(DesFault::TemporarilyMute {
+                    from: start_time + from,
+                    till: start_time + till,
+                }),
+                Fault::Crash { .. } => None,
+            });
+            let validator = HighwayValidator::new(highway, FinalityDetector::new(ftt), fault);
+            nodes.push(Node::new(vid, validator));
+        }
+
+        let harness = HighwayTestHarness {
+            virtual_net: VirtualNet::new(nodes, init_messages),
+            consensus_values: Default::default(),
+            delivery_time_strategy: InstantDeliveryNoDropping,
+            delivery_time_distribution: Distribution::Uniform,
+        };
+
+        Simulation {
+            scenario,
+            harness,
+            now: start_time,
+            latencies: vec![vec![]; scenario.validators.len()],
+        }
+    }
+
+    /// Delivers messages until the era has ended, the timeout has passed or no messages are left.
+    fn run(&mut self, rng: &mut NodeRng) -> Result<(), Error> {
+        let timeout = Timestamp::zero() + self.scenario.timeout;
+        while !self.era_ended() {
+            let entry = match self.harness.virtual_net.pop_message() {
+                Some(entry) if entry.delivery_time <= timeout => entry,
+                Some(_) | None => break,
+            };
+            self.now = entry.delivery_time;
+            self.deliver(rng, entry)?;
+        }
+        Ok(())
+    }
+
+    /// Delivers a single message and schedules the recipient's responses.
+    fn deliver(
+        &mut self,
+        rng: &mut NodeRng,
+        entry: QueueEntry<HighwayMessage>,
+    ) -> Result<(), Error> {
+        let QueueEntry {
+            delivery_time,
+            recipient,
+            message,
+        } = entry;
+        let idx = recipient.0 as usize;
+        if self.is_crashed(idx, delivery_time) {
+            trace!(%recipient, "dropping message to crashed validator");
+            return Ok(());
+        }
+
+        let finalized_before = self.node(idx).validator().finalized_blocks.len();
+        let messages = self
+            .harness
+            .process_message(rng, recipient, message, delivery_time)?;
+        let newly_finalized = &self.node(idx).validator().finalized_blocks[finalized_before..];
+        let latencies: Vec<TimeDiff> = newly_finalized
+            .iter()
+            .map(|block| delivery_time.saturating_diff(block.timestamp))
+            .collect();
+        self.latencies[idx].extend(latencies);
+
+        let mut targeted_messages = vec![];
+        for hwm in messages {
+            let to_self = |hwm, timestamp| {
+                let message = Message::new(recipient, hwm);
+                let target = Target::SingleValidator(recipient);
+                (TargetedMessage::new(message, target), timestamp)
+            };
+            match &hwm {
+                HighwayMessage::NewVertex(_) => {
+                    for other_idx in 0..self.scenario.validators.len() {
+                        if other_idx == idx {
+                            continue;
+                        }
+                        let timestamp = self.arrival_time(rng, idx, other_idx, delivery_time);
+                        let message = Message::new(recipient, hwm.clone());
+                        let target = Target::SingleValidator(ValidatorId(other_idx as u64));
+                        targeted_messages.push((TargetedMessage::new(message, target), timestamp));
+                    }
+                }
+                HighwayMessage::Timer(timestamp) => {
+                    let timestamp = *timestamp;
+                    targeted_messages.push(to_self(hwm, timestamp));
+                }
+                HighwayMessage::RequestBlock(block_context) => {
+                    let timestamp = block_context.timestamp();
+                    targeted_messages.push(to_self(hwm, timestamp));
+                }
+                // The test harness ignores these anyway.
+                HighwayMessage::WeAreFaulty(_) => {}
+            }
+        }
+        self.harness
+            .virtual_net
+            .dispatch_messages(targeted_messages);
+        Ok(())
+    }
+
+    /// Returns the time at which a message sent at `sent` from one validator arrives at the other.
+    fn arrival_time(
+        &self,
+        rng: &mut NodeRng,
+        from: usize,
+        to: usize,
+        sent: Timestamp,
+    ) -> Timestamp {
+        let separated_until = |time: Timestamp| {
+            self.scenario
+                .partitions
+                .iter()
+                .find(|partition| partition.separates(from, to, time))
+                .map(|partition| Timestamp::zero() + partition.till)
+        };
+        // Every iteration moves the departure time past the end of a partition, so this
+        // terminates.
+        let mut departure = sent;
+        loop {
+            if let Some(healed) = separated_until(departure) {
+                departure = healed;
+                continue;
+            }
+            let latency = rng.gen_range(
+                self.scenario.latency.minimum.millis()..=self.scenario.latency.maximum.millis(),
+            );
+            let arrival = departure + TimeDiff::from(latency);
+            match separated_until(arrival) {
+                Some(healed) => departure = healed,
+                None => return arrival,
+            }
+        }
+    }
+
+    fn node(&self, idx: usize) -> &HighwayNode {
+        self.harness
+            .virtual_net
+            .validator(&ValidatorId(idx as u64))
+            .expect("validator index must exist")
+    }
+
+    fn is_crashed(&self, idx: usize, time: Timestamp) -> bool {
+        self.scenario
+            .crash_time(idx)
+            .map_or(false, |crash_time| crash_time <= time)
+    }
+
+    /// Returns whether every validator that hasn't crashed finalized the era's terminal block.
+    fn era_ended(&self) -> bool {
+        (0..self.scenario.validators.len())
+            .filter(|idx| !self.is_crashed(*idx, self.now))
+            .all(|idx| {
+                self.node(idx)
+                    .validator()
+                    .finalized_blocks
+                    .last()
+                    .map_or(false, |block| block.terminal_block_data.is_some())
+            })
+    }
+
+    fn report(&self) -> Report {
+        let count = self.scenario.validators.len();
+        // The finalized chain, as seen by the first correct validator.
+        let reference_idx = (0..count)
+            .find(|idx| self.scenario.validators[*idx].fault.is_none())
+            .unwrap_or(0);
+        let finalized_blocks = &self.node(reference_idx).validator().finalized_blocks;
+        let finalized_keys: HashSet<BlockKey> = finalized_blocks
+            .iter()
+            .map(|block| (block.proposer, block.timestamp, block.value.clone()))
+            .collect();
+        let last_finalized_timestamp = finalized_blocks.last().map(|block| block.timestamp);
+        let rewards = finalized_blocks
+            .last()
+            .and_then(|block| block.terminal_block_data.as_ref())
+            .map(|terminal_block_data| &terminal_block_data.rewards);
+
+        let validators = (0..count)
+            .map(|idx| {
+                let spec = &self.scenario.validators[idx];
+                let node = self.node(idx);
+                let proposals: Vec<BlockKey> = node
+                    .messages_produced()
+                    .filter_map(|hwm| match hwm {
+                        HighwayMessage::NewVertex(vertex) => match &**vertex {
+                            Vertex::Unit(swunit) => Some(swunit.wire_unit()),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .filter_map(|wunit| {
+                        let value = wunit.value.clone()?;
+                        Some((ValidatorId(wunit.creator.0.into()), wunit.timestamp, value))
+                    })
+                    .collect();
+                let orphaned_units = proposals
+                    .iter()
+                    .filter(|key| {
+                        !finalized_keys.contains(key)
+                            && last_finalized_timestamp.map_or(false, |last| key.1 <= last)
+                    })
+                    .count();
+                ValidatorReport {
+                    index: idx,
+                    weight: spec.weight,
+                    fault: spec.fault,
+                    units: node
+                        .messages_produced()
+                        .filter(|hwm| hwm.is_new_unit())
+                        .count(),
+                    proposals: proposals.len(),
+                    orphaned_units,
+                    finalized_blocks: node.validator().finalized_blocks.len(),
+                    finality_latency: LatencyDistribution::new(self.latencies[idx].clone()),
+                    reward_factor: rewards
+                        .map(|rewards| rewards.get(&ValidatorId(idx as u64)).copied().unwrap_or(0)),
+                }
+            })
+            .collect();
+
+        let correct_latencies = (0..count)
+            .filter(|idx| self.scenario.validators[*idx].fault.is_none())
+            .flat_map(|idx| self.latencies[idx].iter().copied())
+            .collect();
+
+        Report {
+            era_ended: self.era_ended(),
+            end_time: self.now.saturating_diff(Timestamp::zero()),
+            finalized_blocks: finalized_blocks.len(),
+            finality_latency: LatencyDistribution::new(correct_latencies),
+            validators,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    const EXAMPLE_SCENARIO: &str =
+        include_str!("../../../../../../resources/local/consensus_sim.toml");
+
+    fn scenario_toml(validators: &str, partitions: &str) -> String {
+        format!(
+            r#"
+            era_duration = '0sec'
+            minimum_era_height = 10
+            timeout = '1h'
+
+            [highway]
+            finality_threshold_fraction = [1, 3]
+            minimum_round_exponent = 12
+            maximum_round_exponent = 16
+            reduced_reward_multiplier = [1, 5]
+
+            [latency]
+            minimum = '50ms'
+            maximum = '300ms'
+
+            {}
+            {}
+            "#,
+            validators, partitions
+        )
+    }
+
+    fn scenario(validators: &str, partitions: &str) -> Scenario {
+        scenario_toml(validators, partitions)
+            .parse()
+            .expect("should parse scenario")
+    }
+
+    const FOUR_VALIDATORS: &str = r#"
+        [[validators]]
+        weight = 100
+        [[validators]]
+        weight = 100
+        [[validators]]
+        weight = 100
+        [[validators]]
+        weight = 100
+        "#;
+
+    #[test]
+    fn should_end_era_without_faults() {
+        let report = run(&scenario(FOUR_VALIDATORS, "")).expect("should run");
+        assert!(report.era_ended);
+        assert_eq!(report.finalized_blocks, 10);
+        assert_eq!(report.finality_latency.as_ref().unwrap().count, 40);
+        for validator in &report.validators {
+            assert_eq!(validator.orphaned_units, 0);
+            assert_eq!(validator.finalized_blocks, 10);
+            assert!(validator.reward_factor.unwrap() > 0);
+        }
+        let _ = report.to_string();
+        let _ = serde_json::to_string(&report).unwrap();
+    }
+
+    #[test]
+    fn should_be_deterministic() {
+        let scenario = scenario(FOUR_VALIDATORS, "");
+        let report0 = serde_json::to_string(&run(&scenario).unwrap()).unwrap();
+        // Only one `TestRng` can be created per thread, so the second run needs its own thread.
+        let report1 = thread::spawn(move || serde_json::to_string(&run(&scenario).unwrap()))
+            .join()
+            .unwrap()
+            .unwrap();
+        assert_eq!(report0, report1);
+    }
+
+    #[test]
+    fn should_tolerate_equivocator_and_crash() {
+        let validators = r#"
+            [[validators]]
+            weight = 100
+            [[validators]]
+            weight = 100
+            [[validators]]
+            weight = 100
+            [[validators]]
+            weight = 30
+            fault = { type = 'equivocate' }
+            [[validators]]
+            weight = 30
+            fault = { type = 'crash', at = '10sec' }
+            "#;
+        let report = run(&scenario(validators, "")).expect("should run");
+        assert!(report.era_ended);
+        assert_eq!(report.finalized_blocks, 10);
+        let crashed = &report.validators[4];
+        assert!(crashed.finalized_blocks < 10);
+        // The crashed validator only gets rewards for the rounds before it crashed.
+        let correct_reward = report.validators[0].reward_factor.unwrap();
+        assert!(crashed.reward_factor.unwrap() < correct_reward * 3 / 10);
+    }
+
+    #[test]
+    fn should_delay_finality_during_partition() {
+        let partitions = r#"
+            [[partitions]]
+            from = '0sec'
+            till = '1min'
+            groups = [[0, 1], [2, 3]]
+            "#;
+        let report = run(&scenario(FOUR_VALIDATORS, partitions)).expect("should run");
+        assert!(report.era_ended);
+        // Neither half has a quorum, so nothing can be finalized before the partition heals.
+        let latency = report.finality_latency.unwrap();
+        assert!(report.end_time > TimeDiff::from_seconds(60));
+        assert!(latency.max.millis() > latency.min.millis());
+    }
+
+    #[test]
+    fn should_reject_invalid_scenario() {
+        let partitions = r#"
+            [[partitions]]
+            from = '0sec'
+            till = '1min'
+            groups = [[0, 1], [2, 4]]
+            "#;
+        let result = scenario_toml(FOUR_VALIDATORS, partitions).parse::<Scenario>();
+        assert!(matches!(result, Err(Error::InvalidScenario(_))));
+    }
+
+    #[test]
+    fn should_run_example_scenario() {
+        let scenario: Scenario = EXAMPLE_SCENARIO.parse().expect("should parse example");
+        let report = run(&scenario).expect("should run");
+        assert!(report.era_ended);
+    }
+}
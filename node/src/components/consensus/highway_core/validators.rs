@@ -83,6 +83,11 @@ impl<VID: Eq + Hash> Validators<VID> {
         self.validators.get(idx.0 as usize).map(Validator::id)
     }
 
+    /// Returns validator weight by index, or `None` if it doesn't exist.
+    pub(crate) fn weight(&self, idx: ValidatorIndex) -> Option<Weight> {
+        self.validators.get(idx.0 as usize).map(Validator::weight)
+    }
+
     /// Returns an iterator over all validators, sorted by ID.
     pub(crate) fn iter(&self) -> impl Iterator<Item = &Validator<VID>> {
         self.validators.iter()
@@ -1,4 +1,4 @@
-use prometheus::{Gauge, IntGauge, Registry};
+use prometheus::{self, Gauge, Histogram, HistogramOpts, IntGauge, Registry};
 
 use crate::{
     types::{FinalizedBlock, Timestamp},
@@ -10,6 +10,8 @@ use crate::{
 pub(super) struct ConsensusMetrics {
     /// Gauge to track time between proposal and finalization.
     finalization_time: Gauge,
+    /// Histogram of the time between proposal and finalization, in seconds.
+    finalization_latency: Histogram,
     /// Amount of finalized blocks.
     finalized_block_count: IntGauge,
     /// Timestamp of the most recently accepted block payload.
@@ -18,6 +20,8 @@ pub(super) struct ConsensusMetrics {
     time_of_last_finalized_block: IntGauge,
     /// The Current era.
     pub(super) current_era: IntGauge,
+    /// The number of validators considered faulty in the era of the latest finalized block.
+    pub(super) faulty_validators: IntGauge,
     /// registry component.
     registry: Registry,
 }
@@ -28,6 +32,14 @@ impl ConsensusMetrics {
             "finalization_time",
             "the amount of time, in milliseconds, between proposal and finalization of a block",
         )?;
+        let finalization_latency = Histogram::with_opts(
+            HistogramOpts::new(
+                "finalization_latency",
+                "the time, in seconds, between proposal and finalization of a block",
+            )
+            // Create buckets from a quarter of a second to about 17 minutes.
+            .buckets(prometheus::exponential_buckets(0.25, 2.0, 13)?),
+        )?;
         let finalized_block_count =
             IntGauge::new("amount_of_blocks", "the number of blocks finalized so far")?;
         let time_of_last_proposed_block = IntGauge::new(
@@ -39,17 +51,25 @@ impl ConsensusMetrics {
             "timestamp of the most recently finalized block",
         )?;
         let current_era = IntGauge::new("current_era", "The current era")?;
+        let faulty_validators = IntGauge::new(
+            "faulty_validators",
+            "the number of validators considered faulty in the era of the latest finalized block",
+        )?;
         registry.register(Box::new(finalization_time.clone()))?;
+        registry.register(Box::new(finalization_latency.clone()))?;
+        registry.register(Box::new(faulty_validators.clone()))?;
         registry.register(Box::new(finalized_block_count.clone()))?;
         registry.register(Box::new(current_era.clone()))?;
         registry.register(Box::new(time_of_last_proposed_block.clone()))?;
         registry.register(Box::new(time_of_last_finalized_block.clone()))?;
         Ok(ConsensusMetrics {
             finalization_time,
+            finalization_latency,
             finalized_block_count,
             time_of_last_proposed_block,
             time_of_last_finalized_block,
             current_era,
+            faulty_validators,
             registry: registry.clone(),
         })
     }
@@ -58,6 +78,8 @@ impl ConsensusMetrics {
     pub(super) fn finalized_block(&mut self, finalized_block: &FinalizedBlock) {
         let time_since_block_payload = finalized_block.timestamp().elapsed().millis() as f64;
         self.finalization_time.set(time_since_block_payload);
+        self.finalization_latency
+            .observe(time_since_block_payload / 1000.0);
         self.time_of_last_finalized_block
             .set(finalized_block.timestamp().millis() as i64);
         self.finalized_block_count
@@ -74,6 +96,8 @@ impl ConsensusMetrics {
 impl Drop for ConsensusMetrics {
     fn drop(&mut self) {
         unregister_metric!(self.registry, self.finalization_time);
+        unregister_metric!(self.registry, self.finalization_latency);
+        unregister_metric!(self.registry, self.faulty_validators);
         unregister_metric!(self.registry, self.finalized_block_count);
         unregister_metric!(self.registry, self.current_era);
         unregister_metric!(self.registry, self.time_of_last_finalized_block);
@@ -1 +1,49 @@
 pub(crate) mod highway;
+pub(crate) mod simple;
+
+use std::collections::{BTreeMap, HashSet};
+
+use casper_types::{ProtocolVersion, U512};
+
+use self::{highway::HighwayProtocol, simple::SimpleConsensus};
+use crate::{
+    components::consensus::{
+        config::{Config, ProtocolConfig},
+        consensus_protocol::{ConsensusProtocol, ProtocolOutcomes},
+        traits::{Context, NodeIdT},
+    },
+    types::{chainspec::ConsensusProtocolName, Timestamp},
+};
+
+/// Creates a new boxed instance of the consensus protocol run under the given protocol version.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn new_consensus_protocol<I: NodeIdT, C: Context + 'static>(
+    instance_id: C::InstanceId,
+    validator_stakes: BTreeMap<C::ValidatorId, U512>,
+    faulty: &HashSet<C::ValidatorId>,
+    inactive: &HashSet<C::ValidatorId>,
+    protocol_config: &ProtocolConfig,
+    protocol_version: ProtocolVersion,
+    config: &Config,
+    prev_cp: Option<&dyn ConsensusProtocol<I, C>>,
+    era_start_time: Timestamp,
+    seed: u64,
+    now: Timestamp,
+) -> (Box<dyn ConsensusProtocol<I, C>>, ProtocolOutcomes<I, C>) {
+    let new_boxed = match protocol_config.consensus_protocol(protocol_version) {
+        ConsensusProtocolName::Highway => HighwayProtocol::new_boxed,
+        ConsensusProtocolName::Simple => SimpleConsensus::new_boxed,
+    };
+    new_boxed(
+        instance_id,
+        validator_stakes,
+        faulty,
+        inactive,
+        protocol_config,
+        config,
+        prev_cp,
+        era_start_time,
+        seed,
+        now,
+    )
+}
@@ -38,6 +38,7 @@ use crate::{
             synchronizer::Synchronizer,
             validators::{ValidatorIndex, Validators},
         },
+        slashing_protection::SlashingGuard,
         traits::{ConsensusValueT, Context, NodeIdT},
         ActionId, TimerId,
     },
@@ -1001,11 +1002,17 @@ where
         secret: C::ValidatorSecret,
         now: Timestamp,
         unit_hash_file: Option<PathBuf>,
+        slashing_guard: Option<SlashingGuard>,
     ) -> ProtocolOutcomes<I, C> {
         let ftt = self.finality_detector.fault_tolerance_threshold();
-        let av_effects = self
-            .highway
-            .activate_validator(our_id, secret, now, unit_hash_file, ftt);
+        let av_effects = self.highway.activate_validator(
+            our_id,
+            secret,
+            now,
+            unit_hash_file,
+            slashing_guard,
+            ftt,
+        );
         self.process_av_effects(av_effects, now)
     }
 
@@ -1059,6 +1066,10 @@ where
         self.highway.validators_with_evidence().collect()
     }
 
+    fn faulty_validators(&self) -> Vec<&C::ValidatorId> {
+        self.highway.faulty_validators().collect()
+    }
+
     fn has_received_messages(&self) -> bool {
         !self.highway.state().is_empty()
             || !self.synchronizer.is_empty()
@@ -1080,4 +1091,8 @@ where
     fn next_round_length(&self) -> Option<TimeDiff> {
         self.highway.next_round_length()
     }
+
+    fn next_round_exp(&self) -> Option<u8> {
+        self.highway.next_round_exp()
+    }
 }
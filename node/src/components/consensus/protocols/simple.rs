@@ -0,0 +1,1501 @@
+//! # A simple round-based consensus protocol
+//!
+//! The protocol proceeds in rounds, and in each round a leader, selected pseudorandomly with
+//! probability proportional to their weight, can propose a block. The proposal points to an
+//! earlier round in which its parent block was proposed, if any.
+//!
+//! Each validator signs an `Echo` of every valid proposal it receives. A proposal is _accepted_
+//! once a quorum has echoed it, its parent proposal is accepted, too, and all rounds between the
+//! parent and the proposal are _skippable_. Correct validators only echo one proposal per round,
+//! so at most one proposal per round can be accepted.
+//!
+//! Once a validator sees the current round's proposal accepted it signs `Vote(true)`. If the
+//! proposal timeout expires first, it signs `Vote(false)` instead. Correct validators never sign
+//! both. A round with a quorum of `true` votes is _committed_: Its proposal and all ancestors are
+//! finalized. A round with a quorum of `false` votes is skippable, so the next leader can use an
+//! earlier round's proposal as the parent. A round can never be both committed and skippable, so
+//! every committed round's proposal is an ancestor of all later committed ones.
+//!
+//! A quorum is any set of validators with more than half of the total weight plus the fault
+//! tolerance threshold. Validators known to be faulty count towards every quorum.
+//!
+//! There are no round lengths to tune: A round ends as soon as it has an accepted proposal or is
+//! skippable, so blocks are finalized as fast as the network allows, but at most once per
+//! `minimum_block_time`.
+
+mod message;
+#[cfg(test)]
+mod tests;
+
+use std::{
+    any::Any,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::{self, Debug, Formatter},
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader},
+    iter,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+use datasize::DataSize;
+use num_traits::AsPrimitive;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use tracing::{debug, error, info, trace, warn};
+
+use casper_types::{system::auction::BLOCK_REWARD, U512};
+
+use crate::{
+    components::consensus::{
+        config::{Config, ProtocolConfig},
+        consensus_protocol::{
+            BlockContext, ConsensusProtocol, FinalizedBlock, ProposedBlock, ProtocolOutcome,
+            ProtocolOutcomes, TerminalBlockData,
+        },
+        highway_core::{
+            validators::{ValidatorIndex, Validators},
+            Weight,
+        },
+        slashing_protection::SlashingGuard,
+        traits::{ConsensusValueT, Context, NodeIdT},
+        ActionId, TimerId,
+    },
+    types::{TimeDiff, Timestamp},
+};
+
+pub(crate) use message::{Content, Message, Proposal, RoundId, SignedMessage};
+
+/// The timer for voting to skip the current round.
+const TIMER_ID_ROUND_TIMEOUT: TimerId = TimerId(0);
+/// The timer for proposing a block once the minimum block time has passed.
+const TIMER_ID_PROPOSE: TimerId = TimerId(1);
+/// The timer for handling a proposal with a future timestamp.
+const TIMER_ID_FUTURE_PROPOSAL: TimerId = TimerId(2);
+/// The timer to request the latest state from a random peer.
+const TIMER_ID_SYNC_PEER: TimerId = TimerId(3);
+
+/// Messages for rounds this far ahead of our current round are dropped.
+const MAX_FUTURE_ROUNDS: RoundId = 100;
+/// The maximum number of rounds included in a single sync response.
+const MAX_SYNC_ROUNDS: RoundId = 50;
+
+/// Whether a proposal is known to be valid.
+#[derive(Clone, Copy, DataSize, Debug, Eq, PartialEq)]
+enum Validity {
+    /// We haven't checked the proposal yet, e.g. because we don't know its parent.
+    Unchecked,
+    /// We requested validation of the consensus value.
+    Pending,
+    Valid,
+    Invalid,
+}
+
+/// A round's proposal, as received from its leader.
+#[derive(DataSize, Debug)]
+struct RoundProposal<I, C>
+where
+    C: Context,
+{
+    proposal: Proposal<C>,
+    hash: C::Hash,
+    /// The peer who sent us the proposal, or `None` if it is our own.
+    sender: Option<I>,
+    validity: Validity,
+}
+
+/// Everything we know about a single round.
+#[derive(DataSize, Debug)]
+struct Round<I, C>
+where
+    C: Context,
+{
+    /// The leader's proposal, if we received it.
+    proposal: Option<RoundProposal<I, C>>,
+    /// The echoes we received, by proposal hash.
+    echoes: BTreeMap<C::Hash, BTreeMap<ValidatorIndex, C::Signature>>,
+    /// The votes we received: `true` to commit the round, `false` to skip it.
+    votes: BTreeMap<bool, BTreeMap<ValidatorIndex, C::Signature>>,
+    /// The relative height of the proposal, once it is accepted.
+    accepted_height: Option<u64>,
+}
+
+impl<I, C: Context> Default for Round<I, C> {
+    fn default() -> Self {
+        Round {
+            proposal: None,
+            echoes: BTreeMap::new(),
+            votes: BTreeMap::new(),
+            accepted_height: None,
+        }
+    }
+}
+
+impl<I, C: Context> Round<I, C> {
+    /// Returns the echo or vote the validator signed, that conflicts with `content`.
+    fn conflicting(
+        &self,
+        idx: ValidatorIndex,
+        content: &Content<C>,
+    ) -> Option<(Content<C>, C::Signature)> {
+        let echoes = self
+            .echoes
+            .iter()
+            .map(|(hash, echoes)| (Content::Echo(*hash), echoes));
+        let votes = self
+            .votes
+            .iter()
+            .map(|(vote, votes)| (Content::Vote(*vote), votes));
+        echoes
+            .chain(votes)
+            .filter(|(other, _)| content.conflicts_with(other))
+            .find_map(|(other, signatures)| Some((other, *signatures.get(&idx)?)))
+    }
+
+    /// Returns whether we have the validator's message with that content.
+    fn contains(&self, idx: ValidatorIndex, content: &Content<C>) -> bool {
+        match content {
+            Content::Echo(hash) => self
+                .echoes
+                .get(hash)
+                .map_or(false, |echoes| echoes.contains_key(&idx)),
+            Content::Vote(vote) => self
+                .votes
+                .get(vote)
+                .map_or(false, |votes| votes.contains_key(&idx)),
+        }
+    }
+
+    /// Returns whether the validator has signed any echo in this round.
+    fn has_echoed(&self, idx: ValidatorIndex) -> bool {
+        self.echoes.values().any(|echoes| echoes.contains_key(&idx))
+    }
+
+    /// Returns whether the validator has signed any vote in this round.
+    fn has_voted(&self, idx: ValidatorIndex) -> bool {
+        self.votes.values().any(|votes| votes.contains_key(&idx))
+    }
+
+    fn insert(&mut self, idx: ValidatorIndex, content: Content<C>, signature: C::Signature) {
+        match content {
+            Content::Echo(hash) => self.echoes.entry(hash).or_default(),
+            Content::Vote(vote) => self.votes.entry(vote).or_default(),
+        }
+        .insert(idx, signature);
+    }
+}
+
+/// The ways in which a validator can be faulty.
+#[derive(DataSize, Debug)]
+enum Fault<C>
+where
+    C: Context,
+{
+    /// The validator was known to be faulty before this era began.
+    Banned,
+    /// The validator signed two conflicting messages in this era.
+    Direct(SignedMessage<C>, Content<C>, C::Signature),
+    /// We have evidence against the validator from a different era.
+    Indirect,
+}
+
+/// Our own validator key, if we are an active validator in this era.
+#[derive(DataSize)]
+struct ActiveValidator<C>
+where
+    C: Context,
+{
+    idx: ValidatorIndex,
+    secret: C::ValidatorSecret,
+    /// The file in which we record every message we sign, so we never sign conflicting messages
+    /// after a restart.
+    wal_file: Option<PathBuf>,
+}
+
+impl<C: Context> Debug for ActiveValidator<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActiveValidator")
+            .field("idx", &self.idx)
+            .field("wal_file", &self.wal_file)
+            .finish()
+    }
+}
+
+/// The round in which we requested a new block, and the parent it will refer to.
+#[derive(DataSize, Debug)]
+struct PendingProposal<C>
+where
+    C: Context,
+{
+    round_id: RoundId,
+    parent: Option<RoundId>,
+    block_context: BlockContext<C>,
+}
+
+#[derive(DataSize, Debug)]
+pub(crate) struct SimpleConsensus<I, C>
+where
+    I: DataSize,
+    C: Context,
+{
+    instance_id: C::InstanceId,
+    validators: Validators<C::ValidatorId>,
+    /// The fault tolerance threshold: the weight of faulty validators the protocol tolerates.
+    ftt: Weight,
+    /// Cumulative weights of the validators that may be leaders; zero for everyone else.
+    cumulative_leader_weights: Vec<Weight>,
+    /// The random seed for the leader sequence.
+    seed: u64,
+    era_start_time: Timestamp,
+    /// The earliest timestamp of the era's terminal block.
+    era_end_time: Timestamp,
+    /// The minimum number of blocks in this era.
+    minimum_era_height: u64,
+    proposal_timeout: TimeDiff,
+    minimum_block_time: TimeDiff,
+    rounds: BTreeMap<RoundId, Round<I, C>>,
+    faults: HashMap<ValidatorIndex, Fault<C>>,
+    /// The round we are currently in: All earlier rounds have an accepted proposal or are
+    /// skippable.
+    current_round: RoundId,
+    /// The time at which we entered the current round.
+    current_round_start: Timestamp,
+    /// All rounds before this one are finalized or skipped.
+    first_non_finalized_round: RoundId,
+    /// The relative height of the next block to be finalized.
+    next_height: u64,
+    /// Whether the era's terminal block has been finalized.
+    finalized_switch_block: bool,
+    active_validator: Option<ActiveValidator<C>>,
+    pending_proposal: Option<PendingProposal<C>>,
+    /// Proposals waiting for validation, with the rounds they were proposed in.
+    pending_values: HashMap<ProposedBlock<C>, HashSet<RoundId>>,
+    /// Whether we already reported that the fault tolerance threshold was exceeded.
+    ftt_exceeded: bool,
+    evidence_only: bool,
+    paused: bool,
+}
+
+impl<I: NodeIdT, C: Context + 'static> SimpleConsensus<I, C> {
+    /// Creates a new boxed `SimpleConsensus` instance.
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    pub(crate) fn new_boxed(
+        instance_id: C::InstanceId,
+        validator_stakes: BTreeMap<C::ValidatorId, U512>,
+        faulty: &HashSet<C::ValidatorId>,
+        inactive: &HashSet<C::ValidatorId>,
+        protocol_config: &ProtocolConfig,
+        _config: &Config,
+        _prev_cp: Option<&dyn ConsensusProtocol<I, C>>,
+        era_start_time: Timestamp,
+        seed: u64,
+        now: Timestamp,
+    ) -> (Box<dyn ConsensusProtocol<I, C>>, ProtocolOutcomes<I, C>) {
+        let sum_stakes: U512 = validator_stakes.iter().map(|(_, stake)| *stake).sum();
+        assert!(
+            !sum_stakes.is_zero(),
+            "cannot start era with total weight 0"
+        );
+        // We need u64 weights. Scale down by  sum / u64::MAX,  rounded up.
+        // If we round up the divisor, the resulting sum is guaranteed to be  <= u64::MAX.
+        let scaling_factor = (sum_stakes + U512::from(u64::MAX) - 1) / U512::from(u64::MAX);
+        let scale_stake = |(key, stake): (C::ValidatorId, U512)| {
+            (key, AsPrimitive::<u64>::as_(stake / scaling_factor))
+        };
+        let mut validators: Validators<C::ValidatorId> =
+            validator_stakes.into_iter().map(scale_stake).collect();
+
+        for vid in faulty {
+            validators.ban(vid);
+        }
+        for vid in inactive {
+            validators.set_cannot_propose(vid);
+        }
+
+        assert!(
+            validators.ensure_nonzero_proposing_stake(),
+            "cannot start era with total weight 0"
+        );
+
+        let total_weight = u128::from(validators.total_weight());
+        let ftt_fraction = protocol_config.simple.finality_threshold_fraction;
+        assert!(
+            ftt_fraction < 1.into(),
+            "finality threshold must be less than 100%"
+        );
+        #[allow(clippy::integer_arithmetic)] // FTT is less than 1, so this can't overflow.
+        let ftt = total_weight * *ftt_fraction.numer() as u128 / *ftt_fraction.denom() as u128;
+        let ftt = Weight(ftt as u64);
+
+        let cannot_propose: HashSet<ValidatorIndex> =
+            validators.iter_cannot_propose_idx().collect();
+        let cumulative_leader_weights = validators
+            .enumerate_ids()
+            .scan(Weight(0), |sum, (idx, _)| {
+                if !cannot_propose.contains(&idx) {
+                    *sum += validators.weight(idx).unwrap_or(Weight(0));
+                }
+                Some(*sum)
+            })
+            .collect();
+        let faults = validators
+            .iter_banned_idx()
+            .map(|idx| (idx, Fault::Banned))
+            .collect();
+
+        info!(%instance_id, ftt = ftt.0, "initializing simple consensus instance");
+
+        let current_round_start = now.max(era_start_time);
+        let outcomes = vec![ProtocolOutcome::ScheduleTimer(
+            current_round_start + protocol_config.simple.proposal_timeout,
+            TIMER_ID_ROUND_TIMEOUT,
+        )];
+
+        let sc = Box::new(SimpleConsensus {
+            instance_id,
+            validators,
+            ftt,
+            cumulative_leader_weights,
+            seed,
+            era_start_time,
+            era_end_time: era_start_time + protocol_config.era_duration,
+            minimum_era_height: protocol_config.minimum_era_height,
+            proposal_timeout: protocol_config.simple.proposal_timeout,
+            minimum_block_time: protocol_config.simple.minimum_block_time,
+            rounds: BTreeMap::new(),
+            faults,
+            current_round: 0,
+            current_round_start,
+            first_non_finalized_round: 0,
+            next_height: 0,
+            finalized_switch_block: false,
+            active_validator: None,
+            pending_proposal: None,
+            pending_values: HashMap::new(),
+            ftt_exceeded: false,
+            evidence_only: false,
+            paused: false,
+        });
+
+        (sc, outcomes)
+    }
+
+    /// Returns the leader of the given round.
+    fn leader(&self, round_id: RoundId) -> ValidatorIndex {
+        let total = self
+            .cumulative_leader_weights
+            .last()
+            .copied()
+            .unwrap_or(Weight(0));
+        if total.is_zero() {
+            // `ensure_nonzero_proposing_stake` makes sure this doesn't happen.
+            error!("no validator is allowed to propose");
+            return ValidatorIndex(0);
+        }
+        let seed = self.seed.wrapping_add(u64::from(round_id));
+        // We select a random one out of the `total` weight units, starting numbering at 1.
+        let r = Weight(
+            ChaCha8Rng::seed_from_u64(seed)
+                .gen_range(0..total.0)
+                .saturating_add(1),
+        );
+        // The leader is the first validator whose cumulative weight reaches `r`.
+        let idx = self
+            .cumulative_leader_weights
+            .partition_point(|cumulative| *cumulative < r);
+        ValidatorIndex(idx as u32)
+    }
+
+    /// Returns the total weight of validators known to be faulty.
+    fn faulty_weight(&self) -> u128 {
+        self.faults
+            .keys()
+            .filter_map(|idx| self.validators.weight(*idx))
+            .map(u128::from)
+            .sum()
+    }
+
+    /// Returns whether the given validators, together with all faulty ones, form a quorum.
+    #[allow(clippy::integer_arithmetic)] // The total weight is less than 2^64, so this fits in u128.
+    fn is_quorum<'a>(&self, idxs: impl IntoIterator<Item = &'a ValidatorIndex>) -> bool {
+        let correct_weight: u128 = idxs
+            .into_iter()
+            .filter(|idx| !self.faults.contains_key(idx))
+            .filter_map(|idx| self.validators.weight(*idx))
+            .map(u128::from)
+            .sum();
+        let weight = correct_weight + self.faulty_weight();
+        let total_weight = u128::from(self.validators.total_weight());
+        2 * weight > total_weight + u128::from(self.ftt)
+    }
+
+    /// Returns the round's proposal if it is accepted, and its relative height.
+    fn accepted_proposal(&self, round_id: RoundId) -> Option<(&Proposal<C>, u64)> {
+        let round = self.rounds.get(&round_id)?;
+        let height = round.accepted_height?;
+        let proposal = &round.proposal.as_ref()?.proposal;
+        Some((proposal, height))
+    }
+
+    /// Returns whether the round has a quorum of votes to skip it.
+    fn is_skippable(&self, round_id: RoundId) -> bool {
+        self.rounds
+            .get(&round_id)
+            .and_then(|round| round.votes.get(&false))
+            .map_or(false, |votes| self.is_quorum(votes.keys()))
+    }
+
+    /// Returns whether the round has an accepted proposal and a quorum of votes to commit it.
+    fn is_committed(&self, round_id: RoundId) -> bool {
+        self.rounds
+            .get(&round_id)
+            .filter(|round| round.accepted_height.is_some())
+            .and_then(|round| round.votes.get(&true))
+            .map_or(false, |votes| self.is_quorum(votes.keys()))
+    }
+
+    /// Returns whether a block at this height and with this timestamp is the era's last one.
+    fn is_terminal(&self, height: u64, timestamp: Timestamp) -> bool {
+        height.saturating_add(1) >= self.minimum_era_height && timestamp >= self.era_end_time
+    }
+
+    /// Returns whether a proposal in round `round_id` can have a parent in round `parent`, i.e.
+    /// whether all rounds in between are skippable.
+    fn can_follow(&self, parent: Option<RoundId>, round_id: RoundId) -> bool {
+        let first = parent.map_or(0, |parent_id| parent_id.saturating_add(1));
+        (first..round_id).all(|skipped_id| self.is_skippable(skipped_id))
+    }
+
+    /// Returns the values of the ancestors of a block whose parent was proposed in `parent`,
+    /// starting with the parent.
+    fn ancestor_values(&self, mut parent: Option<RoundId>) -> Vec<C::ConsensusValue> {
+        iter::from_fn(|| {
+            let (proposal, _) = self.accepted_proposal(parent?)?;
+            parent = proposal.parent;
+            Some(proposal.value.clone())
+        })
+        .collect()
+    }
+
+    /// Checks whether the proposal is ready to be validated, and returns the block context if it
+    /// is. Returns `Some(None)` if the proposal is invalid, and `None` if it is not ready yet.
+    #[allow(clippy::option_option)]
+    fn check_proposal(
+        &self,
+        round_id: RoundId,
+        proposal: &Proposal<C>,
+        now: Timestamp,
+    ) -> Option<Option<BlockContext<C>>> {
+        let min_timestamp = match proposal.parent {
+            None => self.era_start_time,
+            Some(parent_id) => {
+                let (parent, parent_height) = self.accepted_proposal(parent_id)?;
+                if self.is_terminal(parent_height, parent.timestamp) {
+                    return Some(None); // The terminal block can't have children.
+                }
+                parent.timestamp + self.minimum_block_time
+            }
+        };
+        if proposal.timestamp < min_timestamp {
+            return Some(None);
+        }
+        if proposal.timestamp > now || !self.can_follow(proposal.parent, round_id) {
+            return None;
+        }
+        let ancestor_values = self.ancestor_values(proposal.parent);
+        Some(Some(BlockContext::new(proposal.timestamp, ancestor_values)))
+    }
+
+    /// Signs a message with our key and records it in our write-ahead log.
+    fn create_message(&mut self, round_id: RoundId, content: Content<C>) -> Option<Message<C>> {
+        if self.paused {
+            return None;
+        }
+        let av = self.active_validator.as_ref()?;
+        let round = self.rounds.entry(round_id).or_default();
+        if round.conflicting(av.idx, &content).is_some() || round.contains(av.idx, &content) {
+            return None;
+        }
+        let signed_msg =
+            SignedMessage::sign(round_id, self.instance_id, content, av.idx, &av.secret)?;
+        if let Err(err) = append_to_wal(&av.wal_file, &signed_msg) {
+            error!(?err, ?round_id, "could not record message; not signing it");
+            return None;
+        }
+        round.insert(av.idx, content, signed_msg.signature);
+        Some(Message::Signed(signed_msg))
+    }
+
+    /// Validates a signed message and adds it to the protocol state. Returns an error if it is
+    /// invalid.
+    fn add_signed_message(
+        &mut self,
+        signed_msg: SignedMessage<C>,
+    ) -> Result<ProtocolOutcomes<I, C>, anyhow::Error> {
+        let round_id = signed_msg.round_id;
+        let instance_id = signed_msg.instance_id;
+        let content = signed_msg.content;
+        let validator_idx = signed_msg.validator_idx;
+        let signature = signed_msg.signature;
+        if instance_id != self.instance_id {
+            return Err(anyhow!("wrong instance ID: {}", instance_id));
+        }
+        let validator_id = self
+            .validators
+            .id(validator_idx)
+            .ok_or_else(|| anyhow!("invalid validator index: {:?}", validator_idx))?;
+        if round_id > self.current_round.saturating_add(MAX_FUTURE_ROUNDS) {
+            trace!(%round_id, "dropping message for a round too far in the future");
+            return Ok(vec![]);
+        }
+        if self.faults.contains_key(&validator_idx) {
+            trace!(%round_id, ?validator_idx, "dropping message from faulty validator");
+            return Ok(vec![]);
+        }
+        if self
+            .rounds
+            .get(&round_id)
+            .map_or(false, |round| round.contains(validator_idx, &content))
+        {
+            return Ok(vec![]); // We already have this message.
+        }
+        if !signed_msg.verify_signature(validator_id) {
+            return Err(anyhow!("invalid signature"));
+        }
+        let mut outcomes = vec![];
+        if self
+            .active_validator
+            .as_ref()
+            .map_or(false, |av| av.idx == validator_idx)
+        {
+            error!(
+                ?signed_msg,
+                "received a message from a doppelganger. \
+                 Are you running multiple nodes with the same validator key?",
+            );
+            self.deactivate_validator();
+            outcomes.push(ProtocolOutcome::DoppelgangerDetected);
+        }
+        let round = self.rounds.entry(round_id).or_default();
+        if let Some((other_content, other_signature)) = round.conflicting(validator_idx, &content) {
+            outcomes.extend(self.add_evidence(signed_msg, other_content, other_signature));
+            return Ok(outcomes);
+        }
+        round.insert(validator_idx, content, signature);
+        Ok(outcomes)
+    }
+
+    /// Validates evidence of a conflicting pair of messages and marks the validator as faulty.
+    /// Returns an error if the evidence is invalid.
+    fn add_evidence_message(
+        &mut self,
+        signed_msg: SignedMessage<C>,
+        other_content: Content<C>,
+        other_signature: C::Signature,
+    ) -> Result<ProtocolOutcomes<I, C>, anyhow::Error> {
+        if signed_msg.instance_id != self.instance_id {
+            return Err(anyhow!("wrong instance ID: {}", signed_msg.instance_id));
+        }
+        let validator_id = self
+            .validators
+            .id(signed_msg.validator_idx)
+            .ok_or_else(|| anyhow!("invalid validator index: {:?}", signed_msg.validator_idx))?;
+        if !signed_msg.content.conflicts_with(&other_content) {
+            return Err(anyhow!("evidence messages don't conflict"));
+        }
+        if !signed_msg.verify_signature(validator_id)
+            || !signed_msg
+                .with(other_content, other_signature)
+                .verify_signature(validator_id)
+        {
+            return Err(anyhow!("invalid signature in evidence"));
+        }
+        Ok(self.add_evidence(signed_msg, other_content, other_signature))
+    }
+
+    /// Marks the validator as faulty, based on the given conflicting messages.
+    fn add_evidence(
+        &mut self,
+        signed_msg: SignedMessage<C>,
+        other_content: Content<C>,
+        other_signature: C::Signature,
+    ) -> ProtocolOutcomes<I, C> {
+        let idx = signed_msg.validator_idx;
+        if let Some(Fault::Direct(..)) = self.faults.get(&idx) {
+            return vec![];
+        }
+        let validator_id = match self.validators.id(idx) {
+            Some(validator_id) => validator_id.clone(),
+            None => return vec![],
+        };
+        warn!(%validator_id, round_id = signed_msg.round_id, "validator equivocated");
+        let evidence = Message::Evidence(signed_msg.clone(), other_content, other_signature);
+        self.faults.insert(
+            idx,
+            Fault::Direct(signed_msg, other_content, other_signature),
+        );
+        let mut outcomes = vec![
+            ProtocolOutcome::NewEvidence(validator_id),
+            ProtocolOutcome::CreatedGossipMessage(evidence.serialize()),
+        ];
+        if self
+            .active_validator
+            .as_ref()
+            .map_or(false, |av| av.idx == idx)
+        {
+            error!("this validator is faulty");
+            outcomes.push(ProtocolOutcome::WeAreFaulty);
+        }
+        outcomes.extend(self.check_ftt());
+        outcomes
+    }
+
+    /// Returns `FttExceeded` the first time the faulty weight exceeds the threshold.
+    fn check_ftt(&mut self) -> ProtocolOutcomes<I, C> {
+        if self.ftt_exceeded || self.faulty_weight() <= u128::from(self.ftt) {
+            return vec![];
+        }
+        self.ftt_exceeded = true;
+        error!(
+            faulty_weight = %self.faulty_weight(),
+            total_weight = %self.validators.total_weight().0,
+            "too many faulty validators"
+        );
+        vec![ProtocolOutcome::FttExceeded]
+    }
+
+    /// Validates a proposal and the leader's echo, and adds them to the protocol state.
+    fn add_proposal(
+        &mut self,
+        sender: I,
+        proposal: Proposal<C>,
+        echo: SignedMessage<C>,
+        now: Timestamp,
+    ) -> Result<ProtocolOutcomes<I, C>, anyhow::Error> {
+        let round_id = echo.round_id;
+        let hash = proposal.hash();
+        if echo.content != Content::Echo(hash) {
+            return Err(anyhow!("proposal without matching echo"));
+        }
+        if echo.validator_idx != self.leader(round_id) {
+            return Err(anyhow!("proposal by a validator who is not the leader"));
+        }
+        if proposal.parent.map_or(false, |parent| parent >= round_id) {
+            return Err(anyhow!("proposal's parent is not from an earlier round"));
+        }
+        let leader_idx = echo.validator_idx;
+        let mut outcomes = self.add_signed_message(echo)?;
+        let round = match self.rounds.get_mut(&round_id) {
+            Some(round) if round.proposal.is_none() => round,
+            _ => return Ok(outcomes),
+        };
+        if !round
+            .echoes
+            .get(&hash)
+            .map_or(false, |echoes| echoes.contains_key(&leader_idx))
+        {
+            return Ok(outcomes); // The leader equivocated or is faulty.
+        }
+        debug!(%round_id, %hash, timestamp = %proposal.timestamp, "received a proposal");
+        if proposal.timestamp > now {
+            outcomes.push(ProtocolOutcome::ScheduleTimer(
+                proposal.timestamp,
+                TIMER_ID_FUTURE_PROPOSAL,
+            ));
+        }
+        round.proposal = Some(RoundProposal {
+            proposal,
+            hash,
+            sender: Some(sender),
+            validity: Validity::Unchecked,
+        });
+        Ok(outcomes)
+    }
+
+    /// Handles a single message. Returns an error if it is invalid.
+    fn handle_deserialized_message(
+        &mut self,
+        sender: I,
+        msg: Message<C>,
+        now: Timestamp,
+    ) -> Result<ProtocolOutcomes<I, C>, anyhow::Error> {
+        match msg {
+            Message::Evidence(signed_msg, other_content, other_signature) => {
+                self.add_evidence_message(signed_msg, other_content, other_signature)
+            }
+            _ if self.evidence_only => {
+                trace!("received an irrelevant message in evidence-only mode");
+                Ok(vec![])
+            }
+            Message::Proposal { proposal, echo } => {
+                let mut outcomes = self.add_proposal(sender, proposal, echo, now)?;
+                outcomes.extend(self.update(now));
+                Ok(outcomes)
+            }
+            Message::Signed(signed_msg) => {
+                let mut outcomes = self.add_signed_message(signed_msg)?;
+                outcomes.extend(self.update(now));
+                Ok(outcomes)
+            }
+            Message::SyncRequest {
+                instance_id,
+                first_round_id,
+            } => {
+                if instance_id != self.instance_id {
+                    return Err(anyhow!("wrong instance ID: {}", instance_id));
+                }
+                Ok(self.sync_response(sender, first_round_id))
+            }
+            Message::SyncResponse(msgs) => {
+                let first_non_finalized_round = self.first_non_finalized_round;
+                let mut outcomes = vec![];
+                for msg in msgs {
+                    outcomes.extend(match msg {
+                        Message::Proposal { proposal, echo } => {
+                            self.add_proposal(sender.clone(), proposal, echo, now)?
+                        }
+                        Message::Signed(signed_msg) => self.add_signed_message(signed_msg)?,
+                        Message::Evidence(signed_msg, other_content, other_signature) => {
+                            self.add_evidence_message(signed_msg, other_content, other_signature)?
+                        }
+                        Message::SyncRequest { .. } | Message::SyncResponse(_) => {
+                            return Err(anyhow!("unexpected message in sync response"));
+                        }
+                    });
+                }
+                outcomes.extend(self.update(now));
+                // If the peer helped us make progress, they might know about more rounds.
+                if self.first_non_finalized_round > first_non_finalized_round
+                    && !self.finalized_switch_block
+                {
+                    outcomes.push(ProtocolOutcome::CreatedTargetedMessage(
+                        self.sync_request().serialize(),
+                        sender,
+                    ));
+                }
+                Ok(outcomes)
+            }
+        }
+    }
+
+    /// Returns a request for all rounds we haven't finalized yet.
+    fn sync_request(&self) -> Message<C> {
+        Message::SyncRequest {
+            instance_id: self.instance_id,
+            first_round_id: self.first_non_finalized_round,
+        }
+    }
+
+    /// Returns a response with the messages from the requested rounds, and all evidence.
+    fn sync_response(&self, sender: I, first_round_id: RoundId) -> ProtocolOutcomes<I, C> {
+        let to_signed = |round_id: RoundId, idx: ValidatorIndex, content, signature| {
+            Message::Signed(SignedMessage {
+                round_id,
+                instance_id: self.instance_id,
+                content,
+                validator_idx: idx,
+                signature,
+            })
+        };
+        let mut msgs = vec![];
+        let last_round_id = first_round_id.saturating_add(MAX_SYNC_ROUNDS);
+        for (round_id, round) in self.rounds.range(first_round_id..last_round_id) {
+            let round_id = *round_id;
+            let leader_idx = self.leader(round_id);
+            if let Some(round_proposal) = &round.proposal {
+                let leader_signature = round
+                    .echoes
+                    .get(&round_proposal.hash)
+                    .and_then(|echoes| echoes.get(&leader_idx));
+                if let Some(signature) = leader_signature {
+                    let echo = SignedMessage {
+                        round_id,
+                        instance_id: self.instance_id,
+                        content: Content::Echo(round_proposal.hash),
+                        validator_idx: leader_idx,
+                        signature: *signature,
+                    };
+                    let proposal = round_proposal.proposal.clone();
+                    msgs.push(Message::Proposal { proposal, echo });
+                }
+            }
+            for (hash, echoes) in &round.echoes {
+                for (idx, signature) in echoes {
+                    msgs.push(to_signed(round_id, *idx, Content::Echo(*hash), *signature));
+                }
+            }
+            for (vote, votes) in &round.votes {
+                for (idx, signature) in votes {
+                    msgs.push(to_signed(round_id, *idx, Content::Vote(*vote), *signature));
+                }
+            }
+        }
+        msgs.extend(self.faults.values().filter_map(|fault| match fault {
+            Fault::Direct(signed_msg, other_content, other_signature) => Some(Message::Evidence(
+                signed_msg.clone(),
+                *other_content,
+                *other_signature,
+            )),
+            Fault::Banned | Fault::Indirect => None,
+        }));
+        if msgs.is_empty() {
+            return vec![];
+        }
+        let response = Message::SyncResponse(msgs);
+        vec![ProtocolOutcome::CreatedTargetedMessage(
+            response.serialize(),
+            sender,
+        )]
+    }
+
+    /// Validates ready proposals, signs echoes and votes, detects accepted, committed and
+    /// skippable rounds, and moves on to the next round when possible.
+    fn update(&mut self, now: Timestamp) -> ProtocolOutcomes<I, C> {
+        if self.evidence_only {
+            return vec![];
+        }
+        let mut outcomes = vec![];
+        // Proposals only depend on earlier rounds, so a single pass in ascending order suffices.
+        let round_ids: Vec<RoundId> = self
+            .rounds
+            .range(self.first_non_finalized_round..)
+            .map(|(round_id, _)| *round_id)
+            .collect();
+        for round_id in round_ids {
+            outcomes.extend(self.update_round(round_id, now));
+        }
+        outcomes.extend(self.finalize_rounds());
+        outcomes.extend(self.advance_round(now));
+        outcomes
+    }
+
+    fn update_round(&mut self, round_id: RoundId, now: Timestamp) -> ProtocolOutcomes<I, C> {
+        let mut outcomes = vec![];
+        let (validity, proposal, hash) = match self
+            .rounds
+            .get(&round_id)
+            .and_then(|round| round.proposal.as_ref())
+        {
+            Some(rp) => (rp.validity, &rp.proposal, rp.hash),
+            None => return outcomes,
+        };
+        if validity == Validity::Unchecked {
+            let new_validity = match self.check_proposal(round_id, proposal, now) {
+                None => Validity::Unchecked,
+                Some(None) => {
+                    info!(%round_id, %hash, "received an invalid proposal");
+                    Validity::Invalid
+                }
+                Some(Some(_)) if !proposal.value.needs_validation() => Validity::Valid,
+                Some(Some(block_context)) => {
+                    let proposed_block = ProposedBlock::new(proposal.value.clone(), block_context);
+                    if let Some(sender) = self.proposal_sender(round_id) {
+                        self.pending_values
+                            .entry(proposed_block.clone())
+                            .or_default()
+                            .insert(round_id);
+                        outcomes.push(ProtocolOutcome::ValidateConsensusValue {
+                            sender,
+                            proposed_block,
+                        });
+                    }
+                    Validity::Pending
+                }
+            };
+            self.set_validity(round_id, new_validity);
+        }
+        if !self.has_valid_proposal(round_id) {
+            return outcomes;
+        }
+        if let Some(msg) = self.create_message(round_id, Content::Echo(hash)) {
+            outcomes.push(ProtocolOutcome::CreatedGossipMessage(msg.serialize()));
+        }
+        let round = match self.rounds.get(&round_id) {
+            Some(round) => round,
+            None => return outcomes,
+        };
+        if round.accepted_height.is_none() {
+            let is_accepted = round
+                .echoes
+                .get(&hash)
+                .map_or(false, |echoes| self.is_quorum(echoes.keys()));
+            if !is_accepted {
+                return outcomes;
+            }
+            let parent = round.proposal.as_ref().and_then(|rp| rp.proposal.parent);
+            let height = match parent {
+                None => 0,
+                Some(parent_id) => match self.accepted_proposal(parent_id) {
+                    Some((_, parent_height)) => parent_height.saturating_add(1),
+                    None => return outcomes,
+                },
+            };
+            debug!(%round_id, %hash, %height, "proposal accepted");
+            if let Some(round) = self.rounds.get_mut(&round_id) {
+                round.accepted_height = Some(height);
+            }
+        }
+        if let Some(msg) = self.create_message(round_id, Content::Vote(true)) {
+            outcomes.push(ProtocolOutcome::CreatedGossipMessage(msg.serialize()));
+        }
+        outcomes
+    }
+
+    fn proposal_sender(&self, round_id: RoundId) -> Option<I> {
+        self.rounds
+            .get(&round_id)?
+            .proposal
+            .as_ref()?
+            .sender
+            .clone()
+    }
+
+    fn set_validity(&mut self, round_id: RoundId, validity: Validity) {
+        if let Some(rp) = self
+            .rounds
+            .get_mut(&round_id)
+            .and_then(|round| round.proposal.as_mut())
+        {
+            rp.validity = validity;
+        }
+    }
+
+    fn has_valid_proposal(&self, round_id: RoundId) -> bool {
+        self.rounds
+            .get(&round_id)
+            .and_then(|round| round.proposal.as_ref())
+            .map_or(false, |rp| rp.validity == Validity::Valid)
+    }
+
+    /// Finalizes the latest committed round and all its ancestors that aren't finalized yet.
+    fn finalize_rounds(&mut self) -> ProtocolOutcomes<I, C> {
+        if self.finalized_switch_block {
+            return vec![];
+        }
+        let maybe_committed = self
+            .rounds
+            .range(self.first_non_finalized_round..)
+            .rev()
+            .map(|(round_id, _)| *round_id)
+            .find(|round_id| self.is_committed(*round_id));
+        let mut maybe_round_id = match maybe_committed {
+            Some(round_id) => Some(round_id),
+            None => return vec![],
+        };
+        // The validators whose votes committed the latest round also finalized its ancestors.
+        let summit: Vec<C::ValidatorId> = maybe_round_id
+            .and_then(|round_id| self.rounds.get(&round_id))
+            .and_then(|round| round.votes.get(&true))
+            .into_iter()
+            .flat_map(|votes| votes.keys())
+            .filter_map(|idx| self.validators.id(*idx))
+            .cloned()
+            .collect();
+        let mut to_finalize = vec![];
+        while let Some(round_id) = maybe_round_id {
+            let (proposal, height) = match self.accepted_proposal(round_id) {
+                Some((proposal, height)) if height >= self.next_height => (proposal, height),
+                _ => break,
+            };
+            to_finalize.push((round_id, height));
+            maybe_round_id = proposal.parent;
+        }
+        let mut outcomes = vec![];
+        for (round_id, height) in to_finalize.into_iter().rev() {
+            let (proposal, _) = match self.accepted_proposal(round_id) {
+                Some(accepted) => accepted,
+                None => break,
+            };
+            let value = proposal.value.clone();
+            let timestamp = proposal.timestamp;
+            let terminal_block_data = if self.is_terminal(height, timestamp) {
+                Some(self.terminal_block_data(round_id))
+            } else {
+                None
+            };
+            let proposer = match self.validators.id(self.leader(round_id)) {
+                Some(proposer) => proposer.clone(),
+                None => break,
+            };
+            info!(%round_id, %height, %timestamp, %proposer, "finalized block");
+            self.first_non_finalized_round = round_id.saturating_add(1);
+            self.next_height = height.saturating_add(1);
+            self.finalized_switch_block = terminal_block_data.is_some();
+            outcomes.push(ProtocolOutcome::FinalizedBlock(FinalizedBlock {
+                value,
+                timestamp,
+                relative_height: height,
+                equivocators: vec![],
+                terminal_block_data,
+                proposer,
+                summit: summit.clone(),
+            }));
+            if self.finalized_switch_block {
+                break;
+            }
+        }
+        outcomes
+    }
+
+    /// Returns the rewards and inactive validators for an era ending with the block proposed in
+    /// the given round.
+    ///
+    /// Each finalized block's proposer gets the block reward. Validators who were leaders in a
+    /// skipped round and didn't get any block finalized are inactive.
+    fn terminal_block_data(&self, round_id: RoundId) -> TerminalBlockData<C> {
+        let mut finalized_rounds = HashSet::new();
+        let mut maybe_round_id = Some(round_id);
+        while let Some(round_id) = maybe_round_id {
+            finalized_rounds.insert(round_id);
+            maybe_round_id = self
+                .accepted_proposal(round_id)
+                .and_then(|(proposal, _)| proposal.parent);
+        }
+        let mut rewards: BTreeMap<C::ValidatorId, u64> = self
+            .validators
+            .iter()
+            .map(|validator| (validator.id().clone(), 0))
+            .collect();
+        let mut skipped_leaders = HashSet::new();
+        for round_id in 0..=round_id {
+            let leader_idx = self.leader(round_id);
+            if !finalized_rounds.contains(&round_id) {
+                skipped_leaders.insert(leader_idx);
+            } else if let Some(reward) = self
+                .validators
+                .id(leader_idx)
+                .and_then(|leader| rewards.get_mut(leader))
+            {
+                *reward = reward.saturating_add(BLOCK_REWARD);
+            }
+        }
+        let inactive_validators = skipped_leaders
+            .into_iter()
+            .filter_map(|idx| self.validators.id(idx))
+            .filter(|vid| rewards.get(*vid).map_or(true, |reward| *reward == 0))
+            .cloned()
+            .collect();
+        TerminalBlockData {
+            rewards,
+            inactive_validators,
+            equivocation_reports: BTreeMap::new(),
+        }
+    }
+
+    /// Moves on to the next round if the current one has an accepted proposal or is skippable.
+    fn advance_round(&mut self, now: Timestamp) -> ProtocolOutcomes<I, C> {
+        let start_round = self.current_round;
+        while self.accepted_proposal(self.current_round).is_some()
+            || self.is_skippable(self.current_round)
+        {
+            self.current_round = self.current_round.saturating_add(1);
+        }
+        if self.current_round == start_round || self.finalized_switch_block {
+            return vec![];
+        }
+        trace!(round_id = self.current_round, "entering new round");
+        self.current_round_start = now.max(self.era_start_time);
+        let mut outcomes = vec![ProtocolOutcome::ScheduleTimer(
+            self.current_round_start + self.proposal_timeout,
+            TIMER_ID_ROUND_TIMEOUT,
+        )];
+        outcomes.extend(self.propose_if_leader(now));
+        outcomes
+    }
+
+    /// Requests a new block if we are the current round's leader.
+    fn propose_if_leader(&mut self, now: Timestamp) -> ProtocolOutcomes<I, C> {
+        let round_id = self.current_round;
+        let our_idx = match &self.active_validator {
+            Some(av) => av.idx,
+            None => return vec![],
+        };
+        if self.paused
+            || self.evidence_only
+            || self.finalized_switch_block
+            || self.leader(round_id) != our_idx
+            || self
+                .pending_proposal
+                .as_ref()
+                .map_or(false, |pending| pending.round_id == round_id)
+            || self
+                .rounds
+                .get(&round_id)
+                .map_or(false, |round| round.has_echoed(our_idx))
+        {
+            return vec![];
+        }
+        let parent = self
+            .rounds
+            .range(..round_id)
+            .rev()
+            .map(|(round_id, _)| *round_id)
+            .find(|round_id| self.accepted_proposal(*round_id).is_some());
+        if !self.can_follow(parent, round_id) {
+            return vec![];
+        }
+        let min_timestamp = match parent.and_then(|parent_id| self.accepted_proposal(parent_id)) {
+            None => self.era_start_time,
+            Some((parent, parent_height)) => {
+                if self.is_terminal(parent_height, parent.timestamp) {
+                    return vec![];
+                }
+                parent.timestamp + self.minimum_block_time
+            }
+        };
+        if min_timestamp > now {
+            return vec![ProtocolOutcome::ScheduleTimer(
+                min_timestamp,
+                TIMER_ID_PROPOSE,
+            )];
+        }
+        let block_context = BlockContext::new(now, self.ancestor_values(parent));
+        self.pending_proposal = Some(PendingProposal {
+            round_id,
+            parent,
+            block_context: block_context.clone(),
+        });
+        vec![ProtocolOutcome::CreateNewBlock(block_context)]
+    }
+
+    /// Votes to skip the current round if the proposal timeout has expired.
+    fn handle_round_timeout(&mut self, now: Timestamp) -> ProtocolOutcomes<I, C> {
+        if self.evidence_only
+            || self.finalized_switch_block
+            || now < self.current_round_start + self.proposal_timeout
+        {
+            return vec![];
+        }
+        let round_id = self.current_round;
+        let already_voted = match (&self.active_validator, self.rounds.get(&round_id)) {
+            (Some(av), Some(round)) => round.has_voted(av.idx),
+            (Some(_), None) => false,
+            (None, _) => return vec![],
+        };
+        if already_voted {
+            return vec![];
+        }
+        debug!(%round_id, "proposal timeout expired; voting to skip the round");
+        let mut outcomes = vec![];
+        if let Some(msg) = self.create_message(round_id, Content::Vote(false)) {
+            outcomes.push(ProtocolOutcome::CreatedGossipMessage(msg.serialize()));
+        }
+        outcomes.extend(self.update(now));
+        outcomes
+    }
+
+    /// Requests the latest state from a random peer, and schedules the next request.
+    fn handle_sync_peer_timer(&mut self, now: Timestamp) -> ProtocolOutcomes<I, C> {
+        if self.evidence_only || self.finalized_switch_block {
+            return vec![]; // Era has ended. No further progress is expected.
+        }
+        vec![
+            ProtocolOutcome::CreatedMessageToRandomPeer(self.sync_request().serialize()),
+            ProtocolOutcome::ScheduleTimer(now + self.proposal_timeout, TIMER_ID_SYNC_PEER),
+        ]
+    }
+}
+
+impl<I, C> ConsensusProtocol<I, C> for SimpleConsensus<I, C>
+where
+    I: NodeIdT,
+    C: Context + 'static,
+{
+    fn handle_message(
+        &mut self,
+        sender: I,
+        msg: Vec<u8>,
+        now: Timestamp,
+    ) -> ProtocolOutcomes<I, C> {
+        let result = match bincode::deserialize(msg.as_slice()) {
+            Ok(deserialized) => self.handle_deserialized_message(sender.clone(), deserialized, now),
+            Err(err) => Err(err.into()),
+        };
+        match result {
+            Ok(outcomes) => outcomes,
+            Err(err) => vec![ProtocolOutcome::InvalidIncomingMessage(msg, sender, err)],
+        }
+    }
+
+    fn handle_timer(&mut self, now: Timestamp, timer_id: TimerId) -> ProtocolOutcomes<I, C> {
+        match timer_id {
+            TIMER_ID_ROUND_TIMEOUT => self.handle_round_timeout(now),
+            TIMER_ID_PROPOSE => self.propose_if_leader(now),
+            TIMER_ID_FUTURE_PROPOSAL => self.update(now),
+            TIMER_ID_SYNC_PEER => self.handle_sync_peer_timer(now),
+            _ => {
+                error!(timer_id = timer_id.0, "unexpected timer ID");
+                vec![]
+            }
+        }
+    }
+
+    fn handle_is_current(&self, now: Timestamp) -> ProtocolOutcomes<I, C> {
+        // Request the latest protocol state of the current era, and schedule periodic requests.
+        vec![
+            ProtocolOutcome::CreatedMessageToRandomPeer(self.sync_request().serialize()),
+            ProtocolOutcome::ScheduleTimer(now + self.proposal_timeout, TIMER_ID_SYNC_PEER),
+        ]
+    }
+
+    fn handle_action(&mut self, action_id: ActionId, _now: Timestamp) -> ProtocolOutcomes<I, C> {
+        // This protocol doesn't schedule any actions.
+        error!(action_id = action_id.0, "unexpected action ID");
+        vec![]
+    }
+
+    fn propose(
+        &mut self,
+        proposed_block: ProposedBlock<C>,
+        now: Timestamp,
+    ) -> ProtocolOutcomes<I, C> {
+        let (value, block_context) = proposed_block.destructure();
+        let PendingProposal {
+            round_id, parent, ..
+        } = match self.pending_proposal.take() {
+            Some(pending) if pending.block_context == block_context => pending,
+            pending => {
+                warn!(?block_context, "unexpected proposal; dropping it");
+                self.pending_proposal = pending;
+                return vec![];
+            }
+        };
+        if round_id != self.current_round || self.evidence_only || self.finalized_switch_block {
+            debug!(%round_id, "round is over; dropping proposal");
+            return vec![];
+        }
+        let proposal = Proposal {
+            timestamp: block_context.timestamp(),
+            value,
+            parent,
+        };
+        let hash = proposal.hash();
+        let echo = match self.create_message(round_id, Content::Echo(hash)) {
+            Some(Message::Signed(echo)) => echo,
+            _ => return vec![],
+        };
+        info!(%round_id, %hash, timestamp = %proposal.timestamp, "proposing block");
+        let msg = Message::Proposal {
+            proposal: proposal.clone(),
+            echo,
+        };
+        if let Some(round) = self.rounds.get_mut(&round_id) {
+            round.proposal = Some(RoundProposal {
+                proposal,
+                hash,
+                sender: None,
+                validity: Validity::Valid,
+            });
+        }
+        let mut outcomes = vec![ProtocolOutcome::CreatedGossipMessage(msg.serialize())];
+        outcomes.extend(self.update(now));
+        outcomes
+    }
+
+    fn resolve_validity(
+        &mut self,
+        proposed_block: ProposedBlock<C>,
+        valid: bool,
+        now: Timestamp,
+    ) -> ProtocolOutcomes<I, C> {
+        let round_ids = self
+            .pending_values
+            .remove(&proposed_block)
+            .unwrap_or_default();
+        let validity = if valid {
+            Validity::Valid
+        } else {
+            // We don't disconnect from the sender here: The block validator considers the value
+            // "invalid" even if it just couldn't download the deploys, which could just be
+            // because the original sender went offline.
+            warn!(?proposed_block, ?round_ids, "proposal is invalid");
+            Validity::Invalid
+        };
+        for round_id in round_ids {
+            self.set_validity(round_id, validity);
+        }
+        self.update(now)
+    }
+
+    fn activate_validator(
+        &mut self,
+        our_id: C::ValidatorId,
+        secret: C::ValidatorSecret,
+        now: Timestamp,
+        wal_file: Option<PathBuf>,
+        slashing_guard: Option<SlashingGuard>,
+    ) -> ProtocolOutcomes<I, C> {
+        if slashing_guard.is_some() {
+            warn!("the slashing-protection database only covers Highway units and finality signatures");
+        }
+        let idx = match self.validators.get_index(&our_id) {
+            Some(idx) => idx,
+            None => {
+                error!(%our_id, "we are not a validator in this era");
+                return vec![];
+            }
+        };
+        // Restore the messages we signed before a restart, so we don't contradict them.
+        if let Some(path) = &wal_file {
+            match read_wal::<C>(path) {
+                Ok(signed_msgs) => {
+                    for signed_msg in signed_msgs {
+                        if signed_msg.instance_id == self.instance_id
+                            && signed_msg.validator_idx == idx
+                        {
+                            self.rounds.entry(signed_msg.round_id).or_default().insert(
+                                idx,
+                                signed_msg.content,
+                                signed_msg.signature,
+                            );
+                        }
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    error!(
+                        ?err,
+                        ?path,
+                        "could not read our signed messages; not activating"
+                    );
+                    return vec![];
+                }
+            }
+        }
+        self.active_validator = Some(ActiveValidator {
+            idx,
+            secret,
+            wal_file,
+        });
+        let mut outcomes = self.update(now);
+        outcomes.extend(self.propose_if_leader(now));
+        outcomes
+    }
+
+    fn deactivate_validator(&mut self) {
+        self.active_validator = None;
+    }
+
+    fn set_evidence_only(&mut self) {
+        self.rounds.clear();
+        self.pending_proposal = None;
+        self.pending_values.clear();
+        self.evidence_only = true;
+    }
+
+    fn has_evidence(&self, vid: &C::ValidatorId) -> bool {
+        self.validators
+            .get_index(vid)
+            .and_then(|idx| self.faults.get(&idx))
+            .map_or(false, |fault| matches!(fault, Fault::Direct(..)))
+    }
+
+    fn mark_faulty(&mut self, vid: &C::ValidatorId) {
+        if let Some(idx) = self.validators.get_index(vid) {
+            self.faults.entry(idx).or_insert(Fault::Indirect);
+        }
+    }
+
+    fn request_evidence(&self, sender: I, vid: &C::ValidatorId) -> ProtocolOutcomes<I, C> {
+        match self
+            .validators
+            .get_index(vid)
+            .and_then(|idx| self.faults.get(&idx))
+        {
+            Some(Fault::Direct(signed_msg, other_content, other_signature)) => {
+                let msg = Message::Evidence(signed_msg.clone(), *other_content, *other_signature);
+                vec![ProtocolOutcome::CreatedTargetedMessage(
+                    msg.serialize(),
+                    sender,
+                )]
+            }
+            Some(Fault::Banned) | Some(Fault::Indirect) | None => vec![],
+        }
+    }
+
+    /// Sets the pause status: While paused we don't sign any messages.
+    fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    fn validators_with_evidence(&self) -> Vec<&C::ValidatorId> {
+        self.faults
+            .iter()
+            .filter(|(_, fault)| matches!(fault, Fault::Direct(..)))
+            .filter_map(|(idx, _)| self.validators.id(*idx))
+            .collect()
+    }
+
+    fn faulty_validators(&self) -> Vec<&C::ValidatorId> {
+        self.faults
+            .keys()
+            .filter_map(|idx| self.validators.id(*idx))
+            .collect()
+    }
+
+    fn has_received_messages(&self) -> bool {
+        !self.rounds.is_empty()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn is_active(&self) -> bool {
+        self.active_validator.is_some()
+    }
+
+    fn instance_id(&self) -> &C::InstanceId {
+        &self.instance_id
+    }
+
+    fn next_round_length(&self) -> Option<TimeDiff> {
+        // Rounds end as soon as they are decided, so there is no fixed round length.
+        None
+    }
+
+    fn next_round_exp(&self) -> Option<u8> {
+        None
+    }
+}
+
+/// Appends the signed message to the write-ahead log file, if there is one.
+fn append_to_wal<C: Context>(
+    wal_file: &Option<PathBuf>,
+    signed_msg: &SignedMessage<C>,
+) -> io::Result<()> {
+    let wal_file = match wal_file {
+        Some(wal_file) => wal_file,
+        None => return Ok(()),
+    };
+    if let Some(parent_directory) = wal_file.parent() {
+        fs::create_dir_all(parent_directory)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(wal_file)?;
+    bincode::serialize_into(&mut file, signed_msg)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    file.sync_data()
+}
+
+/// Reads all signed messages from the write-ahead log file.
+fn read_wal<C: Context>(path: &Path) -> io::Result<Vec<SignedMessage<C>>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut signed_msgs = vec![];
+    loop {
+        match bincode::deserialize_from(&mut reader) {
+            Ok(signed_msg) => signed_msgs.push(signed_msg),
+            Err(err) => match *err {
+                bincode::ErrorKind::Io(ref io_err)
+                    if io_err.kind() == io::ErrorKind::UnexpectedEof =>
+                {
+                    // Either the end of the file, or an incomplete last entry that was never sent.
+                    return Ok(signed_msgs);
+                }
+                _ => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+            },
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::consensus::{
+        highway_core::validators::ValidatorIndex,
+        traits::{Context, ValidatorSecret},
+    },
+    types::Timestamp,
+};
+
+/// The index of a round in an era.
+pub(crate) type RoundId = u32;
+
+/// A block proposed by the leader of a round.
+#[derive(Clone, DataSize, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "C::Hash: Serialize",
+    deserialize = "C::Hash: Deserialize<'de>",
+))]
+pub(crate) struct Proposal<C>
+where
+    C: Context,
+{
+    /// The block's timestamp.
+    pub(crate) timestamp: Timestamp,
+    /// The proposed consensus value.
+    pub(crate) value: C::ConsensusValue,
+    /// The round of the parent block, or `None` if this is the era's first block.
+    pub(crate) parent: Option<RoundId>,
+}
+
+impl<C: Context> Proposal<C> {
+    /// Returns the proposal's hash, which validators sign in their echoes.
+    pub(crate) fn hash(&self) -> C::Hash {
+        let serialized = bincode::serialize(self).expect("should serialize proposal");
+        <C as Context>::hash(&serialized)
+    }
+}
+
+/// The content of a signed message.
+#[derive(DataSize, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "C::Hash: Serialize",
+    deserialize = "C::Hash: Deserialize<'de>",
+))]
+pub(crate) enum Content<C>
+where
+    C: Context,
+{
+    /// The sender received the round's proposal with this hash and considers it valid.
+    Echo(C::Hash),
+    /// The sender saw the round's proposal accepted in time (`true`), or timed out (`false`).
+    Vote(bool),
+}
+
+impl<C: Context> Clone for Content<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: Context> Copy for Content<C> {}
+
+impl<C: Context> Content<C> {
+    /// Returns whether a correct validator would never sign both `self` and `other` in the same
+    /// round.
+    pub(crate) fn conflicts_with(&self, other: &Content<C>) -> bool {
+        match (self, other) {
+            (Content::Echo(hash0), Content::Echo(hash1)) => hash0 != hash1,
+            (Content::Vote(vote0), Content::Vote(vote1)) => vote0 != vote1,
+            (Content::Echo(_), Content::Vote(_)) | (Content::Vote(_), Content::Echo(_)) => false,
+        }
+    }
+}
+
+/// A message signed by a validator, referring to a round of a particular era.
+#[derive(Clone, DataSize, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "C::Hash: Serialize",
+    deserialize = "C::Hash: Deserialize<'de>",
+))]
+pub(crate) struct SignedMessage<C>
+where
+    C: Context,
+{
+    pub(crate) round_id: RoundId,
+    pub(crate) instance_id: C::InstanceId,
+    pub(crate) content: Content<C>,
+    pub(crate) validator_idx: ValidatorIndex,
+    pub(crate) signature: C::Signature,
+}
+
+impl<C: Context> SignedMessage<C> {
+    /// Creates a new signed message with the validator's secret key, or returns `None` if signing
+    /// failed.
+    pub(crate) fn sign(
+        round_id: RoundId,
+        instance_id: C::InstanceId,
+        content: Content<C>,
+        validator_idx: ValidatorIndex,
+        secret: &C::ValidatorSecret,
+    ) -> Option<Self> {
+        let hash = Self::hash_fields(round_id, &instance_id, &content, validator_idx);
+        Some(SignedMessage {
+            round_id,
+            instance_id,
+            content,
+            validator_idx,
+            signature: secret.sign(&hash)?,
+        })
+    }
+
+    /// Returns a copy of this message with different content and signature, from the same
+    /// validator and for the same round.
+    pub(crate) fn with(&self, content: Content<C>, signature: C::Signature) -> Self {
+        SignedMessage {
+            round_id: self.round_id,
+            instance_id: self.instance_id,
+            content,
+            validator_idx: self.validator_idx,
+            signature,
+        }
+    }
+
+    /// Returns whether the signature is valid for the given validator ID.
+    pub(crate) fn verify_signature(&self, validator_id: &C::ValidatorId) -> bool {
+        let hash = Self::hash_fields(
+            self.round_id,
+            &self.instance_id,
+            &self.content,
+            self.validator_idx,
+        );
+        C::verify_signature(&hash, validator_id, &self.signature)
+    }
+
+    fn hash_fields(
+        round_id: RoundId,
+        instance_id: &C::InstanceId,
+        content: &Content<C>,
+        validator_idx: ValidatorIndex,
+    ) -> C::Hash {
+        let serialized = bincode::serialize(&(round_id, instance_id, content, validator_idx))
+            .expect("should serialize message fields");
+        <C as Context>::hash(&serialized)
+    }
+}
+
+/// A message sent between instances of the simple consensus protocol.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "C::Hash: Serialize",
+    deserialize = "C::Hash: Deserialize<'de>",
+))]
+pub(crate) enum Message<C>
+where
+    C: Context,
+{
+    /// A proposal, together with the leader's echo that signs it.
+    Proposal {
+        proposal: Proposal<C>,
+        echo: SignedMessage<C>,
+    },
+    /// An echo or vote by a validator.
+    Signed(SignedMessage<C>),
+    /// Two conflicting messages by the same validator in the same round: The second one is given
+    /// by its content and signature.
+    Evidence(SignedMessage<C>, Content<C>, C::Signature),
+    /// A request for all messages from round `first_round_id` on, up to the sender's current
+    /// round, and all evidence.
+    SyncRequest {
+        instance_id: C::InstanceId,
+        first_round_id: RoundId,
+    },
+    /// The response to a `SyncRequest`.
+    SyncResponse(Vec<Message<C>>),
+}
+
+impl<C: Context> Message<C> {
+    pub(crate) fn serialize(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("should serialize message")
+    }
+}
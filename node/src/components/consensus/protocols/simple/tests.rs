@@ -0,0 +1,370 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    mem,
+    sync::Arc,
+};
+
+use tempfile::tempdir;
+
+use casper_types::{system::auction::BLOCK_REWARD, PublicKey, SecretKey, U512};
+
+use super::{Content, Message, SignedMessage, SimpleConsensus, TIMER_ID_ROUND_TIMEOUT};
+use crate::{
+    components::consensus::{
+        cl_context::{ClContext, Keypair},
+        config::Config,
+        consensus_protocol::{ConsensusProtocol, FinalizedBlock, ProposedBlock, ProtocolOutcome},
+        highway_core::validators::ValidatorIndex,
+        tests::utils::new_test_chainspec,
+        traits::Context,
+        ActionId, TimerId,
+    },
+    types::{BlockPayload, Timestamp},
+};
+
+type NodeId = usize;
+
+const INSTANCE_ID_DATA: &[u8; 1] = &[123u8; 1];
+const PROPOSAL_TIMEOUT: &str = "1sec";
+
+fn instance_id() -> <ClContext as Context>::InstanceId {
+    ClContext::hash(INSTANCE_ID_DATA)
+}
+
+fn secret_key(seed: u8) -> Arc<SecretKey> {
+    Arc::new(SecretKey::ed25519_from_bytes([seed; SecretKey::ED25519_LENGTH]).unwrap())
+}
+
+/// Returns `count` validators with equal stakes, sorted by public key.
+fn validators(count: u8) -> Vec<(Arc<SecretKey>, PublicKey)> {
+    let mut validators: Vec<_> = (0..count)
+        .map(|seed| {
+            let secret_key = secret_key(seed);
+            let public_key = PublicKey::from(&*secret_key);
+            (secret_key, public_key)
+        })
+        .collect();
+    validators.sort_by(|(_, pk0), (_, pk1)| pk0.cmp(pk1));
+    validators
+}
+
+fn new_simple_consensus(
+    public_keys: &[PublicKey],
+) -> (
+    Box<dyn ConsensusProtocol<NodeId, ClContext>>,
+    Vec<ProtocolOutcome<NodeId, ClContext>>,
+) {
+    let stakes: BTreeMap<PublicKey, U512> = public_keys
+        .iter()
+        .map(|public_key| (public_key.clone(), U512::from(100)))
+        .collect();
+    let mut chainspec = new_test_chainspec(stakes.clone());
+    chainspec.core_config.minimum_era_height = 3;
+    chainspec.simple_config.proposal_timeout = PROPOSAL_TIMEOUT.parse().unwrap();
+    chainspec.simple_config.minimum_block_time = "100ms".parse().unwrap();
+    SimpleConsensus::<NodeId, ClContext>::new_boxed(
+        instance_id(),
+        stakes,
+        &Default::default(),
+        &Default::default(),
+        &(&chainspec).into(),
+        &Config::default(),
+        None,
+        Timestamp::zero(),
+        0,
+        Timestamp::zero(),
+    )
+}
+
+/// A set of instances that exchange messages directly, with a simulated clock.
+struct TestNetwork {
+    /// The instances, or `None` for validators that are offline.
+    instances: Vec<Option<Box<dyn ConsensusProtocol<NodeId, ClContext>>>>,
+    messages: VecDeque<(NodeId, NodeId, Vec<u8>)>,
+    timers: BTreeMap<Timestamp, Vec<(NodeId, TimerId)>>,
+    finalized: Vec<Vec<FinalizedBlock<ClContext>>>,
+    now: Timestamp,
+}
+
+impl TestNetwork {
+    /// Creates a network of validators with equal stakes, where the ones in `offline` don't
+    /// participate.
+    fn new(validators: &[(Arc<SecretKey>, PublicKey)], offline: &[NodeId]) -> Self {
+        let public_keys: Vec<PublicKey> = validators.iter().map(|(_, pk)| pk.clone()).collect();
+        let mut net = TestNetwork {
+            instances: vec![],
+            messages: VecDeque::new(),
+            timers: BTreeMap::new(),
+            finalized: vec![vec![]; validators.len()],
+            now: Timestamp::zero(),
+        };
+        let mut all_outcomes = vec![];
+        for (idx, (secret_key, public_key)) in validators.iter().enumerate() {
+            if offline.contains(&idx) {
+                net.instances.push(None);
+                continue;
+            }
+            let (mut sc, mut outcomes) = new_simple_consensus(&public_keys);
+            let keypair = Keypair::from(Arc::clone(secret_key));
+            outcomes.extend(sc.activate_validator(
+                public_key.clone(),
+                keypair,
+                net.now,
+                None,
+                None,
+            ));
+            net.instances.push(Some(sc));
+            all_outcomes.push((idx, outcomes));
+        }
+        for (idx, outcomes) in all_outcomes {
+            net.handle_outcomes(idx, outcomes);
+        }
+        net
+    }
+
+    fn handle_outcomes(&mut self, idx: NodeId, outcomes: Vec<ProtocolOutcome<NodeId, ClContext>>) {
+        let node_count = self.instances.len();
+        for outcome in outcomes {
+            match outcome {
+                ProtocolOutcome::CreatedGossipMessage(msg) => {
+                    for recipient in (0..node_count).filter(|recipient| *recipient != idx) {
+                        self.messages.push_back((idx, recipient, msg.clone()));
+                    }
+                }
+                ProtocolOutcome::CreatedTargetedMessage(msg, recipient) => {
+                    self.messages.push_back((idx, recipient, msg));
+                }
+                ProtocolOutcome::CreatedMessageToRandomPeer(msg) => {
+                    self.messages.push_back((idx, (idx + 1) % node_count, msg));
+                }
+                ProtocolOutcome::ScheduleTimer(timestamp, timer_id) => {
+                    self.timers
+                        .entry(timestamp)
+                        .or_default()
+                        .push((idx, timer_id));
+                }
+                ProtocolOutcome::CreateNewBlock(block_context) => {
+                    let payload = Arc::new(BlockPayload::new(vec![], vec![], vec![], false));
+                    let proposed_block = ProposedBlock::new(payload, block_context);
+                    let now = self.now;
+                    let outcomes = self.instance(idx).propose(proposed_block, now);
+                    self.handle_outcomes(idx, outcomes);
+                }
+                ProtocolOutcome::FinalizedBlock(mut finalized_block) => {
+                    // The summit depends on which votes a node received first, so it can differ
+                    // between nodes.
+                    let summit = mem::take(&mut finalized_block.summit);
+                    assert!(!summit.is_empty(), "finalized block without a summit");
+                    self.finalized[idx].push(finalized_block);
+                }
+                ProtocolOutcome::InvalidIncomingMessage(_, sender, err) => {
+                    panic!("invalid message from {}: {:?}", sender, err);
+                }
+                ProtocolOutcome::ValidateConsensusValue { .. }
+                | ProtocolOutcome::NewEvidence(_)
+                | ProtocolOutcome::SendEvidence(_, _)
+                | ProtocolOutcome::QueueAction(_)
+                | ProtocolOutcome::WeAreFaulty
+                | ProtocolOutcome::DoppelgangerDetected
+                | ProtocolOutcome::FttExceeded
+                | ProtocolOutcome::StandstillAlert
+                | ProtocolOutcome::Disconnect(_) => {
+                    panic!("unexpected outcome: {:?}", outcome);
+                }
+            }
+        }
+    }
+
+    fn instance(&mut self, idx: NodeId) -> &mut Box<dyn ConsensusProtocol<NodeId, ClContext>> {
+        self.instances[idx].as_mut().expect("instance is offline")
+    }
+
+    /// Delivers messages and fires timers until every online node has finalized the era's
+    /// terminal block, or the simulated clock reaches `max_time`.
+    fn run_until_era_end(&mut self, max_time: Timestamp) {
+        loop {
+            while let Some((sender, recipient, msg)) = self.messages.pop_front() {
+                let now = self.now;
+                if let Some(instance) = self.instances[recipient].as_mut() {
+                    let outcomes = instance.handle_message(sender, msg, now);
+                    self.handle_outcomes(recipient, outcomes);
+                }
+            }
+            if self.era_ended() {
+                return;
+            }
+            let (timestamp, timers) = match self.timers.iter().next() {
+                Some((timestamp, _)) if *timestamp <= max_time => {
+                    let timestamp = *timestamp;
+                    (timestamp, self.timers.remove(&timestamp).unwrap())
+                }
+                _ => panic!("era didn't end before {}", max_time),
+            };
+            self.now = self.now.max(timestamp);
+            for (idx, timer_id) in timers {
+                let now = self.now;
+                let outcomes = self.instance(idx).handle_timer(now, timer_id);
+                self.handle_outcomes(idx, outcomes);
+            }
+        }
+    }
+
+    fn era_ended(&self) -> bool {
+        self.instances
+            .iter()
+            .zip(&self.finalized)
+            .filter(|(instance, _)| instance.is_some())
+            .all(|(_, finalized)| {
+                finalized
+                    .last()
+                    .map_or(false, |block| block.terminal_block_data.is_some())
+            })
+    }
+
+    /// Asserts that all online nodes finalized the same blocks, and returns them.
+    fn finalized_blocks(&self) -> &[FinalizedBlock<ClContext>] {
+        let mut online = self
+            .instances
+            .iter()
+            .zip(&self.finalized)
+            .filter(|(instance, _)| instance.is_some())
+            .map(|(_, finalized)| finalized);
+        let first = online.next().expect("no online node");
+        for finalized in online {
+            assert_eq!(first, finalized);
+        }
+        for (height, block) in first.iter().enumerate() {
+            assert_eq!(height as u64, block.relative_height);
+        }
+        first
+    }
+}
+
+#[test]
+fn finalizes_blocks_until_era_end() {
+    let validators = validators(4);
+    let mut net = TestNetwork::new(&validators, &[]);
+    net.run_until_era_end(Timestamp::from(10_000));
+
+    let blocks = net.finalized_blocks();
+    assert_eq!(3, blocks.len());
+    assert!(blocks[..2]
+        .iter()
+        .all(|block| block.terminal_block_data.is_none()));
+    let terminal_block_data = blocks[2].terminal_block_data.as_ref().unwrap();
+    let total_reward: u64 = terminal_block_data.rewards.values().sum();
+    assert_eq!(3 * BLOCK_REWARD, total_reward);
+    assert!(terminal_block_data.inactive_validators.is_empty());
+    for block in blocks {
+        assert_eq!(BLOCK_REWARD, terminal_block_data.rewards[&block.proposer]);
+    }
+}
+
+#[test]
+fn ignores_unknown_timers_and_actions() {
+    let public_keys: Vec<PublicKey> = validators(3).into_iter().map(|(_, pk)| pk).collect();
+    let (mut sc, _) = new_simple_consensus(&public_keys);
+    assert!(sc
+        .handle_timer(Timestamp::zero(), TimerId(u8::MAX))
+        .is_empty());
+    assert!(sc.handle_action(ActionId(0), Timestamp::zero()).is_empty());
+}
+
+#[test]
+fn skips_rounds_of_offline_validator() {
+    let validators = validators(4);
+    let offline_idx = 2;
+    let mut net = TestNetwork::new(&validators, &[offline_idx]);
+    net.run_until_era_end(Timestamp::from(60_000));
+
+    let blocks = net.finalized_blocks();
+    assert_eq!(3, blocks.len());
+    let offline_key = &validators[offline_idx].1;
+    assert!(blocks.iter().all(|block| block.proposer != *offline_key));
+    let terminal_block_data = blocks[2].terminal_block_data.as_ref().unwrap();
+    assert_eq!(0, terminal_block_data.rewards[offline_key]);
+    assert!(terminal_block_data
+        .inactive_validators
+        .iter()
+        .all(|validator| validator == offline_key));
+}
+
+#[test]
+fn detects_conflicting_votes() {
+    let validators = validators(4);
+    let public_keys: Vec<PublicKey> = validators.iter().map(|(_, pk)| pk.clone()).collect();
+    let (mut sc, _) = new_simple_consensus(&public_keys);
+    let (mut other_sc, _) = new_simple_consensus(&public_keys);
+
+    let (secret_key, public_key) = &validators[1];
+    let keypair = Keypair::from(Arc::clone(secret_key));
+    let sign = |vote| {
+        let content = Content::<ClContext>::Vote(vote);
+        let signed_msg =
+            SignedMessage::sign(0, instance_id(), content, ValidatorIndex(1), &keypair)
+                .expect("should sign message");
+        Message::Signed(signed_msg).serialize()
+    };
+
+    let now = Timestamp::zero();
+    let outcomes = sc.handle_message(0, sign(true), now);
+    assert!(outcomes
+        .iter()
+        .all(|outcome| !matches!(outcome, ProtocolOutcome::NewEvidence(_))));
+    assert!(!sc.has_evidence(public_key));
+
+    let outcomes = sc.handle_message(0, sign(false), now);
+    assert!(outcomes.iter().any(|outcome| matches!(
+        outcome,
+        ProtocolOutcome::NewEvidence(validator) if validator == public_key
+    )));
+    assert!(sc.has_evidence(public_key));
+    assert_eq!(vec![public_key], sc.validators_with_evidence());
+
+    // The evidence convinces other instances, too.
+    let evidence = match sc.request_evidence(1, public_key).pop() {
+        Some(ProtocolOutcome::CreatedTargetedMessage(msg, 1)) => msg,
+        outcome => panic!("unexpected outcome: {:?}", outcome),
+    };
+    other_sc.handle_message(0, evidence, now);
+    assert!(other_sc.has_evidence(public_key));
+}
+
+#[test]
+fn does_not_contradict_messages_signed_before_restart() {
+    let validators = validators(4);
+    let public_keys: Vec<PublicKey> = validators.iter().map(|(_, pk)| pk.clone()).collect();
+    let (secret_key, public_key) = &validators[0];
+    let dir = tempdir().unwrap();
+    let wal_file = dir.path().join("wal");
+    let timeout = Timestamp::zero() + PROPOSAL_TIMEOUT.parse().unwrap();
+
+    // Vote to skip round 0, since the other validators are offline.
+    let (mut sc, _) = new_simple_consensus(&public_keys);
+    let keypair = Keypair::from(Arc::clone(secret_key));
+    sc.activate_validator(
+        public_key.clone(),
+        keypair,
+        Timestamp::zero(),
+        Some(wal_file.clone()),
+        None,
+    );
+    let vote = match sc.handle_timer(timeout, TIMER_ID_ROUND_TIMEOUT).pop() {
+        Some(ProtocolOutcome::CreatedGossipMessage(msg)) => msg,
+        outcome => panic!("unexpected outcome: {:?}", outcome),
+    };
+
+    // After a restart, we neither vote again nor mistake our old vote for a doppelganger's.
+    let (mut sc, _) = new_simple_consensus(&public_keys);
+    let keypair = Keypair::from(Arc::clone(secret_key));
+    sc.activate_validator(
+        public_key.clone(),
+        keypair,
+        Timestamp::zero(),
+        Some(wal_file),
+        None,
+    );
+    assert!(sc.handle_timer(timeout, TIMER_ID_ROUND_TIMEOUT).is_empty());
+    assert!(sc.handle_message(1, vote, timeout).is_empty());
+    assert!(sc.is_active());
+}
@@ -0,0 +1,651 @@
+//! Signing with the validator's secret key, either in-process or in a separate signer process.
+//!
+//! All signatures created with the validator key go through a [`Signer`]: consensus messages
+//! (units, pings, endorsements), finality signatures and the consensus certificates in network
+//! handshakes. The [`LocalSigner`] holds the secret key in memory, the [`RemoteSigner`] delegates
+//! to an external process over a Unix domain socket or a TCP connection with mutual TLS
+//! authentication.
+//!
+//! The remote signer protocol is a sequence of requests and responses on a single connection, each
+//! a JSON object on its own line. A [`Request`] is either
+//!
+//! * `{"type":"public_key"}`, answered with `{"public_key":"<hex>"}`, or
+//! * `{"type":"sign","payload":<payload>}`, answered with `{"signature":"<hex>"}`,
+//!
+//! and any request can be answered with `{"error":"<message>"}`. The [`SigningRequest`] payload is
+//! one of
+//!
+//! * `{"kind":"consensus","hash":"<hex>"}`: the hash of a consensus message, to be signed as is,
+//! * `{"kind":"finality_signature","block_hash":"<hex>","era_id":<u64>}`: the block hash followed
+//!   by the era ID as a little-endian `u64` is signed,
+//! * `{"kind":"handshake","connection_id":"<hex>"}`: the connection ID is signed as is.
+//!
+//! Consensus messages are signed on the reactor thread, since the consensus protocols can't
+//! continue without the signature. While a remote signer handles such a request, the whole node
+//! stalls, so every request, including a reconnection attempt, must complete within the configured
+//! timeout, which may not exceed [`MAX_REMOTE_SIGNER_TIMEOUT`].
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    os::unix::net::{UnixListener, UnixStream},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use datasize::DataSize;
+use openssl::ssl::{SslConnector, SslFiletype, SslMethod, SslStream, SslVerifyMode};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use casper_hashing::Digest;
+use casper_types::{EraId, PublicKey, SecretKey, Signature};
+
+use crate::{
+    crypto,
+    types::{BlockHash, FinalitySignature},
+    utils::{LoadError, Loadable},
+};
+
+/// The longest allowed timeout for a request to the remote signer, during which the node stalls.
+pub(crate) const MAX_REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// An error creating a signature.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// The secret key could not be loaded.
+    #[error("could not load secret key: {0}")]
+    LoadSecretKey(#[from] LoadError<<Arc<SecretKey> as Loadable>::Error>),
+    /// The remote signer's TLS configuration is invalid.
+    #[error("invalid TLS configuration for remote signer: {0}")]
+    Tls(#[from] openssl::error::ErrorStack),
+    /// The connection to the remote signer failed.
+    #[error("could not communicate with remote signer: {0}")]
+    Io(#[from] io::Error),
+    /// The remote signer sent an invalid response.
+    #[error("invalid response from remote signer: {0}")]
+    InvalidResponse(String),
+    /// The remote signer refused the request.
+    #[error("remote signer refused request: {0}")]
+    Refused(String),
+    /// The remote signer's timeout is missing, zero or too long.
+    #[error(
+        "a remote signer requires a non-zero remote_signer_timeout of at most {:?}",
+        MAX_REMOTE_SIGNER_TIMEOUT
+    )]
+    InvalidTimeout,
+    /// The remote signer didn't respond in time.
+    #[error("remote signer request timed out")]
+    TimedOut,
+}
+
+/// A payload to be signed with the validator key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub(crate) enum SigningRequest {
+    /// The hash of a consensus message, e.g. a unit, ping or endorsement.
+    Consensus {
+        /// The hash to sign.
+        hash: Digest,
+    },
+    /// A finality signature for a block.
+    FinalitySignature {
+        /// The hash of the finalized block.
+        block_hash: BlockHash,
+        /// The era the block belongs to.
+        era_id: EraId,
+    },
+    /// The consensus certificate in a network handshake, proving that we hold the validator key.
+    Handshake {
+        /// The ID of the network connection.
+        connection_id: Digest,
+    },
+}
+
+impl SigningRequest {
+    /// Returns the bytes that need to be signed.
+    fn bytes(&self) -> Vec<u8> {
+        match self {
+            SigningRequest::Consensus { hash } => hash.into_vec(),
+            SigningRequest::FinalitySignature { block_hash, era_id } => {
+                FinalitySignature::bytes_to_sign(block_hash, *era_id)
+            }
+            SigningRequest::Handshake { connection_id } => connection_id.into_vec(),
+        }
+    }
+}
+
+/// Creates signatures with the validator key.
+pub(crate) trait Signer: Send + Sync {
+    /// Returns the validator's public key.
+    fn public_key(&self) -> &PublicKey;
+
+    /// Returns a signature of the payload.
+    fn sign(&self, request: &SigningRequest) -> Result<Signature, Error>;
+}
+
+/// A signer holding the secret key in memory.
+pub(crate) struct LocalSigner {
+    secret_key: Arc<SecretKey>,
+    public_key: PublicKey,
+}
+
+impl LocalSigner {
+    /// Creates a signer with the given secret key.
+    pub(crate) fn new(secret_key: Arc<SecretKey>) -> Self {
+        let public_key = PublicKey::from(secret_key.as_ref());
+        LocalSigner {
+            secret_key,
+            public_key,
+        }
+    }
+}
+
+impl Signer for LocalSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    fn sign(&self, request: &SigningRequest) -> Result<Signature, Error> {
+        Ok(crypto::sign(
+            request.bytes(),
+            &self.secret_key,
+            &self.public_key,
+        ))
+    }
+}
+
+/// The configuration of the connection to a remote signer.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+#[serde(tag = "transport", rename_all = "snake_case", deny_unknown_fields)]
+pub(crate) enum RemoteSignerConfig {
+    /// A Unix domain socket on the same machine.
+    Unix {
+        /// Path to the socket the signer listens on.
+        socket_path: PathBuf,
+    },
+    /// A TCP connection with mutual TLS authentication.
+    Tls {
+        /// The signer's address, as `host:port`. The host must match the signer's certificate.
+        address: String,
+        /// Path to the certificate of the authority that issued the signer's certificate.
+        ca_certificate_path: PathBuf,
+        /// Path to the node's client certificate.
+        certificate_path: PathBuf,
+        /// Path to the secret key of the node's client certificate.
+        secret_key_path: PathBuf,
+    },
+}
+
+/// The resolved endpoint of a remote signer.
+enum Endpoint {
+    Unix(PathBuf),
+    Tls {
+        address: String,
+        connector: SslConnector,
+    },
+}
+
+impl Endpoint {
+    /// Creates the endpoint, resolving relative paths from `root`.
+    fn new(config: &RemoteSignerConfig, root: &Path) -> Result<Self, Error> {
+        Ok(match config {
+            RemoteSignerConfig::Unix { socket_path } => Endpoint::Unix(root.join(socket_path)),
+            RemoteSignerConfig::Tls {
+                address,
+                ca_certificate_path,
+                certificate_path,
+                secret_key_path,
+            } => {
+                let mut builder = SslConnector::builder(SslMethod::tls_client())?;
+                builder.set_ca_file(root.join(ca_certificate_path))?;
+                builder.set_certificate_chain_file(root.join(certificate_path))?;
+                builder.set_private_key_file(root.join(secret_key_path), SslFiletype::PEM)?;
+                builder.check_private_key()?;
+                builder.set_verify(SslVerifyMode::PEER);
+                Endpoint::Tls {
+                    address: address.clone(),
+                    connector: builder.build(),
+                }
+            }
+        })
+    }
+
+    /// Opens a new connection to the signer, which must be established before the deadline.
+    fn connect(&self, deadline: Instant) -> Result<Connection, Error> {
+        match self {
+            Endpoint::Unix(path) => {
+                let stream = UnixStream::connect(path)?;
+                stream.set_timeout(remaining(deadline)?)?;
+                Ok(Connection::new(Box::new(stream)))
+            }
+            Endpoint::Tls { address, connector } => {
+                let socket_addr = address.to_socket_addrs()?.next().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::NotFound, "could not resolve address")
+                })?;
+                let stream = TcpStream::connect_timeout(&socket_addr, remaining(deadline)?)?;
+                stream.set_read_timeout(Some(remaining(deadline)?))?;
+                stream.set_write_timeout(Some(remaining(deadline)?))?;
+                let host = address
+                    .rsplit_once(':')
+                    .map_or(address.as_str(), |(host, _)| host);
+                let tls_stream = connector
+                    .connect(host, stream)
+                    .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+                Ok(Connection::new(Box::new(tls_stream)))
+            }
+        }
+    }
+}
+
+impl Debug for Endpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Endpoint::Unix(path) => write!(f, "unix:{}", path.display()),
+            Endpoint::Tls { address, .. } => write!(f, "tls:{}", address),
+        }
+    }
+}
+
+/// Returns the instant at which a request started now times out.
+#[allow(clippy::integer_arithmetic)] // The timeout is at most `MAX_REMOTE_SIGNER_TIMEOUT`.
+fn deadline_after(timeout: Duration) -> Instant {
+    Instant::now() + timeout
+}
+
+/// Returns the time left until the deadline, or an error if it has passed.
+fn remaining(deadline: Instant) -> Result<Duration, Error> {
+    match deadline.checked_duration_since(Instant::now()) {
+        Some(remaining) if remaining > Duration::ZERO => Ok(remaining),
+        _ => Err(Error::TimedOut),
+    }
+}
+
+/// A bidirectional byte stream.
+trait Stream: io::Read + Write + Send {
+    /// Sets the timeout for both reading and writing.
+    fn set_timeout(&self, timeout: Duration) -> io::Result<()>;
+}
+
+impl Stream for UnixStream {
+    fn set_timeout(&self, timeout: Duration) -> io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+}
+
+impl Stream for SslStream<TcpStream> {
+    fn set_timeout(&self, timeout: Duration) -> io::Result<()> {
+        self.get_ref().set_read_timeout(Some(timeout))?;
+        self.get_ref().set_write_timeout(Some(timeout))
+    }
+}
+
+/// A request to the remote signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub(crate) enum Request {
+    /// Returns the validator's public key.
+    PublicKey,
+    /// Returns a signature of the payload.
+    Sign {
+        /// The payload to sign.
+        payload: SigningRequest,
+    },
+}
+
+/// A response from the remote signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub(crate) enum Response {
+    /// The validator's public key.
+    PublicKey(PublicKey),
+    /// The requested signature.
+    Signature(Signature),
+    /// The request failed or was refused.
+    Error(String),
+}
+
+/// A connection to the remote signer, sending one line-delimited JSON request at a time.
+struct Connection {
+    reader: BufReader<Box<dyn Stream>>,
+}
+
+impl Connection {
+    fn new(stream: Box<dyn Stream>) -> Self {
+        Connection {
+            reader: BufReader::new(stream),
+        }
+    }
+
+    /// Sends the request and returns the response, which must arrive before the deadline.
+    fn request(&mut self, request: &Request, deadline: Instant) -> Result<Response, Error> {
+        self.reader.get_ref().set_timeout(remaining(deadline)?)?;
+        write_message(self.reader.get_mut(), request)?;
+        self.reader.get_ref().set_timeout(remaining(deadline)?)?;
+        read_message(&mut self.reader)?.ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "remote signer closed the connection",
+            ))
+        })
+    }
+}
+
+/// A signer delegating to an external process.
+pub(crate) struct RemoteSigner {
+    endpoint: Endpoint,
+    public_key: PublicKey,
+    /// The time within which each request must be answered, including any reconnection attempt.
+    timeout: Duration,
+    /// The open connection, if any. It is reopened on the next request after an error.
+    connection: Mutex<Option<Connection>>,
+}
+
+impl RemoteSigner {
+    /// Connects to the remote signer and requests the public key.
+    ///
+    /// Fails if the timeout is zero or exceeds [`MAX_REMOTE_SIGNER_TIMEOUT`].
+    pub(crate) fn connect(
+        config: &RemoteSignerConfig,
+        timeout: Duration,
+        root: &Path,
+    ) -> Result<Self, Error> {
+        if timeout == Duration::ZERO || timeout > MAX_REMOTE_SIGNER_TIMEOUT {
+            return Err(Error::InvalidTimeout);
+        }
+        let endpoint = Endpoint::new(config, root)?;
+        let deadline = deadline_after(timeout);
+        let mut connection = endpoint.connect(deadline)?;
+        let public_key = match connection.request(&Request::PublicKey, deadline)? {
+            Response::PublicKey(public_key) => public_key,
+            Response::Error(msg) => return Err(Error::Refused(msg)),
+            response => return Err(Error::InvalidResponse(format!("{:?}", response))),
+        };
+        info!(?endpoint, %public_key, ?timeout, "connected to remote signer");
+        Ok(RemoteSigner {
+            endpoint,
+            public_key,
+            timeout,
+            connection: Mutex::new(Some(connection)),
+        })
+    }
+
+    /// Sends the request on the open connection, or on a new one if there is none.
+    fn request(&self, request: &Request, deadline: Instant) -> Result<Response, Error> {
+        // A poisoned lock only means another request failed; the connection is reset below.
+        let mut maybe_connection = self
+            .connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut connection = match maybe_connection.take() {
+            Some(connection) => connection,
+            None => self.endpoint.connect(deadline)?,
+        };
+        let response = connection.request(request, deadline)?;
+        *maybe_connection = Some(connection);
+        Ok(response)
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    fn sign(&self, payload: &SigningRequest) -> Result<Signature, Error> {
+        let request = Request::Sign {
+            payload: payload.clone(),
+        };
+        // If the connection was closed, e.g. because the signer restarted, retry once. Both
+        // attempts together must complete within the timeout.
+        let deadline = deadline_after(self.timeout);
+        let response = match self.request(&request, deadline) {
+            Ok(response) => response,
+            Err(err) => {
+                debug!(%err, "remote signer request failed; reconnecting");
+                self.request(&request, deadline)?
+            }
+        };
+        let signature = match response {
+            Response::Signature(signature) => signature,
+            Response::Error(msg) => return Err(Error::Refused(msg)),
+            response => return Err(Error::InvalidResponse(format!("{:?}", response))),
+        };
+        crypto::verify(payload.bytes(), &signature, &self.public_key).map_err(|err| {
+            Error::InvalidResponse(format!("invalid signature from remote signer: {}", err))
+        })?;
+        Ok(signature)
+    }
+}
+
+/// Writes the message as a single line of JSON.
+fn write_message<W: Write, T: Serialize>(writer: &mut W, message: &T) -> io::Result<()> {
+    let mut bytes = serde_json::to_vec(message)?;
+    bytes.push(b'\n');
+    writer.write_all(&bytes)?;
+    writer.flush()
+}
+
+/// Reads a single line of JSON, or returns `None` if the stream has ended.
+fn read_message<R: BufRead, T: for<'de> Deserialize<'de>>(
+    reader: &mut R,
+) -> Result<Option<T>, Error> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    serde_json::from_str(&line)
+        .map(Some)
+        .map_err(|err| Error::InvalidResponse(err.to_string()))
+}
+
+/// Serves signing requests on the Unix domain socket at the given path, using the given signer.
+///
+/// This is a reference implementation of the remote signer, meant to run as a separate process
+/// with access to the secret key, e.g. as a different user. Each connection is served in its own
+/// thread, since the consensus and networking components of a node connect separately.
+pub(crate) fn serve_unix(socket_path: &Path, signer: Arc<dyn Signer>) -> Result<(), Error> {
+    let listener = UnixListener::bind(socket_path)?;
+    info!(
+        socket_path = %socket_path.display(),
+        public_key = %signer.public_key(),
+        "remote signer listening"
+    );
+    serve(listener, signer);
+    Ok(())
+}
+
+/// Accepts connections on the listener and serves each of them in a new thread.
+fn serve(listener: UnixListener, signer: Arc<dyn Signer>) {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let signer = Arc::clone(&signer);
+                thread::spawn(move || {
+                    if let Err(err) = serve_connection(BufReader::new(stream), &*signer) {
+                        warn!(%err, "remote signer connection failed");
+                    }
+                });
+            }
+            Err(err) => warn!(%err, "failed to accept remote signer connection"),
+        }
+    }
+}
+
+/// Answers requests on a single connection until it is closed.
+fn serve_connection<S>(mut reader: BufReader<S>, signer: &dyn Signer) -> Result<(), Error>
+where
+    S: io::Read + Write,
+{
+    while let Some(request) = read_message::<_, Request>(&mut reader)? {
+        let response = match request {
+            Request::PublicKey => Response::PublicKey(signer.public_key().clone()),
+            Request::Sign { payload } => {
+                debug!(?payload, "signing");
+                match signer.sign(&payload) {
+                    Ok(signature) => Response::Signature(signature),
+                    Err(err) => Response::Error(err.to_string()),
+                }
+            }
+        };
+        write_message(reader.get_mut(), &response)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_millis(500);
+
+    fn secret_key(seed: u8) -> Arc<SecretKey> {
+        Arc::new(SecretKey::ed25519_from_bytes([seed; 32]).unwrap())
+    }
+
+    /// Starts a signer server in a background thread and returns its configuration.
+    fn start_server(dir: &Path, signer: Arc<dyn Signer>) -> RemoteSignerConfig {
+        let socket_path = dir.join("signer.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        thread::spawn(move || serve(listener, signer));
+        RemoteSignerConfig::Unix { socket_path }
+    }
+
+    /// A signer that claims to hold one key but signs with another.
+    struct WrongKeySigner {
+        claimed: LocalSigner,
+        actual: LocalSigner,
+    }
+
+    impl Signer for WrongKeySigner {
+        fn public_key(&self) -> &PublicKey {
+            self.claimed.public_key()
+        }
+
+        fn sign(&self, request: &SigningRequest) -> Result<Signature, Error> {
+            self.actual.sign(request)
+        }
+    }
+
+    #[test]
+    fn remote_signer_should_sign_all_payloads() {
+        let dir = tempdir().unwrap();
+        let local = Arc::new(LocalSigner::new(secret_key(1)));
+        let config = start_server(dir.path(), local.clone());
+        let remote = RemoteSigner::connect(&config, TIMEOUT, dir.path()).unwrap();
+        assert_eq!(local.public_key(), remote.public_key());
+
+        let block_hash = BlockHash::new(Digest::hash(b"block"));
+        let era_id = EraId::new(7);
+        let requests = vec![
+            SigningRequest::Consensus {
+                hash: Digest::hash(b"unit"),
+            },
+            SigningRequest::FinalitySignature { block_hash, era_id },
+            SigningRequest::Handshake {
+                connection_id: Digest::hash(b"connection"),
+            },
+        ];
+        for request in &requests {
+            let signature = remote.sign(request).unwrap();
+            assert_eq!(local.sign(request).unwrap(), signature);
+        }
+
+        // The finality signature payload must match what peers verify.
+        let signature = remote.sign(&requests[1]).unwrap();
+        let finality_signature = FinalitySignature {
+            block_hash,
+            era_id,
+            signature,
+            public_key: remote.public_key().clone(),
+        };
+        assert!(finality_signature.verify().is_ok());
+    }
+
+    #[test]
+    fn remote_signer_should_reject_invalid_signatures() {
+        let dir = tempdir().unwrap();
+        let signer = Arc::new(WrongKeySigner {
+            claimed: LocalSigner::new(secret_key(1)),
+            actual: LocalSigner::new(secret_key(2)),
+        });
+        let config = start_server(dir.path(), signer);
+        let remote = RemoteSigner::connect(&config, TIMEOUT, dir.path()).unwrap();
+        let request = SigningRequest::Consensus {
+            hash: Digest::hash(b"unit"),
+        };
+        assert!(matches!(
+            remote.sign(&request),
+            Err(Error::InvalidResponse(_))
+        ));
+    }
+
+    /// A signer that takes the given time to create each signature.
+    struct SlowSigner {
+        inner: LocalSigner,
+        delay: Duration,
+    }
+
+    impl Signer for SlowSigner {
+        fn public_key(&self) -> &PublicKey {
+            self.inner.public_key()
+        }
+
+        fn sign(&self, request: &SigningRequest) -> Result<Signature, Error> {
+            thread::sleep(self.delay);
+            self.inner.sign(request)
+        }
+    }
+
+    #[test]
+    fn remote_signer_should_give_up_after_timeout() {
+        let dir = tempdir().unwrap();
+        let signer = Arc::new(SlowSigner {
+            inner: LocalSigner::new(secret_key(1)),
+            delay: 4 * TIMEOUT,
+        });
+        let config = start_server(dir.path(), signer);
+        let remote = RemoteSigner::connect(&config, TIMEOUT, dir.path()).unwrap();
+        let request = SigningRequest::Consensus {
+            hash: Digest::hash(b"unit"),
+        };
+        let start = Instant::now();
+        assert!(remote.sign(&request).is_err());
+        // The reconnection attempt doesn't extend the time the caller is blocked.
+        assert!(start.elapsed() < 2 * TIMEOUT);
+    }
+
+    #[test]
+    fn should_refuse_invalid_timeouts() {
+        let dir = tempdir().unwrap();
+        let config = start_server(dir.path(), Arc::new(LocalSigner::new(secret_key(1))));
+        for timeout in [
+            Duration::ZERO,
+            MAX_REMOTE_SIGNER_TIMEOUT + Duration::from_millis(1),
+        ] {
+            assert!(matches!(
+                RemoteSigner::connect(&config, timeout, dir.path()),
+                Err(Error::InvalidTimeout)
+            ));
+        }
+    }
+
+    #[test]
+    fn should_fail_to_connect_without_server() {
+        let dir = tempdir().unwrap();
+        let config = RemoteSignerConfig::Unix {
+            socket_path: dir.path().join("missing.sock"),
+        };
+        assert!(matches!(
+            RemoteSigner::connect(&config, TIMEOUT, dir.path()),
+            Err(Error::Io(_))
+        ));
+    }
+}
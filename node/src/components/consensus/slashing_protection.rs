@@ -0,0 +1,493 @@
+//! A slashing-protection database that can be shared by several nodes using the same validator
+//! key.
+//!
+//! The unit hash files only protect a single node's data directory: If a backup is restored or a
+//! failover node is started with the same key, the node could sign conflicting messages and get
+//! slashed for equivocating. The slashing-protection database records, per validator and era, the
+//! latest unit we signed in each consensus protocol instance and the blocks we created finality
+//! signatures for, and refuses to sign anything conflicting with that history. Eras whose
+//! validators are no longer bonded are pruned at every era switch.
+//!
+//! The database is a JSON file, protected by an exclusive lock on a separate lock file, so it can
+//! be accessed by several processes concurrently, e.g. on a shared file system. The history can be
+//! exported to and imported from a file in the same format, to move it between machines.
+
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+};
+
+use datasize::DataSize;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use casper_hashing::Digest;
+use casper_types::{EraId, PublicKey};
+
+use crate::{
+    types::{BlockHash, Timestamp},
+    utils,
+};
+
+/// An error accessing the slashing-protection database, or a refusal to sign a message.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// The database or its lock file could not be read or written.
+    #[error("failed to access slashing-protection database {path}: {error}")]
+    Io {
+        /// The path of the file that could not be accessed.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// The database file is corrupted.
+    #[error("failed to parse slashing-protection database {path}: {error}")]
+    Parse {
+        /// The path of the file that could not be parsed.
+        path: PathBuf,
+        /// The underlying error.
+        error: serde_json::Error,
+    },
+    /// Signing the unit would conflict with a unit we signed before.
+    #[error(
+        "refusing to sign unit {seq_number} in era {era_id}: already signed unit {} with hash {}",
+        .signed.seq_number,
+        .signed.unit_hash
+    )]
+    ConflictingUnit {
+        /// The era of the unit.
+        era_id: EraId,
+        /// The sequence number of the unit we were asked to sign.
+        seq_number: u64,
+        /// The latest unit we signed before.
+        signed: SignedUnit,
+    },
+    /// Signing the finality signature would conflict with one we created before.
+    #[error(
+        "refusing to sign block {block_hash} at height {height} in era {era_id}: already signed \
+         block {signed}"
+    )]
+    ConflictingFinalitySignature {
+        /// The era of the block.
+        era_id: EraId,
+        /// The block's height.
+        height: u64,
+        /// The hash of the block we were asked to sign.
+        block_hash: BlockHash,
+        /// The hash of the block at the same height that we signed before.
+        signed: BlockHash,
+    },
+}
+
+/// The latest unit a validator signed in a consensus protocol instance.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SignedUnit {
+    /// The ID of the consensus protocol instance. Units in a different instance of the same era,
+    /// e.g. after an emergency restart, don't conflict with this one.
+    pub(crate) instance_id: Digest,
+    /// The unit's sequence number.
+    pub(crate) seq_number: u64,
+    /// The unit's timestamp.
+    pub(crate) timestamp: Timestamp,
+    /// The unit's hash.
+    pub(crate) unit_hash: String,
+}
+
+/// The messages a validator signed in a single era.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct EraHistory {
+    /// The latest unit signed in each instance of this era. There is usually only one instance
+    /// per era, but there can be more e.g. after an emergency restart.
+    pub(crate) last_units: Vec<SignedUnit>,
+    /// The hashes of the blocks we created finality signatures for, by height.
+    pub(crate) finality_signatures: BTreeMap<u64, BlockHash>,
+}
+
+impl EraHistory {
+    /// Returns the latest unit signed in the given instance, if any.
+    fn last_unit_mut(&mut self, instance_id: &Digest) -> Option<&mut SignedUnit> {
+        self.last_units
+            .iter_mut()
+            .find(|signed| signed.instance_id == *instance_id)
+    }
+
+    /// Adds the other history's entries to this one, keeping the later unit in each instance.
+    /// Conflicting entries are kept from `self`: Either way, any new message conflicting with them
+    /// will be refused.
+    fn merge(&mut self, other: EraHistory) {
+        for theirs in other.last_units {
+            match self.last_unit_mut(&theirs.instance_id) {
+                Some(ours) if ours.seq_number >= theirs.seq_number => {}
+                Some(ours) => *ours = theirs,
+                None => self.last_units.push(theirs),
+            }
+        }
+        for (height, block_hash) in other.finality_signatures {
+            self.finality_signatures.entry(height).or_insert(block_hash);
+        }
+    }
+}
+
+/// The signing history of all validators, by public key and era.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct History {
+    pub(crate) validators: BTreeMap<PublicKey, BTreeMap<EraId, EraHistory>>,
+}
+
+impl History {
+    /// Adds the other history's entries to this one.
+    pub(crate) fn merge(&mut self, other: History) {
+        for (public_key, eras) in other.validators {
+            let our_eras = self.validators.entry(public_key).or_default();
+            for (era_id, era_history) in eras {
+                match our_eras.entry(era_id) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(era_history);
+                    }
+                    Entry::Occupied(mut entry) => entry.get_mut().merge(era_history),
+                }
+            }
+        }
+    }
+
+    /// Returns the validator's history in the given era.
+    fn era_mut(&mut self, public_key: &PublicKey, era_id: EraId) -> &mut EraHistory {
+        self.validators
+            .entry(public_key.clone())
+            .or_default()
+            .entry(era_id)
+            .or_default()
+    }
+}
+
+/// A handle to the slashing-protection database file.
+#[derive(Clone, DataSize, Debug)]
+pub(crate) struct SlashingProtection {
+    /// The path to the database file.
+    path: PathBuf,
+    /// The path to the lock file that serializes access to the database.
+    lock_path: PathBuf,
+}
+
+impl SlashingProtection {
+    /// Opens the database at the given path, creating it if it doesn't exist.
+    pub(crate) fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let mut lock_path = path.clone().into_os_string();
+        lock_path.push(".lock");
+        let slashing_protection = SlashingProtection {
+            path,
+            lock_path: lock_path.into(),
+        };
+        // Make sure the database can be read, so that errors are detected on startup.
+        slashing_protection.update(|_| Ok(()))?;
+        Ok(slashing_protection)
+    }
+
+    /// Returns a guard for signing units in the given era.
+    pub(crate) fn guard(
+        &self,
+        public_key: PublicKey,
+        era_id: EraId,
+        instance_id: Digest,
+    ) -> SlashingGuard {
+        SlashingGuard {
+            slashing_protection: self.clone(),
+            public_key,
+            era_id,
+            instance_id,
+        }
+    }
+
+    /// Records a finality signature for the given block, unless we already signed a different
+    /// block at the same height in the same era.
+    pub(crate) fn check_finality_signature(
+        &self,
+        public_key: &PublicKey,
+        era_id: EraId,
+        height: u64,
+        block_hash: BlockHash,
+    ) -> Result<(), Error> {
+        self.update(|history| {
+            let era_history = history.era_mut(public_key, era_id);
+            match era_history.finality_signatures.entry(height) {
+                Entry::Vacant(entry) => {
+                    entry.insert(block_hash);
+                    Ok(())
+                }
+                Entry::Occupied(entry) if *entry.get() == block_hash => Ok(()),
+                Entry::Occupied(entry) => Err(Error::ConflictingFinalitySignature {
+                    era_id,
+                    height,
+                    block_hash,
+                    signed: *entry.get(),
+                }),
+            }
+        })
+    }
+
+    /// Removes the validator's history for all eras before `era_id`.
+    pub(crate) fn prune(&self, public_key: &PublicKey, era_id: EraId) -> Result<(), Error> {
+        self.update(|history| {
+            if let Some(eras) = history.validators.get_mut(public_key) {
+                *eras = eras.split_off(&era_id);
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns the complete history in the database.
+    pub(crate) fn export(&self) -> Result<History, Error> {
+        let mut exported = History::default();
+        self.update(|history| {
+            exported = history.clone();
+            Ok(())
+        })?;
+        Ok(exported)
+    }
+
+    /// Merges the given history into the database.
+    pub(crate) fn import(&self, imported: History) -> Result<(), Error> {
+        self.update(|history| {
+            history.merge(imported);
+            Ok(())
+        })
+    }
+
+    /// Locks the database, applies `f` to the history and writes the result back unless `f`
+    /// returns an error.
+    fn update<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut History) -> Result<(), Error>,
+    {
+        let io_err = |path: &Path| {
+            let path = path.to_path_buf();
+            move |error| Error::Io { path, error }
+        };
+        if let Some(parent_directory) = self.path.parent() {
+            fs::create_dir_all(parent_directory).map_err(io_err(parent_directory))?;
+        }
+        let lock_file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&self.lock_path)
+            .map_err(io_err(&self.lock_path))?;
+        lock_file
+            .lock_exclusive()
+            .map_err(io_err(&self.lock_path))?;
+
+        let mut history = match fs::read(&self.path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|error| Error::Parse {
+                path: self.path.clone(),
+                error,
+            })?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => History::default(),
+            Err(err) => return Err(io_err(&self.path)(err)),
+        };
+        let old_history = history.clone();
+        f(&mut history)?;
+        if history != old_history || !self.path.exists() {
+            self.write(&history).map_err(io_err(&self.path))?;
+        }
+        // The lock is released when the file is closed.
+        Ok(())
+    }
+
+    /// Writes the history to the database path, without ever leaving the database in a partially
+    /// written state.
+    fn write(&self, history: &History) -> io::Result<()> {
+        utils::write_file_atomically(&self.path, serde_json::to_vec_pretty(history)?)
+    }
+}
+
+/// Checks and records the units a validator signs in a single era.
+#[derive(Clone, DataSize, Debug)]
+pub(crate) struct SlashingGuard {
+    slashing_protection: SlashingProtection,
+    public_key: PublicKey,
+    era_id: EraId,
+    instance_id: Digest,
+}
+
+impl SlashingGuard {
+    /// Records the unit as signed, unless it conflicts with an earlier one: We only sign units
+    /// with a higher sequence number than any we signed before, or the same unit again.
+    pub(crate) fn check_unit<H: Display>(
+        &self,
+        seq_number: u64,
+        timestamp: Timestamp,
+        unit_hash: &H,
+    ) -> Result<(), Error> {
+        let unit = SignedUnit {
+            instance_id: self.instance_id,
+            seq_number,
+            timestamp,
+            unit_hash: unit_hash.to_string(),
+        };
+        self.slashing_protection.update(|history| {
+            let era_history = history.era_mut(&self.public_key, self.era_id);
+            match era_history.last_unit_mut(&self.instance_id) {
+                Some(signed) if *signed == unit => Ok(()),
+                Some(signed) if signed.seq_number >= unit.seq_number => {
+                    Err(Error::ConflictingUnit {
+                        era_id: self.era_id,
+                        seq_number,
+                        signed: signed.clone(),
+                    })
+                }
+                Some(signed) => {
+                    *signed = unit;
+                    Ok(())
+                }
+                None => {
+                    era_history.last_units.push(unit);
+                    Ok(())
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use casper_types::SecretKey;
+
+    use super::*;
+    use crate::testing::TestRng;
+
+    fn public_key(seed: u8) -> PublicKey {
+        PublicKey::from(&SecretKey::ed25519_from_bytes([seed; 32]).unwrap())
+    }
+
+    #[test]
+    fn should_refuse_conflicting_units() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("slashing_protection.json");
+        let alice = public_key(1);
+        let instance_id = Digest::hash(b"instance");
+        let era_id = EraId::new(3);
+        let node1 =
+            SlashingProtection::open(&path)
+                .unwrap()
+                .guard(alice.clone(), era_id, instance_id);
+        let node2 =
+            SlashingProtection::open(&path)
+                .unwrap()
+                .guard(alice.clone(), era_id, instance_id);
+        let timestamp = Timestamp::from(1000);
+
+        node1.check_unit(0, timestamp, &"a").unwrap();
+        node1.check_unit(1, timestamp, &"b").unwrap();
+        // Signing the same unit again is fine.
+        node2.check_unit(1, timestamp, &"b").unwrap();
+        // A different unit with the same or a lower sequence number is refused.
+        assert!(matches!(
+            node2.check_unit(1, timestamp, &"c"),
+            Err(Error::ConflictingUnit { .. })
+        ));
+        assert!(matches!(
+            node2.check_unit(0, timestamp, &"a"),
+            Err(Error::ConflictingUnit { .. })
+        ));
+        node2.check_unit(2, timestamp, &"d").unwrap();
+
+        // Other eras, instances and validators are independent.
+        let db = SlashingProtection::open(&path).unwrap();
+        db.guard(alice.clone(), EraId::new(4), instance_id)
+            .check_unit(0, timestamp, &"e")
+            .unwrap();
+        db.guard(alice, era_id, Digest::hash(b"other instance"))
+            .check_unit(0, timestamp, &"f")
+            .unwrap();
+        // Signing in the other instance doesn't affect the history of the first one.
+        assert!(matches!(
+            node2.check_unit(2, timestamp, &"h"),
+            Err(Error::ConflictingUnit { .. })
+        ));
+        db.guard(public_key(2), era_id, instance_id)
+            .check_unit(0, timestamp, &"g")
+            .unwrap();
+    }
+
+    #[test]
+    fn should_refuse_conflicting_finality_signatures() {
+        let mut rng = TestRng::new();
+        let dir = tempdir().unwrap();
+        let db = SlashingProtection::open(dir.path().join("db.json")).unwrap();
+        let alice = public_key(1);
+        let era_id = EraId::new(1);
+        let block_hash = BlockHash::random(&mut rng);
+
+        db.check_finality_signature(&alice, era_id, 5, block_hash)
+            .unwrap();
+        db.check_finality_signature(&alice, era_id, 5, block_hash)
+            .unwrap();
+        assert!(matches!(
+            db.check_finality_signature(&alice, era_id, 5, BlockHash::random(&mut rng)),
+            Err(Error::ConflictingFinalitySignature { .. })
+        ));
+        db.check_finality_signature(&alice, era_id, 6, BlockHash::random(&mut rng))
+            .unwrap();
+    }
+
+    #[test]
+    fn should_export_import_and_prune() {
+        let mut rng = TestRng::new();
+        let dir = tempdir().unwrap();
+        let db1 = SlashingProtection::open(dir.path().join("db1.json")).unwrap();
+        let db2 = SlashingProtection::open(dir.path().join("db2.json")).unwrap();
+        let alice = public_key(1);
+        let instance_id = Digest::hash(b"instance");
+        let timestamp = Timestamp::from(1000);
+        for era in 1..4 {
+            let guard = db1.guard(alice.clone(), EraId::new(era), instance_id);
+            guard.check_unit(7, timestamp, &"a").unwrap();
+        }
+        db2.guard(alice.clone(), EraId::new(3), instance_id)
+            .check_unit(9, timestamp, &"b")
+            .unwrap();
+        let block_hash = BlockHash::random(&mut rng);
+        db2.check_finality_signature(&alice, EraId::new(3), 10, block_hash)
+            .unwrap();
+
+        // The exported history survives a round trip through JSON.
+        let json = serde_json::to_string(&db1.export().unwrap()).unwrap();
+        db2.import(serde_json::from_str(&json).unwrap()).unwrap();
+
+        let guard = db2.guard(alice.clone(), EraId::new(1), instance_id);
+        assert!(guard.check_unit(6, timestamp, &"c").is_err());
+        // The later unit from db2 is kept.
+        let guard = db2.guard(alice.clone(), EraId::new(3), instance_id);
+        assert!(guard.check_unit(8, timestamp, &"c").is_err());
+        assert!(db2
+            .check_finality_signature(&alice, EraId::new(3), 10, BlockHash::random(&mut rng))
+            .is_err());
+
+        // Units from other instances of the same era are kept and enforced, too.
+        let other_instance_id = Digest::hash(b"other instance");
+        let db3 = SlashingProtection::open(dir.path().join("db3.json")).unwrap();
+        db3.guard(alice.clone(), EraId::new(3), other_instance_id)
+            .check_unit(4, timestamp, &"d")
+            .unwrap();
+        db2.import(db3.export().unwrap()).unwrap();
+        let guard = db2.guard(alice.clone(), EraId::new(3), other_instance_id);
+        assert!(guard.check_unit(3, timestamp, &"e").is_err());
+        let guard = db2.guard(alice.clone(), EraId::new(3), instance_id);
+        assert!(guard.check_unit(8, timestamp, &"e").is_err());
+
+        db2.prune(&alice, EraId::new(3)).unwrap();
+        let history = db2.export().unwrap();
+        let eras: Vec<_> = history.validators[&alice].keys().copied().collect();
+        assert_eq!(eras, vec![EraId::new(3)]);
+    }
+}
@@ -0,0 +1,47 @@
+use datasize::DataSize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{EraId, PublicKey};
+
+use crate::types::{BlockHash, Timestamp};
+
+/// Diagnostic information about consensus, e.g. to find out why blocks are slow to finalize.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ConsensusStatus {
+    /// The current era.
+    pub era_id: EraId,
+    /// Our next round exponent, if we are an active Highway validator in the current era. Rounds
+    /// are `2^round_exponent` milliseconds long.
+    pub round_exponent: Option<u8>,
+    /// The validators we have seen equivocating in the current era.
+    pub equivocators: Vec<PublicKey>,
+    /// The validators we consider faulty in the current era. In addition to the equivocators,
+    /// this contains validators banned because of faults in earlier eras.
+    pub faulty_validators: Vec<PublicKey>,
+    /// The most recently finalized block.
+    pub last_finalized_block: Option<FinalizedBlockStatus>,
+    /// The most recently finalized blocks, oldest first.
+    pub recent_finalized_blocks: Vec<FinalizedBlockStatus>,
+}
+
+/// Information about how a block was finalized.
+#[derive(Clone, DataSize, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FinalizedBlockStatus {
+    /// The block's hash, if it has already been executed.
+    pub block_hash: Option<BlockHash>,
+    /// The block's era.
+    pub era_id: EraId,
+    /// The block's height.
+    pub height: u64,
+    /// The validator who proposed the block.
+    pub proposer: PublicKey,
+    /// The time when the block was proposed.
+    pub proposed_at: Timestamp,
+    /// The time when this node saw the block finalized.
+    pub finalized_at: Timestamp,
+    /// The validators whose units contributed to the summit that finalized the block.
+    pub summit: Vec<PublicKey>,
+}
@@ -3,4 +3,5 @@ pub(crate) mod consensus_des_testing;
 /// Message queue.
 pub(crate) mod queue;
 /// Miscellaneous code shared among consensus tests
+#[cfg(test)]
 pub(crate) mod utils;
@@ -270,6 +270,7 @@ where
     }
 }
 
+#[cfg(test)]
 mod virtual_net_tests {
     use super::{Message, Node, Target, TargetedMessage, Timestamp, ValidatorId, VirtualNet};
 
@@ -31,7 +31,7 @@ use casper_execution_engine::{
     },
 };
 use casper_hashing::Digest;
-use casper_types::ProtocolVersion;
+use casper_types::{system::auction::InactivityPolicy, ProtocolVersion};
 
 use crate::{
     components::{contract_runtime::types::StepEffectAndUpcomingEraValidators, Component},
@@ -46,7 +46,9 @@ use crate::{
 pub(crate) use announcements::ContractRuntimeAnnouncement;
 pub(crate) use config::Config;
 pub(crate) use error::{BlockExecutionError, ConfigError};
-pub(crate) use types::{BlockAndExecutionEffects, EraValidatorsRequest};
+pub(crate) use types::{
+    inactivity_policy, BlockAndExecutionEffects, EraValidatorsRequest, SlashingConfig,
+};
 
 /// State to use to construct the next block in the blockchain. Includes the state root hash for the
 /// execution engine as well as certain values the next header will be based on.
@@ -105,6 +107,9 @@ pub(crate) struct ContractRuntime {
     engine_state: Arc<EngineState<LmdbGlobalState>>,
     metrics: Arc<ContractRuntimeMetrics>,
     protocol_version: ProtocolVersion,
+    slashing_config: SlashingConfig,
+    #[data_size(skip)]
+    inactivity_policy: InactivityPolicy,
 
     /// Finalized blocks waiting for their pre-state hash to start executing.
     exec_queue: ExecQueue,
@@ -409,11 +414,15 @@ where
                 );
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let slashing_config = self.slashing_config;
+                let inactivity_policy = self.inactivity_policy;
                 tokio::task::unconstrained(async move {
                     let result = operations::execute_finalized_block(
                         engine_state.as_ref(),
                         metrics.as_ref(),
                         protocol_version,
+                        slashing_config,
+                        inactivity_policy,
                         execution_pre_state,
                         finalized_block,
                         deploys,
@@ -436,6 +445,8 @@ where
                 let exec_queue = Arc::clone(&self.exec_queue);
                 let execution_pre_state = Arc::clone(&self.execution_pre_state);
                 let protocol_version = self.protocol_version;
+                let slashing_config = self.slashing_config;
+                let inactivity_policy = self.inactivity_policy;
                 if self.execution_pre_state.lock().unwrap().next_block_height
                     == finalized_block.height()
                 {
@@ -447,6 +458,8 @@ where
                             execution_pre_state,
                             effect_builder,
                             protocol_version,
+                            slashing_config,
+                            inactivity_policy,
                             finalized_block,
                             deploys,
                             transfers,
@@ -483,6 +496,7 @@ where
 }
 
 impl ContractRuntime {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         protocol_version: ProtocolVersion,
         storage_dir: &Path,
@@ -490,6 +504,8 @@ impl ContractRuntime {
         wasm_config: WasmConfig,
         system_config: SystemConfig,
         max_associated_keys: u32,
+        slashing_config: SlashingConfig,
+        inactivity_policy: InactivityPolicy,
         registry: &Registry,
     ) -> Result<Self, ConfigError> {
         // TODO: This is bogus, get rid of this
@@ -528,6 +544,8 @@ impl ContractRuntime {
         Ok(ContractRuntime {
             execution_pre_state,
             protocol_version,
+            slashing_config,
+            inactivity_policy,
             exec_queue: Arc::new(Mutex::new(BTreeMap::new())),
             engine_state,
             metrics,
@@ -590,6 +608,8 @@ impl ContractRuntime {
         execution_pre_state: Arc<Mutex<ExecutionPreState>>,
         effect_builder: EffectBuilder<REv>,
         protocol_version: ProtocolVersion,
+        slashing_config: SlashingConfig,
+        inactivity_policy: InactivityPolicy,
         finalized_block: FinalizedBlock,
         deploys: Vec<Deploy>,
         transfers: Vec<Deploy>,
@@ -609,6 +629,8 @@ impl ContractRuntime {
                 engine_state.as_ref(),
                 metrics.as_ref(),
                 protocol_version,
+                slashing_config,
+                inactivity_policy,
                 current_execution_pre_state,
                 finalized_block,
                 deploys,
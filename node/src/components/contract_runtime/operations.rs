@@ -10,29 +10,35 @@ use casper_execution_engine::{
     core::engine_state::{
         self, step::EvictItem, DeployItem, EngineState, ExecuteRequest,
         ExecutionResult as EngineExecutionResult, ExecutionResults, GetEraValidatorsRequest,
-        RewardItem, StepError, StepRequest, StepSuccess,
+        RewardItem, SlashItem, StepError, StepRequest, StepSuccess,
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::global_state::lmdb::LmdbGlobalState,
 };
 use casper_hashing::Digest;
-use casper_types::{EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, U512};
+use casper_types::{
+    system::auction::InactivityPolicy, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey,
+    U512,
+};
 
 use crate::{
     components::{
         consensus::EraReport,
         contract_runtime::{
             error::BlockExecutionError, types::StepEffectAndUpcomingEraValidators,
-            BlockAndExecutionEffects, ContractRuntimeMetrics, ExecutionPreState,
+            BlockAndExecutionEffects, ContractRuntimeMetrics, ExecutionPreState, SlashingConfig,
         },
     },
     types::{Block, Deploy, DeployHash, DeployHeader, FinalizedBlock},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn execute_finalized_block(
     engine_state: &EngineState<LmdbGlobalState>,
     metrics: &ContractRuntimeMetrics,
     protocol_version: ProtocolVersion,
+    slashing_config: SlashingConfig,
+    inactivity_policy: InactivityPolicy,
     execution_pre_state: ExecutionPreState,
     finalized_block: FinalizedBlock,
     deploys: Vec<Deploy>,
@@ -91,6 +97,7 @@ pub(super) fn execute_finalized_block(
     // validators
     let maybe_step_effect_and_upcoming_era_validators =
         if let Some(era_report) = finalized_block.era_report() {
+            let slash_items = slashing_config.slash_items(era_report, finalized_block.height());
             let StepSuccess {
                 post_state_hash,
                 execution_effect,
@@ -100,6 +107,8 @@ pub(super) fn execute_finalized_block(
                 protocol_version,
                 state_root_hash,
                 era_report,
+                slash_items,
+                inactivity_policy,
                 finalized_block.timestamp().millis(),
                 finalized_block.era_id().successor(),
             )?;
@@ -225,12 +234,15 @@ fn execute(
     result
 }
 
+#[allow(clippy::too_many_arguments)]
 fn commit_step(
     engine_state: &EngineState<LmdbGlobalState>,
     metrics: &ContractRuntimeMetrics,
     protocol_version: ProtocolVersion,
     pre_state_root_hash: Digest,
     era_report: &EraReport<PublicKey>,
+    slash_items: Vec<SlashItem>,
+    inactivity_policy: InactivityPolicy,
     era_end_timestamp_millis: u64,
     next_era_id: EraId,
 ) -> Result<StepSuccess, StepError> {
@@ -239,6 +251,7 @@ fn commit_step(
         equivocators,
         rewards,
         inactive_validators,
+        equivocation_reports: _,
     } = era_report;
 
     let reward_items = rewards
@@ -246,7 +259,8 @@ fn commit_step(
         .map(|(vid, value)| RewardItem::new(vid.clone(), *value))
         .collect();
 
-    // Both inactive validators and equivocators are evicted
+    // Both inactive validators and equivocators are evicted.  Validators which participated too
+    // little are penalized by the auction according to the inactivity policy.
     let evict_items = inactive_validators
         .iter()
         .chain(equivocators)
@@ -258,9 +272,9 @@ fn commit_step(
         pre_state_hash: pre_state_root_hash,
         protocol_version,
         reward_items,
-        // Note: The Casper Network does not slash, but another network could
-        slash_items: vec![],
+        slash_items,
         evict_items,
+        inactivity_policy,
         run_auction: true,
         next_era_id,
         era_end_timestamp_millis,
@@ -1,12 +1,24 @@
 use std::collections::{BTreeMap, HashMap};
 
+use datasize::DataSize;
+use num::{rational::Ratio, Zero};
+
 use casper_execution_engine::core::engine_state::{
-    execution_effect::ExecutionEffect, GetEraValidatorsRequest,
+    execution_effect::ExecutionEffect, GetEraValidatorsRequest, SlashItem,
 };
 use casper_hashing::Digest;
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey, U512};
+use casper_types::{
+    system::auction::{InactivityPolicy, SlashEvidence},
+    EraId, ExecutionResult, ProtocolVersion, PublicKey, U512,
+};
 
-use crate::types::{Block, DeployHash, DeployHeader};
+use crate::{
+    components::consensus::EraReport,
+    types::{
+        chainspec::{CoreConfig, SlashedFunds},
+        Block, DeployHash, DeployHeader,
+    },
+};
 
 /// Request for validator weights for a specific era.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -48,6 +60,85 @@ impl From<ValidatorWeightsByEraIdRequest> for GetEraValidatorsRequest {
     }
 }
 
+/// How equivocators are slashed at the end of an era.
+#[derive(Copy, Clone, Debug, DataSize)]
+pub(crate) struct SlashingConfig {
+    /// Fraction of the stakes bonded to an equivocator which is slashed.
+    #[data_size(skip)]
+    fraction: Ratio<u64>,
+    /// Where the slashed funds go.
+    slashed_funds: SlashedFunds,
+}
+
+impl SlashingConfig {
+    /// Returns the slash items for the equivocators in `era_report`, which is included in the
+    /// switch block at `block_height`.
+    ///
+    /// If the slashed funds go to the reporter, each equivocator's funds are paid to the validator
+    /// which first cited the equivocation.  They are burned if nobody cited it in the era.
+    pub(crate) fn slash_items(
+        &self,
+        era_report: &EraReport<PublicKey>,
+        block_height: u64,
+    ) -> Vec<SlashItem> {
+        if self.fraction.is_zero() {
+            return vec![];
+        }
+        era_report
+            .equivocators
+            .iter()
+            .map(|equivocator| {
+                let evidence = era_report
+                    .equivocation_reports
+                    .get(equivocator)
+                    .map(|report| {
+                        SlashEvidence::new(
+                            report.reporter.clone(),
+                            report.message_hash.value(),
+                            block_height,
+                        )
+                    });
+                let recipient = match self.slashed_funds {
+                    SlashedFunds::Burn => None,
+                    SlashedFunds::Reporter => evidence
+                        .as_ref()
+                        .map(|evidence| evidence.reporter().clone()),
+                };
+                SlashItem::with_fraction(equivocator.clone(), self.fraction, recipient, evidence)
+            })
+            .collect()
+    }
+}
+
+impl Default for SlashingConfig {
+    /// Returns a config which doesn't slash.
+    fn default() -> Self {
+        SlashingConfig {
+            fraction: Ratio::zero(),
+            slashed_funds: SlashedFunds::Burn,
+        }
+    }
+}
+
+impl From<&CoreConfig> for SlashingConfig {
+    fn from(core_config: &CoreConfig) -> Self {
+        SlashingConfig {
+            fraction: core_config.slash_fraction,
+            slashed_funds: core_config.slashed_funds,
+        }
+    }
+}
+
+/// Returns the policy under which validators which participated too little in an era are
+/// penalized.
+pub(crate) fn inactivity_policy(core_config: &CoreConfig) -> InactivityPolicy {
+    InactivityPolicy::new(
+        core_config.inactivity_threshold,
+        core_config.inactivity_penalty_step,
+        core_config.inactivity_eviction_eras,
+    )
+}
+
 /// Request for era validators.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EraValidatorsRequest {
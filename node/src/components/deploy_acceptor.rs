@@ -3,7 +3,7 @@ mod event;
 mod metrics;
 mod tests;
 
-use std::fmt::Debug;
+use std::{collections::BTreeSet, fmt::Debug};
 
 use datasize::DataSize;
 use prometheus::Registry;
@@ -21,7 +21,7 @@ use casper_hashing::Digest;
 use casper_types::{
     account::{Account, AccountHash},
     system::auction::ARG_AMOUNT,
-    Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion,
+    BlockTime, Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion,
     ContractVersionKey, Key, ProtocolVersion, U512,
 };
 
@@ -96,6 +96,15 @@ pub(crate) enum DeployParameterFailure {
     /// Insufficient deploy signature weight.
     #[error("insufficient deploy signature weight")]
     InsufficientDeploySignatureWeight,
+    /// The deploy's separate payer account does not exist.
+    #[error("payer account {account_hash} does not exist")]
+    NonexistentPayerAccount { account_hash: AccountHash },
+    /// Invalid associated keys for the deploy's separate payer account.
+    #[error("payer account authorization invalid")]
+    InvalidPayerAssociatedKeys,
+    /// Insufficient deploy signature weight for the deploy's separate payer account.
+    #[error("insufficient payer signature weight")]
+    InsufficientPayerSignatureWeight,
     /// The deploy's account has insufficient balance.
     #[error("insufficient balance in account {account_hash}")]
     InsufficientBalance { account_hash: AccountHash },
@@ -285,14 +294,27 @@ impl DeployAcceptor {
                     verification_start_timestamp,
                 )
             }
-            Some(account) => {
-                let authorization_keys = event_metadata
+            Some(mut account) => {
+                // A pending recovery which has taken effect by the time the deploy was created will
+                // have been applied by the time it is executed.
+                let deploy_time =
+                    BlockTime::new(event_metadata.deploy.header().timestamp().millis());
+                account.apply_matured_recovery(deploy_time);
+                let authorization_keys = authorization_keys(&event_metadata.deploy);
+                let maybe_payer_hash = event_metadata
                     .deploy
-                    .approvals()
-                    .iter()
-                    .map(|approval| approval.signer().to_account_hash())
-                    .collect();
-                if !account.can_authorize(&authorization_keys) {
+                    .payer()
+                    .map(|payer| payer.to_account_hash())
+                    .filter(|payer_hash| *payer_hash != account.account_hash());
+                // If a separate payer is specified, only the keys associated with the deploy's
+                // account are checked here.  The remaining keys are checked against the payer's
+                // account.
+                let account_keys = if maybe_payer_hash.is_some() {
+                    account.associated_authorization_keys(&authorization_keys)
+                } else {
+                    authorization_keys.clone()
+                };
+                if !account.can_authorize(&account_keys) {
                     let error = Error::InvalidDeployParameters {
                         prestate_hash,
                         failure: DeployParameterFailure::InvalidAssociatedKeys,
@@ -305,7 +327,7 @@ impl DeployAcceptor {
                         verification_start_timestamp,
                     );
                 }
-                if !account.can_deploy_with(&authorization_keys) {
+                if !account.can_deploy_with(&account_keys) {
                     let error = Error::InvalidDeployParameters {
                         prestate_hash,
                         failure: DeployParameterFailure::InsufficientDeploySignatureWeight,
@@ -318,6 +340,22 @@ impl DeployAcceptor {
                         verification_start_timestamp,
                     );
                 }
+                if let Some(payer_hash) = maybe_payer_hash {
+                    let unmatched_keys = authorization_keys
+                        .difference(&account_keys)
+                        .copied()
+                        .collect();
+                    return effect_builder
+                        .get_account_from_global_state(prestate_hash, payer_hash.into())
+                        .event(move |maybe_payer| Event::GetPayerAccountResult {
+                            event_metadata,
+                            prestate_hash,
+                            payer_hash,
+                            maybe_payer,
+                            unmatched_keys,
+                            verification_start_timestamp,
+                        });
+                }
                 effect_builder
                     .check_purse_balance(prestate_hash, account.main_purse())
                     .event(move |maybe_balance_value| Event::GetBalanceResult {
@@ -331,6 +369,82 @@ impl DeployAcceptor {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn handle_get_payer_account_result<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        event_metadata: EventMetadata,
+        prestate_hash: Digest,
+        payer_hash: AccountHash,
+        maybe_payer: Option<Account>,
+        unmatched_keys: BTreeSet<AccountHash>,
+        verification_start_timestamp: Timestamp,
+    ) -> Effects<Event> {
+        let mut payer = match maybe_payer {
+            Some(payer) => payer,
+            None => {
+                let error = Error::InvalidDeployParameters {
+                    prestate_hash,
+                    failure: DeployParameterFailure::NonexistentPayerAccount {
+                        account_hash: payer_hash,
+                    },
+                };
+                debug!(
+                    ?payer_hash,
+                    "nonexistent payer account associated with the deploy"
+                );
+                return self.handle_invalid_deploy_result(
+                    effect_builder,
+                    event_metadata,
+                    error,
+                    verification_start_timestamp,
+                );
+            }
+        };
+
+        let deploy_time = BlockTime::new(event_metadata.deploy.header().timestamp().millis());
+        payer.apply_matured_recovery(deploy_time);
+
+        // Every key not associated with the deploy's account has to be associated with the payer's.
+        let payer_keys =
+            payer.associated_authorization_keys(&authorization_keys(&event_metadata.deploy));
+        if !payer.can_authorize(&payer_keys) || !unmatched_keys.is_subset(&payer_keys) {
+            let error = Error::InvalidDeployParameters {
+                prestate_hash,
+                failure: DeployParameterFailure::InvalidPayerAssociatedKeys,
+            };
+            debug!(?payer_keys, ?unmatched_keys, "payer authorization invalid");
+            return self.handle_invalid_deploy_result(
+                effect_builder,
+                event_metadata,
+                error,
+                verification_start_timestamp,
+            );
+        }
+        if !payer.can_deploy_with(&payer_keys) {
+            let error = Error::InvalidDeployParameters {
+                prestate_hash,
+                failure: DeployParameterFailure::InsufficientPayerSignatureWeight,
+            };
+            debug!(?payer_keys, "insufficient payer signature weight");
+            return self.handle_invalid_deploy_result(
+                effect_builder,
+                event_metadata,
+                error,
+                verification_start_timestamp,
+            );
+        }
+        effect_builder
+            .check_purse_balance(prestate_hash, payer.main_purse())
+            .event(move |maybe_balance_value| Event::GetBalanceResult {
+                event_metadata,
+                prestate_hash,
+                maybe_balance_value,
+                account_hash: payer.account_hash(),
+                verification_start_timestamp,
+            })
+    }
+
     fn handle_get_balance_result<REv: ReactorEventT>(
         &self,
         effect_builder: EffectBuilder<REv>,
@@ -866,6 +980,15 @@ impl DeployAcceptor {
     }
 }
 
+/// Returns the account hashes of all the signers of the given deploy.
+fn authorization_keys(deploy: &Deploy) -> BTreeSet<AccountHash> {
+    deploy
+        .approvals()
+        .iter()
+        .map(|approval| approval.signer().to_account_hash())
+        .collect()
+}
+
 impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
     type Event = Event;
     type ConstructionError = prometheus::Error;
@@ -905,6 +1028,22 @@ impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
                 maybe_account,
                 verification_start_timestamp,
             ),
+            Event::GetPayerAccountResult {
+                event_metadata,
+                prestate_hash,
+                payer_hash,
+                maybe_payer,
+                unmatched_keys,
+                verification_start_timestamp,
+            } => self.handle_get_payer_account_result(
+                effect_builder,
+                event_metadata,
+                prestate_hash,
+                payer_hash,
+                maybe_payer,
+                unmatched_keys,
+                verification_start_timestamp,
+            ),
             Event::GetBalanceResult {
                 event_metadata,
                 prestate_hash,
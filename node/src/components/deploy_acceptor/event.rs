@@ -1,4 +1,7 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+};
 
 use serde::Serialize;
 
@@ -68,7 +71,18 @@ pub(crate) enum Event {
         maybe_account: Option<Account>,
         verification_start_timestamp: Timestamp,
     },
-    /// The result of querying the balance of the `Account` associated with the `Deploy`.
+    /// The result of querying global state for the `Account` paying for the `Deploy`, where that
+    /// differs from the `Deploy`'s own account.
+    GetPayerAccountResult {
+        event_metadata: EventMetadata,
+        prestate_hash: Digest,
+        payer_hash: AccountHash,
+        maybe_payer: Option<Account>,
+        /// The authorization keys not associated with the `Deploy`'s own account.
+        unmatched_keys: BTreeSet<AccountHash>,
+        verification_start_timestamp: Timestamp,
+    },
+    /// The result of querying the balance of the `Account` paying for the `Deploy`.
     GetBalanceResult {
         event_metadata: EventMetadata,
         prestate_hash: Digest,
@@ -152,6 +166,13 @@ impl Display for Event {
                     event_metadata.deploy.id()
                 )
             }
+            Event::GetPayerAccountResult { event_metadata, .. } => {
+                write!(
+                    formatter,
+                    "verifying payer account to validate deploy with hash {}.",
+                    event_metadata.deploy.id()
+                )
+            }
             Event::GetBalanceResult { event_metadata, .. } => {
                 write!(
                     formatter,
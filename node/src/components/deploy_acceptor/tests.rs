@@ -144,6 +144,8 @@ enum TestScenario {
     DeployWithMangledTransferAmount,
     DeployWithoutTransferTarget,
     DeployWithoutTransferAmount,
+    DeployWithPayer,
+    DeployWithUnsignedPayer,
     BalanceCheckForDeploySentByPeer,
 }
 
@@ -177,7 +179,9 @@ impl TestScenario {
             | TestScenario::DeployWithSessionContract(_)
             | TestScenario::DeployWithSessionContractPackage(_)
             | TestScenario::DeployWithEmptySessionModuleBytes
-            | TestScenario::DeployWithNativeTransferInPayment => Source::Client,
+            | TestScenario::DeployWithNativeTransferInPayment
+            | TestScenario::DeployWithPayer
+            | TestScenario::DeployWithUnsignedPayer => Source::Client,
         }
     }
 
@@ -275,6 +279,8 @@ impl TestScenario {
             TestScenario::DeployWithNativeTransferInPayment => {
                 Deploy::random_with_native_transfer_in_payment_logic(rng)
             }
+            TestScenario::DeployWithPayer => Deploy::random_with_payer(rng, true),
+            TestScenario::DeployWithUnsignedPayer => Deploy::random_with_payer(rng, false),
         }
     }
 
@@ -286,7 +292,8 @@ impl TestScenario {
             | TestScenario::FromPeerAccountWithInsufficientWeight // account check skipped if from peer
             | TestScenario::FromPeerAccountWithInvalidAssociatedKeys // account check skipped if from peer
             | TestScenario::FromClientRepeatedValidDeploy
-            | TestScenario::FromClientValidDeploy => true,
+            | TestScenario::FromClientValidDeploy
+            | TestScenario::DeployWithPayer => true,
             TestScenario::FromPeerInvalidDeploy
             | TestScenario::FromClientInsufficientBalance
             | TestScenario::FromClientMissingAccount
@@ -301,6 +308,7 @@ impl TestScenario {
             | TestScenario::DeployWithMangledTransferAmount
             | TestScenario::DeployWithoutTransferAmount
             | TestScenario::DeployWithoutTransferTarget
+            | TestScenario::DeployWithUnsignedPayer
             | TestScenario::BalanceCheckForDeploySentByPeer => false,
             TestScenario::DeployWithCustomPaymentContract(contract_scenario)
             | TestScenario::DeployWithSessionContract(contract_scenario) => match contract_scenario
@@ -722,7 +730,8 @@ async fn run_deploy_acceptor_without_timeout(
             | TestScenario::DeployWithMangledPaymentAmount
             | TestScenario::DeployWithMangledTransferAmount
             | TestScenario::DeployWithoutTransferTarget
-            | TestScenario::DeployWithoutTransferAmount => {
+            | TestScenario::DeployWithoutTransferAmount
+            | TestScenario::DeployWithUnsignedPayer => {
                 matches!(
                     event,
                     Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::InvalidDeploy {
@@ -803,7 +812,7 @@ async fn run_deploy_acceptor_without_timeout(
             }
             // Check that a, new and valid, deploy sent by a client raises an `AcceptedNewDeploy`
             // announcement with the appropriate source.
-            TestScenario::FromClientValidDeploy => {
+            TestScenario::FromClientValidDeploy | TestScenario::DeployWithPayer => {
                 matches!(
                     event,
                     Event::DeployAcceptorAnnouncement(
@@ -1286,3 +1295,23 @@ async fn should_panic_when_balance_checking_for_deploy_sent_by_peer() {
     let result = run_deploy_acceptor(test_scenario).await;
     assert!(result.is_ok())
 }
+
+#[tokio::test]
+async fn should_accept_deploy_with_payer() {
+    let test_scenario = TestScenario::DeployWithPayer;
+    let result = run_deploy_acceptor(test_scenario).await;
+    assert!(result.is_ok())
+}
+
+#[tokio::test]
+async fn should_reject_deploy_with_unsigned_payer() {
+    let test_scenario = TestScenario::DeployWithUnsignedPayer;
+    let result = run_deploy_acceptor(test_scenario).await;
+    assert!(matches!(
+        result,
+        Err(super::Error::InvalidDeployParameters {
+            failure: DeployParameterFailure::InvalidPayerAssociatedKeys,
+            ..
+        })
+    ))
+}
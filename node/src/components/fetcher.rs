@@ -1,9 +1,14 @@
 mod config;
 mod event;
 mod metrics;
+mod peer_stats;
 mod tests;
 
-use std::{collections::HashMap, fmt::Debug, time::Duration};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    time::{Duration, Instant},
+};
 
 use datasize::DataSize;
 use prometheus::Registry;
@@ -14,8 +19,9 @@ use casper_hashing::Digest;
 use casper_types::{Key, StoredValue};
 
 use crate::{
-    components::{fetcher::event::FetchResponder, Component},
+    components::{fetcher::event::FetchResponder, small_network::Offense, Component},
     effect::{
+        announcements::BlocklistAnnouncement,
         requests::{ContractRuntimeRequest, LinearChainRequest, NetworkRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects,
     },
@@ -28,11 +34,13 @@ use crate::{
 pub(crate) use config::Config;
 pub(crate) use event::{Event, FetchResult};
 use metrics::FetcherMetrics;
+pub(crate) use peer_stats::SharedPeerStats;
 
 /// A helper trait constraining `Fetcher` compatible reactor events.
 pub(crate) trait ReactorEventT<T>:
     From<Event<T>>
     + From<NetworkRequest<NodeId, Message>>
+    + From<BlocklistAnnouncement<NodeId>>
     + From<StorageRequest>
     + From<ContractRuntimeRequest>
     // Won't be needed when we implement "get block by height" feature in storage.
@@ -51,6 +59,7 @@ where
     <T as Item>::Id: 'static,
     REv: From<Event<T>>
         + From<NetworkRequest<NodeId, Message>>
+        + From<BlocklistAnnouncement<NodeId>>
         + From<StorageRequest>
         + From<ContractRuntimeRequest>
         + From<LinearChainRequest<NodeId>>
@@ -62,8 +71,6 @@ where
 pub(crate) trait ItemFetcher<T: Item + 'static> {
     fn responders(&mut self) -> &mut HashMap<T::Id, HashMap<NodeId, Vec<FetchResponder<T>>>>;
 
-    fn peer_timeout(&self) -> Duration;
-
     /// We've been asked to fetch the item by another component of this node.  We'll try to get it
     /// from our own storage component first, and if that fails, we'll send a request to `peer` for
     /// the item.
@@ -105,33 +112,6 @@ pub(crate) trait ItemFetcher<T: Item + 'static> {
         )
     }
 
-    /// Handles the `Err` case for a `Result` of attempting to get the item from the storage
-    /// component.
-    fn failed_to_get_from_storage<REv: ReactorEventT<T>>(
-        &mut self,
-        effect_builder: EffectBuilder<REv>,
-        id: T::Id,
-        peer: NodeId,
-    ) -> Effects<Event<T>> {
-        match Message::new_get_request::<T>(&id) {
-            Ok(message) => {
-                let mut effects = effect_builder.send_message(peer, message).ignore();
-
-                effects.extend(
-                    effect_builder
-                        .set_timeout(self.peer_timeout())
-                        .event(move |_| Event::TimeoutPeer { id, peer }),
-                );
-
-                effects
-            }
-            Err(error) => {
-                error!("failed to construct get request: {}", error);
-                self.signal(id, None, peer)
-            }
-        }
-    }
-
     /// Handles signalling responders with the item or `None`.
     fn signal(
         &mut self,
@@ -173,7 +153,17 @@ where
     T: Item + 'static,
 {
     get_from_peer_timeout: Duration,
+    hedge_delay: Duration,
+    max_batch_size: usize,
+    batch_delay: Duration,
     responders: HashMap<T::Id, HashMap<NodeId, Vec<FetchResponder<T>>>>,
+    /// The peers we sent a request for an item to, with the time each request was sent.
+    #[data_size(skip)]
+    in_flight: HashMap<T::Id, HashMap<NodeId, Instant>>,
+    /// Requests held back to be sent to a peer in a single batch.
+    batches: HashMap<NodeId, Vec<T::Id>>,
+    #[data_size(skip)]
+    peer_stats: SharedPeerStats<NodeId>,
     #[data_size(skip)]
     metrics: FetcherMetrics,
 }
@@ -182,16 +172,170 @@ impl<T: Item> Fetcher<T> {
     pub(crate) fn new(
         name: &str,
         config: Config,
+        peer_stats: SharedPeerStats<NodeId>,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(Fetcher {
             get_from_peer_timeout: config.get_from_peer_timeout().into(),
+            hedge_delay: config.hedge_delay().into(),
+            max_batch_size: config.max_batch_size().into(),
+            batch_delay: config.batch_delay().into(),
             responders: HashMap::new(),
+            in_flight: HashMap::new(),
+            batches: HashMap::new(),
+            peer_stats,
             metrics: FetcherMetrics::new(name, registry)?,
         })
     }
 }
 
+impl<T: Item> Fetcher<T>
+where
+    Fetcher<T>: ItemFetcher<T>,
+{
+    /// Handles the item not being in storage by asking `peer` for it, either right away or as part
+    /// of the next batch of requests to that peer.
+    fn request_from_peer<REv: ReactorEventT<T>>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        id: T::Id,
+        peer: NodeId,
+    ) -> Effects<Event<T>> {
+        self.peer_stats.lock().unwrap().add_peer(peer);
+        if self.max_batch_size <= 1 {
+            return self.send_requests(effect_builder, peer, vec![id]);
+        }
+        let batch = self.batches.entry(peer).or_default();
+        batch.push(id);
+        if batch.len() >= self.max_batch_size {
+            let ids = self.batches.remove(&peer).unwrap_or_default();
+            return self.send_requests(effect_builder, peer, ids);
+        }
+        if batch.len() > 1 {
+            return Effects::new();
+        }
+        // The flush may come early for a later batch if this one fills up first, which is harmless.
+        effect_builder
+            .set_timeout(self.batch_delay)
+            .event(move |_| Event::FlushBatch { peer })
+    }
+
+    /// Sends the requests held back for `peer`.
+    fn flush_batch<REv: ReactorEventT<T>>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        peer: NodeId,
+    ) -> Effects<Event<T>> {
+        match self.batches.remove(&peer) {
+            Some(ids) => self.send_requests(effect_builder, peer, ids),
+            None => Effects::new(),
+        }
+    }
+
+    /// Sends a request for the given items to `peer`, in a batch if there is more than one.
+    fn send_requests<REv: ReactorEventT<T>>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        peer: NodeId,
+        ids: Vec<T::Id>,
+    ) -> Effects<Event<T>> {
+        let maybe_message = match ids.as_slice() {
+            [id] => Message::new_get_request::<T>(id),
+            _ => Message::new_get_request_batch::<T>(&ids),
+        };
+        let message = match maybe_message {
+            Ok(message) => message,
+            Err(error) => {
+                error!("failed to construct get request: {}", error);
+                return ids
+                    .into_iter()
+                    .flat_map(|id| self.signal(id, None, peer))
+                    .collect();
+            }
+        };
+        if ids.len() > 1 {
+            self.metrics.batched_requests.inc();
+        }
+
+        let mut effects = effect_builder.send_message(peer, message).ignore();
+        let sent_at = Instant::now();
+        for id in ids {
+            self.in_flight.entry(id).or_default().insert(peer, sent_at);
+            effects.extend(
+                effect_builder
+                    .set_timeout(self.get_from_peer_timeout)
+                    .event(move |_| Event::TimeoutPeer { id, peer }),
+            );
+            // Only requests made on behalf of a responder are hedged, not hedges themselves.
+            let is_hedgeable = self.hedge_delay < self.get_from_peer_timeout
+                && self
+                    .responders
+                    .get(&id)
+                    .map_or(false, |responders| responders.contains_key(&peer));
+            if is_hedgeable {
+                effects.extend(
+                    effect_builder
+                        .set_timeout(self.hedge_delay)
+                        .event(move |_| Event::HedgeRequest { id, peer }),
+                );
+            }
+        }
+        effects
+    }
+
+    /// Sends the request for an item `peer` has not answered yet to the best other peer as well.
+    fn hedge_request<REv: ReactorEventT<T>>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        id: T::Id,
+        peer: NodeId,
+    ) -> Effects<Event<T>> {
+        let still_waiting = self
+            .responders
+            .get(&id)
+            .map_or(false, |responders| responders.contains_key(&peer));
+        if !still_waiting {
+            return Effects::new();
+        }
+        let maybe_hedge_peer = {
+            let asked = self.in_flight.get(&id);
+            self.peer_stats.lock().unwrap().best_peer(|candidate| {
+                *candidate == peer || asked.map_or(false, |asked| asked.contains_key(candidate))
+            })
+        };
+        match maybe_hedge_peer {
+            Some(hedge_peer) => {
+                debug!(%id, %peer, %hedge_peer, "hedging request");
+                self.metrics.hedged_requests.inc();
+                self.send_requests(effect_builder, hedge_peer, vec![id])
+            }
+            None => Effects::new(),
+        }
+    }
+
+    /// Updates the statistics of `peer` with the outcome of our request for an item, if any.
+    fn record_response(&mut self, id: &T::Id, peer: NodeId, success: bool) {
+        let sent_at = match self.in_flight.get_mut(id) {
+            Some(asked) => {
+                let sent_at = asked.remove(&peer);
+                if asked.is_empty() {
+                    self.in_flight.remove(id);
+                }
+                sent_at
+            }
+            None => None,
+        };
+        if let Some(sent_at) = sent_at {
+            let mut peer_stats = self.peer_stats.lock().unwrap();
+            if success {
+                peer_stats.record_success(peer, sent_at.elapsed());
+            } else {
+                peer_stats.record_failure(peer);
+            }
+        }
+    }
+}
+
 impl ItemFetcher<Deploy> for Fetcher<Deploy> {
     fn responders(
         &mut self,
@@ -199,10 +343,6 @@ impl ItemFetcher<Deploy> for Fetcher<Deploy> {
         &mut self.responders
     }
 
-    fn peer_timeout(&self) -> Duration {
-        self.get_from_peer_timeout
-    }
-
     /// Gets a `Deploy` from the storage component.
     fn get_from_storage<REv: ReactorEventT<Deploy>>(
         &mut self,
@@ -227,10 +367,6 @@ impl ItemFetcher<Block> for Fetcher<Block> {
         &mut self.responders
     }
 
-    fn peer_timeout(&self) -> Duration {
-        self.get_from_peer_timeout
-    }
-
     fn get_from_storage<REv: ReactorEventT<Block>>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -254,10 +390,6 @@ impl ItemFetcher<BlockByHeight> for Fetcher<BlockByHeight> {
         &mut self.responders
     }
 
-    fn peer_timeout(&self) -> Duration {
-        self.get_from_peer_timeout
-    }
-
     fn get_from_storage<REv: ReactorEventT<BlockByHeight>>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -283,10 +415,6 @@ impl ItemFetcher<GlobalStorageTrie> for Fetcher<GlobalStorageTrie> {
         &mut self.responders
     }
 
-    fn peer_timeout(&self) -> Duration {
-        self.get_from_peer_timeout
-    }
-
     fn get_from_storage<REv: ReactorEventT<GlobalStorageTrie>>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -342,12 +470,13 @@ where
                     self.metrics.found_in_storage.inc();
                     self.got_from_storage(item, peer)
                 }
-                None => self.failed_to_get_from_storage(effect_builder, id, peer),
+                None => self.request_from_peer(effect_builder, id, peer),
             },
             Event::GotRemotely { item, source } => {
                 match source {
                     Source::Peer(peer) => {
                         self.metrics.found_on_peer.inc();
+                        self.record_response(&item.id(), peer, true);
                         self.signal(item.id(), Some(FetchResult::FromPeer(item, peer)), peer)
                     }
                     Source::Client | Source::Ourself => {
@@ -360,13 +489,31 @@ where
             Event::RejectedRemotely { .. } => Effects::new(),
             Event::AbsentRemotely { id, peer } => {
                 info!(%id, %peer, "element absent on the remote node");
+                self.record_response(&id, peer, false);
                 self.signal(id, None, peer)
             }
             Event::TimeoutPeer { id, peer } => {
+                self.record_response(&id, peer, false);
+                let still_waiting = self
+                    .responders()
+                    .get(&id)
+                    .map_or(false, |responders| responders.contains_key(&peer));
+                if !still_waiting {
+                    // The peer responded in time.
+                    return Effects::new();
+                }
                 info!(%id, %peer, "request timed out");
                 self.metrics.timeouts.inc();
-                self.signal(id, None, peer)
+                let mut effects = self.signal(id, None, peer);
+                effects.extend(
+                    effect_builder
+                        .announce_offense(peer, Offense::Timeout)
+                        .ignore(),
+                );
+                effects
             }
+            Event::HedgeRequest { id, peer } => self.hedge_request(effect_builder, id, peer),
+            Event::FlushBatch { peer } => self.flush_batch(effect_builder, peer),
         }
     }
 }
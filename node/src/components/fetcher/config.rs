@@ -6,23 +6,61 @@ use serde::{Deserialize, Serialize};
 use crate::types::TimeDiff;
 
 const DEFAULT_GET_FROM_PEER_TIMEOUT: &str = "3sec";
+const DEFAULT_HEDGE_DELAY: &str = "1sec";
+const DEFAULT_MAX_BATCH_SIZE: u16 = 1;
+const DEFAULT_BATCH_DELAY: &str = "10ms";
 
 /// Configuration options for fetching.
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
 pub struct Config {
     get_from_peer_timeout: TimeDiff,
+    /// The delay after which an unanswered request is also sent to the best other known peer.
+    hedge_delay: TimeDiff,
+    /// The maximum number of requests sent to a single peer in one message, and accepted from a
+    /// peer.
+    ///
+    /// Peers announce the batch size they accept in the handshake, and larger batches are split.
+    max_batch_size: u16,
+    /// The time requests are held back to be batched with further requests to the same peer.
+    batch_delay: TimeDiff,
 }
 
 impl Config {
+    /// Returns a config batching requests, with a batch delay long enough for tests to fill a
+    /// batch before it is flushed.
+    #[cfg(test)]
+    pub(crate) fn new_with_batching(max_batch_size: u16) -> Self {
+        Config {
+            max_batch_size,
+            batch_delay: TimeDiff::from_str("1sec").unwrap(),
+            ..Default::default()
+        }
+    }
+
     pub(crate) fn get_from_peer_timeout(&self) -> TimeDiff {
         self.get_from_peer_timeout
     }
+
+    pub(crate) fn hedge_delay(&self) -> TimeDiff {
+        self.hedge_delay
+    }
+
+    pub(crate) fn max_batch_size(&self) -> u16 {
+        self.max_batch_size
+    }
+
+    pub(crate) fn batch_delay(&self) -> TimeDiff {
+        self.batch_delay
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             get_from_peer_timeout: TimeDiff::from_str(DEFAULT_GET_FROM_PEER_TIMEOUT).unwrap(),
+            hedge_delay: TimeDiff::from_str(DEFAULT_HEDGE_DELAY).unwrap(),
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            batch_delay: TimeDiff::from_str(DEFAULT_BATCH_DELAY).unwrap(),
         }
     }
 }
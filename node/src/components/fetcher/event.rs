@@ -50,6 +50,10 @@ pub(crate) enum Event<T: Item> {
     AbsentRemotely { id: T::Id, peer: NodeId },
     /// The timeout has elapsed and we should clean up state.
     TimeoutPeer { id: T::Id, peer: NodeId },
+    /// The peer has not answered our request yet, so we should ask another peer as well.
+    HedgeRequest { id: T::Id, peer: NodeId },
+    /// The requests held back for the peer should be sent.
+    FlushBatch { peer: NodeId },
 }
 
 impl<T: Item> From<FetcherRequest<NodeId, T>> for Event<T> {
@@ -117,6 +121,12 @@ impl<T: Item> Display for Event<T> {
             Event::AbsentRemotely { id, peer } => {
                 write!(formatter, "Item {} was not available on {}", id, peer)
             }
+            Event::HedgeRequest { id, peer } => write!(
+                formatter,
+                "check whether to hedge request for {} with {}",
+                id, peer
+            ),
+            Event::FlushBatch { peer } => write!(formatter, "flush batched requests to {}", peer),
         }
     }
 }
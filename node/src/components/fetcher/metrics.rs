@@ -10,6 +10,10 @@ pub(super) struct FetcherMetrics {
     pub(super) found_on_peer: IntCounter,
     /// Number of fetch requests that timed out.
     pub(super) timeouts: IntCounter,
+    /// Number of requests also sent to a second peer because the first was slow to answer.
+    pub(super) hedged_requests: IntCounter,
+    /// Number of messages sent requesting more than one item.
+    pub(super) batched_requests: IntCounter,
     /// Reference to the registry for unregistering.
     registry: Registry,
 }
@@ -31,14 +35,29 @@ impl FetcherMetrics {
             format!("{}_timeouts", name),
             format!("number of {} fetch requests that timed out", name),
         )?;
+        let hedged_requests = IntCounter::new(
+            format!("{}_hedged_requests", name),
+            format!(
+                "number of {} requests also sent to a second peer because the first was slow",
+                name
+            ),
+        )?;
+        let batched_requests = IntCounter::new(
+            format!("{}_batched_requests", name),
+            format!("number of messages sent requesting more than one {}", name),
+        )?;
         registry.register(Box::new(found_in_storage.clone()))?;
         registry.register(Box::new(found_on_peer.clone()))?;
         registry.register(Box::new(timeouts.clone()))?;
+        registry.register(Box::new(hedged_requests.clone()))?;
+        registry.register(Box::new(batched_requests.clone()))?;
 
         Ok(FetcherMetrics {
             found_in_storage,
             found_on_peer,
             timeouts,
+            hedged_requests,
+            batched_requests,
             registry: registry.clone(),
         })
     }
@@ -49,5 +68,7 @@ impl Drop for FetcherMetrics {
         unregister_metric!(self.registry, self.found_in_storage);
         unregister_metric!(self.registry, self.found_on_peer);
         unregister_metric!(self.registry, self.timeouts);
+        unregister_metric!(self.registry, self.hedged_requests);
+        unregister_metric!(self.registry, self.batched_requests);
     }
 }
@@ -0,0 +1,180 @@
+//! Statistics on how well peers answer our requests.
+//!
+//! The statistics are shared between all fetchers of a reactor and the linear chain synchronizer,
+//! so that each of them can prefer the peers which answered quickly and reliably before.
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// The latency assumed for peers we have not received any response from yet.
+const UNKNOWN_LATENCY: Duration = Duration::from_millis(500);
+
+/// The weight of a new latency sample in the moving average, as a reciprocal.
+const LATENCY_SMOOTHING: u32 = 4;
+
+/// The number of outcomes after which the success and failure counts are halved, so that a peer's
+/// recent behavior outweighs its history.
+const MAX_OUTCOMES: u32 = 64;
+
+/// Peer statistics shared between several components.
+pub(crate) type SharedPeerStats<I> = Arc<Mutex<PeerStats<I>>>;
+
+/// What we know about a single peer.
+#[derive(Clone, Copy, Debug, Default)]
+struct PeerRecord {
+    /// The exponentially weighted moving average of the response latency.
+    latency: Option<Duration>,
+    /// The number of requests the peer answered.
+    successes: u32,
+    /// The number of requests the peer failed to answer in time or at all.
+    failures: u32,
+}
+
+impl PeerRecord {
+    /// Returns the latency to expect when asking the peer, including the cost of asking again
+    /// whenever the peer fails to answer.
+    fn expected_latency(&self) -> f64 {
+        let latency = self.latency.unwrap_or(UNKNOWN_LATENCY).as_secs_f64();
+        // The success rate is smoothed, so that a peer we know nothing about scores one half.
+        let attempts = f64::from(self.successes) + f64::from(self.failures) + 2.0;
+        latency * attempts / (f64::from(self.successes) + 1.0)
+    }
+
+    fn decay(&mut self) {
+        if self.successes + self.failures > MAX_OUTCOMES {
+            self.successes /= 2;
+            self.failures /= 2;
+        }
+    }
+}
+
+/// Per-peer response latency and failure rates.
+#[derive(Debug)]
+pub(crate) struct PeerStats<I> {
+    peers: HashMap<I, PeerRecord>,
+}
+
+impl<I> Default for PeerStats<I> {
+    fn default() -> Self {
+        PeerStats {
+            peers: HashMap::new(),
+        }
+    }
+}
+
+impl<I: Clone + Eq + Hash> PeerStats<I> {
+    /// Adds a peer we can send requests to, keeping its statistics if it is already known.
+    pub(crate) fn add_peer(&mut self, peer: I) {
+        self.peers.entry(peer).or_default();
+    }
+
+    /// Forgets a peer, e.g. because it has been banned.
+    pub(crate) fn remove_peer(&mut self, peer: &I) {
+        self.peers.remove(peer);
+    }
+
+    /// Records that the peer answered a request after the given latency.
+    pub(crate) fn record_success(&mut self, peer: I, latency: Duration) {
+        let record = self.peers.entry(peer).or_default();
+        record.latency = Some(match record.latency {
+            Some(average) => average - average / LATENCY_SMOOTHING + latency / LATENCY_SMOOTHING,
+            None => latency,
+        });
+        record.successes = record.successes.saturating_add(1);
+        record.decay();
+    }
+
+    /// Records that the peer did not answer a request.
+    pub(crate) fn record_failure(&mut self, peer: I) {
+        let record = self.peers.entry(peer).or_default();
+        record.failures = record.failures.saturating_add(1);
+        record.decay();
+    }
+
+    fn expected_latency(&self, peer: &I) -> f64 {
+        self.peers
+            .get(peer)
+            .copied()
+            .unwrap_or_default()
+            .expected_latency()
+    }
+
+    /// Returns the known peer with the lowest expected latency, skipping the peers for which
+    /// `exclude` returns `true`.
+    pub(crate) fn best_peer<F: Fn(&I) -> bool>(&self, exclude: F) -> Option<I> {
+        self.peers
+            .iter()
+            .filter(|(peer, _)| !exclude(peer))
+            .min_by(|(_, left), (_, right)| {
+                left.expected_latency()
+                    .partial_cmp(&right.expected_latency())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .map(|(peer, _)| peer.clone())
+    }
+
+    /// Sorts the given peers by their expected latency, best first.  Peers with equal scores keep
+    /// their relative order.
+    pub(crate) fn sort_best_first(&self, peers: &mut [I]) {
+        peers.sort_by(|left, right| {
+            self.expected_latency(left)
+                .partial_cmp(&self.expected_latency(right))
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_prefer_fast_and_reliable_peers() {
+        let mut stats = PeerStats::default();
+        stats.record_success(1, Duration::from_millis(50));
+        stats.record_success(2, Duration::from_millis(400));
+        stats.record_success(3, Duration::from_millis(50));
+        stats.record_failure(3);
+        stats.record_failure(3);
+        stats.add_peer(4);
+
+        let mut peers = vec![4, 3, 2, 1];
+        stats.sort_best_first(&mut peers);
+        assert_eq!(vec![1, 3, 2, 4], peers);
+
+        assert_eq!(Some(1), stats.best_peer(|_| false));
+        assert_eq!(Some(3), stats.best_peer(|peer| *peer == 1));
+    }
+
+    #[test]
+    fn should_smooth_latency() {
+        let mut stats = PeerStats::default();
+        stats.record_success(1, Duration::from_millis(100));
+        assert_eq!(Some(Duration::from_millis(100)), stats.peers[&1].latency);
+        stats.record_success(1, Duration::from_millis(500));
+        assert_eq!(Some(Duration::from_millis(200)), stats.peers[&1].latency);
+        stats.add_peer(2);
+        assert_eq!(None, stats.peers[&2].latency);
+    }
+
+    #[test]
+    fn should_forgive_old_failures() {
+        let mut stats = PeerStats::default();
+        for _ in 0..MAX_OUTCOMES {
+            stats.record_failure(1);
+        }
+        for _ in 0..(4 * MAX_OUTCOMES) {
+            stats.record_success(1, UNKNOWN_LATENCY);
+        }
+        stats.add_peer(2);
+        assert_eq!(Some(1), stats.best_peer(|_| false));
+
+        stats.remove_peer(&1);
+        assert_eq!(Some(2), stats.best_peer(|_| false));
+    }
+}
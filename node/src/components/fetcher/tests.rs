@@ -12,7 +12,12 @@ use casper_types::ProtocolVersion;
 
 use super::*;
 use crate::{
-    components::{deploy_acceptor, in_memory_network::NetworkController, storage},
+    components::{
+        deploy_acceptor,
+        in_memory_network::NetworkController,
+        small_network::{CaptureRecord, Direction},
+        storage,
+    },
     effect::{
         announcements::{DeployAcceptorAnnouncement, NetworkAnnouncement},
         Responder,
@@ -22,9 +27,10 @@ use crate::{
     testing,
     testing::{
         network::{Network, NetworkedReactor},
+        replay::replay_capture,
         ConditionCheckReactor, TestRng,
     },
-    types::{Deploy, DeployHash, NodeId},
+    types::{Deploy, DeployHash, NodeId, Timestamp},
     utils::{WithDir, RESOURCES_PATH},
 };
 
@@ -80,7 +86,7 @@ reactor!(Reactor {
             "test"
         );
         deploy_acceptor = DeployAcceptor(cfg.deploy_acceptor_config, &*chainspec_loader.chainspec(), registry);
-        deploy_fetcher = Fetcher::<Deploy>("deploy", cfg.fetcher_config, registry);
+        deploy_fetcher = Fetcher::<Deploy>("deploy", cfg.fetcher_config, Default::default(), registry);
     }
 
     events: {
@@ -108,6 +114,7 @@ reactor!(Reactor {
         // from a client.
         RpcServerAnnouncement -> [deploy_acceptor];
         ChainspecLoaderAnnouncement -> [!];
+        BlocklistAnnouncement<NodeId> -> [#];
     }
 });
 
@@ -172,6 +179,22 @@ impl Reactor {
                         }),
                     )
                 }
+                Message::GetRequestBatch {
+                    tag,
+                    serialized_ids,
+                } => serialized_ids
+                    .into_iter()
+                    .flat_map(|serialized_id| {
+                        self.handle_message(
+                            effect_builder,
+                            rng,
+                            NetworkAnnouncement::MessageReceived {
+                                sender,
+                                payload: Message::GetRequest { tag, serialized_id },
+                            },
+                        )
+                    })
+                    .collect(),
                 msg => panic!("should not get {}", msg),
             },
             ann => panic!("should not received any network announcements: {:?}", ann),
@@ -462,3 +485,183 @@ async fn should_timeout_fetch_from_peer() {
 
     NetworkController::<Message>::remove_active();
 }
+
+#[tokio::test]
+async fn should_store_deploy_from_replayed_capture() {
+    NetworkController::<Message>::create_active();
+    let mut rng = TestRng::new();
+    let mut network = Network::<Reactor>::new();
+    let node_id = network.add_nodes(&mut rng, 1).await[0];
+
+    let deploy = Deploy::random_valid_native_transfer(&mut rng);
+    let peer_id = NodeId::random(&mut rng);
+    let record = |direction, payload| CaptureRecord {
+        timestamp: Timestamp::now(),
+        peer_id,
+        direction,
+        payload,
+    };
+    let records = vec![
+        // Outgoing messages are not replayed.
+        record(
+            Direction::Outgoing,
+            Message::new_get_request::<Deploy>(Deploy::random(&mut rng).id()).unwrap(),
+        ),
+        record(
+            Direction::Incoming,
+            Message::new_get_response(&deploy).unwrap(),
+        ),
+    ];
+
+    let replayed = replay_capture(&mut network, &node_id, records, &mut rng, TIMEOUT).await;
+    assert_eq!(1, replayed);
+
+    let stored_deploy = network
+        .nodes()
+        .get(&node_id)
+        .unwrap()
+        .reactor()
+        .inner()
+        .storage
+        .get_deploy_by_hash(*deploy.id());
+    assert_eq!(Some(*deploy.id()), stored_deploy.map(|deploy| *deploy.id()));
+
+    NetworkController::<Message>::remove_active();
+}
+
+#[tokio::test]
+async fn should_hedge_request_to_peer_which_answered_before() {
+    const NETWORK_SIZE: usize = 3;
+
+    NetworkController::<Message>::create_active();
+    let (mut network, mut rng, node_ids) = {
+        let mut network = Network::<Reactor>::new();
+        let mut rng = TestRng::new();
+        let node_ids = network.add_nodes(&mut rng, NETWORK_SIZE).await;
+        (network, rng, node_ids)
+    };
+
+    let holding_node = node_ids[0];
+    let empty_node = node_ids[1];
+    let requesting_node = node_ids[2];
+
+    let first_deploy = Deploy::random_valid_native_transfer(&mut rng);
+    let second_deploy = Deploy::random_valid_native_transfer(&mut rng);
+    store_deploy(&first_deploy, &holding_node, &mut network, None, &mut rng).await;
+    store_deploy(&second_deploy, &holding_node, &mut network, None, &mut rng).await;
+
+    // Let the requesting node learn that the holding node answers requests.
+    let fetched = Arc::new(Mutex::new((false, None)));
+    network
+        .process_injected_effect_on(
+            &requesting_node,
+            fetch_deploy(*first_deploy.id(), holding_node, Arc::clone(&fetched)),
+        )
+        .await;
+    network
+        .settle_on(&mut rng, |_| fetched.lock().unwrap().0, TIMEOUT)
+        .await;
+
+    // Ask the node which does not have the second deploy, and hence never answers.  The request
+    // should be hedged to the holding node well before it times out.
+    let fetched = Arc::new(Mutex::new((false, None)));
+    network
+        .process_injected_effect_on(
+            &requesting_node,
+            fetch_deploy(*second_deploy.id(), empty_node, Arc::clone(&fetched)),
+        )
+        .await;
+
+    let mut validated_deploy = second_deploy.clone();
+    let _ = validated_deploy.is_valid();
+    let expected_result = Some(FetchResult::FromPeer(
+        Box::new(validated_deploy),
+        holding_node,
+    ));
+    let hedge_delay: Duration = Config::default().hedge_delay().into();
+    assert_settled(
+        &requesting_node,
+        *second_deploy.id(),
+        expected_result,
+        fetched,
+        &mut network,
+        &mut rng,
+        hedge_delay + TIMEOUT,
+    )
+    .await;
+
+    let hedged_requests = network.nodes()[&requesting_node]
+        .reactor()
+        .inner()
+        .deploy_fetcher
+        .metrics
+        .hedged_requests
+        .get();
+    assert_eq!(1, hedged_requests);
+
+    NetworkController::<Message>::remove_active();
+}
+
+#[tokio::test]
+async fn should_fetch_batched_requests() {
+    const BATCH_SIZE: usize = 3;
+
+    NetworkController::<Message>::create_active();
+    let mut rng = TestRng::new();
+    let mut network = Network::<Reactor>::new();
+    let holding_node = network.add_nodes(&mut rng, 1).await[0];
+    let (requesting_node, _) = network
+        .add_node_with_config(
+            FetcherTestConfig {
+                fetcher_config: Config::new_with_batching(BATCH_SIZE as u16),
+                ..Default::default()
+            },
+            &mut rng,
+        )
+        .await
+        .unwrap();
+
+    let deploys: Vec<_> = (0..BATCH_SIZE)
+        .map(|_| Deploy::random_valid_native_transfer(&mut rng))
+        .collect();
+    let mut all_fetched = vec![];
+    for deploy in &deploys {
+        store_deploy(deploy, &holding_node, &mut network, None, &mut rng).await;
+        let fetched = Arc::new(Mutex::new((false, None)));
+        network
+            .process_injected_effect_on(
+                &requesting_node,
+                fetch_deploy(*deploy.id(), holding_node, Arc::clone(&fetched)),
+            )
+            .await;
+        all_fetched.push(fetched);
+    }
+
+    let have_all_responded = |_nodes: &HashMap<NodeId, Runner<ConditionCheckReactor<Reactor>>>| {
+        all_fetched.iter().all(|fetched| fetched.lock().unwrap().0)
+    };
+    network
+        .settle_on(&mut rng, have_all_responded, TIMEOUT)
+        .await;
+
+    for (deploy, fetched) in deploys.iter().zip(&all_fetched) {
+        match &fetched.lock().unwrap().1 {
+            Some(FetchResult::FromPeer(fetched_deploy, peer)) => {
+                assert_eq!(deploy.id(), fetched_deploy.id());
+                assert_eq!(holding_node, *peer);
+            }
+            result => panic!("unexpected fetch result {:?}", result),
+        }
+    }
+
+    let batched_requests = network.nodes()[&requesting_node]
+        .reactor()
+        .inner()
+        .deploy_fetcher
+        .metrics
+        .batched_requests
+        .get();
+    assert_eq!(1, batched_requests);
+
+    NetworkController::<Message>::remove_active();
+}
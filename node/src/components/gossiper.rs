@@ -5,12 +5,15 @@ mod event;
 mod gossip_table;
 mod message;
 mod metrics;
+mod sketch;
 mod tests;
 
 use datasize::DataSize;
+use futures::FutureExt;
 use prometheus::Registry;
+use rand::Rng;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     convert::Infallible,
     fmt::{self, Debug, Formatter},
     time::Duration,
@@ -21,7 +24,7 @@ use crate::{
     components::Component,
     effect::{
         announcements::GossiperAnnouncement,
-        requests::{NetworkRequest, StorageRequest},
+        requests::{FetcherRequest, GossipTarget, NetworkRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects,
     },
     protocol::Message as NodeMessage,
@@ -29,11 +32,12 @@ use crate::{
     utils::Source,
     NodeRng,
 };
-pub(crate) use config::Config;
+pub(crate) use config::{Config, GossipMode};
 pub(crate) use event::Event;
 use gossip_table::{GossipAction, GossipTable};
 pub(crate) use message::Message;
 use metrics::GossiperMetrics;
+use sketch::Sketch;
 
 /// A helper trait whose bounds represent the requirements for a reactor event that `Gossiper` can
 /// work with.
@@ -91,6 +95,50 @@ pub(crate) fn get_deploy_from_storage<T: Item + 'static, REv: ReactorEventT<T>>(
         })
 }
 
+/// This function can be passed in to `Gossiper::new_for_partial_items()` as the `fetch_from_peer`
+/// arg when constructing a `Gossiper<Deploy>`.
+pub(crate) fn fetch_deploy_from_peer<REv>(
+    effect_builder: EffectBuilder<REv>,
+    deploy_hash: DeployHash,
+    peer: NodeId,
+) -> Effects<Event<Deploy>>
+where
+    REv: From<FetcherRequest<NodeId, Deploy>> + Send,
+{
+    effect_builder
+        .fetch_deploy(deploy_hash, peer)
+        .then(move |result| async move {
+            if result.is_none() {
+                debug!(%deploy_hash, %peer, "failed to fetch reconciled deploy");
+            }
+        })
+        .ignore()
+}
+
+/// A round of sending sketches to peers, awaiting their responses.
+#[derive(DataSize, Debug)]
+struct Round<I> {
+    /// The IDs of the items in the sketch.
+    item_ids: Vec<I>,
+    /// The peers the sketch was sent to which haven't responded yet.
+    awaiting: HashSet<NodeId>,
+}
+
+/// The state of set reconciliation, used in `GossipMode::Reconciliation`.
+#[derive(DataSize, Debug)]
+struct Reconciliation<I> {
+    /// The interval between two rounds.
+    interval: Duration,
+    /// The number of differing items our sketches can resolve.
+    sketch_capacity: usize,
+    /// The number of peers to send each sketch to.
+    infection_target: usize,
+    /// Whether the next round has been scheduled.
+    round_scheduled: bool,
+    /// The rounds awaiting responses, by salt.
+    rounds: HashMap<u64, Round<I>>,
+}
+
 /// The component which gossips to peers and handles incoming gossip messages from peers.
 #[allow(clippy::type_complexity)]
 #[derive(DataSize)]
@@ -105,6 +153,10 @@ where
     #[data_size(skip)] // Not well supported by datasize.
     get_from_holder:
         Box<dyn Fn(EffectBuilder<REv>, T::Id, NodeId) -> Effects<Event<T>> + Send + 'static>,
+    #[data_size(skip)] // Not well supported by datasize.
+    fetch_from_peer:
+        Box<dyn Fn(EffectBuilder<REv>, T::Id, NodeId) -> Effects<Event<T>> + Send + 'static>,
+    reconciliation: Option<Reconciliation<T::Id>>,
     #[data_size(skip)]
     metrics: GossiperMetrics,
 }
@@ -120,6 +172,9 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
     /// For an example of how `get_from_holder` should be implemented, see
     /// `gossiper::get_deploy_from_store()` which is used by `Gossiper<Deploy>`.
     ///
+    /// `fetch_from_peer` is called by the gossiper to fetch an item a peer offered after reconciling
+    /// with it, e.g. `gossiper::fetch_deploy_from_peer()`.
+    ///
     /// Must be supplied with a name, which should be a snake-case identifier to disambiguate the
     /// specific gossiper from other potentially present gossipers.
     pub(crate) fn new_for_partial_items(
@@ -128,17 +183,32 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
         get_from_holder: impl Fn(EffectBuilder<REv>, T::Id, NodeId) -> Effects<Event<T>>
             + Send
             + 'static,
+        fetch_from_peer: impl Fn(EffectBuilder<REv>, T::Id, NodeId) -> Effects<Event<T>>
+            + Send
+            + 'static,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         assert!(
             !T::ID_IS_COMPLETE_ITEM,
             "this should only be called for types where T::ID_IS_COMPLETE_ITEM is false"
         );
+        let reconciliation = match config.mode() {
+            GossipMode::Push => None,
+            GossipMode::Reconciliation => Some(Reconciliation {
+                interval: config.reconciliation_interval().into(),
+                sketch_capacity: usize::from(config.sketch_capacity()),
+                infection_target: usize::from(config.infection_target()),
+                round_scheduled: false,
+                rounds: HashMap::new(),
+            }),
+        };
         Ok(Gossiper {
             table: GossipTable::new(config),
             gossip_timeout: config.gossip_request_timeout().into(),
             get_from_peer_timeout: config.get_remainder_timeout().into(),
             get_from_holder: Box::new(get_from_holder),
+            fetch_from_peer: Box::new(fetch_from_peer),
+            reconciliation,
             metrics: GossiperMetrics::new(name, registry)?,
         })
     }
@@ -164,6 +234,10 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
             get_from_holder: Box::new(|_, item, _| {
                 panic!("gossiper should never try to get {}", item)
             }),
+            fetch_from_peer: Box::new(|_, item, _| {
+                panic!("gossiper should never try to fetch {}", item)
+            }),
+            reconciliation: None,
             metrics: GossiperMetrics::new(name, registry)?,
         })
     }
@@ -200,6 +274,8 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
     }
 
     /// Gossips the given item ID to `count` random peers excluding the indicated ones.
+    ///
+    /// In reconciliation mode, the item is included in the sketches of the next round instead.
     fn gossip(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -207,9 +283,19 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
         count: usize,
         exclude_peers: HashSet<NodeId>,
     ) -> Effects<Event<T>> {
+        if let Some(reconciliation) = self.reconciliation.as_mut() {
+            if reconciliation.round_scheduled {
+                return Effects::new();
+            }
+            reconciliation.round_scheduled = true;
+            return effect_builder
+                .set_timeout(reconciliation.interval)
+                .event(|_| Event::ReconciliationRound);
+        }
+
         let message = Message::Gossip(item_id);
         effect_builder
-            .gossip_message(message, count, exclude_peers)
+            .gossip_message(message, count, GossipTarget::All, exclude_peers)
             .event(move |peers| Event::GossipedTo {
                 item_id,
                 requested_count: count,
@@ -501,6 +587,351 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
         Effects::new()
     }
 
+    /// Sends a sketch of the items we hold to random peers, if we are still gossiping any of them,
+    /// and schedules the next round.
+    fn start_reconciliation_round(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        rng: &mut NodeRng,
+    ) -> Effects<Event<T>> {
+        let reconciliation = match self.reconciliation.as_mut() {
+            Some(reconciliation) => reconciliation,
+            None => {
+                error!("reconciliation round should not be scheduled in push mode");
+                return Effects::new();
+            }
+        };
+        reconciliation.round_scheduled = false;
+        if self.table.held_current().next().is_none() {
+            // Nothing left to gossip; the next new item schedules a round again.
+            return Effects::new();
+        }
+
+        let salt: u64 = rng.gen();
+        let item_ids: Vec<T::Id> = self.table.held().copied().collect();
+        let mut sketch = Sketch::with_capacity(reconciliation.sketch_capacity);
+        for item_id in &item_ids {
+            sketch.insert(sketch::short_id::<T>(salt, item_id));
+        }
+        let _ = reconciliation.rounds.insert(
+            salt,
+            Round {
+                item_ids,
+                awaiting: HashSet::new(),
+            },
+        );
+
+        let requested_count = reconciliation.infection_target;
+        let mut effects = effect_builder
+            .gossip_message(
+                Message::Sketch { salt, sketch },
+                requested_count,
+                GossipTarget::Reconciling,
+                HashSet::new(),
+            )
+            .event(move |peers| Event::SketchSentTo {
+                salt,
+                requested_count,
+                peers,
+            });
+        reconciliation.round_scheduled = true;
+        effects.extend(
+            effect_builder
+                .set_timeout(reconciliation.interval)
+                .event(|_| Event::ReconciliationRound),
+        );
+        effects
+    }
+
+    /// Handles the response from the network component detailing which peers it sent a sketch to.
+    fn sketch_sent_to(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        salt: u64,
+        requested_count: usize,
+        peers: HashSet<NodeId>,
+    ) -> Effects<Event<T>> {
+        self.metrics.times_gossiped.inc_by(peers.len() as u64);
+        if peers.is_empty() {
+            self.metrics.times_ran_out_of_peers.inc();
+        }
+
+        let rounds = match self.reconciliation.as_mut() {
+            Some(reconciliation) => &mut reconciliation.rounds,
+            None => return Effects::new(),
+        };
+        let item_ids = match rounds.get_mut(&salt) {
+            Some(round) => {
+                round.awaiting = peers.clone();
+                round.item_ids.clone()
+            }
+            None => return Effects::new(),
+        };
+        if peers.is_empty() {
+            let _ = rounds.remove(&salt);
+        }
+
+        // Peers which don't understand sketches are pushed the items still being gossiped instead,
+        // so that together both reach as many peers as were requested.  Like in push mode, we stop
+        // gossiping items once we run out of peers to send them to.
+        let shortfall = requested_count.saturating_sub(peers.len());
+        let mut effects = Effects::new();
+        for item_id in item_ids {
+            if self.table.advertised_to(&item_id, peers.len(), &peers) {
+                effects.extend(effect_builder.announce_finished_gossiping(item_id).ignore());
+                continue;
+            }
+            let exclude_peers = match self.table.holders(&item_id) {
+                Some(holders) if shortfall > 0 => holders.clone(),
+                _ => continue,
+            };
+            effects.extend(
+                effect_builder
+                    .gossip_message(
+                        Message::Gossip(item_id),
+                        shortfall,
+                        GossipTarget::NonReconciling,
+                        exclude_peers,
+                    )
+                    .event(move |peers| Event::GossipedTo {
+                        item_id,
+                        requested_count: shortfall,
+                        peers,
+                    }),
+            );
+        }
+
+        // Set timeouts to check later that the specified peers all responded.
+        for peer in peers {
+            effects.extend(
+                effect_builder
+                    .set_timeout(self.gossip_timeout)
+                    .event(move |_| Event::CheckSketchTimeout { salt, peer }),
+            );
+        }
+        effects
+    }
+
+    /// Removes `peer` from the peers we await a response to the given round's sketch from.
+    ///
+    /// Returns the IDs of the items in the sketch, or `None` if we weren't awaiting a response.
+    fn end_round_with(&mut self, salt: u64, peer: NodeId) -> Option<Vec<T::Id>> {
+        let rounds = &mut self.reconciliation.as_mut()?.rounds;
+        let round = rounds.get_mut(&salt)?;
+        if !round.awaiting.remove(&peer) {
+            return None;
+        }
+        if round.awaiting.is_empty() {
+            rounds.remove(&salt).map(|round| round.item_ids)
+        } else {
+            Some(round.item_ids.clone())
+        }
+    }
+
+    /// Checks that the given peer has responded to a sketch we sent it.
+    fn check_sketch_timeout(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        salt: u64,
+        peer: NodeId,
+    ) -> Effects<Event<T>> {
+        let item_ids = match self.end_round_with(salt, peer) {
+            Some(item_ids) => item_ids,
+            None => return Effects::new(),
+        };
+        debug!(salt = %format_args!("{:x}", salt), %peer, "sketch timed out");
+        let mut effects = Effects::new();
+        for item_id in item_ids {
+            let action = self.table.check_timeout(&item_id, peer);
+            effects.extend(self.handle_reconciled(effect_builder, item_id, action));
+        }
+        effects
+    }
+
+    /// Handles the action resulting from learning through reconciliation whether a peer holds an
+    /// item.
+    fn handle_reconciled(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        item_id: T::Id,
+        action: GossipAction,
+    ) -> Effects<Event<T>> {
+        match action {
+            GossipAction::ShouldGossip(should_gossip) => self.gossip(
+                effect_builder,
+                item_id,
+                should_gossip.count,
+                should_gossip.exclude_peers,
+            ),
+            GossipAction::AnnounceFinished => {
+                effect_builder.announce_finished_gossiping(item_id).ignore()
+            }
+            GossipAction::Noop
+            | GossipAction::GetRemainder { .. }
+            | GossipAction::AwaitingRemainder => Effects::new(),
+        }
+    }
+
+    /// Fetches the given items from `peer`, skipping the ones we already hold.
+    fn fetch_missing(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        item_ids: Vec<T::Id>,
+        peer: NodeId,
+    ) -> Effects<Event<T>> {
+        let missing: HashSet<T::Id> = item_ids
+            .into_iter()
+            .filter(|item_id| !self.table.holds(item_id))
+            .collect();
+        self.metrics.items_fetched.inc_by(missing.len() as u64);
+        missing
+            .into_iter()
+            .flat_map(|item_id| (self.fetch_from_peer)(effect_builder, item_id, peer))
+            .collect()
+    }
+
+    /// Handles an incoming sketch from a peer on the network.
+    ///
+    /// Responds with the items the peer is missing and the short IDs of the items we are missing,
+    /// or with all the items we hold if the sketch can't be decoded.
+    fn handle_sketch(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        salt: u64,
+        sketch: Sketch,
+        sender: NodeId,
+    ) -> Effects<Event<T>> {
+        if T::ID_IS_COMPLETE_ITEM {
+            warn!(%sender, "received sketch for items which are always pushed");
+            return Effects::new();
+        }
+
+        let item_ids: Vec<T::Id> = self.table.held().copied().collect();
+        let short_ids = sketch::short_ids::<T>(salt, &item_ids);
+        let difference = if sketch.is_well_formed() {
+            let mut our_sketch = Sketch::with_cells(sketch.len());
+            for short_id in short_ids.keys() {
+                our_sketch.insert(*short_id);
+            }
+            our_sketch.subtract(&sketch).and_then(Sketch::decode)
+        } else {
+            None
+        };
+        let decoded = difference.and_then(|difference| {
+            let offered: Option<Vec<T::Id>> = difference
+                .ours
+                .iter()
+                .map(|short_id| short_ids.get(short_id).copied())
+                .collect();
+            Some((offered?, difference.theirs))
+        });
+
+        let (offered, requested) = match decoded {
+            Some((offered, requested)) => {
+                self.metrics.reconciliations.inc();
+                debug!(
+                    %sender,
+                    offered = offered.len(),
+                    requested = requested.len(),
+                    "reconciled with peer"
+                );
+                (offered, Some(requested))
+            }
+            None => {
+                self.metrics.reconciliation_failures.inc();
+                debug!(%sender, "failed to decode sketch, offering all items");
+                (item_ids, None)
+            }
+        };
+
+        let mut effects = Effects::new();
+        if requested.is_some() {
+            let offered_set: HashSet<_> = offered.iter().collect();
+            for item_id in short_ids.values() {
+                let action = if offered_set.contains(item_id) {
+                    self.table.we_infected(item_id, sender)
+                } else {
+                    self.table.already_infected(item_id, sender)
+                };
+                effects.extend(self.handle_reconciled(effect_builder, *item_id, action));
+            }
+        }
+
+        let reply = Message::SketchResponse {
+            salt,
+            offered,
+            requested,
+        };
+        effects.extend(effect_builder.send_message(sender, reply).ignore());
+        effects
+    }
+
+    /// Handles an incoming response to a sketch we sent.
+    fn handle_sketch_response(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        salt: u64,
+        offered: Vec<T::Id>,
+        requested: Option<Vec<u64>>,
+        sender: NodeId,
+    ) -> Effects<Event<T>> {
+        if T::ID_IS_COMPLETE_ITEM {
+            warn!(%sender, "received sketch response for items which are always pushed");
+            return Effects::new();
+        }
+
+        let mut effects = self.fetch_missing(effect_builder, offered, sender);
+        let item_ids = match self.end_round_with(salt, sender) {
+            Some(item_ids) => item_ids,
+            None => {
+                debug!(%sender, "received unexpected sketch response");
+                return effects;
+            }
+        };
+
+        // If the peer couldn't decode our sketch, we don't know which of our items it holds, so
+        // we offer all of them without counting the peer as infected by us.
+        let to_offer: HashSet<T::Id> = match requested.as_ref() {
+            Some(requested) => {
+                let requested: HashSet<_> = requested.iter().collect();
+                item_ids
+                    .iter()
+                    .filter(|item_id| requested.contains(&sketch::short_id::<T>(salt, item_id)))
+                    .copied()
+                    .collect()
+            }
+            None => item_ids.iter().copied().collect(),
+        };
+        for item_id in item_ids {
+            let action = if requested.is_some() && to_offer.contains(&item_id) {
+                self.table.we_infected(&item_id, sender)
+            } else {
+                self.table.already_infected(&item_id, sender)
+            };
+            effects.extend(self.handle_reconciled(effect_builder, item_id, action));
+        }
+
+        if !to_offer.is_empty() {
+            let reply = Message::Offer(to_offer.into_iter().collect());
+            effects.extend(effect_builder.send_message(sender, reply).ignore());
+        }
+        effects
+    }
+
+    /// Handles an incoming offer of items from a peer on the network.
+    fn handle_offer(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        item_ids: Vec<T::Id>,
+        sender: NodeId,
+    ) -> Effects<Event<T>> {
+        if T::ID_IS_COMPLETE_ITEM {
+            warn!(%sender, "received offer for items which are always pushed");
+            return Effects::new();
+        }
+        self.fetch_missing(effect_builder, item_ids, sender)
+    }
+
     /// Updates the gossiper metrics from the state of the gossip table.
     fn update_gossip_table_metrics(&self) {
         self.metrics
@@ -523,7 +954,7 @@ where
     fn handle_event(
         &mut self,
         effect_builder: EffectBuilder<REv>,
-        _rng: &mut NodeRng,
+        rng: &mut NodeRng,
         event: Self::Event,
     ) -> Effects<Self::Event> {
         let effects = match event {
@@ -541,12 +972,30 @@ where
             Event::CheckGetFromPeerTimeout { item_id, peer } => {
                 self.check_get_from_peer_timeout(effect_builder, item_id, peer)
             }
+            Event::ReconciliationRound => self.start_reconciliation_round(effect_builder, rng),
+            Event::SketchSentTo {
+                salt,
+                requested_count,
+                peers,
+            } => self.sketch_sent_to(effect_builder, salt, requested_count, peers),
+            Event::CheckSketchTimeout { salt, peer } => {
+                self.check_sketch_timeout(effect_builder, salt, peer)
+            }
             Event::MessageReceived { message, sender } => match message {
                 Message::Gossip(item_id) => self.handle_gossip(effect_builder, item_id, sender),
                 Message::GossipResponse {
                     item_id,
                     is_already_held,
                 } => self.handle_gossip_response(effect_builder, item_id, is_already_held, sender),
+                Message::Sketch { salt, sketch } => {
+                    self.handle_sketch(effect_builder, salt, sketch, sender)
+                }
+                Message::SketchResponse {
+                    salt,
+                    offered,
+                    requested,
+                } => self.handle_sketch_response(effect_builder, salt, offered, requested, sender),
+                Message::Offer(item_ids) => self.handle_offer(effect_builder, item_ids, sender),
             },
             Event::GetFromHolderResult {
                 item_id,
@@ -569,6 +1018,7 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Debug for Gossiper<T, REv> {
             .field("table", &self.table)
             .field("gossip_timeout", &self.gossip_timeout)
             .field("get_from_peer_timeout", &self.get_from_peer_timeout)
+            .field("reconciliation", &self.reconciliation)
             .finish()
     }
 }
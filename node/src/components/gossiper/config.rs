@@ -18,12 +18,32 @@ pub(super) const MAX_SATURATION_LIMIT_PERCENT: u8 = 99;
 pub(super) const DEFAULT_FINISHED_ENTRY_DURATION: &str = "60sec";
 const DEFAULT_GOSSIP_REQUEST_TIMEOUT: &str = "10sec";
 const DEFAULT_GET_REMAINDER_TIMEOUT: &str = "60sec";
+const DEFAULT_RECONCILIATION_INTERVAL: &str = "5sec";
+const DEFAULT_SKETCH_CAPACITY: u16 = 64;
 #[cfg(test)]
 const SMALL_TIMEOUTS_FINISHED_ENTRY_DURATION: &str = "2sec";
 #[cfg(test)]
 const SMALL_TIMEOUTS_GOSSIP_REQUEST_TIMEOUT: &str = "1sec";
 #[cfg(test)]
 const SMALL_TIMEOUTS_GET_REMAINDER_TIMEOUT: &str = "1sec";
+#[cfg(test)]
+const SMALL_TIMEOUTS_RECONCILIATION_INTERVAL: &str = "100ms";
+#[cfg(test)]
+const MANUAL_RECONCILIATION_INTERVAL: &str = "1hour";
+
+/// How items are gossiped.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GossipMode {
+    /// The ID of every new item is pushed to random peers, which reply whether they need it.
+    Push,
+    /// Peers periodically exchange sketches of the IDs of recent items they hold, and fetch the
+    /// items they are missing.
+    ///
+    /// Only used for items which aren't completely contained in their IDs, i.e. deploys.  Other
+    /// items are always pushed.
+    Reconciliation,
+}
 
 /// Configuration options for gossiping.
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
@@ -50,6 +70,14 @@ pub struct Config {
     /// The timeout duration in seconds for retrieving the remaining part(s) of newly-discovered
     /// data from a peer which gossiped information about that data to this node.
     get_remainder_timeout: TimeDiff,
+    /// How deploys are gossiped.  In reconciliation mode, deploys are still pushed to peers which
+    /// don't support reconciliation.
+    mode: GossipMode,
+    /// The interval between two rounds of sending sketches to peers, in reconciliation mode.
+    reconciliation_interval: TimeDiff,
+    /// The number of differing items between two peers a sketch can resolve, in reconciliation
+    /// mode.  If two peers differ by more items, they exchange their full lists of recent items.
+    sketch_capacity: u16,
 }
 
 impl Config {
@@ -70,6 +98,7 @@ impl Config {
             finished_entry_duration,
             gossip_request_timeout,
             get_remainder_timeout,
+            ..Default::default()
         })
     }
 
@@ -86,6 +115,24 @@ impl Config {
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn new_for_reconciliation() -> Self {
+        Config {
+            mode: GossipMode::Reconciliation,
+            reconciliation_interval: TimeDiff::from_str(SMALL_TIMEOUTS_RECONCILIATION_INTERVAL)
+                .unwrap(),
+            ..Config::new_with_small_timeouts()
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_manual_reconciliation() -> Self {
+        Config {
+            reconciliation_interval: TimeDiff::from_str(MANUAL_RECONCILIATION_INTERVAL).unwrap(),
+            ..Config::new_for_reconciliation()
+        }
+    }
+
     pub(crate) fn infection_target(&self) -> u8 {
         self.infection_target
     }
@@ -105,6 +152,18 @@ impl Config {
     pub(crate) fn get_remainder_timeout(&self) -> TimeDiff {
         self.get_remainder_timeout
     }
+
+    pub(crate) fn mode(&self) -> GossipMode {
+        self.mode
+    }
+
+    pub(crate) fn reconciliation_interval(&self) -> TimeDiff {
+        self.reconciliation_interval
+    }
+
+    pub(crate) fn sketch_capacity(&self) -> u16 {
+        self.sketch_capacity
+    }
 }
 
 impl Default for Config {
@@ -115,6 +174,9 @@ impl Default for Config {
             finished_entry_duration: TimeDiff::from_str(DEFAULT_FINISHED_ENTRY_DURATION).unwrap(),
             gossip_request_timeout: TimeDiff::from_str(DEFAULT_GOSSIP_REQUEST_TIMEOUT).unwrap(),
             get_remainder_timeout: TimeDiff::from_str(DEFAULT_GET_REMAINDER_TIMEOUT).unwrap(),
+            mode: GossipMode::Push,
+            reconciliation_interval: TimeDiff::from_str(DEFAULT_RECONCILIATION_INTERVAL).unwrap(),
+            sketch_capacity: DEFAULT_SKETCH_CAPACITY,
         }
     }
 }
@@ -152,6 +214,7 @@ mod tests {
             finished_entry_duration: TimeDiff::from_str(DEFAULT_FINISHED_ENTRY_DURATION).unwrap(),
             gossip_request_timeout: TimeDiff::from_str(DEFAULT_GOSSIP_REQUEST_TIMEOUT).unwrap(),
             get_remainder_timeout: TimeDiff::from_str(DEFAULT_GET_REMAINDER_TIMEOUT).unwrap(),
+            ..Default::default()
         };
 
         // Parsing should fail.
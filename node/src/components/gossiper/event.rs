@@ -33,6 +33,18 @@ pub enum Event<T: Item> {
     CheckGetFromPeerTimeout { item_id: T::Id, peer: NodeId },
     /// An incoming gossip network message.
     MessageReceived { sender: NodeId, message: Message<T> },
+    /// It is time to send sketches of the items we hold to random peers.
+    ReconciliationRound,
+    /// The network component sent the sketch of the round with the given salt to the included
+    /// peers.
+    SketchSentTo {
+        salt: u64,
+        requested_count: usize,
+        peers: HashSet<NodeId>,
+    },
+    /// The timeout for waiting for a response to a sketch has elapsed and we should check the
+    /// response arrived.
+    CheckSketchTimeout { salt: u64, peer: NodeId },
     /// The result of the gossiper getting an item from the component responsible for holding it.
     /// If the result is `Ok`, the item should be sent to the requesting peer.
     GetFromHolderResult {
@@ -64,6 +76,18 @@ impl<T: Item> Display for Event<T> {
                 "check get from peer timeout for {} with {}",
                 item_id, peer
             ),
+            Event::ReconciliationRound => write!(formatter, "reconciliation round"),
+            Event::SketchSentTo { salt, peers, .. } => write!(
+                formatter,
+                "sent sketch {:x} to {}",
+                salt,
+                DisplayIter::new(peers)
+            ),
+            Event::CheckSketchTimeout { salt, peer } => write!(
+                formatter,
+                "check sketch timeout for {:x} with {}",
+                salt, peer
+            ),
             Event::MessageReceived { sender, message } => {
                 write!(formatter, "{} received from {}", message, sender)
             }
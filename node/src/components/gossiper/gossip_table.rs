@@ -198,6 +198,19 @@ impl<T> GossipTable<T> {
     pub fn items_finished(&self) -> usize {
         self.finished.len()
     }
+
+    /// Returns the IDs of the items currently being gossiped which we hold.
+    pub(crate) fn held_current(&self) -> impl Iterator<Item = &T> {
+        self.current
+            .iter()
+            .filter(|(_, state)| state.held_by_us)
+            .map(|(data_id, _)| data_id)
+    }
+
+    /// Returns the IDs of all items we hold, including the finished ones.
+    pub(crate) fn held(&self) -> impl Iterator<Item = &T> {
+        self.held_current().chain(self.finished.iter())
+    }
 }
 
 impl<T: Copy + Eq + Hash + Display> GossipTable<T> {
@@ -361,6 +374,45 @@ impl<T: Copy + Eq + Hash + Display> GossipTable<T> {
         false
     }
 
+    /// We sent a reconciliation sketch including the given data to `peers`, having asked for
+    /// `requested_count` peers.  Like in `reduce_in_flight_count`, the in-flight count is reduced by
+    /// the number of peers missing, where peers already known to hold the data are not counted.
+    ///
+    /// Returns `true` if there was a current entry for this data and it is now finished.
+    pub(crate) fn advertised_to(
+        &mut self,
+        data_id: &T,
+        requested_count: usize,
+        peers: &HashSet<NodeId>,
+    ) -> bool {
+        let new_peers = match self.current.get(data_id) {
+            Some(state) if state.held_by_us => peers
+                .iter()
+                .filter(|peer| !state.holders.contains(peer))
+                .count(),
+            _ => return false,
+        };
+        let missing = requested_count.saturating_sub(new_peers);
+        missing > 0 && self.reduce_in_flight_count(data_id, missing)
+    }
+
+    /// Returns the peers known to hold the given data, if we hold it and are still gossiping it.
+    pub(crate) fn holders(&self, data_id: &T) -> Option<&HashSet<NodeId>> {
+        self.current
+            .get(data_id)
+            .filter(|state| state.held_by_us)
+            .map(|state| &state.holders)
+    }
+
+    /// Returns whether we hold the given data, or have finished gossiping it.
+    pub(crate) fn holds(&self, data_id: &T) -> bool {
+        self.finished.contains(data_id)
+            || self
+                .current
+                .get(data_id)
+                .map_or(false, |state| state.held_by_us)
+    }
+
     /// Checks if gossip request we sent timed out.
     ///
     /// If the peer is already counted as a holder, it has previously responded and this method
@@ -762,6 +814,34 @@ mod tests {
         assert!(gossip_table.finished.contains(&data_id));
     }
 
+    #[test]
+    fn should_terminate_via_advertising_to_holders() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let node_ids = random_node_ids(&mut rng);
+        let data_id: u64 = rng.gen();
+
+        let mut gossip_table = GossipTable::new(Config::default());
+        let _ = gossip_table.new_complete_data(&data_id, Some(node_ids[0]));
+
+        // Advertising to peers which don't hold the item yet shouldn't finish it.
+        let peers: HashSet<_> = node_ids[1..4].iter().copied().collect();
+        assert!(!gossip_table.advertised_to(&data_id, EXPECTED_DEFAULT_INFECTION_TARGET, &peers));
+        for peer in &peers {
+            let _ = gossip_table.already_infected(&data_id, *peer);
+        }
+        assert!(!gossip_table.finished.contains(&data_id));
+
+        // Advertising only to holders is like running out of peers to gossip to.
+        let peers: HashSet<_> = node_ids[0..3].iter().copied().collect();
+        assert!(gossip_table.advertised_to(&data_id, EXPECTED_DEFAULT_INFECTION_TARGET, &peers));
+        assert!(gossip_table.finished.contains(&data_id));
+        assert!(gossip_table.holds(&data_id));
+
+        // Check that calling this again has no effect and continues to return `false`.
+        assert!(!gossip_table.advertised_to(&data_id, EXPECTED_DEFAULT_INFECTION_TARGET, &peers));
+    }
+
     #[test]
     fn should_terminate_via_saturation() {
         let _ = logging::init();
@@ -2,7 +2,7 @@ use std::fmt::{self, Display, Formatter};
 
 use serde::{Deserialize, Serialize};
 
-use super::Item;
+use super::{Item, Sketch};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(bound = "for<'a> T: Deserialize<'a>")]
@@ -15,6 +15,21 @@ pub enum Message<T: Item> {
         item_id: T::Id,
         is_already_held: bool,
     },
+    /// Sent periodically to random peers in reconciliation mode, containing a sketch of the IDs of
+    /// the recent items we hold, derived using `salt`.
+    Sketch { salt: u64, sketch: Sketch },
+    /// Response to a `Sketch` message.  `offered` are the IDs of items the recipient is missing,
+    /// `requested` the short IDs of the items in the sketch the sender is missing.  If the sketch
+    /// could not be decoded, `offered` contains all the sender's recent items and `requested` is
+    /// `None`, asking the recipient to offer all of its recent items.
+    SketchResponse {
+        salt: u64,
+        offered: Vec<T::Id>,
+        requested: Option<Vec<u64>>,
+    },
+    /// Offers the items with the given IDs in response to a `SketchResponse`.  The recipient should
+    /// fetch the ones it doesn't hold.
+    Offer(Vec<T::Id>),
 }
 
 impl<T: Item> Display for Message<T> {
@@ -29,6 +44,26 @@ impl<T: Item> Display for Message<T> {
                 "gossip-response({}, {})",
                 item_id, is_already_held
             ),
+            Message::Sketch { salt, sketch } => {
+                write!(formatter, "sketch({:x}, {} cells)", salt, sketch.len())
+            }
+            Message::SketchResponse {
+                salt,
+                offered,
+                requested,
+            } => {
+                write!(
+                    formatter,
+                    "sketch-response({:x}, {} offered, ",
+                    salt,
+                    offered.len()
+                )?;
+                match requested {
+                    Some(requested) => write!(formatter, "{} requested)", requested.len()),
+                    None => write!(formatter, "all requested)"),
+                }
+            }
+            Message::Offer(item_ids) => write!(formatter, "offer({} items)", item_ids.len()),
         }
     }
 }
@@ -11,6 +11,12 @@ pub struct GossiperMetrics {
     pub(super) times_gossiped: IntCounter,
     /// Number of times the process had to pause due to running out of peers.
     pub(super) times_ran_out_of_peers: IntCounter,
+    /// Number of sketches received from peers which could be decoded.
+    pub(super) reconciliations: IntCounter,
+    /// Number of sketches received from peers which differed too much to be decoded.
+    pub(super) reconciliation_failures: IntCounter,
+    /// Number of items fetched from peers after reconciling with them.
+    pub(super) items_fetched: IntCounter,
     /// Number of items in the gossip table that are currently being gossiped.
     pub(super) table_items_current: IntGauge,
     /// Number of items in the gossip table that are finished.
@@ -37,6 +43,27 @@ impl GossiperMetrics {
                 name
             ),
         )?;
+        let reconciliations = IntCounter::new(
+            format!("{}_reconciliations", name),
+            format!(
+                "number of sketches received by the {} which could be decoded",
+                name
+            ),
+        )?;
+        let reconciliation_failures = IntCounter::new(
+            format!("{}_reconciliation_failures", name),
+            format!(
+                "number of sketches received by the {} which could not be decoded",
+                name
+            ),
+        )?;
+        let items_fetched = IntCounter::new(
+            format!("{}_items_fetched", name),
+            format!(
+                "number of items the {} fetched from peers after reconciling",
+                name
+            ),
+        )?;
         let table_items_current = IntGauge::new(
             format!("{}_table_items_current", name),
             format!(
@@ -55,6 +82,9 @@ impl GossiperMetrics {
         registry.register(Box::new(items_received.clone()))?;
         registry.register(Box::new(times_gossiped.clone()))?;
         registry.register(Box::new(times_ran_out_of_peers.clone()))?;
+        registry.register(Box::new(reconciliations.clone()))?;
+        registry.register(Box::new(reconciliation_failures.clone()))?;
+        registry.register(Box::new(items_fetched.clone()))?;
         registry.register(Box::new(table_items_current.clone()))?;
         registry.register(Box::new(table_items_finished.clone()))?;
 
@@ -62,6 +92,9 @@ impl GossiperMetrics {
             items_received,
             times_gossiped,
             times_ran_out_of_peers,
+            reconciliations,
+            reconciliation_failures,
+            items_fetched,
             table_items_current,
             table_items_finished,
             registry: registry.clone(),
@@ -74,6 +107,9 @@ impl Drop for GossiperMetrics {
         unregister_metric!(self.registry, self.items_received);
         unregister_metric!(self.registry, self.times_gossiped);
         unregister_metric!(self.registry, self.times_ran_out_of_peers);
+        unregister_metric!(self.registry, self.reconciliations);
+        unregister_metric!(self.registry, self.reconciliation_failures);
+        unregister_metric!(self.registry, self.items_fetched);
         unregister_metric!(self.registry, self.table_items_current);
         unregister_metric!(self.registry, self.table_items_finished);
     }
@@ -0,0 +1,279 @@
+//! Compact sketches of sets of item IDs, used to reconcile the items held by two peers.
+//!
+//! A sketch is an invertible Bloom lookup table over 64 bit short IDs.  Subtracting the sketch of
+//! one set from the sketch of another yields a sketch of their symmetric difference, which can be
+//! decoded as long as the difference is not much larger than the capacity the sketch was created
+//! with, however large the sets themselves are.
+//!
+//! Short IDs are derived from the full item IDs using a salt chosen by the peer creating the
+//! sketch, so that a malicious peer cannot easily craft items whose short IDs collide.
+
+use std::{collections::HashMap, convert::TryInto};
+
+use casper_hashing::Digest;
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use crate::types::Item;
+
+/// The number of cells each short ID is added to.
+const HASH_COUNT: usize = 3;
+
+/// The seeds of the hash functions selecting the cells of a short ID.
+const INDEX_SEEDS: [u64; HASH_COUNT] = [
+    0x9e37_79b9_7f4a_7c15,
+    0xc2b2_ae3d_27d4_eb4f,
+    0x1656_67b1_9e37_79f9,
+];
+
+/// The seed of the hash function used to check that a cell holds a single short ID.
+const CHECK_SEED: u64 = 0x27d4_eb2f_1656_67c5;
+
+/// The largest number of cells accepted in a sketch received from a peer.
+const MAX_CELLS: usize = 1 << 16;
+
+/// Returns the short ID of the given item ID, using the given salt.
+pub(super) fn short_id<T: Item>(salt: u64, item_id: &T::Id) -> u64 {
+    let mut data = salt.to_le_bytes().to_vec();
+    // Serializing an item ID into memory cannot fail.
+    data.extend(bincode::serialize(item_id).unwrap_or_default());
+    let digest = Digest::hash(data).value();
+    u64::from_le_bytes(
+        digest[..8]
+            .try_into()
+            .expect("digest is longer than 8 bytes"),
+    )
+}
+
+/// Returns the short IDs of the given item IDs, mapped to the item IDs.
+pub(super) fn short_ids<'a, T: Item + 'a>(
+    salt: u64,
+    item_ids: impl IntoIterator<Item = &'a T::Id>,
+) -> HashMap<u64, T::Id> {
+    item_ids
+        .into_iter()
+        .map(|item_id| (short_id::<T>(salt, item_id), *item_id))
+        .collect()
+}
+
+/// Mixes the bits of a 64 bit value, as in the finalizer of SplitMix64.
+fn mix(mut value: u64) -> u64 {
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+/// A single cell of a sketch.
+#[derive(Clone, Copy, DataSize, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+struct Cell {
+    /// The number of short IDs added to the cell, minus the number removed.
+    count: i32,
+    /// The XOR of all short IDs in the cell.
+    id_sum: u64,
+    /// The XOR of the check hashes of all short IDs in the cell.
+    hash_sum: u64,
+}
+
+impl Cell {
+    /// Adds the given short ID to or removes it from the cell.
+    fn toggle(&mut self, short_id: u64, count: i32) {
+        self.count = self.count.wrapping_add(count);
+        self.id_sum ^= short_id;
+        self.hash_sum ^= mix(short_id ^ CHECK_SEED);
+    }
+
+    /// Returns whether the cell holds exactly one short ID, added or removed.
+    fn is_pure(&self) -> bool {
+        (self.count == 1 || self.count == -1) && self.hash_sum == mix(self.id_sum ^ CHECK_SEED)
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Cell::default()
+    }
+}
+
+/// The symmetric difference of two sets, as decoded from a sketch.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct Difference {
+    /// The short IDs only present in the set the other sketch was subtracted from.
+    pub(super) ours: Vec<u64>,
+    /// The short IDs only present in the set of the subtracted sketch.
+    pub(super) theirs: Vec<u64>,
+}
+
+/// A sketch of a set of short IDs.
+#[derive(Clone, DataSize, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Sketch {
+    cells: Vec<Cell>,
+}
+
+impl Sketch {
+    /// Returns an empty sketch able to decode differences of about `capacity` short IDs.
+    pub(super) fn with_capacity(capacity: usize) -> Self {
+        Sketch::with_cells(2 * capacity)
+    }
+
+    /// Returns an empty sketch with the given number of cells, rounded up to a multiple of the
+    /// number of hash functions.
+    pub(super) fn with_cells(cells: usize) -> Self {
+        let cells_per_hash = ((cells + HASH_COUNT - 1) / HASH_COUNT).max(1);
+        Sketch {
+            cells: vec![Cell::default(); cells_per_hash * HASH_COUNT],
+        }
+    }
+
+    /// Returns the number of cells.
+    pub(super) fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns whether the sketch has the shape of a sketch created by `with_cells`.
+    pub(super) fn is_well_formed(&self) -> bool {
+        !self.cells.is_empty()
+            && self.cells.len() % HASH_COUNT == 0
+            && self.cells.len() <= MAX_CELLS
+    }
+
+    /// Adds a short ID to the sketch.
+    pub(super) fn insert(&mut self, short_id: u64) {
+        self.toggle(short_id, 1);
+    }
+
+    /// Returns the indices of the cells of the given short ID, one in each third of the sketch.
+    fn indices(&self, short_id: u64) -> impl Iterator<Item = usize> {
+        let cells_per_hash = self.cells.len() / HASH_COUNT;
+        INDEX_SEEDS.iter().enumerate().map(move |(i, seed)| {
+            i * cells_per_hash + (mix(short_id ^ seed) % cells_per_hash as u64) as usize
+        })
+    }
+
+    fn toggle(&mut self, short_id: u64, count: i32) {
+        for index in self.indices(short_id).collect::<Vec<_>>() {
+            self.cells[index].toggle(short_id, count);
+        }
+    }
+
+    /// Returns the sketch of the symmetric difference of the two sets, or `None` if the sketches
+    /// have a different number of cells.
+    pub(super) fn subtract(mut self, other: &Sketch) -> Option<Sketch> {
+        if self.cells.len() != other.cells.len() {
+            return None;
+        }
+        for (cell, other_cell) in self.cells.iter_mut().zip(&other.cells) {
+            cell.count = cell.count.wrapping_sub(other_cell.count);
+            cell.id_sum ^= other_cell.id_sum;
+            cell.hash_sum ^= other_cell.hash_sum;
+        }
+        Some(self)
+    }
+
+    /// Decodes a sketch created by `subtract`.
+    ///
+    /// Returns `None` if the difference is too large to be decoded.
+    pub(super) fn decode(mut self) -> Option<Difference> {
+        let mut difference = Difference::default();
+        let mut pure: Vec<usize> = (0..self.cells.len())
+            .filter(|&index| self.cells[index].is_pure())
+            .collect();
+        while let Some(index) = pure.pop() {
+            let cell = self.cells[index];
+            if !cell.is_pure() {
+                continue;
+            }
+            // A malicious peer could craft a sketch which never peels completely.
+            if difference.ours.len() + difference.theirs.len() >= self.cells.len() {
+                return None;
+            }
+            let short_id = cell.id_sum;
+            if cell.count == 1 {
+                difference.ours.push(short_id);
+            } else {
+                difference.theirs.push(short_id);
+            }
+            let indices: Vec<_> = self.indices(short_id).collect();
+            for &index in &indices {
+                self.cells[index].toggle(short_id, -cell.count);
+            }
+            pure.extend(
+                indices
+                    .into_iter()
+                    .filter(|&index| self.cells[index].is_pure()),
+            );
+        }
+        if self.cells.iter().all(Cell::is_empty) {
+            Some(difference)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::testing::TestRng;
+
+    fn sketch_of(capacity: usize, short_ids: &[u64]) -> Sketch {
+        let mut sketch = Sketch::with_capacity(capacity);
+        for &short_id in short_ids {
+            sketch.insert(short_id);
+        }
+        sketch
+    }
+
+    #[test]
+    fn should_decode_difference() {
+        let mut rng = TestRng::new();
+        let common: Vec<u64> = (0..1000).map(|_| rng.gen()).collect();
+        let ours: Vec<u64> = (0..20).map(|_| rng.gen()).collect();
+        let theirs: Vec<u64> = (0..20).map(|_| rng.gen()).collect();
+
+        let our_sketch = sketch_of(64, &[common.clone(), ours.clone()].concat());
+        let their_sketch = sketch_of(64, &[common, theirs.clone()].concat());
+        let difference = our_sketch
+            .subtract(&their_sketch)
+            .unwrap()
+            .decode()
+            .unwrap();
+
+        let sorted = |short_ids: &[u64]| short_ids.iter().copied().collect::<BTreeSet<_>>();
+        assert_eq!(sorted(&ours), sorted(&difference.ours));
+        assert_eq!(sorted(&theirs), sorted(&difference.theirs));
+    }
+
+    #[test]
+    fn should_decode_identical_sets() {
+        let mut rng = TestRng::new();
+        let short_ids: Vec<u64> = (0..100).map(|_| rng.gen()).collect();
+        let difference = sketch_of(8, &short_ids)
+            .subtract(&sketch_of(8, &short_ids))
+            .unwrap()
+            .decode()
+            .unwrap();
+        assert_eq!(Difference::default(), difference);
+    }
+
+    #[test]
+    fn should_fail_to_decode_large_difference() {
+        let mut rng = TestRng::new();
+        let ours: Vec<u64> = (0..200).map(|_| rng.gen()).collect();
+        assert!(sketch_of(8, &ours)
+            .subtract(&sketch_of(8, &[]))
+            .unwrap()
+            .decode()
+            .is_none());
+    }
+
+    #[test]
+    fn should_not_subtract_sketches_of_different_size() {
+        assert!(Sketch::with_capacity(8)
+            .subtract(&Sketch::with_capacity(16))
+            .is_none());
+        assert!(Sketch::with_capacity(8).is_well_formed());
+        assert!(!Sketch { cells: vec![] }.is_well_formed());
+    }
+}
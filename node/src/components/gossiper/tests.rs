@@ -18,20 +18,21 @@ use tokio::time;
 use tracing::debug;
 
 use casper_execution_engine::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
-use casper_types::ProtocolVersion;
+use casper_types::{system::auction::InactivityPolicy, ProtocolVersion};
 
 use super::*;
 use crate::{
     components::{
-        contract_runtime::{self, ContractRuntime, ContractRuntimeAnnouncement},
+        contract_runtime::{self, ContractRuntime, ContractRuntimeAnnouncement, SlashingConfig},
         deploy_acceptor::{self, DeployAcceptor},
+        fetcher::{self, Fetcher},
         in_memory_network::{self, InMemoryNetwork, NetworkController},
         storage::{self, Storage},
     },
     effect::{
         announcements::{
-            ControlAnnouncement, DeployAcceptorAnnouncement, GossiperAnnouncement,
-            NetworkAnnouncement, RpcServerAnnouncement,
+            BlocklistAnnouncement, ControlAnnouncement, DeployAcceptorAnnouncement,
+            GossiperAnnouncement, NetworkAnnouncement, RpcServerAnnouncement,
         },
         requests::{ConsensusRequest, ContractRuntimeRequest, LinearChainRequest},
         Responder,
@@ -63,6 +64,10 @@ enum Event {
     #[from]
     DeployGossiper(super::Event<Deploy>),
     #[from]
+    DeployFetcher(#[serde(skip_serializing)] fetcher::Event<Deploy>),
+    #[from]
+    DeployFetcherRequest(#[serde(skip_serializing)] FetcherRequest<NodeId, Deploy>),
+    #[from]
     NetworkRequest(NetworkRequest<NodeId, NodeMessage>),
     #[from]
     ControlAnnouncement(ControlAnnouncement),
@@ -75,6 +80,8 @@ enum Event {
     #[from]
     DeployGossiperAnnouncement(#[serde(skip_serializing)] GossiperAnnouncement<Deploy>),
     #[from]
+    BlocklistAnnouncement(#[serde(skip_serializing)] BlocklistAnnouncement<NodeId>),
+    #[from]
     ContractRuntime(#[serde(skip_serializing)] Box<ContractRuntimeRequest>),
 }
 
@@ -131,6 +138,10 @@ impl Display for Event {
             Event::Storage(event) => write!(formatter, "storage: {}", event),
             Event::DeployAcceptor(event) => write!(formatter, "deploy acceptor: {}", event),
             Event::DeployGossiper(event) => write!(formatter, "deploy gossiper: {}", event),
+            Event::DeployFetcher(event) => write!(formatter, "deploy fetcher: {}", event),
+            Event::DeployFetcherRequest(req) => {
+                write!(formatter, "deploy fetcher request: {}", req)
+            }
             Event::NetworkRequest(req) => write!(formatter, "network request: {}", req),
             Event::ControlAnnouncement(ctrl_ann) => write!(formatter, "control: {}", ctrl_ann),
             Event::NetworkAnnouncement(ann) => write!(formatter, "network announcement: {}", ann),
@@ -143,6 +154,9 @@ impl Display for Event {
             Event::DeployGossiperAnnouncement(ann) => {
                 write!(formatter, "deploy-gossiper announcement: {}", ann)
             }
+            Event::BlocklistAnnouncement(ann) => {
+                write!(formatter, "blocklist announcement: {}", ann)
+            }
             Event::ContractRuntime(event) => {
                 write!(formatter, "contract-runtime event: {:?}", event)
             }
@@ -162,6 +176,7 @@ struct Reactor {
     storage: Storage,
     deploy_acceptor: DeployAcceptor,
     deploy_gossiper: Gossiper<Deploy, Event>,
+    deploy_fetcher: Fetcher<Deploy>,
     contract_runtime: ContractRuntime,
     _storage_tempdir: TempDir,
 }
@@ -204,6 +219,8 @@ impl reactor::Reactor for Reactor {
             WasmConfig::default(),
             SystemConfig::default(),
             MAX_ASSOCIATED_KEYS,
+            SlashingConfig::default(),
+            InactivityPolicy::default(),
             registry,
         )
         .unwrap();
@@ -218,6 +235,13 @@ impl reactor::Reactor for Reactor {
             "deploy_gossiper",
             config,
             get_deploy_from_storage,
+            fetch_deploy_from_peer,
+            registry,
+        )?;
+        let deploy_fetcher = Fetcher::new(
+            "deploy",
+            fetcher::Config::default(),
+            Default::default(),
             registry,
         )?;
 
@@ -226,6 +250,7 @@ impl reactor::Reactor for Reactor {
             storage,
             deploy_acceptor,
             deploy_gossiper,
+            deploy_fetcher,
             contract_runtime,
             _storage_tempdir: storage_tempdir,
         };
@@ -256,6 +281,13 @@ impl reactor::Reactor for Reactor {
                 self.deploy_gossiper
                     .handle_event(effect_builder, rng, event),
             ),
+            Event::DeployFetcher(event) => reactor::wrap_effects(
+                Event::DeployFetcher,
+                self.deploy_fetcher.handle_event(effect_builder, rng, event),
+            ),
+            Event::DeployFetcherRequest(request) => {
+                self.dispatch_event(effect_builder, rng, Event::DeployFetcher(request.into()))
+            }
             Event::NetworkRequest(request) => reactor::wrap_effects(
                 Event::Network,
                 self.network
@@ -352,9 +384,21 @@ impl reactor::Reactor for Reactor {
             }) => {
                 let event = super::Event::ItemReceived {
                     item_id: *deploy.id(),
+                    source: source.clone(),
+                };
+                let mut effects =
+                    self.dispatch_event(effect_builder, rng, Event::DeployGossiper(event));
+
+                let event = fetcher::Event::GotRemotely {
+                    item: deploy,
                     source,
                 };
-                self.dispatch_event(effect_builder, rng, Event::DeployGossiper(event))
+                effects.extend(self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    Event::DeployFetcher(event),
+                ));
+                effects
             }
             Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::InvalidDeploy {
                 deploy: _,
@@ -364,6 +408,10 @@ impl reactor::Reactor for Reactor {
                 // We do not care about deploy gossiper announcements in the gossiper test.
                 Effects::new()
             }
+            Event::BlocklistAnnouncement(_ann) => {
+                // We do not care about blocking peers in the gossiper test.
+                Effects::new()
+            }
             Event::Network(event) => reactor::wrap_effects(
                 Event::Network,
                 self.network.handle_event(effect_builder, rng, event),
@@ -400,7 +448,7 @@ fn announce_deploy_received(
     }
 }
 
-async fn run_gossip(rng: &mut TestRng, network_size: usize, deploy_count: usize) {
+async fn run_gossip(rng: &mut TestRng, network_size: usize, deploy_count: usize, config: Config) {
     const TIMEOUT: Duration = Duration::from_secs(20);
     const QUIET_FOR: Duration = Duration::from_millis(50);
 
@@ -408,7 +456,11 @@ async fn run_gossip(rng: &mut TestRng, network_size: usize, deploy_count: usize)
     let mut network = Network::<Reactor>::new();
 
     // Add `network_size` nodes.
-    let node_ids = network.add_nodes(rng, network_size).await;
+    let mut node_ids = vec![];
+    for _ in 0..network_size {
+        let (node_id, _runner) = network.add_node_with_config(config, rng).await.unwrap();
+        node_ids.push(node_id);
+    }
 
     // Create `deploy_count` random deploys.
     let (all_deploy_hashes, mut deploys): (BTreeSet<_>, Vec<_>) = iter::repeat_with(|| {
@@ -450,11 +502,96 @@ async fn should_gossip() {
 
     for network_size in &NETWORK_SIZES {
         for deploy_count in &DEPLOY_COUNTS {
-            run_gossip(&mut rng, *network_size, *deploy_count).await
+            run_gossip(&mut rng, *network_size, *deploy_count, Config::default()).await
         }
     }
 }
 
+#[tokio::test]
+async fn should_gossip_via_reconciliation() {
+    const NETWORK_SIZES: [usize; 3] = [2, 5, 20];
+    const DEPLOY_COUNTS: [usize; 3] = [1, 10, 30];
+
+    let mut rng = crate::new_rng();
+
+    for network_size in &NETWORK_SIZES {
+        for deploy_count in &DEPLOY_COUNTS {
+            let config = Config::new_for_reconciliation();
+            run_gossip(&mut rng, *network_size, *deploy_count, config).await
+        }
+    }
+}
+
+#[tokio::test]
+async fn should_fall_back_to_offering_all_items_if_sketch_is_too_small() {
+    // The difference between the nodes exceeds the default sketch capacity of 64 items.
+    const NETWORK_SIZE: usize = 3;
+    const DEPLOY_COUNT: usize = 200;
+    const TIMEOUT: Duration = Duration::from_secs(20);
+    const QUIET_FOR: Duration = Duration::from_millis(50);
+
+    NetworkController::<NodeMessage>::create_active();
+    let mut network = Network::<Reactor>::new();
+    let mut rng = crate::new_rng();
+
+    // Rounds are only started manually, so the nodes can't converge via any other route.
+    let config = Config::new_for_manual_reconciliation();
+    let mut node_ids = vec![];
+    for _ in 0..NETWORK_SIZE {
+        let (node_id, _runner) = network
+            .add_node_with_config(config, &mut rng)
+            .await
+            .unwrap();
+        node_ids.push(node_id);
+    }
+
+    // Give each deploy to a randomly-chosen node.
+    let (all_deploy_hashes, mut deploys): (BTreeSet<_>, Vec<_>) = iter::repeat_with(|| {
+        let deploy = Box::new(Deploy::random_valid_native_transfer(&mut rng));
+        (*deploy.id(), deploy)
+    })
+    .take(DEPLOY_COUNT)
+    .unzip();
+    for deploy in deploys.drain(..) {
+        let index: usize = rng.gen_range(0..NETWORK_SIZE);
+        network
+            .process_injected_effect_on(&node_ids[index], announce_deploy_received(deploy, None))
+            .await;
+    }
+    network.settle(&mut rng, QUIET_FOR, TIMEOUT).await;
+
+    // A single round per node exchanges the full lists of items between every pair of nodes.
+    for node_id in &node_ids {
+        network
+            .process_injected_effect_on(node_id, |_| {
+                async {}.event(|_| Event::DeployGossiper(super::Event::ReconciliationRound))
+            })
+            .await;
+        network.settle(&mut rng, QUIET_FOR, TIMEOUT).await;
+    }
+
+    let nodes = network.nodes();
+    let reconciliation_failures: u64 = nodes
+        .values()
+        .map(|runner| {
+            runner
+                .reactor()
+                .inner()
+                .deploy_gossiper
+                .metrics
+                .reconciliation_failures
+                .get()
+        })
+        .sum();
+    assert!(reconciliation_failures > 0);
+    for runner in nodes.values() {
+        let hashes = runner.reactor().inner().storage.get_all_deploy_hashes();
+        assert_eq!(all_deploy_hashes, hashes);
+    }
+
+    NetworkController::<NodeMessage>::remove_active();
+}
+
 #[tokio::test]
 async fn should_get_from_alternate_source() {
     const NETWORK_SIZE: usize = 3;
@@ -32,8 +32,9 @@
 //! #         Component,
 //! #     },
 //! #     effect::{
-//! #         announcements::NetworkAnnouncement, requests::NetworkRequest, EffectBuilder, EffectExt,
-//! #         Effects,
+//! #         announcements::NetworkAnnouncement,
+//! #         requests::{GossipTarget, NetworkRequest},
+//! #         EffectBuilder, EffectExt, Effects,
 //! #     },
 //! #     reactor::{self, wrap_effects, EventQueueHandle},
 //! #     testing::network::{Network, NetworkedReactor},
@@ -120,6 +121,7 @@
 //!                 if let Some(msg) = self.whispers.pop() {
 //!                     return effect_builder.gossip_message(msg,
 //!                                                          TEST_GOSSIP_COUNT,
+//!                                                          GossipTarget::All,
 //!                                                          Default::default())
 //!                         .event(|_| ShouterEvent::ReadyToSend);
 //!                 }
@@ -296,8 +298,9 @@ use tracing::{debug, error, info, warn};
 use crate::{
     components::Component,
     effect::{
-        announcements::NetworkAnnouncement, requests::NetworkRequest, EffectBuilder, EffectExt,
-        Effects,
+        announcements::NetworkAnnouncement,
+        requests::{GossipTarget, NetworkRequest},
+        EffectBuilder, EffectExt, Effects,
     },
     logging,
     reactor::{EventQueueHandle, QueueKind},
@@ -569,9 +572,14 @@ where
             NetworkRequest::Gossip {
                 payload,
                 count,
+                target,
                 exclude,
                 responder,
             } => {
+                // Every in-memory node understands set-reconciliation gossip messages.
+                if target == GossipTarget::NonReconciling {
+                    return responder.respond(Default::default()).ignore();
+                }
                 if let Ok(guard) = self.nodes.read() {
                     let chosen: HashSet<_> = guard
                         .keys()
@@ -40,7 +40,7 @@ use self::event::{BlockByHashResult, DeploysResult};
 use casper_types::{EraId, ProtocolVersion};
 
 use super::{
-    fetcher::FetchResult,
+    fetcher::{FetchResult, SharedPeerStats},
     storage::{self, Storage},
     Component,
 };
@@ -92,7 +92,7 @@ pub(crate) struct LinearChainSync<I> {
     initial_execution_pre_state: ExecutionPreState,
 }
 
-impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
+impl<I: Clone + Eq + std::hash::Hash + 'static> LinearChainSync<I> {
     // TODO: fix this
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn new<REv, Err>(
@@ -106,6 +106,7 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
         next_upgrade_activation_point: Option<ActivationPoint>,
         initial_execution_pre_state: ExecutionPreState,
         config: Config,
+        peer_stats: SharedPeerStats<I>,
     ) -> Result<(Self, Effects<Event<I>>), Err>
     where
         REv: From<Event<I>> + Send,
@@ -123,6 +124,7 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
                 next_upgrade_activation_point,
                 protocol_version,
                 initial_execution_pre_state,
+                peer_stats,
             )?;
             Ok((linear_chain_sync, timeout_event))
         } else {
@@ -163,7 +165,7 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
             };
             let state_key = create_state_key(chainspec);
             let linear_chain_sync = LinearChainSync {
-                peers: PeersState::new(),
+                peers: PeersState::new(peer_stats),
                 state,
                 metrics: LinearChainSyncMetrics::new(registry)?,
                 next_upgrade_activation_point,
@@ -187,6 +189,7 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
         next_upgrade_activation_point: Option<ActivationPoint>,
         protocol_version: ProtocolVersion,
         initial_execution_pre_state: ExecutionPreState,
+        peer_stats: SharedPeerStats<I>,
     ) -> Result<Self, prometheus::Error> {
         let state_key = create_state_key(chainspec);
         info!(?state, "reusing previous state");
@@ -202,7 +205,7 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
             );
         }
         Ok(LinearChainSync {
-            peers: PeersState::new(),
+            peers: PeersState::new(peer_stats),
             state,
             metrics: LinearChainSyncMetrics::new(registry)?,
             next_upgrade_activation_point,
@@ -577,7 +580,7 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
 
 impl<I, REv> Component<REv> for LinearChainSync<I>
 where
-    I: Display + Clone + Send + PartialEq + 'static,
+    I: Display + Clone + Send + Eq + std::hash::Hash + 'static,
     REv: ReactorEventT<I>,
 {
     type Event = Event<I>;
@@ -1,8 +1,10 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, hash::Hash};
 
 use datasize::DataSize;
 use rand::{seq::SliceRandom, Rng};
 
+use crate::components::fetcher::SharedPeerStats;
+
 #[derive(DataSize, Debug)]
 pub struct PeersState<I> {
     // Set of peers that we can request blocks from.
@@ -15,23 +17,34 @@ pub struct PeersState<I> {
     succ_peers: VecDeque<I>,
     succ_attempts: u8,
     succ_attempts_max: u8,
+    // Response latencies and failure rates of the peers, shared with the fetchers.
+    #[data_size(skip)]
+    peer_stats: SharedPeerStats<I>,
 }
 
-impl<I: Clone + PartialEq + 'static> PeersState<I> {
-    pub fn new() -> Self {
+impl<I: Clone + Eq + Hash + 'static> PeersState<I> {
+    pub fn new(peer_stats: SharedPeerStats<I>) -> Self {
         PeersState {
             peers: Default::default(),
             peers_to_try: Default::default(),
             succ_peers: Default::default(),
             succ_attempts: 0,
             succ_attempts_max: 5,
+            peer_stats,
         }
     }
 
-    /// Resets `peers_to_try` back to all `peers` we know of.
+    /// Resets `peers_to_try` back to all `peers` we know of, ordered so that the peers which
+    /// answered fastest and most reliably so far are tried first.
     pub(crate) fn reset<R: Rng + ?Sized>(&mut self, rng: &mut R) {
         self.peers_to_try = self.peers.clone();
         self.peers_to_try.as_mut_slice().shuffle(rng);
+        self.peer_stats
+            .lock()
+            .unwrap()
+            .sort_best_first(&mut self.peers_to_try);
+        // Peers are popped from the back.
+        self.peers_to_try.reverse();
     }
 
     /// Returns a random peer.
@@ -55,6 +68,7 @@ impl<I: Clone + PartialEq + 'static> PeersState<I> {
     pub(crate) fn ban(&mut self, peer: &I) {
         self.peers.retain(|p| p != peer);
         self.succ_peers.retain(|p| p != peer);
+        self.peer_stats.lock().unwrap().remove_peer(peer);
     }
 
     /// Returns whether known peer set is empty.
@@ -64,6 +78,7 @@ impl<I: Clone + PartialEq + 'static> PeersState<I> {
 
     /// Adds a new peer.
     pub(crate) fn push(&mut self, peer: I) {
+        self.peer_stats.lock().unwrap().add_peer(peer.clone());
         self.peers.push(peer.clone());
         self.peers_to_try.push(peer);
     }
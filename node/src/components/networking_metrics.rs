@@ -17,13 +17,33 @@ pub(super) struct NetworkingMetrics {
     pub(super) open_connections: IntGauge,
     /// Number of messages still waiting to be sent out (broadcast and direct).
     pub(super) queued_messages: IntGauge,
+    /// Number of protocol messages waiting to be sent out.
+    pub(super) queued_messages_protocol: IntGauge,
+    /// Number of consensus messages waiting to be sent out.
+    pub(super) queued_messages_consensus: IntGauge,
+    /// Number of finality signature messages waiting to be sent out.
+    pub(super) queued_messages_finality_signature: IntGauge,
+    /// Number of deploy gossip messages waiting to be sent out.
+    pub(super) queued_messages_deploy_gossip: IntGauge,
+    /// Number of address gossip messages waiting to be sent out.
+    pub(super) queued_messages_address_gossip: IntGauge,
+    /// Number of deploy transfer messages waiting to be sent out.
+    pub(super) queued_messages_deploy_transfer: IntGauge,
+    /// Number of block transfer messages waiting to be sent out.
+    pub(super) queued_messages_block_transfer: IntGauge,
+    /// Number of other messages waiting to be sent out.
+    pub(super) queued_messages_other: IntGauge,
     /// Number of connected peers.
     pub(super) peers: IntGauge,
+    /// Number of connections refused because the peer is not allowed to connect.
+    pub(super) refused_connections: IntCounter,
 
     /// Count of outgoing messages that are protocol overhead.
     pub(super) out_count_protocol: IntCounter,
     /// Count of outgoing messages with consensus payload.
     pub(super) out_count_consensus: IntCounter,
+    /// Count of outgoing messages with finality signature payload.
+    pub(super) out_count_finality_signature: IntCounter,
     /// Count of outgoing messages with deploy gossiper payload.
     pub(super) out_count_deploy_gossip: IntCounter,
     /// Count of outgoing messages with address gossiper payload.
@@ -39,6 +59,8 @@ pub(super) struct NetworkingMetrics {
     pub(super) out_bytes_protocol: IntCounter,
     /// Volume in bytes of outgoing messages with consensus payload.
     pub(super) out_bytes_consensus: IntCounter,
+    /// Volume in bytes of outgoing messages with finality signature payload.
+    pub(super) out_bytes_finality_signature: IntCounter,
     /// Volume in bytes of outgoing messages with deploy gossiper payload.
     pub(super) out_bytes_deploy_gossip: IntCounter,
     /// Volume in bytes of outgoing messages with address gossiper payload.
@@ -50,6 +72,39 @@ pub(super) struct NetworkingMetrics {
     /// Volume in bytes of outgoing messages with other payload.
     pub(super) out_bytes_other: IntCounter,
 
+    /// Volume in bytes of outgoing protocol messages before compression.
+    pub(super) out_bytes_uncompressed_protocol: IntCounter,
+    /// Volume in bytes of outgoing protocol messages after compression.
+    pub(super) out_bytes_compressed_protocol: IntCounter,
+    /// Volume in bytes of outgoing consensus messages before compression.
+    pub(super) out_bytes_uncompressed_consensus: IntCounter,
+    /// Volume in bytes of outgoing consensus messages after compression.
+    pub(super) out_bytes_compressed_consensus: IntCounter,
+    /// Volume in bytes of outgoing finality signature messages before compression.
+    pub(super) out_bytes_uncompressed_finality_signature: IntCounter,
+    /// Volume in bytes of outgoing finality signature messages after compression.
+    pub(super) out_bytes_compressed_finality_signature: IntCounter,
+    /// Volume in bytes of outgoing deploy gossip messages before compression.
+    pub(super) out_bytes_uncompressed_deploy_gossip: IntCounter,
+    /// Volume in bytes of outgoing deploy gossip messages after compression.
+    pub(super) out_bytes_compressed_deploy_gossip: IntCounter,
+    /// Volume in bytes of outgoing address gossip messages before compression.
+    pub(super) out_bytes_uncompressed_address_gossip: IntCounter,
+    /// Volume in bytes of outgoing address gossip messages after compression.
+    pub(super) out_bytes_compressed_address_gossip: IntCounter,
+    /// Volume in bytes of outgoing deploy transfer messages before compression.
+    pub(super) out_bytes_uncompressed_deploy_transfer: IntCounter,
+    /// Volume in bytes of outgoing deploy transfer messages after compression.
+    pub(super) out_bytes_compressed_deploy_transfer: IntCounter,
+    /// Volume in bytes of outgoing block transfer messages before compression.
+    pub(super) out_bytes_uncompressed_block_transfer: IntCounter,
+    /// Volume in bytes of outgoing block transfer messages after compression.
+    pub(super) out_bytes_compressed_block_transfer: IntCounter,
+    /// Volume in bytes of outgoing other messages before compression.
+    pub(super) out_bytes_uncompressed_other: IntCounter,
+    /// Volume in bytes of outgoing other messages after compression.
+    pub(super) out_bytes_compressed_other: IntCounter,
+
     /// Registry instance.
     registry: Registry,
 }
@@ -69,7 +124,43 @@ impl NetworkingMetrics {
             "net_queued_direct_messages",
             "number of messages waiting to be sent out",
         )?;
+        let queued_messages_protocol = IntGauge::new(
+            "net_queued_messages_protocol",
+            "number of protocol messages waiting to be sent out",
+        )?;
+        let queued_messages_consensus = IntGauge::new(
+            "net_queued_messages_consensus",
+            "number of consensus messages waiting to be sent out",
+        )?;
+        let queued_messages_finality_signature = IntGauge::new(
+            "net_queued_messages_finality_signature",
+            "number of finality signature messages waiting to be sent out",
+        )?;
+        let queued_messages_deploy_gossip = IntGauge::new(
+            "net_queued_messages_deploy_gossip",
+            "number of deploy gossip messages waiting to be sent out",
+        )?;
+        let queued_messages_address_gossip = IntGauge::new(
+            "net_queued_messages_address_gossip",
+            "number of address gossip messages waiting to be sent out",
+        )?;
+        let queued_messages_deploy_transfer = IntGauge::new(
+            "net_queued_messages_deploy_transfer",
+            "number of deploy transfer messages waiting to be sent out",
+        )?;
+        let queued_messages_block_transfer = IntGauge::new(
+            "net_queued_messages_block_transfer",
+            "number of block transfer messages waiting to be sent out",
+        )?;
+        let queued_messages_other = IntGauge::new(
+            "net_queued_messages_other",
+            "number of other messages waiting to be sent out",
+        )?;
         let peers = IntGauge::new("peers", "number of connected peers")?;
+        let refused_connections = IntCounter::new(
+            "net_refused_connections",
+            "number of connections refused because the peer is not allowed to connect",
+        )?;
 
         let out_count_protocol = IntCounter::new(
             "net_out_count_protocol",
@@ -79,6 +170,10 @@ impl NetworkingMetrics {
             "net_out_count_consensus",
             "count of outgoing messages with consensus payload",
         )?;
+        let out_count_finality_signature = IntCounter::new(
+            "net_out_count_finality_signature",
+            "count of outgoing messages with finality signature payload",
+        )?;
         let out_count_deploy_gossip = IntCounter::new(
             "net_out_count_deploy_gossip",
             "count of outgoing messages with deploy gossiper payload",
@@ -108,6 +203,10 @@ impl NetworkingMetrics {
             "net_out_bytes_consensus",
             "volume in bytes of outgoing messages with consensus payload",
         )?;
+        let out_bytes_finality_signature = IntCounter::new(
+            "net_out_bytes_finality_signature",
+            "volume in bytes of outgoing messages with finality signature payload",
+        )?;
         let out_bytes_deploy_gossip = IntCounter::new(
             "net_out_bytes_deploy_gossip",
             "volume in bytes of outgoing messages with deploy gossiper payload",
@@ -129,14 +228,89 @@ impl NetworkingMetrics {
             "volume in bytes of outgoing messages with other payload",
         )?;
 
+        let out_bytes_uncompressed_protocol = IntCounter::new(
+            "net_out_bytes_uncompressed_protocol",
+            "volume in bytes of outgoing protocol messages before compression",
+        )?;
+        let out_bytes_compressed_protocol = IntCounter::new(
+            "net_out_bytes_compressed_protocol",
+            "volume in bytes of outgoing protocol messages after compression",
+        )?;
+        let out_bytes_uncompressed_consensus = IntCounter::new(
+            "net_out_bytes_uncompressed_consensus",
+            "volume in bytes of outgoing consensus messages before compression",
+        )?;
+        let out_bytes_compressed_consensus = IntCounter::new(
+            "net_out_bytes_compressed_consensus",
+            "volume in bytes of outgoing consensus messages after compression",
+        )?;
+        let out_bytes_uncompressed_finality_signature = IntCounter::new(
+            "net_out_bytes_uncompressed_finality_signature",
+            "volume in bytes of outgoing finality signature messages before compression",
+        )?;
+        let out_bytes_compressed_finality_signature = IntCounter::new(
+            "net_out_bytes_compressed_finality_signature",
+            "volume in bytes of outgoing finality signature messages after compression",
+        )?;
+        let out_bytes_uncompressed_deploy_gossip = IntCounter::new(
+            "net_out_bytes_uncompressed_deploy_gossip",
+            "volume in bytes of outgoing deploy gossip messages before compression",
+        )?;
+        let out_bytes_compressed_deploy_gossip = IntCounter::new(
+            "net_out_bytes_compressed_deploy_gossip",
+            "volume in bytes of outgoing deploy gossip messages after compression",
+        )?;
+        let out_bytes_uncompressed_address_gossip = IntCounter::new(
+            "net_out_bytes_uncompressed_address_gossip",
+            "volume in bytes of outgoing address gossip messages before compression",
+        )?;
+        let out_bytes_compressed_address_gossip = IntCounter::new(
+            "net_out_bytes_compressed_address_gossip",
+            "volume in bytes of outgoing address gossip messages after compression",
+        )?;
+        let out_bytes_uncompressed_deploy_transfer = IntCounter::new(
+            "net_out_bytes_uncompressed_deploy_transfer",
+            "volume in bytes of outgoing deploy transfer messages before compression",
+        )?;
+        let out_bytes_compressed_deploy_transfer = IntCounter::new(
+            "net_out_bytes_compressed_deploy_transfer",
+            "volume in bytes of outgoing deploy transfer messages after compression",
+        )?;
+        let out_bytes_uncompressed_block_transfer = IntCounter::new(
+            "net_out_bytes_uncompressed_block_transfer",
+            "volume in bytes of outgoing block transfer messages before compression",
+        )?;
+        let out_bytes_compressed_block_transfer = IntCounter::new(
+            "net_out_bytes_compressed_block_transfer",
+            "volume in bytes of outgoing block transfer messages after compression",
+        )?;
+        let out_bytes_uncompressed_other = IntCounter::new(
+            "net_out_bytes_uncompressed_other",
+            "volume in bytes of outgoing other messages before compression",
+        )?;
+        let out_bytes_compressed_other = IntCounter::new(
+            "net_out_bytes_compressed_other",
+            "volume in bytes of outgoing other messages after compression",
+        )?;
+
         registry.register(Box::new(broadcast_requests.clone()))?;
         registry.register(Box::new(direct_message_requests.clone()))?;
         registry.register(Box::new(open_connections.clone()))?;
         registry.register(Box::new(queued_messages.clone()))?;
+        registry.register(Box::new(queued_messages_protocol.clone()))?;
+        registry.register(Box::new(queued_messages_consensus.clone()))?;
+        registry.register(Box::new(queued_messages_finality_signature.clone()))?;
+        registry.register(Box::new(queued_messages_deploy_gossip.clone()))?;
+        registry.register(Box::new(queued_messages_address_gossip.clone()))?;
+        registry.register(Box::new(queued_messages_deploy_transfer.clone()))?;
+        registry.register(Box::new(queued_messages_block_transfer.clone()))?;
+        registry.register(Box::new(queued_messages_other.clone()))?;
         registry.register(Box::new(peers.clone()))?;
+        registry.register(Box::new(refused_connections.clone()))?;
 
         registry.register(Box::new(out_count_protocol.clone()))?;
         registry.register(Box::new(out_count_consensus.clone()))?;
+        registry.register(Box::new(out_count_finality_signature.clone()))?;
         registry.register(Box::new(out_count_deploy_gossip.clone()))?;
         registry.register(Box::new(out_count_address_gossip.clone()))?;
         registry.register(Box::new(out_count_deploy_transfer.clone()))?;
@@ -145,20 +319,48 @@ impl NetworkingMetrics {
 
         registry.register(Box::new(out_bytes_protocol.clone()))?;
         registry.register(Box::new(out_bytes_consensus.clone()))?;
+        registry.register(Box::new(out_bytes_finality_signature.clone()))?;
         registry.register(Box::new(out_bytes_deploy_gossip.clone()))?;
         registry.register(Box::new(out_bytes_address_gossip.clone()))?;
         registry.register(Box::new(out_bytes_deploy_transfer.clone()))?;
         registry.register(Box::new(out_bytes_block_transfer.clone()))?;
         registry.register(Box::new(out_bytes_other.clone()))?;
 
+        registry.register(Box::new(out_bytes_uncompressed_protocol.clone()))?;
+        registry.register(Box::new(out_bytes_compressed_protocol.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_consensus.clone()))?;
+        registry.register(Box::new(out_bytes_compressed_consensus.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_finality_signature.clone()))?;
+        registry.register(Box::new(out_bytes_compressed_finality_signature.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_deploy_gossip.clone()))?;
+        registry.register(Box::new(out_bytes_compressed_deploy_gossip.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_address_gossip.clone()))?;
+        registry.register(Box::new(out_bytes_compressed_address_gossip.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_deploy_transfer.clone()))?;
+        registry.register(Box::new(out_bytes_compressed_deploy_transfer.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_block_transfer.clone()))?;
+        registry.register(Box::new(out_bytes_compressed_block_transfer.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_other.clone()))?;
+        registry.register(Box::new(out_bytes_compressed_other.clone()))?;
+
         Ok(NetworkingMetrics {
             broadcast_requests,
             direct_message_requests,
             open_connections,
             queued_messages,
+            queued_messages_protocol,
+            queued_messages_consensus,
+            queued_messages_finality_signature,
+            queued_messages_deploy_gossip,
+            queued_messages_address_gossip,
+            queued_messages_deploy_transfer,
+            queued_messages_block_transfer,
+            queued_messages_other,
             peers,
+            refused_connections,
             out_count_protocol,
             out_count_consensus,
+            out_count_finality_signature,
             out_count_deploy_gossip,
             out_count_address_gossip,
             out_count_deploy_transfer,
@@ -166,11 +368,28 @@ impl NetworkingMetrics {
             out_count_other,
             out_bytes_protocol,
             out_bytes_consensus,
+            out_bytes_finality_signature,
             out_bytes_deploy_gossip,
             out_bytes_address_gossip,
             out_bytes_deploy_transfer,
             out_bytes_block_transfer,
             out_bytes_other,
+            out_bytes_uncompressed_protocol,
+            out_bytes_compressed_protocol,
+            out_bytes_uncompressed_consensus,
+            out_bytes_compressed_consensus,
+            out_bytes_uncompressed_finality_signature,
+            out_bytes_compressed_finality_signature,
+            out_bytes_uncompressed_deploy_gossip,
+            out_bytes_compressed_deploy_gossip,
+            out_bytes_uncompressed_address_gossip,
+            out_bytes_compressed_address_gossip,
+            out_bytes_uncompressed_deploy_transfer,
+            out_bytes_compressed_deploy_transfer,
+            out_bytes_uncompressed_block_transfer,
+            out_bytes_compressed_block_transfer,
+            out_bytes_uncompressed_other,
+            out_bytes_compressed_other,
             registry: registry.clone(),
         })
     }
@@ -187,6 +406,10 @@ impl NetworkingMetrics {
                     metrics.out_bytes_consensus.inc_by(size);
                     metrics.out_count_consensus.inc();
                 }
+                MessageKind::FinalitySignature => {
+                    metrics.out_bytes_finality_signature.inc_by(size);
+                    metrics.out_count_finality_signature.inc();
+                }
                 MessageKind::DeployGossip => {
                     metrics.out_bytes_deploy_gossip.inc_by(size);
                     metrics.out_count_deploy_gossip.inc();
@@ -212,6 +435,97 @@ impl NetworkingMetrics {
             debug!("not recording metrics, component already shut down");
         }
     }
+
+    /// Returns the gauge of the number of queued messages of the given kind.
+    pub(super) fn queued_messages_of_kind(&self, kind: MessageKind) -> &IntGauge {
+        match kind {
+            MessageKind::Protocol => &self.queued_messages_protocol,
+            MessageKind::Consensus => &self.queued_messages_consensus,
+            MessageKind::FinalitySignature => &self.queued_messages_finality_signature,
+            MessageKind::DeployGossip => &self.queued_messages_deploy_gossip,
+            MessageKind::AddressGossip => &self.queued_messages_address_gossip,
+            MessageKind::DeployTransfer => &self.queued_messages_deploy_transfer,
+            MessageKind::BlockTransfer => &self.queued_messages_block_transfer,
+            MessageKind::Other => &self.queued_messages_other,
+        }
+    }
+
+    /// Records the size of an outgoing message before and after compression.
+    pub(crate) fn record_compression_out(
+        this: &mut Weak<Self>,
+        kind: MessageKind,
+        uncompressed_size: u64,
+        compressed_size: u64,
+    ) {
+        if let Some(metrics) = this.upgrade() {
+            match kind {
+                MessageKind::Protocol => {
+                    metrics
+                        .out_bytes_uncompressed_protocol
+                        .inc_by(uncompressed_size);
+                    metrics
+                        .out_bytes_compressed_protocol
+                        .inc_by(compressed_size);
+                }
+                MessageKind::Consensus => {
+                    metrics
+                        .out_bytes_uncompressed_consensus
+                        .inc_by(uncompressed_size);
+                    metrics
+                        .out_bytes_compressed_consensus
+                        .inc_by(compressed_size);
+                }
+                MessageKind::FinalitySignature => {
+                    metrics
+                        .out_bytes_uncompressed_finality_signature
+                        .inc_by(uncompressed_size);
+                    metrics
+                        .out_bytes_compressed_finality_signature
+                        .inc_by(compressed_size);
+                }
+                MessageKind::DeployGossip => {
+                    metrics
+                        .out_bytes_uncompressed_deploy_gossip
+                        .inc_by(uncompressed_size);
+                    metrics
+                        .out_bytes_compressed_deploy_gossip
+                        .inc_by(compressed_size);
+                }
+                MessageKind::AddressGossip => {
+                    metrics
+                        .out_bytes_uncompressed_address_gossip
+                        .inc_by(uncompressed_size);
+                    metrics
+                        .out_bytes_compressed_address_gossip
+                        .inc_by(compressed_size);
+                }
+                MessageKind::DeployTransfer => {
+                    metrics
+                        .out_bytes_uncompressed_deploy_transfer
+                        .inc_by(uncompressed_size);
+                    metrics
+                        .out_bytes_compressed_deploy_transfer
+                        .inc_by(compressed_size);
+                }
+                MessageKind::BlockTransfer => {
+                    metrics
+                        .out_bytes_uncompressed_block_transfer
+                        .inc_by(uncompressed_size);
+                    metrics
+                        .out_bytes_compressed_block_transfer
+                        .inc_by(compressed_size);
+                }
+                MessageKind::Other => {
+                    metrics
+                        .out_bytes_uncompressed_other
+                        .inc_by(uncompressed_size);
+                    metrics.out_bytes_compressed_other.inc_by(compressed_size);
+                }
+            }
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
 }
 
 impl Drop for NetworkingMetrics {
@@ -220,10 +534,20 @@ impl Drop for NetworkingMetrics {
         unregister_metric!(self.registry, self.direct_message_requests);
         unregister_metric!(self.registry, self.open_connections);
         unregister_metric!(self.registry, self.queued_messages);
+        unregister_metric!(self.registry, self.queued_messages_protocol);
+        unregister_metric!(self.registry, self.queued_messages_consensus);
+        unregister_metric!(self.registry, self.queued_messages_finality_signature);
+        unregister_metric!(self.registry, self.queued_messages_deploy_gossip);
+        unregister_metric!(self.registry, self.queued_messages_address_gossip);
+        unregister_metric!(self.registry, self.queued_messages_deploy_transfer);
+        unregister_metric!(self.registry, self.queued_messages_block_transfer);
+        unregister_metric!(self.registry, self.queued_messages_other);
         unregister_metric!(self.registry, self.peers);
+        unregister_metric!(self.registry, self.refused_connections);
 
         unregister_metric!(self.registry, self.out_count_protocol);
         unregister_metric!(self.registry, self.out_count_consensus);
+        unregister_metric!(self.registry, self.out_count_finality_signature);
         unregister_metric!(self.registry, self.out_count_deploy_gossip);
         unregister_metric!(self.registry, self.out_count_address_gossip);
         unregister_metric!(self.registry, self.out_count_deploy_transfer);
@@ -231,10 +555,31 @@ impl Drop for NetworkingMetrics {
         unregister_metric!(self.registry, self.out_count_other);
         unregister_metric!(self.registry, self.out_bytes_protocol);
         unregister_metric!(self.registry, self.out_bytes_consensus);
+        unregister_metric!(self.registry, self.out_bytes_finality_signature);
         unregister_metric!(self.registry, self.out_bytes_deploy_gossip);
         unregister_metric!(self.registry, self.out_bytes_address_gossip);
         unregister_metric!(self.registry, self.out_bytes_deploy_transfer);
         unregister_metric!(self.registry, self.out_bytes_block_transfer);
         unregister_metric!(self.registry, self.out_bytes_other);
+
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_protocol);
+        unregister_metric!(self.registry, self.out_bytes_compressed_protocol);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_consensus);
+        unregister_metric!(self.registry, self.out_bytes_compressed_consensus);
+        unregister_metric!(
+            self.registry,
+            self.out_bytes_uncompressed_finality_signature
+        );
+        unregister_metric!(self.registry, self.out_bytes_compressed_finality_signature);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_deploy_gossip);
+        unregister_metric!(self.registry, self.out_bytes_compressed_deploy_gossip);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_address_gossip);
+        unregister_metric!(self.registry, self.out_bytes_compressed_address_gossip);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_deploy_transfer);
+        unregister_metric!(self.registry, self.out_bytes_compressed_deploy_transfer);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_block_transfer);
+        unregister_metric!(self.registry, self.out_bytes_compressed_block_transfer);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_other);
+        unregister_metric!(self.registry, self.out_bytes_compressed_other);
     }
 }
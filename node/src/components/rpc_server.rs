@@ -23,13 +23,17 @@ use std::{convert::Infallible, fmt::Debug, time::Instant};
 
 use datasize::DataSize;
 use futures::join;
+use tracing::{debug, warn};
 
 use casper_execution_engine::core::engine_state::{
     self, BalanceRequest, BalanceResult, GetBidsRequest, GetEraValidatorsError, QueryRequest,
     QueryResult,
 };
 use casper_hashing::Digest;
-use casper_types::{system::auction::EraValidators, Key, ProtocolVersion, URef};
+use casper_types::{
+    system::auction::{EraInfo, EraValidators},
+    EraId, Key, ProtocolVersion, StoredValue, URef,
+};
 
 use self::rpcs::chain::BlockIdentifier;
 use super::Component;
@@ -104,6 +108,7 @@ impl RpcServer {
             effect_builder,
             api_version,
             config.qps_limit,
+            config.enable_admin_rpcs,
         ));
 
         Ok(RpcServer {
@@ -163,6 +168,51 @@ impl RpcServer {
     }
 }
 
+/// Writes the seigniorage allocations of `era_id` to the reward history, reading them from the era
+/// info stored in global state as of the era's switch block.
+///
+/// Eras whose global state is not available on this node, e.g. because it was synced from a later
+/// block, are left out of the reward history.
+async fn backfill_reward_history<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    era_id: EraId,
+) {
+    let switch_block_header = match effect_builder
+        .get_switch_block_header_at_era_id_from_storage(era_id)
+        .await
+    {
+        Some(switch_block_header) => switch_block_header,
+        None => return,
+    };
+    let query = QueryRequest::new(
+        *switch_block_header.state_root_hash(),
+        Key::EraInfo(era_id),
+        vec![],
+    );
+    let era_info = match effect_builder.query_global_state(query).await {
+        Ok(QueryResult::Success { value, .. }) => match *value {
+            StoredValue::EraInfo(era_info) => era_info,
+            other => {
+                warn!(%era_id, ?other, "unexpected stored value under era info key");
+                return;
+            }
+        },
+        // No era info was written, so the era allocated no rewards.
+        Ok(QueryResult::ValueNotFound(_)) => EraInfo::new(),
+        Ok(result) => {
+            debug!(%era_id, ?result, "cannot backfill reward history");
+            return;
+        }
+        Err(error) => {
+            warn!(%era_id, %error, "failed to query era info to backfill reward history");
+            return;
+        }
+    };
+    effect_builder
+        .put_era_info_to_storage(era_id, era_info)
+        .await;
+}
+
 impl<REv> Component<REv> for RpcServer
 where
     REv: ReactorEventT,
@@ -260,6 +310,30 @@ where
                     result: Box::new(result),
                     main_responder: responder,
                 }),
+            Event::RpcRequest(RpcRequest::GetRewardHistory {
+                public_key,
+                from_era_id,
+                to_era_id,
+                responder,
+            }) => {
+                let public_key_clone = (*public_key).clone();
+                async move {
+                    for era_id in effect_builder
+                        .get_unindexed_reward_eras_from_storage(from_era_id, to_era_id)
+                        .await
+                    {
+                        backfill_reward_history(effect_builder, era_id).await;
+                    }
+                    effect_builder
+                        .get_reward_history_from_storage(public_key_clone, from_era_id, to_era_id)
+                        .await
+                }
+                .event(move |result| Event::GetRewardHistoryResult {
+                    public_key,
+                    result,
+                    main_responder: responder,
+                })
+            }
             Event::RpcRequest(RpcRequest::GetPeers { responder }) => effect_builder
                 .network_peers()
                 .event(move |peers| Event::GetPeersResult {
@@ -321,6 +395,11 @@ where
                 peers,
                 main_responder,
             } => main_responder.respond(peers).ignore(),
+            Event::GetRewardHistoryResult {
+                result,
+                main_responder,
+                ..
+            } => main_responder.respond(result).ignore(),
         }
     }
 }
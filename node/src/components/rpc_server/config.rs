@@ -18,6 +18,9 @@ pub struct Config {
 
     /// Max rate limit in qps.
     pub qps_limit: u64,
+
+    /// Whether to serve the admin RPCs, which allow managing the node's peers.
+    pub enable_admin_rpcs: bool,
 }
 
 impl Config {
@@ -26,6 +29,7 @@ impl Config {
         Config {
             address: DEFAULT_ADDRESS.to_string(),
             qps_limit: DEFAULT_QPS_LIMIT,
+            enable_admin_rpcs: false,
         }
     }
 }
@@ -8,7 +8,10 @@ use derive_more::From;
 use casper_execution_engine::core::engine_state::{
     self, BalanceResult, GetBidsResult, GetEraValidatorsError, QueryResult,
 };
-use casper_types::{system::auction::EraValidators, Transfer};
+use casper_types::{
+    system::auction::{EraValidators, SeigniorageAllocation},
+    EraId, PublicKey, Transfer,
+};
 
 use crate::{
     effect::{requests::RpcRequest, Responder},
@@ -51,6 +54,11 @@ pub(crate) enum Event {
         peers: BTreeMap<NodeId, String>,
         main_responder: Responder<BTreeMap<NodeId, String>>,
     },
+    GetRewardHistoryResult {
+        public_key: Box<PublicKey>,
+        result: Vec<(EraId, Vec<SeigniorageAllocation>)>,
+        main_responder: Responder<Vec<(EraId, Vec<SeigniorageAllocation>)>>,
+    },
     GetBalanceResult {
         result: Result<BalanceResult, engine_state::Error>,
         main_responder: Responder<Result<BalanceResult, engine_state::Error>>,
@@ -99,6 +107,14 @@ impl Display for Event {
                 write!(formatter, "get deploy result for {}: {:?}", hash, result)
             }
             Event::GetPeersResult { peers, .. } => write!(formatter, "get peers: {}", peers.len()),
+            Event::GetRewardHistoryResult {
+                public_key, result, ..
+            } => write!(
+                formatter,
+                "get reward history result for {}: {} eras",
+                public_key,
+                result.len()
+            ),
         }
     }
 }
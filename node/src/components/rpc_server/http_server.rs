@@ -50,6 +50,7 @@ pub(super) async fn run<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
     qps_limit: u64,
+    enable_admin_rpcs: bool,
 ) {
     // RPC filters.
     let rpc_put_deploy = rpcs::account::PutDeploy::create_filter(effect_builder, api_version);
@@ -69,15 +70,47 @@ pub(super) async fn run<REv: ReactorEventT>(
     let rpc_get_status = rpcs::info::GetStatus::create_filter(effect_builder, api_version);
     let rpc_get_era_info =
         rpcs::chain::GetEraInfoBySwitchBlock::create_filter(effect_builder, api_version);
+    let rpc_get_inactivity =
+        rpcs::chain::GetInactivityBySwitchBlock::create_filter(effect_builder, api_version);
+    let rpc_get_reward_history =
+        rpcs::chain::GetRewardHistory::create_filter(effect_builder, api_version);
     let rpc_get_auction_info =
         rpcs::state::GetAuctionInfo::create_filter(effect_builder, api_version);
     let rpc_get_trie = rpcs::state::GetTrie::create_filter(effect_builder, api_version);
     let rpcs_get_validator_changes =
         rpcs::info::GetValidatorChanges::create_filter(effect_builder, api_version);
+    let rpc_get_consensus_status =
+        rpcs::info::GetConsensusStatus::create_filter(effect_builder, api_version);
     let rpc_get_rpcs = rpcs::docs::ListRpcs::create_filter(effect_builder, api_version);
     let rpc_get_dictionary_item =
         rpcs::state::GetDictionaryItem::create_filter(effect_builder, api_version);
 
+    // Admin RPC filters, which are rejected as unknown methods unless enabled.
+    let admin_enabled = warp::any()
+        .and_then(move || async move {
+            if enable_admin_rpcs {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            }
+        })
+        .untuple_one();
+    let rpc_get_connections =
+        rpcs::admin::GetConnections::create_filter(effect_builder, api_version);
+    let rpc_get_banned_peers =
+        rpcs::admin::GetBannedPeers::create_filter(effect_builder, api_version);
+    let rpc_ban_peer = rpcs::admin::BanPeer::create_filter(effect_builder, api_version);
+    let rpc_unban_peer = rpcs::admin::UnbanPeer::create_filter(effect_builder, api_version);
+    let admin_routes = admin_enabled.and(
+        rpc_get_connections
+            .or(rpc_get_banned_peers)
+            .unify()
+            .or(rpc_ban_peer)
+            .unify()
+            .or(rpc_unban_peer)
+            .unify(),
+    );
+
     // Catch requests where the method is not one we handle.
     let unknown_method = warp::path(RPC_API_PATH)
         .and(warp_json_rpc::filters::json_rpc())
@@ -103,13 +136,17 @@ pub(super) async fn run<REv: ReactorEventT>(
         .or(rpc_get_peers)
         .or(rpc_get_status)
         .or(rpc_get_era_info)
+        .or(rpc_get_inactivity)
+        .or(rpc_get_reward_history)
         .or(rpc_get_auction_info)
         .or(rpc_get_account_info)
         .or(rpcs_get_validator_changes)
+        .or(rpc_get_consensus_status)
         .or(rpc_get_rpcs)
         .or(rpc_get_dictionary_item)
         .or(rpc_get_trie)
         .or(rpc_query_global_state)
+        .or(admin_routes)
         .or(unknown_method)
         .or(parse_failure);
 
@@ -3,6 +3,7 @@
 //! See <https://github.com/CasperLabs/ceps/blob/master/text/0009-client-api.md#rpcs> for info.
 
 pub mod account;
+pub mod admin;
 pub mod chain;
 pub mod docs;
 pub mod info;
@@ -49,6 +50,8 @@ enum ErrorCode {
     NoSuchAccount = -32009,
     FailedToGetDictionaryURef = -32010,
     FailedToGetTrie = -32011,
+    InvalidEraRange = -32012,
+    ParseNodeId = -32013,
     // Same error code as warp_json INTERNAL_ERROR.
     InternalError = -32063,
 }
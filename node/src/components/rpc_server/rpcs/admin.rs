@@ -0,0 +1,480 @@
+//! RPCs for node operators to inspect and manage the node's peers.
+//!
+//! These are only served if `enable_admin_rpcs` is set in the JSON-RPC server configuration.
+
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use std::str;
+
+use futures::{future::BoxFuture, FutureExt};
+use http::Response;
+use hyper::Body;
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use warp_json_rpc::Builder;
+
+use casper_types::{ProtocolVersion, PublicKey};
+
+use super::{
+    docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
+    Error, ErrorCode, ReactorEventT, RpcWithParams, RpcWithParamsExt, RpcWithoutParams,
+    RpcWithoutParamsExt,
+};
+use crate::{
+    components::small_network::{
+        self, Ban, ConnectionDetails, Direction, KindTraffic, MessageKind, PeerDetails,
+    },
+    crypto::AsymmetricKeyExt,
+    effect::EffectBuilder,
+    types::{NodeId, TimeDiff, Timestamp},
+};
+
+static GET_CONNECTIONS_RESULT: Lazy<GetConnectionsResult> = Lazy::new(|| GetConnectionsResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    connections: vec![Connection {
+        direction: ConnectionDirection::Outgoing,
+        address: "127.0.0.1:34553".to_string(),
+        state: ConnectionState::Connected,
+        failures_so_far: None,
+        peer: Some(ConnectedPeer {
+            node_id: hex::encode(NodeId::doc_example().hash_bytes()),
+            protocol_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+            network_name: "casper-example".to_string(),
+            is_compressed: false,
+            consensus_public_key: Some(PublicKey::doc_example().clone()),
+            is_active_validator: true,
+            last_seen: Some(*Timestamp::doc_example()),
+            traffic: vec![MessageKindTraffic::from(KindTraffic {
+                kind: MessageKind::Consensus,
+                messages_sent: 12,
+                bytes_sent: 3456,
+                messages_received: 1,
+                bytes_received: 210,
+            })],
+        }),
+    }],
+});
+
+static BANNED_PEER: Lazy<BannedPeer> = Lazy::new(|| BannedPeer {
+    node_id: hex::encode(NodeId::doc_example().hash_bytes()),
+    banned_until: *Timestamp::doc_example(),
+    reason: "invalid deploy".to_string(),
+    addresses: vec!["127.0.0.1:34553".to_string()],
+});
+static GET_BANNED_PEERS_RESULT: Lazy<GetBannedPeersResult> = Lazy::new(|| GetBannedPeersResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    banned_peers: vec![BANNED_PEER.clone()],
+});
+static BAN_PEER_PARAMS: Lazy<BanPeerParams> = Lazy::new(|| BanPeerParams {
+    node_id: BANNED_PEER.node_id.clone(),
+    duration: Some("1h".parse().unwrap()),
+});
+static BAN_PEER_RESULT: Lazy<BanPeerResult> = Lazy::new(|| BanPeerResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+});
+static UNBAN_PEER_PARAMS: Lazy<UnbanPeerParams> = Lazy::new(|| UnbanPeerParams {
+    node_id: BANNED_PEER.node_id.clone(),
+});
+static UNBAN_PEER_RESULT: Lazy<UnbanPeerResult> = Lazy::new(|| UnbanPeerResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    was_banned: true,
+});
+
+/// The direction of a connection.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionDirection {
+    /// The peer dialed us.
+    Incoming,
+    /// We dialed the peer.
+    Outgoing,
+}
+
+/// The state of a connection.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    /// Connecting to the address.
+    Connecting,
+    /// Waiting to retry connecting to the address after a failed attempt.
+    Waiting,
+    /// Connected.
+    Connected,
+    /// The address is blocked.
+    Blocked,
+    /// The address is the node's own.
+    Loopback,
+}
+
+/// The traffic of one kind of message on a connection.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MessageKindTraffic {
+    /// The kind of message.
+    pub kind: String,
+    /// The number of messages sent.
+    pub messages_sent: u64,
+    /// The number of bytes sent.
+    pub bytes_sent: u64,
+    /// The number of messages received.
+    pub messages_received: u64,
+    /// The number of bytes received.
+    pub bytes_received: u64,
+}
+
+impl From<KindTraffic> for MessageKindTraffic {
+    fn from(traffic: KindTraffic) -> Self {
+        MessageKindTraffic {
+            kind: traffic.kind.to_string(),
+            messages_sent: traffic.messages_sent,
+            bytes_sent: traffic.bytes_sent,
+            messages_received: traffic.messages_received,
+            bytes_received: traffic.bytes_received,
+        }
+    }
+}
+
+/// The peer at the other end of an established connection.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectedPeer {
+    /// The hex-encoded node ID.
+    pub node_id: String,
+    /// The protocol version the peer sent in its handshake.
+    #[schemars(with = "String")]
+    pub protocol_version: ProtocolVersion,
+    /// The network name the peer sent in its handshake.
+    pub network_name: String,
+    /// Whether messages on the connection are compressed.
+    pub is_compressed: bool,
+    /// The consensus key the peer proved to hold in its handshake, if any.
+    pub consensus_public_key: Option<PublicKey>,
+    /// Whether the consensus key belongs to a validator of the current era.
+    pub is_active_validator: bool,
+    /// When the last message was received on the connection, if any.
+    pub last_seen: Option<Timestamp>,
+    /// The traffic on the connection by kind of message, omitting kinds without any traffic.
+    pub traffic: Vec<MessageKindTraffic>,
+}
+
+impl From<PeerDetails> for ConnectedPeer {
+    fn from(peer: PeerDetails) -> Self {
+        ConnectedPeer {
+            node_id: hex::encode(peer.peer_id.hash_bytes()),
+            protocol_version: peer.protocol_version,
+            network_name: peer.network_name,
+            is_compressed: peer.is_compressed,
+            consensus_public_key: peer.consensus_public_key,
+            is_active_validator: peer.is_active_validator,
+            last_seen: peer.traffic.last_received,
+            traffic: peer
+                .traffic
+                .kinds
+                .into_iter()
+                .map(MessageKindTraffic::from)
+                .collect(),
+        }
+    }
+}
+
+/// A connection, or an address the node is trying to connect to.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Connection {
+    /// Whether the node dialed the peer or the peer dialed the node.
+    pub direction: ConnectionDirection,
+    /// The address dialed for outgoing connections, the peer's address for incoming ones.
+    pub address: String,
+    /// The state of the connection.
+    pub state: ConnectionState,
+    /// The number of failed attempts to connect, while connecting or waiting.
+    pub failures_so_far: Option<u8>,
+    /// The peer, if the connection is established.
+    pub peer: Option<ConnectedPeer>,
+}
+
+impl From<ConnectionDetails> for Connection {
+    fn from(connection: ConnectionDetails) -> Self {
+        let direction = match connection.direction {
+            Direction::Incoming => ConnectionDirection::Incoming,
+            Direction::Outgoing => ConnectionDirection::Outgoing,
+        };
+        let (state, failures_so_far) = match connection.state {
+            small_network::ConnectionState::Connecting { failures_so_far } => {
+                (ConnectionState::Connecting, Some(failures_so_far))
+            }
+            small_network::ConnectionState::Waiting { failures_so_far } => {
+                (ConnectionState::Waiting, Some(failures_so_far))
+            }
+            small_network::ConnectionState::Connected => (ConnectionState::Connected, None),
+            small_network::ConnectionState::Blocked => (ConnectionState::Blocked, None),
+            small_network::ConnectionState::Loopback => (ConnectionState::Loopback, None),
+        };
+        Connection {
+            direction,
+            address: connection.addr.to_string(),
+            state,
+            failures_so_far,
+            peer: connection.peer.map(ConnectedPeer::from),
+        }
+    }
+}
+
+/// Result for "admin_get_connections" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetConnectionsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The node's connections.
+    pub connections: Vec<Connection>,
+}
+
+impl DocExample for GetConnectionsResult {
+    fn doc_example() -> &'static Self {
+        &*GET_CONNECTIONS_RESULT
+    }
+}
+
+/// "admin_get_connections" RPC.
+pub struct GetConnections {}
+
+impl RpcWithoutParams for GetConnections {
+    const METHOD: &'static str = "admin_get_connections";
+    type ResponseResult = GetConnectionsResult;
+}
+
+impl RpcWithoutParamsExt for GetConnections {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let connections = effect_builder.get_connections::<NodeId>().await;
+            let result = Self::ResponseResult {
+                api_version,
+                connections: connections.into_iter().map(Connection::from).collect(),
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// A banned peer.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BannedPeer {
+    /// The hex-encoded node ID.
+    pub node_id: String,
+    /// The time at which the ban expires.
+    pub banned_until: Timestamp,
+    /// Why the peer was banned.
+    pub reason: String,
+    /// The network addresses at which the peer was found.
+    pub addresses: Vec<String>,
+}
+
+impl From<Ban> for BannedPeer {
+    fn from(ban: Ban) -> Self {
+        BannedPeer {
+            node_id: hex::encode(ban.peer_id.hash_bytes()),
+            banned_until: ban.until,
+            reason: ban.reason,
+            addresses: ban.addresses.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+/// Result for "admin_get_banned_peers" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBannedPeersResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The currently banned peers.
+    pub banned_peers: Vec<BannedPeer>,
+}
+
+impl DocExample for GetBannedPeersResult {
+    fn doc_example() -> &'static Self {
+        &*GET_BANNED_PEERS_RESULT
+    }
+}
+
+/// "admin_get_banned_peers" RPC.
+pub struct GetBannedPeers {}
+
+impl RpcWithoutParams for GetBannedPeers {
+    const METHOD: &'static str = "admin_get_banned_peers";
+    type ResponseResult = GetBannedPeersResult;
+}
+
+impl RpcWithoutParamsExt for GetBannedPeers {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let bans = effect_builder.get_banned_peers::<NodeId>().await;
+            let result = Self::ResponseResult {
+                api_version,
+                banned_peers: bans.into_iter().map(BannedPeer::from).collect(),
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "admin_ban_peer" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BanPeerParams {
+    /// The hex-encoded node ID, as returned by "admin_get_banned_peers".
+    pub node_id: String,
+    /// How long to ban the peer for.  If not given, the node's configured ban duration is used.
+    #[serde(default)]
+    pub duration: Option<TimeDiff>,
+}
+
+impl DocExample for BanPeerParams {
+    fn doc_example() -> &'static Self {
+        &*BAN_PEER_PARAMS
+    }
+}
+
+/// Result for "admin_ban_peer" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BanPeerResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+}
+
+impl DocExample for BanPeerResult {
+    fn doc_example() -> &'static Self {
+        &*BAN_PEER_RESULT
+    }
+}
+
+/// "admin_ban_peer" RPC.
+pub struct BanPeer {}
+
+impl RpcWithParams for BanPeer {
+    const METHOD: &'static str = "admin_ban_peer";
+    type RequestParams = BanPeerParams;
+    type ResponseResult = BanPeerResult;
+}
+
+impl RpcWithParamsExt for BanPeer {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let node_id = match params
+                .node_id
+                .parse::<NodeId>()
+                .map_err(|error| format!("failed to parse node id: {}", error))
+            {
+                Ok(node_id) => node_id,
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::ParseNodeId as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            effect_builder.ban_peer(node_id, params.duration).await;
+            Ok(response_builder.success(Self::ResponseResult { api_version })?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "admin_unban_peer" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UnbanPeerParams {
+    /// The hex-encoded node ID, as returned by "admin_get_banned_peers".
+    pub node_id: String,
+}
+
+impl DocExample for UnbanPeerParams {
+    fn doc_example() -> &'static Self {
+        &*UNBAN_PEER_PARAMS
+    }
+}
+
+/// Result for "admin_unban_peer" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UnbanPeerResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// Whether the peer was banned.
+    pub was_banned: bool,
+}
+
+impl DocExample for UnbanPeerResult {
+    fn doc_example() -> &'static Self {
+        &*UNBAN_PEER_RESULT
+    }
+}
+
+/// "admin_unban_peer" RPC.
+pub struct UnbanPeer {}
+
+impl RpcWithParams for UnbanPeer {
+    const METHOD: &'static str = "admin_unban_peer";
+    type RequestParams = UnbanPeerParams;
+    type ResponseResult = UnbanPeerResult;
+}
+
+impl RpcWithParamsExt for UnbanPeer {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let node_id = match params
+                .node_id
+                .parse::<NodeId>()
+                .map_err(|error| format!("failed to parse node id: {}", error))
+            {
+                Ok(node_id) => node_id,
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::ParseNodeId as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let was_banned = effect_builder.unban_peer(node_id).await;
+            let result = Self::ResponseResult {
+                api_version,
+                was_banned,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
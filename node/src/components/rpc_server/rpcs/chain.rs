@@ -17,21 +17,28 @@ use tracing::info;
 use warp_json_rpc::Builder;
 
 use casper_hashing::Digest;
-use casper_types::{Key, ProtocolVersion, Transfer};
+use casper_types::{
+    system::auction::{InactivityRecord, SeigniorageAllocation},
+    AsymmetricType, EraId, Key, ProtocolVersion, PublicKey, Transfer, U512,
+};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithOptionalParams, RpcWithOptionalParamsExt,
+    RpcWithParams, RpcWithParamsExt,
 };
 use crate::{
     effect::EffectBuilder,
     reactor::QueueKind,
     rpcs::common,
-    types::{Block, BlockHash, BlockSignatures, Item, JsonBlock},
+    types::{json_compatibility::StoredValue, Block, BlockHash, BlockSignatures, Item, JsonBlock},
 };
 pub use era_summary::EraSummary;
 use era_summary::ERA_SUMMARY;
 
+/// The maximum number of eras a single "chain_get_reward_history" request may span.
+pub const MAX_REWARD_HISTORY_ERA_RANGE: u64 = 100;
+
 static GET_BLOCK_PARAMS: Lazy<GetBlockParams> = Lazy::new(|| GetBlockParams {
     block_identifier: BlockIdentifier::Hash(Block::doc_example().id()),
 });
@@ -65,6 +72,55 @@ static GET_ERA_INFO_RESULT: Lazy<GetEraInfoResult> = Lazy::new(|| GetEraInfoResu
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     era_summary: Some(ERA_SUMMARY.clone()),
 });
+static GET_INACTIVITY_PARAMS: Lazy<GetInactivityParams> = Lazy::new(|| GetInactivityParams {
+    block_identifier: BlockIdentifier::Hash(Block::doc_example().id()),
+});
+static GET_INACTIVITY_RESULT: Lazy<GetInactivityResult> = Lazy::new(|| GetInactivityResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    era_inactivity: Some(EraInactivity {
+        block_hash: Block::doc_example().id(),
+        era_id: EraId::from(42),
+        inactivity: vec![InactivityRecord::new(
+            PublicKey::from_hex(
+                "012a1732addc639ea43a89e25d3ad912e40232156dcaa4b9edfc709f43d2fb0876",
+            )
+            .unwrap(),
+            20,
+            2,
+            U512::from(500),
+            false,
+        )],
+    }),
+});
+static GET_REWARD_HISTORY_PARAMS: Lazy<GetRewardHistoryParams> =
+    Lazy::new(|| GetRewardHistoryParams {
+        public_key: PublicKey::from_hex(
+            "012a1732addc639ea43a89e25d3ad912e40232156dcaa4b9edfc709f43d2fb0876",
+        )
+        .unwrap(),
+        from_era_id: EraId::from(41),
+        to_era_id: EraId::from(42),
+    });
+static GET_REWARD_HISTORY_RESULT: Lazy<GetRewardHistoryResult> = Lazy::new(|| {
+    let delegator_public_key =
+        PublicKey::from_hex("01e1b46a25baa8a5c28beb3c9cfb79b572effa04076f00befa57eb70b016153f18")
+            .unwrap();
+    let validator_public_key = GET_REWARD_HISTORY_PARAMS.public_key.clone();
+    GetRewardHistoryResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        rewards: vec![EraRewards {
+            era_id: EraId::from(42),
+            seigniorage_allocations: vec![
+                SeigniorageAllocation::delegator(
+                    delegator_public_key,
+                    validator_public_key.clone(),
+                    U512::from(1000),
+                ),
+                SeigniorageAllocation::validator(validator_public_key, U512::from(2000)),
+            ],
+        }],
+    }
+});
 
 /// Identifier for possible ways to retrieve a block.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, JsonSchema)]
@@ -365,74 +421,291 @@ impl RpcWithOptionalParamsExt for GetEraInfoBySwitchBlock {
         async move {
             // TODO: decide if/how to handle era id
             let maybe_block_id = maybe_params.map(|params| params.block_identifier);
-            let maybe_block = match get_block(maybe_block_id, effect_builder).await {
-                Ok(maybe_block) => maybe_block,
-                Err(error) => return Ok(response_builder.error(error)?),
+            let (block, stored_value, proof_bytes) =
+                match get_era_info_by_switch_block(maybe_block_id, effect_builder).await {
+                    Ok(Some(era_info)) => era_info,
+                    Ok(None) => {
+                        return Ok(response_builder.success(Self::ResponseResult {
+                            api_version,
+                            era_summary: None,
+                        })?)
+                    }
+                    Err(error) => return Ok(response_builder.error(error)?),
+                };
+
+            let result = Self::ResponseResult {
+                api_version,
+                era_summary: Some(EraSummary {
+                    block_hash: *block.hash(),
+                    era_id: block.header().era_id(),
+                    stored_value,
+                    state_root_hash: *block.state_root_hash(),
+                    merkle_proof: hex::encode(proof_bytes),
+                }),
             };
 
-            let block = match maybe_block {
-                Some(block) => block,
-                None => {
-                    return Ok(response_builder.success(Self::ResponseResult {
-                        api_version,
-                        era_summary: None,
-                    })?)
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "chain_get_inactivity_by_switch_block" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetInactivityParams {
+    /// The block identifier.
+    pub block_identifier: BlockIdentifier,
+}
+
+impl DocExample for GetInactivityParams {
+    fn doc_example() -> &'static Self {
+        &*GET_INACTIVITY_PARAMS
+    }
+}
+
+/// The validators which participated too little in an era.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EraInactivity {
+    /// The hash of the era's switch block.
+    pub block_hash: BlockHash,
+    /// The era id.
+    pub era_id: EraId,
+    /// The inactive validators, with the penalties applied to them.
+    pub inactivity: Vec<InactivityRecord>,
+}
+
+/// Result for "chain_get_inactivity_by_switch_block" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetInactivityResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The inactivity in the era ended by the block, if it is a switch block.
+    pub era_inactivity: Option<EraInactivity>,
+}
+
+impl DocExample for GetInactivityResult {
+    fn doc_example() -> &'static Self {
+        &*GET_INACTIVITY_RESULT
+    }
+}
+
+/// "chain_get_inactivity_by_switch_block" RPC.
+pub struct GetInactivityBySwitchBlock {}
+
+impl RpcWithOptionalParams for GetInactivityBySwitchBlock {
+    const METHOD: &'static str = "chain_get_inactivity_by_switch_block";
+    type OptionalRequestParams = GetInactivityParams;
+    type ResponseResult = GetInactivityResult;
+}
+
+impl RpcWithOptionalParamsExt for GetInactivityBySwitchBlock {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        maybe_params: Option<Self::OptionalRequestParams>,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let maybe_block_id = maybe_params.map(|params| params.block_identifier);
+            let (block, stored_value) =
+                match get_era_info_by_switch_block(maybe_block_id, effect_builder).await {
+                    Ok(Some((block, stored_value, _))) => (block, stored_value),
+                    Ok(None) => {
+                        return Ok(response_builder.success(Self::ResponseResult {
+                            api_version,
+                            era_inactivity: None,
+                        })?)
+                    }
+                    Err(error) => return Ok(response_builder.error(error)?),
+                };
+
+            let era_info = match stored_value {
+                StoredValue::EraInfo(era_info) => era_info,
+                _ => {
+                    let error_msg = "era info query returned unexpected stored value".to_string();
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailed as i64,
+                        error_msg,
+                    ))?);
                 }
             };
 
-            let era_id = match block.header().era_end() {
-                Some(_) => block.header().era_id(),
-                None => {
-                    return Ok(response_builder.success(Self::ResponseResult {
-                        api_version,
-                        era_summary: None,
-                    })?)
-                }
+            let result = Self::ResponseResult {
+                api_version,
+                era_inactivity: Some(EraInactivity {
+                    block_hash: *block.hash(),
+                    era_id: block.header().era_id(),
+                    inactivity: era_info.inactivity().clone(),
+                }),
             };
 
-            let state_root_hash = block.state_root_hash().to_owned();
-            let base_key = Key::EraInfo(era_id);
-            let path = Vec::new();
-            let query_result = effect_builder
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "chain_get_reward_history" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetRewardHistoryParams {
+    /// The public key of the validator or delegator.
+    pub public_key: PublicKey,
+    /// The first era of the range, inclusive.
+    pub from_era_id: EraId,
+    /// The last era of the range, inclusive.  The range may span at most 100 eras.
+    pub to_era_id: EraId,
+}
+
+impl DocExample for GetRewardHistoryParams {
+    fn doc_example() -> &'static Self {
+        &*GET_REWARD_HISTORY_PARAMS
+    }
+}
+
+/// The rewards paid to a single recipient at the end of an era.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EraRewards {
+    /// The era id.
+    pub era_id: EraId,
+    /// The seigniorage allocations made to the recipient in this era.
+    pub seigniorage_allocations: Vec<SeigniorageAllocation>,
+}
+
+/// Result for "chain_get_reward_history" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetRewardHistoryResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The rewards paid in each era of the range in which the recipient received any, in
+    /// ascending era order.
+    pub rewards: Vec<EraRewards>,
+}
+
+impl DocExample for GetRewardHistoryResult {
+    fn doc_example() -> &'static Self {
+        &*GET_REWARD_HISTORY_RESULT
+    }
+}
+
+/// "chain_get_reward_history" RPC.
+pub struct GetRewardHistory {}
+
+impl RpcWithParams for GetRewardHistory {
+    const METHOD: &'static str = "chain_get_reward_history";
+    type RequestParams = GetRewardHistoryParams;
+    type ResponseResult = GetRewardHistoryResult;
+}
+
+impl RpcWithParamsExt for GetRewardHistory {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            if params.from_era_id > params.to_era_id {
+                let error_msg = format!(
+                    "invalid era range: {} is after {}",
+                    params.from_era_id, params.to_era_id
+                );
+                info!("{}", error_msg);
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::InvalidEraRange as i64,
+                    error_msg,
+                ))?);
+            }
+
+            let era_count =
+                (params.to_era_id.value() - params.from_era_id.value()).saturating_add(1);
+            if era_count > MAX_REWARD_HISTORY_ERA_RANGE {
+                let error_msg = format!(
+                    "invalid era range: {} to {} spans {} eras, more than the maximum of {}",
+                    params.from_era_id, params.to_era_id, era_count, MAX_REWARD_HISTORY_ERA_RANGE
+                );
+                info!("{}", error_msg);
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::InvalidEraRange as i64,
+                    error_msg,
+                ))?);
+            }
+
+            let reward_history = effect_builder
                 .make_request(
-                    |responder| RpcRequest::QueryGlobalState {
-                        state_root_hash,
-                        base_key,
-                        path,
+                    |responder| RpcRequest::GetRewardHistory {
+                        public_key: Box::new(params.public_key),
+                        from_era_id: params.from_era_id,
+                        to_era_id: params.to_era_id,
                         responder,
                     },
                     QueueKind::Api,
                 )
                 .await;
 
-            let (stored_value, proof_bytes) = match common::extract_query_result(query_result) {
-                Ok(tuple) => tuple,
-                Err((error_code, error_msg)) => {
-                    info!("{}", error_msg);
-                    return Ok(response_builder
-                        .error(warp_json_rpc::Error::custom(error_code as i64, error_msg))?);
-                }
-            };
-
-            let block_hash = block.hash().to_owned();
+            let rewards = reward_history
+                .into_iter()
+                .map(|(era_id, seigniorage_allocations)| EraRewards {
+                    era_id,
+                    seigniorage_allocations,
+                })
+                .collect();
 
             let result = Self::ResponseResult {
                 api_version,
-                era_summary: Some(EraSummary {
-                    block_hash,
-                    era_id,
-                    stored_value,
-                    state_root_hash,
-                    merkle_proof: hex::encode(proof_bytes),
-                }),
+                rewards,
             };
-
             Ok(response_builder.success(result)?)
         }
         .boxed()
     }
 }
 
+/// Queries the era info written at the end of the era of the given switch block, returning the
+/// block, the era info and the merkle proof of the query.
+///
+/// Returns `None` if there is no such block, or if it is not a switch block.
+async fn get_era_info_by_switch_block<REv: ReactorEventT>(
+    maybe_id: Option<BlockIdentifier>,
+    effect_builder: EffectBuilder<REv>,
+) -> Result<Option<(Block, StoredValue, Vec<u8>)>, warp_json_rpc::Error> {
+    let block = match get_block(maybe_id, effect_builder).await? {
+        Some(block) if block.header().is_switch_block() => block,
+        _ => return Ok(None),
+    };
+
+    let state_root_hash = *block.state_root_hash();
+    let base_key = Key::EraInfo(block.header().era_id());
+    let path = Vec::new();
+    let query_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::QueryGlobalState {
+                state_root_hash,
+                base_key,
+                path,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+
+    match common::extract_query_result(query_result) {
+        Ok((stored_value, proof_bytes)) => Ok(Some((block, stored_value, proof_bytes))),
+        Err((error_code, error_msg)) => {
+            info!("{}", error_msg);
+            Err(warp_json_rpc::Error::custom(error_code as i64, error_msg))
+        }
+    }
+}
+
 async fn get_block<REv: ReactorEventT>(
     maybe_id: Option<BlockIdentifier>,
     effect_builder: EffectBuilder<REv>,
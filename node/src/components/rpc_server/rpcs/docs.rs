@@ -29,8 +29,8 @@ use super::{
 use crate::{
     effect::EffectBuilder,
     rpcs::{
-        chain::GetEraInfoBySwitchBlock,
-        info::GetValidatorChanges,
+        chain::{GetEraInfoBySwitchBlock, GetInactivityBySwitchBlock, GetRewardHistory},
+        info::{GetConsensusStatus, GetValidatorChanges},
         state::{GetAccountInfo, GetDictionaryItem, QueryGlobalState},
     },
 };
@@ -85,6 +85,9 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     schema.push_without_params::<GetStatus>("returns the current status of the node");
     schema
         .push_without_params::<GetValidatorChanges>("returns status changes of active validators");
+    schema.push_without_params::<GetConsensusStatus>(
+        "returns the current era, faulty validators and how recent blocks were finalized",
+    );
     schema.push_with_optional_params::<GetBlock>("returns a Block from the network");
     schema.push_with_optional_params::<GetBlockTransfers>(
         "returns all transfers for a Block from the network",
@@ -97,6 +100,12 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     schema.push_with_optional_params::<GetEraInfoBySwitchBlock>(
         "returns an EraInfo from the network",
     );
+    schema.push_with_optional_params::<GetInactivityBySwitchBlock>(
+        "returns the validators penalized for inactivity in the era ended by a switch block",
+    );
+    schema.push_with_params::<GetRewardHistory>(
+        "returns the rewards paid to a validator or delegator over a range of eras",
+    );
     schema.push_with_optional_params::<GetAuctionInfo>(
         "returns the bids and validators as of either a specific block (by height or hash), or the most recently added block",
     );
@@ -537,6 +546,13 @@ mod tests {
         assert!(incorrect_optional_params.is_empty())
     }
 
+    #[test]
+    fn check_chain_get_inactivity_by_switch_block_required_fields() {
+        let incorrect_optional_params =
+            check_optional_params_fields::<GetInactivityBySwitchBlock>();
+        assert!(incorrect_optional_params.is_empty())
+    }
+
     #[test]
     fn check_state_get_auction_info_required_fields() {
         let incorrect_optional_params = check_optional_params_fields::<GetAuctionInfo>();
@@ -22,7 +22,7 @@ use super::{
     RpcWithoutParamsExt,
 };
 use crate::{
-    components::consensus::ValidatorChange,
+    components::consensus::{ConsensusStatus, FinalizedBlockStatus, ValidatorChange},
     crypto::AsymmetricKeyExt,
     effect::EffectBuilder,
     reactor::QueueKind,
@@ -53,6 +53,30 @@ static GET_VALIDATOR_CHANGES_RESULT: Lazy<GetValidatorChangesResult> = Lazy::new
         changes,
     }
 });
+static GET_CONSENSUS_STATUS_RESULT: Lazy<GetConsensusStatusResult> = Lazy::new(|| {
+    let block = Block::doc_example();
+    let public_key = PublicKey::doc_example().clone();
+    let finalized_block = FinalizedBlockStatus {
+        block_hash: Some(*block.hash()),
+        era_id: block.header().era_id(),
+        height: block.header().height(),
+        proposer: block.body().proposer().clone(),
+        proposed_at: block.header().timestamp(),
+        finalized_at: block.header().timestamp() + "3s".parse().unwrap(),
+        summit: vec![public_key],
+    };
+    GetConsensusStatusResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        status: Some(ConsensusStatus {
+            era_id: block.header().era_id(),
+            round_exponent: Some(16),
+            equivocators: vec![],
+            faulty_validators: vec![],
+            last_finalized_block: Some(finalized_block.clone()),
+            recent_finalized_blocks: vec![finalized_block],
+        }),
+    }
+});
 
 /// Params for "info_get_deploy" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -342,3 +366,46 @@ impl RpcWithoutParamsExt for GetValidatorChanges {
         .boxed()
     }
 }
+
+/// Result for the "info_get_consensus_status" RPC.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetConsensusStatusResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The consensus status, or `None` if the node is not running consensus yet.
+    pub status: Option<ConsensusStatus>,
+}
+
+impl DocExample for GetConsensusStatusResult {
+    fn doc_example() -> &'static Self {
+        &*GET_CONSENSUS_STATUS_RESULT
+    }
+}
+
+/// "info_get_consensus_status" RPC.
+pub struct GetConsensusStatus {}
+
+impl RpcWithoutParams for GetConsensusStatus {
+    const METHOD: &'static str = "info_get_consensus_status";
+    type ResponseResult = GetConsensusStatusResult;
+}
+
+impl RpcWithoutParamsExt for GetConsensusStatus {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let status = effect_builder.get_consensus_full_status().await;
+            let result = Self::ResponseResult {
+                api_version,
+                status,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
@@ -23,8 +23,12 @@
 //! Nodes gossip their public listening addresses periodically, and will try to establish and
 //! maintain an outgoing connection to any new address learned.
 
+mod allowlist;
+mod capture;
 mod chain_info;
+mod compression;
 mod config;
+mod connection_info;
 mod counting_format;
 mod error;
 mod event;
@@ -33,6 +37,8 @@ mod limiter;
 mod message;
 mod message_pack_format;
 mod outgoing;
+mod reputation;
+mod send_queue;
 mod symmetry;
 pub(crate) mod tasks;
 #[cfg(test)]
@@ -42,7 +48,7 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet},
     convert::Infallible,
     fmt::{self, Debug, Display, Formatter},
-    io,
+    fs, io,
     net::{SocketAddr, TcpListener},
     result,
     sync::{Arc, Weak},
@@ -58,45 +64,49 @@ use prometheus::Registry;
 use rand::seq::{IteratorRandom, SliceRandom};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::{
-    net::TcpStream,
-    sync::{
-        mpsc::{self, UnboundedSender},
-        watch,
-    },
-    task::JoinHandle,
-};
+use tokio::{net::TcpStream, sync::watch, task::JoinHandle};
 use tokio_openssl::SslStream;
 use tokio_util::codec::LengthDelimitedCodec;
 use tracing::{debug, error, info, trace, warn, Instrument, Span};
 
+pub use self::{allowlist::AccessMode, send_queue::QueueWeights};
 use self::{
-    counting_format::{ConnectionId, CountingFormat, Role},
+    allowlist::{AccessControl, AllowlistFile},
+    capture::CaptureWriter,
+    compression::{CompressingFormat, CompressionHandle, CompressionSettings},
+    connection_info::ConnectionInfo,
+    counting_format::{ConnectionId, ConnectionStats, CountingFormat, Role},
     error::{ConnectionError, Result},
     event::{IncomingConnection, OutgoingConnection},
     limiter::Limiter,
     message::ConsensusKeyPair,
     message_pack_format::MessagePackFormat,
-    outgoing::{DialOutcome, DialRequest, OutgoingConfig, OutgoingManager},
+    outgoing::{DialOutcome, DialRequest, OutgoingConfig, OutgoingManager, OutgoingState},
+    reputation::Reputation,
+    send_queue::QueueSender,
     symmetry::ConnectionSymmetry,
     tasks::NetworkContext,
 };
 pub(crate) use self::{
+    capture::{read_capture, CaptureRecord, Direction},
+    connection_info::{ConnectionDetails, ConnectionState, PeerDetails},
+    counting_format::KindTraffic,
     event::Event,
     gossiped_address::GossipedAddress,
     message::{Message, MessageKind, Payload, PayloadWeights},
+    reputation::{Ban, Offense},
 };
 use super::{consensus, contract_runtime::ContractRuntimeAnnouncement};
 use crate::{
     components::{networking_metrics::NetworkingMetrics, Component},
     effect::{
         announcements::{BlocklistAnnouncement, NetworkAnnouncement},
-        requests::{NetworkInfoRequest, NetworkRequest, StorageRequest},
+        requests::{GossipTarget, NetworkInfoRequest, NetworkRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects,
     },
     reactor::{EventQueueHandle, Finalize, ReactorEvent},
     tls::{self, TlsCert, ValidationError},
-    types::NodeId,
+    types::{NodeId, Timestamp},
     utils::{self, display_error, WithDir},
     NodeRng,
 };
@@ -124,9 +134,11 @@ const OUTGOING_MANAGER_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(Clone, DataSize, Debug)]
 pub(crate) struct OutgoingHandle<P> {
-    #[data_size(skip)] // Unfortunately, there is no way to inspect an `UnboundedSender`.
-    sender: UnboundedSender<Arc<Message<P>>>,
+    #[data_size(skip)] // Unfortunately, there is no way to inspect a `QueueSender`.
+    sender: QueueSender<P>,
     peer_addr: SocketAddr,
+    #[data_size(skip)]
+    info: ConnectionInfo,
 }
 
 impl<P> Display for OutgoingHandle<P> {
@@ -180,6 +192,28 @@ where
 
     /// The era that is considered the active era by the small network component.
     active_era: EraId,
+
+    /// The peers' scores and the ban list.
+    reputation: Reputation,
+
+    /// The allowlist file, if this is a private network.
+    #[data_size(skip)]
+    allowlist_file: Option<AllowlistFile>,
+
+    /// The consensus keys that connected peers presented in the handshake.
+    peer_consensus_keys: HashMap<NodeId, PublicKey>,
+
+    /// The validators of the active era.
+    active_validators: HashSet<PublicKey>,
+
+    /// The established incoming connections, by the address the peer dialed us from. Dropping the
+    /// sender closes the connection.
+    #[data_size(skip)]
+    incoming_connections: HashMap<SocketAddr, (NodeId, ConnectionInfo, watch::Sender<()>)>,
+
+    /// The file incoming and outgoing messages are recorded to, if capturing is enabled.
+    #[data_size(skip)]
+    capture: Option<CaptureWriter>,
 }
 
 impl<REv, P> SmallNetwork<REv, P>
@@ -189,15 +223,82 @@ where
         ReactorEvent + From<Event<P>> + From<NetworkAnnouncement<NodeId, P>> + From<StorageRequest>,
 {
     /// Creates a new small network component instance.
+    ///
+    /// `max_batch_size` is the maximum number of requests we accept from a peer in a single batch,
+    /// as announced in our handshake.
     #[allow(clippy::type_complexity)]
     pub(crate) fn new<C: Into<ChainInfo>>(
         event_queue: EventQueueHandle<REv>,
-        cfg: Config,
+        cfg: WithDir<Config>,
         consensus_cfg: Option<WithDir<&consensus::Config>>,
         registry: &Registry,
         small_network_identity: SmallNetworkIdentity,
         chain_info_source: C,
+        max_batch_size: u16,
     ) -> Result<(SmallNetwork<REv, P>, Effects<Event<P>>)> {
+        let ban_list_path = cfg
+            .value()
+            .ban_list_path
+            .clone()
+            .map(|path| cfg.with_dir(path));
+        let allowlist_path = cfg
+            .value()
+            .allowlist_path
+            .clone()
+            .map(|path| cfg.with_dir(path));
+        let compression_dictionary_path = cfg
+            .value()
+            .compression_dictionary_path
+            .clone()
+            .map(|path| cfg.with_dir(path));
+        let capture_path = cfg
+            .value()
+            .capture_path
+            .clone()
+            .map(|path| cfg.with_dir(path));
+        let (_, cfg) = cfg.into_parts();
+        let reputation = Reputation::new(cfg.ban_duration, ban_list_path, Timestamp::now())
+            .map_err(Error::LoadBanList)?;
+
+        let access_control = AccessControl::new(cfg.access_mode);
+        // The validators are only known once we have joined the network, so we need at least one
+        // peer on the allowlist to connect to.
+        let allowlist_file = match allowlist_path {
+            Some(path) if !access_control.is_open() => {
+                let mut allowlist_file = AllowlistFile::new(path);
+                let node_ids = allowlist_file
+                    .reload_if_modified()
+                    .map_err(Error::LoadAllowlist)?
+                    .unwrap_or_default();
+                if node_ids.is_empty() {
+                    return Err(Error::EmptyAllowlist);
+                }
+                info!(count = node_ids.len(), "loaded allowlist");
+                access_control.set_node_ids(node_ids);
+                Some(allowlist_file)
+            }
+            None if !access_control.is_open() => return Err(Error::EmptyAllowlist),
+            _ => None,
+        };
+
+        let compression = if cfg.enable_compression {
+            let dictionary = compression_dictionary_path
+                .map(fs::read)
+                .transpose()
+                .map_err(Error::ReadCompressionDictionary)?;
+            Some(CompressionSettings::new(cfg.compression_level, dictionary))
+        } else {
+            None
+        };
+
+        let capture = capture_path
+            .map(|path| {
+                warn!(path = %path.display(), "capturing all network traffic");
+                CaptureWriter::create(path)
+            })
+            .transpose()
+            .map_err(Error::CreateCapture)?;
+
         let mut known_addresses = HashSet::new();
         for address in &cfg.known_addresses {
             match utils::resolve_address(address) {
@@ -267,11 +368,11 @@ where
         let consensus_keys = consensus_cfg
             .map(|cfg| {
                 let root = cfg.dir();
-                cfg.value().load_keys(root)
+                cfg.value().load_signer(root)
             })
             .transpose()
             .map_err(Error::LoadConsensusKeys)?
-            .map(|(secret_key, public_key)| ConsensusKeyPair::new(secret_key, public_key));
+            .map(ConsensusKeyPair::new);
 
         let context = Arc::new(NetworkContext {
             event_queue,
@@ -283,6 +384,9 @@ where
             public_addr,
             consensus_keys,
             payload_weights: cfg.estimator_weights.clone(),
+            access_control,
+            compression,
+            max_batch_size,
         });
 
         // Run the server task.
@@ -314,6 +418,12 @@ where
             incoming_limiter,
             // We start with an empty set of validators for era 0 and expect to be updated.
             active_era: EraId::new(0),
+            reputation,
+            allowlist_file,
+            peer_consensus_keys: HashMap::new(),
+            active_validators: HashSet::new(),
+            incoming_connections: HashMap::new(),
+            capture,
         };
 
         let effect_builder = EffectBuilder::new(event_queue);
@@ -351,18 +461,20 @@ where
         }
     }
 
-    /// Queues a message to `count` random nodes on the network.
+    /// Queues a message to `count` random nodes of the `target` kind on the network.
     fn gossip_message(
         &self,
         rng: &mut NodeRng,
         msg: Arc<Message<P>>,
         count: usize,
+        target: GossipTarget,
         exclude: HashSet<NodeId>,
     ) -> HashSet<NodeId> {
         let peer_ids = self
             .outgoing_manager
             .connected_peers()
             .filter(|peer_id| !exclude.contains(peer_id))
+            .filter(|peer_id| self.is_gossip_target(*peer_id, target))
             .choose_multiple(rng, count);
 
         if peer_ids.len() != count {
@@ -384,15 +496,38 @@ where
         peer_ids.into_iter().collect()
     }
 
+    /// Returns whether the peer is of the `target` kind for gossiping.
+    fn is_gossip_target(&self, peer_id: NodeId, target: GossipTarget) -> bool {
+        let supports_reconciliation = self
+            .outgoing_manager
+            .get_route(peer_id)
+            .map_or(false, |connection| {
+                connection.info.supports_gossip_reconciliation
+            });
+        match target {
+            GossipTarget::All => true,
+            GossipTarget::Reconciling => supports_reconciliation,
+            GossipTarget::NonReconciling => !supports_reconciliation,
+        }
+    }
+
     /// Queues a message to be sent to a specific node.
     fn send_message(&self, dest: NodeId, msg: Arc<Message<P>>) {
         // Try to send the message.
         if let Some(connection) = self.outgoing_manager.get_route(dest) {
+            if let Message::Payload(ref payload) = *msg {
+                // Batches the peer doesn't accept are sent as several smaller ones.
+                if let Some(payloads) = payload.split_batch(connection.info.max_batch_size) {
+                    for payload in payloads {
+                        self.send_message(dest, Arc::new(Message::Payload(payload)));
+                    }
+                    return;
+                }
+                self.capture(Direction::Outgoing, dest, payload);
+            }
             if let Err(msg) = connection.sender.send(msg) {
                 // We lost the connection, but that fact has not reached us yet.
                 warn!(our_id=%self.context.our_id, %dest, ?msg, "dropped outgoing message, lost connection");
-            } else {
-                self.net_metrics.queued_messages.inc();
             }
         } else {
             // We are not connected, so the reconnection is likely already in progress.
@@ -441,10 +576,24 @@ where
                 public_addr,
                 peer_id,
                 peer_consensus_public_key,
+                info,
                 stream,
             } => {
+                if self.reputation.is_banned(&peer_id) {
+                    // Dropping the stream closes the connection.
+                    info!(%public_addr, "refusing incoming connection from banned peer");
+                    return Effects::new();
+                }
+
                 info!(%public_addr, "new incoming connection established");
 
+                if let Some(ref public_key) = peer_consensus_public_key {
+                    self.peer_consensus_keys.insert(peer_id, public_key.clone());
+                }
+                let (close_sender, close_receiver) = watch::channel(());
+                self.incoming_connections
+                    .insert(peer_addr, (peer_id, info, close_sender));
+
                 // Learn the address the peer gave us.
                 let dial_requests =
                     self.outgoing_manager
@@ -470,6 +619,7 @@ where
                         self.incoming_limiter
                             .create_handle(peer_id, peer_consensus_public_key),
                         self.shutdown_receiver.clone(),
+                        close_receiver,
                         peer_id,
                         span.clone(),
                     )
@@ -510,6 +660,8 @@ where
                 .entry(*peer_id)
                 .or_default()
                 .remove_incoming(peer_addr, Instant::now());
+            self.incoming_connections.remove(&peer_addr);
+            self.forget_consensus_key_if_disconnected(&peer_id);
 
             Effects::new()
         })
@@ -532,7 +684,7 @@ where
             | ConnectionError::InvalidConsensusCertificate(_) => false,
 
             // Definitely something we want to avoid.
-            ConnectionError::WrongNetwork(_) => true,
+            ConnectionError::WrongNetwork(_) | ConnectionError::PeerNotAllowed => true,
         }
     }
 
@@ -587,12 +739,31 @@ where
                 peer_addr,
                 peer_id,
                 peer_consensus_public_key,
+                info,
                 sink,
             } => {
+                if self.reputation.is_banned(&peer_id) {
+                    // Blocking the address first makes the outgoing manager discard the
+                    // connection, and dropping the sink closes it.
+                    info!("connected to banned peer, blocking its address");
+                    self.reputation.add_banned_address(&peer_id, peer_addr);
+                    let request = self.outgoing_manager.block_addr(peer_addr, now);
+                    return self.process_dial_requests(request);
+                }
+
                 info!("new outgoing connection established");
 
-                let (sender, receiver) = mpsc::unbounded_channel();
-                let handle = OutgoingHandle { sender, peer_addr };
+                if let Some(ref public_key) = peer_consensus_public_key {
+                    self.peer_consensus_keys.insert(peer_id, public_key.clone());
+                }
+
+                let (sender, receiver) =
+                    send_queue::channel(&self.cfg.queue_weights, &self.net_metrics);
+                let handle = OutgoingHandle {
+                    sender,
+                    peer_addr,
+                    info,
+                };
 
                 let request = self
                     .outgoing_manager
@@ -620,7 +791,6 @@ where
                         sink,
                         self.outgoing_limiter
                             .create_handle(peer_id, peer_consensus_public_key),
-                        self.net_metrics.queued_messages.clone(),
                     )
                     .instrument(span)
                     .event(move |_| Event::OutgoingDropped {
@@ -647,7 +817,122 @@ where
             .entry(peer_id)
             .or_default()
             .unmark_outgoing(Instant::now());
+        self.forget_consensus_key_if_disconnected(&peer_id);
+
+        self.process_dial_requests(requests)
+    }
 
+    /// Removes the consensus key the peer presented in the handshake, once we have no connections
+    /// to it left.
+    fn forget_consensus_key_if_disconnected(&mut self, peer_id: &NodeId) {
+        if matches!(
+            self.connection_symmetries.get(peer_id),
+            None | Some(ConnectionSymmetry::Gone)
+        ) {
+            self.peer_consensus_keys.remove(peer_id);
+        }
+    }
+
+    /// Disconnects from a banned peer, closes its incoming connections and blocks all addresses at
+    /// which we found it.
+    fn disconnect_banned_peer(&mut self, peer_id: NodeId) -> Effects<Event<P>> {
+        if let Some(addr) = self.outgoing_manager.get_addr(peer_id) {
+            self.reputation.add_banned_address(&peer_id, addr);
+        }
+        let banned_incoming = self
+            .incoming_connections
+            .iter()
+            .filter(|(_, (incoming_peer_id, _, _))| *incoming_peer_id == peer_id)
+            .map(|(&addr, _)| addr)
+            .collect::<Vec<_>>();
+        for addr in banned_incoming {
+            info!(%addr, %peer_id, "closing incoming connection of banned peer");
+            // Dropping the sender makes the message reader close the connection.
+            self.incoming_connections.remove(&addr);
+        }
+        self.peer_consensus_keys.remove(&peer_id);
+
+        let now = Instant::now();
+        let addresses = self
+            .reputation
+            .bans()
+            .filter(|ban| ban.peer_id == peer_id)
+            .flat_map(|ban| ban.addresses.clone())
+            .collect::<Vec<_>>();
+        let requests = addresses
+            .into_iter()
+            .filter_map(|addr| self.outgoing_manager.block_addr(addr, now))
+            .collect::<Vec<_>>();
+        self.process_dial_requests(requests)
+    }
+
+    /// Unblocks the addresses of a peer whose ban was lifted or expired.
+    fn redeem_banned_addresses(&mut self, ban: Ban) -> Effects<Event<P>> {
+        let now = Instant::now();
+        let requests = ban
+            .addresses
+            .into_iter()
+            .filter_map(|addr| self.outgoing_manager.redeem_addr(addr, now))
+            .collect::<Vec<_>>();
+        self.process_dial_requests(requests)
+    }
+
+    /// Returns `true` if the peer is allowed to connect.
+    fn is_allowed(&self, peer_id: &NodeId) -> bool {
+        self.context
+            .access_control
+            .is_allowed(peer_id, self.peer_consensus_keys.get(peer_id))
+    }
+
+    /// Re-reads the allowlist if the file changed.
+    fn reload_allowlist(&mut self) -> Effects<Event<P>> {
+        let allowlist_file = match self.allowlist_file.as_mut() {
+            Some(allowlist_file) => allowlist_file,
+            None => return Effects::new(),
+        };
+        match allowlist_file.reload_if_modified() {
+            Ok(Some(node_ids)) => {
+                info!(count = node_ids.len(), "reloaded allowlist");
+                self.context.access_control.set_node_ids(node_ids);
+                self.disconnect_disallowed_peers()
+            }
+            Ok(None) => Effects::new(),
+            Err(err) => {
+                warn!(%err, "failed to reload allowlist, keeping the previous one");
+                Effects::new()
+            }
+        }
+    }
+
+    /// Closes the connections to peers that are no longer allowed to connect, after the allowlist
+    /// or the set of validators changed, and blocks the addresses we dialed them at.
+    fn disconnect_disallowed_peers(&mut self) -> Effects<Event<P>> {
+        let disallowed_incoming = self
+            .incoming_connections
+            .iter()
+            .filter(|(_, (peer_id, _, _))| !self.is_allowed(peer_id))
+            .map(|(&addr, _)| addr)
+            .collect::<Vec<_>>();
+        for addr in disallowed_incoming {
+            info!(%addr, "peer is no longer allowed to connect, closing incoming connection");
+            // Dropping the sender makes the message reader close the connection.
+            self.incoming_connections.remove(&addr);
+        }
+
+        let now = Instant::now();
+        let addresses = self
+            .outgoing_manager
+            .connected_peers()
+            .filter(|peer_id| !self.is_allowed(peer_id))
+            .filter_map(|peer_id| self.outgoing_manager.get_addr(peer_id))
+            .collect::<Vec<_>>();
+        let requests = addresses
+            .into_iter()
+            .filter_map(|addr| {
+                info!(%addr, "peer is no longer allowed to connect, blocking its address");
+                self.outgoing_manager.block_addr(addr, now)
+            })
+            .collect::<Vec<_>>();
         self.process_dial_requests(requests)
     }
 
@@ -669,6 +954,13 @@ where
         for request in requests.into_iter() {
             trace!(%request, "processing dial request");
             match request {
+                // Known and gossiped addresses at which we found a banned peer are blocked instead
+                // of dialed, and so are blocked addresses that are due to be retried.
+                DialRequest::Dial { addr, span } if self.reputation.is_banned_address(&addr) => {
+                    span.in_scope(|| debug!("not dialing address of banned peer"));
+                    let request = self.outgoing_manager.block_addr(addr, Instant::now());
+                    effects.extend(self.process_dial_requests(request));
+                }
                 DialRequest::Dial { addr, span } => effects.extend(
                     tasks::connect_outgoing(self.context.clone(), addr)
                         .instrument(span.clone())
@@ -708,12 +1000,32 @@ where
                 warn!("received unexpected handshake");
                 Effects::new()
             }
-            Message::Payload(payload) => effect_builder
-                .announce_message_received(peer_id, payload)
-                .ignore(),
+            Message::Payload(_) if self.reputation.is_banned(&peer_id) => {
+                debug!("dropping message from banned peer");
+                Effects::new()
+            }
+            Message::Payload(_) if !self.is_allowed(&peer_id) => {
+                debug!("dropping message from peer that is no longer allowed to connect");
+                Effects::new()
+            }
+            Message::Payload(payload) => {
+                self.capture(Direction::Incoming, peer_id, &payload);
+                effect_builder
+                    .announce_message_received(peer_id, payload)
+                    .ignore()
+            }
         })
     }
 
+    /// Records a message to the capture file, if capturing is enabled.
+    fn capture(&self, direction: Direction, peer_id: NodeId, payload: &P) {
+        if let Some(capture) = self.capture.as_ref() {
+            if let Err(err) = capture.record(direction, peer_id, payload) {
+                error!(%err, "failed to capture message, no longer capturing");
+            }
+        }
+    }
+
     /// Emits an announcement that a connection has been completed.
     fn connection_completed(
         &self,
@@ -748,6 +1060,55 @@ where
         ret
     }
 
+    /// Returns the details of all outgoing connections, including those not established, and of all
+    /// incoming connections.
+    pub(crate) fn connections(&self) -> Vec<ConnectionDetails> {
+        let outgoing = self
+            .outgoing_manager
+            .outgoing_states()
+            .map(|(addr, state)| {
+                let peer = match state {
+                    OutgoingState::Connected { peer_id, handle } => {
+                        Some(self.peer_details(*peer_id, &handle.info))
+                    }
+                    _ => None,
+                };
+                ConnectionDetails {
+                    direction: Direction::Outgoing,
+                    addr,
+                    state: ConnectionState::from(state),
+                    peer,
+                }
+            });
+        let incoming = self
+            .incoming_connections
+            .iter()
+            .map(|(&addr, (peer_id, info, _))| ConnectionDetails {
+                direction: Direction::Incoming,
+                addr,
+                state: ConnectionState::Connected,
+                peer: Some(self.peer_details(*peer_id, info)),
+            });
+        outgoing.chain(incoming).collect()
+    }
+
+    /// Returns the details of the peer at the other end of an established connection.
+    fn peer_details(&self, peer_id: NodeId, info: &ConnectionInfo) -> PeerDetails {
+        let consensus_public_key = self.peer_consensus_keys.get(&peer_id).cloned();
+        let is_active_validator = consensus_public_key.as_ref().map_or(false, |public_key| {
+            self.active_validators.contains(public_key)
+        });
+        PeerDetails {
+            peer_id,
+            protocol_version: info.protocol_version,
+            network_name: info.network_name.clone(),
+            is_compressed: info.is_compressed,
+            consensus_public_key,
+            is_active_validator,
+            traffic: info.stats.snapshot(),
+        }
+    }
+
     /// Returns the node id of this network node.
     #[cfg(test)]
     pub(crate) fn node_id(&self) -> NodeId {
@@ -840,6 +1201,7 @@ where
                     NetworkRequest::Gossip {
                         payload,
                         count,
+                        target,
                         exclude,
                         responder,
                     } => {
@@ -848,6 +1210,7 @@ where
                             rng,
                             Arc::new(Message::Payload(*payload)),
                             count,
+                            target,
                             exclude,
                         );
                         responder.respond(sent_to).ignore()
@@ -859,10 +1222,48 @@ where
                     responder.respond(self.peers()).ignore()
                 }
                 NetworkInfoRequest::GetPeersInRandomOrder { responder } => {
-                    let mut peers_vec: Vec<NodeId> = self.peers().keys().cloned().collect();
+                    let now = Timestamp::now();
+                    let mut peers_vec: Vec<NodeId> = self
+                        .peers()
+                        .keys()
+                        .filter(|peer_id| !self.reputation.is_banned(peer_id))
+                        .cloned()
+                        .collect();
                     peers_vec.shuffle(rng);
+                    // Prefer peers that haven't committed any offenses recently, e.g. timed out.
+                    peers_vec.sort_by_key(|peer_id| self.reputation.penalty(peer_id, now));
                     responder.respond(peers_vec).ignore()
                 }
+                NetworkInfoRequest::GetConnections { responder } => {
+                    responder.respond(self.connections()).ignore()
+                }
+                NetworkInfoRequest::GetBannedPeers { responder } => responder
+                    .respond(self.reputation.bans().cloned().collect())
+                    .ignore(),
+                NetworkInfoRequest::BanPeer {
+                    peer_id,
+                    duration,
+                    responder,
+                } => {
+                    warn!(%peer_id, "banning peer as requested");
+                    let reason = "banned by operator".to_string();
+                    self.reputation
+                        .ban(*peer_id, duration, reason, Timestamp::now());
+                    let mut effects = self.disconnect_banned_peer(*peer_id);
+                    effects.extend(responder.respond(()).ignore());
+                    effects
+                }
+                NetworkInfoRequest::UnbanPeer { peer_id, responder } => {
+                    match self.reputation.unban(&peer_id) {
+                        Some(ban) => {
+                            info!(%peer_id, "lifting ban as requested");
+                            let mut effects = self.redeem_banned_addresses(ban);
+                            effects.extend(responder.respond(true).ignore());
+                            effects
+                        }
+                        None => responder.respond(false).ignore(),
+                    }
+                }
             },
             Event::PeerAddressReceived(gossiped_address) => {
                 let requests = self.outgoing_manager.learn_addr(
@@ -872,16 +1273,18 @@ where
                 );
                 self.process_dial_requests(requests)
             }
-            Event::BlocklistAnnouncement(BlocklistAnnouncement::OffenseCommitted(peer_id)) => {
-                // TODO: We do not have a proper by-node-ID blocklist, but rather only block the
-                // current outgoing address of a peer.
-                warn!(%peer_id, "adding peer to blocklist after transgression");
-
-                if let Some(addr) = self.outgoing_manager.get_addr(*peer_id) {
-                    let requests = self.outgoing_manager.block_addr(addr, Instant::now());
-                    self.process_dial_requests(requests)
+            Event::BlocklistAnnouncement(BlocklistAnnouncement::OffenseCommitted {
+                offender,
+                offense,
+            }) => {
+                if self
+                    .reputation
+                    .record_offense(*offender, offense, Timestamp::now())
+                {
+                    warn!(peer_id = %offender, %offense, "banning peer after transgression");
+                    self.disconnect_banned_peer(*offender)
                 } else {
-                    // Peer got away with it, no longer an outgoing connection.
+                    debug!(peer_id = %offender, %offense, "peer committed offense");
                     Effects::new()
                 }
             }
@@ -924,10 +1327,18 @@ where
                         upcoming = upcoming_validators.len(),
                         "updating active and upcoming validators"
                     );
+                    self.context.access_control.set_validators(
+                        active_validators
+                            .union(&upcoming_validators)
+                            .cloned()
+                            .collect(),
+                    );
+                    self.active_validators = active_validators.clone();
                     self.incoming_limiter
                         .update_validators(active_validators.clone(), upcoming_validators.clone());
                     self.outgoing_limiter
                         .update_validators(active_validators, upcoming_validators);
+                    return self.disconnect_disallowed_peers();
                 }
 
                 Effects::new()
@@ -947,6 +1358,15 @@ where
                 let requests = self.outgoing_manager.perform_housekeeping(now);
                 let mut effects = self.process_dial_requests(requests);
 
+                for ban in self.reputation.expire_bans(Timestamp::now()) {
+                    info!(peer_id = %ban.peer_id, "ban expired");
+                    effects.extend(self.redeem_banned_addresses(ban));
+                }
+                effects.extend(self.reload_allowlist());
+                if let Some(Err(err)) = self.capture.as_ref().map(CaptureWriter::flush) {
+                    error!(%err, "failed to flush capture file, no longer capturing");
+                }
+
                 effects.extend(
                     effect_builder
                         .set_timeout(OUTGOING_MANAGER_SWEEP_INTERVAL)
@@ -1016,16 +1436,20 @@ pub(crate) type FramedTransport<P> = tokio_serde::Framed<
     tokio_util::codec::Framed<Transport, LengthDelimitedCodec>,
     Message<P>,
     Arc<Message<P>>,
-    CountingFormat<MessagePackFormat>,
+    CountingFormat<CompressingFormat<MessagePackFormat>>,
 >;
 
 /// Constructs a new framed transport on a stream.
+///
+/// Messages are compressed once `compression` is set after negotiating the handshake.
 fn framed<P>(
     metrics: Weak<NetworkingMetrics>,
+    stats: Arc<ConnectionStats>,
     connection_id: ConnectionId,
     stream: Transport,
     role: Role,
     maximum_net_message_size: u32,
+    compression: CompressionHandle,
 ) -> FramedTransport<P>
 where
     for<'de> P: Serialize + Deserialize<'de>,
@@ -1040,7 +1464,18 @@ where
 
     tokio_serde::Framed::new(
         length_delimited,
-        CountingFormat::new(metrics, connection_id, role, MessagePackFormat),
+        CountingFormat::new(
+            metrics.clone(),
+            stats,
+            connection_id,
+            role,
+            CompressingFormat::new(
+                metrics,
+                compression,
+                maximum_net_message_size,
+                MessagePackFormat,
+            ),
+        ),
     )
 }
 
@@ -0,0 +1,257 @@
+//! Access control for private networks.
+//!
+//! In a private network, only peers whose node ID is on the allowlist may connect to us, and we
+//! only keep connections we dialed to such peers. Optionally, the validators of the current and the
+//! upcoming era are admitted as well; they prove their identity with the consensus certificate in
+//! the handshake. Any other peer is refused during the handshake.
+//!
+//! The allowlist is read from a file with one hex-encoded node ID per line. It must not be empty,
+//! since the validators are only known after joining the network. The file is re-read whenever it
+//! is modified, so peers can be added or removed without restarting the node; connections to peers
+//! which are no longer allowed are closed.
+
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::RwLock,
+    time::SystemTime,
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use casper_types::PublicKey;
+
+use crate::types::{NodeId, ParseNodeIdError};
+
+/// An error reading the allowlist.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// The allowlist file could not be read.
+    #[error("failed to read allowlist {path}: {error}")]
+    Io {
+        /// The path of the allowlist file.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// A line of the allowlist file is not a valid node ID.
+    #[error("failed to parse line {line} of allowlist {path}: {error}")]
+    Parse {
+        /// The path of the allowlist file.
+        path: PathBuf,
+        /// The number of the offending line, starting at 1.
+        line: usize,
+        /// The underlying error.
+        error: ParseNodeIdError,
+    },
+}
+
+/// Which peers are allowed to connect.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessMode {
+    /// Any peer on the same network may connect.
+    Open,
+    /// Only peers on the allowlist may connect.
+    Allowlist,
+    /// Only the validators of the current and the upcoming era and the peers on the allowlist may
+    /// connect.
+    ValidatorsAndAllowlist,
+}
+
+/// The peers allowed to connect, shared between the component and its connection tasks.
+#[derive(Debug)]
+pub(crate) struct AccessControl {
+    /// Which peers are allowed to connect.
+    mode: AccessMode,
+    /// The node IDs on the allowlist.
+    node_ids: RwLock<HashSet<NodeId>>,
+    /// The validators of the current and the upcoming era.
+    validators: RwLock<HashSet<PublicKey>>,
+}
+
+impl AccessControl {
+    /// Creates a new access control with an empty allowlist and no known validators.
+    pub(super) fn new(mode: AccessMode) -> Self {
+        AccessControl {
+            mode,
+            node_ids: Default::default(),
+            validators: Default::default(),
+        }
+    }
+
+    /// Returns `true` if any peer may connect.
+    pub(super) fn is_open(&self) -> bool {
+        self.mode == AccessMode::Open
+    }
+
+    /// Returns `false` if the peer is not allowed to connect, whatever consensus key it presents
+    /// in the handshake.
+    pub(super) fn may_handshake(&self, peer_id: &NodeId) -> bool {
+        match self.mode {
+            AccessMode::Open | AccessMode::ValidatorsAndAllowlist => true,
+            AccessMode::Allowlist => self.is_allowlisted(peer_id),
+        }
+    }
+
+    /// Returns `true` if the peer, having presented the given consensus key in the handshake, is
+    /// allowed to connect.
+    pub(super) fn is_allowed(&self, peer_id: &NodeId, consensus_key: Option<&PublicKey>) -> bool {
+        match self.mode {
+            AccessMode::Open => true,
+            AccessMode::Allowlist => self.is_allowlisted(peer_id),
+            AccessMode::ValidatorsAndAllowlist => {
+                self.is_allowlisted(peer_id)
+                    || consensus_key.map_or(false, |key| {
+                        self.validators
+                            .read()
+                            .expect("validators lock poisoned")
+                            .contains(key)
+                    })
+            }
+        }
+    }
+
+    /// Replaces the allowlist.
+    pub(super) fn set_node_ids(&self, node_ids: HashSet<NodeId>) {
+        *self.node_ids.write().expect("allowlist lock poisoned") = node_ids;
+    }
+
+    /// Replaces the set of validators that may connect.
+    pub(super) fn set_validators(&self, validators: HashSet<PublicKey>) {
+        *self.validators.write().expect("validators lock poisoned") = validators;
+    }
+
+    fn is_allowlisted(&self, peer_id: &NodeId) -> bool {
+        self.node_ids
+            .read()
+            .expect("allowlist lock poisoned")
+            .contains(peer_id)
+    }
+}
+
+/// The allowlist file, and when it was last read.
+#[derive(Debug)]
+pub(super) struct AllowlistFile {
+    /// The path of the allowlist file.
+    path: PathBuf,
+    /// The modification time of the file when it was last read, or `Some(None)` if it could not be
+    /// accessed. `None` if it was never read.
+    modified: Option<Option<SystemTime>>,
+}
+
+impl AllowlistFile {
+    /// Creates a new allowlist file, which is read on the first call to `reload_if_modified`.
+    pub(super) fn new(path: PathBuf) -> Self {
+        AllowlistFile {
+            path,
+            modified: None,
+        }
+    }
+
+    /// Reads the allowlist if the file was modified since it was last read.
+    ///
+    /// Returns `None` if the file is unchanged. Errors are only returned once, until the file is
+    /// modified again.
+    pub(super) fn reload_if_modified(&mut self) -> Result<Option<HashSet<NodeId>>, Error> {
+        let modified = fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if self.modified == Some(modified) {
+            return Ok(None);
+        }
+        self.modified = Some(modified);
+        read_allowlist(&self.path).map(Some)
+    }
+}
+
+/// Reads the node IDs from the allowlist file. Empty lines and lines starting with `#` are ignored.
+fn read_allowlist(path: &Path) -> Result<HashSet<NodeId>, Error> {
+    let contents = fs::read_to_string(path).map_err(|error| Error::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, node_id)| {
+            node_id.parse().map_err(|error| Error::Parse {
+                path: path.to_path_buf(),
+                line,
+                error,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crypto::AsymmetricKeyExt, testing::TestRng};
+
+    #[test]
+    fn access_modes() {
+        let mut rng = TestRng::new();
+        let listed = NodeId::random(&mut rng);
+        let other = NodeId::random(&mut rng);
+        let validator = PublicKey::random(&mut rng);
+
+        for mode in [
+            AccessMode::Open,
+            AccessMode::Allowlist,
+            AccessMode::ValidatorsAndAllowlist,
+        ] {
+            let access = AccessControl::new(mode);
+            access.set_node_ids(vec![listed].into_iter().collect());
+            access.set_validators(vec![validator.clone()].into_iter().collect());
+
+            assert!(access.may_handshake(&listed));
+            assert!(access.is_allowed(&listed, None));
+            assert_eq!(mode != AccessMode::Allowlist, access.may_handshake(&other));
+            assert_eq!(mode == AccessMode::Open, access.is_allowed(&other, None));
+            assert_eq!(
+                mode != AccessMode::Allowlist,
+                access.is_allowed(&other, Some(&validator))
+            );
+        }
+    }
+
+    #[test]
+    fn allowlist_file_is_reloaded() {
+        let mut rng = TestRng::new();
+        let node_ids: Vec<NodeId> = (0..3).map(|_| NodeId::random(&mut rng)).collect();
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("allowlist");
+        let contents = format!(
+            "# Consortium members\n{}\n\n  {}\n",
+            hex::encode(node_ids[0].hash_bytes()),
+            hex::encode(node_ids[1].hash_bytes())
+        );
+        fs::write(&path, contents).unwrap();
+
+        let mut file = AllowlistFile::new(path.clone());
+        let expected: HashSet<_> = node_ids[..2].iter().copied().collect();
+        assert_eq!(Some(expected), file.reload_if_modified().unwrap());
+        assert_eq!(None, file.reload_if_modified().unwrap());
+
+        // Pretend the file was last read a long time ago, as the modification time may not change
+        // on file systems with a coarse resolution.
+        fs::write(&path, hex::encode(node_ids[2].hash_bytes())).unwrap();
+        file.modified = Some(Some(SystemTime::UNIX_EPOCH));
+        let expected: HashSet<_> = node_ids[2..].iter().copied().collect();
+        assert_eq!(Some(expected), file.reload_if_modified().unwrap());
+
+        fs::write(&path, "not a node id").unwrap();
+        file.modified = None;
+        assert!(matches!(
+            file.reload_if_modified(),
+            Err(Error::Parse { line: 1, .. })
+        ));
+        assert_eq!(None, file.reload_if_modified().unwrap());
+    }
+}
@@ -0,0 +1,255 @@
+//! Capture of network traffic for offline replay.
+//!
+//! If enabled, every payload received from or queued for a peer is appended to a capture file,
+//! together with the time and the peer's node ID. A capture can be fed back into a test reactor to
+//! reproduce the sequence of messages a node received.
+//!
+//! The file starts with a magic number, followed by the records. Each record is encoded as
+//! MessagePack and prefixed with its length as a big-endian `u32`.
+
+use std::{
+    convert::TryFrom,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::types::{NodeId, Timestamp};
+
+/// The magic number at the start of every capture file.
+const CAPTURE_MAGIC: &[u8] = b"CSPRCAP1";
+
+/// An error writing or reading a capture file.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// The capture file could not be accessed.
+    #[error("failed to access capture file {}: {error}", .path.display())]
+    Io {
+        /// The path of the capture file.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// The file is not a capture file.
+    #[error("{} is not a capture file", .0.display())]
+    NotACapture(PathBuf),
+    /// A record could not be encoded.
+    #[error("failed to encode capture record: {0}")]
+    Encode(#[from] rmp_serde::encode::Error),
+    /// A record could not be decoded.
+    #[error("failed to decode capture record {index}: {error}")]
+    Decode {
+        /// The index of the offending record, starting at 0.
+        index: usize,
+        /// The underlying error.
+        error: rmp_serde::decode::Error,
+    },
+}
+
+/// Whether a message was received or sent.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) enum Direction {
+    /// The message was received from the peer.
+    Incoming,
+    /// The message was queued to be sent to the peer.
+    Outgoing,
+}
+
+/// A captured message.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CaptureRecord<P> {
+    /// When the message was received or queued.
+    pub(crate) timestamp: Timestamp,
+    /// The peer the message was received from or sent to.
+    pub(crate) peer_id: NodeId,
+    /// Whether the message was received or sent.
+    pub(crate) direction: Direction,
+    /// The message.
+    pub(crate) payload: P,
+}
+
+/// Writes captured messages to a file.
+#[derive(Debug)]
+pub(super) struct CaptureWriter {
+    /// The path of the capture file.
+    path: PathBuf,
+    /// The buffered file; `None` after writing failed.
+    writer: Mutex<Option<BufWriter<File>>>,
+}
+
+impl CaptureWriter {
+    /// Creates a new capture file, replacing any existing file at the path.
+    pub(super) fn create(path: PathBuf) -> Result<Self, Error> {
+        let mut writer = File::create(&path)
+            .map(BufWriter::new)
+            .map_err(|error| Error::Io {
+                path: path.clone(),
+                error,
+            })?;
+        writer.write_all(CAPTURE_MAGIC).map_err(|error| Error::Io {
+            path: path.clone(),
+            error,
+        })?;
+        Ok(CaptureWriter {
+            path,
+            writer: Mutex::new(Some(writer)),
+        })
+    }
+
+    /// Appends a message to the capture.
+    ///
+    /// Capturing stops on the first error, which is returned once.
+    pub(super) fn record<P: Serialize>(
+        &self,
+        direction: Direction,
+        peer_id: NodeId,
+        payload: &P,
+    ) -> Result<(), Error> {
+        let record = CaptureRecord {
+            timestamp: Timestamp::now(),
+            peer_id,
+            direction,
+            payload,
+        };
+        let encoded = rmp_serde::to_vec(&record)?;
+        let length = u32::try_from(encoded.len()).unwrap_or(u32::MAX);
+        self.write(|writer| {
+            writer.write_all(&length.to_be_bytes())?;
+            writer.write_all(&encoded)
+        })
+    }
+
+    /// Writes buffered records to the file.
+    pub(super) fn flush(&self) -> Result<(), Error> {
+        self.write(|writer| writer.flush())
+    }
+
+    fn write<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+    {
+        let mut guard = self.writer.lock().expect("capture lock poisoned");
+        let writer = match guard.as_mut() {
+            Some(writer) => writer,
+            None => return Ok(()),
+        };
+        f(writer).map_err(|error| {
+            *guard = None;
+            Error::Io {
+                path: self.path.clone(),
+                error,
+            }
+        })
+    }
+}
+
+/// Reads all records from a capture file.
+///
+/// A truncated last record, as left behind by a node that was killed while writing, is ignored.
+pub(crate) fn read_capture<P: DeserializeOwned>(
+    path: &Path,
+) -> Result<Vec<CaptureRecord<P>>, Error> {
+    let io_error = |error| Error::Io {
+        path: path.to_path_buf(),
+        error,
+    };
+    let mut reader = File::open(path).map(BufReader::new).map_err(io_error)?;
+
+    let mut magic = [0; CAPTURE_MAGIC.len()];
+    if reader.read_exact(&mut magic).is_err() || magic != CAPTURE_MAGIC {
+        return Err(Error::NotACapture(path.to_path_buf()));
+    }
+
+    let mut records = Vec::new();
+    loop {
+        let mut length = [0; 4];
+        match reader.read_exact(&mut length) {
+            Ok(()) => (),
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(error) => return Err(io_error(error)),
+        }
+        let mut encoded = vec![0; u32::from_be_bytes(length) as usize];
+        match reader.read_exact(&mut encoded) {
+            Ok(()) => (),
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(error) => return Err(io_error(error)),
+        }
+        let record = rmp_serde::from_read_ref(&encoded).map_err(|error| Error::Decode {
+            index: records.len(),
+            error,
+        })?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::OpenOptions;
+
+    use super::*;
+    use crate::{protocol::Message, testing::TestRng, types::Deploy};
+
+    #[test]
+    fn capture_roundtrip() {
+        let mut rng = TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("capture");
+
+        let peers = [NodeId::random(&mut rng), NodeId::random(&mut rng)];
+        let messages: Vec<Message> = (0..3)
+            .map(|_| Message::new_get_request::<Deploy>(Deploy::random(&mut rng).id()).unwrap())
+            .collect();
+
+        let writer = CaptureWriter::create(path.clone()).unwrap();
+        writer
+            .record(Direction::Incoming, peers[0], &messages[0])
+            .unwrap();
+        writer
+            .record(Direction::Outgoing, peers[1], &messages[1])
+            .unwrap();
+        writer
+            .record(Direction::Incoming, peers[1], &messages[2])
+            .unwrap();
+        writer.flush().unwrap();
+
+        // Simulate a node killed while writing a record.
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&[0, 0, 1, 0, 42])
+            .unwrap();
+
+        let records: Vec<CaptureRecord<Message>> = read_capture(&path).unwrap();
+        assert_eq!(3, records.len());
+        let summary: Vec<_> = records
+            .iter()
+            .map(|record| (record.direction, record.peer_id, record.payload.to_string()))
+            .collect();
+        assert_eq!(
+            vec![
+                (Direction::Incoming, peers[0], messages[0].to_string()),
+                (Direction::Outgoing, peers[1], messages[1].to_string()),
+                (Direction::Incoming, peers[1], messages[2].to_string()),
+            ],
+            summary
+        );
+        assert!(records[0].timestamp <= records[2].timestamp);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("not-a-capture");
+        std::fs::write(&path, b"hello").unwrap();
+        assert!(matches!(
+            read_capture::<Message>(&path),
+            Err(Error::NotACapture(_))
+        ));
+    }
+}
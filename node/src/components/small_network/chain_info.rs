@@ -8,11 +8,7 @@ use std::net::SocketAddr;
 use casper_types::ProtocolVersion;
 use datasize::DataSize;
 
-use super::{
-    counting_format::ConnectionId,
-    message::{ConsensusCertificate, ConsensusKeyPair},
-    Message,
-};
+use super::{compression::CompressionSupport, message::ConsensusCertificate, Message};
 use crate::types::Chainspec;
 
 /// Data retained from the chainspec by the small networking component.
@@ -44,15 +40,18 @@ impl ChainInfo {
     pub(super) fn create_handshake<P>(
         &self,
         public_addr: SocketAddr,
-        consensus_keys: Option<&ConsensusKeyPair>,
-        connection_id: ConnectionId,
+        consensus_certificate: Option<ConsensusCertificate>,
+        compression: Option<CompressionSupport>,
+        max_batch_size: u16,
     ) -> Message<P> {
         Message::Handshake {
             network_name: self.network_name.clone(),
             public_addr,
             protocol_version: self.protocol_version,
-            consensus_certificate: consensus_keys
-                .map(|key_pair| ConsensusCertificate::create(connection_id, key_pair)),
+            consensus_certificate,
+            compression,
+            gossip_reconciliation: true,
+            max_batch_size,
         }
     }
 }
@@ -0,0 +1,376 @@
+//! Compression of network messages.
+//!
+//! Nodes announce the compression they support in their handshake. If both sides of a connection
+//! support it, every message after the handshake is prefixed with a tag byte telling whether the
+//! rest of the frame is zstd-compressed or plain MessagePack. Nodes that predate compression ignore
+//! the announcement and keep exchanging plain MessagePack, so mixed-version networks keep working.
+//!
+//! Optionally, nodes can share a zstd dictionary, which considerably improves the compression of
+//! small and medium-sized messages like deploys. The dictionary is only used if both sides of a
+//! connection have the same one.
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    io::{self, Read},
+    pin::Pin,
+    sync::{Arc, Weak},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use once_cell::sync::OnceCell;
+use pin_project::pin_project;
+use serde::{Deserialize, Serialize};
+use tokio_serde::{Deserializer, Serializer};
+use tracing::warn;
+
+use casper_hashing::Digest;
+
+use super::{Message, Payload};
+use crate::components::networking_metrics::NetworkingMetrics;
+
+/// Messages smaller than this many bytes are sent uncompressed, as compressing them does not pay
+/// off.
+const MIN_COMPRESSED_SIZE: usize = 256;
+
+/// Tag of a frame containing plain MessagePack.
+const TAG_UNCOMPRESSED: u8 = 0;
+
+/// Tag of a frame containing zstd-compressed MessagePack.
+const TAG_ZSTD: u8 = 1;
+
+/// The compression a node supports, as announced in its handshake.
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub(crate) struct CompressionSupport {
+    /// The hash of the node's zstd dictionary, if it has one.
+    dictionary_hash: Option<Digest>,
+}
+
+/// A zstd dictionary shared by the nodes of a network.
+#[derive(Debug)]
+struct Dictionary {
+    bytes: Vec<u8>,
+    hash: Digest,
+}
+
+/// This node's compression settings.
+#[derive(Debug)]
+pub(super) struct CompressionSettings {
+    /// The zstd compression level.
+    level: i32,
+    /// The shared dictionary, if configured.
+    dictionary: Option<Arc<Dictionary>>,
+}
+
+impl CompressionSettings {
+    /// Creates new compression settings with the given zstd level and optional dictionary.
+    pub(super) fn new(level: i32, dictionary: Option<Vec<u8>>) -> Self {
+        let dictionary = dictionary.map(|bytes| {
+            let hash = Digest::hash(&bytes);
+            Arc::new(Dictionary { bytes, hash })
+        });
+        CompressionSettings { level, dictionary }
+    }
+
+    /// Returns the compression support to announce in our handshake.
+    pub(super) fn support(&self) -> CompressionSupport {
+        CompressionSupport {
+            dictionary_hash: self.dictionary.as_ref().map(|dictionary| dictionary.hash),
+        }
+    }
+
+    /// Returns the compression to use on a connection to a peer with the given support.
+    pub(super) fn negotiate(&self, remote: &CompressionSupport) -> Compression {
+        let dictionary = self
+            .dictionary
+            .as_ref()
+            .filter(|dictionary| remote.dictionary_hash == Some(dictionary.hash))
+            .cloned();
+        if self.dictionary.is_some() && dictionary.is_none() {
+            warn!("peer does not share our compression dictionary, compressing without it");
+        }
+        Compression {
+            level: self.level,
+            dictionary,
+        }
+    }
+}
+
+/// The compression negotiated for a connection.
+#[derive(Debug)]
+pub(super) struct Compression {
+    /// The zstd compression level.
+    level: i32,
+    /// The dictionary, if both sides share it.
+    dictionary: Option<Arc<Dictionary>>,
+}
+
+impl Compression {
+    /// Returns the dictionary bytes, or an empty slice if there is no shared dictionary.
+    fn dictionary(&self) -> &[u8] {
+        self.dictionary
+            .as_ref()
+            .map_or(&[], |dictionary| dictionary.bytes.as_slice())
+    }
+}
+
+/// The compression of a connection, set once the handshake has been negotiated.
+pub(super) type CompressionHandle = Arc<OnceCell<Compression>>;
+
+/// A serializer/deserializer wrapper compressing network messages once compression has been
+/// negotiated, and recording the compressed and uncompressed size of outgoing messages.
+#[pin_project]
+pub struct CompressingFormat<F> {
+    /// The actual serializer performing the work.
+    #[pin]
+    inner: F,
+    /// The compression of the connection; unset until the handshake has been negotiated.
+    compression: CompressionHandle,
+    /// The compressor, created on first use.
+    compressor: Option<zstd::block::Compressor>,
+    /// The maximum size of a decompressed message.
+    maximum_message_size: usize,
+    /// Metrics to update.
+    metrics: Weak<NetworkingMetrics>,
+}
+
+impl<F> CompressingFormat<F> {
+    /// Creates a new compressing formatter.
+    pub(super) fn new(
+        metrics: Weak<NetworkingMetrics>,
+        compression: CompressionHandle,
+        maximum_message_size: u32,
+        inner: F,
+    ) -> Self {
+        CompressingFormat {
+            inner,
+            compression,
+            compressor: None,
+            maximum_message_size: maximum_message_size as usize,
+            metrics,
+        }
+    }
+}
+
+impl<F: Debug> Debug for CompressingFormat<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompressingFormat")
+            .field("inner", &self.inner)
+            .field("compression", &self.compression)
+            .field("maximum_message_size", &self.maximum_message_size)
+            .finish()
+    }
+}
+
+impl<F, P> Serializer<Arc<Message<P>>> for CompressingFormat<F>
+where
+    F: Serializer<Arc<Message<P>>, Error = io::Error>,
+    P: Payload,
+{
+    type Error = io::Error;
+
+    fn serialize(self: Pin<&mut Self>, item: &Arc<Message<P>>) -> Result<Bytes, Self::Error> {
+        let this = self.project();
+        let serialized = F::serialize(this.inner, item)?;
+        let uncompressed_size = serialized.len();
+
+        let compression = match this.compression.get() {
+            Some(compression) => compression,
+            None => {
+                NetworkingMetrics::record_compression_out(
+                    this.metrics,
+                    item.classify(),
+                    uncompressed_size as u64,
+                    uncompressed_size as u64,
+                );
+                return Ok(serialized);
+            }
+        };
+
+        let compressed = if uncompressed_size >= MIN_COMPRESSED_SIZE {
+            let compressor = this.compressor.get_or_insert_with(|| {
+                zstd::block::Compressor::with_dict(compression.dictionary().to_vec())
+            });
+            Some(compressor.compress(&serialized, compression.level)?)
+                .filter(|compressed| compressed.len() < uncompressed_size)
+        } else {
+            None
+        };
+
+        let frame = match compressed {
+            Some(compressed) => tagged(TAG_ZSTD, &compressed),
+            None => tagged(TAG_UNCOMPRESSED, &serialized),
+        };
+        NetworkingMetrics::record_compression_out(
+            this.metrics,
+            item.classify(),
+            uncompressed_size as u64,
+            frame.len() as u64 - 1,
+        );
+        Ok(frame)
+    }
+}
+
+impl<F, P> Deserializer<Message<P>> for CompressingFormat<F>
+where
+    F: Deserializer<Message<P>, Error = io::Error>,
+    P: Payload,
+{
+    type Error = io::Error;
+
+    fn deserialize(self: Pin<&mut Self>, src: &BytesMut) -> Result<Message<P>, Self::Error> {
+        let this = self.project();
+        let compression = match this.compression.get() {
+            Some(compression) => compression,
+            None => return F::deserialize(this.inner, src),
+        };
+
+        let (tag, body) = src
+            .split_first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "empty frame"))?;
+        let decompressed = match *tag {
+            TAG_UNCOMPRESSED => BytesMut::from(body),
+            TAG_ZSTD => decompress(body, compression.dictionary(), *this.maximum_message_size)?,
+            tag => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unknown compression tag {}", tag),
+                ))
+            }
+        };
+        F::deserialize(this.inner, &decompressed)
+    }
+}
+
+/// Returns a frame consisting of the tag followed by the body.
+fn tagged(tag: u8, body: &[u8]) -> Bytes {
+    let mut frame = BytesMut::with_capacity(body.len() + 1);
+    frame.put_u8(tag);
+    frame.put_slice(body);
+    frame.freeze()
+}
+
+/// Decompresses a zstd frame, failing if the result would exceed the maximum message size.
+fn decompress(data: &[u8], dictionary: &[u8], maximum_size: usize) -> io::Result<BytesMut> {
+    let decoder = zstd::stream::read::Decoder::with_dictionary(data, dictionary)?;
+    let mut decompressed = Vec::new();
+    decoder
+        .take(maximum_size as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() > maximum_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "decompressed message exceeds the maximum message size",
+        ));
+    }
+    Ok(BytesMut::from(decompressed.as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{components::small_network::MessagePackFormat, protocol};
+
+    type TestFormat = CompressingFormat<MessagePackFormat>;
+
+    fn format(compression: Option<Compression>) -> TestFormat {
+        let handle = CompressionHandle::default();
+        if let Some(compression) = compression {
+            handle.set(compression).unwrap();
+        }
+        CompressingFormat::new(Weak::new(), handle, 1024 * 1024, MessagePackFormat)
+    }
+
+    fn roundtrip(
+        sender: &mut TestFormat,
+        receiver: &mut TestFormat,
+        message: Message<protocol::Message>,
+    ) -> (Bytes, Message<protocol::Message>) {
+        let frame = Pin::new(sender).serialize(&Arc::new(message)).unwrap();
+        let received = Pin::new(receiver)
+            .deserialize(&BytesMut::from(frame.as_ref()))
+            .unwrap();
+        (frame, received)
+    }
+
+    fn large_message() -> Message<protocol::Message> {
+        Message::Handshake {
+            network_name: "casper-test-network".repeat(100),
+            public_addr: ([12, 34, 56, 78], 12346).into(),
+            protocol_version: Default::default(),
+            consensus_certificate: None,
+            compression: None,
+            gossip_reconciliation: false,
+            max_batch_size: 1,
+        }
+    }
+
+    #[test]
+    fn compresses_large_messages_only() {
+        let settings = CompressionSettings::new(3, None);
+        let support = settings.support();
+        let mut sender = format(Some(settings.negotiate(&support)));
+        let mut receiver = format(Some(settings.negotiate(&support)));
+
+        let (frame, received) = roundtrip(&mut sender, &mut receiver, large_message());
+        assert_eq!(TAG_ZSTD, frame[0]);
+        assert!(frame.len() < 500);
+        assert_eq!(large_message().to_string(), received.to_string());
+
+        let small_message = Message::Handshake {
+            network_name: "casper-test-network".to_string(),
+            public_addr: ([12, 34, 56, 78], 12346).into(),
+            protocol_version: Default::default(),
+            consensus_certificate: None,
+            compression: Some(support),
+            gossip_reconciliation: false,
+            max_batch_size: 1,
+        };
+        let (frame, received) = roundtrip(&mut sender, &mut receiver, small_message.clone());
+        assert_eq!(TAG_UNCOMPRESSED, frame[0]);
+        assert_eq!(small_message.to_string(), received.to_string());
+    }
+
+    #[test]
+    fn uses_dictionary_only_if_shared() {
+        let ours = CompressionSettings::new(3, Some(b"casper-test-network".repeat(10)));
+        let same = CompressionSettings::new(3, Some(b"casper-test-network".repeat(10)));
+        let other = CompressionSettings::new(3, Some(b"another dictionary".to_vec()));
+        let none = CompressionSettings::new(3, None);
+
+        assert!(ours.negotiate(&same.support()).dictionary.is_some());
+        assert!(ours.negotiate(&other.support()).dictionary.is_none());
+        assert!(ours.negotiate(&none.support()).dictionary.is_none());
+        assert!(none.negotiate(&ours.support()).dictionary.is_none());
+
+        let mut sender = format(Some(ours.negotiate(&same.support())));
+        let mut receiver = format(Some(same.negotiate(&ours.support())));
+        let (_, received) = roundtrip(&mut sender, &mut receiver, large_message());
+        assert_eq!(large_message().to_string(), received.to_string());
+    }
+
+    #[test]
+    fn plain_messagepack_without_negotiated_compression() {
+        let mut sender = format(None);
+        let mut receiver = format(None);
+        let (frame, _) = roundtrip(&mut sender, &mut receiver, large_message());
+        let expected = rmp_serde::to_vec(&large_message()).unwrap();
+        assert_eq!(expected.as_slice(), frame.as_ref());
+    }
+
+    #[test]
+    fn rejects_oversized_messages() {
+        let settings = CompressionSettings::new(3, None);
+        let support = settings.support();
+        let mut sender = format(Some(settings.negotiate(&support)));
+        let mut receiver = format(Some(settings.negotiate(&support)));
+        receiver.maximum_message_size = 1000;
+
+        let frame = Pin::new(&mut sender)
+            .serialize(&Arc::new(large_message()))
+            .unwrap();
+        assert_eq!(TAG_ZSTD, frame[0]);
+        let result: Result<Message<protocol::Message>, _> =
+            Pin::new(&mut receiver).deserialize(&BytesMut::from(frame.as_ref()));
+        assert!(result.is_err());
+    }
+}
@@ -1,13 +1,13 @@
 #[cfg(test)]
 use std::net::{Ipv4Addr, SocketAddr};
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
 use crate::types::TimeDiff;
 
-use super::PayloadWeights;
+use super::{AccessMode, PayloadWeights, QueueWeights};
 
 /// Default binding address.
 ///
@@ -22,6 +22,12 @@ const DEFAULT_PUBLIC_ADDRESS: &str = "127.0.0.1:0";
 /// Default interval for gossiping network addresses.
 const DEFAULT_GOSSIP_INTERVAL: &str = "30sec";
 
+/// Default duration for which misbehaving peers are banned.
+const DEFAULT_BAN_DURATION: &str = "10min";
+
+/// Default zstd compression level.
+const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
 // Default values for networking configuration:
 impl Default for Config {
     fn default() -> Self {
@@ -35,6 +41,15 @@ impl Default for Config {
             max_outgoing_byte_rate_non_validators: 0,
             max_incoming_message_rate_non_validators: 0,
             estimator_weights: Default::default(),
+            queue_weights: Default::default(),
+            ban_duration: TimeDiff::from_str(DEFAULT_BAN_DURATION).unwrap(),
+            ban_list_path: None,
+            access_mode: AccessMode::Open,
+            allowlist_path: None,
+            enable_compression: true,
+            compression_level: DEFAULT_COMPRESSION_LEVEL,
+            compression_dictionary_path: None,
+            capture_path: None,
         }
     }
 }
@@ -64,6 +79,30 @@ pub struct Config {
     pub max_incoming_message_rate_non_validators: u32,
     /// Weight distribution for the payload impact estimator.
     pub estimator_weights: PayloadWeights,
+    /// The number of messages of each kind sent per round of the outgoing queues.
+    pub queue_weights: QueueWeights,
+    /// How long a misbehaving peer stays banned.
+    pub ban_duration: TimeDiff,
+    /// Path to the file the ban list is written to, so that bans survive a restart. If not set,
+    /// bans are only kept in memory.
+    pub ban_list_path: Option<PathBuf>,
+    /// Which peers may connect: any peer, only peers on the allowlist, or only the current and
+    /// upcoming era's validators and peers on the allowlist.
+    pub access_mode: AccessMode,
+    /// Path to the allowlist file, containing one hex-encoded node ID per line. The file is re-read
+    /// whenever it changes. Required and must not be empty unless `access_mode` is `open`.
+    pub allowlist_path: Option<PathBuf>,
+    /// Whether to compress messages to peers that support compression.
+    pub enable_compression: bool,
+    /// The zstd compression level, from 1 (fastest) to 19 (smallest).
+    pub compression_level: i32,
+    /// Path to a zstd dictionary shared by the nodes of the network, which improves the
+    /// compression of small messages. It is only used with peers that have the same dictionary.
+    pub compression_dictionary_path: Option<PathBuf>,
+    /// Path to a file all incoming and outgoing messages are recorded to, so they can be replayed
+    /// offline. The file is replaced on startup and grows quickly, so this is meant for debugging
+    /// only. If not set, no messages are recorded.
+    pub capture_path: Option<PathBuf>,
 }
 
 #[cfg(test)]
@@ -0,0 +1,105 @@
+//! Information about the node's connections, for inspection by node operators.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use casper_types::{ProtocolVersion, PublicKey};
+use datasize::DataSize;
+
+use super::{
+    counting_format::{ConnectionStats, TrafficStats},
+    outgoing::OutgoingState,
+    Direction,
+};
+use crate::types::NodeId;
+
+/// What we learned about a peer while establishing a connection with it.
+#[derive(Clone, Debug)]
+pub(crate) struct ConnectionInfo {
+    /// The protocol version the peer sent in its handshake.
+    pub(super) protocol_version: ProtocolVersion,
+    /// The network name the peer sent in its handshake.
+    pub(super) network_name: String,
+    /// Whether messages on the connection are compressed.
+    pub(super) is_compressed: bool,
+    /// Whether the peer understands set-reconciliation gossip messages.
+    pub(super) supports_gossip_reconciliation: bool,
+    /// The maximum number of requests the peer accepts in a single batch.
+    pub(super) max_batch_size: u16,
+    /// The traffic on the connection.
+    pub(super) stats: Arc<ConnectionStats>,
+}
+
+/// The state of a connection, as tracked by the outgoing manager for outgoing connections.
+///
+/// Incoming connections are only known while they are connected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ConnectionState {
+    /// We are currently connecting to the address.
+    Connecting {
+        /// The number of attempts that failed so far.
+        failures_so_far: u8,
+    },
+    /// Connecting failed and we are waiting before trying again.
+    Waiting {
+        /// The number of attempts that failed so far.
+        failures_so_far: u8,
+    },
+    /// The connection is established.
+    Connected,
+    /// The address is blocked.
+    Blocked,
+    /// The address is our own.
+    Loopback,
+}
+
+impl<H, E> From<&OutgoingState<H, E>> for ConnectionState
+where
+    H: DataSize,
+    E: DataSize,
+{
+    fn from(state: &OutgoingState<H, E>) -> Self {
+        match *state {
+            OutgoingState::Connecting {
+                failures_so_far, ..
+            } => ConnectionState::Connecting { failures_so_far },
+            OutgoingState::Waiting {
+                failures_so_far, ..
+            } => ConnectionState::Waiting { failures_so_far },
+            OutgoingState::Connected { .. } => ConnectionState::Connected,
+            OutgoingState::Blocked { .. } => ConnectionState::Blocked,
+            OutgoingState::Loopback => ConnectionState::Loopback,
+        }
+    }
+}
+
+/// A connection or address we are trying to connect to.
+#[derive(Clone, Debug)]
+pub(crate) struct ConnectionDetails {
+    /// Whether we dialed the peer or the peer dialed us.
+    pub(crate) direction: Direction,
+    /// The address we dialed for outgoing connections, the peer's address for incoming ones.
+    pub(crate) addr: SocketAddr,
+    /// The state of the connection.
+    pub(crate) state: ConnectionState,
+    /// The peer at the other end, if the connection is established.
+    pub(crate) peer: Option<PeerDetails>,
+}
+
+/// The peer at the other end of an established connection.
+#[derive(Clone, Debug)]
+pub(crate) struct PeerDetails {
+    /// The peer's node ID.
+    pub(crate) peer_id: NodeId,
+    /// The protocol version the peer sent in its handshake.
+    pub(crate) protocol_version: ProtocolVersion,
+    /// The network name the peer sent in its handshake.
+    pub(crate) network_name: String,
+    /// Whether messages on the connection are compressed.
+    pub(crate) is_compressed: bool,
+    /// The consensus key the peer proved to hold in its handshake, if any.
+    pub(crate) consensus_public_key: Option<PublicKey>,
+    /// Whether the consensus key belongs to a validator of the current era.
+    pub(crate) is_active_validator: bool,
+    /// The traffic on the connection.
+    pub(crate) traffic: TrafficStats,
+}
@@ -4,12 +4,17 @@
 //! unique ID per established connection that can be independently derive by peers on either of a
 //! connection. [`TraceId`] identifies a single message, distinguishing even messages that are sent
 //! to the same peer with equal contents.
+//!
+//! Each connection also keeps its own [`ConnectionStats`], so that its traffic can be inspected.
 
 use std::{
     convert::TryFrom,
     fmt::{self, Display, Formatter},
     pin::Pin,
-    sync::{Arc, Weak},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Weak,
+    },
 };
 
 use bytes::{Bytes, BytesMut};
@@ -24,10 +29,14 @@ use tracing::{trace, warn};
 
 use casper_hashing::Digest;
 
-use super::{tls::KeyFingerprint, Message, Payload};
+use super::{tls::KeyFingerprint, Message, MessageKind, Payload};
 #[cfg(test)]
 use crate::testing::TestRng;
-use crate::{components::networking_metrics::NetworkingMetrics, types::NodeId, utils};
+use crate::{
+    components::networking_metrics::NetworkingMetrics,
+    types::{NodeId, Timestamp},
+    utils,
+};
 
 /// Lazily-evaluated network message ID generator.
 ///
@@ -62,6 +71,8 @@ pub struct CountingFormat<F> {
     role: Role,
     /// Metrics to update.
     metrics: Weak<NetworkingMetrics>,
+    /// Traffic statistics of this connection.
+    stats: Arc<ConnectionStats>,
 }
 
 impl<F> CountingFormat<F> {
@@ -69,12 +80,14 @@ impl<F> CountingFormat<F> {
     #[inline]
     pub(super) fn new(
         metrics: Weak<NetworkingMetrics>,
+        stats: Arc<ConnectionStats>,
         connection_id: ConnectionId,
         role: Role,
         inner: F,
     ) -> Self {
         Self {
             metrics,
+            stats,
             connection_id,
             out_count: 0,
             in_count: 0,
@@ -100,6 +113,7 @@ where
         let msg_size = serialized.len() as u64;
         let msg_kind = item.classify();
         NetworkingMetrics::record_payload_out(this.metrics, msg_kind, msg_size);
+        this.stats.record_sent(msg_kind, msg_size);
 
         let trace_id = this
             .connection_id
@@ -131,6 +145,8 @@ where
 
         let deserialized = F::deserialize(projection, src)?;
         let msg_kind = deserialized.classify();
+        this.stats
+            .record_received(msg_kind, msg_size, Timestamp::now());
 
         let trace_id = this
             .connection_id
@@ -146,6 +162,92 @@ where
     }
 }
 
+/// Message and byte counters for a single message kind.
+#[derive(Debug, Default)]
+struct KindCounters {
+    messages: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl KindCounters {
+    fn record(&self, size: u64) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(size, Ordering::Relaxed);
+    }
+}
+
+/// Traffic on a single connection, broken down by message kind.
+///
+/// Updated by the connection's [`CountingFormat`] and read when the connection is inspected.
+#[derive(Debug, Default)]
+pub(crate) struct ConnectionStats {
+    /// Counters for sent messages, indexed by message kind.
+    sent: [KindCounters; MessageKind::ALL.len()],
+    /// Counters for received messages, indexed by message kind.
+    received: [KindCounters; MessageKind::ALL.len()],
+    /// When the last message was received, in milliseconds since the Unix epoch, or zero.
+    last_received: AtomicU64,
+}
+
+impl ConnectionStats {
+    /// Records a sent message of the given serialized size.
+    pub(super) fn record_sent(&self, kind: MessageKind, size: u64) {
+        self.sent[kind as usize].record(size);
+    }
+
+    /// Records a message of the given serialized size, received at `now`.
+    pub(super) fn record_received(&self, kind: MessageKind, size: u64, now: Timestamp) {
+        self.received[kind as usize].record(size);
+        self.last_received.store(now.millis(), Ordering::Relaxed);
+    }
+
+    /// Returns the traffic so far.
+    pub(crate) fn snapshot(&self) -> TrafficStats {
+        let kinds = MessageKind::ALL
+            .iter()
+            .map(|&kind| {
+                let sent = &self.sent[kind as usize];
+                let received = &self.received[kind as usize];
+                KindTraffic {
+                    kind,
+                    messages_sent: sent.messages.load(Ordering::Relaxed),
+                    bytes_sent: sent.bytes.load(Ordering::Relaxed),
+                    messages_received: received.messages.load(Ordering::Relaxed),
+                    bytes_received: received.bytes.load(Ordering::Relaxed),
+                }
+            })
+            .filter(|traffic| traffic.messages_sent > 0 || traffic.messages_received > 0)
+            .collect();
+        let last_received = match self.last_received.load(Ordering::Relaxed) {
+            0 => None,
+            millis => Some(Timestamp::from(millis)),
+        };
+        TrafficStats {
+            kinds,
+            last_received,
+        }
+    }
+}
+
+/// The traffic of a single message kind on a connection.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct KindTraffic {
+    pub(crate) kind: MessageKind,
+    pub(crate) messages_sent: u64,
+    pub(crate) bytes_sent: u64,
+    pub(crate) messages_received: u64,
+    pub(crate) bytes_received: u64,
+}
+
+/// A snapshot of the traffic on a connection.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TrafficStats {
+    /// The traffic by message kind, omitting kinds of which no messages were sent or received.
+    pub(crate) kinds: Vec<KindTraffic>,
+    /// When the last message was received, if any.
+    pub(crate) last_received: Option<Timestamp>,
+}
+
 /// An ID identifying a connection.
 ///
 /// The ID is guaranteed to be the same on both ends of the connection, but not guaranteed to be
@@ -265,6 +367,11 @@ impl ConnectionId {
         &self.0
     }
 
+    /// Returns the connection ID as a digest, to be signed in a consensus certificate.
+    pub(super) fn to_digest(self) -> Digest {
+        Digest::from(self.0)
+    }
+
     /// Creates a new connection ID from an existing SSL connection.
     #[inline]
     pub(crate) fn from_connection(ssl: &SslRef, our_id: NodeId, their_id: NodeId) -> Self {
@@ -304,9 +411,11 @@ impl Role {
 
 #[cfg(test)]
 mod tests {
-    use crate::types::NodeId;
+    use crate::types::{NodeId, Timestamp};
 
-    use super::{ConnectionId, Role, TlsRandomData, TraceId};
+    use super::{
+        ConnectionId, ConnectionStats, KindTraffic, MessageKind, Role, TlsRandomData, TraceId,
+    };
 
     #[test]
     fn trace_id_has_16_character() {
@@ -367,4 +476,45 @@ mod tests {
         assert_eq!(msg_ba_0_on_b, msg_ba_0_on_a);
         assert_ne!(msg_ba_0_on_b, msg_ab_0_on_b);
     }
+
+    #[test]
+    fn connection_stats_count_traffic_by_kind() {
+        let stats = ConnectionStats::default();
+        assert!(stats.snapshot().kinds.is_empty());
+        assert_eq!(stats.snapshot().last_received, None);
+
+        stats.record_sent(MessageKind::Consensus, 100);
+        stats.record_sent(MessageKind::Consensus, 50);
+        stats.record_received(MessageKind::Protocol, 30, Timestamp::from(1000));
+        stats.record_received(MessageKind::DeployGossip, 20, Timestamp::from(2000));
+
+        let snapshot = stats.snapshot();
+        assert_eq!(
+            snapshot.kinds,
+            vec![
+                KindTraffic {
+                    kind: MessageKind::Protocol,
+                    messages_sent: 0,
+                    bytes_sent: 0,
+                    messages_received: 1,
+                    bytes_received: 30,
+                },
+                KindTraffic {
+                    kind: MessageKind::Consensus,
+                    messages_sent: 2,
+                    bytes_sent: 150,
+                    messages_received: 0,
+                    bytes_received: 0,
+                },
+                KindTraffic {
+                    kind: MessageKind::DeployGossip,
+                    messages_sent: 0,
+                    bytes_sent: 0,
+                    messages_received: 1,
+                    bytes_received: 20,
+                },
+            ]
+        );
+        assert_eq!(snapshot.last_received, Some(Timestamp::from(2000)));
+    }
 }
@@ -1,15 +1,13 @@
-use std::{error, io, net::SocketAddr, result, sync::Arc};
+use std::{error, io, net::SocketAddr, result};
 
-use casper_types::SecretKey;
 use datasize::DataSize;
 use openssl::{error::ErrorStack, ssl};
 use serde::Serialize;
 use thiserror::Error;
 
+use super::{allowlist, capture, reputation};
 use crate::{
-    crypto,
-    tls::ValidationError,
-    utils::{LoadError, Loadable, ResolveAddressError},
+    components::consensus::signer, crypto, tls::ValidationError, utils::ResolveAddressError,
 };
 
 pub(super) type Result<T> = result::Result<T, Error>;
@@ -25,7 +23,41 @@ pub enum Error {
     LoadConsensusKeys(
         #[serde(skip_serializing)]
         #[source]
-        LoadError<<Arc<SecretKey> as Loadable>::Error>,
+        signer::Error,
+    ),
+    /// The ban list could not be loaded.
+    #[error("failed to load ban list: {0}")]
+    LoadBanList(
+        #[serde(skip_serializing)]
+        #[source]
+        reputation::Error,
+    ),
+    /// The allowlist could not be loaded.
+    #[error("failed to load allowlist: {0}")]
+    LoadAllowlist(
+        #[serde(skip_serializing)]
+        #[source]
+        allowlist::Error,
+    ),
+    /// A private network was configured without any peers on the allowlist.
+    #[error(
+        "the allowlist must not be empty unless the access mode is 'open', since the validators \
+         are only known after joining the network"
+    )]
+    EmptyAllowlist,
+    /// The compression dictionary could not be read.
+    #[error("failed to read compression dictionary: {0}")]
+    ReadCompressionDictionary(
+        #[serde(skip_serializing)]
+        #[source]
+        io::Error,
+    ),
+    /// The capture file could not be created.
+    #[error("failed to create capture file: {0}")]
+    CreateCapture(
+        #[serde(skip_serializing)]
+        #[source]
+        capture::Error,
     ),
     /// Failed to create a TCP listener.
     #[error("failed to create listener on {1}")]
@@ -152,6 +184,9 @@ pub enum ConnectionError {
         #[source]
         crypto::Error,
     ),
+    /// The peer is not on the allowlist of our private network.
+    #[error("peer is not allowed to connect")]
+    PeerNotAllowed,
 }
 
 /// IO operation that can time out or close.
@@ -12,7 +12,10 @@ use serde::Serialize;
 use static_assertions::const_assert;
 use tracing::Span;
 
-use super::{error::ConnectionError, FramedTransport, GossipedAddress, Message, NodeId};
+use super::{
+    connection_info::ConnectionInfo, error::ConnectionError, FramedTransport, GossipedAddress,
+    Message, NodeId,
+};
 use crate::{
     components::contract_runtime::ContractRuntimeAnnouncement,
     effect::{
@@ -176,6 +179,9 @@ pub(crate) enum IncomingConnection<P> {
         peer_id: NodeId,
         /// The public key the peer is validating with, if any.
         peer_consensus_public_key: Option<PublicKey>,
+        /// The handshake details and traffic statistics of the connection.
+        #[serde(skip_serializing)]
+        info: ConnectionInfo,
         /// Stream of incoming messages. for incoming connections.
         #[serde(skip_serializing)]
         stream: SplitStream<FramedTransport<P>>,
@@ -199,6 +205,7 @@ impl<P> Display for IncomingConnection<P> {
                 public_addr,
                 peer_id,
                 peer_consensus_public_key,
+                info: _,
                 stream: _,
             } => {
                 write!(
@@ -246,6 +253,9 @@ pub(crate) enum OutgoingConnection<P> {
         peer_id: NodeId,
         /// The public key the peer is validating with, if any.
         peer_consensus_public_key: Option<PublicKey>,
+        /// The handshake details and traffic statistics of the connection.
+        #[serde(skip_serializing)]
+        info: ConnectionInfo,
         /// Sink for outgoing messages.
         #[serde(skip_serializing)]
         sink: SplitSink<FramedTransport<P>, Arc<Message<P>>>,
@@ -268,6 +278,7 @@ impl<P> Display for OutgoingConnection<P> {
                 peer_addr,
                 peer_id,
                 peer_consensus_public_key,
+                info: _,
                 sink: _,
             } => {
                 write!(f, "connection established to {}/{}", peer_addr, peer_id)?;
@@ -4,13 +4,17 @@ use std::{
     sync::Arc,
 };
 
-use casper_types::{ProtocolVersion, PublicKey, SecretKey, Signature};
+use casper_types::{ProtocolVersion, PublicKey, Signature};
 use datasize::DataSize;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::warn;
 
-use crate::crypto;
+use crate::{
+    components::consensus::signer::{Signer, SigningRequest},
+    crypto,
+};
 
-use super::counting_format::ConnectionId;
+use super::{compression::CompressionSupport, counting_format::ConnectionId};
 
 /// The default protocol version to use in absence of one in the protocol version field.
 #[inline]
@@ -18,6 +22,12 @@ fn default_protocol_version() -> ProtocolVersion {
     ProtocolVersion::V1_0_0
 }
 
+/// The default batch size to use in absence of one in the max batch size field, i.e. no batching.
+#[inline]
+fn default_max_batch_size() -> u16 {
+    1
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub(crate) enum Message<P> {
     Handshake {
@@ -31,6 +41,15 @@ pub(crate) enum Message<P> {
         /// A self-signed certificate indicating validator status.
         #[serde(default)]
         consensus_certificate: Option<ConsensusCertificate>,
+        /// The compression the node supports, if any.
+        #[serde(default)]
+        compression: Option<CompressionSupport>,
+        /// Whether the node understands set-reconciliation gossip messages.
+        #[serde(default)]
+        gossip_reconciliation: bool,
+        /// The maximum number of requests the node accepts in a single batch.
+        #[serde(default = "default_max_batch_size")]
+        max_batch_size: u16,
     },
     Payload(P),
 }
@@ -55,24 +74,16 @@ impl<P: Payload> Message<P> {
     }
 }
 
-/// A pair of secret keys used by consensus.
+/// The signer of the key pair used by consensus.
+#[derive(Clone)]
 pub(super) struct ConsensusKeyPair {
-    secret_key: Arc<SecretKey>,
-    public_key: PublicKey,
+    signer: Arc<dyn Signer>,
 }
 
 impl ConsensusKeyPair {
     /// Creates a new key pair for consensus signing.
-    pub(super) fn new(secret_key: Arc<SecretKey>, public_key: PublicKey) -> Self {
-        Self {
-            secret_key,
-            public_key,
-        }
-    }
-
-    /// Sign a value using this keypair.
-    fn sign<T: AsRef<[u8]>>(&self, value: T) -> Signature {
-        crypto::sign(value, &self.secret_key, &self.public_key)
+    pub(super) fn new(signer: Arc<dyn Signer>) -> Self {
+        Self { signer }
     }
 }
 
@@ -85,11 +96,21 @@ pub(crate) struct ConsensusCertificate {
 
 impl ConsensusCertificate {
     /// Creates a new consensus certificate from a connection ID and key pair.
-    pub(super) fn create(connection_id: ConnectionId, key_pair: &ConsensusKeyPair) -> Self {
-        let signature = key_pair.sign(connection_id.as_bytes());
-        ConsensusCertificate {
-            public_key: key_pair.public_key.clone(),
-            signature,
+    ///
+    /// Returns `None` if signing failed. This may block if the signer is a remote one.
+    pub(super) fn create(connection_id: ConnectionId, key_pair: &ConsensusKeyPair) -> Option<Self> {
+        let request = SigningRequest::Handshake {
+            connection_id: connection_id.to_digest(),
+        };
+        match key_pair.signer.sign(&request) {
+            Ok(signature) => Some(ConsensusCertificate {
+                public_key: key_pair.signer.public_key().clone(),
+                signature,
+            }),
+            Err(err) => {
+                warn!(%err, "failed to sign consensus certificate; sending handshake without it");
+                None
+            }
         }
     }
 
@@ -114,6 +135,9 @@ impl<P: Display> Display for Message<P> {
                 public_addr,
                 protocol_version,
                 consensus_certificate,
+                compression,
+                gossip_reconciliation,
+                max_batch_size,
             } => {
                 write!(
                     f,
@@ -122,10 +146,18 @@ impl<P: Display> Display for Message<P> {
                 )?;
 
                 if let Some(cert) = consensus_certificate {
-                    write!(f, "{}", cert)
+                    write!(f, "{}", cert)?;
                 } else {
-                    f.write_str("-")
+                    f.write_str("-")?;
                 }
+
+                write!(
+                    f,
+                    ", compression: {}, gossip reconciliation: {}, max batch size: {}",
+                    compression.is_some(),
+                    gossip_reconciliation,
+                    max_batch_size
+                )
             }
             Message::Payload(payload) => write!(f, "payload: {}", payload),
         }
@@ -133,12 +165,14 @@ impl<P: Display> Display for Message<P> {
 }
 
 /// A classification system for networking messages.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub(crate) enum MessageKind {
     /// Non-payload messages, like handshakes.
     Protocol,
     /// Messages directly related to consensus.
     Consensus,
+    /// Finality signatures being gossiped.
+    FinalitySignature,
     /// Deploys being gossiped.
     DeployGossip,
     /// Addresses being gossiped.
@@ -151,11 +185,26 @@ pub(crate) enum MessageKind {
     Other,
 }
 
+impl MessageKind {
+    /// All message kinds, in the order of their declaration.
+    pub(crate) const ALL: [MessageKind; 8] = [
+        MessageKind::Protocol,
+        MessageKind::Consensus,
+        MessageKind::FinalitySignature,
+        MessageKind::DeployGossip,
+        MessageKind::AddressGossip,
+        MessageKind::DeployTransfer,
+        MessageKind::BlockTransfer,
+        MessageKind::Other,
+    ];
+}
+
 impl Display for MessageKind {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             MessageKind::Protocol => f.write_str("protocol"),
             MessageKind::Consensus => f.write_str("consensus"),
+            MessageKind::FinalitySignature => f.write_str("finality_signature"),
             MessageKind::DeployGossip => f.write_str("deploy_gossip"),
             MessageKind::AddressGossip => f.write_str("address_gossip"),
             MessageKind::DeployTransfer => f.write_str("deploy_transfer"),
@@ -179,6 +228,14 @@ pub(crate) trait Payload:
     fn incoming_resource_estimate(&self, _weights: &PayloadWeights) -> u32 {
         0
     }
+
+    /// Splits a batch of requests into payloads of at most `max_batch_size` requests each, for a
+    /// peer which doesn't accept larger batches.
+    ///
+    /// Returns `None` if the payload doesn't need to be split.
+    fn split_batch(&self, _max_batch_size: u16) -> Option<Vec<Self>> {
+        None
+    }
 }
 
 /// A generic configuration for payload weights.
@@ -192,6 +249,9 @@ pub struct PayloadWeights {
     pub consensus: u32,
     /// Weight to attach to deploy requests.
     pub deploy_requests: u32,
+    /// Weight to attach to requests for blocks and block headers.
+    #[serde(default)]
+    pub block_requests: u32,
 }
 
 #[cfg(test)]
@@ -203,7 +263,7 @@ mod tests {
     use casper_types::ProtocolVersion;
     use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-    use crate::protocol;
+    use crate::{components::small_network::compression::CompressionSettings, protocol};
 
     use super::Message;
 
@@ -311,6 +371,9 @@ mod tests {
             protocol_version: ProtocolVersion::from_parts(5, 6, 7),
             // TODO: Test _with_ handshake instead.
             consensus_certificate: None,
+            compression: Some(CompressionSettings::new(3, None).support()),
+            gossip_reconciliation: true,
+            max_batch_size: 10,
         };
 
         let legacy_handshake: V1_0_0_Message = roundtrip_message(&modern_handshake);
@@ -344,11 +407,17 @@ mod tests {
                 public_addr,
                 protocol_version,
                 consensus_certificate,
+                compression,
+                gossip_reconciliation,
+                max_batch_size,
             } => {
                 assert_eq!(network_name, "example-handshake");
                 assert_eq!(public_addr, ([12, 34, 56, 78], 12346).into());
                 assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
                 assert!(consensus_certificate.is_none());
+                assert!(compression.is_none());
+                assert!(!gossip_reconciliation);
+                assert_eq!(max_batch_size, 1);
             }
             Message::Payload(_) => {
                 panic!("did not expect modern handshake to deserialize to payload")
@@ -366,11 +435,17 @@ mod tests {
                 public_addr,
                 protocol_version,
                 consensus_certificate,
+                compression,
+                gossip_reconciliation,
+                max_batch_size,
             } => {
                 assert_eq!(network_name, "serialization-test");
                 assert_eq!(public_addr, ([12, 34, 56, 78], 12346).into());
                 assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
                 assert!(consensus_certificate.is_none());
+                assert!(compression.is_none());
+                assert!(!gossip_reconciliation);
+                assert_eq!(max_batch_size, 1);
             }
             Message::Payload(_) => {
                 panic!("did not expect modern handshake to deserialize to payload")
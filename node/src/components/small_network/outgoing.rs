@@ -426,6 +426,16 @@ where
         self.routes.keys().cloned()
     }
 
+    /// Iterates over all known addresses and the states of their connections.
+    #[allow(clippy::needless_lifetimes)]
+    pub(crate) fn outgoing_states<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = (SocketAddr, &'a OutgoingState<H, E>)> + 'a {
+        self.outgoing
+            .iter()
+            .map(|(&addr, outgoing)| (addr, &outgoing.state))
+    }
+
     /// Notify about a potentially new address that has been discovered.
     ///
     /// Immediately triggers the connection process to said address if it was not known before.
@@ -514,8 +524,6 @@ where
     /// Removes an address from the block list.
     ///
     /// Does nothing if the address was not blocked.
-    // This function is currently not in use by `small_network` itself.
-    #[allow(dead_code)]
     pub(crate) fn redeem_addr(&mut self, addr: SocketAddr, now: Instant) -> Option<DialRequest<H>> {
         let span = make_span(addr, self.outgoing.get(&addr));
         span.clone()
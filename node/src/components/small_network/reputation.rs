@@ -0,0 +1,418 @@
+//! Peer reputation and the ban list.
+//!
+//! Other components report offenses committed by peers, e.g. sending invalid deploys or consensus
+//! messages, or failing to respond to requests in time. Each offense adds a penalty to the peer's
+//! score, which recovers over time. Once the penalty reaches the ban threshold, the peer is banned
+//! for the configured duration: We disconnect from it, refuse its incoming connections and don't
+//! dial any address at which we found it, whether the address is a known address or was gossiped
+//! to us.
+//!
+//! Minor offenses like timeouts only make a peer less preferred; on their own they never get a
+//! peer banned. The ban list is written to a file, so bans survive a restart.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::{
+    types::{NodeId, TimeDiff, Timestamp},
+    utils,
+};
+
+/// The penalty at which a peer gets banned.
+const BAN_THRESHOLD: u32 = 100;
+
+/// The number of penalty points a peer recovers per minute.
+const PENALTY_RECOVERY_PER_MINUTE: u64 = 10;
+
+/// An error reading the ban list.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// The ban list file could not be read.
+    #[error("failed to read ban list {path}: {error}")]
+    Io {
+        /// The path of the ban list file.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// The ban list file is corrupted.
+    #[error("failed to parse ban list {path}: {error}")]
+    Parse {
+        /// The path of the ban list file.
+        path: PathBuf,
+        /// The underlying error.
+        error: serde_json::Error,
+    },
+}
+
+/// A kind of misbehavior reported by another component.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub(crate) enum Offense {
+    /// The peer didn't respond to a request in time.
+    Timeout,
+    /// The peer sent us an invalid deploy.
+    InvalidDeploy,
+    /// The peer sent us a block proposal that turned out to be invalid.
+    InvalidProposal,
+    /// The peer sent us an invalid consensus message or evidence.
+    InvalidConsensusMessage,
+    /// The peer sent us a batch of more requests than we accept.
+    OversizedRequestBatch,
+}
+
+impl Offense {
+    /// Returns the penalty for committing this offense once.
+    fn penalty(self) -> u32 {
+        match self {
+            Offense::Timeout => 5,
+            Offense::InvalidDeploy | Offense::OversizedRequestBatch => 20,
+            Offense::InvalidProposal | Offense::InvalidConsensusMessage => BAN_THRESHOLD,
+        }
+    }
+
+    /// Returns whether this offense can get a peer banned.
+    fn is_bannable(self) -> bool {
+        match self {
+            Offense::Timeout => false,
+            Offense::InvalidDeploy
+            | Offense::InvalidProposal
+            | Offense::InvalidConsensusMessage
+            | Offense::OversizedRequestBatch => true,
+        }
+    }
+}
+
+impl Display for Offense {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Offense::Timeout => write!(f, "request timed out"),
+            Offense::InvalidDeploy => write!(f, "invalid deploy"),
+            Offense::InvalidProposal => write!(f, "invalid block proposal"),
+            Offense::InvalidConsensusMessage => write!(f, "invalid consensus message"),
+            Offense::OversizedRequestBatch => write!(f, "oversized request batch"),
+        }
+    }
+}
+
+/// A banned peer.
+#[derive(Clone, DataSize, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Ban {
+    /// The banned peer's ID.
+    pub(crate) peer_id: NodeId,
+    /// The time when the ban expires.
+    pub(crate) until: Timestamp,
+    /// Why the peer was banned.
+    pub(crate) reason: String,
+    /// The addresses at which we found the peer. We don't dial these while it is banned.
+    pub(crate) addresses: BTreeSet<SocketAddr>,
+}
+
+/// A peer's accumulated penalty.
+#[derive(Clone, Copy, DataSize, Debug)]
+struct Score {
+    /// The penalty at the time of the last update.
+    penalty: u32,
+    /// The time of the last update.
+    updated: Timestamp,
+}
+
+impl Score {
+    /// Returns the penalty, reduced by the amount recovered since the last update.
+    fn current_penalty(&self, now: Timestamp) -> u32 {
+        let elapsed_millis = now.saturating_diff(self.updated).millis();
+        let recovered = elapsed_millis.saturating_mul(PENALTY_RECOVERY_PER_MINUTE) / 60_000;
+        let recovered = u32::try_from(recovered).unwrap_or(u32::MAX);
+        self.penalty.saturating_sub(recovered)
+    }
+}
+
+/// The scores of all peers that committed offenses, and the ban list.
+#[derive(DataSize, Debug)]
+pub(super) struct Reputation {
+    /// How long a peer stays banned.
+    ban_duration: TimeDiff,
+    /// The file the ban list is written to, if any.
+    path: Option<PathBuf>,
+    /// The scores of peers that committed offenses and are not currently banned.
+    scores: HashMap<NodeId, Score>,
+    /// The banned peers.
+    bans: BTreeMap<NodeId, Ban>,
+}
+
+impl Reputation {
+    /// Creates a new reputation tracker, loading the ban list from `path` if it exists. Bans that
+    /// have expired by `now` are discarded.
+    pub(super) fn new(
+        ban_duration: TimeDiff,
+        path: Option<PathBuf>,
+        now: Timestamp,
+    ) -> Result<Self, Error> {
+        let bans = match &path {
+            Some(path) => read_ban_list(path)?,
+            None => Vec::new(),
+        };
+        let mut reputation = Reputation {
+            ban_duration,
+            path,
+            scores: HashMap::new(),
+            bans: bans.into_iter().map(|ban| (ban.peer_id, ban)).collect(),
+        };
+        reputation.expire_bans(now);
+        Ok(reputation)
+    }
+
+    /// Records an offense committed by the peer. Returns `true` if the peer is newly banned.
+    pub(super) fn record_offense(
+        &mut self,
+        peer_id: NodeId,
+        offense: Offense,
+        now: Timestamp,
+    ) -> bool {
+        if self.is_banned(&peer_id) {
+            return false;
+        }
+        let current = self
+            .scores
+            .get(&peer_id)
+            .map_or(0, |score| score.current_penalty(now));
+        let mut penalty = current.saturating_add(offense.penalty());
+        if !offense.is_bannable() {
+            penalty = penalty.min(BAN_THRESHOLD.saturating_sub(1)).max(current);
+        }
+        if penalty < BAN_THRESHOLD {
+            self.scores.insert(
+                peer_id,
+                Score {
+                    penalty,
+                    updated: now,
+                },
+            );
+            return false;
+        }
+        self.ban(peer_id, None, offense.to_string(), now);
+        true
+    }
+
+    /// Bans the peer for the given duration, or the configured one if `None`.
+    pub(super) fn ban(
+        &mut self,
+        peer_id: NodeId,
+        duration: Option<TimeDiff>,
+        reason: String,
+        now: Timestamp,
+    ) {
+        self.scores.remove(&peer_id);
+        let until = now + duration.unwrap_or(self.ban_duration);
+        let addresses = self
+            .bans
+            .remove(&peer_id)
+            .map(|ban| ban.addresses)
+            .unwrap_or_default();
+        let ban = Ban {
+            peer_id,
+            until,
+            reason,
+            addresses,
+        };
+        self.bans.insert(peer_id, ban);
+        self.persist();
+    }
+
+    /// Lifts the peer's ban. Returns the ban, or `None` if the peer wasn't banned.
+    pub(super) fn unban(&mut self, peer_id: &NodeId) -> Option<Ban> {
+        let ban = self.bans.remove(peer_id)?;
+        self.persist();
+        Some(ban)
+    }
+
+    /// Records that we found the banned peer at the given address.
+    pub(super) fn add_banned_address(&mut self, peer_id: &NodeId, addr: SocketAddr) {
+        if let Some(ban) = self.bans.get_mut(peer_id) {
+            if ban.addresses.insert(addr) {
+                self.persist();
+            }
+        }
+    }
+
+    /// Returns whether the peer is banned.
+    pub(super) fn is_banned(&self, peer_id: &NodeId) -> bool {
+        self.bans.contains_key(peer_id)
+    }
+
+    /// Returns whether we found a banned peer at the given address.
+    pub(super) fn is_banned_address(&self, addr: &SocketAddr) -> bool {
+        self.bans.values().any(|ban| ban.addresses.contains(addr))
+    }
+
+    /// Returns the peer's current penalty. Peers with a lower penalty should be preferred.
+    pub(super) fn penalty(&self, peer_id: &NodeId, now: Timestamp) -> u32 {
+        if self.is_banned(peer_id) {
+            return BAN_THRESHOLD;
+        }
+        self.scores
+            .get(peer_id)
+            .map_or(0, |score| score.current_penalty(now))
+    }
+
+    /// Returns all current bans.
+    pub(super) fn bans(&self) -> impl Iterator<Item = &Ban> {
+        self.bans.values()
+    }
+
+    /// Removes the bans that have expired by `now` and returns them. Also forgets the scores of
+    /// peers that have fully recovered.
+    pub(super) fn expire_bans(&mut self, now: Timestamp) -> Vec<Ban> {
+        self.scores
+            .retain(|_, score| score.current_penalty(now) > 0);
+        let expired: Vec<NodeId> = self
+            .bans
+            .values()
+            .filter(|ban| ban.until <= now)
+            .map(|ban| ban.peer_id)
+            .collect();
+        if expired.is_empty() {
+            return Vec::new();
+        }
+        let expired = expired
+            .iter()
+            .filter_map(|peer_id| self.bans.remove(peer_id))
+            .collect();
+        self.persist();
+        expired
+    }
+
+    /// Writes the ban list to the file, if configured. Failures are logged, but otherwise
+    /// ignored: The bans still apply until the node is restarted.
+    fn persist(&self) {
+        if let Some(path) = &self.path {
+            let bans: Vec<&Ban> = self.bans.values().collect();
+            if let Err(error) = write_ban_list(path, &bans) {
+                warn!(path = %path.display(), %error, "failed to write ban list");
+            }
+        }
+    }
+}
+
+/// Reads the ban list from the file, or returns an empty list if it doesn't exist.
+fn read_ban_list(path: &Path) -> Result<Vec<Ban>, Error> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|error| Error::Parse {
+            path: path.to_path_buf(),
+            error,
+        }),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(Error::Io {
+            path: path.to_path_buf(),
+            error,
+        }),
+    }
+}
+
+/// Writes the ban list to `path`, without ever leaving the file in a partially written state.
+fn write_ban_list(path: &Path, bans: &[&Ban]) -> io::Result<()> {
+    if let Some(parent_directory) = path.parent() {
+        fs::create_dir_all(parent_directory)?;
+    }
+    utils::write_file_atomically(path, serde_json::to_vec_pretty(bans)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRng;
+
+    const MINUTE: u32 = 60;
+
+    #[test]
+    fn minor_offenses_never_ban() {
+        let mut rng = TestRng::new();
+        let peer_id = NodeId::random(&mut rng);
+        let now = Timestamp::zero();
+        let mut reputation =
+            Reputation::new(TimeDiff::from_seconds(10 * MINUTE), None, now).unwrap();
+
+        for _ in 0..100 {
+            assert!(!reputation.record_offense(peer_id, Offense::Timeout, now));
+        }
+        assert_eq!(BAN_THRESHOLD - 1, reputation.penalty(&peer_id, now));
+
+        // A single invalid deploy on top of that gets the peer banned.
+        assert!(reputation.record_offense(peer_id, Offense::InvalidDeploy, now));
+        assert!(reputation.is_banned(&peer_id));
+    }
+
+    #[test]
+    fn penalties_recover() {
+        let mut rng = TestRng::new();
+        let peer_id = NodeId::random(&mut rng);
+        let mut now = Timestamp::zero();
+        let mut reputation =
+            Reputation::new(TimeDiff::from_seconds(10 * MINUTE), None, now).unwrap();
+
+        // Four invalid deploys are tolerated, but not five in quick succession.
+        for _ in 0..4 {
+            assert!(!reputation.record_offense(peer_id, Offense::InvalidDeploy, now));
+        }
+        assert_eq!(80, reputation.penalty(&peer_id, now));
+
+        // After two minutes, the penalty has decreased by 20.
+        now += TimeDiff::from_seconds(2 * MINUTE);
+        assert_eq!(60, reputation.penalty(&peer_id, now));
+        assert!(!reputation.record_offense(peer_id, Offense::InvalidDeploy, now));
+        assert!(reputation.record_offense(peer_id, Offense::InvalidDeploy, now));
+
+        // The ban expires after ten minutes.
+        now += TimeDiff::from_seconds(10 * MINUTE);
+        let expired = reputation.expire_bans(now);
+        assert_eq!(
+            vec![peer_id],
+            expired.iter().map(|ban| ban.peer_id).collect::<Vec<_>>()
+        );
+        assert!(!reputation.is_banned(&peer_id));
+        assert_eq!(0, reputation.penalty(&peer_id, now));
+    }
+
+    #[test]
+    fn ban_list_is_persisted() {
+        let mut rng = TestRng::new();
+        let peer_id = NodeId::random(&mut rng);
+        let other_peer_id = NodeId::random(&mut rng);
+        let addr: SocketAddr = "127.0.0.1:34553".parse().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ban_list.json");
+        let now = Timestamp::zero();
+        let ban_duration = TimeDiff::from_seconds(10 * MINUTE);
+
+        let mut reputation = Reputation::new(ban_duration, Some(path.clone()), now).unwrap();
+        assert!(reputation.record_offense(peer_id, Offense::InvalidConsensusMessage, now));
+        reputation.add_banned_address(&peer_id, addr);
+        reputation.ban(
+            other_peer_id,
+            Some(TimeDiff::from_seconds(MINUTE)),
+            "banned by operator".to_string(),
+            now,
+        );
+
+        // After a restart, the bans are still in place, unless they have expired.
+        let later = now + TimeDiff::from_seconds(2 * MINUTE);
+        let reputation = Reputation::new(ban_duration, Some(path), later).unwrap();
+        assert!(reputation.is_banned(&peer_id));
+        assert!(reputation.is_banned_address(&addr));
+        assert!(!reputation.is_banned(&other_peer_id));
+        let bans: Vec<_> = reputation.bans().collect();
+        assert_eq!(1, bans.len());
+        assert_eq!("invalid consensus message", bans[0].reason);
+    }
+}
@@ -0,0 +1,375 @@
+//! Prioritized queue of outgoing messages for a single connection.
+//!
+//! Every message kind has its own first-in, first-out queue. The sender task takes messages from
+//! these queues in rounds: within a round, queues are served in order of urgency, consensus first,
+//! then finality signatures, gossip and finally bulk transfers, and every queue may send up to its
+//! configured weight in messages. Once no queue with messages left has any credit, a new round
+//! starts. This way a burst of gossip or a large block transfer cannot delay consensus messages
+//! for long, while less urgent traffic still gets its share of the connection.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use datasize::DataSize;
+use prometheus::IntGauge;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+use super::{Message, MessageKind, Payload};
+use crate::components::networking_metrics::NetworkingMetrics;
+
+/// The message kinds in the order their queues are served within a round, most urgent first.
+const PRIORITY_ORDER: [MessageKind; 8] = [
+    MessageKind::Protocol,
+    MessageKind::Consensus,
+    MessageKind::FinalitySignature,
+    MessageKind::AddressGossip,
+    MessageKind::DeployGossip,
+    MessageKind::DeployTransfer,
+    MessageKind::BlockTransfer,
+    MessageKind::Other,
+];
+
+/// The number of messages of each kind that may be sent per round.
+///
+/// A weight of zero is treated as one, so that no kind of message is starved.
+#[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
+pub struct QueueWeights {
+    /// Weight of consensus messages. Also used for protocol messages.
+    pub consensus: u32,
+    /// Weight of finality signatures.
+    pub finality_signature: u32,
+    /// Weight of gossiped deploys.
+    pub deploy_gossip: u32,
+    /// Weight of gossiped addresses.
+    pub address_gossip: u32,
+    /// Weight of deploys requested by peers.
+    pub deploy_transfer: u32,
+    /// Weight of blocks requested by peers.
+    pub block_transfer: u32,
+    /// Weight of any other messages.
+    pub other: u32,
+}
+
+impl Default for QueueWeights {
+    fn default() -> Self {
+        QueueWeights {
+            consensus: 16,
+            finality_signature: 8,
+            deploy_gossip: 4,
+            address_gossip: 1,
+            deploy_transfer: 4,
+            block_transfer: 2,
+            other: 1,
+        }
+    }
+}
+
+impl QueueWeights {
+    /// Returns the weight of the given kind of message.
+    fn weight(&self, kind: MessageKind) -> u32 {
+        let weight = match kind {
+            MessageKind::Protocol | MessageKind::Consensus => self.consensus,
+            MessageKind::FinalitySignature => self.finality_signature,
+            MessageKind::DeployGossip => self.deploy_gossip,
+            MessageKind::AddressGossip => self.address_gossip,
+            MessageKind::DeployTransfer => self.deploy_transfer,
+            MessageKind::BlockTransfer => self.block_transfer,
+            MessageKind::Other => self.other,
+        };
+        weight.max(1)
+    }
+}
+
+/// The queue of a single kind of message.
+#[derive(Debug)]
+struct KindQueue<P> {
+    /// The messages waiting to be sent.
+    messages: VecDeque<Arc<Message<P>>>,
+    /// The number of messages that may be sent per round.
+    weight: u32,
+    /// The number of messages that may still be sent in the current round.
+    credit: u32,
+    /// Metric tracking the number of queued messages of this kind.
+    depth: IntGauge,
+}
+
+/// The state shared by the sender and the receiver of a queue.
+#[derive(Debug)]
+struct State<P> {
+    /// The queues, in the order they are served.
+    queues: Vec<KindQueue<P>>,
+    /// The number of live senders.
+    senders: usize,
+    /// Whether the receiver was dropped.
+    receiver_dropped: bool,
+    /// Metric tracking the number of queued messages of all kinds.
+    total_depth: IntGauge,
+}
+
+impl<P> State<P> {
+    /// Takes the next message to send, starting a new round if necessary.
+    fn pop(&mut self) -> Option<Arc<Message<P>>> {
+        let message = self.pop_in_round().or_else(|| {
+            for queue in &mut self.queues {
+                queue.credit = queue.weight;
+            }
+            self.pop_in_round()
+        })?;
+        self.total_depth.dec();
+        Some(message)
+    }
+
+    /// Takes the next message from the most urgent queue that still has credit in this round.
+    fn pop_in_round(&mut self) -> Option<Arc<Message<P>>> {
+        let queue = self
+            .queues
+            .iter_mut()
+            .find(|queue| queue.credit > 0 && !queue.messages.is_empty())?;
+        queue.credit -= 1;
+        queue.depth.dec();
+        queue.messages.pop_front()
+    }
+
+    /// Drops all queued messages.
+    fn clear(&mut self) {
+        for queue in &mut self.queues {
+            let count = queue.messages.len() as i64;
+            queue.depth.sub(count);
+            self.total_depth.sub(count);
+            queue.messages.clear();
+        }
+    }
+}
+
+/// The queue shared by the sender and the receiver.
+#[derive(Debug)]
+struct Shared<P> {
+    state: Mutex<State<P>>,
+    /// Notifies the receiver of new messages or a dropped sender.
+    notify: Notify,
+}
+
+/// Creates a new prioritized queue with the given weights.
+pub(super) fn channel<P: Payload>(
+    weights: &QueueWeights,
+    metrics: &NetworkingMetrics,
+) -> (QueueSender<P>, QueueReceiver<P>) {
+    let queues = PRIORITY_ORDER
+        .iter()
+        .map(|&kind| KindQueue {
+            messages: VecDeque::new(),
+            weight: weights.weight(kind),
+            credit: weights.weight(kind),
+            depth: metrics.queued_messages_of_kind(kind).clone(),
+        })
+        .collect();
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queues,
+            senders: 1,
+            receiver_dropped: false,
+            total_depth: metrics.queued_messages.clone(),
+        }),
+        notify: Notify::new(),
+    });
+    (
+        QueueSender {
+            shared: shared.clone(),
+        },
+        QueueReceiver { shared },
+    )
+}
+
+/// The sending half of a prioritized queue.
+#[derive(Debug)]
+pub(super) struct QueueSender<P> {
+    shared: Arc<Shared<P>>,
+}
+
+impl<P: Payload> QueueSender<P> {
+    /// Queues a message.
+    ///
+    /// Returns the message if the receiver was dropped.
+    pub(super) fn send(&self, message: Arc<Message<P>>) -> Result<(), Arc<Message<P>>> {
+        let kind = message.classify();
+        {
+            let mut state = self.shared.state.lock().expect("send queue lock poisoned");
+            if state.receiver_dropped {
+                return Err(message);
+            }
+            let index = PRIORITY_ORDER
+                .iter()
+                .position(|&queued_kind| queued_kind == kind)
+                .expect("every message kind has a queue");
+            let queue = &mut state.queues[index];
+            queue.messages.push_back(message);
+            queue.depth.inc();
+            state.total_depth.inc();
+        }
+        self.shared.notify.notify_one();
+        Ok(())
+    }
+}
+
+impl<P> Clone for QueueSender<P> {
+    fn clone(&self) -> Self {
+        self.shared
+            .state
+            .lock()
+            .expect("send queue lock poisoned")
+            .senders += 1;
+        QueueSender {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<P> Drop for QueueSender<P> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.senders -= 1;
+        }
+        self.shared.notify.notify_one();
+    }
+}
+
+/// The receiving half of a prioritized queue.
+#[derive(Debug)]
+pub(super) struct QueueReceiver<P> {
+    shared: Arc<Shared<P>>,
+}
+
+impl<P: Payload> QueueReceiver<P> {
+    /// Takes the next message to send, waiting for one if all queues are empty.
+    ///
+    /// Returns `None` once all senders were dropped and all queued messages were taken.
+    pub(super) async fn recv(&mut self) -> Option<Arc<Message<P>>> {
+        loop {
+            let notified = self.shared.notify.notified();
+            {
+                let mut state = self.shared.state.lock().expect("send queue lock poisoned");
+                if let Some(message) = state.pop() {
+                    return Some(message);
+                }
+                if state.senders == 0 {
+                    return None;
+                }
+            }
+            notified.await;
+        }
+    }
+}
+
+impl<P> Drop for QueueReceiver<P> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.receiver_dropped = true;
+            state.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus::Registry;
+
+    use super::*;
+    use crate::{
+        components::gossiper,
+        protocol,
+        testing::TestRng,
+        types::{Deploy, FinalitySignature},
+    };
+
+    fn message(kind: MessageKind, rng: &mut TestRng) -> Arc<Message<protocol::Message>> {
+        let payload = match kind {
+            MessageKind::FinalitySignature => protocol::Message::FinalitySignature(Box::new(
+                FinalitySignature::random_for_block(Default::default(), 0),
+            )),
+            MessageKind::DeployGossip => {
+                let deploy = Deploy::random(rng);
+                protocol::Message::DeployGossiper(gossiper::Message::Gossip(*deploy.id()))
+            }
+            MessageKind::DeployTransfer => {
+                protocol::Message::new_get_request::<Deploy>(Deploy::random(rng).id())
+                    .expect("should create request")
+            }
+            _ => panic!("unexpected kind {}", kind),
+        };
+        let message = Arc::new(Message::Payload(payload));
+        assert_eq!(kind, message.classify());
+        message
+    }
+
+    async fn receive_kinds(
+        receiver: &mut QueueReceiver<protocol::Message>,
+        count: usize,
+    ) -> Vec<MessageKind> {
+        let mut kinds = Vec::new();
+        for _ in 0..count {
+            kinds.push(receiver.recv().await.unwrap().classify());
+        }
+        kinds
+    }
+
+    #[tokio::test]
+    async fn serves_queues_by_priority_and_weight() {
+        let mut rng = TestRng::new();
+        let metrics = NetworkingMetrics::new(&Registry::new()).unwrap();
+        let weights = QueueWeights {
+            finality_signature: 2,
+            deploy_gossip: 0,
+            deploy_transfer: 1,
+            ..Default::default()
+        };
+        let (sender, mut receiver) = channel(&weights, &metrics);
+
+        for _ in 0..3 {
+            sender
+                .send(message(MessageKind::DeployTransfer, &mut rng))
+                .unwrap();
+            sender
+                .send(message(MessageKind::DeployGossip, &mut rng))
+                .unwrap();
+            sender
+                .send(message(MessageKind::FinalitySignature, &mut rng))
+                .unwrap();
+        }
+        assert_eq!(9, metrics.queued_messages.get());
+        assert_eq!(
+            3,
+            metrics
+                .queued_messages_of_kind(MessageKind::DeployGossip)
+                .get()
+        );
+
+        use MessageKind::{DeployGossip as G, DeployTransfer as T, FinalitySignature as F};
+        assert_eq!(
+            vec![F, F, G, T, F, G, T, G, T],
+            receive_kinds(&mut receiver, 9).await
+        );
+        assert_eq!(0, metrics.queued_messages.get());
+
+        drop(sender);
+        assert!(receiver.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn dropping_receiver_drops_queued_messages() {
+        let mut rng = TestRng::new();
+        let metrics = NetworkingMetrics::new(&Registry::new()).unwrap();
+        let (sender, receiver) = channel(&QueueWeights::default(), &metrics);
+
+        sender
+            .send(message(MessageKind::DeployGossip, &mut rng))
+            .unwrap();
+        drop(receiver);
+        assert_eq!(0, metrics.queued_messages.get());
+        assert!(sender
+            .send(message(MessageKind::DeployGossip, &mut rng))
+            .is_err());
+    }
+}
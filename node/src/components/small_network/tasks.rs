@@ -10,7 +10,7 @@ use std::{
     time::Duration,
 };
 
-use casper_types::PublicKey;
+use casper_types::{ProtocolVersion, PublicKey};
 use futures::{
     future::{self, Either},
     stream::{SplitSink, SplitStream},
@@ -20,12 +20,8 @@ use openssl::{
     pkey::{PKey, Private},
     ssl::Ssl,
 };
-use prometheus::IntGauge;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use tokio::{
-    net::TcpStream,
-    sync::{mpsc::UnboundedReceiver, watch},
-};
+use tokio::{net::TcpStream, sync::watch};
 use tokio_openssl::SslStream;
 use tracing::{
     debug, error_span,
@@ -34,13 +30,17 @@ use tracing::{
 };
 
 use super::{
+    allowlist::AccessControl,
     chain_info::ChainInfo,
-    counting_format::{ConnectionId, Role},
+    compression::{CompressionHandle, CompressionSettings},
+    connection_info::ConnectionInfo,
+    counting_format::{ConnectionId, ConnectionStats, Role},
     error::{ConnectionError, IoError},
     event::{IncomingConnection, OutgoingConnection},
     framed,
     limiter::LimiterHandle,
-    message::ConsensusKeyPair,
+    message::{ConsensusCertificate, ConsensusKeyPair},
+    send_queue::QueueReceiver,
     Event, FramedTransport, Message, Payload, Transport,
 };
 use crate::{
@@ -119,25 +119,54 @@ where
         return OutgoingConnection::Loopback { peer_addr };
     }
 
+    if !context.access_control.may_handshake(&peer_id) {
+        return OutgoingConnection::Failed {
+            peer_addr,
+            peer_id,
+            error: refuse_connection(&context),
+        };
+    }
+
     debug!("Outgoing TLS connection established");
 
     // Setup connection sink and stream.
     let connection_id = ConnectionId::from_connection(transport.ssl(), context.our_id, peer_id);
+    let compression = CompressionHandle::default();
+    let stats = Arc::new(ConnectionStats::default());
     let mut transport = framed::<P>(
         context.net_metrics.clone(),
+        stats.clone(),
         connection_id,
         transport,
         Role::Dialer,
         context.chain_info.maximum_net_message_size,
+        compression.clone(),
     );
 
     // Negotiate the handshake, concluding the incoming connection process.
-    match negotiate_handshake(&context, &mut transport, connection_id).await {
-        Ok((public_addr, peer_consensus_public_key)) => {
+    match negotiate_handshake(&context, &mut transport, connection_id, &compression).await {
+        Ok(HandshakeOutcome {
+            public_addr,
+            peer_consensus_public_key,
+            protocol_version,
+            supports_gossip_reconciliation,
+            max_batch_size,
+        }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("validator_id", &field::display(public_key));
             }
 
+            if !context
+                .access_control
+                .is_allowed(&peer_id, peer_consensus_public_key.as_ref())
+            {
+                return OutgoingConnection::Failed {
+                    peer_addr,
+                    peer_id,
+                    error: refuse_connection(&context),
+                };
+            }
+
             if public_addr != peer_addr {
                 // We don't need the `public_addr`, as we already connected, but warn anyway.
                 warn!(%public_addr, %peer_addr, "peer advertises a different public address than what we connected to");
@@ -146,10 +175,20 @@ where
             // Close the receiving end of the transport.
             let (sink, _stream) = transport.split();
 
+            let info = ConnectionInfo {
+                protocol_version,
+                network_name: context.chain_info.network_name.clone(),
+                is_compressed: compression.get().is_some(),
+                supports_gossip_reconciliation,
+                max_batch_size,
+                stats,
+            };
+
             OutgoingConnection::Established {
                 peer_addr,
                 peer_id,
                 peer_consensus_public_key,
+                info,
                 sink,
             }
         }
@@ -184,6 +223,22 @@ where
     pub(super) consensus_keys: Option<ConsensusKeyPair>,
     /// Weights to estimate payloads with.
     pub(super) payload_weights: PayloadWeights,
+    /// The peers allowed to connect.
+    pub(super) access_control: AccessControl,
+    /// Our compression settings, if compression is enabled.
+    pub(super) compression: Option<CompressionSettings>,
+    /// The maximum number of requests we accept in a single batch.
+    pub(super) max_batch_size: u16,
+}
+
+/// Counts and logs a connection with a peer that is not allowed to connect, and returns the error
+/// to close it with.
+fn refuse_connection<REv>(context: &NetworkContext<REv>) -> ConnectionError {
+    info!("peer is not allowed to connect, closing connection");
+    if let Some(net_metrics) = context.net_metrics.upgrade() {
+        net_metrics.refused_connections.inc();
+    }
+    ConnectionError::PeerNotAllowed
 }
 
 /// Handles an incoming connection.
@@ -216,33 +271,72 @@ where
         return IncomingConnection::Loopback;
     }
 
+    if !context.access_control.may_handshake(&peer_id) {
+        return IncomingConnection::Failed {
+            peer_addr,
+            peer_id,
+            error: refuse_connection(&context),
+        };
+    }
+
     debug!("Incoming TLS connection established");
 
     // Setup connection sink and stream.
     let connection_id = ConnectionId::from_connection(transport.ssl(), context.our_id, peer_id);
+    let compression = CompressionHandle::default();
+    let stats = Arc::new(ConnectionStats::default());
     let mut transport = framed::<P>(
         context.net_metrics.clone(),
+        stats.clone(),
         connection_id,
         transport,
         Role::Listener,
         context.chain_info.maximum_net_message_size,
+        compression.clone(),
     );
 
     // Negotiate the handshake, concluding the incoming connection process.
-    match negotiate_handshake(&context, &mut transport, connection_id).await {
-        Ok((public_addr, peer_consensus_public_key)) => {
+    match negotiate_handshake(&context, &mut transport, connection_id, &compression).await {
+        Ok(HandshakeOutcome {
+            public_addr,
+            peer_consensus_public_key,
+            protocol_version,
+            supports_gossip_reconciliation,
+            max_batch_size,
+        }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("validator_id", &field::display(public_key));
             }
 
+            if !context
+                .access_control
+                .is_allowed(&peer_id, peer_consensus_public_key.as_ref())
+            {
+                return IncomingConnection::Failed {
+                    peer_addr,
+                    peer_id,
+                    error: refuse_connection(&context),
+                };
+            }
+
             // Close the receiving end of the transport.
             let (_sink, stream) = transport.split();
 
+            let info = ConnectionInfo {
+                protocol_version,
+                network_name: context.chain_info.network_name.clone(),
+                is_compressed: compression.get().is_some(),
+                supports_gossip_reconciliation,
+                max_batch_size,
+                stats,
+            };
+
             IncomingConnection::Established {
                 peer_addr,
                 public_addr,
                 peer_id,
                 peer_consensus_public_key,
+                info,
                 stream,
             }
         }
@@ -316,19 +410,55 @@ where
     }
 }
 
+/// What we learned about a peer from its handshake.
+struct HandshakeOutcome {
+    /// The public address the peer advertised.
+    public_addr: SocketAddr,
+    /// The peer's consensus key, if it proved to hold one.
+    peer_consensus_public_key: Option<PublicKey>,
+    /// The protocol version the peer is running.
+    protocol_version: ProtocolVersion,
+    /// Whether the peer understands set-reconciliation gossip messages.
+    supports_gossip_reconciliation: bool,
+    /// The maximum number of requests the peer accepts in a single batch.
+    max_batch_size: u16,
+}
+
+/// Exchanges handshakes with the peer.
+///
+/// If both sides support compression, `compression` is set, so that all further messages are
+/// compressed.
 async fn negotiate_handshake<P, REv>(
     context: &NetworkContext<REv>,
     transport: &mut FramedTransport<P>,
     connection_id: ConnectionId,
-) -> Result<(SocketAddr, Option<PublicKey>), ConnectionError>
+    compression: &CompressionHandle,
+) -> Result<HandshakeOutcome, ConnectionError>
 where
     P: Payload,
 {
+    // Signing may require a round trip to a remote signer, so it must not block the executor.
+    let consensus_certificate = match context.consensus_keys.clone() {
+        Some(key_pair) => tokio::task::spawn_blocking(move || {
+            ConsensusCertificate::create(connection_id, &key_pair)
+        })
+        .await
+        .unwrap_or_else(|err| {
+            warn!(%err, "consensus certificate task failed");
+            None
+        }),
+        None => None,
+    };
+
     // Send down a handshake and expect one in response.
     let handshake = context.chain_info.create_handshake(
         context.public_addr,
-        context.consensus_keys.as_ref(),
-        connection_id,
+        consensus_certificate,
+        context
+            .compression
+            .as_ref()
+            .map(CompressionSettings::support),
+        context.max_batch_size,
     );
 
     io_timeout(HANDSHAKE_TIMEOUT, transport.send(Arc::new(handshake)))
@@ -344,6 +474,9 @@ where
         public_addr,
         protocol_version,
         consensus_certificate,
+        compression: remote_compression,
+        gossip_reconciliation,
+        max_batch_size,
     } = remote_handshake
     {
         debug!(%protocol_version, "handshake received");
@@ -360,7 +493,21 @@ where
             })
             .transpose()?;
 
-        Ok((public_addr, peer_consensus_public_key))
+        if let (Some(settings), Some(remote_compression)) =
+            (context.compression.as_ref(), remote_compression)
+        {
+            debug!("compressing messages");
+            // The handle is fresh, so it cannot have been set before.
+            let _ = compression.set(settings.negotiate(&remote_compression));
+        }
+
+        Ok(HandshakeOutcome {
+            public_addr,
+            peer_consensus_public_key,
+            protocol_version,
+            supports_gossip_reconciliation: gossip_reconciliation,
+            max_batch_size,
+        })
     } else {
         // Received a non-handshake, this is an error.
         Err(ConnectionError::DidNotSendHandshake)
@@ -448,6 +595,7 @@ pub(super) async fn message_reader<REv, P>(
     mut stream: SplitStream<FramedTransport<P>>,
     limiter: Box<dyn LimiterHandle>,
     mut shutdown_receiver: watch::Receiver<()>,
+    mut close_receiver: watch::Receiver<()>,
     peer_id: NodeId,
     span: Span,
 ) -> io::Result<()>
@@ -492,10 +640,16 @@ where
         Ok(())
     };
 
-    let shutdown_messages = async move { while shutdown_receiver.changed().await.is_ok() {} };
+    let shutdown_messages = async move {
+        future::select(
+            Box::pin(async move { while shutdown_receiver.changed().await.is_ok() {} }),
+            Box::pin(async move { while close_receiver.changed().await.is_ok() {} }),
+        )
+        .await
+    };
 
-    // Now we can wait for either the `shutdown` channel's remote end to do be dropped or the
-    // while loop to terminate.
+    // Now we can wait for either the `shutdown` or the `close` channel's remote end to do be
+    // dropped or the while loop to terminate.
     match future::select(Box::pin(shutdown_messages), Box::pin(read_messages)).await {
         Either::Left(_) => info!("shutting down incoming connection message reader"),
         Either::Right(_) => (),
@@ -506,18 +660,16 @@ where
 
 /// Network message sender.
 ///
-/// Reads from a channel and sends all messages, until the stream is closed or an error occurs.
+/// Reads from a prioritized queue and sends all messages, until the queue is closed or an error
+/// occurs.
 pub(super) async fn message_sender<P>(
-    mut queue: UnboundedReceiver<Arc<Message<P>>>,
+    mut queue: QueueReceiver<P>,
     mut sink: SplitSink<FramedTransport<P>, Arc<Message<P>>>,
     limiter: Box<dyn LimiterHandle>,
-    counter: IntGauge,
 ) where
     P: Payload,
 {
     while let Some(message) = queue.recv().await {
-        counter.dec();
-
         // TODO: Refactor message sending to not use `tokio_serde` anymore to avoid duplicate
         //       serialization.
         let estimated_wire_size = rmp_serde::to_vec(&message)
@@ -6,6 +6,7 @@
 use std::{
     collections::{HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
+    fs,
     time::{Duration, Instant},
 };
 
@@ -17,8 +18,8 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
 use super::{
-    chain_info::ChainInfo, Config, Event as SmallNetworkEvent, GossipedAddress, MessageKind,
-    Payload, SmallNetwork,
+    chain_info::ChainInfo, reputation::Offense, AccessMode, Config, ConnectionState, Direction,
+    Event as SmallNetworkEvent, GossipedAddress, MessageKind, Payload, SmallNetwork,
 };
 use crate::{
     components::{
@@ -27,9 +28,11 @@ use crate::{
         Component,
     },
     effect::{
-        announcements::{ControlAnnouncement, GossiperAnnouncement, NetworkAnnouncement},
+        announcements::{
+            BlocklistAnnouncement, ControlAnnouncement, GossiperAnnouncement, NetworkAnnouncement,
+        },
         requests::{NetworkRequest, StorageRequest},
-        EffectBuilder, Effects,
+        EffectBuilder, EffectExt, Effects,
     },
     protocol,
     reactor::{self, EventQueueHandle, Finalize, Reactor, Runner},
@@ -39,7 +42,7 @@ use crate::{
         ConditionCheckReactor,
     },
     types::NodeId,
-    utils::Source,
+    utils::{Source, WithDir},
     NodeRng,
 };
 
@@ -146,11 +149,12 @@ impl Reactor for TestReactor {
         let small_network_identity = SmallNetworkIdentity::new()?;
         let (net, effects) = SmallNetwork::new(
             event_queue,
-            cfg,
+            WithDir::new(".", cfg),
             None,
             registry,
             small_network_identity,
             ChainInfo::create_for_testing(),
+            1,
         )?;
         let gossiper_config = gossiper::Config::new_with_small_timeouts();
         let address_gossiper =
@@ -487,3 +491,218 @@ async fn ensure_peers_metric_is_correct() {
         net.finalize().await;
     }
 }
+
+/// Check that the connections of a node can be inspected, including their traffic.
+#[tokio::test]
+async fn connections_report_peers_and_traffic() {
+    init_logging();
+
+    let mut rng = crate::new_rng();
+    let first_node_port = testing::unused_port_on_localhost();
+
+    let mut net = Network::new();
+    net.add_node_with_config(
+        Config::default_local_net_first_node(first_node_port),
+        &mut rng,
+    )
+    .await
+    .unwrap();
+    net.add_node_with_config(Config::default_local_net(first_node_port), &mut rng)
+        .await
+        .unwrap();
+
+    // Wait until both nodes have dialed each other.
+    net.settle_on(
+        &mut rng,
+        |nodes: &Nodes<TestReactor>| {
+            nodes.values().all(|runner| {
+                let connections = runner.reactor().inner().net.connections();
+                let is_connected = |direction| {
+                    connections.iter().any(|connection| {
+                        connection.direction == direction
+                            && connection.state == ConnectionState::Connected
+                    })
+                };
+                is_connected(Direction::Outgoing) && is_connected(Direction::Incoming)
+            })
+        },
+        Duration::from_secs(20),
+    )
+    .await;
+
+    let node_ids: Vec<NodeId> = net.nodes().keys().copied().collect();
+    for (node_id, runner) in net.nodes() {
+        let other_id = node_ids.iter().find(|id| *id != node_id).unwrap();
+        let connections = runner.reactor().inner().net.connections();
+
+        for &direction in &[Direction::Outgoing, Direction::Incoming] {
+            let connection = connections
+                .iter()
+                .find(|connection| {
+                    connection.direction == direction
+                        && connection.state == ConnectionState::Connected
+                })
+                .unwrap_or_else(|| panic!("missing {:?} connection", direction));
+            let peer = connection.peer.as_ref().expect("should have peer details");
+            assert_eq!(peer.peer_id, *other_id);
+            assert_eq!(
+                peer.protocol_version,
+                ChainInfo::create_for_testing().protocol_version
+            );
+            assert!(!peer.is_active_validator);
+
+            // Both sides send and receive a handshake on every connection.
+            let protocol_traffic = peer
+                .traffic
+                .kinds
+                .iter()
+                .find(|traffic| traffic.kind == MessageKind::Protocol)
+                .expect("should have handshake traffic");
+            assert_eq!(protocol_traffic.messages_sent, 1);
+            assert_eq!(protocol_traffic.messages_received, 1);
+            assert!(protocol_traffic.bytes_received > 0);
+            assert!(peer.traffic.last_received.is_some());
+        }
+    }
+
+    net.finalize().await;
+}
+
+/// Check that all connections to a peer are closed once it is removed from the allowlist.
+#[tokio::test]
+async fn removing_peer_from_allowlist_closes_its_connections() {
+    init_logging();
+
+    let mut rng = crate::new_rng();
+    let first_node_port = testing::unused_port_on_localhost();
+    let tempdir = tempfile::tempdir().unwrap();
+    let first_allowlist_path = tempdir.path().join("allowlist1");
+    let second_allowlist_path = tempdir.path().join("allowlist2");
+
+    // The node IDs are only known once the nodes are created, so the allowlist files initially
+    // contain an unknown node, and the allowlists are updated before the nodes connect.
+    let unknown_node = hex::encode(NodeId::random(&mut rng).hash_bytes());
+    let mut net = Network::<TestReactor>::new();
+    let mut node_ids = vec![];
+    for (cfg, allowlist_path) in [
+        (
+            Config::default_local_net_first_node(first_node_port),
+            &first_allowlist_path,
+        ),
+        (
+            Config::default_local_net(first_node_port),
+            &second_allowlist_path,
+        ),
+    ] {
+        fs::write(allowlist_path, &unknown_node).unwrap();
+        let cfg = Config {
+            access_mode: AccessMode::Allowlist,
+            allowlist_path: Some(allowlist_path.clone()),
+            ..cfg
+        };
+        let (node_id, _) = net.add_node_with_config(cfg, &mut rng).await.unwrap();
+        node_ids.push(node_id);
+    }
+    for runner in net.nodes().values() {
+        let access_control = &runner.reactor().inner().net.context.access_control;
+        access_control.set_node_ids(node_ids.iter().copied().collect());
+    }
+
+    let connection_count = |nodes: &Nodes<TestReactor>, node_id: &NodeId| {
+        nodes[node_id]
+            .reactor()
+            .inner()
+            .net
+            .connections()
+            .into_iter()
+            .filter(|connection| connection.state == ConnectionState::Connected)
+            .count()
+    };
+
+    // Wait until both nodes have dialed each other.
+    net.settle_on(
+        &mut rng,
+        |nodes: &Nodes<TestReactor>| {
+            node_ids
+                .iter()
+                .all(|node_id| connection_count(nodes, node_id) == 2)
+        },
+        Duration::from_secs(20),
+    )
+    .await;
+
+    // Remove the second node from the first node's allowlist: Both the incoming and the outgoing
+    // connection must be closed.
+    fs::write(&first_allowlist_path, hex::encode(node_ids[0].hash_bytes())).unwrap();
+    net.settle_on(
+        &mut rng,
+        |nodes: &Nodes<TestReactor>| connection_count(nodes, &node_ids[0]) == 0,
+        Duration::from_secs(20),
+    )
+    .await;
+
+    net.finalize().await;
+}
+
+/// Check that the incoming connection of a banned peer is closed.
+#[tokio::test]
+async fn banning_peer_closes_its_incoming_connection() {
+    init_logging();
+
+    let mut rng = crate::new_rng();
+    let first_node_port = testing::unused_port_on_localhost();
+
+    let mut net = Network::<TestReactor>::new();
+    let (first_node_id, _) = net
+        .add_node_with_config(
+            Config::default_local_net_first_node(first_node_port),
+            &mut rng,
+        )
+        .await
+        .unwrap();
+    let (second_node_id, _) = net
+        .add_node_with_config(Config::default_local_net(first_node_port), &mut rng)
+        .await
+        .unwrap();
+
+    let has_incoming_from_second = |nodes: &Nodes<TestReactor>| {
+        nodes[&first_node_id]
+            .reactor()
+            .inner()
+            .net
+            .incoming_connections
+            .values()
+            .any(|(peer_id, _, _)| *peer_id == second_node_id)
+    };
+
+    // Wait until the second node has dialed the first one.
+    net.settle_on(&mut rng, has_incoming_from_second, Duration::from_secs(20))
+        .await;
+
+    net.process_injected_effect_on(&first_node_id, |_| {
+        async {}.event(move |_| {
+            Event::SmallNet(SmallNetworkEvent::BlocklistAnnouncement(
+                BlocklistAnnouncement::OffenseCommitted {
+                    offender: Box::new(second_node_id),
+                    offense: Offense::InvalidProposal,
+                },
+            ))
+        })
+    })
+    .await;
+    net.settle_on(
+        &mut rng,
+        |nodes: &Nodes<TestReactor>| !has_incoming_from_second(nodes),
+        Duration::from_secs(20),
+    )
+    .await;
+
+    assert!(!net.nodes()[&first_node_id]
+        .reactor()
+        .inner()
+        .net
+        .peer_consensus_keys
+        .contains_key(&second_node_id));
+
+    net.finalize().await;
+}
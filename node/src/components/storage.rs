@@ -7,7 +7,8 @@
 //! * storing and loading deploys,
 //! * [temporary until refactored] holding `DeployMetadata` for each deploy,
 //! * holding a read-only copy of the chainspec,
-//! * keeping an index of blocks by height and
+//! * keeping an index of blocks by height,
+//! * keeping a history of the seigniorage allocations received by each validator and delegator and
 //! * [unimplemented] managing disk usage by pruning blocks and deploys from storage.
 //!
 //! Any I/O performed by the component is done on the event handling thread, this is on purpose as
@@ -68,8 +69,9 @@ use tracing::{debug, error, info, warn};
 
 use casper_hashing::Digest;
 use casper_types::{
-    bytesrepr::{FromBytes, ToBytes},
-    EraId, ExecutionResult, ProtocolVersion, PublicKey, Transfer, Transform,
+    bytesrepr::{FromBytes, ToBytes, U64_SERIALIZED_LENGTH},
+    system::auction::{EraInfo, SeigniorageAllocation},
+    EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey, Transfer, Transform,
 };
 
 use crate::{
@@ -112,7 +114,7 @@ const DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE: usize = 300 * GIB;
 /// Default max state store size.
 const DEFAULT_MAX_STATE_STORE_SIZE: usize = 10 * GIB;
 /// Maximum number of allowed dbs.
-const MAX_DB_COUNT: u32 = 11;
+const MAX_DB_COUNT: u32 = 13;
 
 /// OS-specific lmdb flags.
 #[cfg(not(target_os = "macos"))]
@@ -346,6 +348,13 @@ pub(crate) struct Storage {
     /// The state storage database.
     #[data_size(skip)]
     state_store_db: Database,
+    /// The reward history database, holding the seigniorage allocations received by each public
+    /// key in each era.
+    #[data_size(skip)]
+    reward_history_db: Database,
+    /// The database of eras whose seigniorage allocations have been written to the reward history.
+    #[data_size(skip)]
+    reward_history_era_db: Database,
     /// A map of block height to block ID.
     block_height_index: BTreeMap<u64, BlockHash>,
     /// A map of era ID to switch block ID.
@@ -449,6 +458,9 @@ impl Storage {
         let deploy_hashes_db = env.create_db(Some("deploy_hashes"), DatabaseFlags::empty())?;
         let transfer_hashes_db = env.create_db(Some("transfer_hashes"), DatabaseFlags::empty())?;
         let proposer_db = env.create_db(Some("proposers"), DatabaseFlags::empty())?;
+        let reward_history_db = env.create_db(Some("reward_history"), DatabaseFlags::empty())?;
+        let reward_history_era_db =
+            env.create_db(Some("reward_history_eras"), DatabaseFlags::empty())?;
 
         // We now need to restore the block-height index. Log messages allow timing here.
         info!("reindexing block store");
@@ -572,6 +584,8 @@ impl Storage {
             deploy_metadata_db,
             transfer_db,
             state_store_db,
+            reward_history_db,
+            reward_history_era_db,
             block_height_index,
             switch_block_era_id_index,
             deploy_hash_index,
@@ -895,6 +909,38 @@ impl Storage {
                     self.get_finality_signatures(&mut self.env.begin_ro_txn()?, &block_hash)?;
                 responder.respond(result).ignore()
             }
+            StorageRequest::PutEraInfo {
+                era_id,
+                era_info,
+                responder,
+            } => {
+                self.write_era_info(era_id, &era_info)?;
+                responder.respond(()).ignore()
+            }
+            StorageRequest::GetRewardHistory {
+                public_key,
+                from_era_id,
+                to_era_id,
+                responder,
+            } => responder
+                .respond(self.get_reward_history(
+                    &mut self.env.begin_ro_txn()?,
+                    &public_key,
+                    from_era_id,
+                    to_era_id,
+                )?)
+                .ignore(),
+            StorageRequest::GetUnindexedRewardEras {
+                from_era_id,
+                to_era_id,
+                responder,
+            } => responder
+                .respond(self.get_unindexed_reward_eras(
+                    &mut self.env.begin_ro_txn()?,
+                    from_era_id,
+                    to_era_id,
+                )?)
+                .ignore(),
             StorageRequest::GetFinalizedDeploys { ttl, responder } => {
                 responder.respond(self.get_finalized_deploys(ttl)?).ignore()
             }
@@ -1348,6 +1394,125 @@ impl Storage {
     ) -> Result<Option<BlockSignatures>, Error> {
         Ok(tx.get_value(self.block_metadata_db, block_hash)?)
     }
+
+    /// Writes the seigniorage allocations of an era to the reward history, grouped by recipient.
+    ///
+    /// Writing the allocations of the same era again replaces them.
+    fn write_era_info(&self, era_id: EraId, era_info: &EraInfo) -> Result<(), Error> {
+        let mut allocations_by_recipient: BTreeMap<&PublicKey, Vec<SeigniorageAllocation>> =
+            BTreeMap::new();
+        for allocation in era_info.seigniorage_allocations() {
+            let recipient = match allocation {
+                SeigniorageAllocation::Validator {
+                    validator_public_key,
+                    ..
+                } => validator_public_key,
+                SeigniorageAllocation::Delegator {
+                    delegator_public_key,
+                    ..
+                } => delegator_public_key,
+            };
+            allocations_by_recipient
+                .entry(recipient)
+                .or_default()
+                .push(allocation.clone());
+        }
+
+        let mut txn = self.env.begin_rw_txn()?;
+        for (recipient, allocations) in allocations_by_recipient {
+            let key = reward_history_key(recipient, era_id)?;
+            let _ = txn.put_value(self.reward_history_db, &key, &allocations, true)?;
+        }
+        let _ = txn.put_value(
+            self.reward_history_era_db,
+            &era_id.value().to_be_bytes(),
+            &(),
+            true,
+        )?;
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Retrieves the seigniorage allocations received by `public_key` in the eras from
+    /// `from_era_id` to `to_era_id` inclusive, in ascending era order.
+    fn get_reward_history<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        public_key: &PublicKey,
+        from_era_id: EraId,
+        to_era_id: EraId,
+    ) -> Result<Vec<(EraId, Vec<SeigniorageAllocation>)>, Error> {
+        let start_key = reward_history_key(public_key, from_era_id)?;
+        let end_key = reward_history_key(public_key, to_era_id)?;
+        let era_id_offset = start_key.len() - U64_SERIALIZED_LENGTH;
+
+        // `Cursor::iter_from` panics if there is no entry at or after the start key, so the cursor
+        // is positioned and advanced manually.
+        let cursor = tx.open_ro_cursor(self.reward_history_db)?;
+        let mut entry = cursor.get(Some(&start_key), None, lmdb_sys::MDB_SET_RANGE);
+        let mut result = vec![];
+        loop {
+            let (raw_key, raw_val) = match entry {
+                Ok((Some(raw_key), raw_val)) if raw_key <= end_key.as_slice() => (raw_key, raw_val),
+                Ok(_) | Err(lmdb::Error::NotFound) => break,
+                Err(error) => return Err(error.into()),
+            };
+            let mut era_id_bytes = [0; U64_SERIALIZED_LENGTH];
+            era_id_bytes.copy_from_slice(&raw_key[era_id_offset..]);
+            let era_id = EraId::from(u64::from_be_bytes(era_id_bytes));
+            result.push((era_id, lmdb_ext::deserialize(raw_val)?));
+            entry = cursor.get(None, None, lmdb_sys::MDB_NEXT);
+        }
+        Ok(result)
+    }
+
+    /// Retrieves the eras from `from_era_id` to `to_era_id` inclusive which have a stored switch
+    /// block, but whose seigniorage allocations have not been written to the reward history.
+    ///
+    /// The reward history is only written as steps are executed, so this covers the eras which
+    /// ended before the node started indexing rewards, as well as those which were synced rather
+    /// than executed.
+    fn get_unindexed_reward_eras<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        from_era_id: EraId,
+        to_era_id: EraId,
+    ) -> Result<Vec<EraId>, Error> {
+        let mut result = vec![];
+        for era_id in self
+            .switch_block_era_id_index
+            .range(from_era_id..=to_era_id)
+            .map(|(era_id, _)| *era_id)
+        {
+            let indexed: Option<()> =
+                tx.get_value(self.reward_history_era_db, &era_id.value().to_be_bytes())?;
+            if indexed.is_none() {
+                result.push(era_id);
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Returns the key under which the seigniorage allocations received by `public_key` in `era_id` are
+/// stored in the reward history database.
+///
+/// The era ID is encoded in big-endian so that the entries of each public key are ordered by era.
+fn reward_history_key(public_key: &PublicKey, era_id: EraId) -> Result<Vec<u8>, Error> {
+    let mut key = lmdb_ext::serialize_bytesrepr(public_key)?;
+    key.extend_from_slice(&era_id.value().to_be_bytes());
+    Ok(key)
+}
+
+/// Returns the era info written by a step, if any.
+pub(crate) fn era_info_from_step_effect(execution_effect: &ExecutionEffect) -> Option<EraInfo> {
+    execution_effect
+        .transforms
+        .iter()
+        .find_map(|transform_entry| match &transform_entry.transform {
+            Transform::WriteEraInfo(era_info) => Some(era_info.clone()),
+            _ => None,
+        })
 }
 
 /// Inserts the relevant entries to the two indices.
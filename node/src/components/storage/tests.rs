@@ -13,7 +13,10 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use smallvec::smallvec;
 
 use casper_hashing::Digest;
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey, SecretKey};
+use casper_types::{
+    system::auction::{EraInfo, SeigniorageAllocation},
+    EraId, ExecutionResult, ProtocolVersion, PublicKey, SecretKey, U512,
+};
 
 use super::{
     construct_block_body_to_block_header_reverse_lookup, garbage_collect_block_body_v2_db,
@@ -326,6 +329,65 @@ fn put_execution_results(
     response
 }
 
+/// Stores the era info written at the end of an era in a storage component.
+fn put_era_info(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    era_id: EraId,
+    era_info: EraInfo,
+) {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::PutEraInfo {
+            era_id,
+            era_info: Box::new(era_info),
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Gets the reward history of a validator or delegator from a storage component.
+fn get_reward_history(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    public_key: PublicKey,
+    from_era_id: EraId,
+    to_era_id: EraId,
+) -> Vec<(EraId, Vec<SeigniorageAllocation>)> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetRewardHistory {
+            public_key: Box::new(public_key),
+            from_era_id,
+            to_era_id,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Gets the eras whose rewards are missing from the reward history from a storage component.
+fn get_unindexed_reward_eras(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    from_era_id: EraId,
+    to_era_id: EraId,
+) -> Vec<EraId> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetUnindexedRewardEras {
+            from_era_id,
+            to_era_id,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 /// Saves state from the storage component.
 fn save_state<T>(
     harness: &mut ComponentHarness<UnitTestEvent>,
@@ -921,6 +983,150 @@ struct StateData {
     b: i32,
 }
 
+#[test]
+fn store_and_query_reward_history() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let validator = PublicKey::random(&mut harness.rng);
+    let other_validator = PublicKey::random(&mut harness.rng);
+    let delegator = PublicKey::random(&mut harness.rng);
+
+    // The delegator only delegates in even eras.
+    let mut expected_validator_history = vec![];
+    let mut expected_delegator_history = vec![];
+    for era in 1..=5 {
+        let era_id = EraId::from(era);
+        let validator_reward =
+            SeigniorageAllocation::validator(validator.clone(), U512::from(era * 100));
+        let delegator_reward = SeigniorageAllocation::delegator(
+            delegator.clone(),
+            validator.clone(),
+            U512::from(era * 10),
+        );
+        let mut era_info = EraInfo::new();
+        era_info
+            .seigniorage_allocations_mut()
+            .push(validator_reward.clone());
+        era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::validator(
+                other_validator.clone(),
+                U512::from(era),
+            ));
+        if era % 2 == 0 {
+            era_info
+                .seigniorage_allocations_mut()
+                .push(delegator_reward.clone());
+            expected_delegator_history.push((era_id, vec![delegator_reward]));
+        }
+        expected_validator_history.push((era_id, vec![validator_reward]));
+        put_era_info(&mut harness, &mut storage, era_id, era_info);
+    }
+
+    // The whole range.
+    let history = get_reward_history(
+        &mut harness,
+        &mut storage,
+        validator.clone(),
+        EraId::from(0),
+        EraId::from(10),
+    );
+    assert_eq!(history, expected_validator_history);
+
+    let history = get_reward_history(
+        &mut harness,
+        &mut storage,
+        delegator.clone(),
+        EraId::from(0),
+        EraId::from(10),
+    );
+    assert_eq!(history, expected_delegator_history);
+
+    // A range in the middle, with inclusive bounds.
+    let history = get_reward_history(
+        &mut harness,
+        &mut storage,
+        validator.clone(),
+        EraId::from(2),
+        EraId::from(4),
+    );
+    assert_eq!(history, expected_validator_history[1..4].to_vec());
+
+    // A single era in which the delegator was not rewarded.
+    let history = get_reward_history(
+        &mut harness,
+        &mut storage,
+        delegator,
+        EraId::from(3),
+        EraId::from(3),
+    );
+    assert!(history.is_empty());
+
+    // A range after the last stored era.
+    let history = get_reward_history(
+        &mut harness,
+        &mut storage,
+        validator,
+        EraId::from(6),
+        EraId::from(8),
+    );
+    assert!(history.is_empty());
+
+    // An unknown recipient.
+    let unknown = PublicKey::random(&mut harness.rng);
+    let history = get_reward_history(
+        &mut harness,
+        &mut storage,
+        unknown,
+        EraId::from(0),
+        EraId::from(10),
+    );
+    assert!(history.is_empty());
+}
+
+#[test]
+fn should_report_unindexed_reward_eras() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    // Store the switch blocks of eras 0 to 4, but only index the rewards of eras 3 and 4.
+    for era in 0..5 {
+        let switch_block = Box::new(Block::random_with_specifics(
+            &mut harness.rng,
+            EraId::from(era),
+            era * 10 + 9,
+            ProtocolVersion::V1_0_0,
+            true,
+        ));
+        put_block(&mut harness, &mut storage, switch_block);
+    }
+    put_era_info(&mut harness, &mut storage, EraId::from(3), EraInfo::new());
+    put_era_info(&mut harness, &mut storage, EraId::from(4), EraInfo::new());
+
+    let unindexed =
+        get_unindexed_reward_eras(&mut harness, &mut storage, EraId::from(0), EraId::from(10));
+    assert_eq!(
+        unindexed,
+        vec![EraId::from(0), EraId::from(1), EraId::from(2)]
+    );
+
+    let unindexed =
+        get_unindexed_reward_eras(&mut harness, &mut storage, EraId::from(1), EraId::from(1));
+    assert_eq!(unindexed, vec![EraId::from(1)]);
+
+    // Eras without a stored switch block cannot be indexed, so they are not reported.
+    let unindexed =
+        get_unindexed_reward_eras(&mut harness, &mut storage, EraId::from(3), EraId::from(10));
+    assert!(unindexed.is_empty());
+
+    // Indexing an era's rewards removes it from the unindexed eras.
+    put_era_info(&mut harness, &mut storage, EraId::from(1), EraInfo::new());
+    let unindexed =
+        get_unindexed_reward_eras(&mut harness, &mut storage, EraId::from(0), EraId::from(10));
+    assert_eq!(unindexed, vec![EraId::from(0), EraId::from(2)]);
+}
+
 #[test]
 fn store_and_load_state_data() {
     let key1 = b"sample-key-1".to_vec();
@@ -93,19 +93,21 @@ use casper_execution_engine::{
 };
 use casper_hashing::Digest;
 use casper_types::{
-    account::Account, system::auction::EraValidators, Contract, ContractPackage, EraId,
-    ExecutionResult, Key, ProtocolVersion, PublicKey, StoredValue, Transfer, URef, U512,
+    account::Account,
+    system::auction::{EraInfo, EraValidators, SeigniorageAllocation},
+    Contract, ContractPackage, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey,
+    StoredValue, Transfer, URef, U512,
 };
 
 use crate::{
     components::{
         block_validator::ValidatingBlock,
         chainspec_loader::{CurrentRunInfo, NextUpgrade},
-        consensus::{BlockContext, ClContext, ValidatorChange},
+        consensus::{BlockContext, ClContext, ConsensusStatus, ValidatorChange},
         contract_runtime::EraValidatorsRequest,
         deploy_acceptor,
         fetcher::FetchResult,
-        small_network::GossipedAddress,
+        small_network::{Ban, ConnectionDetails, GossipedAddress, Offense},
     },
     reactor::{EventQueueHandle, QueueKind},
     types::{
@@ -122,8 +124,8 @@ use announcements::{
 };
 use requests::{
     BlockPayloadRequest, BlockProposerRequest, BlockValidationRequest, ChainspecLoaderRequest,
-    ConsensusRequest, ContractRuntimeRequest, FetcherRequest, MetricsRequest, NetworkInfoRequest,
-    NetworkRequest, StateStoreRequest, StorageRequest,
+    ConsensusRequest, ContractRuntimeRequest, FetcherRequest, GossipTarget, MetricsRequest,
+    NetworkInfoRequest, NetworkRequest, StateStoreRequest, StorageRequest,
 };
 
 use self::announcements::{BlockProposerAnnouncement, BlocklistAnnouncement};
@@ -548,14 +550,15 @@ impl<REv> EffectBuilder<REv> {
 
     /// Gossips a network message.
     ///
-    /// A low-level "gossip" function, selects `count` randomly chosen nodes on the network,
-    /// excluding the indicated ones, and sends each a copy of the message.
+    /// A low-level "gossip" function, selects `count` randomly chosen nodes of the `target` kind on
+    /// the network, excluding the indicated ones, and sends each a copy of the message.
     ///
     /// Returns the IDs of the chosen nodes.
     pub(crate) async fn gossip_message<I, P>(
         self,
         payload: P,
         count: usize,
+        target: GossipTarget,
         exclude: HashSet<I>,
     ) -> HashSet<I>
     where
@@ -567,6 +570,7 @@ impl<REv> EffectBuilder<REv> {
             |responder| NetworkRequest::Gossip {
                 payload: Box::new(payload),
                 count,
+                target,
                 exclude,
                 responder,
             },
@@ -601,6 +605,65 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the details of all connections.
+    pub(crate) async fn get_connections<I>(self) -> Vec<ConnectionDetails>
+    where
+        REv: From<NetworkInfoRequest<I>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::GetConnections { responder },
+            QueueKind::Api,
+        )
+        .await
+    }
+
+    /// Gets the banned peers.
+    pub(crate) async fn get_banned_peers<I>(self) -> Vec<Ban>
+    where
+        REv: From<NetworkInfoRequest<I>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::GetBannedPeers { responder },
+            QueueKind::Api,
+        )
+        .await
+    }
+
+    /// Bans a peer for the given duration, or the configured ban duration if `None`.
+    pub(crate) async fn ban_peer<I>(self, peer_id: I, duration: Option<TimeDiff>)
+    where
+        REv: From<NetworkInfoRequest<I>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::BanPeer {
+                peer_id: Box::new(peer_id),
+                duration,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await
+    }
+
+    /// Lifts a peer's ban. Returns `true` if the peer was banned.
+    pub(crate) async fn unban_peer<I>(self, peer_id: I) -> bool
+    where
+        REv: From<NetworkInfoRequest<I>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::UnbanPeer {
+                peer_id: Box::new(peer_id),
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await
+    }
+
     /// Announces which deploys have expired.
     pub(crate) async fn announce_expired_deploys(self, hashes: Vec<DeployHash>)
     where
@@ -1027,6 +1090,66 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Puts the seigniorage allocations of an era into the reward history in storage.
+    pub(crate) async fn put_era_info_to_storage(self, era_id: EraId, era_info: EraInfo)
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::PutEraInfo {
+                era_id,
+                era_info: Box::new(era_info),
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets the seigniorage allocations received by a validator or delegator in the given range
+    /// of eras from the reward history in storage.
+    pub(crate) async fn get_reward_history_from_storage(
+        self,
+        public_key: PublicKey,
+        from_era_id: EraId,
+        to_era_id: EraId,
+    ) -> Vec<(EraId, Vec<SeigniorageAllocation>)>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetRewardHistory {
+                public_key: Box::new(public_key),
+                from_era_id,
+                to_era_id,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets the eras in the given range which have a switch block in storage, but whose
+    /// seigniorage allocations are missing from the reward history.
+    pub(crate) async fn get_unindexed_reward_eras_from_storage(
+        self,
+        from_era_id: EraId,
+        to_era_id: EraId,
+    ) -> Vec<EraId>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetUnindexedRewardEras {
+                from_era_id,
+                to_era_id,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the requested deploys from the deploy store.
     pub(crate) async fn get_deploy_and_metadata_from_storage(
         self,
@@ -1184,6 +1307,22 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Checks whether the given deploy is included in a finalized block, even if that block has
+    /// not been executed and stored yet.
+    pub(crate) async fn is_deploy_finalized(self, deploy_hash: DeployHash) -> bool
+    where
+        REv: From<BlockProposerRequest>,
+    {
+        self.make_request(
+            |responder| BlockProposerRequest::IsFinalized {
+                deploy_hash,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Executes a finalized block.
     pub(crate) async fn execute_finalized_block(
         self,
@@ -1304,14 +1443,18 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
-    /// Announce the intent to disconnect from a specific peer, which consensus thinks is faulty.
-    pub(crate) async fn announce_disconnect_from_peer<I>(self, peer: I)
+    /// Announces that a peer committed an offense, which affects its reputation and can get it
+    /// banned.
+    pub(crate) async fn announce_offense<I>(self, offender: I, offense: Offense)
     where
         REv: From<BlocklistAnnouncement<I>>,
     {
         self.0
             .schedule(
-                BlocklistAnnouncement::OffenseCommitted(Box::new(peer)),
+                BlocklistAnnouncement::OffenseCommitted {
+                    offender: Box::new(offender),
+                    offense,
+                },
                 QueueKind::Regular,
             )
             .await
@@ -1734,6 +1877,16 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Returns diagnostic information about the current era and recently finalized blocks, or
+    /// `None` if consensus is not running.
+    pub(crate) async fn get_consensus_full_status(self) -> Option<ConsensusStatus>
+    where
+        REv: From<ConsensusRequest>,
+    {
+        self.make_request(ConsensusRequest::FullStatus, QueueKind::Regular)
+            .await
+    }
+
     /// Collects the key blocks for the eras identified by provided era IDs. Returns
     /// `Some(HashMap(era_id → block_header))` if all the blocks have been read correctly, and
     /// `None` if at least one was missing. The header for EraId `n` is from the key block for that
@@ -15,7 +15,9 @@ use casper_types::{EraId, ExecutionResult, PublicKey};
 
 use crate::{
     components::{
-        chainspec_loader::NextUpgrade, deploy_acceptor::Error, small_network::GossipedAddress,
+        chainspec_loader::NextUpgrade,
+        deploy_acceptor::Error,
+        small_network::{GossipedAddress, Offense},
     },
     effect::Responder,
     types::{
@@ -216,8 +218,13 @@ impl Display for ConsensusAnnouncement {
 /// A block-list related announcement.
 #[derive(Debug, Serialize)]
 pub(crate) enum BlocklistAnnouncement<I> {
-    /// A given peer committed a blockable offense.
-    OffenseCommitted(Box<I>),
+    /// A given peer committed an offense that affects its reputation.
+    OffenseCommitted {
+        /// The peer that committed the offense.
+        offender: Box<I>,
+        /// The kind of offense.
+        offense: Offense,
+    },
 }
 
 impl<I> Display for BlocklistAnnouncement<I>
@@ -226,8 +233,8 @@ where
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            BlocklistAnnouncement::OffenseCommitted(peer) => {
-                write!(f, "peer {} committed offense", peer)
+            BlocklistAnnouncement::OffenseCommitted { offender, offense } => {
+                write!(f, "peer {} committed offense: {}", offender, offense)
             }
         }
     }
@@ -30,20 +30,21 @@ use casper_execution_engine::{
 };
 use casper_hashing::Digest;
 use casper_types::{
-    system::auction::EraValidators, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey,
-    StoredValue, Transfer, URef,
+    system::auction::{EraInfo, EraValidators, SeigniorageAllocation},
+    EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, StoredValue, Transfer, URef,
 };
 
 use crate::{
     components::{
         block_validator::ValidatingBlock,
         chainspec_loader::CurrentRunInfo,
-        consensus::{BlockContext, ClContext, ValidatorChange},
+        consensus::{BlockContext, ClContext, ConsensusStatus, ValidatorChange},
         contract_runtime::{
             BlockAndExecutionEffects, BlockExecutionError, EraValidatorsRequest, ExecutionPreState,
         },
         deploy_acceptor::Error,
         fetcher::FetchResult,
+        small_network::{Ban, ConnectionDetails},
     },
     effect::Responder,
     rpcs::{chain::BlockIdentifier, docs::OpenRpcSchema},
@@ -78,6 +79,17 @@ impl Display for MetricsRequest {
     }
 }
 
+/// The peers a gossiped message may be sent to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub(crate) enum GossipTarget {
+    /// Any connected peer.
+    All,
+    /// Only peers which understand set-reconciliation gossip messages.
+    Reconciling,
+    /// Only peers which do not understand set-reconciliation gossip messages.
+    NonReconciling,
+}
+
 const _NETWORK_EVENT_SIZE: usize = mem::size_of::<NetworkRequest<NodeId, String>>();
 const_assert!(_NETWORK_EVENT_SIZE < 89);
 
@@ -111,6 +123,8 @@ pub(crate) enum NetworkRequest<I, P> {
         payload: Box<P>,
         /// Number of peers to gossip to. This is an upper bound, otherwise best-effort.
         count: usize,
+        /// The kind of peers to gossip to.
+        target: GossipTarget,
         /// Node IDs of nodes to exclude from gossiping to.
         #[serde(skip_serializing)]
         exclude: HashSet<I>,
@@ -145,11 +159,13 @@ impl<I, P> NetworkRequest<I, P> {
             NetworkRequest::Gossip {
                 payload,
                 count,
+                target,
                 exclude,
                 responder,
             } => NetworkRequest::Gossip {
                 payload: Box::new(wrap_payload(*payload)),
                 count,
+                target,
                 exclude,
                 responder,
             },
@@ -191,6 +207,32 @@ pub(crate) enum NetworkInfoRequest<I> {
         /// Responds with a vector in a random order.
         responder: Responder<Vec<I>>,
     },
+    /// Get the details of all connections, including outgoing ones not yet established.
+    GetConnections {
+        /// Responder to be called with the connection details.
+        responder: Responder<Vec<ConnectionDetails>>,
+    },
+    /// Get the banned peers.
+    GetBannedPeers {
+        /// Responder to be called with the current bans.
+        responder: Responder<Vec<Ban>>,
+    },
+    /// Ban a peer.
+    BanPeer {
+        /// The peer to ban.
+        peer_id: Box<I>,
+        /// How long to ban the peer for. If `None`, the configured ban duration is used.
+        duration: Option<TimeDiff>,
+        /// Responder to be called once the peer is banned.
+        responder: Responder<()>,
+    },
+    /// Lift a peer's ban.
+    UnbanPeer {
+        /// The peer to unban.
+        peer_id: Box<I>,
+        /// Responder to be called with `true` if the peer was banned.
+        responder: Responder<bool>,
+    },
 }
 
 impl<I> Display for NetworkInfoRequest<I>
@@ -203,6 +245,14 @@ where
             NetworkInfoRequest::GetPeersInRandomOrder { responder: _ } => {
                 write!(formatter, "get peers in random order")
             }
+            NetworkInfoRequest::GetConnections { responder: _ } => {
+                write!(formatter, "get connections")
+            }
+            NetworkInfoRequest::GetBannedPeers { responder: _ } => {
+                write!(formatter, "get banned peers")
+            }
+            NetworkInfoRequest::BanPeer { peer_id, .. } => write!(formatter, "ban {}", peer_id),
+            NetworkInfoRequest::UnbanPeer { peer_id, .. } => write!(formatter, "unban {}", peer_id),
         }
     }
 }
@@ -356,6 +406,38 @@ pub(crate) enum StorageRequest {
         /// stored.
         responder: Responder<bool>,
     },
+    /// Store the seigniorage allocations of an era in the reward history.
+    PutEraInfo {
+        /// The era in which the rewards were allocated.
+        era_id: EraId,
+        /// The era info written by the step at the end of the era.
+        era_info: Box<EraInfo>,
+        /// Responder to call when the allocations have been stored.
+        responder: Responder<()>,
+    },
+    /// Retrieve the seigniorage allocations received by a validator or delegator in a range of
+    /// eras.
+    GetRewardHistory {
+        /// The public key of the validator or delegator.
+        public_key: Box<PublicKey>,
+        /// The first era of the range.
+        from_era_id: EraId,
+        /// The last era of the range, inclusive.
+        to_era_id: EraId,
+        /// Responder to call with the allocations, per era and in ascending era order.  Eras in
+        /// which the key received nothing are omitted.
+        responder: Responder<Vec<(EraId, Vec<SeigniorageAllocation>)>>,
+    },
+    /// Retrieve the eras in a range which have a stored switch block, but whose seigniorage
+    /// allocations are missing from the reward history.
+    GetUnindexedRewardEras {
+        /// The first era of the range.
+        from_era_id: EraId,
+        /// The last era of the range, inclusive.
+        to_era_id: EraId,
+        /// Responder to call with the unindexed eras, in ascending era order.
+        responder: Responder<Vec<EraId>>,
+    },
 }
 
 impl Display for StorageRequest {
@@ -422,6 +504,28 @@ impl Display for StorageRequest {
             StorageRequest::GetFinalizedDeploys { ttl, .. } => {
                 write!(formatter, "get finalized deploys, ttl: {:?}", ttl)
             }
+            StorageRequest::PutEraInfo { era_id, .. } => {
+                write!(formatter, "put era info for {}", era_id)
+            }
+            StorageRequest::GetRewardHistory {
+                public_key,
+                from_era_id,
+                to_era_id,
+                ..
+            } => write!(
+                formatter,
+                "get reward history for {} from {} to {}",
+                public_key, from_era_id, to_era_id
+            ),
+            StorageRequest::GetUnindexedRewardEras {
+                from_era_id,
+                to_era_id,
+                ..
+            } => write!(
+                formatter,
+                "get unindexed reward eras from {} to {}",
+                from_era_id, to_era_id
+            ),
         }
     }
 }
@@ -488,6 +592,14 @@ pub(crate) struct BlockPayloadRequest {
 pub(crate) enum BlockProposerRequest {
     /// Request a list of deploys to propose in a new block.
     RequestBlockPayload(BlockPayloadRequest),
+    /// Request whether a deploy is included in a finalized block, which may not have been executed
+    /// yet.
+    IsFinalized {
+        /// The hash of the deploy.
+        deploy_hash: DeployHash,
+        /// Responder to call with the result.
+        responder: Responder<bool>,
+    },
 }
 
 impl Display for BlockProposerRequest {
@@ -506,6 +618,9 @@ impl Display for BlockProposerRequest {
                 context.height(),
                 next_finalized
             ),
+            BlockProposerRequest::IsFinalized { deploy_hash, .. } => {
+                write!(formatter, "is deploy {} finalized", deploy_hash)
+            }
         }
     }
 }
@@ -582,6 +697,17 @@ pub(crate) enum RpcRequest<I> {
         /// Responder to call with the result.
         responder: Responder<Option<(Deploy, DeployMetadata)>>,
     },
+    /// Return the seigniorage allocations received by a validator or delegator in a range of eras.
+    GetRewardHistory {
+        /// The public key of the validator or delegator.
+        public_key: Box<PublicKey>,
+        /// The first era of the range.
+        from_era_id: EraId,
+        /// The last era of the range, inclusive.
+        to_era_id: EraId,
+        /// Responder to call with the result.
+        responder: Responder<Vec<(EraId, Vec<SeigniorageAllocation>)>>,
+    },
     /// Return the connected peers.
     GetPeers {
         /// Responder to call with the result.
@@ -639,6 +765,16 @@ impl<I> Display for RpcRequest<I> {
                 state_root_hash, purse_uref
             ),
             RpcRequest::GetDeploy { hash, .. } => write!(formatter, "get {}", hash),
+            RpcRequest::GetRewardHistory {
+                public_key,
+                from_era_id,
+                to_era_id,
+                ..
+            } => write!(
+                formatter,
+                "get reward history for {} from {} to {}",
+                public_key, from_era_id, to_era_id
+            ),
             RpcRequest::GetPeers { .. } => write!(formatter, "get peers"),
             RpcRequest::GetStatus { .. } => write!(formatter, "get status"),
         }
@@ -923,6 +1059,8 @@ pub(crate) enum ConsensusRequest {
     Status(Responder<Option<(PublicKey, Option<TimeDiff>)>>),
     /// Request for a list of validator status changes, by public key.
     ValidatorChanges(Responder<BTreeMap<PublicKey, Vec<(EraId, ValidatorChange)>>>),
+    /// Request for diagnostic information about the current era and recently finalized blocks.
+    FullStatus(Responder<Option<ConsensusStatus>>),
 }
 
 /// ChainspecLoader component requests.
@@ -37,6 +37,8 @@ pub(crate) mod utils;
 pub mod cli;
 pub mod crypto;
 pub mod types;
+#[cfg(feature = "consensus-sim")]
+pub use components::consensus::consensus_sim;
 pub use components::rpc_server::rpcs;
 
 use std::sync::{
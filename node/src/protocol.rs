@@ -1,6 +1,9 @@
 //! A network message type used for communication between nodes
 
-use std::fmt::{self, Display, Formatter};
+use std::{
+    convert::TryInto,
+    fmt::{self, Display, Formatter},
+};
 
 use derive_more::From;
 use fmt::Debug;
@@ -44,6 +47,14 @@ pub(crate) enum Message {
     /// Finality signature.
     #[from]
     FinalitySignature(Box<FinalitySignature>),
+    /// Request to get several items of the same type from a peer, answered by one `GetResponse`
+    /// per item.
+    GetRequestBatch {
+        /// The type tag of the requested items.
+        tag: Tag,
+        /// The serialized IDs of the requested items.
+        serialized_ids: Vec<Vec<u8>>,
+    },
 }
 
 impl Payload for Message {
@@ -53,7 +64,9 @@ impl Payload for Message {
             Message::Consensus(_) => MessageKind::Consensus,
             Message::DeployGossiper(_) => MessageKind::DeployGossip,
             Message::AddressGossiper(_) => MessageKind::AddressGossip,
-            Message::GetRequest { tag, .. } | Message::GetResponse { tag, .. } => {
+            Message::GetRequest { tag, .. }
+            | Message::GetResponse { tag, .. }
+            | Message::GetRequestBatch { tag, .. } => {
                 match tag {
                     Tag::Deploy => MessageKind::DeployTransfer,
                     Tag::Block => MessageKind::BlockTransfer,
@@ -64,7 +77,7 @@ impl Payload for Message {
                     Tag::BlockHeaderAndFinalitySignaturesByHeight => MessageKind::BlockTransfer,
                 }
             }
-            Message::FinalitySignature(_) => MessageKind::Consensus,
+            Message::FinalitySignature(_) => MessageKind::FinalitySignature,
         }
     }
 
@@ -74,7 +87,8 @@ impl Payload for Message {
             Message::Consensus(_) => weights.consensus,
             Message::DeployGossiper(_) => 0,
             Message::AddressGossiper(_) => 0,
-            Message::GetRequest { tag, .. } | Message::GetResponse { tag, .. } => match tag {
+            Message::GetRequest { tag, .. } => get_request_weight(*tag, weights),
+            Message::GetResponse { tag, .. } => match tag {
                 Tag::Deploy => weights.deploy_requests,
                 Tag::Block => 0,
                 Tag::GossipedAddress => 0,
@@ -83,8 +97,50 @@ impl Payload for Message {
                 Tag::BlockHeaderAndFinalitySignaturesByHeight => 0,
             },
             Message::FinalitySignature(_) => 0,
+            // Every request in a batch is as expensive to answer as an individual one.
+            Message::GetRequestBatch {
+                tag,
+                serialized_ids,
+            } => get_request_weight(*tag, weights)
+                .saturating_mul(serialized_ids.len().try_into().unwrap_or(u32::MAX)),
         }
     }
+
+    fn split_batch(&self, max_batch_size: u16) -> Option<Vec<Self>> {
+        let (tag, serialized_ids) = match self {
+            Message::GetRequestBatch {
+                tag,
+                serialized_ids,
+            } if serialized_ids.len() > usize::from(max_batch_size) => (*tag, serialized_ids),
+            _ => return None,
+        };
+        let chunks = serialized_ids
+            .chunks(usize::from(max_batch_size.max(1)))
+            .map(|chunk| match chunk {
+                [serialized_id] => Message::GetRequest {
+                    tag,
+                    serialized_id: serialized_id.clone(),
+                },
+                _ => Message::GetRequestBatch {
+                    tag,
+                    serialized_ids: chunk.to_vec(),
+                },
+            })
+            .collect();
+        Some(chunks)
+    }
+}
+
+/// Returns the weight of a single request for an item with the given tag.
+fn get_request_weight(tag: Tag, weights: &PayloadWeights) -> u32 {
+    match tag {
+        Tag::Deploy => weights.deploy_requests,
+        Tag::Block => weights.block_requests,
+        Tag::GossipedAddress => 0,
+        Tag::BlockByHeight => weights.block_requests,
+        Tag::BlockHeaderByHash => weights.block_requests,
+        Tag::BlockHeaderAndFinalitySignaturesByHeight => weights.block_requests,
+    }
 }
 
 impl Message {
@@ -95,6 +151,16 @@ impl Message {
         })
     }
 
+    pub(crate) fn new_get_request_batch<T: Item>(ids: &[T::Id]) -> Result<Self, bincode::Error> {
+        Ok(Message::GetRequestBatch {
+            tag: T::TAG,
+            serialized_ids: ids
+                .iter()
+                .map(bincode::serialize)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
     pub(crate) fn new_get_response<T: Item>(item: &T) -> Result<Self, bincode::Error> {
         Ok(Message::GetResponse {
             tag: T::TAG,
@@ -134,6 +200,17 @@ impl Debug for Message {
             Message::FinalitySignature(fs) => {
                 f.debug_tuple("FinalitySignature").field(&fs).finish()
             }
+            Message::GetRequestBatch {
+                tag,
+                serialized_ids,
+            } => f
+                .debug_struct("GetRequestBatch")
+                .field("tag", tag)
+                .field(
+                    "serialized_ids",
+                    &serialized_ids.iter().map(HexFmt).collect::<Vec<_>>(),
+                )
+                .finish(),
         }
     }
 }
@@ -156,6 +233,72 @@ impl Display for Message {
             Message::FinalitySignature(fs) => {
                 write!(f, "FinalitySignature::({})", fs)
             }
+            Message::GetRequestBatch {
+                tag,
+                serialized_ids,
+            } => write!(
+                f,
+                "GetRequestBatch({}, {} items)",
+                tag,
+                serialized_ids.len()
+            ),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(tag: Tag, count: u8) -> Message {
+        Message::GetRequestBatch {
+            tag,
+            serialized_ids: (0..count).map(|id| vec![id]).collect(),
+        }
+    }
+
+    #[test]
+    fn should_split_batches_larger_than_accepted() {
+        assert!(batch(Tag::Deploy, 3).split_batch(3).is_none());
+
+        let parts = batch(Tag::Deploy, 5).split_batch(2).unwrap();
+        let sizes: Vec<_> = parts
+            .iter()
+            .map(|part| match part {
+                Message::GetRequestBatch { serialized_ids, .. } => serialized_ids.len(),
+                Message::GetRequest { .. } => 1,
+                other => panic!("unexpected part {}", other),
+            })
+            .collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        assert!(matches!(parts[2], Message::GetRequest { .. }));
+
+        // Peers not accepting batches get individual requests.
+        let parts = batch(Tag::Block, 3).split_batch(1).unwrap();
+        assert_eq!(parts.len(), 3);
+        assert!(parts
+            .iter()
+            .all(|part| matches!(part, Message::GetRequest { .. })));
+    }
+
+    #[test]
+    fn should_weight_batches_per_item() {
+        let weights = PayloadWeights {
+            consensus: 0,
+            deploy_requests: 2,
+            block_requests: 3,
+        };
+        assert_eq!(
+            batch(Tag::Deploy, 4).incoming_resource_estimate(&weights),
+            8
+        );
+        assert_eq!(
+            batch(Tag::Block, 4).incoming_resource_estimate(&weights),
+            12
+        );
+        assert_eq!(
+            batch(Tag::BlockByHeight, 1).incoming_resource_estimate(&weights),
+            3
+        );
+    }
+}
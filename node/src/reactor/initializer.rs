@@ -15,7 +15,7 @@ use casper_execution_engine::core::engine_state;
 use crate::{
     components::{
         chainspec_loader::{self, ChainspecLoader},
-        contract_runtime::{self, ContractRuntime, ContractRuntimeAnnouncement},
+        contract_runtime::{self, ContractRuntime, ContractRuntimeAnnouncement, SlashingConfig},
         gossiper,
         small_network::{GossipedAddress, SmallNetworkIdentity, SmallNetworkIdentityError},
         storage::{self, Storage},
@@ -224,6 +224,8 @@ impl Reactor {
             chainspec_loader.chainspec().wasm_config,
             chainspec_loader.chainspec().system_costs_config,
             chainspec_loader.chainspec().core_config.max_associated_keys,
+            SlashingConfig::from(&chainspec_loader.chainspec().core_config),
+            contract_runtime::inactivity_policy(&chainspec_loader.chainspec().core_config),
             registry,
         )?;
 
@@ -28,20 +28,21 @@ use crate::{
         deploy_acceptor::{self, DeployAcceptor},
         event_stream_server,
         event_stream_server::{DeployGetter, EventStreamServer},
-        fetcher::{self, Fetcher},
+        fetcher::{self, Fetcher, SharedPeerStats},
         gossiper::{self, Gossiper},
         linear_chain,
         linear_chain_sync::{self, LinearChainSync},
         metrics::Metrics,
         rest_server::{self, RestServer},
-        small_network::{self, GossipedAddress, SmallNetwork, SmallNetworkIdentity},
+        small_network::{self, GossipedAddress, Offense, SmallNetwork, SmallNetworkIdentity},
         storage::{self, Storage},
         Component,
     },
     effect::{
         announcements::{
-            ChainspecLoaderAnnouncement, ControlAnnouncement, DeployAcceptorAnnouncement,
-            GossiperAnnouncement, LinearChainAnnouncement, LinearChainBlock, NetworkAnnouncement,
+            BlocklistAnnouncement, ChainspecLoaderAnnouncement, ControlAnnouncement,
+            DeployAcceptorAnnouncement, GossiperAnnouncement, LinearChainAnnouncement,
+            LinearChainBlock, NetworkAnnouncement,
         },
         requests::{
             BlockProposerRequest, BlockValidationRequest, ChainspecLoaderRequest, ConsensusRequest,
@@ -196,6 +197,10 @@ pub(crate) enum JoinerEvent {
     /// Consensus request.
     #[from]
     ConsensusRequest(#[serde(skip_serializing)] ConsensusRequest),
+
+    /// Blocklist announcement.
+    #[from]
+    BlocklistAnnouncement(#[serde(skip_serializing)] BlocklistAnnouncement<NodeId>),
 }
 
 impl ReactorEvent for JoinerEvent {
@@ -240,6 +245,7 @@ impl ReactorEvent for JoinerEvent {
             JoinerEvent::LinearChainAnnouncement(_) => "LinearChainAnnouncement",
             JoinerEvent::ChainspecLoaderAnnouncement(_) => "ChainspecLoaderAnnouncement",
             JoinerEvent::ConsensusRequest(_) => "ConsensusRequest",
+            JoinerEvent::BlocklistAnnouncement(_) => "BlocklistAnnouncement",
         }
     }
 }
@@ -332,6 +338,9 @@ impl Display for JoinerEvent {
             }
             JoinerEvent::StateStoreRequest(req) => write!(f, "state store request: {}", req),
             JoinerEvent::ConsensusRequest(req) => write!(f, "consensus request: {:?}", req),
+            JoinerEvent::BlocklistAnnouncement(ann) => {
+                write!(f, "blocklist announcement: {}", ann)
+            }
         }
     }
 }
@@ -409,14 +418,18 @@ impl reactor::Reactor for Reactor {
 
         let (small_network, small_network_effects) = SmallNetwork::new(
             event_queue,
-            config.network.clone(),
+            WithDir::new(&root, config.network.clone()),
             Some(WithDir::new(&root, &config.consensus)),
             registry,
             small_network_identity,
             chainspec_loader.chainspec().as_ref(),
+            config.fetcher.max_batch_size(),
         )?;
 
-        let linear_chain_fetcher = Fetcher::new("linear_chain", config.fetcher, registry)?;
+        // All fetchers and the linear chain synchronizer learn which peers answer best together.
+        let peer_stats = SharedPeerStats::default();
+        let linear_chain_fetcher =
+            Fetcher::new("linear_chain", config.fetcher, peer_stats.clone(), registry)?;
 
         let mut effects = reactor::wrap_effects(JoinerEvent::SmallNetwork, small_network_effects);
 
@@ -466,20 +479,30 @@ impl reactor::Reactor for Reactor {
 
         let block_validator = BlockValidator::new(Arc::clone(chainspec_loader.chainspec()));
 
-        let deploy_fetcher = Fetcher::new("deploy", config.fetcher, registry)?;
+        let deploy_fetcher = Fetcher::new("deploy", config.fetcher, peer_stats.clone(), registry)?;
 
-        let block_by_height_fetcher = Fetcher::new("block_by_height", config.fetcher, registry)?;
+        let block_by_height_fetcher = Fetcher::new(
+            "block_by_height",
+            config.fetcher,
+            peer_stats.clone(),
+            registry,
+        )?;
 
         let block_header_and_finality_signatures_by_height_fetcher: Fetcher<
             BlockHeaderWithMetadata,
         > = Fetcher::new(
             "block_header_and_finality_signatures_by_height",
             config.fetcher,
+            peer_stats.clone(),
             registry,
         )?;
 
-        let block_header_by_hash_fetcher: Fetcher<BlockHeader> =
-            Fetcher::new("block_header_by_hash", config.fetcher, registry)?;
+        let block_header_by_hash_fetcher: Fetcher<BlockHeader> = Fetcher::new(
+            "block_header_by_hash",
+            config.fetcher,
+            peer_stats.clone(),
+            registry,
+        )?;
 
         let deploy_acceptor = DeployAcceptor::new(
             config.deploy_acceptor,
@@ -509,6 +532,7 @@ impl reactor::Reactor for Reactor {
             maybe_next_activation_point,
             chainspec_loader.initial_execution_pre_state(),
             config.linear_chain_sync,
+            peer_stats,
         )?;
 
         effects.extend(reactor::wrap_effects(
@@ -690,9 +714,25 @@ impl reactor::Reactor for Reactor {
                 DeployAcceptorAnnouncement::InvalidDeploy { deploy, source },
             ) => {
                 let deploy_hash = *deploy.id();
-                let peer = source;
-                warn!(?deploy_hash, ?peer, "Invalid deploy received from a peer.");
-                Effects::new()
+                warn!(
+                    ?deploy_hash,
+                    ?source,
+                    "Invalid deploy received from a peer."
+                );
+                match source {
+                    Source::Peer(peer) => {
+                        let ann = BlocklistAnnouncement::OffenseCommitted {
+                            offender: Box::new(peer),
+                            offense: Offense::InvalidDeploy,
+                        };
+                        self.dispatch_event(
+                            effect_builder,
+                            rng,
+                            JoinerEvent::SmallNetwork(ann.into()),
+                        )
+                    }
+                    Source::Client | Source::Ourself => Effects::new(),
+                }
             }
             JoinerEvent::Storage(event) => reactor::wrap_effects(
                 JoinerEvent::Storage,
@@ -793,18 +833,37 @@ impl reactor::Reactor for Reactor {
                     era_id,
                     execution_effect,
                 },
-            ) => self.dispatch_event(
-                effect_builder,
-                rng,
-                JoinerEvent::EventStreamServer(event_stream_server::Event::Step {
-                    era_id,
-                    execution_effect,
-                }),
-            ),
+            ) => {
+                let maybe_era_info = storage::era_info_from_step_effect(&execution_effect);
+                let mut effects = self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    JoinerEvent::EventStreamServer(event_stream_server::Event::Step {
+                        era_id,
+                        execution_effect,
+                    }),
+                );
+                if let Some(era_info) = maybe_era_info {
+                    effects.extend(
+                        effect_builder
+                            .put_era_info_to_storage(era_id, era_info)
+                            .ignore(),
+                    );
+                }
+                effects
+            }
             JoinerEvent::LinearChain(event) => reactor::wrap_effects(
                 JoinerEvent::LinearChain,
                 self.linear_chain.handle_event(effect_builder, rng, event),
             ),
+            JoinerEvent::BlockProposerRequest(BlockProposerRequest::IsFinalized {
+                responder,
+                ..
+            }) => {
+                // There is no block proposer while joining, so only stored blocks are known to be
+                // finalized.
+                responder.respond(false).ignore()
+            }
             JoinerEvent::BlockProposerRequest(request) => {
                 // Consensus component should not be trying to create new blocks during joining
                 // phase.
@@ -915,6 +974,13 @@ impl reactor::Reactor for Reactor {
                 // no consensus, respond with empty map
                 responder.respond(BTreeMap::new()).ignore()
             }
+            JoinerEvent::ConsensusRequest(ConsensusRequest::FullStatus(responder)) => {
+                // no consensus, respond with None
+                responder.respond(None).ignore()
+            }
+            JoinerEvent::BlocklistAnnouncement(ann) => {
+                self.dispatch_event(effect_builder, rng, JoinerEvent::SmallNetwork(ann.into()))
+            }
         }
     }
 
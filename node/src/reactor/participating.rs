@@ -30,17 +30,17 @@ use crate::{
         block_proposer::{self, BlockProposer},
         block_validator::{self, BlockValidator},
         chainspec_loader::{self, ChainspecLoader},
-        consensus::{self, EraSupervisor, HighwayProtocol},
+        consensus::{self, EraSupervisor},
         contract_runtime::{ContractRuntime, ContractRuntimeAnnouncement, ExecutionPreState},
         deploy_acceptor::{self, DeployAcceptor},
         event_stream_server::{self, EventStreamServer},
-        fetcher::{self, Fetcher},
+        fetcher::{self, Fetcher, SharedPeerStats},
         gossiper::{self, Gossiper},
         linear_chain,
         metrics::Metrics,
         rest_server::{self, RestServer},
         rpc_server::{self, RpcServer},
-        small_network::{self, GossipedAddress, SmallNetwork, SmallNetworkIdentity},
+        small_network::{self, GossipedAddress, Offense, SmallNetwork, SmallNetworkIdentity},
         storage::{self, Storage},
         Component,
     },
@@ -417,6 +417,9 @@ pub(crate) struct Reactor {
     linear_chain: LinearChainComponent<NodeId>,
 
     // Non-components.
+    /// The maximum number of requests we accept from a peer in a single batch.
+    max_batch_size: u16,
+
     #[data_size(skip)] // Never allocates heap data.
     memory_metrics: MemoryMetrics,
 
@@ -499,11 +502,17 @@ impl reactor::Reactor for Reactor {
             registry,
         )?;
 
-        let deploy_fetcher = Fetcher::new("deploy", config.fetcher, registry)?;
+        let deploy_fetcher = Fetcher::new(
+            "deploy",
+            config.fetcher,
+            SharedPeerStats::default(),
+            registry,
+        )?;
         let deploy_gossiper = Gossiper::new_for_partial_items(
             "deploy_gossiper",
             config.gossip,
             gossiper::get_deploy_from_storage::<Deploy, ParticipatingEvent>,
+            gossiper::fetch_deploy_from_peer::<ParticipatingEvent>,
             registry,
         )?;
         let (block_proposer, block_proposer_effects) = BlockProposer::new(
@@ -524,11 +533,12 @@ impl reactor::Reactor for Reactor {
 
         let (small_network, small_network_effects) = SmallNetwork::new(
             event_queue,
-            config.network,
+            WithDir::new(&root, config.network),
             Some(WithDir::new(&root, &config.consensus)),
             registry,
             small_network_identity,
             chainspec_loader.chainspec().as_ref(),
+            config.fetcher.max_batch_size(),
         )?;
 
         let mut effects =
@@ -545,7 +555,7 @@ impl reactor::Reactor for Reactor {
             maybe_latest_block_header.as_ref(),
             maybe_next_activation_point,
             registry,
-            Box::new(HighwayProtocol::new_boxed),
+            Box::new(consensus::new_consensus_protocol),
         )?;
         effects.extend(reactor::wrap_effects(
             ParticipatingEvent::Consensus,
@@ -605,6 +615,7 @@ impl reactor::Reactor for Reactor {
                 block_proposer,
                 block_validator,
                 linear_chain,
+                max_batch_size: config.fetcher.max_batch_size(),
                 memory_metrics,
                 event_queue_metrics,
             },
@@ -957,6 +968,42 @@ impl reactor::Reactor for Reactor {
                     Message::FinalitySignature(fs) => ParticipatingEvent::LinearChain(
                         linear_chain::Event::FinalitySignatureReceived(fs, true),
                     ),
+                    Message::GetRequestBatch {
+                        tag,
+                        serialized_ids,
+                    } => {
+                        // We announced the batch size we accept in the handshake.
+                        if serialized_ids.len() > usize::from(self.max_batch_size) {
+                            warn!(
+                                %sender,
+                                batch_size = serialized_ids.len(),
+                                "rejecting oversized request batch"
+                            );
+                            let ann = BlocklistAnnouncement::OffenseCommitted {
+                                offender: Box::new(sender),
+                                offense: Offense::OversizedRequestBatch,
+                            };
+                            return self.dispatch_event(
+                                effect_builder,
+                                rng,
+                                ParticipatingEvent::SmallNetwork(ann.into()),
+                            );
+                        }
+                        // Batched requests are answered exactly like individual ones.
+                        return serialized_ids
+                            .into_iter()
+                            .flat_map(|serialized_id| {
+                                let payload = Message::GetRequest { tag, serialized_id };
+                                self.dispatch_event(
+                                    effect_builder,
+                                    rng,
+                                    ParticipatingEvent::NetworkAnnouncement(
+                                        NetworkAnnouncement::MessageReceived { sender, payload },
+                                    ),
+                                )
+                            })
+                            .collect();
+                    }
                 };
                 self.dispatch_event(effect_builder, rng, reactor_event)
             }
@@ -1043,11 +1090,21 @@ impl reactor::Reactor for Reactor {
                 effects
             }
             ParticipatingEvent::DeployAcceptorAnnouncement(
-                DeployAcceptorAnnouncement::InvalidDeploy {
-                    deploy: _,
-                    source: _,
-                },
-            ) => Effects::new(),
+                DeployAcceptorAnnouncement::InvalidDeploy { deploy: _, source },
+            ) => match source {
+                Source::Peer(peer) => {
+                    let ann = BlocklistAnnouncement::OffenseCommitted {
+                        offender: Box::new(peer),
+                        offense: Offense::InvalidDeploy,
+                    };
+                    self.dispatch_event(
+                        effect_builder,
+                        rng,
+                        ParticipatingEvent::SmallNetwork(ann.into()),
+                    )
+                }
+                Source::Client | Source::Ourself => Effects::new(),
+            },
             ParticipatingEvent::ConsensusAnnouncement(consensus_announcement) => {
                 match consensus_announcement {
                     ConsensusAnnouncement::Finalized(block) => {
@@ -1121,12 +1178,21 @@ impl reactor::Reactor for Reactor {
                     execution_effect,
                 },
             ) => {
+                let maybe_era_info = storage::era_info_from_step_effect(&execution_effect);
                 let reactor_event =
                     ParticipatingEvent::EventStreamServer(event_stream_server::Event::Step {
                         era_id,
                         execution_effect,
                     });
-                self.dispatch_event(effect_builder, rng, reactor_event)
+                let mut effects = self.dispatch_event(effect_builder, rng, reactor_event);
+                if let Some(era_info) = maybe_era_info {
+                    effects.extend(
+                        effect_builder
+                            .put_era_info_to_storage(era_id, era_info)
+                            .ignore(),
+                    );
+                }
+                effects
             }
             ParticipatingEvent::DeployGossiperAnnouncement(
                 GossiperAnnouncement::NewCompleteItem(gossiped_deploy_id),
@@ -28,7 +28,7 @@ use crate::{
         self, filter_reactor::FilterReactor, network::Network, ConditionCheckReactor, TestRng,
     },
     types::{
-        chainspec::{AccountConfig, AccountsConfig, ValidatorConfig},
+        chainspec::{AccountConfig, AccountsConfig, ConsensusProtocolName, ValidatorConfig},
         ActivationPoint, BlockHeader, Chainspec, ExitCode, Timestamp,
     },
     utils::{External, Loadable, WithDir, RESOURCES_PATH},
@@ -289,6 +289,85 @@ async fn run_participating_network() {
         .await;
 }
 
+#[tokio::test]
+async fn run_simple_consensus_network() {
+    testing::init_logging();
+
+    let mut rng = crate::new_rng();
+
+    // Instantiate a new chain with a fixed size, running the simple protocol.
+    const NETWORK_SIZE: usize = 5;
+    let mut chain = TestChain::new(&mut rng, NETWORK_SIZE);
+    chain.chainspec_mut().core_config.consensus_protocol = ConsensusProtocolName::Simple;
+
+    let mut net = chain
+        .create_initialized_network(&mut rng)
+        .await
+        .expect("network initialization failed");
+
+    net.settle_on(
+        &mut rng,
+        is_in_era(EraId::from(2)),
+        Duration::from_secs(120),
+    )
+    .await;
+
+    // All nodes agree on the switch blocks, and all validators took part.
+    let switch_blocks = SwitchBlocks::collect(net.nodes(), 2);
+    for era_number in 0..2 {
+        assert_eq!(switch_blocks.equivocators(era_number), []);
+        assert_eq!(switch_blocks.inactive_validators(era_number), []);
+    }
+}
+
+#[tokio::test]
+async fn run_simple_consensus_network_with_offline_validator() {
+    testing::init_logging();
+
+    let mut rng = crate::new_rng();
+
+    // Five validators with equal stakes, one of which never comes online. Its weight is below the
+    // fault tolerance threshold, so the others skip its rounds and keep finalizing blocks.
+    let keys: Vec<Arc<SecretKey>> = (0..4)
+        .map(|_| Arc::new(SecretKey::random(&mut rng)))
+        .collect();
+    let offline_pk = PublicKey::from(&SecretKey::random(&mut rng));
+    let stakes: BTreeMap<PublicKey, U512> = keys
+        .iter()
+        .map(|secret_key| PublicKey::from(&**secret_key))
+        .chain(iter::once(offline_pk.clone()))
+        .map(|public_key| (public_key, U512::from(100)))
+        .collect();
+
+    let mut chain = TestChain::new_with_keys(&mut rng, keys, stakes);
+    chain.chainspec_mut().core_config.consensus_protocol = ConsensusProtocolName::Simple;
+    chain.chainspec_mut().core_config.minimum_era_height = 5;
+    chain.chainspec_mut().simple_config.proposal_timeout = "2sec".parse().unwrap();
+
+    let mut net = chain
+        .create_initialized_network(&mut rng)
+        .await
+        .expect("network initialization failed");
+
+    net.settle_on(
+        &mut rng,
+        is_in_era(EraId::from(2)),
+        Duration::from_secs(180),
+    )
+    .await;
+
+    // The offline validator is at most reported as inactive, never as an equivocator, and the
+    // online ones are never reported as inactive.
+    let switch_blocks = SwitchBlocks::collect(net.nodes(), 2);
+    for era_number in 0..2 {
+        assert_eq!(switch_blocks.equivocators(era_number), []);
+        assert!(switch_blocks
+            .inactive_validators(era_number)
+            .iter()
+            .all(|public_key| *public_key == offline_pk));
+    }
+}
+
 #[tokio::test]
 async fn run_equivocator_network() {
     testing::init_logging();
@@ -7,6 +7,7 @@ mod condition_check_reactor;
 pub(crate) mod filter_reactor;
 mod multi_stage_test_reactor;
 pub(crate) mod network;
+pub(crate) mod replay;
 pub(crate) mod test_clock;
 mod test_rng;
 
@@ -0,0 +1,73 @@
+//! Replay of captured network traffic.
+//!
+//! Feeds the messages a node received, as recorded by the networking component when
+//! `network.capture_path` is set, into a node of a test network. Use
+//! `small_network::read_capture` to load a capture file.
+
+use std::{fmt::Debug, time::Duration};
+
+use serde::Serialize;
+use tracing::debug;
+
+use super::{
+    network::{Network, NetworkedReactor},
+    TestRng,
+};
+use crate::{
+    components::small_network::{CaptureRecord, Direction},
+    effect::{announcements::NetworkAnnouncement, EffectExt},
+    protocol::Message,
+    reactor::Reactor,
+    types::NodeId,
+};
+
+/// How long the network has to be idle before the next message is replayed.
+const QUIET_FOR: Duration = Duration::from_millis(100);
+
+/// Feeds the incoming messages of a capture to the given node, in the order they were received.
+///
+/// After each message, the network is settled, so that the node handles each message before the
+/// next one arrives. Outgoing messages in the capture are skipped, as the node sends its own.
+///
+/// Returns the number of replayed messages.
+///
+/// # Panics
+///
+/// Panics if the network does not settle within `within` after any of the messages.
+pub(crate) async fn replay_capture<R>(
+    network: &mut Network<R>,
+    node_id: &R::NodeId,
+    records: Vec<CaptureRecord<Message>>,
+    rng: &mut TestRng,
+    within: Duration,
+) -> usize
+where
+    R: Reactor + NetworkedReactor,
+    R::Config: Default,
+    <R as Reactor>::Error: Debug,
+    R::Event: Serialize + From<NetworkAnnouncement<NodeId, Message>>,
+    R::Error: From<prometheus::Error>,
+{
+    let mut replayed = 0;
+    for record in records {
+        if record.direction != Direction::Incoming {
+            continue;
+        }
+        debug!(
+            timestamp = %record.timestamp,
+            sender = %record.peer_id,
+            payload = %record.payload,
+            "replaying message"
+        );
+        network
+            .process_injected_effect_on(node_id, |effect_builder| {
+                effect_builder
+                    .announce_message_received(record.peer_id, record.payload)
+                    .ignore()
+            })
+            .await;
+        network.settle(rng, QUIET_FOR, within).await;
+        replayed += 1;
+    }
+    replayed
+}
@@ -36,7 +36,7 @@ pub use error::BlockValidationError;
 pub use exit_code::ExitCode;
 pub use item::{Item, Tag};
 pub use node_config::NodeConfig;
-pub(crate) use node_id::NodeId;
+pub(crate) use node_id::{NodeId, ParseNodeIdError};
 pub use peers_map::PeersMap;
 pub(crate) use shared_object::SharedObject;
 pub use status_feed::{ChainspecInfo, GetStatusResult, StatusFeed};
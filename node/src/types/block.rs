@@ -53,16 +53,26 @@ static ERA_REPORT: Lazy<EraReport> = Lazy::new(|| {
     let secret_key_2 = SecretKey::ed25519_from_bytes([1; 32]).unwrap();
     let public_key_2 = PublicKey::from(&secret_key_2);
     let mut rewards = BTreeMap::new();
-    rewards.insert(public_key_2, 1000);
+    rewards.insert(public_key_2.clone(), 1000);
 
     let secret_key_3 = SecretKey::ed25519_from_bytes([2; 32]).unwrap();
     let public_key_3 = PublicKey::from(&secret_key_3);
     let inactive_validators = vec![public_key_3];
 
+    let mut equivocation_reports = BTreeMap::new();
+    equivocation_reports.insert(
+        equivocators[0].clone(),
+        consensus::EquivocationReport {
+            reporter: public_key_2,
+            message_hash: Digest::hash([3; 32]),
+        },
+    );
+
     EraReport {
         equivocators,
         rewards,
         inactive_validators,
+        equivocation_reports,
     }
 });
 static ERA_END: Lazy<EraEnd> = Lazy::new(|| {
@@ -284,6 +294,7 @@ impl ToBytes for EraReport {
         buffer.extend(self.equivocators.to_bytes()?);
         buffer.extend(self.rewards.to_bytes()?);
         buffer.extend(self.inactive_validators.to_bytes()?);
+        buffer.extend(self.equivocation_reports.to_bytes()?);
         Ok(buffer)
     }
 
@@ -291,6 +302,7 @@ impl ToBytes for EraReport {
         self.equivocators.serialized_length()
             + self.rewards.serialized_length()
             + self.inactive_validators.serialized_length()
+            + self.equivocation_reports.serialized_length()
     }
 }
 
@@ -299,11 +311,14 @@ impl FromBytes for EraReport {
         let (equivocators, remainder) = Vec::<PublicKey>::from_bytes(bytes)?;
         let (rewards, remainder) = BTreeMap::<PublicKey, u64>::from_bytes(remainder)?;
         let (inactive_validators, remainder) = Vec::<PublicKey>::from_bytes(remainder)?;
+        let (equivocation_reports, remainder) =
+            BTreeMap::<PublicKey, consensus::EquivocationReport<PublicKey>>::from_bytes(remainder)?;
 
         let era_report = EraReport {
             equivocators,
             rewards,
             inactive_validators,
+            equivocation_reports,
         };
         Ok((era_report, remainder))
     }
@@ -419,12 +434,26 @@ impl FinalizedBlock {
             let equivocators_count = rng.gen_range(0..5);
             let rewards_count = rng.gen_range(0..5);
             let inactive_count = rng.gen_range(0..5);
+            let equivocators: Vec<PublicKey> = iter::repeat_with(|| {
+                PublicKey::from(&SecretKey::ed25519_from_bytes(rng.gen::<[u8; 32]>()).unwrap())
+            })
+            .take(equivocators_count)
+            .collect();
+            let mut equivocation_reports = BTreeMap::new();
+            for equivocator in &equivocators {
+                if rng.gen() {
+                    let reporter = PublicKey::from(
+                        &SecretKey::ed25519_from_bytes(rng.gen::<[u8; 32]>()).unwrap(),
+                    );
+                    let report = consensus::EquivocationReport {
+                        reporter,
+                        message_hash: Digest::hash(rng.gen::<[u8; 32]>()),
+                    };
+                    equivocation_reports.insert(equivocator.clone(), report);
+                }
+            }
             Some(EraReport {
-                equivocators: iter::repeat_with(|| {
-                    PublicKey::from(&SecretKey::ed25519_from_bytes(rng.gen::<[u8; 32]>()).unwrap())
-                })
-                .take(equivocators_count)
-                .collect(),
+                equivocators,
                 rewards: iter::repeat_with(|| {
                     let pub_key = PublicKey::from(
                         &SecretKey::ed25519_from_bytes(rng.gen::<[u8; 32]>()).unwrap(),
@@ -439,6 +468,7 @@ impl FinalizedBlock {
                 })
                 .take(inactive_count)
                 .collect(),
+                equivocation_reports,
             })
         } else {
             None
@@ -1635,6 +1665,14 @@ pub(crate) mod json_compatibility {
         weight: U512,
     }
 
+    #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq, DataSize)]
+    #[serde(deny_unknown_fields)]
+    struct EquivocationReport {
+        equivocator: PublicKey,
+        reporter: PublicKey,
+        message_hash: Digest,
+    }
+
     /// Equivocation and reward information to be included in the terminal block.
     #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq, DataSize)]
     #[serde(deny_unknown_fields)]
@@ -1642,6 +1680,7 @@ pub(crate) mod json_compatibility {
         equivocators: Vec<PublicKey>,
         rewards: Vec<Reward>,
         inactive_validators: Vec<PublicKey>,
+        equivocation_reports: Vec<EquivocationReport>,
     }
 
     impl From<EraReport> for JsonEraReport {
@@ -1654,6 +1693,15 @@ pub(crate) mod json_compatibility {
                     .map(|(validator, amount)| Reward { validator, amount })
                     .collect(),
                 inactive_validators: era_report.inactive_validators,
+                equivocation_reports: era_report
+                    .equivocation_reports
+                    .into_iter()
+                    .map(|(equivocator, report)| EquivocationReport {
+                        equivocator,
+                        reporter: report.reporter,
+                        message_hash: report.message_hash,
+                    })
+                    .collect(),
             }
         }
     }
@@ -1667,10 +1715,22 @@ pub(crate) mod json_compatibility {
                 .map(|reward| (reward.validator, reward.amount))
                 .collect();
             let inactive_validators = era_report.inactive_validators;
+            let equivocation_reports = era_report
+                .equivocation_reports
+                .into_iter()
+                .map(|report| {
+                    let equivocation_report = consensus::EquivocationReport {
+                        reporter: report.reporter,
+                        message_hash: report.message_hash,
+                    };
+                    (report.equivocator, equivocation_report)
+                })
+                .collect();
             EraReport {
                 equivocators,
                 rewards,
                 inactive_validators,
+                equivocation_reports,
             }
         }
     }
@@ -1925,8 +1985,7 @@ impl FinalitySignature {
         secret_key: &SecretKey,
         public_key: PublicKey,
     ) -> Self {
-        let mut bytes = block_hash.inner().into_vec();
-        bytes.extend_from_slice(&era_id.to_le_bytes());
+        let bytes = Self::bytes_to_sign(&block_hash, era_id);
         let signature = crypto::sign(bytes, secret_key, &public_key);
         FinalitySignature {
             block_hash,
@@ -1938,12 +1997,17 @@ impl FinalitySignature {
 
     /// Verifies whether the signature is correct.
     pub fn verify(&self) -> crypto::Result<()> {
-        // NOTE: This needs to be in sync with the `new` constructor.
-        let mut bytes = self.block_hash.inner().into_vec();
-        bytes.extend_from_slice(&self.era_id.to_le_bytes());
+        let bytes = Self::bytes_to_sign(&self.block_hash, self.era_id);
         crypto::verify(bytes, &self.signature, &self.public_key)
     }
 
+    /// Returns the bytes that are signed by a finality signature.
+    pub(crate) fn bytes_to_sign(block_hash: &BlockHash, era_id: EraId) -> Vec<u8> {
+        let mut bytes = block_hash.inner().into_vec();
+        bytes.extend_from_slice(&era_id.to_le_bytes());
+        bytes
+    }
+
     #[cfg(test)]
     pub fn random_for_block(block_hash: BlockHash, era_id: u64) -> Self {
         let (sec_key, pub_key) = generate_ed25519_keypair();
@@ -11,6 +11,7 @@ mod highway_config;
 mod network_config;
 mod parse_toml;
 mod protocol_config;
+mod simple_config;
 
 use std::{fmt::Debug, path::Path};
 
@@ -34,9 +35,15 @@ use casper_types::{
 pub(crate) use self::accounts_config::{AccountConfig, ValidatorConfig};
 pub use self::error::Error;
 pub(crate) use self::{
-    accounts_config::AccountsConfig, activation_point::ActivationPoint, core_config::CoreConfig,
-    deploy_config::DeployConfig, global_state_update::GlobalStateUpdate,
-    highway_config::HighwayConfig, network_config::NetworkConfig, protocol_config::ProtocolConfig,
+    accounts_config::AccountsConfig,
+    activation_point::ActivationPoint,
+    core_config::{ConsensusProtocolName, CoreConfig, SlashedFunds},
+    deploy_config::DeployConfig,
+    global_state_update::GlobalStateUpdate,
+    highway_config::HighwayConfig,
+    network_config::NetworkConfig,
+    protocol_config::ProtocolConfig,
+    simple_config::SimpleConfig,
 };
 #[cfg(test)]
 use crate::testing::TestRng;
@@ -57,6 +64,8 @@ pub struct Chainspec {
     pub(crate) core_config: CoreConfig,
     #[serde(rename = "highway")]
     pub(crate) highway_config: HighwayConfig,
+    #[serde(rename = "simple")]
+    pub(crate) simple_config: SimpleConfig,
     #[serde(rename = "deploys")]
     pub(crate) deploy_config: DeployConfig,
     #[serde(rename = "wasm")]
@@ -68,17 +77,23 @@ pub struct Chainspec {
 impl Chainspec {
     /// Returns `false` and logs errors if the values set in the config don't make sense.
     pub(crate) fn is_valid(&self) -> bool {
-        let min_era_ms = 1u64 << self.highway_config.minimum_round_exponent;
+        let min_era_ms = match self.core_config.consensus_protocol {
+            ConsensusProtocolName::Highway => 1u64 << self.highway_config.minimum_round_exponent,
+            ConsensusProtocolName::Simple => self.simple_config.minimum_block_time.millis(),
+        };
         // If the era duration is set to zero, we will treat it as explicitly stating that eras
         // should be defined by height only.
         if self.core_config.era_duration.millis() > 0
             && self.core_config.era_duration.millis()
                 < self.core_config.minimum_era_height * min_era_ms
         {
-            warn!("era duration is less than minimum era height * round length!");
+            warn!("era duration is less than minimum era height * minimum block time!");
         }
 
-        self.protocol_config.is_valid() && self.highway_config.is_valid()
+        self.protocol_config.is_valid()
+            && self.highway_config.is_valid()
+            && self.simple_config.is_valid()
+            && self.core_config.is_valid()
     }
 
     /// Serializes `self` and hashes the resulting bytes.
@@ -109,6 +124,7 @@ impl Chainspec {
         let network_config = NetworkConfig::random(rng);
         let core_config = CoreConfig::random(rng);
         let highway_config = HighwayConfig::random(rng);
+        let simple_config = SimpleConfig::random(rng);
         let deploy_config = DeployConfig::random(rng);
         let wasm_costs_config = rng.gen();
         let system_costs_config = rng.gen();
@@ -118,6 +134,7 @@ impl Chainspec {
             network_config,
             core_config,
             highway_config,
+            simple_config,
             deploy_config,
             wasm_config: wasm_costs_config,
             system_costs_config,
@@ -132,6 +149,7 @@ impl ToBytes for Chainspec {
         buffer.extend(self.network_config.to_bytes()?);
         buffer.extend(self.core_config.to_bytes()?);
         buffer.extend(self.highway_config.to_bytes()?);
+        buffer.extend(self.simple_config.to_bytes()?);
         buffer.extend(self.deploy_config.to_bytes()?);
         buffer.extend(self.wasm_config.to_bytes()?);
         buffer.extend(self.system_costs_config.to_bytes()?);
@@ -143,6 +161,7 @@ impl ToBytes for Chainspec {
             + self.network_config.serialized_length()
             + self.core_config.serialized_length()
             + self.highway_config.serialized_length()
+            + self.simple_config.serialized_length()
             + self.deploy_config.serialized_length()
             + self.wasm_config.serialized_length()
             + self.system_costs_config.serialized_length()
@@ -155,6 +174,7 @@ impl FromBytes for Chainspec {
         let (network_config, remainder) = NetworkConfig::from_bytes(remainder)?;
         let (core_config, remainder) = CoreConfig::from_bytes(remainder)?;
         let (highway_config, remainder) = HighwayConfig::from_bytes(remainder)?;
+        let (simple_config, remainder) = SimpleConfig::from_bytes(remainder)?;
         let (deploy_config, remainder) = DeployConfig::from_bytes(remainder)?;
         let (wasm_config, remainder) = WasmConfig::from_bytes(remainder)?;
         let (system_costs_config, remainder) = SystemConfig::from_bytes(remainder)?;
@@ -163,6 +183,7 @@ impl FromBytes for Chainspec {
             network_config,
             core_config,
             highway_config,
+            simple_config,
             deploy_config,
             wasm_config,
             system_costs_config,
@@ -190,6 +211,11 @@ impl From<&Chainspec> for ExecConfig {
             chainspec.core_config.locked_funds_period.millis(),
             chainspec.core_config.round_seigniorage_rate,
             chainspec.core_config.unbonding_delay,
+            chainspec.core_config.minimum_delegation_amount,
+            chainspec.core_config.maximum_delegation_amount,
+            chainspec.core_config.max_delegators_per_validator,
+            chainspec.core_config.delegation_rate_change_delay,
+            chainspec.core_config.max_delegation_rate_increase,
             chainspec
                 .protocol_config
                 .activation_point
@@ -264,6 +290,9 @@ mod tests {
             remove_contract_user_group_urefs: HostFunction::new(131, [0, 1, 2, 3, 4, 5]),
             print: HostFunction::new(123, [0, 1]),
             blake2b: HostFunction::new(133, [0, 1, 2, 3]),
+            set_spending_limit: HostFunction::new(144, [0, 1, 2, 3, 4, 5]),
+            remove_spending_limit: HostFunction::new(143, [0, 1]),
+            manage_recovery: HostFunction::new(142, [0, 1, 2, 3, 4]),
         });
     static EXPECTED_GENESIS_WASM_COSTS: Lazy<WasmConfig> = Lazy::new(|| {
         WasmConfig::new(
@@ -349,6 +378,20 @@ mod tests {
 
         assert_eq!(spec.core_config.era_duration, TimeDiff::from(180000));
         assert_eq!(spec.core_config.minimum_era_height, 9);
+        assert_eq!(spec.core_config.minimum_delegation_amount, 15);
+        assert_eq!(spec.core_config.maximum_delegation_amount, 16);
+        assert_eq!(spec.core_config.max_delegators_per_validator, 17);
+        assert_eq!(spec.core_config.delegation_rate_change_delay, 18);
+        assert_eq!(spec.core_config.max_delegation_rate_increase, 19);
+        assert_eq!(spec.core_config.slash_fraction, Ratio::new(1, 20));
+        assert_eq!(spec.core_config.slashed_funds, SlashedFunds::Reporter);
+        assert_eq!(spec.core_config.inactivity_threshold, Ratio::new(1, 21));
+        assert_eq!(spec.core_config.inactivity_penalty_step, Ratio::new(1, 22));
+        assert_eq!(spec.core_config.inactivity_eviction_eras, 23);
+        assert_eq!(
+            spec.core_config.consensus_protocol,
+            ConsensusProtocolName::Simple
+        );
         assert_eq!(
             spec.highway_config.finality_threshold_fraction,
             Ratio::new(2, 25)
@@ -359,6 +402,12 @@ mod tests {
             spec.highway_config.reduced_reward_multiplier,
             Ratio::new(1, 5)
         );
+        assert_eq!(
+            spec.simple_config.finality_threshold_fraction,
+            Ratio::new(1, 4)
+        );
+        assert_eq!(spec.simple_config.proposal_timeout, TimeDiff::from(5000));
+        assert_eq!(spec.simple_config.minimum_block_time, TimeDiff::from(2000));
 
         assert_eq!(
             spec.deploy_config.max_payment_cost,
@@ -369,6 +418,7 @@ mod tests {
         assert_eq!(spec.deploy_config.max_block_size, 12);
         assert_eq!(spec.deploy_config.block_max_deploy_count, 125);
         assert_eq!(spec.deploy_config.block_gas_limit, 13);
+        assert_eq!(spec.deploy_config.min_gas_price, 14);
 
         assert_eq!(spec.wasm_config, *EXPECTED_GENESIS_WASM_COSTS);
     }
@@ -3,13 +3,91 @@ use num::rational::Ratio;
 #[cfg(test)]
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use tracing::error;
 
-use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    system::auction::{DelegationRate, DELEGATION_RATE_DENOMINATOR},
+};
 
 #[cfg(test)]
 use crate::testing::TestRng;
 use crate::types::TimeDiff;
 
+const BURN_TAG: u8 = 0;
+const REPORTER_TAG: u8 = 1;
+
+const HIGHWAY_TAG: u8 = 0;
+const SIMPLE_TAG: u8 = 1;
+
+/// The consensus protocol run by the validators in each era.
+#[derive(Copy, Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsensusProtocolName {
+    /// The Highway protocol.
+    Highway,
+    /// The simple round-based protocol with a single leader per round.
+    Simple,
+}
+
+impl ToBytes for ConsensusProtocolName {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        match self {
+            ConsensusProtocolName::Highway => HIGHWAY_TAG.to_bytes(),
+            ConsensusProtocolName::Simple => SIMPLE_TAG.to_bytes(),
+        }
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for ConsensusProtocolName {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            HIGHWAY_TAG => Ok((ConsensusProtocolName::Highway, remainder)),
+            SIMPLE_TAG => Ok((ConsensusProtocolName::Simple, remainder)),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// Where the funds slashed from an offending validator go.
+#[derive(Copy, Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashedFunds {
+    /// The slashed funds are burned.
+    Burn,
+    /// The slashed funds are paid into the stake of the validator which reported the offense.
+    Reporter,
+}
+
+impl ToBytes for SlashedFunds {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        match self {
+            SlashedFunds::Burn => BURN_TAG.to_bytes(),
+            SlashedFunds::Reporter => REPORTER_TAG.to_bytes(),
+        }
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for SlashedFunds {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            BURN_TAG => Ok((SlashedFunds::Burn, remainder)),
+            REPORTER_TAG => Ok((SlashedFunds::Reporter, remainder)),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
 #[derive(Copy, Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
 #[serde(deny_unknown_fields)]
@@ -30,6 +108,79 @@ pub struct CoreConfig {
     pub(crate) round_seigniorage_rate: Ratio<u64>,
     /// Maximum number of associated keys for a single account.
     pub(crate) max_associated_keys: u32,
+    /// Minimum amount, in motes, a delegator must have delegated to a single validator.
+    pub(crate) minimum_delegation_amount: u64,
+    /// Maximum amount, in motes, a delegator may have delegated to a single validator.
+    pub(crate) maximum_delegation_amount: u64,
+    /// Maximum number of delegators a single validator may have.
+    pub(crate) max_delegators_per_validator: u32,
+    /// Number of eras after which a validator's delegation rate increase takes effect.
+    pub(crate) delegation_rate_change_delay: u64,
+    /// Maximum increase of a validator's delegation rate allowed in a single change.
+    pub(crate) max_delegation_rate_increase: DelegationRate,
+    /// Fraction of the stakes bonded to an equivocating validator which is slashed.
+    #[data_size(skip)]
+    pub(crate) slash_fraction: Ratio<u64>,
+    /// Where the slashed funds go.
+    pub(crate) slashed_funds: SlashedFunds,
+    /// Participation, relative to the era's most active validator, below which a validator is
+    /// inactive in an era.  If zero, inactivity is not penalized.  Validators which didn't
+    /// participate at all are evicted at the end of the era regardless.
+    #[data_size(skip)]
+    pub(crate) inactivity_threshold: Ratio<u64>,
+    /// Fraction of an inactive validator's reward withheld for each consecutive inactive era.
+    #[data_size(skip)]
+    pub(crate) inactivity_penalty_step: Ratio<u64>,
+    /// Number of consecutive inactive eras after which a validator is evicted.  If zero, inactive
+    /// validators are never evicted.
+    pub(crate) inactivity_eviction_eras: u64,
+    /// The consensus protocol to use in each era.
+    pub(crate) consensus_protocol: ConsensusProtocolName,
+}
+
+impl CoreConfig {
+    /// Returns `false` if the delegation bounds, delegation rate limits, slash fraction or
+    /// inactivity policy are inconsistent.
+    pub(super) fn is_valid(&self) -> bool {
+        if self.minimum_delegation_amount > self.maximum_delegation_amount {
+            error!(
+                min = %self.minimum_delegation_amount,
+                max = %self.maximum_delegation_amount,
+                "minimum delegation amount is greater than the maximum delegation amount",
+            );
+            return false;
+        }
+        if self.max_delegation_rate_increase > DELEGATION_RATE_DENOMINATOR {
+            error!(
+                max_delegation_rate_increase = %self.max_delegation_rate_increase,
+                "maximum delegation rate increase is greater than {}",
+                DELEGATION_RATE_DENOMINATOR,
+            );
+            return false;
+        }
+        if self.slash_fraction > Ratio::from_integer(1) {
+            error!(
+                slash_fraction = %self.slash_fraction,
+                "slash fraction is greater than one",
+            );
+            return false;
+        }
+        if self.inactivity_threshold > Ratio::from_integer(1) {
+            error!(
+                inactivity_threshold = %self.inactivity_threshold,
+                "inactivity threshold is greater than one",
+            );
+            return false;
+        }
+        if self.inactivity_penalty_step > Ratio::from_integer(1) {
+            error!(
+                inactivity_penalty_step = %self.inactivity_penalty_step,
+                "inactivity penalty step is greater than one",
+            );
+            return false;
+        }
+        true
+    }
 }
 
 #[cfg(test)]
@@ -47,6 +198,34 @@ impl CoreConfig {
             rng.gen_range(1..1_000_000_000),
         );
         let max_associated_keys = rng.gen();
+        let minimum_delegation_amount = rng.gen_range(0..1_000_000_000_000);
+        let maximum_delegation_amount = rng.gen_range(minimum_delegation_amount..i64::MAX as u64);
+        let max_delegators_per_validator = rng.gen();
+        let delegation_rate_change_delay = rng.gen_range(0..1_000);
+        let max_delegation_rate_increase = rng.gen_range(0..=DELEGATION_RATE_DENOMINATOR);
+        let slash_fraction = {
+            let denominator = rng.gen_range(1..1_000_000_000);
+            Ratio::new(rng.gen_range(0..=denominator), denominator)
+        };
+        let slashed_funds = if rng.gen() {
+            SlashedFunds::Burn
+        } else {
+            SlashedFunds::Reporter
+        };
+        let inactivity_threshold = {
+            let denominator = rng.gen_range(1..1_000_000_000);
+            Ratio::new(rng.gen_range(0..=denominator), denominator)
+        };
+        let inactivity_penalty_step = {
+            let denominator = rng.gen_range(1..1_000_000_000);
+            Ratio::new(rng.gen_range(0..=denominator), denominator)
+        };
+        let inactivity_eviction_eras = rng.gen_range(0..10);
+        let consensus_protocol = if rng.gen() {
+            ConsensusProtocolName::Highway
+        } else {
+            ConsensusProtocolName::Simple
+        };
 
         CoreConfig {
             era_duration,
@@ -57,6 +236,17 @@ impl CoreConfig {
             unbonding_delay,
             round_seigniorage_rate,
             max_associated_keys,
+            minimum_delegation_amount,
+            maximum_delegation_amount,
+            max_delegators_per_validator,
+            delegation_rate_change_delay,
+            max_delegation_rate_increase,
+            slash_fraction,
+            slashed_funds,
+            inactivity_threshold,
+            inactivity_penalty_step,
+            inactivity_eviction_eras,
+            consensus_protocol,
         }
     }
 }
@@ -72,6 +262,17 @@ impl ToBytes for CoreConfig {
         buffer.extend(self.unbonding_delay.to_bytes()?);
         buffer.extend(self.round_seigniorage_rate.to_bytes()?);
         buffer.extend(self.max_associated_keys.to_bytes()?);
+        buffer.extend(self.minimum_delegation_amount.to_bytes()?);
+        buffer.extend(self.maximum_delegation_amount.to_bytes()?);
+        buffer.extend(self.max_delegators_per_validator.to_bytes()?);
+        buffer.extend(self.delegation_rate_change_delay.to_bytes()?);
+        buffer.extend(self.max_delegation_rate_increase.to_bytes()?);
+        buffer.extend(self.slash_fraction.to_bytes()?);
+        buffer.extend(self.slashed_funds.to_bytes()?);
+        buffer.extend(self.inactivity_threshold.to_bytes()?);
+        buffer.extend(self.inactivity_penalty_step.to_bytes()?);
+        buffer.extend(self.inactivity_eviction_eras.to_bytes()?);
+        buffer.extend(self.consensus_protocol.to_bytes()?);
         Ok(buffer)
     }
 
@@ -84,6 +285,17 @@ impl ToBytes for CoreConfig {
             + self.unbonding_delay.serialized_length()
             + self.round_seigniorage_rate.serialized_length()
             + self.max_associated_keys.serialized_length()
+            + self.minimum_delegation_amount.serialized_length()
+            + self.maximum_delegation_amount.serialized_length()
+            + self.max_delegators_per_validator.serialized_length()
+            + self.delegation_rate_change_delay.serialized_length()
+            + self.max_delegation_rate_increase.serialized_length()
+            + self.slash_fraction.serialized_length()
+            + self.slashed_funds.serialized_length()
+            + self.inactivity_threshold.serialized_length()
+            + self.inactivity_penalty_step.serialized_length()
+            + self.inactivity_eviction_eras.serialized_length()
+            + self.consensus_protocol.serialized_length()
     }
 }
 
@@ -97,6 +309,17 @@ impl FromBytes for CoreConfig {
         let (unbonding_delay, remainder) = u64::from_bytes(remainder)?;
         let (round_seigniorage_rate, remainder) = Ratio::<u64>::from_bytes(remainder)?;
         let (max_associated_keys, remainder) = FromBytes::from_bytes(remainder)?;
+        let (minimum_delegation_amount, remainder) = u64::from_bytes(remainder)?;
+        let (maximum_delegation_amount, remainder) = u64::from_bytes(remainder)?;
+        let (max_delegators_per_validator, remainder) = u32::from_bytes(remainder)?;
+        let (delegation_rate_change_delay, remainder) = u64::from_bytes(remainder)?;
+        let (max_delegation_rate_increase, remainder) = DelegationRate::from_bytes(remainder)?;
+        let (slash_fraction, remainder) = Ratio::<u64>::from_bytes(remainder)?;
+        let (slashed_funds, remainder) = SlashedFunds::from_bytes(remainder)?;
+        let (inactivity_threshold, remainder) = Ratio::<u64>::from_bytes(remainder)?;
+        let (inactivity_penalty_step, remainder) = Ratio::<u64>::from_bytes(remainder)?;
+        let (inactivity_eviction_eras, remainder) = u64::from_bytes(remainder)?;
+        let (consensus_protocol, remainder) = ConsensusProtocolName::from_bytes(remainder)?;
         let config = CoreConfig {
             era_duration,
             minimum_era_height,
@@ -106,6 +329,17 @@ impl FromBytes for CoreConfig {
             unbonding_delay,
             round_seigniorage_rate,
             max_associated_keys,
+            minimum_delegation_amount,
+            maximum_delegation_amount,
+            max_delegators_per_validator,
+            delegation_rate_change_delay,
+            max_delegation_rate_increase,
+            slash_fraction,
+            slashed_funds,
+            inactivity_threshold,
+            inactivity_penalty_step,
+            inactivity_eviction_eras,
+            consensus_protocol,
         };
         Ok((config, remainder))
     }
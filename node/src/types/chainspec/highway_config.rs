@@ -27,7 +27,7 @@ pub(crate) struct HighwayConfig {
 
 impl HighwayConfig {
     /// Checks whether the values set in the config make sense and returns `false` if they don't.
-    pub(super) fn is_valid(&self) -> bool {
+    pub(crate) fn is_valid(&self) -> bool {
         if self.minimum_round_exponent > self.maximum_round_exponent {
             error!(
                 min = %self.minimum_round_exponent,
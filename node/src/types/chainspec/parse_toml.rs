@@ -16,7 +16,7 @@ use casper_types::{EraId, ProtocolVersion};
 use super::{
     accounts_config::AccountsConfig, global_state_update::GlobalStateUpdateConfig, ActivationPoint,
     Chainspec, CoreConfig, DeployConfig, Error, GlobalStateUpdate, HighwayConfig, NetworkConfig,
-    ProtocolConfig,
+    ProtocolConfig, SimpleConfig,
 };
 use crate::utils::{self, Loadable};
 
@@ -48,6 +48,7 @@ pub(super) struct TomlChainspec {
     core: CoreConfig,
     deploys: DeployConfig,
     highway: HighwayConfig,
+    simple: SimpleConfig,
     wasm: WasmConfig,
     system_costs: SystemConfig,
 }
@@ -67,6 +68,7 @@ impl From<&Chainspec> for TomlChainspec {
         let core = chainspec.core_config;
         let deploys = chainspec.deploy_config;
         let highway = chainspec.highway_config;
+        let simple = chainspec.simple_config;
         let wasm = chainspec.wasm_config;
         let system_costs = chainspec.system_costs_config;
 
@@ -76,6 +78,7 @@ impl From<&Chainspec> for TomlChainspec {
             core,
             deploys,
             highway,
+            simple,
             wasm,
             system_costs,
         }
@@ -118,6 +121,7 @@ pub(super) fn parse_toml<P: AsRef<Path>>(chainspec_path: P) -> Result<Chainspec,
         core_config: toml_chainspec.core,
         deploy_config: toml_chainspec.deploys,
         highway_config: toml_chainspec.highway,
+        simple_config: toml_chainspec.simple,
         wasm_config: toml_chainspec.wasm,
         system_costs_config: toml_chainspec.system_costs,
     })
@@ -0,0 +1,145 @@
+use datasize::DataSize;
+use num::rational::Ratio;
+#[cfg(test)]
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+
+#[cfg(test)]
+use crate::testing::TestRng;
+use crate::types::TimeDiff;
+
+/// Configuration of the simple round-based consensus protocol.
+#[derive(Copy, Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub(crate) struct SimpleConfig {
+    /// The fraction of the total weight of faulty validators the protocol tolerates.  Must be less
+    /// than one third.
+    #[data_size(skip)]
+    pub(crate) finality_threshold_fraction: Ratio<u64>,
+    /// The time after which a round without an accepted proposal is skipped.
+    pub(crate) proposal_timeout: TimeDiff,
+    /// The minimum time between a block and its child.
+    pub(crate) minimum_block_time: TimeDiff,
+}
+
+impl SimpleConfig {
+    /// Checks whether the values set in the config make sense and returns `false` if they don't.
+    pub(super) fn is_valid(&self) -> bool {
+        if self.finality_threshold_fraction <= Ratio::new(0, 1)
+            || self.finality_threshold_fraction >= Ratio::new(1, 3)
+        {
+            error!(
+                ftf = %self.finality_threshold_fraction,
+                "finality threshold fraction is not in the range (0, 1/3)",
+            );
+            return false;
+        }
+
+        if self.proposal_timeout.millis() == 0 {
+            error!("proposal timeout is zero");
+            return false;
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+impl SimpleConfig {
+    /// Generates a random instance using a `TestRng`.
+    pub fn random(rng: &mut TestRng) -> Self {
+        let finality_threshold_fraction = Ratio::new(rng.gen_range(1..33), 100);
+        let proposal_timeout = TimeDiff::from(rng.gen_range(1_000..60_000));
+        let minimum_block_time = TimeDiff::from(rng.gen_range(0..10_000));
+
+        SimpleConfig {
+            finality_threshold_fraction,
+            proposal_timeout,
+            minimum_block_time,
+        }
+    }
+}
+
+impl ToBytes for SimpleConfig {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.finality_threshold_fraction.to_bytes()?);
+        buffer.extend(self.proposal_timeout.to_bytes()?);
+        buffer.extend(self.minimum_block_time.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.finality_threshold_fraction.serialized_length()
+            + self.proposal_timeout.serialized_length()
+            + self.minimum_block_time.serialized_length()
+    }
+}
+
+impl FromBytes for SimpleConfig {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (finality_threshold_fraction, remainder) = Ratio::<u64>::from_bytes(bytes)?;
+        let (proposal_timeout, remainder) = TimeDiff::from_bytes(remainder)?;
+        let (minimum_block_time, remainder) = TimeDiff::from_bytes(remainder)?;
+        let config = SimpleConfig {
+            finality_threshold_fraction,
+            proposal_timeout,
+            minimum_block_time,
+        };
+        Ok((config, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut rng = crate::new_rng();
+        let config = SimpleConfig::random(&mut rng);
+        bytesrepr::test_serialization_roundtrip(&config);
+    }
+
+    #[test]
+    fn toml_roundtrip() {
+        let mut rng = crate::new_rng();
+        let config = SimpleConfig::random(&mut rng);
+        let encoded = toml::to_string_pretty(&config).unwrap();
+        let decoded = toml::from_str(&encoded).unwrap();
+        assert_eq!(config, decoded);
+    }
+
+    #[test]
+    fn should_validate_for_finality_threshold() {
+        let mut rng = crate::new_rng();
+        let mut simple_config = SimpleConfig::random(&mut rng);
+
+        // Should be valid for FTT > 0 and < 1/3.
+        simple_config.finality_threshold_fraction = Ratio::new(1, u64::MAX);
+        assert!(simple_config.is_valid());
+        simple_config.finality_threshold_fraction = Ratio::new(32, 100);
+        assert!(simple_config.is_valid());
+
+        // Should be invalid for FTT == 0 or >= 1/3.
+        simple_config.finality_threshold_fraction = Ratio::new(0, 1);
+        assert!(!simple_config.is_valid());
+        simple_config.finality_threshold_fraction = Ratio::new(1, 3);
+        assert!(!simple_config.is_valid());
+        simple_config.finality_threshold_fraction = Ratio::new(1, 2);
+        assert!(!simple_config.is_valid());
+    }
+
+    #[test]
+    fn should_validate_for_proposal_timeout() {
+        let mut rng = crate::new_rng();
+        let mut simple_config = SimpleConfig::random(&mut rng);
+
+        simple_config.proposal_timeout = TimeDiff::from(0);
+        assert!(!simple_config.is_valid());
+    }
+}
@@ -32,7 +32,7 @@ use casper_types::bytesrepr::Bytes;
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     runtime_args,
-    system::standard_payment::ARG_AMOUNT,
+    system::{handle_payment::ARG_PAYER, standard_payment::ARG_AMOUNT},
     AsymmetricType, ExecutionResult, Motes, PublicKey, RuntimeArgs, SecretKey, Signature, U512,
 };
 
@@ -128,6 +128,15 @@ pub enum DeployConfigurationFailure {
         got: TimeDiff,
     },
 
+    /// The gas price is below the minimum.
+    #[error("gas price of {got} is below the minimum of {min_gas_price}")]
+    InsufficientGasPrice {
+        /// The minimum gas price.
+        min_gas_price: u64,
+        /// The received gas price.
+        got: u64,
+    },
+
     /// The provided body hash does not match the actual hash of the body.
     #[error("the provided body hash does not match the actual hash of the body")]
     InvalidBodyHash,
@@ -201,6 +210,14 @@ pub enum DeployConfigurationFailure {
         attempted: U512,
     },
 
+    /// Failed to parse payment "payer" runtime argument.
+    #[error("failed to parse payment 'payer' as a public key")]
+    FailedToParsePayer,
+
+    /// A separate payer was specified for a native transfer.
+    #[error("a separate payer is not supported for native transfers")]
+    PayerNotSupportedForTransfer,
+
     /// The amount of approvals on the deploy exceeds the max_associated_keys limit.
     #[error("number of associated keys {got} exceeds the maximum {max_associated_keys}")]
     ExcessiveApprovals {
@@ -441,7 +458,8 @@ impl DeployHeader {
         let timestamp_valid = self.timestamp() <= current_timestamp;
         let not_expired = !self.expired(current_timestamp);
         let num_deps_valid = self.dependencies().len() <= deploy_config.max_dependencies as usize;
-        ttl_valid && timestamp_valid && not_expired && num_deps_valid
+        let gas_price_valid = self.gas_price() >= deploy_config.min_gas_price;
+        ttl_valid && timestamp_valid && not_expired && num_deps_valid && gas_price_valid
     }
 }
 
@@ -663,6 +681,21 @@ impl Deploy {
         &self.approvals
     }
 
+    /// Returns the public key of the account paying for this deploy, if one was specified via the
+    /// "payer" runtime argument of the payment code.
+    ///
+    /// If present, payment code is executed in the context of the payer's account, and the
+    /// approvals must include enough signatures from the payer's associated keys to meet its
+    /// deployment threshold.
+    pub fn payer(&self) -> Option<PublicKey> {
+        self.payment()
+            .args()
+            .get(ARG_PAYER)?
+            .clone()
+            .into_t::<PublicKey>()
+            .ok()
+    }
+
     /// Returns the hash of this deploy wrapped in `DeployOrTransferHash`.
     pub fn deploy_or_transfer_hash(&self) -> DeployOrTransferHash {
         if self.session.is_transfer() {
@@ -782,6 +815,19 @@ impl Deploy {
             });
         }
 
+        if header.gas_price() < config.min_gas_price {
+            info!(
+                deploy_hash = %self.id(),
+                deploy_header = %header,
+                min_gas_price = %config.min_gas_price,
+                "deploy gas price insufficient"
+            );
+            return Err(DeployConfigurationFailure::InsufficientGasPrice {
+                min_gas_price: config.min_gas_price,
+                got: header.gas_price(),
+            });
+        }
+
         if self.approvals.len() > max_associated_keys as usize {
             info!(
                 deploy_hash = %self.id(),
@@ -820,6 +866,17 @@ impl Deploy {
             }
         }
 
+        if let Some(value) = self.payment().args().get(ARG_PAYER) {
+            if self.session().is_transfer() {
+                info!("separate payer specified for native transfer");
+                return Err(DeployConfigurationFailure::PayerNotSupportedForTransfer);
+            }
+            value.clone().into_t::<PublicKey>().map_err(|_| {
+                info!("failed to parse payment 'payer' runtime argument as a public key");
+                DeployConfigurationFailure::FailedToParsePayer
+            })?;
+        }
+
         let payment_args_length = self.payment().args().serialized_length();
         if payment_args_length > config.payment_args_max_length as usize {
             info!(
@@ -1146,6 +1203,41 @@ impl Deploy {
         Self::random_transfer_with_session(rng, session)
     }
 
+    /// Creates a deploy whose payment is made by a separate, randomly generated payer account.
+    /// The payer's approval is only added if `payer_signs` is true.
+    pub(crate) fn random_with_payer(rng: &mut TestRng, payer_signs: bool) -> Self {
+        let deploy = Self::random_valid_native_transfer(rng);
+        let secret_key = SecretKey::random(rng);
+        let payer_secret_key = SecretKey::random(rng);
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! {
+                ARG_AMOUNT => U512::from(10),
+                ARG_PAYER => PublicKey::from(&payer_secret_key),
+            },
+        };
+        let session = ExecutableDeployItem::ModuleBytes {
+            module_bytes: vec![1].into(),
+            args: Default::default(),
+        };
+
+        let mut deploy = Deploy::new(
+            deploy.header.timestamp,
+            deploy.header.ttl,
+            deploy.header.gas_price,
+            deploy.header.dependencies,
+            deploy.header.chain_name,
+            payment,
+            session,
+            &secret_key,
+            None,
+        );
+        if payer_signs {
+            deploy.sign(&payer_secret_key);
+        }
+        deploy
+    }
+
     /// Creates a deploy with native transfer as payment code.
     pub(crate) fn random_with_native_transfer_in_payment_logic(rng: &mut TestRng) -> Self {
         let transfer_args = runtime_args! {
@@ -1292,7 +1384,14 @@ impl From<Deploy> for DeployItem {
             deploy.payment().clone(),
             deploy.header().gas_price(),
             authorization_keys,
+            deploy.payer().map(|payer| payer.to_account_hash()),
             casper_types::DeployHash::new(deploy.id().inner().value()),
+            deploy
+                .header()
+                .dependencies()
+                .iter()
+                .map(|dependency| casper_types::DeployHash::new(dependency.inner().value()))
+                .collect(),
         )
     }
 }
@@ -1626,6 +1725,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn not_acceptable_due_to_insufficient_gas_price() {
+        let mut rng = crate::new_rng();
+        let chain_name = "net-1";
+        let deploy_config = DeployConfig {
+            min_gas_price: 2,
+            ..DeployConfig::default()
+        };
+
+        let deploy = create_deploy(
+            &mut rng,
+            deploy_config.max_ttl,
+            deploy_config.max_dependencies.into(),
+            chain_name,
+        );
+
+        let expected_error = DeployConfigurationFailure::InsufficientGasPrice {
+            min_gas_price: 2,
+            got: 1,
+        };
+
+        assert_eq!(
+            deploy.is_config_compliant(chain_name, &deploy_config, DEFAULT_MAX_ASSOCIATED_KEYS),
+            Err(expected_error)
+        );
+        assert!(
+            deploy.is_valid.is_none(),
+            "deploy should not have run expensive `is_valid` call"
+        );
+    }
+
     #[test]
     fn not_acceptable_due_to_missing_payment_amount() {
         let mut rng = crate::new_rng();
@@ -1904,4 +2034,59 @@ mod tests {
             deploy.is_config_compliant(chain_name, &deploy_config, DEFAULT_MAX_ASSOCIATED_KEYS)
         )
     }
+
+    #[test]
+    fn not_acceptable_due_to_mangled_payer() {
+        let mut rng = crate::new_rng();
+        let chain_name = "net-1";
+        let deploy_config = DeployConfig::default();
+        let mut deploy = create_deploy(
+            &mut rng,
+            deploy_config.max_ttl,
+            deploy_config.max_dependencies as usize,
+            chain_name,
+        );
+
+        deploy.payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! {
+                "amount" => U512::from(MAX_PAYMENT_AMOUNT),
+                "payer" => "mangled-payer",
+            },
+        };
+        deploy.session = ExecutableDeployItem::ModuleBytes {
+            module_bytes: vec![1].into(),
+            args: RuntimeArgs::new(),
+        };
+
+        assert_eq!(
+            Err(DeployConfigurationFailure::FailedToParsePayer),
+            deploy.is_config_compliant(chain_name, &deploy_config, DEFAULT_MAX_ASSOCIATED_KEYS)
+        )
+    }
+
+    #[test]
+    fn not_acceptable_due_to_payer_for_transfer() {
+        let mut rng = crate::new_rng();
+        let chain_name = "net-1";
+        let deploy_config = DeployConfig::default();
+        let mut deploy = create_deploy(
+            &mut rng,
+            deploy_config.max_ttl,
+            deploy_config.max_dependencies as usize,
+            chain_name,
+        );
+
+        deploy.payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! {
+                "payer" => PublicKey::random(&mut rng),
+            },
+        };
+
+        assert_eq!(
+            Err(DeployConfigurationFailure::PayerNotSupportedForTransfer),
+            deploy.is_config_compliant(chain_name, &deploy_config, DEFAULT_MAX_ASSOCIATED_KEYS)
+        )
+    }
 }
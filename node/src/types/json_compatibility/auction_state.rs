@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use casper_hashing::Digest;
 use casper_types::{
-    system::auction::{Bid, Bids, DelegationRate, Delegator, EraValidators},
+    system::auction::{Bid, Bids, DelegationRate, DelegationRateChange, Delegator, EraValidators},
     AccessRights, EraId, PublicKey, SecretKey, URef, U512,
 };
 
@@ -109,6 +109,8 @@ pub struct JsonBid {
     delegators: Vec<JsonDelegator>,
     /// Is this an inactive validator.
     inactive: bool,
+    /// A delegation rate increase which has yet to take effect.
+    pending_delegation_rate_change: Option<DelegationRateChange>,
 }
 
 impl From<Bid> for JsonBid {
@@ -128,6 +130,7 @@ impl From<Bid> for JsonBid {
             delegation_rate: *bid.delegation_rate(),
             delegators: json_delegators,
             inactive: bid.inactive(),
+            pending_delegation_rate_change: bid.pending_delegation_rate_change().copied(),
         }
     }
 }
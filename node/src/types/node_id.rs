@@ -1,4 +1,8 @@
-use std::fmt::{self, Debug, Display, Formatter};
+use std::{
+    convert::TryFrom,
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
 
 use datasize::DataSize;
 use hex_fmt::HexFmt;
@@ -6,6 +10,7 @@ use once_cell::sync::Lazy;
 #[cfg(test)]
 use rand::Rng;
 use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
 
 #[cfg(test)]
 use crate::testing::TestRng;
@@ -100,13 +105,34 @@ impl From<KeyFingerprint> for NodeId {
     }
 }
 
-#[cfg(test)]
 impl From<[u8; KeyFingerprint::LENGTH]> for NodeId {
     fn from(raw_bytes: [u8; KeyFingerprint::LENGTH]) -> Self {
         NodeId(KeyFingerprint::from(raw_bytes))
     }
 }
 
+/// Error parsing a `NodeId` from the hex encoding of its full key fingerprint.
+#[derive(Debug, Error)]
+pub enum ParseNodeIdError {
+    /// The string is not valid hex.
+    #[error("invalid hex: {0}")]
+    Hex(#[from] hex::FromHexError),
+    /// The decoded bytes have the wrong length.
+    #[error("expected {} bytes, got {0}", KeyFingerprint::LENGTH)]
+    Length(usize),
+}
+
+impl FromStr for NodeId {
+    type Err = ParseNodeIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(value)?;
+        <[u8; KeyFingerprint::LENGTH]>::try_from(bytes.as_slice())
+            .map(NodeId::from)
+            .map_err(|_| ParseNodeIdError::Length(bytes.len()))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -128,6 +154,16 @@ mod test {
         assert_eq!(node_id, decoded);
     }
 
+    #[test]
+    fn from_str_roundtrip() {
+        let mut rng = crate::new_rng();
+        let node_id = NodeId::random(&mut rng);
+        let encoded = hex::encode(node_id.hash_bytes());
+        assert_eq!(node_id, encoded.parse().unwrap());
+        assert!(encoded[2..].parse::<NodeId>().is_err());
+        assert!("tls:0102".parse::<NodeId>().is_err());
+    }
+
     #[test]
     fn bincode_known_specimen() {
         let node_id = NodeId::from(EXAMPLE_HASH_RAW);
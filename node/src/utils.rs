@@ -221,6 +221,21 @@ pub(crate) fn write_private_file<P: AsRef<Path>, B: AsRef<[u8]>>(
         })
 }
 
+/// Writes data to `path` via a temporary file which is synced to disk and then moved to `path`, so
+/// that `path` is never left in a partially written state.
+pub(crate) fn write_file_atomically<P: AsRef<Path>, B: AsRef<[u8]>>(
+    filename: P,
+    data: B,
+) -> io::Result<()> {
+    let path = filename.as_ref();
+    let mut tmp_path = path.to_path_buf().into_os_string();
+    tmp_path.push(".tmp");
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(data.as_ref())?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
 /// With-directory context.
 ///
 /// Associates a type with a "working directory".
@@ -385,9 +400,9 @@ pub async fn wait_for_arc_drop<T>(arc: Arc<T>, attempts: usize, retry_delay: Dur
 
 #[cfg(test)]
 mod tests {
-    use std::{sync::Arc, time::Duration};
+    use std::{fs, sync::Arc, time::Duration};
 
-    use super::{wait_for_arc_drop, xor};
+    use super::{wait_for_arc_drop, write_file_atomically, xor};
 
     #[test]
     fn xor_works() {
@@ -409,6 +424,19 @@ mod tests {
         xor(&mut lhs, &rhs);
     }
 
+    #[test]
+    fn write_file_atomically_replaces_file() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("file");
+
+        write_file_atomically(&path, b"first").unwrap();
+        write_file_atomically(&path, b"second").unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"second");
+        // Only the file itself is left behind, not the temporary file.
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+    }
+
     #[tokio::test]
     async fn arc_drop_waits_for_drop() {
         let retry_delay = Duration::from_millis(25);
@@ -5,14 +5,20 @@ use core::convert::TryFrom;
 
 use casper_types::{
     account::{
-        AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
-        UpdateKeyFailure, Weight,
+        AccountHash, ActionType, AddKeyFailure, RecoveryActionType, RecoveryConfig,
+        RecoveryFailure, RecoveryRequest, RemoveKeyFailure, SetThresholdFailure,
+        SpendingLimitFailure, SpendingWindow, UpdateKeyFailure, Weight,
     },
-    bytesrepr, URef, UREF_SERIALIZED_LENGTH,
+    bytesrepr::{self, ToBytes},
+    URef, U512, UREF_SERIALIZED_LENGTH,
 };
 
 use super::to_ptr;
-use crate::{contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
+use crate::{
+    contract_api::{self, runtime},
+    ext_ffi,
+    unwrap_or_revert::UnwrapOrRevert,
+};
 
 /// Retrieves the ID of the account's main purse.
 #[doc(hidden)]
@@ -94,3 +100,89 @@ pub fn update_associated_key(
         Err(UpdateKeyFailure::try_from(result).unwrap_or_revert())
     }
 }
+
+/// Sets the spending limit of the given [`AccountHash`] in the account's associated keys to
+/// `limit` motes per [`SpendingWindow`], replacing any previous limit.
+pub fn set_spending_limit(
+    account_hash: AccountHash,
+    limit: U512,
+    window: SpendingWindow,
+) -> Result<(), SpendingLimitFailure> {
+    let (account_hash_ptr, account_hash_size, _bytes1) = to_ptr(account_hash);
+    let (limit_ptr, limit_size, _bytes2) = to_ptr(limit);
+    let (window_ptr, window_size, _bytes3) = to_ptr(window);
+    let result = unsafe {
+        ext_ffi::casper_set_spending_limit(
+            account_hash_ptr,
+            account_hash_size,
+            limit_ptr,
+            limit_size,
+            window_ptr,
+            window_size,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(SpendingLimitFailure::try_from(result).unwrap_or_revert())
+    }
+}
+
+/// Removes the spending limit of the given [`AccountHash`] in the account's associated keys.
+pub fn remove_spending_limit(account_hash: AccountHash) -> Result<(), SpendingLimitFailure> {
+    let (account_hash_ptr, account_hash_size, _bytes) = to_ptr(account_hash);
+    let result =
+        unsafe { ext_ffi::casper_remove_spending_limit(account_hash_ptr, account_hash_size) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(SpendingLimitFailure::try_from(result).unwrap_or_revert())
+    }
+}
+
+fn manage_recovery<T: ToBytes>(
+    action: RecoveryActionType,
+    account_hash: AccountHash,
+    value: T,
+) -> Result<(), RecoveryFailure> {
+    let (account_hash_ptr, account_hash_size, _bytes1) = to_ptr(account_hash);
+    let (value_ptr, value_size, _bytes2) = to_ptr(value);
+    let result = unsafe {
+        ext_ffi::casper_manage_recovery(
+            action as u32,
+            account_hash_ptr,
+            account_hash_size,
+            value_ptr,
+            value_size,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(RecoveryFailure::try_from(result).unwrap_or_revert())
+    }
+}
+
+/// Sets the keys which may recover the account, or clears them along with any pending recovery if
+/// `recovery_config` is `None`.
+pub fn set_recovery_config(recovery_config: Option<RecoveryConfig>) -> Result<(), RecoveryFailure> {
+    manage_recovery(
+        RecoveryActionType::Configure,
+        runtime::get_caller(),
+        recovery_config,
+    )
+}
+
+/// Starts the recovery of the account identified by `account_hash`, which replaces its associated
+/// keys and action thresholds with those of `request` once its recovery delay has passed.
+pub fn start_recovery(
+    account_hash: AccountHash,
+    request: RecoveryRequest,
+) -> Result<(), RecoveryFailure> {
+    manage_recovery(RecoveryActionType::Start, account_hash, request)
+}
+
+/// Cancels the account's pending recovery.
+pub fn cancel_recovery() -> Result<(), RecoveryFailure> {
+    manage_recovery(RecoveryActionType::Cancel, runtime::get_caller(), ())
+}
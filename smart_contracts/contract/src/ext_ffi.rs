@@ -211,6 +211,82 @@ extern "C" {
     /// * `action` - index representing the action threshold to set
     /// * `threshold` - new value of the threshold for performing this action
     pub fn casper_set_action_threshold(permission_level: u32, threshold: u32) -> i32;
+    /// This function sets the spending limit of the given associated key of the current
+    /// account, replacing any previous limit. Deploys which rely on a limited key to meet the
+    /// deployment threshold may only transfer up to the limit out of the account's purses within
+    /// each spending window, and limited keys don't count towards the key management threshold.
+    /// The result returned is a status code where 0 represents success, 1 means the key is not
+    /// associated to the account, 2 means permission denied (this could be because the function
+    /// was called outside of session code or because the key management threshold was not met
+    /// by the unlimited keys authorizing the deploy), and 3 means the key cannot be limited
+    /// because the remaining unlimited keys would not meet the key management threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_hash_ptr` - pointer to the bytes in wasm memory representing the account hash
+    ///   of the key to limit
+    /// * `account_hash_size` - size of the account hash
+    /// * `limit_ptr` - pointer to the bytes in wasm memory representing the limit in motes as a
+    ///   serialized `U512`
+    /// * `limit_size` - size of the limit
+    /// * `window_ptr` - pointer to the bytes in wasm memory representing a serialized
+    ///   `SpendingWindow`
+    /// * `window_size` - size of the spending window
+    pub fn casper_set_spending_limit(
+        account_hash_ptr: *const u8,
+        account_hash_size: usize,
+        limit_ptr: *const u8,
+        limit_size: usize,
+        window_ptr: *const u8,
+        window_size: usize,
+    ) -> i32;
+    /// This function removes the spending limit of the given associated key of the current
+    /// account. The result returned is a status code where 0 represents success, 1 means the key
+    /// has no spending limit, and 2 means permission denied (this could be because the function
+    /// was called outside of session code or because the key management threshold was not met
+    /// by the unlimited keys authorizing the deploy).
+    ///
+    /// # Arguments
+    ///
+    /// * `account_hash_ptr` - pointer to the bytes in wasm memory representing the account hash
+    ///   of the limited key
+    /// * `account_hash_size` - size of the account hash
+    pub fn casper_remove_spending_limit(
+        account_hash_ptr: *const u8,
+        account_hash_size: usize,
+    ) -> i32;
+    /// This function performs an action on the recovery of the given account. The action is one
+    /// of:
+    ///
+    /// * `0` (configure) - sets the current account's recovery keys, threshold and delay from a
+    ///   serialized `Option<RecoveryConfig>`, where `None` clears the configuration along with
+    ///   any pending recovery. Requires the key management threshold to be met.
+    /// * `1` (start) - starts a recovery of the given account from a serialized
+    ///   `RecoveryRequest`. Requires the weight of the given account's recovery keys authorizing
+    ///   the deploy to meet its recovery threshold. The request replaces the account's associated
+    ///   keys and action thresholds once the configured delay has passed.
+    /// * `2` (cancel) - cancels the current account's pending recovery. Requires a single key
+    ///   authorizing the deploy whose weight meets the deployment threshold on its own. The value
+    ///   is ignored.
+    ///
+    /// The result returned is a status code where 0 represents success, and any other value is a
+    /// `RecoveryFailure`.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - index representing the recovery action to perform
+    /// * `account_hash_ptr` - pointer to the bytes in wasm memory representing the account hash
+    ///   of the account to recover; for configure and cancel this must be the current account
+    /// * `account_hash_size` - size of the account hash
+    /// * `value_ptr` - pointer to the bytes in wasm memory representing the action's value
+    /// * `value_size` - size of the value
+    pub fn casper_manage_recovery(
+        action: u32,
+        account_hash_ptr: *const u8,
+        account_hash_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    ) -> i32;
     /// This function returns the public key of the account for this deploy. The
     /// result is always 36-bytes in length (4 bytes prefix on a 32-byte public
     /// key); it is up to the caller to ensure the right amount of memory is
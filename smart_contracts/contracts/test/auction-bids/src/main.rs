@@ -10,8 +10,9 @@ use casper_contract::contract_api::{runtime, system};
 use casper_types::{
     runtime_args,
     system::auction::{
-        ARG_DELEGATOR, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_REWARD_FACTORS, ARG_VALIDATOR,
-        METHOD_DELEGATE, METHOD_DISTRIBUTE, METHOD_RUN_AUCTION, METHOD_UNDELEGATE,
+        InactivityPolicy, ARG_DELEGATOR, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_INACTIVITY_POLICY,
+        ARG_REWARD_FACTORS, ARG_VALIDATOR, METHOD_DELEGATE, METHOD_DISTRIBUTE, METHOD_RUN_AUCTION,
+        METHOD_UNDELEGATE,
     },
     ApiError, PublicKey, RuntimeArgs, U512,
 };
@@ -84,7 +85,8 @@ fn distribute() {
     let auction = system::get_auction();
     let reward_factors: BTreeMap<PublicKey, u64> = runtime::get_named_arg(ARG_REWARD_FACTORS);
     let args = runtime_args! {
-        ARG_REWARD_FACTORS => reward_factors
+        ARG_REWARD_FACTORS => reward_factors,
+        ARG_INACTIVITY_POLICY => InactivityPolicy::default(),
     };
     runtime::call_contract::<()>(auction, METHOD_DISTRIBUTE, args);
 }
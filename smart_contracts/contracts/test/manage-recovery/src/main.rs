@@ -0,0 +1,59 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use casper_contract::{
+    contract_api::{account, runtime},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::{
+        AccountHash, ActionThresholds, AssociatedKeys, RecoveryConfig, RecoveryRequest, Weight,
+    },
+    ApiError,
+};
+
+const ARG_ACTION: &str = "action";
+const ARG_ACCOUNT: &str = "account";
+const ARG_DELAY: &str = "delay";
+
+const ACTION_CONFIGURE: &str = "configure";
+const ACTION_CLEAR: &str = "clear";
+const ACTION_START: &str = "start";
+const ACTION_CANCEL: &str = "cancel";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let action: String = runtime::get_named_arg(ARG_ACTION);
+
+    match action.as_str() {
+        // Makes `account` the sole recovery key of the current account.
+        ACTION_CONFIGURE => {
+            let recovery_key: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+            let delay: u64 = runtime::get_named_arg(ARG_DELAY);
+            let recovery_config = RecoveryConfig::new(
+                AssociatedKeys::new(recovery_key, Weight::new(1)),
+                Weight::new(1),
+                delay,
+            )
+            .unwrap_or_revert();
+            account::set_recovery_config(Some(recovery_config)).unwrap_or_revert()
+        }
+        ACTION_CLEAR => account::set_recovery_config(None).unwrap_or_revert(),
+        // Starts replacing the keys of `account` with the caller's key.
+        ACTION_START => {
+            let target: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+            let request = RecoveryRequest::new(
+                AssociatedKeys::new(runtime::get_caller(), Weight::new(1)),
+                ActionThresholds::new(Weight::new(1), Weight::new(1)).unwrap_or_revert(),
+            )
+            .unwrap_or_revert();
+            account::start_recovery(target, request).unwrap_or_revert()
+        }
+        ACTION_CANCEL => account::cancel_recovery().unwrap_or_revert(),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,27 @@
+#![no_std]
+#![no_main]
+
+use casper_contract::{
+    contract_api::{account, runtime},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::{AccountHash, SpendingWindow},
+    U512,
+};
+
+const ARG_ACCOUNT: &str = "account";
+const ARG_LIMIT: &str = "limit";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+    let limit: Option<U512> = runtime::get_named_arg(ARG_LIMIT);
+
+    match limit {
+        Some(limit) => {
+            account::set_spending_limit(account, limit, SpendingWindow::Era).unwrap_or_revert()
+        }
+        None => account::remove_spending_limit(account).unwrap_or_revert(),
+    }
+}
@@ -5,6 +5,8 @@ pub mod action_thresholds;
 mod action_type;
 pub mod associated_keys;
 mod error;
+pub mod recovery;
+pub mod spending_limits;
 mod weight;
 
 use serde::Serialize;
@@ -23,15 +25,17 @@ use blake2::{
 pub use self::{
     account_hash::{AccountHash, ACCOUNT_HASH_FORMATTED_STRING_PREFIX, ACCOUNT_HASH_LENGTH},
     action_thresholds::ActionThresholds,
-    action_type::ActionType,
+    action_type::{ActionType, RecoveryActionType},
     associated_keys::AssociatedKeys,
     error::{FromStrError, SetThresholdFailure, TryFromIntError, TryFromSliceForAccountHashError},
+    recovery::{PendingRecovery, RecoveryConfig, RecoveryFailure, RecoveryRequest},
+    spending_limits::{SpendingLimit, SpendingLimitFailure, SpendingLimits, SpendingWindow},
     weight::{Weight, WEIGHT_SERIALIZED_LENGTH},
 };
 use crate::{
     bytesrepr::{self, FromBytes, ToBytes},
     contracts::NamedKeys,
-    AccessRights, URef, BLAKE2B_DIGEST_LENGTH,
+    AccessRights, BlockTime, EraId, URef, BLAKE2B_DIGEST_LENGTH, U512,
 };
 
 /// Represents an Account in the global state.
@@ -42,6 +46,9 @@ pub struct Account {
     main_purse: URef,
     associated_keys: AssociatedKeys,
     action_thresholds: ActionThresholds,
+    spending_limits: SpendingLimits,
+    recovery_config: Option<RecoveryConfig>,
+    pending_recovery: Option<PendingRecovery>,
 }
 
 impl Account {
@@ -59,6 +66,9 @@ impl Account {
             main_purse,
             associated_keys,
             action_thresholds,
+            spending_limits: SpendingLimits::default(),
+            recovery_config: None,
+            pending_recovery: None,
         }
     }
 
@@ -119,6 +129,21 @@ impl Account {
         &self.action_thresholds
     }
 
+    /// Returns spending limits of the associated keys.
+    pub fn spending_limits(&self) -> &SpendingLimits {
+        &self.spending_limits
+    }
+
+    /// Returns the recovery configuration, if any.
+    pub fn recovery_config(&self) -> Option<&RecoveryConfig> {
+        self.recovery_config.as_ref()
+    }
+
+    /// Returns the pending recovery, if any.
+    pub fn pending_recovery(&self) -> Option<&PendingRecovery> {
+        self.pending_recovery.as_ref()
+    }
+
     /// Adds an associated key to an account.
     pub fn add_associated_key(
         &mut self,
@@ -138,6 +163,8 @@ impl Account {
         // equal to all of the thresholds.
         total_weight_without >= *self.action_thresholds().deployment()
             && total_weight_without >= *self.action_thresholds().key_management()
+            && self.unlimited_keys_weight(Some(account_hash))
+                >= *self.action_thresholds().key_management()
     }
 
     /// Checks if adding a weight to a sum of all weights excluding the given key would make the
@@ -153,8 +180,41 @@ impl Account {
 
         // Returns true if the new weight would be greater or equal to all of
         // the thresholds.
+        // Keys with a spending limit don't count towards the key management threshold
+        let unlimited_weight = self.unlimited_keys_weight(Some(account_hash));
+        let new_unlimited_weight = if self.spending_limits.contains_key(&account_hash) {
+            unlimited_weight.value()
+        } else {
+            unlimited_weight.value().saturating_add(weight.value())
+        };
+
         new_weight >= self.action_thresholds().deployment().value()
             && new_weight >= self.action_thresholds().key_management().value()
+            && new_unlimited_weight >= self.action_thresholds().key_management().value()
+    }
+
+    /// Calculates total weight of all associated keys without a spending limit, optionally
+    /// excluding the given key.
+    fn unlimited_keys_weight(&self, excluding: Option<AccountHash>) -> Weight {
+        let unlimited_keys = self
+            .associated_keys
+            .iter()
+            .map(|(key, _)| *key)
+            .filter(|key| Some(*key) != excluding && !self.spending_limits.contains_key(key))
+            .collect();
+        self.associated_keys.calculate_keys_weight(&unlimited_keys)
+    }
+
+    /// Returns the subset of the given authorization keys which don't have a spending limit.
+    fn unlimited_authorization_keys(
+        &self,
+        authorization_keys: &BTreeSet<AccountHash>,
+    ) -> BTreeSet<AccountHash> {
+        authorization_keys
+            .iter()
+            .filter(|key| !self.spending_limits.contains_key(key))
+            .copied()
+            .collect()
     }
 
     /// Removes an associated key from an account.
@@ -171,7 +231,9 @@ impl Account {
                 return Err(RemoveKeyFailure::ThresholdViolation);
             }
         }
-        self.associated_keys.remove_key(&account_hash)
+        self.associated_keys.remove_key(&account_hash)?;
+        self.spending_limits.remove(&account_hash);
+        Ok(())
     }
 
     /// Updates an associated key.
@@ -193,6 +255,38 @@ impl Account {
         self.associated_keys.update_key(account_hash, weight)
     }
 
+    /// Sets the spending limit of an associated key, replacing any previous limit.
+    ///
+    /// Returns an error if the remaining unlimited keys would no longer be able to meet the key
+    /// management threshold.
+    pub fn set_spending_limit(
+        &mut self,
+        account_hash: AccountHash,
+        spending_limit: SpendingLimit,
+    ) -> Result<(), SpendingLimitFailure> {
+        if !self.associated_keys.contains_key(&account_hash) {
+            return Err(SpendingLimitFailure::MissingKey);
+        }
+        if self.unlimited_keys_weight(Some(account_hash))
+            < *self.action_thresholds().key_management()
+        {
+            return Err(SpendingLimitFailure::ThresholdViolation);
+        }
+        self.spending_limits.insert(account_hash, spending_limit);
+        Ok(())
+    }
+
+    /// Removes the spending limit of an associated key.
+    pub fn remove_spending_limit(
+        &mut self,
+        account_hash: AccountHash,
+    ) -> Result<(), SpendingLimitFailure> {
+        self.spending_limits
+            .remove(&account_hash)
+            .map(|_| ())
+            .ok_or(SpendingLimitFailure::MissingKey)
+    }
+
     /// Sets new action threshold for a given action type for the account.
     ///
     /// Returns an error if the new action threshold weight is greater than the total weight of the
@@ -205,6 +299,12 @@ impl Account {
         // Verify if new threshold weight exceeds total weight of all associated
         // keys.
         self.can_set_threshold(weight)?;
+        // Keys with a spending limit don't count towards the key management threshold
+        if let ActionType::KeyManagement = action_type {
+            if weight > self.unlimited_keys_weight(None) {
+                return Err(SetThresholdFailure::InsufficientTotalWeight);
+            }
+        }
         // Set new weight for given action
         self.action_thresholds.set_threshold(action_type, weight)
     }
@@ -226,6 +326,18 @@ impl Account {
                 .all(|e| self.associated_keys.contains_key(e))
     }
 
+    /// Returns the subset of the given authorization keys which are associated with this account.
+    pub fn associated_authorization_keys(
+        &self,
+        authorization_keys: &BTreeSet<AccountHash>,
+    ) -> BTreeSet<AccountHash> {
+        authorization_keys
+            .iter()
+            .filter(|key| self.associated_keys.contains_key(key))
+            .copied()
+            .collect()
+    }
+
     /// Checks whether the sum of the weights of all authorization keys is
     /// greater or equal to deploy threshold.
     pub fn can_deploy_with(&self, authorization_keys: &BTreeSet<AccountHash>) -> bool {
@@ -238,53 +350,206 @@ impl Account {
 
     /// Checks whether the sum of the weights of all authorization keys is
     /// greater or equal to key management threshold.
+    ///
+    /// Keys with a spending limit don't contribute any weight, so that they can't lift their own
+    /// limits.
     pub fn can_manage_keys_with(&self, authorization_keys: &BTreeSet<AccountHash>) -> bool {
         let total_weight = self
             .associated_keys
-            .calculate_keys_weight(authorization_keys);
+            .calculate_keys_weight(&self.unlimited_authorization_keys(authorization_keys));
 
         total_weight >= *self.action_thresholds().key_management()
     }
+
+    /// Records spending of `amount` against the spending limits of the given authorization keys.
+    ///
+    /// Spending is unrestricted if the authorization keys without a spending limit meet the
+    /// deploy threshold on their own.  Otherwise it is recorded against every limited
+    /// authorization key, and no limit is updated if the amount exceeds any one of them.
+    pub fn record_spending(
+        &mut self,
+        authorization_keys: &BTreeSet<AccountHash>,
+        amount: U512,
+        era_id: EraId,
+        blocktime: BlockTime,
+    ) -> Result<(), SpendingLimitFailure> {
+        if self.can_deploy_with(&self.unlimited_authorization_keys(authorization_keys)) {
+            return Ok(());
+        }
+        let mut spending_limits = self.spending_limits.clone();
+        for key in authorization_keys {
+            if let Some(spending_limit) = spending_limits.get_mut(key) {
+                spending_limit.record_spending(amount, era_id, blocktime)?;
+            }
+        }
+        self.spending_limits = spending_limits;
+        Ok(())
+    }
+
+    /// Sets or clears the recovery configuration.
+    ///
+    /// Clearing the configuration also drops any pending recovery.
+    pub fn set_recovery_config(&mut self, recovery_config: Option<RecoveryConfig>) {
+        if recovery_config.is_none() {
+            self.pending_recovery = None;
+        }
+        self.recovery_config = recovery_config;
+    }
+
+    /// Starts a recovery which takes effect once the configured delay has passed since
+    /// `blocktime`.
+    ///
+    /// The recovery keys among the authorization keys need to meet the recovery threshold, and
+    /// only one recovery can be pending at a time.
+    pub fn start_recovery(
+        &mut self,
+        authorization_keys: &BTreeSet<AccountHash>,
+        request: RecoveryRequest,
+        blocktime: BlockTime,
+    ) -> Result<(), RecoveryFailure> {
+        let recovery_config = self
+            .recovery_config
+            .as_ref()
+            .ok_or(RecoveryFailure::NotConfigured)?;
+        if !recovery_config.can_recover_with(authorization_keys) {
+            return Err(RecoveryFailure::PermissionDenied);
+        }
+        if self.pending_recovery.is_some() {
+            return Err(RecoveryFailure::AlreadyPending);
+        }
+        let effective_at = u64::from(blocktime).saturating_add(recovery_config.delay());
+        self.pending_recovery = Some(PendingRecovery::new(request, effective_at));
+        Ok(())
+    }
+
+    /// Cancels the pending recovery.
+    ///
+    /// Any single associated key whose weight meets the deploy threshold on its own may cancel.
+    pub fn cancel_recovery(
+        &mut self,
+        authorization_keys: &BTreeSet<AccountHash>,
+    ) -> Result<(), RecoveryFailure> {
+        if self.pending_recovery.is_none() {
+            return Err(RecoveryFailure::NoPendingRecovery);
+        }
+        let deployment_threshold = *self.action_thresholds.deployment();
+        let can_cancel = authorization_keys.iter().any(|key| {
+            matches!(self.associated_keys.get(key), Some(weight) if *weight >= deployment_threshold)
+        });
+        if !can_cancel {
+            return Err(RecoveryFailure::PermissionDenied);
+        }
+        self.pending_recovery = None;
+        Ok(())
+    }
+
+    /// Applies the pending recovery if it has taken effect at `blocktime`, replacing the
+    /// associated keys and action thresholds and dropping all spending limits.
+    ///
+    /// Returns `true` if the account was modified.
+    pub fn apply_matured_recovery(&mut self, blocktime: BlockTime) -> bool {
+        match self.pending_recovery.take() {
+            Some(pending_recovery) if pending_recovery.is_effective(blocktime) => {
+                let (associated_keys, action_thresholds) =
+                    pending_recovery.into_request().into_parts();
+                self.associated_keys = associated_keys;
+                self.action_thresholds = action_thresholds;
+                self.spending_limits = SpendingLimits::default();
+                true
+            }
+            pending_recovery => {
+                self.pending_recovery = pending_recovery;
+                false
+            }
+        }
+    }
 }
 
-impl ToBytes for Account {
-    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+impl Account {
+    /// Returns `true` if the account uses neither spending limits nor recovery, so that it can be
+    /// stored in the layout accounts had before those were introduced.
+    pub(crate) fn has_legacy_layout(&self) -> bool {
+        self.spending_limits.is_empty()
+            && self.recovery_config.is_none()
+            && self.pending_recovery.is_none()
+    }
+
+    /// Serializes the fields of the layout accounts had before spending limits and recovery were
+    /// introduced.
+    pub(crate) fn legacy_to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
-        result.append(&mut self.account_hash.to_bytes()?);
-        result.append(&mut self.named_keys.to_bytes()?);
-        result.append(&mut self.main_purse.to_bytes()?);
-        result.append(&mut self.associated_keys.to_bytes()?);
-        result.append(&mut self.action_thresholds.to_bytes()?);
+        self.append_legacy_bytes(&mut result)?;
         Ok(result)
     }
 
-    fn serialized_length(&self) -> usize {
+    /// Returns the length of the account serialized by [`Account::legacy_to_bytes`].
+    pub(crate) fn legacy_serialized_length(&self) -> usize {
         self.account_hash.serialized_length()
             + self.named_keys.serialized_length()
             + self.main_purse.serialized_length()
             + self.associated_keys.serialized_length()
             + self.action_thresholds.serialized_length()
     }
-}
 
-impl FromBytes for Account {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+    /// Deserializes an account stored in the layout accounts had before spending limits and
+    /// recovery were introduced.
+    pub(crate) fn legacy_from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (account_hash, rem) = AccountHash::from_bytes(bytes)?;
         let (named_keys, rem) = NamedKeys::from_bytes(rem)?;
         let (main_purse, rem) = URef::from_bytes(rem)?;
         let (associated_keys, rem) = AssociatedKeys::from_bytes(rem)?;
         let (action_thresholds, rem) = ActionThresholds::from_bytes(rem)?;
         Ok((
-            Account {
+            Account::new(
                 account_hash,
                 named_keys,
                 main_purse,
                 associated_keys,
                 action_thresholds,
-            },
+            ),
             rem,
         ))
     }
+
+    fn append_legacy_bytes(&self, result: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        result.append(&mut self.account_hash.to_bytes()?);
+        result.append(&mut self.named_keys.to_bytes()?);
+        result.append(&mut self.main_purse.to_bytes()?);
+        result.append(&mut self.associated_keys.to_bytes()?);
+        result.append(&mut self.action_thresholds.to_bytes()?);
+        Ok(())
+    }
+}
+
+impl ToBytes for Account {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.append_legacy_bytes(&mut result)?;
+        result.append(&mut self.spending_limits.to_bytes()?);
+        result.append(&mut self.recovery_config.to_bytes()?);
+        result.append(&mut self.pending_recovery.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.legacy_serialized_length()
+            + self.spending_limits.serialized_length()
+            + self.recovery_config.serialized_length()
+            + self.pending_recovery.serialized_length()
+    }
+}
+
+impl FromBytes for Account {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (mut account, rem) = Account::legacy_from_bytes(bytes)?;
+        let (spending_limits, rem) = SpendingLimits::from_bytes(rem)?;
+        let (recovery_config, rem) = Option::<RecoveryConfig>::from_bytes(rem)?;
+        let (pending_recovery, rem) = Option::<PendingRecovery>::from_bytes(rem)?;
+        account.spending_limits = spending_limits;
+        account.recovery_config = recovery_config;
+        account.pending_recovery = pending_recovery;
+        Ok((account, rem))
+    }
 }
 
 #[doc(hidden)]
@@ -448,7 +713,10 @@ pub mod gens {
     use crate::{
         account::{
             action_thresholds::gens::action_thresholds_arb,
-            associated_keys::gens::associated_keys_arb, Account, Weight,
+            associated_keys::gens::associated_keys_arb,
+            recovery::gens::{pending_recovery_arb, recovery_config_arb},
+            spending_limits::gens::spending_limit_arb,
+            Account, Weight,
         },
         gens::{account_hash_arb, named_keys_arb, uref_arb},
     };
@@ -460,15 +728,24 @@ pub mod gens {
             purse in uref_arb(),
             thresholds in action_thresholds_arb(),
             mut associated_keys in associated_keys_arb(),
+            spending_limit in proptest::option::of(spending_limit_arb()),
+            recovery_config in proptest::option::of(recovery_config_arb()),
+            pending_recovery in proptest::option::of(pending_recovery_arb()),
         ) -> Account {
                 associated_keys.add_key(account_hash, Weight::new(1)).unwrap();
-                Account::new(
+                let mut account = Account::new(
                     account_hash,
                     urefs,
                     purse,
                     associated_keys,
                     thresholds,
-                )
+                );
+                if let Some(spending_limit) = spending_limit {
+                    account.spending_limits.insert(account_hash, spending_limit);
+                }
+                account.recovery_config = recovery_config;
+                account.pending_recovery = pending_recovery;
+                account
         }
     }
 }
@@ -481,7 +758,7 @@ mod tests {
             SetThresholdFailure, UpdateKeyFailure, Weight,
         },
         contracts::NamedKeys,
-        AccessRights, URef,
+        AccessRights, CLValue, StoredValue, URef,
     };
     use std::{collections::BTreeSet, convert::TryFrom, iter::FromIterator, vec::Vec};
 
@@ -686,6 +963,35 @@ mod tests {
         ])));
     }
 
+    #[test]
+    fn account_associated_authorization_keys() {
+        let associated_keys = {
+            let mut res = AssociatedKeys::new(AccountHash::new([1u8; 32]), Weight::new(1));
+            res.add_key(AccountHash::new([2u8; 32]), Weight::new(1))
+                .expect("should add key 1");
+            res
+        };
+        let account = Account::new(
+            AccountHash::new([0u8; 32]),
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            associated_keys,
+            Default::default(),
+        );
+
+        let authorization_keys = BTreeSet::from_iter(vec![
+            AccountHash::new([2u8; 32]),
+            AccountHash::new([5u8; 32]),
+        ]);
+        assert_eq!(
+            account.associated_authorization_keys(&authorization_keys),
+            BTreeSet::from_iter(vec![AccountHash::new([2u8; 32])])
+        );
+        assert!(account
+            .associated_authorization_keys(&BTreeSet::from_iter(vec![AccountHash::new([5u8; 32])]))
+            .is_empty());
+    }
+
     #[test]
     fn account_can_manage_keys_with() {
         let associated_keys = {
@@ -935,6 +1241,236 @@ mod tests {
             .update_associated_key(key_1, Weight::new(1))
             .expect("should work");
     }
+
+    fn account_with_hot_key() -> (Account, AccountHash, AccountHash) {
+        let identity_key = AccountHash::new([1u8; 32]);
+        let hot_key = AccountHash::new([2u8; 32]);
+        let associated_keys = {
+            let mut res = AssociatedKeys::new(identity_key, Weight::new(2));
+            res.add_key(hot_key, Weight::new(1))
+                .expect("should add hot key");
+            res
+        };
+        let account = Account::new(
+            identity_key,
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            associated_keys,
+            ActionThresholds::new(Weight::new(1), Weight::new(2))
+                .expect("should create thresholds"),
+        );
+        (account, identity_key, hot_key)
+    }
+
+    #[test]
+    fn spending_limit_should_restrict_limited_keys_only() {
+        let (mut account, identity_key, hot_key) = account_with_hot_key();
+        account
+            .set_spending_limit(
+                hot_key,
+                SpendingLimit::new(U512::from(100), SpendingWindow::Era),
+            )
+            .expect("should set spending limit");
+
+        let era_id = EraId::new(1);
+        let blocktime = BlockTime::new(0);
+        let hot_keys = BTreeSet::from_iter(vec![hot_key]);
+        let all_keys = BTreeSet::from_iter(vec![identity_key, hot_key]);
+
+        account
+            .record_spending(&all_keys, U512::from(1_000), era_id, blocktime)
+            .expect("unlimited key should not be restricted");
+        account
+            .record_spending(&hot_keys, U512::from(100), era_id, blocktime)
+            .expect("should spend up to the limit");
+        assert_eq!(
+            account.record_spending(&hot_keys, U512::one(), era_id, blocktime),
+            Err(SpendingLimitFailure::LimitExceeded)
+        );
+        assert_eq!(
+            account
+                .spending_limits()
+                .get(&hot_key)
+                .expect("should have spending limit")
+                .spent(),
+            U512::from(100)
+        );
+    }
+
+    #[test]
+    fn limited_keys_should_not_manage_keys() {
+        let (mut account, identity_key, hot_key) = account_with_hot_key();
+        let hot_keys = BTreeSet::from_iter(vec![hot_key, AccountHash::new([3u8; 32])]);
+        account
+            .update_associated_key(hot_key, Weight::new(2))
+            .expect("should update hot key");
+        assert!(account.can_manage_keys_with(&hot_keys));
+
+        account
+            .set_spending_limit(
+                hot_key,
+                SpendingLimit::new(U512::from(100), SpendingWindow::Era),
+            )
+            .expect("should set spending limit");
+        assert!(!account.can_manage_keys_with(&hot_keys));
+        assert!(account.can_manage_keys_with(&BTreeSet::from_iter(vec![identity_key])));
+    }
+
+    #[test]
+    fn spending_limit_should_not_lock_out_key_management() {
+        let (mut account, identity_key, hot_key) = account_with_hot_key();
+        let spending_limit = SpendingLimit::new(U512::from(100), SpendingWindow::Era);
+        assert_eq!(
+            account.set_spending_limit(identity_key, spending_limit.clone()),
+            Err(SpendingLimitFailure::ThresholdViolation)
+        );
+        assert_eq!(
+            account.set_spending_limit(AccountHash::new([3u8; 32]), spending_limit.clone()),
+            Err(SpendingLimitFailure::MissingKey)
+        );
+        account
+            .set_spending_limit(hot_key, spending_limit)
+            .expect("should set spending limit");
+        assert_eq!(
+            account.update_associated_key(identity_key, Weight::new(1)),
+            Err(UpdateKeyFailure::ThresholdViolation)
+        );
+
+        account
+            .remove_associated_key(hot_key)
+            .expect("should remove hot key");
+        assert!(account.spending_limits().is_empty());
+        assert_eq!(
+            account.remove_spending_limit(hot_key),
+            Err(SpendingLimitFailure::MissingKey)
+        );
+    }
+
+    #[test]
+    fn should_store_account_without_extensions_in_legacy_layout() {
+        let (account, _, _) = account_with_hot_key();
+        let stored_value = StoredValue::Account(account.clone());
+        let bytes = stored_value.to_bytes().expect("should serialize");
+        assert_eq!(bytes.len(), stored_value.serialized_length());
+
+        let mut legacy_bytes = vec![1u8];
+        legacy_bytes.extend(account.legacy_to_bytes().expect("should serialize"));
+        assert_eq!(bytes, legacy_bytes);
+
+        bytesrepr::test_serialization_roundtrip(&stored_value);
+    }
+
+    #[test]
+    fn should_deserialize_accounts_followed_by_other_values() {
+        let (legacy_account, _, hot_key) = account_with_hot_key();
+        let mut extended_account = legacy_account.clone();
+        extended_account
+            .set_spending_limit(
+                hot_key,
+                SpendingLimit::new(U512::from(100), SpendingWindow::Era),
+            )
+            .expect("should set spending limit");
+        assert!(!extended_account.has_legacy_layout());
+
+        let stored_values = vec![
+            StoredValue::Account(legacy_account),
+            StoredValue::Account(extended_account),
+            StoredValue::CLValue(CLValue::from_t(1u8).expect("should create CLValue")),
+        ];
+        bytesrepr::test_serialization_roundtrip(&stored_values);
+    }
+
+    fn account_with_recovery_key() -> (Account, AccountHash, AccountHash, RecoveryRequest) {
+        let (mut account, identity_key, hot_key) = account_with_hot_key();
+        let recovery_key = AccountHash::new([3u8; 32]);
+        let recovery_config = RecoveryConfig::new(
+            AssociatedKeys::new(recovery_key, Weight::new(1)),
+            Weight::new(1),
+            1_000,
+        )
+        .expect("should create recovery config");
+        account.set_recovery_config(Some(recovery_config));
+        let new_key = AccountHash::new([4u8; 32]);
+        let request = RecoveryRequest::new(
+            AssociatedKeys::new(new_key, Weight::new(1)),
+            ActionThresholds::new(Weight::new(1), Weight::new(1))
+                .expect("should create thresholds"),
+        )
+        .expect("should create recovery request");
+        (account, identity_key, hot_key, request)
+    }
+
+    #[test]
+    fn recovery_should_take_effect_after_delay() {
+        let (mut account, identity_key, _, request) = account_with_recovery_key();
+        let recovery_keys = BTreeSet::from_iter(vec![AccountHash::new([3u8; 32])]);
+
+        assert_eq!(
+            account.start_recovery(
+                &BTreeSet::from_iter(vec![identity_key]),
+                request.clone(),
+                BlockTime::new(0)
+            ),
+            Err(RecoveryFailure::PermissionDenied)
+        );
+        account
+            .start_recovery(&recovery_keys, request.clone(), BlockTime::new(0))
+            .expect("should start recovery");
+        assert_eq!(
+            account.start_recovery(&recovery_keys, request.clone(), BlockTime::new(0)),
+            Err(RecoveryFailure::AlreadyPending)
+        );
+
+        assert!(!account.apply_matured_recovery(BlockTime::new(999)));
+        assert!(account.associated_keys().contains_key(&identity_key));
+        assert!(account.pending_recovery().is_some());
+
+        assert!(account.apply_matured_recovery(BlockTime::new(1_000)));
+        assert_eq!(account.associated_keys(), request.associated_keys());
+        assert_eq!(account.action_thresholds(), request.action_thresholds());
+        assert!(account.pending_recovery().is_none());
+        assert!(account.recovery_config().is_some());
+    }
+
+    #[test]
+    fn recovery_should_be_cancellable_by_deploying_key() {
+        let (mut account, identity_key, hot_key, request) = account_with_recovery_key();
+        let recovery_keys = BTreeSet::from_iter(vec![AccountHash::new([3u8; 32])]);
+        assert_eq!(
+            account.cancel_recovery(&BTreeSet::from_iter(vec![identity_key])),
+            Err(RecoveryFailure::NoPendingRecovery)
+        );
+
+        account
+            .start_recovery(&recovery_keys, request, BlockTime::new(0))
+            .expect("should start recovery");
+        assert_eq!(
+            account.cancel_recovery(&recovery_keys),
+            Err(RecoveryFailure::PermissionDenied)
+        );
+        account
+            .cancel_recovery(&BTreeSet::from_iter(vec![hot_key]))
+            .expect("should cancel recovery");
+        assert!(account.pending_recovery().is_none());
+        assert!(!account.apply_matured_recovery(BlockTime::new(1_000)));
+        assert!(account.associated_keys().contains_key(&identity_key));
+    }
+
+    #[test]
+    fn recovery_requires_config() {
+        let (mut account, _, _, request) = account_with_recovery_key();
+        let recovery_keys = BTreeSet::from_iter(vec![AccountHash::new([3u8; 32])]);
+        account
+            .start_recovery(&recovery_keys, request.clone(), BlockTime::new(0))
+            .expect("should start recovery");
+
+        account.set_recovery_config(None);
+        assert!(account.pending_recovery().is_none());
+        assert_eq!(
+            account.start_recovery(&recovery_keys, request, BlockTime::new(0)),
+            Err(RecoveryFailure::NotConfigured)
+        );
+    }
 }
 
 #[cfg(test)]
@@ -30,3 +30,30 @@ impl TryFrom<u32> for ActionType {
         }
     }
 }
+
+/// The various actions which can be performed on the recovery of a given account.
+#[repr(u32)]
+pub enum RecoveryActionType {
+    /// Represents setting or clearing the recovery keys, threshold and delay of an account.
+    Configure = 0,
+    /// Represents starting a recovery which replaces the associated keys and action thresholds of
+    /// an account once the recovery delay has passed.
+    Start = 1,
+    /// Represents cancelling a pending recovery.
+    Cancel = 2,
+}
+
+// This conversion is not intended to be used by third party crates.
+#[doc(hidden)]
+impl TryFrom<u32> for RecoveryActionType {
+    type Error = TryFromIntError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == RecoveryActionType::Configure as u32 => Ok(RecoveryActionType::Configure),
+            d if d == RecoveryActionType::Start as u32 => Ok(RecoveryActionType::Start),
+            d if d == RecoveryActionType::Cancel as u32 => Ok(RecoveryActionType::Cancel),
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
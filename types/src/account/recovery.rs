@@ -0,0 +1,385 @@
+//! This module contains types and functions for recovering an account whose keys have been lost.
+
+use alloc::{collections::BTreeSet, vec::Vec};
+use core::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{AccountHash, ActionThresholds, AssociatedKeys, TryFromIntError, Weight},
+    bytesrepr::{self, Error, FromBytes, ToBytes},
+    BlockTime,
+};
+
+/// The keys which may start the recovery of an account, and the delay before a recovery takes
+/// effect.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct RecoveryConfig {
+    recovery_keys: AssociatedKeys,
+    threshold: Weight,
+    delay: u64,
+}
+
+impl RecoveryConfig {
+    /// Creates a new `RecoveryConfig`.
+    ///
+    /// A recovery can be started by deploys authorized by recovery keys whose total weight meets
+    /// `threshold`, and takes effect once `delay` milliseconds have passed.
+    pub fn new(
+        recovery_keys: AssociatedKeys,
+        threshold: Weight,
+        delay: u64,
+    ) -> Result<Self, RecoveryFailure> {
+        if threshold.value() == 0 || recovery_keys.total_keys_weight() < threshold {
+            return Err(RecoveryFailure::InvalidConfig);
+        }
+        Ok(RecoveryConfig {
+            recovery_keys,
+            threshold,
+            delay,
+        })
+    }
+
+    /// Returns the recovery keys.
+    pub fn recovery_keys(&self) -> &AssociatedKeys {
+        &self.recovery_keys
+    }
+
+    /// Returns the total weight of recovery keys required to start a recovery.
+    pub fn threshold(&self) -> Weight {
+        self.threshold
+    }
+
+    /// Returns the delay in milliseconds between starting a recovery and it taking effect.
+    pub fn delay(&self) -> u64 {
+        self.delay
+    }
+
+    /// Checks whether the sum of the weights of the recovery keys among the authorization keys is
+    /// greater or equal to the recovery threshold.
+    pub fn can_recover_with(&self, authorization_keys: &BTreeSet<AccountHash>) -> bool {
+        self.recovery_keys.calculate_keys_weight(authorization_keys) >= self.threshold
+    }
+}
+
+impl ToBytes for RecoveryConfig {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.recovery_keys.to_bytes()?);
+        result.append(&mut self.threshold.to_bytes()?);
+        result.append(&mut self.delay.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.recovery_keys.serialized_length()
+            + self.threshold.serialized_length()
+            + self.delay.serialized_length()
+    }
+}
+
+impl FromBytes for RecoveryConfig {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (recovery_keys, rem) = AssociatedKeys::from_bytes(bytes)?;
+        let (threshold, rem) = Weight::from_bytes(rem)?;
+        let (delay, rem) = u64::from_bytes(rem)?;
+        // Re-validate rather than trusting the serialized form.
+        let recovery_config =
+            RecoveryConfig::new(recovery_keys, threshold, delay).map_err(|_| Error::Formatting)?;
+        Ok((recovery_config, rem))
+    }
+}
+
+/// The associated keys and action thresholds which replace those of an account once a recovery
+/// takes effect.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct RecoveryRequest {
+    associated_keys: AssociatedKeys,
+    action_thresholds: ActionThresholds,
+}
+
+impl RecoveryRequest {
+    /// Creates a new `RecoveryRequest`.
+    ///
+    /// Returns an error if the new associated keys can't meet the new action thresholds.
+    pub fn new(
+        associated_keys: AssociatedKeys,
+        action_thresholds: ActionThresholds,
+    ) -> Result<Self, RecoveryFailure> {
+        let total_weight = associated_keys.total_keys_weight();
+        if total_weight < *action_thresholds.deployment()
+            || total_weight < *action_thresholds.key_management()
+        {
+            return Err(RecoveryFailure::InvalidRequest);
+        }
+        Ok(RecoveryRequest {
+            associated_keys,
+            action_thresholds,
+        })
+    }
+
+    /// Returns the new associated keys.
+    pub fn associated_keys(&self) -> &AssociatedKeys {
+        &self.associated_keys
+    }
+
+    /// Returns the new action thresholds.
+    pub fn action_thresholds(&self) -> &ActionThresholds {
+        &self.action_thresholds
+    }
+
+    /// Consumes `self`, returning the new associated keys and action thresholds.
+    pub fn into_parts(self) -> (AssociatedKeys, ActionThresholds) {
+        (self.associated_keys, self.action_thresholds)
+    }
+}
+
+impl ToBytes for RecoveryRequest {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.associated_keys.to_bytes()?);
+        result.append(&mut self.action_thresholds.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.associated_keys.serialized_length() + self.action_thresholds.serialized_length()
+    }
+}
+
+impl FromBytes for RecoveryRequest {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (associated_keys, rem) = AssociatedKeys::from_bytes(bytes)?;
+        let (action_thresholds, rem) = ActionThresholds::from_bytes(rem)?;
+        // Re-validate rather than trusting the serialized form.
+        let request = RecoveryRequest::new(associated_keys, action_thresholds)
+            .map_err(|_| Error::Formatting)?;
+        Ok((request, rem))
+    }
+}
+
+/// A [`RecoveryRequest`] which has been started but hasn't taken effect yet.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct PendingRecovery {
+    request: RecoveryRequest,
+    effective_at: u64,
+}
+
+impl PendingRecovery {
+    /// Creates a new `PendingRecovery` taking effect at the given block time, in milliseconds.
+    pub fn new(request: RecoveryRequest, effective_at: u64) -> Self {
+        PendingRecovery {
+            request,
+            effective_at,
+        }
+    }
+
+    /// Returns the request which will take effect.
+    pub fn request(&self) -> &RecoveryRequest {
+        &self.request
+    }
+
+    /// Returns the block time from which the request takes effect, in milliseconds.
+    pub fn effective_at(&self) -> u64 {
+        self.effective_at
+    }
+
+    /// Returns `true` if the request takes effect at the given block time.
+    pub fn is_effective(&self, blocktime: BlockTime) -> bool {
+        u64::from(blocktime) >= self.effective_at
+    }
+
+    /// Consumes `self`, returning the request.
+    pub fn into_request(self) -> RecoveryRequest {
+        self.request
+    }
+}
+
+impl ToBytes for PendingRecovery {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.request.to_bytes()?);
+        result.append(&mut self.effective_at.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.request.serialized_length() + self.effective_at.serialized_length()
+    }
+}
+
+impl FromBytes for PendingRecovery {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (request, rem) = RecoveryRequest::from_bytes(bytes)?;
+        let (effective_at, rem) = u64::from_bytes(rem)?;
+        Ok((
+            PendingRecovery {
+                request,
+                effective_at,
+            },
+            rem,
+        ))
+    }
+}
+
+/// Errors that can occur while configuring, starting or cancelling the recovery of an account.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[repr(i32)]
+pub enum RecoveryFailure {
+    /// Caller doesn't have sufficient permissions to perform the recovery action on the given
+    /// account.
+    PermissionDenied = 1,
+    /// The given account has no recovery configured.
+    NotConfigured = 2,
+    /// A recovery of the given account is already pending.
+    AlreadyPending = 3,
+    /// No recovery of the given account is pending.
+    NoPendingRecovery = 4,
+    /// The recovery keys can't meet the recovery threshold, or the threshold is zero.
+    InvalidConfig = 5,
+    /// The new associated keys can't meet the new action thresholds.
+    InvalidRequest = 6,
+    /// The given account doesn't exist.
+    MissingAccount = 7,
+}
+
+impl Display for RecoveryFailure {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            RecoveryFailure::PermissionDenied => {
+                formatter.write_str("Unable to manage recovery due to insufficient permissions")
+            }
+            RecoveryFailure::NotConfigured => {
+                formatter.write_str("Unable to recover an account without recovery keys")
+            }
+            RecoveryFailure::AlreadyPending => {
+                formatter.write_str("Unable to start a recovery while another one is pending")
+            }
+            RecoveryFailure::NoPendingRecovery => {
+                formatter.write_str("Unable to cancel a recovery which is not pending")
+            }
+            RecoveryFailure::InvalidConfig => formatter
+                .write_str("Unable to configure recovery keys which can't meet their threshold"),
+            RecoveryFailure::InvalidRequest => formatter.write_str(
+                "Unable to start a recovery whose keys can't meet its action thresholds",
+            ),
+            RecoveryFailure::MissingAccount => {
+                formatter.write_str("Unable to recover an account that does not exist")
+            }
+        }
+    }
+}
+
+// This conversion is not intended to be used by third party crates.
+#[doc(hidden)]
+impl TryFrom<i32> for RecoveryFailure {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == RecoveryFailure::PermissionDenied as i32 => {
+                Ok(RecoveryFailure::PermissionDenied)
+            }
+            d if d == RecoveryFailure::NotConfigured as i32 => Ok(RecoveryFailure::NotConfigured),
+            d if d == RecoveryFailure::AlreadyPending as i32 => Ok(RecoveryFailure::AlreadyPending),
+            d if d == RecoveryFailure::NoPendingRecovery as i32 => {
+                Ok(RecoveryFailure::NoPendingRecovery)
+            }
+            d if d == RecoveryFailure::InvalidConfig as i32 => Ok(RecoveryFailure::InvalidConfig),
+            d if d == RecoveryFailure::InvalidRequest as i32 => Ok(RecoveryFailure::InvalidRequest),
+            d if d == RecoveryFailure::MissingAccount as i32 => Ok(RecoveryFailure::MissingAccount),
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
+#[doc(hidden)]
+#[cfg(any(feature = "gens", test))]
+pub mod gens {
+    use proptest::prelude::*;
+
+    use crate::account::{
+        action_thresholds::gens::action_thresholds_arb, associated_keys::gens::associated_keys_arb,
+        Weight,
+    };
+
+    use super::{PendingRecovery, RecoveryConfig, RecoveryRequest};
+
+    pub fn recovery_config_arb() -> impl Strategy<Value = RecoveryConfig> {
+        (associated_keys_arb(), any::<u64>())
+            .prop_filter("needs recovery keys", |(recovery_keys, _)| {
+                recovery_keys.total_keys_weight().value() != 0
+            })
+            .prop_map(|(recovery_keys, delay)| {
+                let threshold = Weight::new(1);
+                RecoveryConfig::new(recovery_keys, threshold, delay).unwrap()
+            })
+    }
+
+    pub fn pending_recovery_arb() -> impl Strategy<Value = PendingRecovery> {
+        (associated_keys_arb(), action_thresholds_arb(), any::<u64>()).prop_filter_map(
+            "new keys must meet thresholds",
+            |(associated_keys, action_thresholds, effective_at)| {
+                RecoveryRequest::new(associated_keys, action_thresholds)
+                    .ok()
+                    .map(|request| PendingRecovery::new(request, effective_at))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn try_from_i32_for_recovery_failure() {
+        let max_valid_value_for_variant = RecoveryFailure::MissingAccount as i32;
+        assert_eq!(
+            Err(TryFromIntError(())),
+            RecoveryFailure::try_from(max_valid_value_for_variant + 1),
+            "Did you forget to update `RecoveryFailure::try_from` for a new variant of \
+                   `RecoveryFailure`, or `max_valid_value_for_variant` in this test?"
+        );
+    }
+
+    #[test]
+    fn should_not_create_unreachable_recovery_config() {
+        let recovery_keys = AssociatedKeys::new(AccountHash::new([1u8; 32]), Weight::new(1));
+        assert_eq!(
+            RecoveryConfig::new(recovery_keys.clone(), Weight::new(2), 0),
+            Err(RecoveryFailure::InvalidConfig)
+        );
+        assert_eq!(
+            RecoveryConfig::new(recovery_keys, Weight::new(0), 0),
+            Err(RecoveryFailure::InvalidConfig)
+        );
+    }
+
+    #[test]
+    fn should_not_create_unreachable_recovery_request() {
+        let associated_keys = AssociatedKeys::new(AccountHash::new([1u8; 32]), Weight::new(1));
+        let action_thresholds = ActionThresholds::new(Weight::new(1), Weight::new(2)).unwrap();
+        assert_eq!(
+            RecoveryRequest::new(associated_keys, action_thresholds),
+            Err(RecoveryFailure::InvalidRequest)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_value_recovery_config(recovery_config in gens::recovery_config_arb()) {
+            bytesrepr::test_serialization_roundtrip(&recovery_config);
+        }
+
+        #[test]
+        fn test_value_pending_recovery(pending_recovery in gens::pending_recovery_arb()) {
+            bytesrepr::test_serialization_roundtrip(&pending_recovery);
+        }
+    }
+}
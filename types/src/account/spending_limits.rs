@@ -0,0 +1,440 @@
+//! This module contains types and functions for working with spending limits of keys associated
+//! with an account.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{AccountHash, TryFromIntError},
+    bytesrepr::{self, Error, FromBytes, ToBytes, U64_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
+    BlockTime, EraId, U512,
+};
+
+const ERA_TAG: u8 = 0;
+const DURATION_TAG: u8 = 1;
+
+/// The period over which spending is accumulated against a [`SpendingLimit`].
+#[derive(PartialOrd, Ord, PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum SpendingWindow {
+    /// Spending is accumulated within a single era and starts over once the next era begins.
+    Era,
+    /// Spending is accumulated for the given number of milliseconds, starting from the first
+    /// transfer made after the previous window elapsed.
+    Duration(u64),
+}
+
+impl ToBytes for SpendingWindow {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            SpendingWindow::Era => result.push(ERA_TAG),
+            SpendingWindow::Duration(millis) => {
+                result.push(DURATION_TAG);
+                result.append(&mut millis.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                SpendingWindow::Era => 0,
+                SpendingWindow::Duration(_) => U64_SERIALIZED_LENGTH,
+            }
+    }
+}
+
+impl FromBytes for SpendingWindow {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        match tag {
+            ERA_TAG => Ok((SpendingWindow::Era, rem)),
+            DURATION_TAG => {
+                let (millis, rem) = u64::from_bytes(rem)?;
+                Ok((SpendingWindow::Duration(millis), rem))
+            }
+            _ => Err(Error::Formatting),
+        }
+    }
+}
+
+/// The maximum amount of motes which may be transferred out of an account's purses within a
+/// [`SpendingWindow`] by deploys which rely on a given associated key for authorization.
+#[derive(PartialOrd, Ord, PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct SpendingLimit {
+    limit: U512,
+    window: SpendingWindow,
+    window_start: u64,
+    spent: U512,
+}
+
+impl SpendingLimit {
+    /// Constructs a new `SpendingLimit` with nothing spent yet.
+    pub fn new(limit: U512, window: SpendingWindow) -> Self {
+        SpendingLimit {
+            limit,
+            window,
+            window_start: 0,
+            spent: U512::zero(),
+        }
+    }
+
+    /// Returns the maximum amount of motes which may be spent within a single window.
+    pub fn limit(&self) -> U512 {
+        self.limit
+    }
+
+    /// Returns the window over which spending is accumulated.
+    pub fn window(&self) -> SpendingWindow {
+        self.window
+    }
+
+    /// Returns the amount of motes spent within the most recent window.
+    pub fn spent(&self) -> U512 {
+        self.spent
+    }
+
+    /// Returns the amount of motes which may still be spent at the given era and block time.
+    pub fn remaining(&self, era_id: EraId, blocktime: BlockTime) -> U512 {
+        let spent = if self.has_elapsed(era_id, blocktime) {
+            U512::zero()
+        } else {
+            self.spent
+        };
+        self.limit.saturating_sub(spent)
+    }
+
+    /// Records spending of `amount` at the given era and block time, starting a new window first
+    /// if the current one has elapsed.
+    ///
+    /// Leaves `self` unchanged and returns an error if the amount would exceed the limit.
+    pub fn record_spending(
+        &mut self,
+        amount: U512,
+        era_id: EraId,
+        blocktime: BlockTime,
+    ) -> Result<(), SpendingLimitFailure> {
+        let has_elapsed = self.has_elapsed(era_id, blocktime);
+        let spent = if has_elapsed {
+            U512::zero()
+        } else {
+            self.spent
+        };
+        let spent = spent
+            .checked_add(amount)
+            .filter(|spent| *spent <= self.limit)
+            .ok_or(SpendingLimitFailure::LimitExceeded)?;
+        if has_elapsed {
+            self.window_start = self.window_position(era_id, blocktime);
+        }
+        self.spent = spent;
+        Ok(())
+    }
+
+    fn window_position(&self, era_id: EraId, blocktime: BlockTime) -> u64 {
+        match self.window {
+            SpendingWindow::Era => era_id.value(),
+            SpendingWindow::Duration(_) => blocktime.into(),
+        }
+    }
+
+    fn has_elapsed(&self, era_id: EraId, blocktime: BlockTime) -> bool {
+        let position = self.window_position(era_id, blocktime);
+        match self.window {
+            SpendingWindow::Era => position != self.window_start,
+            SpendingWindow::Duration(millis) => {
+                position.saturating_sub(self.window_start) >= millis
+            }
+        }
+    }
+}
+
+impl ToBytes for SpendingLimit {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.limit.to_bytes()?);
+        result.append(&mut self.window.to_bytes()?);
+        result.append(&mut self.window_start.to_bytes()?);
+        result.append(&mut self.spent.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.limit.serialized_length()
+            + self.window.serialized_length()
+            + self.window_start.serialized_length()
+            + self.spent.serialized_length()
+    }
+}
+
+impl FromBytes for SpendingLimit {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (limit, rem) = U512::from_bytes(bytes)?;
+        let (window, rem) = SpendingWindow::from_bytes(rem)?;
+        let (window_start, rem) = u64::from_bytes(rem)?;
+        let (spent, rem) = U512::from_bytes(rem)?;
+        Ok((
+            SpendingLimit {
+                limit,
+                window,
+                window_start,
+                spent,
+            },
+            rem,
+        ))
+    }
+}
+
+/// A mapping of associated keys to the [`SpendingLimit`]s which apply to them.
+///
+/// Associated keys without an entry are not limited.
+#[derive(Default, PartialOrd, Ord, PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct SpendingLimits(BTreeMap<AccountHash, SpendingLimit>);
+
+impl SpendingLimits {
+    /// Sets the spending limit of a key, replacing any previous limit.
+    pub fn insert(&mut self, key: AccountHash, spending_limit: SpendingLimit) {
+        self.0.insert(key, spending_limit);
+    }
+
+    /// Removes the spending limit of a key.
+    pub fn remove(&mut self, key: &AccountHash) -> Option<SpendingLimit> {
+        self.0.remove(key)
+    }
+
+    /// Returns the spending limit of a key.
+    pub fn get(&self, key: &AccountHash) -> Option<&SpendingLimit> {
+        self.0.get(key)
+    }
+
+    /// Returns a mutable reference to the spending limit of a key.
+    pub fn get_mut(&mut self, key: &AccountHash) -> Option<&mut SpendingLimit> {
+        self.0.get_mut(key)
+    }
+
+    /// Returns `true` if the given key has a spending limit.
+    pub fn contains_key(&self, key: &AccountHash) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// Returns an iterator over the limited keys and their spending limits.
+    pub fn iter(&self) -> impl Iterator<Item = (&AccountHash, &SpendingLimit)> {
+        self.0.iter()
+    }
+
+    /// Returns the count of limited keys.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no keys are limited.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<BTreeMap<AccountHash, SpendingLimit>> for SpendingLimits {
+    fn from(spending_limits: BTreeMap<AccountHash, SpendingLimit>) -> Self {
+        Self(spending_limits)
+    }
+}
+
+impl ToBytes for SpendingLimits {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+impl FromBytes for SpendingLimits {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (spending_limits, rem) = FromBytes::from_bytes(bytes)?;
+        Ok((SpendingLimits(spending_limits), rem))
+    }
+}
+
+/// Errors that can occur while managing the spending limits of an account's associated keys, or
+/// while spending against them.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[repr(i32)]
+pub enum SpendingLimitFailure {
+    /// The given [`AccountHash`] is not associated with the given account, or has no spending
+    /// limit to remove.
+    MissingKey = 1,
+    /// Caller doesn't have sufficient permissions to manage the spending limits of the given
+    /// account.
+    PermissionDenied = 2,
+    /// Limiting the given [`AccountHash`] would leave the unlimited associated keys unable to meet
+    /// the key management threshold of the given account.
+    ThresholdViolation = 3,
+    /// The amount would exceed the spending limit of one of the keys authorizing the deploy.
+    LimitExceeded = 4,
+}
+
+impl Display for SpendingLimitFailure {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            SpendingLimitFailure::MissingKey => {
+                formatter.write_str("Unable to manage the spending limit of a missing key")
+            }
+            SpendingLimitFailure::PermissionDenied => formatter
+                .write_str("Unable to manage spending limits due to insufficient permissions"),
+            SpendingLimitFailure::ThresholdViolation => formatter.write_str(
+                "Unable to limit a key which would violate action threshold constraints",
+            ),
+            SpendingLimitFailure::LimitExceeded => {
+                formatter.write_str("Spending limit of an authorizing key exceeded")
+            }
+        }
+    }
+}
+
+// This conversion is not intended to be used by third party crates.
+#[doc(hidden)]
+impl TryFrom<i32> for SpendingLimitFailure {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            d if d == SpendingLimitFailure::MissingKey as i32 => {
+                Ok(SpendingLimitFailure::MissingKey)
+            }
+            d if d == SpendingLimitFailure::PermissionDenied as i32 => {
+                Ok(SpendingLimitFailure::PermissionDenied)
+            }
+            d if d == SpendingLimitFailure::ThresholdViolation as i32 => {
+                Ok(SpendingLimitFailure::ThresholdViolation)
+            }
+            d if d == SpendingLimitFailure::LimitExceeded as i32 => {
+                Ok(SpendingLimitFailure::LimitExceeded)
+            }
+            _ => Err(TryFromIntError(())),
+        }
+    }
+}
+
+#[doc(hidden)]
+#[cfg(any(feature = "gens", test))]
+pub mod gens {
+    use proptest::prelude::*;
+
+    use crate::gens::{account_hash_arb, u512_arb};
+
+    use super::{SpendingLimit, SpendingLimits, SpendingWindow};
+
+    pub fn spending_window_arb() -> impl Strategy<Value = SpendingWindow> {
+        prop_oneof![
+            Just(SpendingWindow::Era),
+            any::<u64>().prop_map(SpendingWindow::Duration),
+        ]
+    }
+
+    pub fn spending_limit_arb() -> impl Strategy<Value = SpendingLimit> {
+        (u512_arb(), spending_window_arb(), any::<u64>(), u512_arb()).prop_map(
+            |(limit, window, window_start, spent)| SpendingLimit {
+                limit,
+                window,
+                window_start,
+                spent,
+            },
+        )
+    }
+
+    pub fn spending_limits_arb() -> impl Strategy<Value = SpendingLimits> {
+        proptest::collection::btree_map(account_hash_arb(), spending_limit_arb(), 0..10)
+            .prop_map(SpendingLimits::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const LIMIT: u64 = 100;
+
+    #[test]
+    fn try_from_i32_for_spending_limit_failure() {
+        let max_valid_value_for_variant = SpendingLimitFailure::LimitExceeded as i32;
+        assert_eq!(
+            Err(TryFromIntError(())),
+            SpendingLimitFailure::try_from(max_valid_value_for_variant + 1),
+            "Did you forget to update `SpendingLimitFailure::try_from` for a new variant of \
+                   `SpendingLimitFailure`, or `max_valid_value_for_variant` in this test?"
+        );
+    }
+
+    #[test]
+    fn should_accumulate_spending_within_era() {
+        let mut spending_limit = SpendingLimit::new(U512::from(LIMIT), SpendingWindow::Era);
+        let era_id = EraId::new(1);
+        let blocktime = BlockTime::new(0);
+
+        spending_limit
+            .record_spending(U512::from(60), era_id, blocktime)
+            .unwrap();
+        assert_eq!(spending_limit.remaining(era_id, blocktime), U512::from(40));
+        assert_eq!(
+            spending_limit.record_spending(U512::from(41), era_id, blocktime),
+            Err(SpendingLimitFailure::LimitExceeded)
+        );
+        assert_eq!(spending_limit.spent(), U512::from(60));
+        spending_limit
+            .record_spending(U512::from(40), era_id, blocktime)
+            .unwrap();
+        assert_eq!(spending_limit.remaining(era_id, blocktime), U512::zero());
+    }
+
+    #[test]
+    fn should_start_new_window_in_next_era() {
+        let mut spending_limit = SpendingLimit::new(U512::from(LIMIT), SpendingWindow::Era);
+        let blocktime = BlockTime::new(0);
+
+        spending_limit
+            .record_spending(U512::from(LIMIT), EraId::new(1), blocktime)
+            .unwrap();
+        spending_limit
+            .record_spending(U512::from(LIMIT), EraId::new(2), blocktime)
+            .unwrap();
+        assert_eq!(spending_limit.spent(), U512::from(LIMIT));
+    }
+
+    #[test]
+    fn should_start_new_window_once_duration_elapsed() {
+        let mut spending_limit =
+            SpendingLimit::new(U512::from(LIMIT), SpendingWindow::Duration(1_000));
+        let era_id = EraId::new(1);
+
+        spending_limit
+            .record_spending(U512::from(LIMIT), era_id, BlockTime::new(5_000))
+            .unwrap();
+        assert_eq!(
+            spending_limit.record_spending(U512::one(), era_id, BlockTime::new(5_999)),
+            Err(SpendingLimitFailure::LimitExceeded)
+        );
+        spending_limit
+            .record_spending(U512::one(), era_id, BlockTime::new(6_000))
+            .unwrap();
+        assert_eq!(
+            spending_limit.remaining(era_id, BlockTime::new(6_999)),
+            U512::from(LIMIT - 1)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_value_spending_limits(spending_limits in gens::spending_limits_arb()) {
+            bytesrepr::test_serialization_roundtrip(&spending_limits);
+        }
+    }
+}
@@ -8,8 +8,8 @@ use core::{
 
 use crate::{
     account::{
-        AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, TryFromIntError,
-        TryFromSliceForAccountHashError, UpdateKeyFailure,
+        AddKeyFailure, RecoveryFailure, RemoveKeyFailure, SetThresholdFailure,
+        SpendingLimitFailure, TryFromIntError, TryFromSliceForAccountHashError, UpdateKeyFailure,
     },
     bytesrepr, contracts,
     system::{auction, handle_payment, mint},
@@ -437,6 +437,32 @@ impl From<SetThresholdFailure> for ApiError {
     }
 }
 
+impl From<SpendingLimitFailure> for ApiError {
+    fn from(error: SpendingLimitFailure) -> Self {
+        match error {
+            SpendingLimitFailure::MissingKey => ApiError::MissingKey,
+            SpendingLimitFailure::PermissionDenied => ApiError::PermissionDenied,
+            SpendingLimitFailure::ThresholdViolation => ApiError::ThresholdViolation,
+            SpendingLimitFailure::LimitExceeded => mint::Error::SpendingLimitExceeded.into(),
+        }
+    }
+}
+
+impl From<RecoveryFailure> for ApiError {
+    fn from(error: RecoveryFailure) -> Self {
+        match error {
+            RecoveryFailure::PermissionDenied => ApiError::PermissionDenied,
+            RecoveryFailure::NotConfigured
+            | RecoveryFailure::NoPendingRecovery
+            | RecoveryFailure::MissingAccount => ApiError::MissingKey,
+            RecoveryFailure::AlreadyPending => ApiError::DuplicateKey,
+            RecoveryFailure::InvalidConfig | RecoveryFailure::InvalidRequest => {
+                ApiError::ThresholdViolation
+            }
+        }
+    }
+}
+
 impl From<CLValueError> for ApiError {
     fn from(error: CLValueError) -> Self {
         match error {
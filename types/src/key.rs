@@ -43,6 +43,7 @@ const BID_PREFIX: &str = "bid-";
 const WITHDRAW_PREFIX: &str = "withdraw-";
 const DICTIONARY_PREFIX: &str = "dictionary-";
 const SYSTEM_CONTRACT_REGISTRY_PREFIX: &str = "system-contract-registry-";
+const REDELEGATION_PREFIX: &str = "redelegation-";
 
 /// The number of bytes in a Blake2b hash
 pub const BLAKE2B_DIGEST_LENGTH: usize = 32;
@@ -71,6 +72,7 @@ const KEY_WITHDRAW_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_HAS
 const KEY_DICTIONARY_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_DICTIONARY_LENGTH;
 const KEY_SYSTEM_CONTRACT_REGISTRY_SERIALIZED_LENGTH: usize =
     KEY_ID_SERIALIZED_LENGTH + SYSTEM_CONTRACT_REGISTRY_KEY.len();
+const KEY_REDELEGATION_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_HASH_LENGTH;
 
 /// An alias for [`Key`]s hash variant.
 pub type HashAddr = [u8; KEY_HASH_LENGTH];
@@ -93,6 +95,7 @@ pub enum KeyTag {
     Withdraw = 8,
     Dictionary = 9,
     SystemContractRegistry = 10,
+    Redelegation = 11,
 }
 
 /// The type under which data (e.g. [`CLValue`](crate::CLValue)s, smart contracts, user accounts)
@@ -124,6 +127,8 @@ pub enum Key {
     Dictionary(DictionaryAddr),
     /// A `Key` variant under which system contract hashes are stored.
     SystemContractRegistry,
+    /// A `Key` under which we store a delegator's pending redelegations.
+    Redelegation(AccountHash),
 }
 
 /// Errors produced when converting a `String` into a `Key`.
@@ -151,6 +156,8 @@ pub enum FromStrError {
     Dictionary(String),
     /// System contract registry parse error.
     SystemContractRegistry(String),
+    /// Redelegation parse error.
+    Redelegation(String),
     /// Unknown prefix.
     UnknownPrefix,
 }
@@ -197,6 +204,9 @@ impl Display for FromStrError {
                     error
                 )
             }
+            FromStrError::Redelegation(error) => {
+                write!(f, "redelegation-key from string error: {}", error)
+            }
             FromStrError::UnknownPrefix => write!(f, "unknown prefix for key"),
         }
     }
@@ -218,6 +228,7 @@ impl Key {
             Key::Withdraw(_) => String::from("Key::Unbond"),
             Key::Dictionary(_) => String::from("Key::Dictionary"),
             Key::SystemContractRegistry => String::from("Key::SystemContractRegistry"),
+            Key::Redelegation(_) => String::from("Key::Redelegation"),
         }
     }
 
@@ -276,6 +287,13 @@ impl Key {
                     base16::encode_lower(&SYSTEM_CONTRACT_REGISTRY_KEY)
                 )
             }
+            Key::Redelegation(account_hash) => {
+                format!(
+                    "{}{}",
+                    REDELEGATION_PREFIX,
+                    base16::encode_lower(&account_hash)
+                )
+            }
         }
     }
 
@@ -363,6 +381,14 @@ impl Key {
             return Ok(Key::SystemContractRegistry);
         }
 
+        if let Some(hex) = input.strip_prefix(REDELEGATION_PREFIX) {
+            let hash = base16::decode(hex)
+                .map_err(|error| FromStrError::Redelegation(error.to_string()))?;
+            let account_hash = <[u8; ACCOUNT_HASH_LENGTH]>::try_from(hash.as_ref())
+                .map_err(|error| FromStrError::Redelegation(error.to_string()))?;
+            return Ok(Key::Redelegation(AccountHash::new(account_hash)));
+        }
+
         Err(FromStrError::UnknownPrefix)
     }
 
@@ -449,6 +475,7 @@ impl Display for Key {
                 "Key::SystemContractRegistry({})",
                 HexFmt(SYSTEM_CONTRACT_REGISTRY_KEY)
             ),
+            Key::Redelegation(account_hash) => write!(f, "Key::Redelegation({})", account_hash),
         }
     }
 }
@@ -473,6 +500,7 @@ impl Tagged<KeyTag> for Key {
             Key::Withdraw(_) => KeyTag::Withdraw,
             Key::Dictionary(_) => KeyTag::Dictionary,
             Key::SystemContractRegistry => KeyTag::SystemContractRegistry,
+            Key::Redelegation(_) => KeyTag::Redelegation,
         }
     }
 }
@@ -558,6 +586,9 @@ impl ToBytes for Key {
             Key::SystemContractRegistry => {
                 result.append(&mut SYSTEM_CONTRACT_REGISTRY_KEY.to_bytes()?)
             }
+            Key::Redelegation(account_hash) => {
+                result.append(&mut account_hash.to_bytes()?);
+            }
         }
         Ok(result)
     }
@@ -577,6 +608,7 @@ impl ToBytes for Key {
             Key::Withdraw(_) => KEY_WITHDRAW_SERIALIZED_LENGTH,
             Key::Dictionary(_) => KEY_DICTIONARY_SERIALIZED_LENGTH,
             Key::SystemContractRegistry => KEY_SYSTEM_CONTRACT_REGISTRY_SERIALIZED_LENGTH,
+            Key::Redelegation(_) => KEY_REDELEGATION_SERIALIZED_LENGTH,
         }
     }
 }
@@ -629,6 +661,10 @@ impl FromBytes for Key {
                 let (_, rem): ([u8; 32], &[u8]) = FromBytes::from_bytes(remainder)?;
                 Ok((Key::SystemContractRegistry, rem))
             }
+            tag if tag == KeyTag::Redelegation as u8 => {
+                let (account_hash, rem) = AccountHash::from_bytes(remainder)?;
+                Ok((Key::Redelegation(account_hash), rem))
+            }
             _ => Err(Error::Formatting),
         }
     }
@@ -636,7 +672,7 @@ impl FromBytes for Key {
 
 impl Distribution<Key> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Key {
-        match rng.gen_range(0..=10) {
+        match rng.gen_range(0..=11) {
             0 => Key::Account(rng.gen()),
             1 => Key::Hash(rng.gen()),
             2 => Key::URef(rng.gen()),
@@ -648,6 +684,7 @@ impl Distribution<Key> for Standard {
             8 => Key::Withdraw(rng.gen()),
             9 => Key::Dictionary(rng.gen()),
             10 => Key::SystemContractRegistry,
+            11 => Key::Redelegation(rng.gen()),
             _ => unreachable!(),
         }
     }
@@ -669,6 +706,7 @@ mod serde_helpers {
         Withdraw(String),
         Dictionary(String),
         SystemContractRegistry(String),
+        Redelegation(String),
     }
 
     impl From<&Key> for HumanReadable {
@@ -688,6 +726,7 @@ mod serde_helpers {
                 Key::SystemContractRegistry => {
                     HumanReadable::SystemContractRegistry(formatted_string)
                 }
+                Key::Redelegation(_) => HumanReadable::Redelegation(formatted_string),
             }
         }
     }
@@ -705,7 +744,8 @@ mod serde_helpers {
                 | HumanReadable::EraInfo(formatted_string)
                 | HumanReadable::Balance(formatted_string)
                 | HumanReadable::Bid(formatted_string)
-                | HumanReadable::Withdraw(formatted_string) => {
+                | HumanReadable::Withdraw(formatted_string)
+                | HumanReadable::Redelegation(formatted_string) => {
                     Key::from_formatted_str(&formatted_string)
                 }
                 HumanReadable::Dictionary(formatted_string) => {
@@ -731,6 +771,7 @@ mod serde_helpers {
         Withdraw(&'a AccountHash),
         Dictionary(&'a HashAddr),
         SystemContractRegistry,
+        Redelegation(&'a AccountHash),
     }
 
     impl<'a> From<&'a Key> for BinarySerHelper<'a> {
@@ -747,6 +788,7 @@ mod serde_helpers {
                 Key::Withdraw(account_hash) => BinarySerHelper::Withdraw(account_hash),
                 Key::Dictionary(addr) => BinarySerHelper::Dictionary(addr),
                 Key::SystemContractRegistry => BinarySerHelper::SystemContractRegistry,
+                Key::Redelegation(account_hash) => BinarySerHelper::Redelegation(account_hash),
             }
         }
     }
@@ -764,6 +806,7 @@ mod serde_helpers {
         Withdraw(AccountHash),
         Dictionary(DictionaryAddr),
         SystemContractRegistry,
+        Redelegation(AccountHash),
     }
 
     impl From<BinaryDeserHelper> for Key {
@@ -780,6 +823,7 @@ mod serde_helpers {
                 BinaryDeserHelper::Withdraw(account_hash) => Key::Withdraw(account_hash),
                 BinaryDeserHelper::Dictionary(addr) => Key::Dictionary(addr),
                 BinaryDeserHelper::SystemContractRegistry => Key::SystemContractRegistry,
+                BinaryDeserHelper::Redelegation(account_hash) => Key::Redelegation(account_hash),
             }
         }
     }
@@ -831,7 +875,8 @@ mod tests {
     const WITHDRAW_KEY: Key = Key::Withdraw(AccountHash::new([42; 32]));
     const DICTIONARY_KEY: Key = Key::Dictionary([42; 32]);
     const REGISTRY_KEY: Key = Key::SystemContractRegistry;
-    const KEYS: [Key; 11] = [
+    const REDELEGATION_KEY: Key = Key::Redelegation(AccountHash::new([42; 32]));
+    const KEYS: [Key; 12] = [
         ACCOUNT_KEY,
         HASH_KEY,
         UREF_KEY,
@@ -843,6 +888,7 @@ mod tests {
         WITHDRAW_KEY,
         DICTIONARY_KEY,
         REGISTRY_KEY,
+        REDELEGATION_KEY,
     ];
     const HEX_STRING: &str = "2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a";
 
@@ -936,7 +982,11 @@ mod tests {
                 "Key::SystemContractRegistry({})",
                 HexFmt(SYSTEM_CONTRACT_REGISTRY_KEY)
             )
-        )
+        );
+        assert_eq!(
+            format!("{}", REDELEGATION_KEY),
+            format!("Key::Redelegation({})", HEX_STRING)
+        );
     }
 
     #[test]
@@ -1053,6 +1103,10 @@ mod tests {
             .unwrap_err()
             .to_string()
             .starts_with("system-contract-registry-key from string error: "));
+        assert!(Key::from_formatted_str(REDELEGATION_PREFIX)
+            .unwrap_err()
+            .to_string()
+            .starts_with("redelegation-key from string error: "));
 
         let invalid_prefix = "a-0000000000000000000000000000000000000000000000000000000000000000";
         assert_eq!(
@@ -1095,6 +1149,7 @@ mod tests {
                 r#"{{"SystemContractRegistry":"system-contract-registry-{}"}}"#,
                 HexFmt(SYSTEM_CONTRACT_REGISTRY_KEY)
             ),
+            format!(r#"{{"Redelegation":"redelegation-{}"}}"#, HEX_STRING),
         ];
 
         assert_eq!(
@@ -1142,5 +1197,6 @@ mod tests {
         round_trip(&Key::Withdraw(AccountHash::new(zeros)));
         round_trip(&Key::Dictionary(zeros));
         round_trip(&Key::SystemContractRegistry);
+        round_trip(&Key::Redelegation(AccountHash::new(zeros)));
     }
 }
@@ -32,6 +32,8 @@ enum Tag {
     EraInfo = 7,
     Bid = 8,
     Withdraw = 9,
+    // Accounts using spending limits or recovery, which don't fit the original account layout.
+    ExtendedAccount = 10,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -294,7 +296,10 @@ impl ToBytes for StoredValue {
         let mut result = bytesrepr::allocate_buffer(self)?;
         let (tag, mut serialized_data) = match self {
             StoredValue::CLValue(cl_value) => (Tag::CLValue, cl_value.to_bytes()?),
-            StoredValue::Account(account) => (Tag::Account, account.to_bytes()?),
+            StoredValue::Account(account) if account.has_legacy_layout() => {
+                (Tag::Account, account.legacy_to_bytes()?)
+            }
+            StoredValue::Account(account) => (Tag::ExtendedAccount, account.to_bytes()?),
             StoredValue::ContractWasm(contract_wasm) => {
                 (Tag::ContractWasm, contract_wasm.to_bytes()?)
             }
@@ -319,6 +324,9 @@ impl ToBytes for StoredValue {
         U8_SERIALIZED_LENGTH
             + match self {
                 StoredValue::CLValue(cl_value) => cl_value.serialized_length(),
+                StoredValue::Account(account) if account.has_legacy_layout() => {
+                    account.legacy_serialized_length()
+                }
                 StoredValue::Account(account) => account.serialized_length(),
                 StoredValue::ContractWasm(contract_wasm) => contract_wasm.serialized_length(),
                 StoredValue::Contract(contract_header) => contract_header.serialized_length(),
@@ -340,7 +348,9 @@ impl FromBytes for StoredValue {
         match tag {
             tag if tag == Tag::CLValue as u8 => CLValue::from_bytes(remainder)
                 .map(|(cl_value, remainder)| (StoredValue::CLValue(cl_value), remainder)),
-            tag if tag == Tag::Account as u8 => Account::from_bytes(remainder)
+            tag if tag == Tag::Account as u8 => Account::legacy_from_bytes(remainder)
+                .map(|(account, remainder)| (StoredValue::Account(account), remainder)),
+            tag if tag == Tag::ExtendedAccount as u8 => Account::from_bytes(remainder)
                 .map(|(account, remainder)| (StoredValue::Account(account), remainder)),
             tag if tag == Tag::ContractWasm as u8 => {
                 ContractWasm::from_bytes(remainder).map(|(contract_wasm, remainder)| {
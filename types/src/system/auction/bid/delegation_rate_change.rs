@@ -0,0 +1,72 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    system::auction::DelegationRate,
+    EraId,
+};
+
+/// A delegation rate increase requested by a validator which has yet to take effect.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct DelegationRateChange {
+    /// The requested delegation rate.
+    delegation_rate: DelegationRate,
+    /// The era from which the requested delegation rate applies.
+    activation_era: EraId,
+}
+
+impl DelegationRateChange {
+    /// Creates a new pending delegation rate change.
+    pub fn new(delegation_rate: DelegationRate, activation_era: EraId) -> Self {
+        DelegationRateChange {
+            delegation_rate,
+            activation_era,
+        }
+    }
+
+    /// Returns the requested delegation rate.
+    pub fn delegation_rate(&self) -> DelegationRate {
+        self.delegation_rate
+    }
+
+    /// Returns the era from which the requested delegation rate applies.
+    pub fn activation_era(&self) -> EraId {
+        self.activation_era
+    }
+}
+
+impl ToBytes for DelegationRateChange {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.extend(self.delegation_rate.to_bytes()?);
+        result.extend(self.activation_era.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.delegation_rate.serialized_length() + self.activation_era.serialized_length()
+    }
+}
+
+impl FromBytes for DelegationRateChange {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (delegation_rate, bytes) = FromBytes::from_bytes(bytes)?;
+        let (activation_era, bytes) = FromBytes::from_bytes(bytes)?;
+        Ok((
+            DelegationRateChange {
+                delegation_rate,
+                activation_era,
+            },
+            bytes,
+        ))
+    }
+}
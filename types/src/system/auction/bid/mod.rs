@@ -1,6 +1,7 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
+mod delegation_rate_change;
 mod vesting;
 
 use alloc::{collections::BTreeMap, vec::Vec};
@@ -10,13 +11,22 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    bytesrepr::{self, FromBytes, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     system::auction::{DelegationRate, Delegator, Error},
-    CLType, CLTyped, PublicKey, URef, U512,
+    CLType, CLTyped, EraId, PublicKey, URef, U512,
 };
 
+pub use delegation_rate_change::DelegationRateChange;
 pub use vesting::VestingSchedule;
 
+/// Flag set in the serialized `inactive` field of a bid if the validator is inactive.
+const INACTIVE_FLAG: u8 = 1;
+/// Flag set in the serialized `inactive` field of a bid if a pending delegation rate change follows.
+///
+/// Bids serialized before delegation rate changes were rate-limited hold a plain `bool` there, so
+/// they never have this flag set and remain readable wherever they are embedded.
+const PENDING_DELEGATION_RATE_CHANGE_FLAG: u8 = 2;
+
 /// An entry in the validator map.
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
@@ -36,6 +46,12 @@ pub struct Bid {
     delegators: BTreeMap<PublicKey, Delegator>,
     /// `true` if validator has been "evicted"
     inactive: bool,
+    /// Delegation rate increase which has yet to take effect.
+    ///
+    /// Skipped by serde so that execution results stored by nodes remain readable; it is exposed
+    /// to clients through the auction info instead.
+    #[serde(skip)]
+    pending_delegation_rate_change: Option<DelegationRateChange>,
 }
 
 impl Bid {
@@ -58,6 +74,7 @@ impl Bid {
             vesting_schedule,
             delegators,
             inactive,
+            pending_delegation_rate_change: None,
         }
     }
 
@@ -79,6 +96,7 @@ impl Bid {
             vesting_schedule,
             delegators,
             inactive,
+            pending_delegation_rate_change: None,
         }
     }
 
@@ -97,6 +115,7 @@ impl Bid {
             vesting_schedule,
             delegators,
             inactive,
+            pending_delegation_rate_change: None,
         }
     }
 
@@ -199,12 +218,43 @@ impl Bid {
         Ok(updated_staked_amount)
     }
 
-    /// Updates the delegation rate of the provided bid
+    /// Updates the delegation rate of the provided bid, cancelling any pending delegation rate
+    /// change.
     pub fn with_delegation_rate(&mut self, delegation_rate: DelegationRate) -> &mut Self {
         self.delegation_rate = delegation_rate;
+        self.pending_delegation_rate_change = None;
         self
     }
 
+    /// Returns the delegation rate change of the provided bid which has yet to take effect, if
+    /// any.
+    pub fn pending_delegation_rate_change(&self) -> Option<&DelegationRateChange> {
+        self.pending_delegation_rate_change.as_ref()
+    }
+
+    /// Schedules a delegation rate change of the provided bid, replacing any pending one.
+    pub fn with_pending_delegation_rate_change(
+        &mut self,
+        delegation_rate_change: DelegationRateChange,
+    ) -> &mut Self {
+        self.pending_delegation_rate_change = Some(delegation_rate_change);
+        self
+    }
+
+    /// Applies the pending delegation rate change of the provided bid if it takes effect in or
+    /// before the given era.
+    ///
+    /// Returns `true` if the delegation rate was changed.
+    pub fn apply_delegation_rate_change(&mut self, era_id: EraId) -> bool {
+        match self.pending_delegation_rate_change {
+            Some(delegation_rate_change) if delegation_rate_change.activation_era() <= era_id => {
+                self.with_delegation_rate(delegation_rate_change.delegation_rate());
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Initializes the vesting schedule of provided bid if the provided timestamp is greater than
     /// or equal to the bid's initial release timestamp and the bid is owned by a genesis
     /// validator.
@@ -263,6 +313,18 @@ impl Bid {
             .and_then(|delegators_sum| delegators_sum.checked_add(*self.staked_amount()))
             .ok_or(Error::InvalidAmount)
     }
+
+    /// Returns the flags stored in place of the `inactive` field when serializing the bid.
+    fn flags(&self) -> u8 {
+        let mut flags = 0;
+        if self.inactive {
+            flags |= INACTIVE_FLAG;
+        }
+        if self.pending_delegation_rate_change.is_some() {
+            flags |= PENDING_DELEGATION_RATE_CHANGE_FLAG;
+        }
+        flags
+    }
 }
 
 impl CLTyped for Bid {
@@ -280,7 +342,10 @@ impl ToBytes for Bid {
         result.extend(self.delegation_rate.to_bytes()?);
         result.extend(self.vesting_schedule.to_bytes()?);
         result.extend(self.delegators.to_bytes()?);
-        result.extend(self.inactive.to_bytes()?);
+        result.push(self.flags());
+        if let Some(delegation_rate_change) = &self.pending_delegation_rate_change {
+            result.extend(delegation_rate_change.to_bytes()?);
+        }
         Ok(result)
     }
 
@@ -291,7 +356,11 @@ impl ToBytes for Bid {
             + self.delegation_rate.serialized_length()
             + self.vesting_schedule.serialized_length()
             + self.delegators.serialized_length()
-            + self.inactive.serialized_length()
+            + U8_SERIALIZED_LENGTH
+            + self
+                .pending_delegation_rate_change
+                .as_ref()
+                .map_or(0, ToBytes::serialized_length)
     }
 }
 
@@ -303,7 +372,18 @@ impl FromBytes for Bid {
         let (delegation_rate, bytes) = FromBytes::from_bytes(bytes)?;
         let (vesting_schedule, bytes) = FromBytes::from_bytes(bytes)?;
         let (delegators, bytes) = FromBytes::from_bytes(bytes)?;
-        let (inactive, bytes) = FromBytes::from_bytes(bytes)?;
+        let (flags, bytes) = u8::from_bytes(bytes)?;
+        if flags & !(INACTIVE_FLAG | PENDING_DELEGATION_RATE_CHANGE_FLAG) != 0 {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let inactive = flags & INACTIVE_FLAG != 0;
+        let (pending_delegation_rate_change, bytes) =
+            if flags & PENDING_DELEGATION_RATE_CHANGE_FLAG != 0 {
+                let (delegation_rate_change, bytes) = DelegationRateChange::from_bytes(bytes)?;
+                (Some(delegation_rate_change), bytes)
+            } else {
+                (None, bytes)
+            };
         Ok((
             Bid {
                 validator_public_key,
@@ -313,6 +393,7 @@ impl FromBytes for Bid {
                 vesting_schedule,
                 delegators,
                 inactive,
+                pending_delegation_rate_change,
             },
             bytes,
         ))
@@ -321,12 +402,15 @@ impl FromBytes for Bid {
 
 #[cfg(test)]
 mod tests {
-    use alloc::collections::BTreeMap;
+    use alloc::{collections::BTreeMap, vec::Vec};
 
     use crate::{
-        bytesrepr,
-        system::auction::{bid::VestingSchedule, Bid, DelegationRate, Delegator},
-        AccessRights, PublicKey, SecretKey, URef, U512,
+        bytesrepr::{self, FromBytes, ToBytes},
+        system::auction::{
+            bid::{DelegationRateChange, VestingSchedule},
+            Bid, DelegationRate, Delegator,
+        },
+        AccessRights, EraId, PublicKey, SecretKey, URef, U512,
     };
 
     #[test]
@@ -341,10 +425,64 @@ mod tests {
             vesting_schedule: Some(VestingSchedule::default()),
             delegators: BTreeMap::default(),
             inactive: true,
+            pending_delegation_rate_change: Some(DelegationRateChange::new(
+                DelegationRate::max_value(),
+                EraId::MAX,
+            )),
         };
         bytesrepr::test_serialization_roundtrip(&founding_validator);
     }
 
+    #[test]
+    fn should_deserialize_bid_without_pending_delegation_rate_change() {
+        let mut bid = Bid::unlocked(
+            PublicKey::from(
+                &SecretKey::ed25519_from_bytes([0u8; SecretKey::ED25519_LENGTH]).unwrap(),
+            ),
+            URef::new([42; 32], AccessRights::READ_ADD_WRITE),
+            U512::one(),
+            DelegationRate::max_value(),
+        );
+        bid.deactivate();
+
+        // The layout of bids written before delegation rate changes were rate-limited, followed
+        // by further data as in a list of transforms.
+        let mut legacy_bytes = Vec::new();
+        legacy_bytes.extend(bid.validator_public_key.to_bytes().unwrap());
+        legacy_bytes.extend(bid.bonding_purse.to_bytes().unwrap());
+        legacy_bytes.extend(bid.staked_amount.to_bytes().unwrap());
+        legacy_bytes.extend(bid.delegation_rate.to_bytes().unwrap());
+        legacy_bytes.extend(bid.vesting_schedule.to_bytes().unwrap());
+        legacy_bytes.extend(bid.delegators.to_bytes().unwrap());
+        legacy_bytes.extend(bid.inactive.to_bytes().unwrap());
+        assert_eq!(legacy_bytes, bid.to_bytes().unwrap());
+        legacy_bytes.extend([1, 2, 3]);
+
+        let (deserialized, remainder) = Bid::from_bytes(&legacy_bytes).unwrap();
+        assert_eq!(deserialized, bid);
+        assert_eq!(remainder, [1, 2, 3]);
+    }
+
+    #[test]
+    fn should_apply_delegation_rate_change_in_activation_era() {
+        let mut bid = Bid::unlocked(
+            PublicKey::from(
+                &SecretKey::ed25519_from_bytes([0u8; SecretKey::ED25519_LENGTH]).unwrap(),
+            ),
+            URef::new([42; 32], AccessRights::READ_ADD_WRITE),
+            U512::one(),
+            10,
+        );
+        bid.with_pending_delegation_rate_change(DelegationRateChange::new(20, EraId::new(5)));
+
+        assert!(!bid.apply_delegation_rate_change(EraId::new(4)));
+        assert_eq!(*bid.delegation_rate(), 10);
+
+        assert!(bid.apply_delegation_rate_change(EraId::new(5)));
+        assert_eq!(*bid.delegation_rate(), 20);
+        assert!(bid.pending_delegation_rate_change().is_none());
+    }
+
     #[test]
     fn should_initialize_delegators_different_timestamps() {
         const WEEK_MILLIS: u64 = 7 * 24 * 60 * 60 * 1000;
@@ -26,16 +26,22 @@ pub const ARG_PUBLIC_KEY: &str = "public_key";
 pub const ARG_VALIDATOR: &str = "validator";
 /// Named constant for `delegator`.
 pub const ARG_DELEGATOR: &str = "delegator";
+/// Named constant for `new_validator`.
+pub const ARG_NEW_VALIDATOR: &str = "new_validator";
 /// Named constant for `validator_purse`.
 pub const ARG_VALIDATOR_PURSE: &str = "validator_purse";
 /// Named constant for `validator_keys`.
 pub const ARG_VALIDATOR_KEYS: &str = "validator_keys";
 /// Named constant for `validator_public_keys`.
 pub const ARG_VALIDATOR_PUBLIC_KEYS: &str = "validator_public_keys";
+/// Named constant for `slash_requests`.
+pub const ARG_SLASH_REQUESTS: &str = "slash_requests";
 /// Named constant for `era_id`.
 pub const ARG_ERA_ID: &str = "era_id";
 /// Named constant for `reward_factors`.
 pub const ARG_REWARD_FACTORS: &str = "reward_factors";
+/// Named constant for `inactivity_policy`.
+pub const ARG_INACTIVITY_POLICY: &str = "inactivity_policy";
 /// Named constant for `validator_public_key`.
 pub const ARG_VALIDATOR_PUBLIC_KEY: &str = "validator_public_key";
 /// Named constant for `delegator_public_key`.
@@ -67,6 +73,8 @@ pub const METHOD_WITHDRAW_BID: &str = "withdraw_bid";
 pub const METHOD_DELEGATE: &str = "delegate";
 /// Named constant for method `undelegate`.
 pub const METHOD_UNDELEGATE: &str = "undelegate";
+/// Named constant for method `redelegate`.
+pub const METHOD_REDELEGATE: &str = "redelegate";
 /// Named constant for method `run_auction`.
 pub const METHOD_RUN_AUCTION: &str = "run_auction";
 /// Named constant for method `slash`.
@@ -92,3 +100,13 @@ pub const AUCTION_DELAY_KEY: &str = "auction_delay";
 pub const LOCKED_FUNDS_PERIOD_KEY: &str = "locked_funds_period";
 /// Unbonding delay expressed in eras.
 pub const UNBONDING_DELAY_KEY: &str = "unbonding_delay";
+/// Minimum amount a delegator must have delegated to a single validator.
+pub const MINIMUM_DELEGATION_AMOUNT_KEY: &str = "minimum_delegation_amount";
+/// Maximum amount a delegator may have delegated to a single validator.
+pub const MAXIMUM_DELEGATION_AMOUNT_KEY: &str = "maximum_delegation_amount";
+/// Maximum number of delegators a single validator may have.
+pub const MAX_DELEGATORS_PER_VALIDATOR_KEY: &str = "max_delegators_per_validator";
+/// Number of eras after which a delegation rate increase takes effect.
+pub const DELEGATION_RATE_CHANGE_DELAY_KEY: &str = "delegation_rate_change_delay";
+/// Maximum increase of a validator's delegation rate allowed in a single change.
+pub const MAX_DELEGATION_RATE_INCREASE_KEY: &str = "max_delegation_rate_increase";
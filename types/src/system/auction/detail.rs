@@ -2,13 +2,16 @@ use alloc::{collections::BTreeMap, vec::Vec};
 use core::convert::TryInto;
 
 use num_rational::Ratio;
+use num_traits::Zero;
 
 use crate::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::auction::{
-        constants::*, Auction, Bids, EraId, Error, RuntimeProvider, SeigniorageAllocation,
-        SeigniorageRecipientsSnapshot, StorageProvider, UnbondingPurse, UnbondingPurses,
+        constants::*, Auction, Bid, Bids, DelegationRate, DelegationRateChange, Delegator, EraId,
+        Error, InactivityPolicy, Redelegation, RuntimeProvider, SeigniorageAllocation,
+        SeigniorageRecipients, SeigniorageRecipientsSnapshot, StorageProvider, UnbondingPurse,
+        UnbondingPurses,
     },
     CLTyped, Key, KeyTag, PublicKey, URef, U512,
 };
@@ -182,6 +185,259 @@ where
     read_from(provider, UNBONDING_DELAY_KEY)
 }
 
+/// Reads an optional configuration value, returning `default` if the auction contract was
+/// installed before the value was introduced.
+fn read_config_or<P, T>(provider: &mut P, name: &str, default: T) -> Result<T, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+    T: FromBytes + CLTyped,
+{
+    if provider.named_keys_get(name).is_none() {
+        return Ok(default);
+    }
+    read_from(provider, name)
+}
+
+/// Returns the minimum amount a delegator must have delegated to a single validator.
+pub fn get_minimum_delegation_amount<P>(provider: &mut P) -> Result<U512, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let minimum_delegation_amount: u64 =
+        read_config_or(provider, MINIMUM_DELEGATION_AMOUNT_KEY, 0)?;
+    Ok(U512::from(minimum_delegation_amount))
+}
+
+/// Returns the maximum amount a delegator may have delegated to a single validator.
+pub fn get_maximum_delegation_amount<P>(provider: &mut P) -> Result<U512, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let maximum_delegation_amount: u64 =
+        read_config_or(provider, MAXIMUM_DELEGATION_AMOUNT_KEY, u64::MAX)?;
+    Ok(U512::from(maximum_delegation_amount))
+}
+
+/// Returns the maximum number of delegators a single validator may have.
+pub fn get_max_delegators_per_validator<P>(provider: &mut P) -> Result<u32, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    read_config_or(provider, MAX_DELEGATORS_PER_VALIDATOR_KEY, u32::MAX)
+}
+
+/// Returns the number of eras after which a delegation rate increase takes effect.
+pub fn get_delegation_rate_change_delay<P>(provider: &mut P) -> Result<u64, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    read_config_or(provider, DELEGATION_RATE_CHANGE_DELAY_KEY, 0)
+}
+
+/// Returns the maximum increase of a validator's delegation rate allowed in a single change.
+pub fn get_max_delegation_rate_increase<P>(provider: &mut P) -> Result<DelegationRate, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    read_config_or(
+        provider,
+        MAX_DELEGATION_RATE_INCREASE_KEY,
+        DELEGATION_RATE_DENOMINATOR,
+    )
+}
+
+/// Updates the delegation rate of an existing bid.
+///
+/// Decreases take effect immediately and cancel any pending change.  Increases are limited to the
+/// configured maximum and take effect after the configured delay; requesting the already pending
+/// rate again keeps its activation era.
+pub(crate) fn update_delegation_rate<P>(
+    provider: &mut P,
+    bid: &mut Bid,
+    delegation_rate: DelegationRate,
+) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let current_delegation_rate = *bid.delegation_rate();
+    if delegation_rate <= current_delegation_rate {
+        bid.with_delegation_rate(delegation_rate);
+        return Ok(());
+    }
+
+    if delegation_rate - current_delegation_rate > get_max_delegation_rate_increase(provider)? {
+        return Err(Error::DelegationRateIncreaseTooLarge);
+    }
+
+    let delegation_rate_change_delay = get_delegation_rate_change_delay(provider)?;
+    if delegation_rate_change_delay == 0 {
+        bid.with_delegation_rate(delegation_rate);
+        return Ok(());
+    }
+
+    if matches!(
+        bid.pending_delegation_rate_change(),
+        Some(pending) if pending.delegation_rate() == delegation_rate
+    ) {
+        return Ok(());
+    }
+
+    let activation_era = get_era_id(provider)?
+        .checked_add(delegation_rate_change_delay)
+        .ok_or(Error::ArithmeticOverflow)?;
+    bid.with_pending_delegation_rate_change(DelegationRateChange::new(
+        delegation_rate,
+        activation_era,
+    ));
+    Ok(())
+}
+
+/// Checks that a delegator's stake with the validator owning `bid` would stay within the
+/// configured delegation bounds after adding `amount` to it.
+pub(crate) fn check_delegation_bounds<P>(
+    provider: &mut P,
+    bid: &Bid,
+    delegator_public_key: &PublicKey,
+    amount: U512,
+) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let new_stake = match bid.delegators().get(delegator_public_key) {
+        Some(delegator) => delegator
+            .staked_amount()
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?,
+        None => {
+            let max_delegators_per_validator = get_max_delegators_per_validator(provider)?;
+            if bid.delegators().len() >= max_delegators_per_validator as usize {
+                return Err(Error::ExceededDelegatorSizeLimit);
+            }
+            amount
+        }
+    };
+    if new_stake < get_minimum_delegation_amount(provider)? {
+        return Err(Error::DelegationAmountTooSmall);
+    }
+    if new_stake > get_maximum_delegation_amount(provider)? {
+        return Err(Error::DelegationAmountTooLarge);
+    }
+    Ok(())
+}
+
+/// Returns the pending redelegation requests, keyed by the account hash of their delegator.
+/// Delegators without pending requests are left out.
+pub fn get_redelegations<P>(
+    provider: &mut P,
+) -> Result<BTreeMap<AccountHash, Vec<Redelegation>>, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let redelegation_keys = provider.get_keys(&KeyTag::Redelegation)?;
+
+    let mut ret = BTreeMap::new();
+
+    for key in redelegation_keys {
+        let account_hash = match key {
+            Key::Redelegation(account_hash) => account_hash,
+            _ => return Err(Error::InvalidKeyVariant),
+        };
+        let redelegations = provider.read_redelegations(&account_hash)?;
+        if !redelegations.is_empty() {
+            ret.insert(account_hash, redelegations);
+        }
+    }
+
+    Ok(ret)
+}
+
+/// Moves the stake of every pending redelegation request into a delegation with its new
+/// validator.  If the new validator can no longer accept the stake, it is turned into an
+/// unbonding request with the old validator instead, as if it had been undelegated in the era the
+/// redelegation was requested, so it is only paid out after the unbonding delay and remains
+/// slashable until then.  The processed requests are removed from their delegators' entries.
+///
+/// This function can be called by the system only.
+pub(crate) fn process_redelegations<P: Auction>(provider: &mut P) -> Result<(), Error> {
+    if provider.get_caller() != PublicKey::System.to_account_hash() {
+        return Err(Error::InvalidCaller);
+    }
+
+    for (delegator_account_hash, redelegations) in get_redelegations(provider)? {
+        for redelegation in redelegations {
+            if !rebond(provider, &redelegation)? {
+                let unbonding_purse = redelegation.unbonding_purse();
+                let validator_account_hash =
+                    AccountHash::from(unbonding_purse.validator_public_key());
+                let mut unbonding_purses = provider.read_withdraw(&validator_account_hash)?;
+                unbonding_purses.push(unbonding_purse.clone());
+                provider.write_withdraw(validator_account_hash, unbonding_purses)?;
+            }
+        }
+        provider.write_redelegations(delegator_account_hash, Vec::new())?;
+    }
+
+    Ok(())
+}
+
+/// Delegates the stake of a redelegation request to its new validator.  Returns `false` if the new
+/// validator no longer exists or the delegation would violate the configured delegation bounds.
+fn rebond<P: Auction>(provider: &mut P, redelegation: &Redelegation) -> Result<bool, Error> {
+    let unbonding_purse = redelegation.unbonding_purse();
+    let delegator_public_key = unbonding_purse.unbonder_public_key();
+    let new_validator_public_key = redelegation.new_validator_public_key();
+    let amount = *unbonding_purse.amount();
+
+    let new_validator_account_hash = AccountHash::from(new_validator_public_key);
+    let mut bid = match provider.read_bid(&new_validator_account_hash)? {
+        Some(bid) => bid,
+        None => return Ok(false),
+    };
+
+    match check_delegation_bounds(provider, &bid, delegator_public_key, amount) {
+        Ok(()) => (),
+        Err(Error::DelegationAmountTooSmall)
+        | Err(Error::DelegationAmountTooLarge)
+        | Err(Error::ExceededDelegatorSizeLimit) => return Ok(false),
+        Err(error) => return Err(error),
+    }
+
+    let target = match bid.delegators().get(delegator_public_key) {
+        Some(delegator) => *delegator.bonding_purse(),
+        None => provider.create_purse()?,
+    };
+    provider
+        .mint_transfer_direct(
+            Some(PublicKey::System.to_account_hash()),
+            *unbonding_purse.bonding_purse(),
+            target,
+            amount,
+            None,
+        )
+        .map_err(|_| Error::TransferToDelegatorPurse)?
+        .map_err(|_| Error::TransferToDelegatorPurse)?;
+
+    let delegators = bid.delegators_mut();
+    match delegators.get_mut(delegator_public_key) {
+        Some(delegator) => {
+            delegator.increase_stake(amount)?;
+        }
+        None => {
+            let delegator = Delegator::unlocked(
+                delegator_public_key.clone(),
+                amount,
+                target,
+                new_validator_public_key.clone(),
+            );
+            delegators.insert(delegator_public_key.clone(), delegator);
+        }
+    }
+
+    provider.write_bid(new_validator_account_hash, bid)?;
+
+    Ok(true)
+}
+
 /// Iterates over unbonding entries and checks if a locked amount can be paid already if
 /// a specific era is reached.
 ///
@@ -249,6 +505,34 @@ pub(crate) fn create_unbonding_purse<P: Auction + ?Sized>(
     Ok(())
 }
 
+/// Creates a new redelegation request moving `amount` from the delegator's bonding purse with
+/// `validator_public_key` to `new_validator_public_key`.
+pub(crate) fn create_redelegation<P: Auction>(
+    provider: &mut P,
+    validator_public_key: PublicKey,
+    delegator_public_key: PublicKey,
+    bonding_purse: URef,
+    amount: U512,
+    new_validator_public_key: PublicKey,
+) -> Result<(), Error> {
+    if provider.get_balance(bonding_purse)?.unwrap_or_default() < amount {
+        return Err(Error::UnbondTooLarge);
+    }
+
+    let era_of_creation = provider.read_era_id()?;
+    let unbonding_purse = UnbondingPurse::new(
+        bonding_purse,
+        validator_public_key,
+        delegator_public_key,
+        era_of_creation,
+        amount,
+    );
+    let delegator_account_hash = AccountHash::from(unbonding_purse.unbonder_public_key());
+    let mut redelegations = provider.read_redelegations(&delegator_account_hash)?;
+    redelegations.push(Redelegation::new(unbonding_purse, new_validator_public_key));
+    provider.write_redelegations(delegator_account_hash, redelegations)
+}
+
 /// Reinvests delegator reward by increasing its stake.
 pub fn reinvest_delegator_rewards<P>(
     provider: &mut P,
@@ -331,3 +615,244 @@ where
 
     Ok(bonding_purse)
 }
+
+/// Returns the part of `amount` slashed by `fraction`.
+fn slashed_part(amount: U512, fraction: Ratio<u64>) -> Result<U512, Error> {
+    amount
+        .checked_mul(U512::from(*fraction.numer()))
+        .map(|product| product / U512::from(*fraction.denom()))
+        .ok_or(Error::ArithmeticOverflow)
+}
+
+/// Slashes `fraction` of an unbonding purse.  Returns the slashed amount and the remainder of the
+/// purse, unless nothing remains of it.
+fn slash_unbonding_purse(
+    unbonding_purse: &UnbondingPurse,
+    fraction: Ratio<u64>,
+) -> Result<(U512, Option<UnbondingPurse>), Error> {
+    let slashed_amount = slashed_part(*unbonding_purse.amount(), fraction)?;
+    let remaining_amount = *unbonding_purse.amount() - slashed_amount;
+    if remaining_amount.is_zero() {
+        return Ok((slashed_amount, None));
+    }
+    let remaining_purse = UnbondingPurse::new(
+        *unbonding_purse.bonding_purse(),
+        unbonding_purse.validator_public_key().clone(),
+        unbonding_purse.unbonder_public_key().clone(),
+        unbonding_purse.era_of_creation(),
+        remaining_amount,
+    );
+    Ok((slashed_amount, Some(remaining_purse)))
+}
+
+/// Slashes `fraction` of the stakes bonded to a validator: its own stake, its delegators' stakes
+/// and the stakes which are unbonding or being redelegated away from it.  Slashing the whole stake
+/// also deactivates the validator's bid.
+///
+/// Returns the total slashed amount.
+pub(crate) fn slash_stakes<P: Auction>(
+    provider: &mut P,
+    validator_public_key: &PublicKey,
+    fraction: Ratio<u64>,
+) -> Result<U512, Error> {
+    let mut slashed_amount = U512::zero();
+
+    let validator_account_hash = AccountHash::from(validator_public_key);
+    if let Some(mut bid) = provider.read_bid(&validator_account_hash)? {
+        let validator_slashed_amount = slashed_part(*bid.staked_amount(), fraction)?;
+        *bid.staked_amount_mut() -= validator_slashed_amount;
+        slashed_amount += validator_slashed_amount;
+
+        for delegator in bid.delegators_mut().values_mut() {
+            let delegator_slashed_amount = slashed_part(*delegator.staked_amount(), fraction)?;
+            *delegator.staked_amount_mut() -= delegator_slashed_amount;
+            slashed_amount += delegator_slashed_amount;
+        }
+
+        if fraction == Ratio::from_integer(1) {
+            bid.deactivate();
+        }
+        provider.write_bid(validator_account_hash, bid)?;
+    }
+
+    let unbonding_purses = provider.read_withdraw(&validator_account_hash)?;
+    if !unbonding_purses.is_empty() {
+        let mut remaining_purses = Vec::with_capacity(unbonding_purses.len());
+        for unbonding_purse in &unbonding_purses {
+            let (purse_slashed_amount, remaining_purse) =
+                slash_unbonding_purse(unbonding_purse, fraction)?;
+            slashed_amount += purse_slashed_amount;
+            remaining_purses.extend(remaining_purse);
+        }
+        provider.write_withdraw(validator_account_hash, remaining_purses)?;
+    }
+
+    for (delegator_account_hash, redelegations) in get_redelegations(provider)? {
+        if !redelegations.iter().any(|redelegation| {
+            redelegation.unbonding_purse().validator_public_key() == validator_public_key
+        }) {
+            continue;
+        }
+        let mut remaining_redelegations = Vec::with_capacity(redelegations.len());
+        for redelegation in redelegations {
+            if redelegation.unbonding_purse().validator_public_key() != validator_public_key {
+                remaining_redelegations.push(redelegation);
+                continue;
+            }
+            let (purse_slashed_amount, remaining_purse) =
+                slash_unbonding_purse(redelegation.unbonding_purse(), fraction)?;
+            slashed_amount += purse_slashed_amount;
+            if let Some(remaining_purse) = remaining_purse {
+                remaining_redelegations.push(Redelegation::new(
+                    remaining_purse,
+                    redelegation.new_validator_public_key().clone(),
+                ));
+            }
+        }
+        provider.write_redelegations(delegator_account_hash, remaining_redelegations)?;
+    }
+
+    Ok(slashed_amount)
+}
+
+/// Pays slashed funds into the stake of the given validator.
+///
+/// Returns `false` if the recipient has no bid to pay into.
+pub(crate) fn pay_slashed_amount<P: Auction>(
+    provider: &mut P,
+    recipient: &PublicKey,
+    amount: U512,
+) -> Result<bool, Error> {
+    let recipient_account_hash = AccountHash::from(recipient);
+    let mut bid = match provider.read_bid(&recipient_account_hash)? {
+        Some(bid) => bid,
+        None => return Ok(false),
+    };
+    if amount.is_zero() {
+        return Ok(true);
+    }
+
+    bid.increase_stake(amount)?;
+    let bonding_purse = *bid.bonding_purse();
+    provider.write_bid(recipient_account_hash, bid)?;
+
+    let tmp_purse = provider.mint(amount).map_err(|_| Error::MintReward)?;
+    provider
+        .mint_transfer_direct(
+            Some(recipient_account_hash),
+            tmp_purse,
+            bonding_purse,
+            amount,
+            None,
+        )
+        .map_err(|_| Error::ValidatorRewardTransfer)?
+        .map_err(|_| Error::ValidatorRewardTransfer)?;
+
+    Ok(true)
+}
+
+/// How a validator which was inactive in an era participated in it.
+pub(crate) struct Inactivity {
+    /// Participation in percent of the most active validator's.
+    pub(crate) participation_percent: u8,
+    /// Number of consecutive eras, including the current one, in which the validator was inactive.
+    pub(crate) consecutive_inactive_eras: u64,
+}
+
+/// Returns the validators which were inactive in `era_id` under `inactivity_policy`.
+///
+/// A validator's participation is its reward factor per unit of stake, relative to the highest one
+/// among the era's validators, so that a validator which took part in every finalized block has a
+/// participation of one.  If no validator was rewarded, nobody is considered inactive.  The number
+/// of consecutive inactive eras is carried over from the previous era's [`EraInfo`](super::EraInfo).
+pub(crate) fn inactive_validators<P: Auction>(
+    provider: &mut P,
+    era_id: EraId,
+    reward_factors: &BTreeMap<PublicKey, u64>,
+    seigniorage_recipients: &SeigniorageRecipients,
+    inactivity_policy: InactivityPolicy,
+) -> Result<BTreeMap<PublicKey, Inactivity>, Error> {
+    let mut inactive_validators = BTreeMap::new();
+    if !inactivity_policy.is_enabled() {
+        return Ok(inactive_validators);
+    }
+
+    let mut reward_rates = BTreeMap::new();
+    for (public_key, reward_factor) in reward_factors {
+        let total_stake = seigniorage_recipients
+            .get(public_key)
+            .ok_or(Error::ValidatorNotFound)?
+            .total_stake()
+            .ok_or(Error::ArithmeticOverflow)?;
+        if !total_stake.is_zero() {
+            reward_rates.insert(
+                public_key,
+                Ratio::new(U512::from(*reward_factor), total_stake),
+            );
+        }
+    }
+    let max_reward_rate = match reward_rates.values().max() {
+        Some(max_reward_rate) if !max_reward_rate.is_zero() => *max_reward_rate,
+        _ => return Ok(inactive_validators),
+    };
+
+    let previous_era_info = match era_id.checked_sub(1) {
+        Some(previous_era_id) => provider.read_era_info(previous_era_id)?,
+        None => None,
+    };
+    let previous_inactivity: BTreeMap<&PublicKey, u64> = previous_era_info
+        .iter()
+        .flat_map(|era_info| era_info.inactivity())
+        .map(|record| {
+            (
+                record.validator_public_key(),
+                record.consecutive_inactive_eras(),
+            )
+        })
+        .collect();
+
+    let threshold = inactivity_policy.threshold();
+    let threshold = Ratio::new(
+        U512::from(*threshold.numer()),
+        U512::from(*threshold.denom()),
+    );
+    for (public_key, reward_rate) in reward_rates {
+        let participation = reward_rate / max_reward_rate;
+        if participation >= threshold {
+            continue;
+        }
+        let participation_percent = (participation * Ratio::from_integer(U512::from(100)))
+            .to_integer()
+            .as_u64() as u8;
+        let consecutive_inactive_eras = previous_inactivity
+            .get(public_key)
+            .copied()
+            .unwrap_or_default()
+            .saturating_add(1);
+        inactive_validators.insert(
+            public_key.clone(),
+            Inactivity {
+                participation_percent,
+                consecutive_inactive_eras,
+            },
+        );
+    }
+    Ok(inactive_validators)
+}
+
+/// Deactivates the bid of a validator evicted for inactivity.
+///
+/// Returns `false` if the validator has no bid or it was already inactive.
+pub(crate) fn evict_inactive_validator<P: Auction>(
+    provider: &mut P,
+    validator_public_key: &PublicKey,
+) -> Result<bool, Error> {
+    let validator_account_hash = AccountHash::from(validator_public_key);
+    let mut bid = match provider.read_bid(&validator_account_hash)? {
+        Some(bid) if !bid.inactive() => bid,
+        _ => return Ok(false),
+    };
+    bid.deactivate();
+    provider.write_bid(validator_account_hash, bid)?;
+    Ok(true)
+}
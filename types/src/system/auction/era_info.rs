@@ -8,12 +8,18 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    bytesrepr::{self, FromBytes, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
+    system::auction::SlashEvidence,
     CLType, CLTyped, PublicKey, U512,
 };
 
 const SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG: u8 = 0;
 const SEIGNIORAGE_ALLOCATION_DELEGATOR_TAG: u8 = 1;
+// Slashes and inactivity are serialized as further entries of the list of seigniorage allocations,
+// each behind a tag of its own, so that era infos written before they were recorded keep their
+// layout.
+const SLASH_RECORD_TAG: u8 = 2;
+const INACTIVITY_RECORD_TAG: u8 = 3;
 
 /// Information about a seigniorage allocation
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
@@ -157,20 +163,225 @@ impl CLTyped for SeigniorageAllocation {
     }
 }
 
+/// Information about a slashing of a validator
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SlashRecord {
+    /// Slashed validator's public key
+    validator_public_key: PublicKey,
+    /// Total amount slashed from the validator, its delegators and their unbonding stakes
+    amount: U512,
+    /// Validator which received the slashed amount, or `None` if it was burned
+    recipient: Option<PublicKey>,
+    /// Reference to the evidence of the offense, if known
+    evidence: Option<SlashEvidence>,
+}
+
+impl SlashRecord {
+    /// Constructs a [`SlashRecord`]
+    pub const fn new(
+        validator_public_key: PublicKey,
+        amount: U512,
+        recipient: Option<PublicKey>,
+        evidence: Option<SlashEvidence>,
+    ) -> Self {
+        SlashRecord {
+            validator_public_key,
+            amount,
+            recipient,
+            evidence,
+        }
+    }
+
+    /// Returns the slashed validator's public key
+    pub fn validator_public_key(&self) -> &PublicKey {
+        &self.validator_public_key
+    }
+
+    /// Returns the total slashed amount
+    pub fn amount(&self) -> &U512 {
+        &self.amount
+    }
+
+    /// Returns the validator which received the slashed amount, if any
+    pub fn recipient(&self) -> Option<&PublicKey> {
+        self.recipient.as_ref()
+    }
+
+    /// Returns the reference to the evidence of the offense, if known
+    pub fn evidence(&self) -> Option<&SlashEvidence> {
+        self.evidence.as_ref()
+    }
+}
+
+impl ToBytes for SlashRecord {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.append(&mut self.validator_public_key.to_bytes()?);
+        buffer.append(&mut self.amount.to_bytes()?);
+        buffer.append(&mut self.recipient.to_bytes()?);
+        buffer.append(&mut self.evidence.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.validator_public_key.serialized_length()
+            + self.amount.serialized_length()
+            + self.recipient.serialized_length()
+            + self.evidence.serialized_length()
+    }
+}
+
+impl FromBytes for SlashRecord {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (validator_public_key, rem) = PublicKey::from_bytes(bytes)?;
+        let (amount, rem) = U512::from_bytes(rem)?;
+        let (recipient, rem) = Option::<PublicKey>::from_bytes(rem)?;
+        let (evidence, rem) = Option::<SlashEvidence>::from_bytes(rem)?;
+        Ok((
+            SlashRecord::new(validator_public_key, amount, recipient, evidence),
+            rem,
+        ))
+    }
+}
+
+impl CLTyped for SlashRecord {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+/// Information about a validator which participated too little in an era
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct InactivityRecord {
+    /// Inactive validator's public key
+    validator_public_key: PublicKey,
+    /// Validator's participation in the era, in percent of the most active validator's
+    participation_percent: u8,
+    /// Number of consecutive eras, including this one, in which the validator was inactive
+    consecutive_inactive_eras: u64,
+    /// Amount of the validator's and its delegators' reward withheld as a penalty
+    withheld_reward: U512,
+    /// Whether the validator's bid was deactivated at the end of the era
+    evicted: bool,
+}
+
+impl InactivityRecord {
+    /// Constructs an [`InactivityRecord`]
+    pub const fn new(
+        validator_public_key: PublicKey,
+        participation_percent: u8,
+        consecutive_inactive_eras: u64,
+        withheld_reward: U512,
+        evicted: bool,
+    ) -> Self {
+        InactivityRecord {
+            validator_public_key,
+            participation_percent,
+            consecutive_inactive_eras,
+            withheld_reward,
+            evicted,
+        }
+    }
+
+    /// Returns the inactive validator's public key
+    pub fn validator_public_key(&self) -> &PublicKey {
+        &self.validator_public_key
+    }
+
+    /// Returns the validator's participation in the era, in percent of the most active validator's
+    pub fn participation_percent(&self) -> u8 {
+        self.participation_percent
+    }
+
+    /// Returns the number of consecutive eras, including this one, in which the validator was
+    /// inactive
+    pub fn consecutive_inactive_eras(&self) -> u64 {
+        self.consecutive_inactive_eras
+    }
+
+    /// Returns the amount of reward withheld as a penalty
+    pub fn withheld_reward(&self) -> &U512 {
+        &self.withheld_reward
+    }
+
+    /// Returns whether the validator's bid was deactivated at the end of the era
+    pub fn evicted(&self) -> bool {
+        self.evicted
+    }
+}
+
+impl ToBytes for InactivityRecord {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.append(&mut self.validator_public_key.to_bytes()?);
+        buffer.append(&mut self.participation_percent.to_bytes()?);
+        buffer.append(&mut self.consecutive_inactive_eras.to_bytes()?);
+        buffer.append(&mut self.withheld_reward.to_bytes()?);
+        buffer.append(&mut self.evicted.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.validator_public_key.serialized_length()
+            + self.participation_percent.serialized_length()
+            + self.consecutive_inactive_eras.serialized_length()
+            + self.withheld_reward.serialized_length()
+            + self.evicted.serialized_length()
+    }
+}
+
+impl FromBytes for InactivityRecord {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (validator_public_key, rem) = PublicKey::from_bytes(bytes)?;
+        let (participation_percent, rem) = u8::from_bytes(rem)?;
+        let (consecutive_inactive_eras, rem) = u64::from_bytes(rem)?;
+        let (withheld_reward, rem) = U512::from_bytes(rem)?;
+        let (evicted, rem) = bool::from_bytes(rem)?;
+        Ok((
+            InactivityRecord::new(
+                validator_public_key,
+                participation_percent,
+                consecutive_inactive_eras,
+                withheld_reward,
+                evicted,
+            ),
+            rem,
+        ))
+    }
+}
+
+impl CLTyped for InactivityRecord {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
 /// Auction metadata.  Intended to be recorded at each era.
 #[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct EraInfo {
     seigniorage_allocations: Vec<SeigniorageAllocation>,
+    #[serde(default)]
+    slashes: Vec<SlashRecord>,
+    #[serde(default)]
+    inactivity: Vec<InactivityRecord>,
 }
 
 impl EraInfo {
     /// Constructs a [`EraInfo`].
     pub fn new() -> Self {
         let seigniorage_allocations = Vec::new();
+        let slashes = Vec::new();
+        let inactivity = Vec::new();
         EraInfo {
             seigniorage_allocations,
+            slashes,
+            inactivity,
         }
     }
 
@@ -184,6 +395,26 @@ impl EraInfo {
         &mut self.seigniorage_allocations
     }
 
+    /// Returns a reference to the slashes recorded in the era
+    pub fn slashes(&self) -> &Vec<SlashRecord> {
+        &self.slashes
+    }
+
+    /// Returns a mutable reference to the slashes recorded in the era
+    pub fn slashes_mut(&mut self) -> &mut Vec<SlashRecord> {
+        &mut self.slashes
+    }
+
+    /// Returns a reference to the validators recorded as inactive in the era
+    pub fn inactivity(&self) -> &Vec<InactivityRecord> {
+        &self.inactivity
+    }
+
+    /// Returns a mutable reference to the validators recorded as inactive in the era
+    pub fn inactivity_mut(&mut self) -> &mut Vec<InactivityRecord> {
+        &mut self.inactivity
+    }
+
     /// Returns all seigniorage allocations that match the provided public key
     /// using the following criteria:
     /// * If the match candidate is a validator allocation, the provided public key is matched
@@ -208,23 +439,72 @@ impl EraInfo {
 
 impl ToBytes for EraInfo {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        self.seigniorage_allocations.to_bytes()
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        let entry_count =
+            self.seigniorage_allocations.len() + self.slashes.len() + self.inactivity.len();
+        buffer.append(&mut (entry_count as u32).to_bytes()?);
+        for seigniorage_allocation in &self.seigniorage_allocations {
+            buffer.append(&mut seigniorage_allocation.to_bytes()?);
+        }
+        for slash in &self.slashes {
+            buffer.push(SLASH_RECORD_TAG);
+            buffer.append(&mut slash.to_bytes()?);
+        }
+        for inactivity_record in &self.inactivity {
+            buffer.push(INACTIVITY_RECORD_TAG);
+            buffer.append(&mut inactivity_record.to_bytes()?);
+        }
+        Ok(buffer)
     }
 
     fn serialized_length(&self) -> usize {
-        self.seigniorage_allocations.serialized_length()
+        U32_SERIALIZED_LENGTH
+            + self
+                .seigniorage_allocations
+                .iter()
+                .map(ToBytes::serialized_length)
+                .sum::<usize>()
+            + self
+                .slashes
+                .iter()
+                .map(|slash| U8_SERIALIZED_LENGTH + slash.serialized_length())
+                .sum::<usize>()
+            + self
+                .inactivity
+                .iter()
+                .map(|inactivity_record| {
+                    U8_SERIALIZED_LENGTH + inactivity_record.serialized_length()
+                })
+                .sum::<usize>()
     }
 }
 
 impl FromBytes for EraInfo {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (seigniorage_allocations, rem) = Vec::<SeigniorageAllocation>::from_bytes(bytes)?;
-        Ok((
-            EraInfo {
-                seigniorage_allocations,
-            },
-            rem,
-        ))
+        let (entry_count, mut rem) = u32::from_bytes(bytes)?;
+        let mut era_info = EraInfo::new();
+        for _ in 0..entry_count {
+            rem = match rem.split_first() {
+                Some((&SLASH_RECORD_TAG, rem)) => {
+                    let (slash, rem) = SlashRecord::from_bytes(rem)?;
+                    era_info.slashes.push(slash);
+                    rem
+                }
+                Some((&INACTIVITY_RECORD_TAG, rem)) => {
+                    let (inactivity_record, rem) = InactivityRecord::from_bytes(rem)?;
+                    era_info.inactivity.push(inactivity_record);
+                    rem
+                }
+                _ => {
+                    let (seigniorage_allocation, rem) = SeigniorageAllocation::from_bytes(rem)?;
+                    era_info
+                        .seigniorage_allocations
+                        .push(seigniorage_allocation);
+                    rem
+                }
+            };
+        }
+        Ok((era_info, rem))
     }
 }
 
@@ -234,19 +514,23 @@ impl CLTyped for EraInfo {
     }
 }
 
-/// Generators for [`SeigniorageAllocation`] and [`EraInfo`]
+/// Generators for [`SeigniorageAllocation`], [`SlashEvidence`], [`SlashRecord`],
+/// [`InactivityRecord`] and [`EraInfo`]
 #[cfg(any(feature = "gens", test))]
 pub mod gens {
     use proptest::{
         collection::{self, SizeRange},
-        prelude::Strategy,
+        option,
+        prelude::{any, Strategy},
         prop_oneof,
     };
 
     use crate::{
         crypto::gens::public_key_arb,
         gens::u512_arb,
-        system::auction::{EraInfo, SeigniorageAllocation},
+        system::auction::{
+            EraInfo, InactivityRecord, SeigniorageAllocation, SlashEvidence, SlashRecord,
+        },
     };
 
     fn seigniorage_allocation_validator_arb() -> impl Strategy<Value = SeigniorageAllocation> {
@@ -271,13 +555,71 @@ pub mod gens {
         ]
     }
 
+    /// Creates an arbitrary [`SlashEvidence`](crate::system::auction::SlashEvidence)
+    pub fn slash_evidence_arb() -> impl Strategy<Value = SlashEvidence> {
+        (public_key_arb(), any::<[u8; 32]>(), any::<u64>()).prop_map(
+            |(reporter, message_hash, block_height)| {
+                SlashEvidence::new(reporter, message_hash, block_height)
+            },
+        )
+    }
+
+    /// Creates an arbitrary [`SlashRecord`](crate::system::auction::SlashRecord)
+    pub fn slash_record_arb() -> impl Strategy<Value = SlashRecord> {
+        (
+            public_key_arb(),
+            u512_arb(),
+            option::of(public_key_arb()),
+            option::of(slash_evidence_arb()),
+        )
+            .prop_map(|(validator_public_key, amount, recipient, evidence)| {
+                SlashRecord::new(validator_public_key, amount, recipient, evidence)
+            })
+    }
+
+    /// Creates an arbitrary [`InactivityRecord`](crate::system::auction::InactivityRecord)
+    pub fn inactivity_record_arb() -> impl Strategy<Value = InactivityRecord> {
+        (
+            public_key_arb(),
+            any::<u8>(),
+            any::<u64>(),
+            u512_arb(),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(
+                    validator_public_key,
+                    participation_percent,
+                    consecutive_inactive_eras,
+                    withheld_reward,
+                    evicted,
+                )| {
+                    InactivityRecord::new(
+                        validator_public_key,
+                        participation_percent,
+                        consecutive_inactive_eras,
+                        withheld_reward,
+                        evicted,
+                    )
+                },
+            )
+    }
+
     /// Creates an arbitrary [`EraInfo`]
     pub fn era_info_arb(size: impl Into<SizeRange>) -> impl Strategy<Value = EraInfo> {
-        collection::vec(seigniorage_allocation_arb(), size).prop_map(|allocations| {
-            let mut era_info = EraInfo::new();
-            *era_info.seigniorage_allocations_mut() = allocations;
-            era_info
-        })
+        let size = size.into();
+        (
+            collection::vec(seigniorage_allocation_arb(), size.clone()),
+            collection::vec(slash_record_arb(), size.clone()),
+            collection::vec(inactivity_record_arb(), size),
+        )
+            .prop_map(|(allocations, slashes, inactivity)| {
+                let mut era_info = EraInfo::new();
+                *era_info.seigniorage_allocations_mut() = allocations;
+                *era_info.slashes_mut() = slashes;
+                *era_info.inactivity_mut() = inactivity;
+                era_info
+            })
     }
 }
 
@@ -285,7 +627,11 @@ pub mod gens {
 mod tests {
     use proptest::prelude::*;
 
-    use crate::bytesrepr;
+    use crate::{
+        bytesrepr::{self, ToBytes},
+        system::auction::{EraInfo, InactivityRecord, SeigniorageAllocation, SlashRecord},
+        PublicKey, U512,
+    };
 
     use super::gens;
 
@@ -295,4 +641,41 @@ mod tests {
             bytesrepr::test_serialization_roundtrip(&era_info)
         }
     }
+
+    #[test]
+    fn should_keep_layout_of_era_info_without_slashes_or_inactivity() {
+        let mut era_info = EraInfo::new();
+        era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::validator(
+                PublicKey::System,
+                U512::one(),
+            ));
+        let bytes = era_info.seigniorage_allocations().to_bytes().unwrap();
+        assert_eq!(era_info.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn should_deserialize_era_infos_followed_by_other_values() {
+        let mut legacy_era_info = EraInfo::new();
+        legacy_era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::validator(
+                PublicKey::System,
+                U512::one(),
+            ));
+        let mut era_info = legacy_era_info.clone();
+        era_info
+            .slashes_mut()
+            .push(SlashRecord::new(PublicKey::System, U512::one(), None, None));
+        era_info.inactivity_mut().push(InactivityRecord::new(
+            PublicKey::System,
+            10,
+            2,
+            U512::one(),
+            false,
+        ));
+
+        bytesrepr::test_serialization_roundtrip(&vec![legacy_era_info, era_info, EraInfo::new()]);
+    }
 }
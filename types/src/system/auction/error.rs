@@ -262,10 +262,52 @@ pub enum Error {
     /// assert_eq!(39, Error::ArithmeticOverflow as u8);
     /// ```
     ArithmeticOverflow = 39,
+    /// The resulting delegation would be below the configured minimum delegation amount.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(41, Error::DelegationAmountTooSmall as u8);
+    /// ```
+    DelegationAmountTooSmall = 41,
+    /// The resulting delegation would exceed the configured maximum delegation amount.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(42, Error::DelegationAmountTooLarge as u8);
+    /// ```
+    DelegationAmountTooLarge = 42,
+    /// The validator already has the maximum number of delegators allowed.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(43, Error::ExceededDelegatorSizeLimit as u8);
+    /// ```
+    ExceededDelegatorSizeLimit = 43,
+    /// The validator to redelegate to was not found.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(44, Error::RedelegationValidatorNotFound as u8);
+    /// ```
+    RedelegationValidatorNotFound = 44,
+    /// The validator to redelegate to is the one currently delegated to.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(45, Error::RedelegationToSameValidator as u8);
+    /// ```
+    RedelegationToSameValidator = 45,
+    /// The requested delegation rate increase exceeds the configured maximum.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(46, Error::DelegationRateIncreaseTooLarge as u8);
+    /// ```
+    DelegationRateIncreaseTooLarge = 46,
+    /// Slash fraction is greater than one.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(47, Error::InvalidSlashFraction as u8);
+    /// ```
+    InvalidSlashFraction = 47,
     // NOTE: These variants below and related plumbing will be removed once support for WASM
     // system contracts will be dropped.
     #[doc(hidden)]
-    GasLimit,
+    GasLimit = 40,
 }
 
 impl Display for Error {
@@ -311,6 +353,19 @@ impl Display for Error {
             Error::DelegationRateTooLarge => formatter.write_str("Delegation rate too large"),
             Error::DelegatorFundsLocked => formatter.write_str("Delegator's funds are locked"),
             Error::ArithmeticOverflow => formatter.write_str("Arithmetic overflow"),
+            Error::DelegationAmountTooSmall => formatter.write_str("Delegation amount too small"),
+            Error::DelegationAmountTooLarge => formatter.write_str("Delegation amount too large"),
+            Error::ExceededDelegatorSizeLimit => formatter.write_str("Exceeded delegator size limit"),
+            Error::RedelegationValidatorNotFound => {
+                formatter.write_str("Redelegation validator not found")
+            }
+            Error::RedelegationToSameValidator => {
+                formatter.write_str("Redelegation to the same validator")
+            }
+            Error::DelegationRateIncreaseTooLarge => {
+                formatter.write_str("Delegation rate increase too large")
+            }
+            Error::InvalidSlashFraction => formatter.write_str("Invalid slash fraction"),
             Error::GasLimit => formatter.write_str("GasLimit"),
         }
     }
@@ -381,6 +436,21 @@ impl TryFrom<u8> for Error {
             d if d == Error::DelegatorFundsLocked as u8 => Ok(Error::DelegatorFundsLocked),
             d if d == Error::GasLimit as u8 => Ok(Error::GasLimit),
             d if d == Error::ArithmeticOverflow as u8 => Ok(Error::ArithmeticOverflow),
+            d if d == Error::DelegationAmountTooSmall as u8 => Ok(Error::DelegationAmountTooSmall),
+            d if d == Error::DelegationAmountTooLarge as u8 => Ok(Error::DelegationAmountTooLarge),
+            d if d == Error::ExceededDelegatorSizeLimit as u8 => {
+                Ok(Error::ExceededDelegatorSizeLimit)
+            }
+            d if d == Error::RedelegationValidatorNotFound as u8 => {
+                Ok(Error::RedelegationValidatorNotFound)
+            }
+            d if d == Error::RedelegationToSameValidator as u8 => {
+                Ok(Error::RedelegationToSameValidator)
+            }
+            d if d == Error::DelegationRateIncreaseTooLarge as u8 => {
+                Ok(Error::DelegationRateIncreaseTooLarge)
+            }
+            d if d == Error::InvalidSlashFraction as u8 => Ok(Error::InvalidSlashFraction),
             _ => Err(TryFromU8ForError(())),
         }
     }
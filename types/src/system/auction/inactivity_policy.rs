@@ -0,0 +1,157 @@
+use alloc::vec::Vec;
+
+use num_rational::Ratio;
+use num_traits::{CheckedMul, Zero};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// How validators which participate too little in an era are penalized.
+///
+/// A validator's participation is its reward factor per unit of stake, relative to that of the
+/// most active validator of the era.  A validator whose participation is below the threshold is
+/// inactive in that era, and its reward is reduced by the penalty step for each consecutive era in
+/// which it has been inactive.  After the configured number of consecutive inactive eras its bid is
+/// deactivated.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct InactivityPolicy {
+    /// Participation below which a validator is inactive in an era.
+    threshold: Ratio<u64>,
+    /// Fraction of the reward withheld for each consecutive inactive era.
+    penalty_step: Ratio<u64>,
+    /// Number of consecutive inactive eras after which a validator's bid is deactivated.  If zero,
+    /// bids are never deactivated.
+    eviction_eras: u64,
+}
+
+impl InactivityPolicy {
+    /// Creates a new [`InactivityPolicy`].
+    pub fn new(threshold: Ratio<u64>, penalty_step: Ratio<u64>, eviction_eras: u64) -> Self {
+        InactivityPolicy {
+            threshold,
+            penalty_step,
+            eviction_eras,
+        }
+    }
+
+    /// Returns the participation below which a validator is inactive in an era.
+    pub fn threshold(&self) -> Ratio<u64> {
+        self.threshold
+    }
+
+    /// Returns the fraction of the reward withheld for each consecutive inactive era.
+    pub fn penalty_step(&self) -> Ratio<u64> {
+        self.penalty_step
+    }
+
+    /// Returns the number of consecutive inactive eras after which a validator's bid is
+    /// deactivated.
+    pub fn eviction_eras(&self) -> u64 {
+        self.eviction_eras
+    }
+
+    /// Returns `true` if any validator can be considered inactive under this policy.
+    pub fn is_enabled(&self) -> bool {
+        !self.threshold.is_zero()
+    }
+
+    /// Returns the fraction of the reward withheld from a validator which has been inactive for
+    /// `consecutive_inactive_eras` eras, including the current one.
+    pub fn penalty(&self, consecutive_inactive_eras: u64) -> Ratio<u64> {
+        let one = Ratio::from_integer(1);
+        self.penalty_step
+            .checked_mul(&Ratio::from_integer(consecutive_inactive_eras))
+            .map_or(one, |penalty| penalty.min(one))
+    }
+
+    /// Returns `true` if a validator which has been inactive for `consecutive_inactive_eras` eras,
+    /// including the current one, is evicted.
+    pub fn is_evicted(&self, consecutive_inactive_eras: u64) -> bool {
+        self.eviction_eras != 0 && consecutive_inactive_eras >= self.eviction_eras
+    }
+}
+
+impl Default for InactivityPolicy {
+    /// Returns a policy under which no validator is ever inactive.
+    fn default() -> Self {
+        InactivityPolicy {
+            threshold: Ratio::zero(),
+            penalty_step: Ratio::zero(),
+            eviction_eras: 0,
+        }
+    }
+}
+
+impl ToBytes for InactivityPolicy {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.extend(self.threshold.to_bytes()?);
+        result.extend(self.penalty_step.to_bytes()?);
+        result.extend(self.eviction_eras.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.threshold.serialized_length()
+            + self.penalty_step.serialized_length()
+            + self.eviction_eras.serialized_length()
+    }
+}
+
+impl FromBytes for InactivityPolicy {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (threshold, bytes) = FromBytes::from_bytes(bytes)?;
+        let (penalty_step, bytes) = FromBytes::from_bytes(bytes)?;
+        let (eviction_eras, bytes) = FromBytes::from_bytes(bytes)?;
+        Ok((
+            InactivityPolicy {
+                threshold,
+                penalty_step,
+                eviction_eras,
+            },
+            bytes,
+        ))
+    }
+}
+
+impl CLTyped for InactivityPolicy {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_rational::Ratio;
+
+    use crate::{bytesrepr, system::auction::InactivityPolicy};
+
+    #[test]
+    fn serialization_roundtrip() {
+        let policy = InactivityPolicy::new(Ratio::new(1, 2), Ratio::new(1, 4), 3);
+        bytesrepr::test_serialization_roundtrip(&policy);
+        bytesrepr::test_serialization_roundtrip(&InactivityPolicy::default());
+    }
+
+    #[test]
+    fn should_increase_penalty_in_steps() {
+        let policy = InactivityPolicy::new(Ratio::new(1, 2), Ratio::new(1, 3), 0);
+        assert_eq!(policy.penalty(0), Ratio::from_integer(0));
+        assert_eq!(policy.penalty(1), Ratio::new(1, 3));
+        assert_eq!(policy.penalty(2), Ratio::new(2, 3));
+        assert_eq!(policy.penalty(3), Ratio::from_integer(1));
+        assert_eq!(policy.penalty(4), Ratio::from_integer(1));
+        assert_eq!(policy.penalty(u64::MAX), Ratio::from_integer(1));
+        assert!(!policy.is_evicted(u64::MAX));
+    }
+
+    #[test]
+    fn should_evict_after_configured_eras() {
+        let policy = InactivityPolicy::new(Ratio::new(1, 2), Ratio::new(1, 4), 3);
+        assert!(!policy.is_evicted(2));
+        assert!(policy.is_evicted(3));
+        assert!(policy.is_evicted(4));
+    }
+}
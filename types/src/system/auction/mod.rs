@@ -6,8 +6,11 @@ mod detail;
 mod entry_points;
 mod era_info;
 mod error;
+mod inactivity_policy;
 mod providers;
+mod redelegation;
 mod seigniorage_recipient;
+mod slash_request;
 mod unbonding_purse;
 
 use alloc::{collections::BTreeMap, vec::Vec};
@@ -17,14 +20,17 @@ use num_traits::{CheckedMul, CheckedSub};
 
 use crate::{account::AccountHash, EraId, PublicKey, U512};
 
-pub use bid::Bid;
+pub use bid::{Bid, DelegationRateChange};
 pub use constants::*;
 pub use delegator::Delegator;
 pub use entry_points::auction_entry_points;
 pub use era_info::*;
 pub use error::Error;
+pub use inactivity_policy::InactivityPolicy;
 pub use providers::{AccountProvider, MintProvider, RuntimeProvider, StorageProvider};
+pub use redelegation::Redelegation;
 pub use seigniorage_recipient::SeigniorageRecipient;
+pub use slash_request::{SlashEvidence, SlashRequest, MESSAGE_HASH_LENGTH};
 pub use unbonding_purse::UnbondingPurse;
 
 /// Representation of delegation rate of tokens. Range from 0..=100.
@@ -99,6 +105,10 @@ pub trait Auction:
     /// Validators cannot create a bid with 0 amount, and the delegation rate can't exceed
     /// [`DELEGATION_RATE_DENOMINATOR`].
     ///
+    /// An existing validator lowering its delegation rate does so immediately.  An increase can't
+    /// exceed the configured maximum increase and only takes effect once the configured number of
+    /// eras has passed; until then it is recorded as the bid's pending delegation rate change.
+    ///
     /// Returns a [`U512`] value indicating total amount of tokens staked for given `public_key`.
     fn add_bid(
         &mut self,
@@ -130,6 +140,7 @@ pub trait Auction:
                 if bid.inactive() {
                     bid.activate();
                 }
+                detail::update_delegation_rate(self, &mut bid, delegation_rate)?;
                 self.mint_transfer_direct(
                     Some(PublicKey::System.to_account_hash()),
                     source,
@@ -139,9 +150,7 @@ pub trait Auction:
                 )
                 .map_err(|_| Error::TransferToBidPurse)?
                 .map_err(|_| Error::TransferToBidPurse)?;
-                let updated_amount = bid
-                    .with_delegation_rate(delegation_rate)
-                    .increase_stake(amount)?;
+                let updated_amount = bid.increase_stake(amount)?;
                 self.write_bid(account_hash, bid)?;
                 updated_amount
             }
@@ -261,6 +270,8 @@ pub trait Auction:
             }
         };
 
+        detail::check_delegation_bounds(self, &bid, &delegator_public_key, amount)?;
+
         let delegators = bid.delegators_mut();
 
         let new_delegation_amount = match delegators.get_mut(&delegator_public_key) {
@@ -310,6 +321,10 @@ pub trait Auction:
     ///
     /// The arguments are the delegator's key, the validator's key, and the amount.
     ///
+    /// Fails with [`Error::DelegationAmountTooSmall`] if the remaining amount would be non-zero but
+    /// below the minimum delegation amount, in which case the delegator has to undelegate their
+    /// whole stake.
+    ///
     /// Returns the remaining bid amount after the stake was decreased.
     fn undelegate(
         &mut self,
@@ -331,10 +346,19 @@ pub trait Auction:
             None => return Err(Error::ValidatorNotFound),
         };
 
+        let minimum_delegation_amount = detail::get_minimum_delegation_amount(self)?;
+
         let delegators = bid.delegators_mut();
 
         let new_amount = match delegators.get_mut(&delegator_public_key) {
             Some(delegator) => {
+                // A delegator may withdraw everything, but may not leave behind a delegation
+                // below the minimum.
+                if let Some(remaining) = delegator.staked_amount().checked_sub(&amount) {
+                    if !remaining.is_zero() && remaining < minimum_delegation_amount {
+                        return Err(Error::DelegationAmountTooSmall);
+                    }
+                }
                 detail::create_unbonding_purse(
                     self,
                     validator_public_key,
@@ -358,43 +382,142 @@ pub trait Auction:
         Ok(new_amount)
     }
 
-    /// Slashes each validator.
+    /// Moves specified amount of motes from the entry in delegators map for given validator to a
+    /// new validator.  The stake stops earning rewards with the current validator immediately and
+    /// is delegated to the new validator at the end of the current era, without waiting for the
+    /// unbonding delay.
+    ///
+    /// The arguments are the delegator's key, the current validator's key, the amount and the new
+    /// validator's key.
+    ///
+    /// Returns the remaining bid amount with the current validator after the stake was decreased.
+    fn redelegate(
+        &mut self,
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        amount: U512,
+        new_validator_public_key: PublicKey,
+    ) -> Result<U512, Error> {
+        let provided_account_hash =
+            AccountHash::from_public_key(&delegator_public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        if validator_public_key == new_validator_public_key {
+            return Err(Error::RedelegationToSameValidator);
+        }
+
+        let validator_account_hash = AccountHash::from(&validator_public_key);
+
+        let mut bid = match self.read_bid(&validator_account_hash)? {
+            Some(bid) => bid,
+            None => return Err(Error::ValidatorNotFound),
+        };
+
+        // Reject requests the new validator couldn't accept up front.  The bounds are checked
+        // again once the redelegation is processed.
+        let new_validator_account_hash = AccountHash::from(&new_validator_public_key);
+        match self.read_bid(&new_validator_account_hash)? {
+            Some(new_bid) => {
+                detail::check_delegation_bounds(self, &new_bid, &delegator_public_key, amount)?
+            }
+            None => return Err(Error::RedelegationValidatorNotFound),
+        }
+
+        let minimum_delegation_amount = detail::get_minimum_delegation_amount(self)?;
+
+        let delegators = bid.delegators_mut();
+
+        let new_amount = match delegators.get_mut(&delegator_public_key) {
+            Some(delegator) => {
+                if let Some(remaining) = delegator.staked_amount().checked_sub(&amount) {
+                    if !remaining.is_zero() && remaining < minimum_delegation_amount {
+                        return Err(Error::DelegationAmountTooSmall);
+                    }
+                }
+                detail::create_redelegation(
+                    self,
+                    validator_public_key,
+                    delegator_public_key.clone(),
+                    *delegator.bonding_purse(),
+                    amount,
+                    new_validator_public_key,
+                )?;
+
+                let era_end_timestamp_millis = detail::get_era_end_timestamp_millis(self)?;
+                let updated_stake = delegator.decrease_stake(amount, era_end_timestamp_millis)?;
+                if updated_stake == U512::zero() {
+                    delegators.remove(&delegator_public_key);
+                };
+                updated_stake
+            }
+            None => return Err(Error::DelegatorNotFound),
+        };
+
+        self.write_bid(validator_account_hash, bid)?;
+
+        Ok(new_amount)
+    }
+
+    /// Slashes each validator in `slash_requests` by the requested fraction of the stakes bonded
+    /// to it.
+    ///
+    /// The slashed funds are paid into the stake of the request's recipient if there is one, and
+    /// burned otherwise.  Each slash is recorded in the current era's [`EraInfo`].
     ///
     /// This can be only invoked through a system call.
-    fn slash(&mut self, validator_public_keys: Vec<PublicKey>) -> Result<(), Error> {
+    fn slash(&mut self, slash_requests: Vec<SlashRequest>) -> Result<(), Error> {
         if self.get_caller() != PublicKey::System.to_account_hash() {
             return Err(Error::InvalidCaller);
         }
 
-        let mut burned_amount: U512 = U512::zero();
-
-        for validator_public_key in validator_public_keys {
-            // Burn stake, deactivate
-            let validator_account_hash = AccountHash::from(&validator_public_key);
-            if let Some(mut bid) = self.read_bid(&validator_account_hash)? {
-                burned_amount += *bid.staked_amount();
-                *bid.staked_amount_mut() = U512::zero();
-                bid.deactivate();
-                // Reset delegator stakes when deactivating validator bid.
-                for delegator in bid.delegators_mut().values_mut() {
-                    *delegator.staked_amount_mut() = U512::zero();
-                }
-                self.write_bid(validator_account_hash, bid)?;
-            };
+        if slash_requests
+            .iter()
+            .any(|slash_request| slash_request.fraction() > Ratio::from_integer(1))
+        {
+            return Err(Error::InvalidSlashFraction);
+        }
 
-            let validator_account_hash = AccountHash::from(&validator_public_key);
-            // Update unbonding entries for given validator
-            let unbonding_purses = self.read_withdraw(&validator_account_hash)?;
-            if !unbonding_purses.is_empty() {
-                burned_amount += unbonding_purses
-                    .into_iter()
-                    .map(|unbonding_purse| *unbonding_purse.amount())
-                    .sum();
-                self.write_withdraw(validator_account_hash, Vec::new())?;
+        let mut slashed_amount: U512 = U512::zero();
+        let mut slash_records = Vec::with_capacity(slash_requests.len());
+
+        for slash_request in &slash_requests {
+            let validator_public_key = slash_request.validator_public_key();
+            let amount =
+                detail::slash_stakes(self, validator_public_key, slash_request.fraction())?;
+            slashed_amount += amount;
+
+            // A recipient which is slashed itself doesn't receive anything.
+            let mut recipient = slash_request.recipient().filter(|recipient| {
+                !slash_requests
+                    .iter()
+                    .any(|other| other.validator_public_key() == *recipient)
+            });
+            if let Some(public_key) = recipient {
+                if !detail::pay_slashed_amount(self, public_key, amount)? {
+                    recipient = None;
+                }
             }
+
+            slash_records.push(SlashRecord::new(
+                validator_public_key.clone(),
+                amount,
+                recipient.cloned(),
+                slash_request.evidence().cloned(),
+            ));
         }
 
-        self.reduce_total_supply(burned_amount)?;
+        // Amounts paid to recipients were minted anew, so the whole slashed amount is burned.
+        self.reduce_total_supply(slashed_amount)?;
+
+        if !slash_records.is_empty() {
+            let era_id = detail::get_era_id(self)?;
+            let mut era_info = self.read_era_info(era_id)?.unwrap_or_default();
+            era_info.slashes_mut().extend(slash_records);
+            self.record_era_info(era_id, era_info)?;
+        }
 
         Ok(())
     }
@@ -417,6 +540,11 @@ pub trait Auction:
         let auction_delay = detail::get_auction_delay(self)?;
         let snapshot_size = auction_delay as usize + 1;
         let mut era_id: EraId = detail::get_era_id(self)?;
+
+        // Process redelegation requests before reading the bids so that redelegated stake counts
+        // towards the new validator's weight.
+        detail::process_redelegations(self)?;
+
         let mut bids = detail::get_bids(self)?;
 
         // Process unbond requests
@@ -424,11 +552,16 @@ pub trait Auction:
 
         // Process bids
         let mut bids_modified = false;
+        let next_era_id = era_id.successor();
         for (validator_public_key, bid) in bids.iter_mut() {
             if bid.process(era_end_timestamp_millis) {
                 bids_modified = true;
             }
 
+            if bid.apply_delegation_rate_change(next_era_id) {
+                bids_modified = true;
+            }
+
             if evicted_validators.contains(validator_public_key) {
                 bids_modified = bid.deactivate()
             }
@@ -514,7 +647,15 @@ pub trait Auction:
 
     /// Mint and distribute seigniorage rewards to validators and their delegators,
     /// according to `reward_factors` returned by the consensus component.
-    fn distribute(&mut self, reward_factors: BTreeMap<PublicKey, u64>) -> Result<(), Error> {
+    ///
+    /// The rewards of validators which were inactive in the era under `inactivity_policy` are
+    /// reduced, and validators which were inactive for too long are evicted.  Both are recorded in
+    /// the era's [`EraInfo`].
+    fn distribute(
+        &mut self,
+        reward_factors: BTreeMap<PublicKey, u64>,
+        inactivity_policy: InactivityPolicy,
+    ) -> Result<(), Error> {
         if self.get_caller() != PublicKey::System.to_account_hash() {
             return Err(Error::InvalidCaller);
         }
@@ -527,6 +668,15 @@ pub trait Auction:
             return Err(Error::MismatchedEraValidators);
         }
 
+        let inactive_validators = detail::inactive_validators(
+            self,
+            era_id,
+            &reward_factors,
+            &seigniorage_recipients,
+            inactivity_policy,
+        )?;
+        let mut inactivity_records = Vec::with_capacity(inactive_validators.len());
+
         let mut era_info = EraInfo::new();
         let mut seigniorage_allocations = era_info.seigniorage_allocations_mut();
 
@@ -541,13 +691,34 @@ pub trait Auction:
                 continue;
             }
 
-            let total_reward: Ratio<U512> = {
+            let mut total_reward: Ratio<U512> = {
                 let reward_rate = Ratio::new(U512::from(reward_factor), U512::from(BLOCK_REWARD));
                 reward_rate
                     .checked_mul(&Ratio::from(base_round_reward))
                     .ok_or(Error::ArithmeticOverflow)?
             };
 
+            if let Some(inactivity) = inactive_validators.get(&public_key) {
+                let penalty = inactivity_policy.penalty(inactivity.consecutive_inactive_eras);
+                let penalty =
+                    Ratio::new(U512::from(*penalty.numer()), U512::from(*penalty.denom()));
+                let withheld_reward = total_reward
+                    .checked_mul(&penalty)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                total_reward = total_reward
+                    .checked_sub(&withheld_reward)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                let evicted = inactivity_policy.is_evicted(inactivity.consecutive_inactive_eras)
+                    && detail::evict_inactive_validator(self, &public_key)?;
+                inactivity_records.push(InactivityRecord::new(
+                    public_key.clone(),
+                    inactivity.participation_percent,
+                    inactivity.consecutive_inactive_eras,
+                    withheld_reward.to_integer(),
+                    evicted,
+                ));
+            }
+
             let delegator_total_stake: U512 = recipient
                 .delegator_total_stake()
                 .ok_or(Error::ArithmeticOverflow)?;
@@ -628,6 +799,7 @@ pub trait Auction:
             }
         }
 
+        era_info.inactivity_mut().extend(inactivity_records);
         self.record_era_info(era_id, era_info)?;
 
         Ok(())
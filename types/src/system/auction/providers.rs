@@ -4,7 +4,7 @@ use crate::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::{
-        auction::{Bid, EraId, EraInfo, Error, UnbondingPurse},
+        auction::{Bid, EraId, EraInfo, Error, Redelegation, UnbondingPurse},
         mint,
     },
     CLTyped, Key, KeyTag, URef, BLAKE2B_DIGEST_LENGTH, U512,
@@ -52,6 +52,22 @@ pub trait StorageProvider {
         unbonding_purses: Vec<UnbondingPurse>,
     ) -> Result<(), Error>;
 
+    /// Reads the pending [`Redelegation`]s of the delegator with the given account hash.
+    fn read_redelegations(
+        &mut self,
+        account_hash: &AccountHash,
+    ) -> Result<Vec<Redelegation>, Error>;
+
+    /// Writes the pending [`Redelegation`]s of the delegator with the given account hash.
+    fn write_redelegations(
+        &mut self,
+        account_hash: AccountHash,
+        redelegations: Vec<Redelegation>,
+    ) -> Result<(), Error>;
+
+    /// Reads era info recorded at the given era id.
+    fn read_era_info(&mut self, era_id: EraId) -> Result<Option<EraInfo>, Error>;
+
     /// Records era info at the given era id.
     fn record_era_info(&mut self, era_id: EraId, era_info: EraInfo) -> Result<(), Error>;
 }
@@ -0,0 +1,108 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    system::auction::UnbondingPurse,
+    CLType, CLTyped, PublicKey,
+};
+
+/// Pending request to move delegated stake from one validator to another.
+///
+/// Redelegations are applied at the end of the era in which they were made, without waiting for
+/// the unbonding delay.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct Redelegation {
+    /// Stake leaving the current validator.
+    unbonding_purse: UnbondingPurse,
+    /// Public key of the validator the stake is moved to.
+    new_validator_public_key: PublicKey,
+}
+
+impl Redelegation {
+    /// Creates [`Redelegation`] instance for a redelegation request.
+    pub const fn new(unbonding_purse: UnbondingPurse, new_validator_public_key: PublicKey) -> Self {
+        Self {
+            unbonding_purse,
+            new_validator_public_key,
+        }
+    }
+
+    /// Returns the stake leaving the current validator.
+    pub fn unbonding_purse(&self) -> &UnbondingPurse {
+        &self.unbonding_purse
+    }
+
+    /// Returns public key of the validator the stake is moved to.
+    pub fn new_validator_public_key(&self) -> &PublicKey {
+        &self.new_validator_public_key
+    }
+}
+
+impl ToBytes for Redelegation {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.extend(&self.unbonding_purse.to_bytes()?);
+        result.extend(&self.new_validator_public_key.to_bytes()?);
+        Ok(result)
+    }
+    fn serialized_length(&self) -> usize {
+        self.unbonding_purse.serialized_length() + self.new_validator_public_key.serialized_length()
+    }
+}
+
+impl FromBytes for Redelegation {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (unbonding_purse, bytes) = FromBytes::from_bytes(bytes)?;
+        let (new_validator_public_key, bytes) = FromBytes::from_bytes(bytes)?;
+        Ok((
+            Redelegation {
+                unbonding_purse,
+                new_validator_public_key,
+            },
+            bytes,
+        ))
+    }
+}
+
+impl CLTyped for Redelegation {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bytesrepr,
+        system::auction::{EraId, Redelegation, UnbondingPurse},
+        AccessRights, PublicKey, SecretKey, URef, U512,
+    };
+
+    fn public_key(seed: u8) -> PublicKey {
+        let secret_key = SecretKey::ed25519_from_bytes([seed; SecretKey::ED25519_LENGTH]).unwrap();
+        PublicKey::from(&secret_key)
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let unbonding_purse = UnbondingPurse::new(
+            URef::new([41; 32], AccessRights::READ_ADD_WRITE),
+            public_key(42),
+            public_key(43),
+            EraId::MAX,
+            U512::max_value() - 1,
+        );
+        let redelegation = Redelegation::new(unbonding_purse, public_key(44));
+
+        bytesrepr::test_serialization_roundtrip(&redelegation);
+    }
+}
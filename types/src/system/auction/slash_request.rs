@@ -0,0 +1,263 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::{string::String, vec::Vec};
+use core::convert::TryFrom;
+
+use num_rational::Ratio;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, PublicKey,
+};
+
+/// The length in bytes of the hash of a consensus message citing an offense.
+pub const MESSAGE_HASH_LENGTH: usize = 32;
+
+/// Reference to the evidence of a validator's offense.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SlashEvidence {
+    /// Validator whose consensus message first cited the offense
+    reporter: PublicKey,
+    /// Hash of the consensus message which first cited the offense
+    #[serde(
+        serialize_with = "serialize_message_hash",
+        deserialize_with = "deserialize_message_hash"
+    )]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
+    message_hash: [u8; MESSAGE_HASH_LENGTH],
+    /// Height of the block which reported the offense
+    block_height: u64,
+}
+
+impl SlashEvidence {
+    /// Creates a new [`SlashEvidence`].
+    pub fn new(
+        reporter: PublicKey,
+        message_hash: [u8; MESSAGE_HASH_LENGTH],
+        block_height: u64,
+    ) -> Self {
+        SlashEvidence {
+            reporter,
+            message_hash,
+            block_height,
+        }
+    }
+
+    /// Returns the validator whose consensus message first cited the offense.
+    pub fn reporter(&self) -> &PublicKey {
+        &self.reporter
+    }
+
+    /// Returns the hash of the consensus message which first cited the offense.
+    pub fn message_hash(&self) -> &[u8; MESSAGE_HASH_LENGTH] {
+        &self.message_hash
+    }
+
+    /// Returns the height of the block which reported the offense.
+    pub fn block_height(&self) -> u64 {
+        self.block_height
+    }
+}
+
+fn serialize_message_hash<S: Serializer>(
+    message_hash: &[u8; MESSAGE_HASH_LENGTH],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        base16::encode_lower(message_hash).serialize(serializer)
+    } else {
+        message_hash.serialize(serializer)
+    }
+}
+
+fn deserialize_message_hash<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<[u8; MESSAGE_HASH_LENGTH], D::Error> {
+    if deserializer.is_human_readable() {
+        let hex_string = String::deserialize(deserializer)?;
+        let vec_bytes = base16::decode(hex_string.as_bytes()).map_err(SerdeError::custom)?;
+        <[u8; MESSAGE_HASH_LENGTH]>::try_from(vec_bytes.as_ref()).map_err(SerdeError::custom)
+    } else {
+        <[u8; MESSAGE_HASH_LENGTH]>::deserialize(deserializer)
+    }
+}
+
+impl ToBytes for SlashEvidence {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.extend(self.reporter.to_bytes()?);
+        result.extend(self.message_hash.to_bytes()?);
+        result.extend(self.block_height.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.reporter.serialized_length()
+            + self.message_hash.serialized_length()
+            + self.block_height.serialized_length()
+    }
+}
+
+impl FromBytes for SlashEvidence {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (reporter, bytes) = FromBytes::from_bytes(bytes)?;
+        let (message_hash, bytes) = FromBytes::from_bytes(bytes)?;
+        let (block_height, bytes) = FromBytes::from_bytes(bytes)?;
+        Ok((
+            SlashEvidence {
+                reporter,
+                message_hash,
+                block_height,
+            },
+            bytes,
+        ))
+    }
+}
+
+impl CLTyped for SlashEvidence {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+/// Request to slash a fraction of a validator's stake for a single offense.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SlashRequest {
+    /// Public key of the offending validator.
+    validator_public_key: PublicKey,
+    /// Fraction of the stakes bonded to the validator which is slashed.
+    fraction: Ratio<u64>,
+    /// Validator which receives the slashed funds.  If `None`, the slashed funds are burned.
+    recipient: Option<PublicKey>,
+    /// Reference to the evidence of the offense, if known.
+    evidence: Option<SlashEvidence>,
+}
+
+impl SlashRequest {
+    /// Creates a new [`SlashRequest`].
+    pub fn new(
+        validator_public_key: PublicKey,
+        fraction: Ratio<u64>,
+        recipient: Option<PublicKey>,
+        evidence: Option<SlashEvidence>,
+    ) -> Self {
+        SlashRequest {
+            validator_public_key,
+            fraction,
+            recipient,
+            evidence,
+        }
+    }
+
+    /// Returns the public key of the offending validator.
+    pub fn validator_public_key(&self) -> &PublicKey {
+        &self.validator_public_key
+    }
+
+    /// Returns the fraction of the stakes bonded to the validator which is slashed.
+    pub fn fraction(&self) -> Ratio<u64> {
+        self.fraction
+    }
+
+    /// Returns the validator which receives the slashed funds, if any.
+    pub fn recipient(&self) -> Option<&PublicKey> {
+        self.recipient.as_ref()
+    }
+
+    /// Returns the reference to the evidence of the offense, if known.
+    pub fn evidence(&self) -> Option<&SlashEvidence> {
+        self.evidence.as_ref()
+    }
+}
+
+impl ToBytes for SlashRequest {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.extend(self.validator_public_key.to_bytes()?);
+        result.extend(self.fraction.to_bytes()?);
+        result.extend(self.recipient.to_bytes()?);
+        result.extend(self.evidence.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.validator_public_key.serialized_length()
+            + self.fraction.serialized_length()
+            + self.recipient.serialized_length()
+            + self.evidence.serialized_length()
+    }
+}
+
+impl FromBytes for SlashRequest {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (validator_public_key, bytes) = FromBytes::from_bytes(bytes)?;
+        let (fraction, bytes) = FromBytes::from_bytes(bytes)?;
+        let (recipient, bytes) = FromBytes::from_bytes(bytes)?;
+        let (evidence, bytes) = FromBytes::from_bytes(bytes)?;
+        Ok((
+            SlashRequest {
+                validator_public_key,
+                fraction,
+                recipient,
+                evidence,
+            },
+            bytes,
+        ))
+    }
+}
+
+impl CLTyped for SlashRequest {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_rational::Ratio;
+
+    use crate::{
+        bytesrepr,
+        system::auction::{SlashEvidence, SlashRequest},
+        PublicKey, SecretKey,
+    };
+
+    fn public_key(seed: u8) -> PublicKey {
+        let secret_key = SecretKey::ed25519_from_bytes([seed; SecretKey::ED25519_LENGTH]).unwrap();
+        PublicKey::from(&secret_key)
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let evidence = SlashEvidence::new(public_key(43), [44; 32], 45);
+        let slash_request = SlashRequest::new(
+            public_key(42),
+            Ratio::new(1, 10),
+            Some(public_key(43)),
+            Some(evidence.clone()),
+        );
+        bytesrepr::test_serialization_roundtrip(&slash_request);
+
+        let slash_request =
+            SlashRequest::new(public_key(42), Ratio::new(1, 10), None, Some(evidence));
+        bytesrepr::test_serialization_roundtrip(&slash_request);
+
+        let slash_request = SlashRequest::new(public_key(42), Ratio::from_integer(1), None, None);
+        bytesrepr::test_serialization_roundtrip(&slash_request);
+    }
+
+    #[test]
+    fn slash_evidence_json_roundtrip() {
+        let evidence = SlashEvidence::new(public_key(42), [43; 32], 44);
+        let json_string = serde_json::to_string(&evidence).unwrap();
+        assert!(json_string.contains(&"2b".repeat(32)));
+        let decoded: SlashEvidence = serde_json::from_str(&json_string).unwrap();
+        assert_eq!(decoded, evidence);
+    }
+}
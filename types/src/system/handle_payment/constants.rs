@@ -6,6 +6,9 @@ pub const ARG_AMOUNT: &str = "amount";
 pub const ARG_ACCOUNT: &str = "account";
 /// Named constant for `target`.
 pub const ARG_TARGET: &str = "target";
+/// Named constant for `payer`, the optional payment code argument naming a separate account which
+/// pays for the deploy.
+pub const ARG_PAYER: &str = "payer";
 
 /// Named constant for method `get_payment_purse`.
 pub const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
@@ -141,6 +141,13 @@ pub enum Error {
     /// Raised when an entry point is called from invalid account context.
     InvalidContext = 20,
 
+    /// The transfer would exceed the spending limit of a key authorizing the deploy.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(21, Error::SpendingLimitExceeded as u8);
+    /// ```
+    SpendingLimitExceeded = 21,
+
     #[cfg(test)]
     #[doc(hidden)]
     Sentinel,
@@ -192,6 +199,7 @@ impl TryFrom<u8> for Error {
             d if d == Error::ArithmeticOverflow as u8 => Ok(Error::ArithmeticOverflow),
             d if d == Error::GasLimit as u8 => Ok(Error::GasLimit),
             d if d == Error::InvalidContext as u8 => Ok(Error::InvalidContext),
+            d if d == Error::SpendingLimitExceeded as u8 => Ok(Error::SpendingLimitExceeded),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -248,6 +256,7 @@ impl Display for Error {
             Error::ArithmeticOverflow => formatter.write_str("Arithmetic overflow has occurred"),
             Error::GasLimit => formatter.write_str("GasLimit"),
             Error::InvalidContext => formatter.write_str("Invalid context"),
+            Error::SpendingLimitExceeded => formatter.write_str("Spending limit exceeded"),
             #[cfg(test)]
             Error::Sentinel => formatter.write_str("Sentinel error"),
         }
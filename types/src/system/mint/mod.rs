@@ -123,6 +123,9 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         if self.read_balance(target)?.is_none() {
             return Err(Error::DestNotFound);
         }
+        if self.get_phase() != Phase::System {
+            self.record_spending(source, amount)?;
+        }
         self.write_balance(source, source_balance - amount)?;
         self.add_balance(target, amount)?;
         self.record_transfer(maybe_to, source, target, amount, id)?;
@@ -1,7 +1,7 @@
 use crate::{
     account::AccountHash,
     system::{mint::Error, CallStackElement},
-    Key, Phase,
+    Key, Phase, URef, U512,
 };
 
 /// Provider of runtime host functionality.
@@ -20,4 +20,8 @@ pub trait RuntimeProvider {
 
     /// This method should handle obtaining a given named [`Key`] under a `name`.
     fn get_key(&self, name: &str) -> Option<Key>;
+
+    /// This method should record the transfer of `amount` out of the `source` purse against the
+    /// spending limits of the keys authorizing the current deploy.
+    fn record_spending(&mut self, source: URef, amount: U512) -> Result<(), Error>;
 }
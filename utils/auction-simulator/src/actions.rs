@@ -0,0 +1,147 @@
+use clap::ArgMatches;
+
+use casper_engine_test_support::internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder};
+use casper_types::{
+    runtime_args,
+    system::auction::{self, DelegationRate},
+    ProtocolVersion, PublicKey, RuntimeArgs, U512,
+};
+
+use crate::utils::{parse_number, parse_public_key, parse_u512, split_fields};
+
+/// A hypothetical auction action applied to the state before the simulated eras are run.
+#[derive(Debug)]
+pub(crate) enum Action {
+    AddBid {
+        public_key: PublicKey,
+        amount: U512,
+        delegation_rate: DelegationRate,
+    },
+    Delegate {
+        delegator: PublicKey,
+        validator: PublicKey,
+        amount: U512,
+    },
+    WithdrawBid {
+        public_key: PublicKey,
+        amount: U512,
+    },
+}
+
+impl Action {
+    fn parse_add_bid(value: &str) -> Self {
+        let [public_key, amount, delegation_rate] = split_fields(value);
+        Action::AddBid {
+            public_key: parse_public_key(public_key),
+            amount: parse_u512(amount),
+            delegation_rate: parse_number(delegation_rate),
+        }
+    }
+
+    fn parse_delegate(value: &str) -> Self {
+        let [delegator, validator, amount] = split_fields(value);
+        Action::Delegate {
+            delegator: parse_public_key(delegator),
+            validator: parse_public_key(validator),
+            amount: parse_u512(amount),
+        }
+    }
+
+    fn parse_withdraw_bid(value: &str) -> Self {
+        let [public_key, amount] = split_fields(value);
+        Action::WithdrawBid {
+            public_key: parse_public_key(public_key),
+            amount: parse_u512(amount),
+        }
+    }
+
+    /// Executes the action as a deploy calling the auction contract directly, and commits it.
+    ///
+    /// Panics if the deploy fails, as the rest of the simulation would be meaningless.
+    pub(crate) fn apply(
+        &self,
+        builder: &mut InMemoryWasmTestBuilder,
+        protocol_version: ProtocolVersion,
+        block_time: u64,
+    ) {
+        let (sender, entry_point, args) = match self {
+            Action::AddBid {
+                public_key,
+                amount,
+                delegation_rate,
+            } => (
+                public_key,
+                auction::METHOD_ADD_BID,
+                runtime_args! {
+                    auction::ARG_PUBLIC_KEY => public_key.clone(),
+                    auction::ARG_AMOUNT => *amount,
+                    auction::ARG_DELEGATION_RATE => *delegation_rate,
+                },
+            ),
+            Action::Delegate {
+                delegator,
+                validator,
+                amount,
+            } => (
+                delegator,
+                auction::METHOD_DELEGATE,
+                runtime_args! {
+                    auction::ARG_DELEGATOR => delegator.clone(),
+                    auction::ARG_VALIDATOR => validator.clone(),
+                    auction::ARG_AMOUNT => *amount,
+                },
+            ),
+            Action::WithdrawBid { public_key, amount } => (
+                public_key,
+                auction::METHOD_WITHDRAW_BID,
+                runtime_args! {
+                    auction::ARG_PUBLIC_KEY => public_key.clone(),
+                    auction::ARG_AMOUNT => *amount,
+                },
+            ),
+        };
+
+        let auction_hash = builder.get_system_auction_hash();
+        let exec_request = ExecuteRequestBuilder::contract_call_by_hash(
+            sender.to_account_hash(),
+            auction_hash,
+            entry_point,
+            args,
+        )
+        .with_protocol_version(protocol_version)
+        .with_block_time(block_time)
+        .build();
+
+        builder.exec(exec_request);
+        if builder.is_error() {
+            let index = builder.get_exec_results_count() - 1;
+            panic!(
+                "{:?} failed: {}",
+                self,
+                builder
+                    .exec_error_message(index)
+                    .unwrap_or_else(|| "unknown error".to_string())
+            );
+        }
+        builder.commit();
+    }
+}
+
+/// Parses the actions given on the command line, in the order they were given.
+pub(crate) fn parse_actions(matches: &ArgMatches<'_>) -> Vec<Action> {
+    let mut indexed_actions = vec![];
+    let mut collect = |name: &str, parse: fn(&str) -> Action| {
+        if let (Some(indices), Some(values)) = (matches.indices_of(name), matches.values_of(name)) {
+            indexed_actions.extend(indices.zip(values.map(parse)));
+        }
+    };
+    collect("add_bid", Action::parse_add_bid);
+    collect("delegate", Action::parse_delegate);
+    collect("withdraw_bid", Action::parse_withdraw_bid);
+    indexed_actions.sort_by_key(|(index, _action)| *index);
+
+    indexed_actions
+        .into_iter()
+        .map(|(_index, action)| action)
+        .collect()
+}
@@ -0,0 +1,108 @@
+mod actions;
+mod simulation;
+mod state;
+mod utils;
+
+use clap::{crate_version, App, Arg};
+
+use crate::simulation::run_simulation;
+
+fn main() {
+    let matches = App::new("Auction Simulator")
+        .version(crate_version!())
+        .about(
+            "Runs the auction and reward distribution on a copy of the global state, optionally \
+            after applying hypothetical bids, delegations and withdrawals, and reports the \
+            resulting validator sets and payouts",
+        )
+        .arg(
+            Arg::with_name("data_dir")
+                .short("d")
+                .long("data-dir")
+                .value_name("PATH")
+                .help(
+                    "Data storage directory containing the global state database file, e.g. the \
+                    node's storage directory or the lmdb-data directory of retrieve-state",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("hash")
+                .short("s")
+                .long("state-hash")
+                .value_name("HEX_STRING")
+                .help("The global state hash to be used as the base")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("protocol_version")
+                .short("p")
+                .long("protocol-version")
+                .value_name("VERSION")
+                .help("The protocol version of the global state")
+                .takes_value(true)
+                .default_value("1.0.0"),
+        )
+        .arg(
+            Arg::with_name("eras")
+                .short("e")
+                .long("eras")
+                .value_name("COUNT")
+                .help("The number of eras to simulate")
+                .takes_value(true)
+                .default_value("1"),
+        )
+        .arg(
+            Arg::with_name("era_duration")
+                .long("era-duration-millis")
+                .value_name("MILLIS")
+                .help("The duration of each simulated era")
+                .takes_value(true)
+                .default_value("7200000"),
+        )
+        .arg(
+            Arg::with_name("reward_factor")
+                .short("r")
+                .long("reward-factor")
+                .value_name("KEY,FACTOR")
+                .help(
+                    "A validator's reward factor, out of a block reward of 1000000000000; if none \
+                    are given, the block reward is split in proportion to the validators' weights",
+                )
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("add_bid")
+                .long("add-bid")
+                .value_name("KEY,AMOUNT,DELEGATION_RATE")
+                .help("A hypothetical bid, applied before the first simulated era")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("delegate")
+                .long("delegate")
+                .value_name("DELEGATOR_KEY,VALIDATOR_KEY,AMOUNT")
+                .help("A hypothetical delegation, applied before the first simulated era")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("withdraw_bid")
+                .long("withdraw-bid")
+                .value_name("KEY,AMOUNT")
+                .help("A hypothetical bid withdrawal, applied before the first simulated era")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .get_matches();
+
+    run_simulation(&matches);
+}
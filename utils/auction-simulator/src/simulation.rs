@@ -0,0 +1,189 @@
+use std::{collections::BTreeMap, path::Path};
+
+use clap::ArgMatches;
+
+use casper_engine_test_support::internal::{InMemoryWasmTestBuilder, StepRequestBuilder};
+use casper_execution_engine::{
+    core::engine_state::{GetEraValidatorsRequest, RewardItem},
+    shared::newtypes::CorrelationId,
+};
+use casper_types::{
+    system::auction::{
+        EraInfo, EraValidators, SeigniorageAllocation, ValidatorWeights, BLOCK_REWARD,
+        ERA_END_TIMESTAMP_MILLIS_KEY, ERA_ID_KEY,
+    },
+    AsymmetricType, EraId, Key, ProtocolVersion, PublicKey, U512,
+};
+
+use crate::{
+    actions::parse_actions,
+    state::load_state,
+    utils::{hash_from_str, parse_number, parse_public_key, split_fields},
+};
+
+pub(crate) fn run_simulation(matches: &ArgMatches<'_>) {
+    let data_dir = Path::new(matches.value_of("data_dir").unwrap_or("."));
+    let state_hash = hash_from_str(matches.value_of("hash").unwrap());
+    let protocol_version: ProtocolVersion =
+        parse_number(matches.value_of("protocol_version").unwrap());
+    let era_count: u64 = parse_number(matches.value_of("eras").unwrap());
+    let era_duration_millis: u64 = parse_number(matches.value_of("era_duration").unwrap());
+    let reward_factors: BTreeMap<PublicKey, u64> = matches
+        .values_of("reward_factor")
+        .into_iter()
+        .flatten()
+        .map(|value| {
+            let [public_key, factor] = split_fields(value);
+            (parse_public_key(public_key), parse_number(factor))
+        })
+        .collect();
+    let actions = parse_actions(matches);
+
+    let mut builder = load_state(data_dir, state_hash);
+    let auction_hash = builder.get_system_auction_hash();
+    let mut era_end_timestamp_millis: u64 =
+        builder.get_value(auction_hash, ERA_END_TIMESTAMP_MILLIS_KEY);
+
+    for action in &actions {
+        action.apply(&mut builder, protocol_version, era_end_timestamp_millis);
+    }
+
+    for _ in 0..era_count {
+        let era_id: EraId = builder.get_value(auction_hash, ERA_ID_KEY);
+        let era_validators = get_era_validators(&builder, protocol_version);
+        let validator_weights = era_validators
+            .get(&era_id)
+            .unwrap_or_else(|| panic!("no validators found for era {}", era_id));
+        let era_reward_factors = era_reward_factors(validator_weights, &reward_factors);
+
+        era_end_timestamp_millis += era_duration_millis;
+        let step_request = era_reward_factors
+            .iter()
+            .fold(
+                StepRequestBuilder::new(),
+                |step_request, (public_key, factor)| {
+                    step_request.with_reward_item(RewardItem::new(public_key.clone(), *factor))
+                },
+            )
+            .with_parent_state_hash(builder.get_post_state_hash())
+            .with_protocol_version(protocol_version)
+            .with_next_era_id(era_id.successor())
+            .with_era_end_timestamp_millis(era_end_timestamp_millis)
+            .build();
+        builder.step(step_request);
+
+        println!("{}", era_id);
+        print_validators(validator_weights, Some(&era_reward_factors));
+        print_payouts(&get_era_info(&builder, era_id));
+        println!();
+    }
+
+    let next_era_id: EraId = builder.get_value(auction_hash, ERA_ID_KEY);
+    for (era_id, validator_weights) in get_era_validators(&builder, protocol_version)
+        .iter()
+        .filter(|(era_id, _)| **era_id >= next_era_id)
+    {
+        println!("{} (upcoming)", era_id);
+        print_validators(validator_weights, None);
+        println!();
+    }
+}
+
+/// Returns the reward factors to distribute at the end of an era with the given validators.
+///
+/// Explicitly given factors are used as they are, with missing validators getting nothing.  If no
+/// factors were given, the block reward is split in proportion to the validators' weights, as if
+/// every validator had participated fully.
+fn era_reward_factors(
+    validator_weights: &ValidatorWeights,
+    reward_factors: &BTreeMap<PublicKey, u64>,
+) -> BTreeMap<PublicKey, u64> {
+    if !reward_factors.is_empty() {
+        return validator_weights
+            .keys()
+            .map(|public_key| {
+                let factor = reward_factors.get(public_key).copied().unwrap_or_default();
+                (public_key.clone(), factor)
+            })
+            .collect();
+    }
+
+    let total_weight = validator_weights
+        .values()
+        .fold(U512::zero(), |total, weight| total + *weight);
+    validator_weights
+        .iter()
+        .map(|(public_key, weight)| {
+            let factor = if total_weight.is_zero() {
+                0
+            } else {
+                (U512::from(BLOCK_REWARD) * *weight / total_weight).as_u64()
+            };
+            (public_key.clone(), factor)
+        })
+        .collect()
+}
+
+fn get_era_validators(
+    builder: &InMemoryWasmTestBuilder,
+    protocol_version: ProtocolVersion,
+) -> EraValidators {
+    let request = GetEraValidatorsRequest::new(builder.get_post_state_hash(), protocol_version);
+    builder
+        .get_engine_state()
+        .get_era_validators(CorrelationId::new(), request)
+        .unwrap_or_else(|error| panic!("failed to get era validators: {:?}", error))
+}
+
+fn get_era_info(builder: &InMemoryWasmTestBuilder, era_id: EraId) -> EraInfo {
+    builder
+        .query(None, Key::EraInfo(era_id), &[])
+        .unwrap_or_else(|error| panic!("failed to read era info for era {}: {}", era_id, error))
+        .as_era_info()
+        .cloned()
+        .expect("should be era info")
+}
+
+fn print_validators(
+    validator_weights: &ValidatorWeights,
+    reward_factors: Option<&BTreeMap<PublicKey, u64>>,
+) {
+    println!("  validators:");
+    for (public_key, weight) in validator_weights {
+        match reward_factors.and_then(|reward_factors| reward_factors.get(public_key)) {
+            Some(factor) => println!(
+                "    {}: weight {}, reward factor {}",
+                public_key.to_hex(),
+                weight,
+                factor
+            ),
+            None => println!("    {}: weight {}", public_key.to_hex(), weight),
+        }
+    }
+}
+
+fn print_payouts(era_info: &EraInfo) {
+    println!("  payouts:");
+    for allocation in era_info.seigniorage_allocations() {
+        match allocation {
+            SeigniorageAllocation::Validator {
+                validator_public_key,
+                amount,
+            } => println!(
+                "    validator {}: {}",
+                validator_public_key.to_hex(),
+                amount
+            ),
+            SeigniorageAllocation::Delegator {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+            } => println!(
+                "    delegator {} via {}: {}",
+                delegator_public_key.to_hex(),
+                validator_public_key.to_hex(),
+                amount
+            ),
+        }
+    }
+}
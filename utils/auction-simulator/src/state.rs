@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use casper_engine_test_support::internal::{InMemoryWasmTestBuilder, LmdbWasmTestBuilder};
+use casper_execution_engine::{
+    shared::newtypes::CorrelationId, storage::global_state::in_memory::InMemoryGlobalState,
+};
+use casper_hashing::Digest;
+
+/// Copies the global state under `state_hash` from the LMDB database in `data_dir` into memory.
+///
+/// Only the tries reachable from `state_hash` are copied, so nothing the simulation commits ever
+/// reaches the database on disk.
+pub(crate) fn load_state(data_dir: &Path, state_hash: Digest) -> InMemoryWasmTestBuilder {
+    let lmdb_builder = LmdbWasmTestBuilder::open_raw(data_dir, Default::default(), state_hash);
+    let source = lmdb_builder.get_engine_state();
+
+    let builder = InMemoryWasmTestBuilder::new(
+        InMemoryGlobalState::empty().unwrap(),
+        Default::default(),
+        state_hash,
+    );
+    let target = builder.get_engine_state();
+
+    let correlation_id = CorrelationId::new();
+    let mut missing_trie_keys = vec![state_hash];
+    let mut copied_count = 0_usize;
+    while let Some(trie_key) = missing_trie_keys.pop() {
+        let trie = source
+            .get_trie(correlation_id, trie_key)
+            .unwrap_or_else(|error| panic!("failed to read trie {}: {:?}", trie_key, error))
+            .unwrap_or_else(|| {
+                panic!(
+                    "trie {} not found in {}; is the state hash correct?",
+                    trie_key,
+                    data_dir.display()
+                )
+            });
+        let descendants = target
+            .put_trie_and_find_missing_descendant_trie_keys(correlation_id, &trie)
+            .unwrap_or_else(|error| panic!("failed to copy trie {}: {:?}", trie_key, error));
+        missing_trie_keys.extend(descendants);
+        copied_count += 1;
+    }
+    eprintln!("copied {} tries into memory", copied_count);
+
+    builder
+}
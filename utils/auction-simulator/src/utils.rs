@@ -0,0 +1,41 @@
+use std::{convert::TryInto, fmt::Display};
+
+use casper_hashing::Digest;
+use casper_types::{AsymmetricType, PublicKey, U512};
+
+/// Parses a Digest from a string. Panics if parsing fails.
+pub(crate) fn hash_from_str(hex_str: &str) -> Digest {
+    (&base16::decode(hex_str).unwrap()[..]).try_into().unwrap()
+}
+
+/// Parses a hex-encoded public key. Panics if parsing fails.
+pub(crate) fn parse_public_key(hex_str: &str) -> PublicKey {
+    PublicKey::from_hex(hex_str.as_bytes())
+        .unwrap_or_else(|error| panic!("invalid public key {}: {}", hex_str, error))
+}
+
+/// Parses an amount of motes given in decimal. Panics if parsing fails.
+pub(crate) fn parse_u512(dec_str: &str) -> U512 {
+    U512::from_dec_str(dec_str)
+        .unwrap_or_else(|error| panic!("invalid amount {}: {:?}", dec_str, error))
+}
+
+/// Parses a decimal number. Panics if parsing fails.
+pub(crate) fn parse_number<T>(dec_str: &str) -> T
+where
+    T: std::str::FromStr,
+    T::Err: Display,
+{
+    dec_str
+        .parse()
+        .unwrap_or_else(|error| panic!("invalid number {}: {}", dec_str, error))
+}
+
+/// Splits a comma-separated argument value into exactly `N` fields. Panics on a different count.
+pub(crate) fn split_fields<const N: usize>(value: &str) -> [&str; N] {
+    value
+        .split(',')
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap_or_else(|_| panic!("expected {} comma-separated fields in {}", N, value))
+}
@@ -2,11 +2,13 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use casper_engine_test_support::internal::LmdbWasmTestBuilder;
 use casper_types::{
+    bytesrepr::FromBytes,
     system::auction::{
-        Bid, SeigniorageRecipient, SeigniorageRecipientsSnapshot,
-        SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
+        Bid, Delegator, SeigniorageRecipient, SeigniorageRecipientsSnapshot,
+        MAXIMUM_DELEGATION_AMOUNT_KEY, MAX_DELEGATORS_PER_VALIDATOR_KEY,
+        MINIMUM_DELEGATION_AMOUNT_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
     },
-    AsymmetricType, EraId, Key, PublicKey, StoredValue, U512,
+    AsymmetricType, CLTyped, EraId, Key, PublicKey, StoredValue, U512,
 };
 
 use crate::utils::ValidatorsDiff;
@@ -33,6 +35,76 @@ pub fn read_snapshot(builder: &LmdbWasmTestBuilder) -> (Key, SeigniorageRecipien
     (validators_key, cl_value.into_t().expect("should convert"))
 }
 
+/// The bounds on delegations enforced by the auction contract.
+pub struct DelegationBounds {
+    minimum_amount: U512,
+    maximum_amount: U512,
+    max_delegators_per_validator: u32,
+}
+
+impl DelegationBounds {
+    /// Panics if the delegators of the given validator violate the bounds.
+    fn check(&self, validator: &PublicKey, delegators: &BTreeMap<PublicKey, Delegator>) {
+        if delegators.len() > self.max_delegators_per_validator as usize {
+            panic!(
+                "validator {} has {} delegators, more than the maximum of {}",
+                validator,
+                delegators.len(),
+                self.max_delegators_per_validator
+            );
+        }
+        for (delegator_public_key, delegator) in delegators {
+            let staked_amount = *delegator.staked_amount();
+            if staked_amount < self.minimum_amount || staked_amount > self.maximum_amount {
+                panic!(
+                    "delegator {} of validator {} has a stake of {} motes, outside of the \
+                    delegation bounds of {} to {} motes",
+                    delegator_public_key,
+                    validator,
+                    staked_amount,
+                    self.minimum_amount,
+                    self.maximum_amount
+                );
+            }
+        }
+    }
+}
+
+/// Reads the delegation bounds stored in the global state, falling back to the auction
+/// contract's defaults for those which are not set.
+pub fn read_delegation_bounds(builder: &LmdbWasmTestBuilder) -> DelegationBounds {
+    let auction_contract_hash = builder.get_system_auction_hash();
+    let named_keys = builder
+        .get_contract(auction_contract_hash)
+        .expect("auction should exist")
+        .take_named_keys();
+
+    let read_or = |name: &str, default: u64| match named_keys.get(name) {
+        Some(key) => read_cl_value(builder, *key),
+        None => default,
+    };
+
+    DelegationBounds {
+        minimum_amount: U512::from(read_or(MINIMUM_DELEGATION_AMOUNT_KEY, 0)),
+        maximum_amount: U512::from(read_or(MAXIMUM_DELEGATION_AMOUNT_KEY, u64::MAX)),
+        max_delegators_per_validator: named_keys
+            .get(MAX_DELEGATORS_PER_VALIDATOR_KEY)
+            .map_or(u32::MAX, |key| read_cl_value(builder, *key)),
+    }
+}
+
+/// Reads the `CLValue` stored under the given key and converts it to `T`.
+fn read_cl_value<T: CLTyped + FromBytes>(builder: &LmdbWasmTestBuilder, key: Key) -> T {
+    builder
+        .query(None, key, &[])
+        .expect("should query")
+        .as_cl_value()
+        .cloned()
+        .expect("should be cl value")
+        .into_t()
+        .expect("should convert")
+}
+
 /// Generates a new `SeigniorageRecipientsSnapshot` based on:
 /// - The list of validators, in format (validator_public_key,stake), both expressed as strings.
 /// - The starting era ID (the era ID at which the snapshot should start).
@@ -81,7 +153,8 @@ pub fn find_large_bids(
 }
 
 /// Generates a set of writes necessary to "fix" the bids, ie.:
-/// - set the bids of the new validators to their desired stakes,
+/// - set the bids of the new validators to their desired stakes, keeping the delegators of those
+/// which already had a bid as long as they are within the delegation bounds,
 /// - remove the bids of the old validators that are no longer validators,
 /// - remove all the bids that are larger than the smallest bid among the new validators
 /// (necessary, because such bidders would outbid the validators decided by the social consensus).
@@ -89,9 +162,11 @@ pub fn generate_entries_removing_bids(
     builder: &mut LmdbWasmTestBuilder,
     validators_diff: &ValidatorsDiff,
     new_snapshot: &SeigniorageRecipientsSnapshot,
+    delegation_bounds: &DelegationBounds,
 ) -> BTreeMap<Key, StoredValue> {
     let large_bids = find_large_bids(builder, new_snapshot);
     let to_unbid = validators_diff.removed.union(&large_bids);
+    let old_bids = builder.get_bids();
 
     validators_diff
         .added
@@ -106,16 +181,17 @@ pub fn generate_entries_removing_bids(
                 .stake();
             let account_hash = pkey.to_account_hash();
             let account = builder.get_account(account_hash).unwrap();
-            (
-                Key::Bid(account_hash),
-                Bid::unlocked(
-                    pkey.clone(),
-                    account.main_purse(),
-                    amount,
-                    Default::default(),
-                )
-                .into(),
-            )
+            let mut bid = Bid::unlocked(
+                pkey.clone(),
+                account.main_purse(),
+                amount,
+                Default::default(),
+            );
+            if let Some(old_bid) = old_bids.get(pkey) {
+                delegation_bounds.check(pkey, old_bid.delegators());
+                *bid.delegators_mut() = old_bid.delegators().clone();
+            }
+            (Key::Bid(account_hash), bid.into())
         })
         .chain(to_unbid.into_iter().map(|pkey| {
             let account_hash = pkey.to_account_hash();
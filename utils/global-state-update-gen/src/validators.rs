@@ -6,7 +6,7 @@ use casper_types::{CLValue, StoredValue};
 use crate::{
     auction_utils::{
         gen_snapshot, generate_entries_removing_bids, generate_entries_removing_withdraws,
-        read_snapshot,
+        read_delegation_bounds, read_snapshot,
     },
     utils::{hash_from_str, print_entry, validators_diff},
 };
@@ -48,10 +48,16 @@ pub(crate) fn generate_validators_update(matches: &ArgMatches<'_>) {
 
     let validators_diff = validators_diff(&old_snapshot, &new_snapshot);
 
+    // Read the delegation bounds the new bids have to respect.
+    let delegation_bounds = read_delegation_bounds(&test_builder);
+
     // Print the writes fixing the bids.
-    for (key, value) in
-        generate_entries_removing_bids(&mut test_builder, &validators_diff, &new_snapshot)
-    {
+    for (key, value) in generate_entries_removing_bids(
+        &mut test_builder,
+        &validators_diff,
+        &new_snapshot,
+        &delegation_bounds,
+    ) {
         print_entry(&key, &value);
     }
 